@@ -0,0 +1,219 @@
+//! Replays recorded intern operations against the interners
+//!
+//! Set `LASSO_REPLAY` to the path of a workload file to replay it, otherwise a workload where
+//! every thread interns all of `input.txt` is generated. Workload files hold one operation per
+//! line as `timestamp<TAB>thread-id<TAB>string`, where the timestamp is any integer that orders
+//! the operations and `\t`, `\n` and `\\` within the string are unescaped. Empty lines and
+//! lines starting with `#` are skipped.
+//!
+//! Every thread id gets its own thread which interns its strings in timestamp order as fast as
+//! it can, so the timestamps decide the order of each thread's operations but not their pacing
+//!
+//! ```text
+//! LASSO_REPLAY=workload.tsv cargo bench --features multi-threaded --bench replay
+//! ```
+
+mod setup;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use lasso::{Capacity, Rodeo, Spur, ThreadedRodeo};
+use setup::{bench_lines, NUM_THREADS};
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    sync::{Arc, Barrier},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// A single recorded call to `get_or_intern`
+struct Operation {
+    timestamp: u64,
+    thread: u64,
+    string: String,
+}
+
+struct Workload {
+    /// Every operation, in timestamp order
+    operations: Vec<Operation>,
+    /// The strings interned by each thread, in timestamp order
+    threads: Vec<Arc<[String]>>,
+    unique_strings: usize,
+}
+
+impl Workload {
+    fn load() -> Self {
+        let mut operations = match env::var_os("LASSO_REPLAY") {
+            Some(path) => {
+                let file = fs::read_to_string(&path).unwrap_or_else(|err| {
+                    panic!("failed to read the workload at {:?}: {}", path, err)
+                });
+
+                file.lines()
+                    .enumerate()
+                    .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+                    .map(|(idx, line)| {
+                        parse_operation(line).unwrap_or_else(|| {
+                            panic!("line {} of the workload is malformed: {:?}", idx + 1, line)
+                        })
+                    })
+                    .collect()
+            }
+
+            None => generated(),
+        };
+        operations.sort_by_key(|op| op.timestamp);
+
+        let mut threads: BTreeMap<u64, Vec<String>> = BTreeMap::new();
+        for op in &operations {
+            threads
+                .entry(op.thread)
+                .or_default()
+                .push(op.string.clone());
+        }
+
+        let mut unique = Rodeo::<Spur>::new();
+        operations.iter().for_each(|op| {
+            unique.get_or_intern(&op.string);
+        });
+
+        Self {
+            operations,
+            threads: threads.into_values().map(Arc::from).collect(),
+            unique_strings: unique.len(),
+        }
+    }
+
+    fn bytes(&self) -> u64 {
+        self.operations
+            .iter()
+            .map(|op| op.string.len() as u64)
+            .sum()
+    }
+
+    fn capacity(&self) -> Capacity {
+        Capacity::for_strings(self.unique_strings)
+    }
+}
+
+fn parse_operation(line: &str) -> Option<Operation> {
+    let mut fields = line.splitn(3, '\t');
+    let timestamp = fields.next()?.trim().parse().ok()?;
+    let thread = fields.next()?.trim().parse().ok()?;
+    let string = unescape(fields.next()?)?;
+
+    Some(Operation {
+        timestamp,
+        thread,
+        string,
+    })
+}
+
+fn unescape(escaped: &str) -> Option<String> {
+    let mut string = String::with_capacity(escaped.len());
+    let mut chars = escaped.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            string.push(match chars.next()? {
+                't' => '\t',
+                'n' => '\n',
+                '\\' => '\\',
+                _ => return None,
+            });
+        } else {
+            string.push(c);
+        }
+    }
+
+    Some(string)
+}
+
+/// Every thread interns all of the bench input, the most contended workload there is
+fn generated() -> Vec<Operation> {
+    let lines = bench_lines();
+
+    (0..NUM_THREADS as u64)
+        .flat_map(|thread| {
+            lines
+                .iter()
+                .enumerate()
+                .map(move |(timestamp, &line)| Operation {
+                    timestamp: timestamp as u64,
+                    thread,
+                    string: line.to_owned(),
+                })
+        })
+        .collect()
+}
+
+/// Replays each thread's strings on its own thread, timing from when they all start interning
+/// until the last one finishes
+fn replay_threaded(workload: &Workload, iters: u64) -> Duration {
+    let mut elapsed = Duration::ZERO;
+
+    for _ in 0..iters {
+        let rodeo: Arc<ThreadedRodeo> = Arc::new(ThreadedRodeo::with_capacity(workload.capacity()));
+        let barrier = Arc::new(Barrier::new(workload.threads.len() + 1));
+
+        let threads: Vec<_> = workload
+            .threads
+            .iter()
+            .map(|strings| {
+                let (rodeo, barrier, strings) = (
+                    Arc::clone(&rodeo),
+                    Arc::clone(&barrier),
+                    Arc::clone(strings),
+                );
+
+                thread::spawn(move || {
+                    barrier.wait();
+                    for string in strings.iter() {
+                        black_box(rodeo.get_or_intern(string));
+                    }
+                })
+            })
+            .collect();
+
+        barrier.wait();
+        let start = Instant::now();
+        threads.into_iter().for_each(|x| x.join().unwrap());
+        elapsed += start.elapsed();
+    }
+
+    elapsed
+}
+
+fn replay(c: &mut Criterion) {
+    let workload = Workload::load();
+
+    let mut group = c.benchmark_group(format!(
+        "Replay ({} operations, {} threads)",
+        workload.operations.len(),
+        workload.threads.len(),
+    ));
+    group.throughput(Throughput::Bytes(workload.bytes()));
+
+    group.bench_function("ThreadedRodeo", |b| {
+        b.iter_custom(|iters| replay_threaded(&workload, iters))
+    });
+
+    // The operations of every thread interleaved into one in timestamp order, to compare the
+    // concurrent interner against the best case of a single thread
+    group.bench_function("Rodeo (serialized)", |b| {
+        b.iter_batched(
+            || Rodeo::<Spur>::with_capacity(workload.capacity()),
+            |mut rodeo| {
+                for op in &workload.operations {
+                    black_box(rodeo.get_or_intern(&op.string));
+                }
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, replay);
+criterion_main!(benches);
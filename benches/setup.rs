@@ -62,7 +62,7 @@ impl<S: BuildHasher + Clone> RodeoFilledSetup<S> {
         let mut rodeo = Rodeo::with_capacity_and_hasher(
             Capacity::new(
                 lines.len(),
-                NonZeroUsize::new(lines.iter().map(|l| l.as_bytes().len()).sum()).unwrap(),
+                NonZeroUsize::new(lines.iter().map(|l| l.len()).sum()).unwrap(),
             ),
             hash_builder,
         );
@@ -128,7 +128,7 @@ impl<S: BuildHasher + Clone> ReaderFilledSetup<S> {
         let mut rodeo = Rodeo::with_capacity_and_hasher(
             Capacity::new(
                 lines.len(),
-                NonZeroUsize::new(lines.iter().map(|l| l.as_bytes().len()).sum()).unwrap(),
+                NonZeroUsize::new(lines.iter().map(|l| l.len()).sum()).unwrap(),
             ),
             hash_builder,
         );
@@ -260,7 +260,7 @@ impl ResolverFilledSetup {
         let mut rodeo = Rodeo::with_capacity_and_hasher(
             Capacity::new(
                 lines.len(),
-                NonZeroUsize::new(lines.iter().map(|l| l.as_bytes().len()).sum()).unwrap(),
+                NonZeroUsize::new(lines.iter().map(|l| l.len()).sum()).unwrap(),
             ),
             RandomState::new(),
         );
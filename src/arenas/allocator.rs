@@ -0,0 +1,166 @@
+use crate::{LassoError, LassoResult};
+use alloc::alloc::{alloc, dealloc, Layout};
+use core::{
+    fmt::{self, Debug},
+    ptr::NonNull,
+};
+
+#[cfg(feature = "allocator-api2")]
+use alloc::sync::Arc;
+
+/// The allocator that arena buckets get their memory from
+///
+/// Custom allocators are type-erased so that the arenas (and by extension the interners)
+/// don't need to carry around an extra generic parameter
+#[derive(Clone, Default)]
+pub(crate) enum BucketAllocator {
+    /// The global allocator
+    #[default]
+    Global,
+    /// A user-provided allocator
+    #[cfg(feature = "allocator-api2")]
+    Custom(Arc<dyn allocator_api2::alloc::Allocator + Send + Sync>),
+    /// Maps buckets large enough to span a huge page directly, asking the kernel
+    /// to back them with huge pages
+    #[cfg(all(feature = "hugepages", target_os = "linux"))]
+    HugePages,
+}
+
+impl BucketAllocator {
+    /// Allocate a block of memory with the given layout
+    ///
+    /// `layout` must have a non-zero size
+    pub(crate) fn allocate(&self, layout: Layout) -> LassoResult<NonNull<u8>> {
+        debug_assert_ne!(layout.size(), 0);
+
+        let ptr = match self {
+            // Safety: The given layout has a non-zero size
+            Self::Global => NonNull::new(unsafe { alloc(layout) }),
+
+            #[cfg(feature = "allocator-api2")]
+            Self::Custom(allocator) => allocator.allocate(layout).ok().map(NonNull::cast),
+
+            #[cfg(all(feature = "hugepages", target_os = "linux"))]
+            Self::HugePages => hugepages::allocate(layout),
+        };
+
+        ptr.ok_or_else(|| LassoError::failed_allocation(layout.size()))
+    }
+
+    /// Deallocate a block of memory
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by this allocator (or a clone of it) using
+    /// the same `layout`
+    ///
+    pub(crate) unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        match self {
+            // Safety: The caller promises the pointer came from the global allocator
+            Self::Global => unsafe { dealloc(ptr.as_ptr(), layout) },
+
+            // Safety: The caller promises the pointer came from this allocator
+            #[cfg(feature = "allocator-api2")]
+            Self::Custom(allocator) => unsafe { allocator.deallocate(ptr, layout) },
+
+            // Safety: The caller promises the pointer came from `hugepages::allocate()`
+            #[cfg(all(feature = "hugepages", target_os = "linux"))]
+            Self::HugePages => unsafe { hugepages::deallocate(ptr, layout) },
+        }
+    }
+}
+
+impl Debug for BucketAllocator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Global => f.write_str("Global"),
+            #[cfg(feature = "allocator-api2")]
+            Self::Custom(_) => f.write_str("Custom"),
+            #[cfg(all(feature = "hugepages", target_os = "linux"))]
+            Self::HugePages => f.write_str("HugePages"),
+        }
+    }
+}
+
+#[cfg(all(feature = "hugepages", target_os = "linux"))]
+mod hugepages {
+    use alloc::alloc::{alloc, dealloc, Layout};
+    use core::ptr::{self, NonNull};
+
+    /// The size of a huge page on most linux systems
+    ///
+    /// Allocations smaller than this can't be backed by a huge page, so they're
+    /// given to the global allocator instead
+    pub(super) const HUGE_PAGE_SIZE: usize = 2 * 1024 * 1024;
+
+    /// Get the size of the mapping made for `layout`, which is `layout`'s size rounded
+    /// up to the nearest multiple of the huge page size
+    fn mapping_size(layout: Layout) -> Option<usize> {
+        let size = layout.size().checked_add(HUGE_PAGE_SIZE - 1)?;
+        Some(size & !(HUGE_PAGE_SIZE - 1))
+    }
+
+    /// Allocate memory for `layout`, first trying to map explicit huge pages, then
+    /// falling back to transparent huge pages
+    pub(super) fn allocate(layout: Layout) -> Option<NonNull<u8>> {
+        if layout.size() < HUGE_PAGE_SIZE {
+            // Safety: The caller ensures the given layout has a non-zero size
+            return NonNull::new(unsafe { alloc(layout) });
+        }
+
+        // mmap always gives out page-aligned memory
+        debug_assert!(layout.align() <= 4096);
+        let size = mapping_size(layout)?;
+        let map = |flags| {
+            // Safety: We're asking for a fresh anonymous mapping, no existing memory is touched
+            let ptr = unsafe {
+                libc::mmap(
+                    ptr::null_mut(),
+                    size,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                    libc::MAP_PRIVATE | libc::MAP_ANONYMOUS | flags,
+                    -1,
+                    0,
+                )
+            };
+
+            if ptr == libc::MAP_FAILED {
+                None
+            } else {
+                NonNull::new(ptr.cast::<u8>())
+            }
+        };
+
+        // Explicit huge pages only work when the system has reserved some, so fall back
+        // to a normal mapping and ask for it to be backed by transparent huge pages
+        map(libc::MAP_HUGETLB).or_else(|| {
+            let ptr = map(0)?;
+
+            // Safety: `ptr` points to a mapping of `size` bytes. Failing to advise the kernel
+            //         isn't an error, the mapping will just be backed by normal pages
+            unsafe { libc::madvise(ptr.as_ptr().cast(), size, libc::MADV_HUGEPAGE) };
+
+            Some(ptr)
+        })
+    }
+
+    /// Deallocate memory allocated by [`allocate()`]
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by [`allocate()`] with the same `layout`
+    ///
+    pub(super) unsafe fn deallocate(ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() < HUGE_PAGE_SIZE {
+            // Safety: Small allocations come from the global allocator
+            unsafe { dealloc(ptr.as_ptr(), layout) };
+        } else {
+            // A mapping was successfully made for the layout so its size can't overflow
+            let size = mapping_size(layout).unwrap_or(layout.size());
+
+            // Safety: Large allocations are a mapping of exactly `mapping_size()` bytes
+            let result = unsafe { libc::munmap(ptr.as_ptr().cast(), size) };
+            debug_assert_eq!(result, 0, "failed to unmap a bucket");
+        }
+    }
+}
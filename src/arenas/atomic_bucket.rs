@@ -1,30 +1,50 @@
-use crate::{LassoError, LassoErrorKind, LassoResult};
-use alloc::alloc::{alloc, dealloc, Layout};
+use crate::{
+    arenas::allocator::BucketAllocator,
+    sync::{spin_loop, AtomicPtr, AtomicUsize, Ordering},
+    LassoError, LassoResult,
+};
+use alloc::alloc::Layout;
 use core::{
-    hint,
-    mem::{align_of, size_of},
     num::NonZeroUsize,
     ptr::{self, addr_of_mut, NonNull},
     slice,
-    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
 };
 
 pub(super) struct AtomicBucketList {
     /// The first bucket in the list, will be null if the list currently
     /// has no buckets
     head: AtomicPtr<AtomicBucket>,
+    /// The allocator that all buckets within the list are allocated from
+    allocator: BucketAllocator,
 }
 
 impl AtomicBucketList {
     /// Create a new bucket list
-    pub fn new(first_bucket_capacity: NonZeroUsize) -> LassoResult<Self> {
-        let bucket = AtomicBucket::with_capacity(first_bucket_capacity)?;
+    pub fn new(
+        first_bucket_capacity: NonZeroUsize,
+        allocator: BucketAllocator,
+    ) -> LassoResult<Self> {
+        let bucket = AtomicBucket::with_capacity(first_bucket_capacity, &allocator)?;
 
         Ok(Self {
             head: AtomicPtr::new(bucket.as_ptr()),
+            allocator,
         })
     }
 
+    /// Create a bucket list without any buckets
+    pub fn empty(allocator: BucketAllocator) -> Self {
+        Self {
+            head: AtomicPtr::new(ptr::null_mut()),
+            allocator,
+        }
+    }
+
+    /// Get the allocator that buckets within the list are allocated from
+    pub const fn allocator(&self) -> &BucketAllocator {
+        &self.allocator
+    }
+
     pub fn iter(&self) -> AtomicBucketIter<'_> {
         AtomicBucketIter {
             current: &self.head,
@@ -71,10 +91,63 @@ impl AtomicBucketList {
             }
         }
     }
-}
 
-impl Drop for AtomicBucketList {
-    fn drop(&mut self) {
+    /// Move every bucket of the current list onto the front of `retired`, leaving the
+    /// current list empty
+    ///
+    /// Both lists must share the same allocator
+    pub fn retire_into(&self, retired: &Self) {
+        let first = self.head.swap(ptr::null_mut(), Ordering::AcqRel);
+        if first.is_null() {
+            return;
+        }
+
+        // Find the last bucket of the detached chain so it can be linked to `retired`
+        let mut last = first;
+        // Safety: Every bucket within the chain is valid
+        unsafe {
+            loop {
+                let next = (*last).next.load(Ordering::Acquire);
+                if next.is_null() {
+                    break;
+                }
+                last = next;
+            }
+        }
+
+        let mut head_ptr = retired.head.load(Ordering::Acquire);
+        loop {
+            // Safety: The detached chain can't be reached through any list, so we're
+            //         the only one touching its last bucket
+            unsafe { (*last).next.store(head_ptr, Ordering::Release) };
+
+            match retired.head.compare_exchange_weak(
+                head_ptr,
+                first,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => break,
+                Err(new_head) => head_ptr = new_head,
+            }
+        }
+    }
+
+    /// Deallocate every bucket within the list, leaving it empty
+    pub fn clear(&mut self) {
+        // Safety: We have exclusive access to all buckets
+        unsafe { self.deallocate_buckets() };
+        self.head.store(ptr::null_mut(), Ordering::Release);
+    }
+
+    /// Deallocate every bucket within the list without touching its head pointer
+    ///
+    /// # Safety
+    ///
+    /// The caller must have exclusive access to all buckets, and the list's
+    /// head must not be used afterwards without being reset
+    ///
+    unsafe fn deallocate_buckets(&mut self) {
         // Safety: We should have exclusive access to all buckets
         unsafe {
             let mut head_ptr = self.head.load(Ordering::Acquire);
@@ -93,12 +166,21 @@ impl Drop for AtomicBucketList {
                     .expect("buckets with invalid capacities can't be constructed");
 
                 // Deallocate all memory that the bucket allocated
-                dealloc(current_ptr.cast(), layout);
+                // Safety: The bucket was allocated with the list's allocator
+                self.allocator
+                    .deallocate(NonNull::new_unchecked(current_ptr.cast()), layout);
             }
         }
     }
 }
 
+impl Drop for AtomicBucketList {
+    fn drop(&mut self) {
+        // Safety: The list is being dropped so nothing else can access its buckets
+        unsafe { self.deallocate_buckets() };
+    }
+}
+
 pub(super) struct AtomicBucketIter<'a> {
     current: &'a AtomicPtr<AtomicBucket>,
 }
@@ -147,7 +229,8 @@ impl UniqueBucketRef {
     /// Get the current bucket's length
     #[inline]
     pub fn len(&self) -> usize {
-        unsafe { *(*addr_of_mut!((*self.as_ptr()).len)).get_mut() }
+        // Nothing else can access the bucket, so there's nothing to synchronize with
+        self.bucket.length().load(Ordering::Relaxed)
     }
 
     /// Get the current bucket's capacity
@@ -172,8 +255,9 @@ impl UniqueBucketRef {
             self.capacity(),
         );
 
-        // Safety: We have exclusive access to the bucket
-        unsafe { *(*addr_of_mut!((*self.as_ptr()).len)).get_mut() = new_length };
+        // The bucket is published to other threads through the list's head pointer,
+        // which synchronizes this store
+        self.bucket.length().store(new_length, Ordering::Relaxed);
     }
 
     /// Push a slice of bytes to the current bucket
@@ -283,7 +367,7 @@ impl BucketRef {
                         return Ok(len);
                     }
                     Err(loaded) => {
-                        hint::spin_loop();
+                        spin_loop();
                         len = loaded;
                     }
                 }
@@ -318,18 +402,18 @@ pub(super) struct AtomicBucket {
 
 impl AtomicBucket {
     /// Allocates a bucket with space for `capacity` items
-    pub(crate) fn with_capacity(capacity: NonZeroUsize) -> LassoResult<UniqueBucketRef> {
+    ///
+    /// The bucket must only be pushed onto a list using the same allocator
+    pub(crate) fn with_capacity(
+        capacity: NonZeroUsize,
+        allocator: &BucketAllocator,
+    ) -> LassoResult<UniqueBucketRef> {
         // Create the bucket's layout
         let layout = Self::layout(capacity)?;
         debug_assert_ne!(layout.size(), 0);
 
         // Allocate memory for the bucket
-        // Safety: The given layout has a non-zero size
-        let ptr = unsafe {
-            NonNull::new(alloc(layout))
-                .ok_or_else(|| LassoError::new(LassoErrorKind::FailedAllocation))?
-                .cast::<Self>()
-        };
+        let ptr = allocator.allocate(layout)?.cast::<Self>();
 
         // Write to the fields of the bucket
         // Safety: We have exclusive access to the bucket and can write
@@ -349,31 +433,22 @@ impl AtomicBucket {
         Ok(unsafe { UniqueBucketRef::new(ptr) })
     }
 
-    /// Create the layout for a bucket
-    ///
-    /// # Safety
-    ///
-    /// `capacity` must be a power of two that won't overflow when rounded up
-    ///
+    /// Create the layout for a bucket, returning an error if the bucket would be
+    /// too large to allocate
     fn layout(capacity: NonZeroUsize) -> LassoResult<Layout> {
         let next = Layout::new::<AtomicPtr<Self>>();
         let len = Layout::new::<usize>();
         let cap = Layout::new::<NonZeroUsize>();
 
-        // Safety: Align will always be a non-zero power of two and the
-        //         size will not overflow when rounded up
-        debug_assert!(
-            Layout::from_size_align(size_of::<u8>() * capacity.get(), align_of::<u8>()).is_ok()
-        );
-        let data = unsafe {
-            Layout::from_size_align_unchecked(size_of::<u8>() * capacity.get(), align_of::<u8>())
-        };
+        // Buckets larger than `isize::MAX` can't be allocated
+        let data = Layout::array::<u8>(capacity.get())
+            .map_err(|_| LassoError::failed_allocation(capacity.get()))?;
 
         next.extend(len)
             .and_then(|(layout, _)| layout.extend(cap))
             .and_then(|(layout, _)| layout.extend(data))
             .map(|(layout, _)| layout.pad_to_align())
-            .map_err(|_| LassoError::new(LassoErrorKind::FailedAllocation))
+            .map_err(|_| LassoError::failed_allocation(capacity.get()))
     }
 }
 
@@ -1,10 +1,9 @@
-use crate::{LassoError, LassoErrorKind, LassoResult};
-use alloc::alloc::{alloc, dealloc, Layout};
+use crate::{arenas::allocator::BucketAllocator, LassoError, LassoResult};
+use alloc::alloc::Layout;
 use core::{
     mem::{align_of, size_of},
     num::NonZeroUsize,
     ptr::NonNull,
-    slice,
 };
 
 /// A bucket to hold a number of stored items
@@ -15,37 +14,29 @@ pub(super) struct Bucket {
     items: NonNull<u8>,
     /// The total number of Ts that can be stored
     capacity: NonZeroUsize,
+    /// The allocator that `items` was allocated from
+    allocator: BucketAllocator,
 }
 
 impl Bucket {
     /// Allocates a bucket with space for `capacity` items
-    pub(crate) fn with_capacity(capacity: NonZeroUsize) -> LassoResult<Self> {
-        unsafe {
-            debug_assert!(Layout::from_size_align(
-                size_of::<u8>() * capacity.get(),
-                align_of::<u8>(),
-            )
-            .is_ok());
-
-            // Safety: Align will always be a non-zero power of two and the
-            //         size will not overflow when rounded up
-            let layout = Layout::from_size_align_unchecked(
-                size_of::<u8>() * capacity.get(),
-                align_of::<u8>(),
-            );
-
-            // Allocate the bucket's memory
-            let items = NonNull::new(alloc(layout))
-                // TODO: When `Result`s are piped through return this as a unique error
-                .ok_or_else(|| LassoError::new(LassoErrorKind::FailedAllocation))?
-                .cast();
-
-            Ok(Self {
-                index: 0,
-                capacity,
-                items,
-            })
-        }
+    pub(crate) fn with_capacity(
+        capacity: NonZeroUsize,
+        allocator: &BucketAllocator,
+    ) -> LassoResult<Self> {
+        // Buckets larger than `isize::MAX` can't be allocated
+        let layout = Layout::array::<u8>(capacity.get())
+            .map_err(|_| LassoError::failed_allocation(capacity.get()))?;
+
+        // Allocate the bucket's memory
+        let items = allocator.allocate(layout)?;
+
+        Ok(Self {
+            index: 0,
+            capacity,
+            items,
+            allocator: allocator.clone(),
+        })
     }
 
     /// Get the number of available slots for the current bucket
@@ -53,65 +44,78 @@ impl Bucket {
         self.capacity.get() - self.index
     }
 
-    /// Returns whether the current bucket is full
-    pub(crate) fn is_full(&self) -> bool {
-        self.index == self.capacity.get()
-    }
-
     /// Marks the bucket as being totally unused, meaning that all of `capacity`
     /// is valid for allocations
     pub(crate) fn clear(&mut self) {
         self.index = 0;
     }
 
-    /// Push a slice to the current bucket, returning a pointer to it
+    /// Get the number of bytes that have been pushed to the bucket
+    pub(crate) fn len(&self) -> usize {
+        self.index
+    }
+
+    /// Get the number of bytes the bucket can hold
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity.get()
+    }
+
+    /// Marks everything after the first `len` bytes as unused
     ///
     /// # Safety
     ///
-    /// The current bucket must have room for all bytes of the slice and
-    /// the caller promises to forget the reference before the arena is dropped.
-    /// Additionally, `slice` must be valid UTF-8 and should come from an `&str`
+    /// No references may remain to anything pushed past the first `len` bytes
     ///
-    pub(crate) unsafe fn push_slice(&mut self, slice: &[u8]) -> &'static str {
-        debug_assert!(!self.is_full());
-        debug_assert!(slice.len() <= self.capacity.get() - self.index);
+    pub(crate) unsafe fn truncate(&mut self, len: usize) {
+        debug_assert!(len <= self.index);
+        self.index = len;
+    }
 
-        unsafe {
-            // Get a pointer to the start of free bytes
-            let ptr = self.items.as_ptr().add(self.index);
-
-            // Make the slice that we'll fill with the string's data
-            let target = slice::from_raw_parts_mut(ptr, slice.len());
-            // Copy the data from the source string into the bucket's buffer
-            target.copy_from_slice(slice);
-            // Increment the index so that the string we just made isn't overwritten
-            self.index += slice.len();
-
-            // Create a string from that slice
-            // Safety: The source string was valid utf8, so the created buffer will be as well
-            core::str::from_utf8_unchecked(target)
-        }
+    /// Get the number of bytes that have to be skipped for the next byte pushed to start at a
+    /// multiple of `align`, which must be a power of two
+    pub(crate) fn padding_for(&self, align: usize) -> usize {
+        let next = self.items.as_ptr() as usize + self.index;
+        next.wrapping_neg() & (align - 1)
+    }
+
+    /// Get the pointer to the bucket's memory, which identifies the bucket for
+    /// as long as it's alive
+    pub(crate) fn as_ptr(&self) -> NonNull<u8> {
+        self.items
+    }
+
+    /// Marks the next `len` bytes of the current bucket as used, returning a pointer to them
+    ///
+    /// The bytes are left uninitialized
+    ///
+    /// # Safety
+    ///
+    /// The current bucket must have room for `len` more bytes
+    ///
+    pub(crate) unsafe fn take(&mut self, len: usize) -> NonNull<u8> {
+        debug_assert!(len <= self.capacity.get() - self.index);
+
+        // Safety: The bucket has room for `len` bytes starting at `index`, so the pointer
+        //         stays within its allocation
+        let ptr = unsafe { NonNull::new_unchecked(self.items.as_ptr().add(self.index)) };
+        // Increment the index so that the bytes just taken aren't handed out again
+        self.index += len;
+
+        ptr
     }
 }
 
 impl Drop for Bucket {
     fn drop(&mut self) {
+        debug_assert!(Layout::array::<u8>(self.capacity.get()).is_ok());
+
         // Safety: We have exclusive access to the pointers since the contract of
         //         `store_str` should be withheld
         unsafe {
-            let items = self.items.as_ptr();
-
-            debug_assert!(Layout::from_size_align(
-                size_of::<u8>() * self.capacity.get(),
-                align_of::<u8>(),
-            )
-            .is_ok());
-
             // Deallocate all memory that the bucket allocated
-            dealloc(
-                items,
-                // Safety: Align will always be a non-zero power of two and the
-                //         size will not overflow when rounded up
+            self.allocator.deallocate(
+                self.items,
+                // Safety: The layout was checked when the bucket was allocated
                 Layout::from_size_align_unchecked(
                     size_of::<u8>() * self.capacity.get(),
                     align_of::<u8>(),
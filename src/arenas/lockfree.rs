@@ -1,12 +1,17 @@
 use crate::{
-    arenas::atomic_bucket::{AtomicBucket, AtomicBucketList},
+    arenas::{
+        allocator::BucketAllocator,
+        atomic_bucket::{AtomicBucket, AtomicBucketList},
+        owned::{OwnedString, OwnedStringList},
+    },
+    sync::{AtomicBool, AtomicUsize, Ordering},
     Capacity, LassoError, LassoErrorKind, LassoResult, MemoryLimits,
 };
+use alloc::string::String;
 use core::{
     fmt::{self, Debug},
     num::NonZeroUsize,
     slice, str,
-    sync::atomic::{AtomicUsize, Ordering},
 };
 
 /// An arena allocator that dynamically grows in size when needed, allocating memory in large chunks
@@ -22,21 +27,101 @@ pub(crate) struct LockfreeArena {
     bucket_capacity: AtomicUsize,
     memory_usage: AtomicUsize,
     max_memory_usage: AtomicUsize,
+    /// Memory used outside of the arena that's counted against `max_memory_usage`
+    reserved_memory: AtomicUsize,
+    /// Whether `reserved_memory` should be checked before every insertion
+    total_budget: AtomicBool,
+    /// Strings that were too large for a shared bucket and were adopted instead of copied
+    owned_strings: OwnedStringList,
+    /// Whether owned strings too large for a shared bucket should be adopted
+    adopt_large_strings: AtomicBool,
+    /// Strings longer than this are always given an allocation of their own
+    spill_threshold: AtomicUsize,
+    /// Whether the interner shrinks its maps when it's cleared
+    shrink_on_clear: AtomicBool,
+    /// The capacity of the first bucket, which `bucket_capacity` is reset to on `retire()`
+    initial_capacity: NonZeroUsize,
+    /// Buckets that were retired by `retire()` and can't be freed until the arena is
+    /// exclusively owned
+    retired_buckets: AtomicBucketList,
+    /// Adopted strings that were retired along with `retired_buckets`
+    retired_strings: OwnedStringList,
+    /// The part of `memory_usage` taken up by retired buckets and strings
+    retired_memory: AtomicUsize,
 }
 
 impl LockfreeArena {
     /// Create a new Arena with the default bucket size of 4096 bytes
     pub fn new(capacity: NonZeroUsize, max_memory_usage: usize) -> LassoResult<Self> {
+        Self::with_allocator(capacity, max_memory_usage, BucketAllocator::Global)
+    }
+
+    /// Create a new Arena that allocates all of its buckets from `allocator`
+    pub fn with_allocator(
+        capacity: NonZeroUsize,
+        max_memory_usage: usize,
+        allocator: BucketAllocator,
+    ) -> LassoResult<Self> {
         Ok(Self {
+            retired_buckets: AtomicBucketList::empty(allocator.clone()),
             // Allocate one bucket
-            buckets: AtomicBucketList::new(capacity)?,
+            buckets: AtomicBucketList::new(capacity, allocator)?,
             bucket_capacity: AtomicUsize::new(capacity.get()),
             // The current capacity is whatever size the bucket we just allocated is
             memory_usage: AtomicUsize::new(capacity.get()),
             max_memory_usage: AtomicUsize::new(max_memory_usage),
+            reserved_memory: AtomicUsize::new(0),
+            total_budget: AtomicBool::new(false),
+            owned_strings: OwnedStringList::new(),
+            adopt_large_strings: AtomicBool::new(false),
+            spill_threshold: AtomicUsize::new(usize::MAX),
+            shrink_on_clear: AtomicBool::new(false),
+            initial_capacity: capacity,
+            retired_strings: OwnedStringList::new(),
+            retired_memory: AtomicUsize::new(0),
         })
     }
 
+    /// Stop handing out memory from every current bucket and adopted string, making the
+    /// arena behave as if it were freshly created
+    ///
+    /// Retired memory stays allocated (and counted by `current_memory_usage()`) so that
+    /// previously stored strings remain valid, it's only freed by `free_retired()` or
+    /// when the arena is dropped
+    ///
+    /// Must not be called while other threads are storing strings
+    ///
+    pub(crate) fn retire(&self) {
+        self.buckets.retire_into(&self.retired_buckets);
+        self.owned_strings.retire_into(&self.retired_strings);
+        self.set_bucket_capacity(self.initial_capacity.get());
+
+        // Everything that's currently allocated has just been retired
+        self.retired_memory
+            .store(self.current_memory_usage(), Ordering::Relaxed);
+    }
+
+    /// Free all memory retired by `retire()`
+    ///
+    /// # Safety
+    ///
+    /// Every string stored before the last call to `retire()` must have been dropped
+    ///
+    pub(crate) unsafe fn free_retired(&mut self) {
+        self.retired_buckets.clear();
+        self.retired_strings.clear();
+
+        let retired_memory = self.retired_memory.swap(0, Ordering::Relaxed);
+        self.memory_usage
+            .fetch_sub(retired_memory, Ordering::Relaxed);
+    }
+
+    /// Get the amount of memory that's retired and waiting to be freed
+    #[inline]
+    pub(crate) fn retired_memory_usage(&self) -> usize {
+        self.retired_memory.load(Ordering::Relaxed)
+    }
+
     #[inline]
     pub(crate) fn current_memory_usage(&self) -> usize {
         self.memory_usage.load(Ordering::Relaxed)
@@ -53,6 +138,90 @@ impl LockfreeArena {
         self.max_memory_usage.load(Ordering::Relaxed)
     }
 
+    #[inline]
+    pub(crate) fn set_total_budget(&self, total_budget: bool) {
+        self.total_budget.store(total_budget, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn is_total_budget(&self) -> bool {
+        self.total_budget.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub(crate) fn set_adopt_large_strings(&self, adopt_large_strings: bool) {
+        self.adopt_large_strings
+            .store(adopt_large_strings, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn is_adopting_large_strings(&self) -> bool {
+        self.adopt_large_strings.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub(crate) fn set_spill_threshold(&self, spill_threshold: usize) {
+        self.spill_threshold
+            .store(spill_threshold, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn get_spill_threshold(&self) -> usize {
+        self.spill_threshold.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub(crate) fn set_shrink_on_clear(&self, shrink_on_clear: bool) {
+        self.shrink_on_clear
+            .store(shrink_on_clear, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn is_shrinking_on_clear(&self) -> bool {
+        self.shrink_on_clear.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub(crate) fn set_reserved_memory(&self, reserved_memory: usize) {
+        self.reserved_memory
+            .store(reserved_memory, Ordering::Relaxed);
+    }
+
+    /// The memory used by the arena plus any memory reserved outside of it
+    fn budgeted_usage(&self) -> usize {
+        self.current_memory_usage()
+            .saturating_add(self.reserved_memory.load(Ordering::Relaxed))
+    }
+
+    /// Returns an error if the reserved memory pushes usage past `max_memory_usage` before
+    /// storing a string of `len` bytes, always succeeds if the arena doesn't have a total budget
+    ///
+    /// Empty strings are never stored, so they always succeed too
+    pub(crate) fn check_reserved_memory(&self, len: usize) -> LassoResult<()> {
+        let (budgeted_usage, max_memory_usage) =
+            (self.budgeted_usage(), self.get_max_memory_usage());
+        if len != 0 && self.is_total_budget() && budgeted_usage > max_memory_usage {
+            Err(LassoError::new(LassoErrorKind::MemoryLimitReached)
+                .with_memory_usage(budgeted_usage, max_memory_usage))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns `true` if a string of `len` bytes gets an allocation of its own instead of
+    /// being put in a shared bucket
+    ///
+    /// Strings that are larger than the spill threshold or that wouldn't even fit in the
+    /// next doubled bucket are spilled, so they don't distort the size of future buckets
+    fn spills(&self, len: usize) -> bool {
+        len > self.get_spill_threshold()
+            || len
+                > self
+                    .bucket_capacity
+                    .load(Ordering::Relaxed)
+                    .saturating_mul(2)
+    }
+
     fn set_bucket_capacity(&self, capacity: usize) {
         debug_assert_ne!(capacity, 0);
         self.bucket_capacity.store(capacity, Ordering::Relaxed);
@@ -62,16 +231,26 @@ impl LockfreeArena {
     /// the attempted amount surpasses `max_memory_usage`
     // TODO: Make this return a `Result`
     fn allocate_memory(&self, requested_mem: usize) -> LassoResult<()> {
-        if self.memory_usage.load(Ordering::Relaxed) + requested_mem
-            > self.max_memory_usage.load(Ordering::Relaxed)
-        {
-            Err(LassoError::new(LassoErrorKind::MemoryLimitReached))
-        } else {
-            self.memory_usage
-                .fetch_add(requested_mem, Ordering::Relaxed);
-
-            Ok(())
-        }
+        let max_memory_usage = self.get_max_memory_usage();
+        let reserved_memory = self.reserved_memory.load(Ordering::Relaxed);
+
+        // The check and the update have to happen at once, otherwise concurrent allocations
+        // could each see enough room and together go over the limit
+        self.memory_usage
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |memory_usage| {
+                let memory_usage = memory_usage.checked_add(requested_mem)?;
+
+                (memory_usage.saturating_add(reserved_memory) <= max_memory_usage)
+                    .then_some(memory_usage)
+            })
+            .map(|_| ())
+            .map_err(|memory_usage| {
+                LassoError::memory_limit_reached(
+                    requested_mem,
+                    memory_usage.saturating_add(reserved_memory),
+                    max_memory_usage,
+                )
+            })
     }
 
     /// Store a slice in the Arena, returning `None` if memory is exhausted
@@ -91,6 +270,9 @@ impl LockfreeArena {
         let slice = string.as_bytes();
         debug_assert_ne!(slice.len(), 0);
 
+        // Spilled strings skip the shared buckets entirely
+        let spills = self.spills(slice.len());
+
         // Iterate over all of the buckets within the list while attempting to find one
         // that has enough space to fit our string within it
         //
@@ -100,33 +282,38 @@ impl LockfreeArena {
         // really matter, but it's worth that the opposite tradeoff can be made by adding bounded
         // retries within this loop, the worst-case performance suffers in exchange for potentially
         // better memory usage.
-        for bucket in self.buckets.iter() {
-            if let Ok(start) = bucket.try_inc_length(slice.len()) {
-                // Safety: We now have exclusive access to `bucket[start..start + slice.len()]`
-                let allocated = unsafe { bucket.slice_mut(start) };
-                // Copy the given slice into the allocation
-                unsafe { allocated.copy_from_nonoverlapping(slice.as_ptr(), slice.len()) };
-
-                // Return the successfully allocated string
-                let string = unsafe {
-                    str::from_utf8_unchecked(slice::from_raw_parts(allocated, slice.len()))
-                };
-                return Ok(string);
-            }
+        if !spills {
+            for bucket in self.buckets.iter() {
+                if let Ok(start) = bucket.try_inc_length(slice.len()) {
+                    // Safety: We now have exclusive access to `bucket[start..start + slice.len()]`
+                    let allocated = unsafe { bucket.slice_mut(start) };
+                    // Copy the given slice into the allocation
+                    unsafe { allocated.copy_from_nonoverlapping(slice.as_ptr(), slice.len()) };
+
+                    // Return the successfully allocated string
+                    let string = unsafe {
+                        str::from_utf8_unchecked(slice::from_raw_parts(allocated, slice.len()))
+                    };
+                    return Ok(string);
+                }
 
-            // Otherwise the bucket doesn't have sufficient capacity for the string
-            // so we carry on searching through allocated buckets
+                // Otherwise the bucket doesn't have sufficient capacity for the string
+                // so we carry on searching through allocated buckets
+            }
         }
 
         // If we couldn't find a pre-existing bucket with enough room in it, allocate our own bucket
 
-        let next_capacity = self.bucket_capacity.load(Ordering::Relaxed) * 2;
+        let next_capacity = self
+            .bucket_capacity
+            .load(Ordering::Relaxed)
+            .saturating_mul(2);
         debug_assert_ne!(next_capacity, 0);
 
-        // If the current string's length is greater than the doubled current capacity, allocate a bucket exactly the
-        // size of the large string and push it back in the buckets vector. This ensures that obscenely large strings will
-        // not permanently affect the resource consumption of the interner
-        if slice.len() > next_capacity {
+        // If the current string is too large for a shared bucket, allocate a bucket exactly the size of the large
+        // string and push it back in the buckets vector. This ensures that obscenely large strings will not
+        // permanently affect the resource consumption of the interner
+        if spills {
             // Check that we haven't exhausted our memory limit
             self.allocate_memory(slice.len())?;
 
@@ -135,7 +322,7 @@ impl LockfreeArena {
             let non_zero_len = unsafe { NonZeroUsize::new_unchecked(slice.len()) };
             debug_assert_ne!(slice.len(), 0);
 
-            let mut bucket = AtomicBucket::with_capacity(non_zero_len)?;
+            let mut bucket = AtomicBucket::with_capacity(non_zero_len, self.buckets.allocator())?;
 
             // Safety: The new bucket will have exactly enough room for the string and we have
             //         exclusive access to the bucket since we just created it
@@ -144,11 +331,11 @@ impl LockfreeArena {
 
             Ok(allocated_string)
         } else {
-            let memory_usage = self.current_memory_usage();
+            let memory_usage = self.budgeted_usage();
             let max_memory_usage = self.get_max_memory_usage();
 
             // If trying to use the doubled capacity will surpass our memory limit, just allocate as much as we can
-            if memory_usage + next_capacity > max_memory_usage {
+            if memory_usage.saturating_add(next_capacity) > max_memory_usage {
                 let remaining_memory = max_memory_usage.saturating_sub(memory_usage);
 
                 // Check that we haven't exhausted our memory limit
@@ -156,8 +343,14 @@ impl LockfreeArena {
 
                 // Set the capacity to twice of what it currently is to allow for fewer allocations as more strings are interned
                 let mut bucket = AtomicBucket::with_capacity(
-                    NonZeroUsize::new(remaining_memory)
-                        .ok_or_else(|| LassoError::new(LassoErrorKind::MemoryLimitReached))?,
+                    NonZeroUsize::new(remaining_memory).ok_or_else(|| {
+                        LassoError::memory_limit_reached(
+                            slice.len(),
+                            memory_usage,
+                            max_memory_usage,
+                        )
+                    })?,
+                    self.buckets.allocator(),
                 )?;
 
                 // Safety: The new bucket will have exactly enough room for the string and we have
@@ -181,7 +374,7 @@ impl LockfreeArena {
                 let capacity = unsafe { NonZeroUsize::new_unchecked(next_capacity) };
                 debug_assert_ne!(next_capacity, 0);
 
-                let mut bucket = AtomicBucket::with_capacity(capacity)?;
+                let mut bucket = AtomicBucket::with_capacity(capacity, self.buckets.allocator())?;
 
                 // Safety: The new bucket will have enough room for the string
                 let allocated_string = unsafe { bucket.push_slice(slice) };
@@ -193,6 +386,28 @@ impl LockfreeArena {
     }
 }
 
+impl LockfreeArena {
+    /// Store an owned string in the Arena, adopting its buffer instead of copying it if
+    /// adoption is enabled and the string is too large to share a bucket
+    ///
+    /// # Safety
+    ///
+    /// The reference passed back must be dropped before the arena that created it is
+    ///
+    pub unsafe fn store_string(&self, string: String) -> LassoResult<&'static str> {
+        if !self.is_adopting_large_strings() || !self.spills(string.len()) {
+            // Safety: The caller upholds the same contract
+            return unsafe { self.store_str(&string) };
+        }
+
+        // Adopted strings count their whole buffer against the memory limit
+        self.allocate_memory(string.capacity())?;
+
+        // Safety: The string is kept until the arena is dropped
+        Ok(unsafe { self.owned_strings.push(OwnedString::new(string)) })
+    }
+}
+
 impl Default for LockfreeArena {
     fn default() -> Self {
         Self::new(
@@ -219,6 +434,7 @@ impl Debug for LockfreeArena {
 
         f.debug_struct("Arena")
             .field("buckets", &TotalBuckets(self.buckets.len()))
+            .field("retired_buckets", &TotalBuckets(self.retired_buckets.len()))
             .field(
                 "bucket_capacity",
                 &self.bucket_capacity.load(Ordering::Relaxed),
@@ -228,6 +444,17 @@ impl Debug for LockfreeArena {
                 "max_memory_usage",
                 &self.max_memory_usage.load(Ordering::Relaxed),
             )
+            .field(
+                "reserved_memory",
+                &self.reserved_memory.load(Ordering::Relaxed),
+            )
+            .field("allocator", self.buckets.allocator())
+            .field(
+                "adopt_large_strings",
+                &self.adopt_large_strings.load(Ordering::Relaxed),
+            )
+            .field("spill_threshold", &self.get_spill_threshold())
+            .field("retired_memory", &self.retired_memory_usage())
             .finish()
     }
 }
@@ -236,6 +463,52 @@ impl Debug for LockfreeArena {
 mod tests {
     use super::*;
 
+    #[test]
+    fn spill_threshold() {
+        let arena = LockfreeArena::new(NonZeroUsize::new(1024).unwrap(), usize::MAX).unwrap();
+        arena.set_spill_threshold(100);
+
+        // Large strings get their own bucket even though they'd fit in the current one
+        let large = "a".repeat(500);
+        assert_eq!(unsafe { arena.store_str(&large) }, Ok(large.as_str()));
+        assert_eq!(arena.current_memory_usage(), 1024 + 500);
+        assert_eq!(arena.bucket_capacity.load(Ordering::Relaxed), 1024);
+        assert_eq!(arena.buckets.len(), 2);
+
+        // Smaller strings still share the existing bucket
+        assert_eq!(unsafe { arena.store_str("small") }, Ok("small"));
+        assert_eq!(arena.current_memory_usage(), 1024 + 500);
+        assert_eq!(arena.buckets.len(), 2);
+    }
+
+    #[test]
+    fn retire() {
+        let mut arena = LockfreeArena::new(NonZeroUsize::new(16).unwrap(), usize::MAX).unwrap();
+        arena.set_adopt_large_strings(true);
+
+        let small = unsafe { arena.store_str("small").unwrap() };
+        let large = unsafe { arena.store_string("a".repeat(100)).unwrap() };
+        assert_eq!(arena.current_memory_usage(), 16 + 100);
+
+        arena.retire();
+        assert_eq!(arena.buckets.len(), 0);
+        assert_eq!(arena.retired_buckets.len(), 1);
+        assert_eq!(arena.retired_memory_usage(), 16 + 100);
+        assert_eq!(arena.bucket_capacity.load(Ordering::Relaxed), 16);
+
+        // Retired strings stay alive until they're freed
+        assert_eq!(small, "small");
+        assert_eq!(large, "a".repeat(100));
+
+        assert_eq!(unsafe { arena.store_str("new") }, Ok("new"));
+        assert_eq!(arena.buckets.len(), 1);
+
+        unsafe { arena.free_retired() };
+        assert_eq!(arena.retired_buckets.len(), 0);
+        assert_eq!(arena.retired_memory_usage(), 0);
+        assert_eq!(arena.current_memory_usage(), 32);
+    }
+
     #[test]
     fn string() {
         let arena = LockfreeArena::default();
@@ -313,3 +586,94 @@ mod tests {
         }
     }
 }
+
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::LockfreeArena;
+    use core::num::NonZeroUsize;
+    use loom::{model::Builder, sync::Arc, thread};
+
+    fn model<F>(f: F)
+    where
+        F: Fn() + Sync + Send + 'static,
+    {
+        let mut builder = Builder::new();
+        builder.preemption_bound.get_or_insert(3);
+        builder.check(f);
+    }
+
+    #[test]
+    fn loom_concurrent_grow() {
+        model(|| {
+            // Every string is too large to share a bucket, so each one forces the arena to grow
+            let arena =
+                Arc::new(LockfreeArena::new(NonZeroUsize::new(4).unwrap(), usize::MAX).unwrap());
+
+            let other = Arc::clone(&arena);
+            let thread = thread::spawn(move || {
+                // Safety: The strings are dropped before the arena
+                let (first, second) = unsafe {
+                    (
+                        other.store_str("abc").unwrap(),
+                        other.store_str("defgh").unwrap(),
+                    )
+                };
+
+                assert_eq!(first, "abc");
+                assert_eq!(second, "defgh");
+            });
+
+            // Safety: The strings are dropped before the arena
+            let (first, second) = unsafe {
+                (
+                    arena.store_str("ijk").unwrap(),
+                    arena.store_str("lmnop").unwrap(),
+                )
+            };
+            thread.join().unwrap();
+
+            assert_eq!(first, "ijk");
+            assert_eq!(second, "lmnop");
+            assert!(arena.current_memory_usage() >= 4 + 3 + 3 + 5 + 5);
+        });
+    }
+
+    #[test]
+    fn loom_concurrent_fill() {
+        model(|| {
+            // Both threads race to fill the same bucket
+            let arena =
+                Arc::new(LockfreeArena::new(NonZeroUsize::new(4).unwrap(), usize::MAX).unwrap());
+
+            let other = Arc::clone(&arena);
+            let thread = thread::spawn(move || {
+                // Safety: The string is dropped before the arena
+                assert_eq!(unsafe { other.store_str("ab").unwrap() }, "ab");
+            });
+
+            // Safety: The string is dropped before the arena
+            assert_eq!(unsafe { arena.store_str("cd").unwrap() }, "cd");
+            thread.join().unwrap();
+
+            // Both strings fit into the first bucket unless the race forced a new one
+            assert!(arena.current_memory_usage() >= 4);
+        });
+    }
+
+    #[test]
+    fn loom_concurrent_memory_limit() {
+        model(|| {
+            // Only one of the threads can grow the arena
+            let arena = Arc::new(LockfreeArena::new(NonZeroUsize::new(1).unwrap(), 3).unwrap());
+
+            let other = Arc::clone(&arena);
+            // Safety: The strings are dropped before the arena
+            let thread = thread::spawn(move || unsafe { other.store_str("ab").is_ok() });
+            let stored = unsafe { arena.store_str("cd").is_ok() };
+            let other_stored = thread.join().unwrap();
+
+            assert!(stored || other_stored);
+            assert!(arena.current_memory_usage() <= 3);
+        });
+    }
+}
@@ -1,4 +1,6 @@
+mod allocator;
 mod bucket;
+mod owned;
 mod single_threaded;
 
 #[cfg(feature = "multi-threaded")]
@@ -6,10 +8,12 @@ mod atomic_bucket;
 #[cfg(feature = "multi-threaded")]
 mod lockfree;
 
+pub(crate) use allocator::BucketAllocator;
 #[cfg(feature = "multi-threaded")]
 pub(crate) use lockfree::LockfreeArena;
-pub(crate) use single_threaded::Arena;
+pub(crate) use single_threaded::{Arena, ArenaMark};
 
+use crate::LassoResult;
 use core::fmt::{self, Debug};
 
 /// A wrapper type to abstract over all arena types
@@ -22,6 +26,33 @@ pub(crate) enum AnyArena {
     Lockfree(LockfreeArena),
 }
 
+impl AnyArena {
+    /// Store a slice in the underlying arena
+    ///
+    /// # Safety
+    ///
+    /// The reference passed back must be dropped before the arena that created it is
+    ///
+    pub(crate) unsafe fn store_str(&mut self, string: &str) -> LassoResult<&'static str> {
+        match self {
+            // Safety: The caller upholds the arena's contract
+            Self::Arena(arena) => unsafe { arena.store_str(string) },
+            #[cfg(feature = "multi-threaded")]
+            // Safety: The caller upholds the arena's contract
+            Self::Lockfree(arena) => unsafe { arena.store_str(string) },
+        }
+    }
+
+    /// Get the memory allocated by the underlying arena
+    pub(crate) fn memory_usage(&self) -> usize {
+        match self {
+            Self::Arena(arena) => arena.memory_usage(),
+            #[cfg(feature = "multi-threaded")]
+            Self::Lockfree(arena) => arena.current_memory_usage(),
+        }
+    }
+}
+
 impl Debug for AnyArena {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
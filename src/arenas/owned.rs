@@ -0,0 +1,167 @@
+#[cfg(feature = "multi-threaded")]
+use crate::sync::{AtomicPtr, Ordering};
+#[cfg(feature = "multi-threaded")]
+use alloc::boxed::Box;
+use alloc::string::String;
+#[cfg(feature = "multi-threaded")]
+use core::ptr;
+use core::{mem::ManuallyDrop, ptr::NonNull, slice, str};
+
+/// A string whose buffer was handed over to an arena instead of being copied into it
+///
+/// The buffer is kept exactly as the [`String`] allocated it so that adopting a string
+/// never reallocates, and it's freed when this is dropped
+pub(crate) struct OwnedString {
+    ptr: NonNull<u8>,
+    len: usize,
+    capacity: usize,
+}
+
+impl OwnedString {
+    pub(crate) fn new(string: String) -> Self {
+        let mut string = ManuallyDrop::new(string);
+
+        Self {
+            // Safety: A string's buffer is never null, even when it hasn't allocated
+            ptr: unsafe { NonNull::new_unchecked(string.as_mut_ptr()) },
+            len: string.len(),
+            capacity: string.capacity(),
+        }
+    }
+
+    /// The number of bytes the string's buffer takes up
+    pub(crate) const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get a pointer to the start of the string's buffer
+    pub(crate) const fn as_ptr(&self) -> *const u8 {
+        self.ptr.as_ptr()
+    }
+
+    /// Get the contents of the string
+    ///
+    /// # Safety
+    ///
+    /// The returned string must be dropped before the current `OwnedString` is
+    ///
+    pub(crate) unsafe fn as_static_str(&self) -> &'static str {
+        // Safety: The buffer came from a `String` so it holds `len` bytes of valid UTF-8,
+        //         and the caller promises not to use it after it's freed
+        unsafe { str::from_utf8_unchecked(slice::from_raw_parts(self.ptr.as_ptr(), self.len)) }
+    }
+}
+
+impl Drop for OwnedString {
+    fn drop(&mut self) {
+        // Safety: The parts were taken from a `String` that was never dropped
+        drop(unsafe { String::from_raw_parts(self.ptr.as_ptr(), self.len, self.capacity) });
+    }
+}
+
+// Safety: `OwnedString` uniquely owns its buffer just like `String` does
+unsafe impl Send for OwnedString {}
+// Safety: The buffer is never mutated through a shared reference
+unsafe impl Sync for OwnedString {}
+
+/// A lock-free list of adopted strings that can be pushed to concurrently
+#[cfg(feature = "multi-threaded")]
+pub(crate) struct OwnedStringList {
+    head: AtomicPtr<OwnedStringNode>,
+}
+
+#[cfg(feature = "multi-threaded")]
+struct OwnedStringNode {
+    string: OwnedString,
+    next: *mut OwnedStringNode,
+}
+
+#[cfg(feature = "multi-threaded")]
+impl OwnedStringList {
+    pub(crate) fn new() -> Self {
+        Self {
+            head: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Add a string to the list, returning its contents
+    ///
+    /// # Safety
+    ///
+    /// The returned string must be dropped before the list is
+    ///
+    pub(crate) unsafe fn push(&self, string: OwnedString) -> &'static str {
+        let node = Box::into_raw(Box::new(OwnedStringNode {
+            string,
+            next: ptr::null_mut(),
+        }));
+
+        // Safety: The node was just allocated, and the string lives as long as the list does
+        let contents = unsafe { (*node).string.as_static_str() };
+
+        let mut head = self.head.load(Ordering::Acquire);
+        loop {
+            // Safety: Nobody else can see the node until it's been published
+            unsafe { (*node).next = head };
+
+            match self
+                .head
+                .compare_exchange_weak(head, node, Ordering::AcqRel, Ordering::Acquire)
+            {
+                Ok(_) => return contents,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    /// Move every string of the current list onto the front of `retired`, leaving the
+    /// current list empty
+    pub(crate) fn retire_into(&self, retired: &Self) {
+        let first = self.head.swap(ptr::null_mut(), Ordering::AcqRel);
+        if first.is_null() {
+            return;
+        }
+
+        // Safety: Every node within the detached chain is valid and can't be reached
+        //         through any list, so we're the only one touching them
+        unsafe {
+            let mut last = first;
+            while !(*last).next.is_null() {
+                last = (*last).next;
+            }
+
+            let mut head = retired.head.load(Ordering::Acquire);
+            loop {
+                (*last).next = head;
+
+                match retired.head.compare_exchange_weak(
+                    head,
+                    first,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => break,
+                    Err(current) => head = current,
+                }
+            }
+        }
+    }
+
+    /// Free every string within the list, leaving it empty
+    pub(crate) fn clear(&mut self) {
+        let mut node = self.head.swap(ptr::null_mut(), Ordering::AcqRel);
+
+        while !node.is_null() {
+            // Safety: Every node was made by `Box::into_raw()` and is only freed once
+            let current = unsafe { Box::from_raw(node) };
+            node = current.next;
+        }
+    }
+}
+
+#[cfg(feature = "multi-threaded")]
+impl Drop for OwnedStringList {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
@@ -1,8 +1,9 @@
 use crate::{
-    arenas::bucket::Bucket, Capacity, LassoError, LassoErrorKind, LassoResult, MemoryLimits,
+    arenas::{allocator::BucketAllocator, bucket::Bucket, owned::OwnedString},
+    Capacity, LassoError, LassoErrorKind, LassoResult, MemoryLimits,
 };
-use alloc::{format, vec, vec::Vec};
-use core::{fmt, num::NonZeroUsize};
+use alloc::{format, string::String, vec, vec::Vec};
+use core::{fmt, num::NonZeroUsize, ptr, ptr::NonNull, slice};
 
 /// An arena allocator that dynamically grows in size when needed, allocating memory in large chunks
 pub(crate) struct Arena {
@@ -10,23 +11,67 @@ pub(crate) struct Arena {
     buckets: Vec<Bucket>,
     /// The default capacity of each bucket
     bucket_capacity: NonZeroUsize,
+    /// The capacity of the first bucket, used to start over once every bucket is released
+    initial_capacity: NonZeroUsize,
     memory_usage: usize,
     pub(crate) max_memory_usage: usize,
+    /// Memory used outside of the arena that's counted against `max_memory_usage`
+    pub(crate) reserved_memory: usize,
+    /// Whether `reserved_memory` should be checked before every insertion
+    pub(crate) total_budget: bool,
+    /// The allocator that all buckets are allocated from
+    allocator: BucketAllocator,
+    /// Strings that were too large for a shared bucket and were adopted instead of copied
+    owned_strings: Vec<OwnedString>,
+    /// Whether owned strings too large for a shared bucket should be adopted
+    pub(crate) adopt_large_strings: bool,
+    /// Strings longer than this are always given an allocation of their own
+    pub(crate) spill_threshold: usize,
+    /// Whether every stored string is followed by a NUL byte
+    pub(crate) nul_terminated: bool,
+    /// Whether the arena is limited to its first bucket and never allocates again
+    pub(crate) fixed: bool,
+    /// Whether the interner shrinks its maps and releases empty buckets when it's cleared
+    pub(crate) shrink_on_clear: bool,
 }
 
 impl Arena {
     /// Create a new Arena with the default bucket size of 4096 bytes
     pub fn new(capacity: NonZeroUsize, max_memory_usage: usize) -> LassoResult<Self> {
+        Self::with_allocator(capacity, max_memory_usage, BucketAllocator::Global)
+    }
+
+    /// Create a new Arena that allocates all of its buckets from `allocator`
+    pub fn with_allocator(
+        capacity: NonZeroUsize,
+        max_memory_usage: usize,
+        allocator: BucketAllocator,
+    ) -> LassoResult<Self> {
         Ok(Self {
             // Allocate one bucket
-            buckets: vec![Bucket::with_capacity(capacity)?],
+            buckets: vec![Bucket::with_capacity(capacity, &allocator)?],
             bucket_capacity: capacity,
+            initial_capacity: capacity,
             // The current capacity is whatever size the bucket we just allocated is
             memory_usage: capacity.get(),
             max_memory_usage,
+            reserved_memory: 0,
+            total_budget: false,
+            allocator,
+            owned_strings: Vec::new(),
+            adopt_large_strings: false,
+            spill_threshold: usize::MAX,
+            nul_terminated: false,
+            fixed: false,
+            shrink_on_clear: false,
         })
     }
 
+    /// Get the allocator that the arena's buckets are allocated from
+    pub const fn allocator(&self) -> &BucketAllocator {
+        &self.allocator
+    }
+
     pub const fn memory_usage(&self) -> usize {
         self.memory_usage
     }
@@ -38,88 +83,318 @@ impl Arena {
         for bucket in &mut self.buckets {
             bucket.clear();
         }
+
+        // Adopted strings aren't reused, so they're freed
+        for string in self.owned_strings.drain(..) {
+            self.memory_usage -= string.capacity();
+        }
+    }
+
+    /// Frees every bucket that nothing is stored in, which is all of them after the arena
+    /// is cleared
+    ///
+    /// Fixed arenas keep their only bucket
+    pub fn release_empty_buckets(&mut self) {
+        if self.fixed {
+            return;
+        }
+
+        let mut released = 0;
+        self.buckets.retain(|bucket| {
+            let empty = bucket.len() == 0;
+            if empty {
+                released += bucket.capacity();
+            }
+
+            !empty
+        });
+        self.memory_usage -= released;
+
+        // Buckets grow from the initial capacity again, like they did when the arena was new
+        if self.buckets.is_empty() {
+            self.bucket_capacity = self.initial_capacity;
+        }
+    }
+
+    /// Records the arena's current state so that everything stored after it can be
+    /// freed with [`Arena::rewind`]
+    pub fn mark(&self) -> ArenaMark {
+        ArenaMark {
+            buckets: self.buckets.iter().map(Bucket::as_ptr).collect(),
+            last_len: self.buckets.last().map_or(0, Bucket::len),
+            bucket_capacity: self.bucket_capacity,
+            memory_usage: self.memory_usage,
+            owned_strings: self.owned_strings.len(),
+        }
+    }
+
+    /// Frees everything stored in the arena since `mark` was made
+    ///
+    /// Buckets are only ever written to while they're the last bucket, so the buckets that
+    /// existed when the mark was made are untouched except for the one that was last then
+    ///
+    /// # Safety
+    ///
+    /// `mark` must have been made by the current arena, it must not have been cleared since,
+    /// and no references may remain to anything stored since the mark was made
+    ///
+    pub unsafe fn rewind(&mut self, mark: ArenaMark) {
+        // New buckets are allocated while the old ones are still alive, so a bucket whose
+        // pointer isn't in the mark was allocated after it
+        self.buckets
+            .retain(|bucket| mark.buckets.contains(&bucket.as_ptr()));
+        if let Some(last) = self.buckets.last_mut() {
+            debug_assert_eq!(mark.buckets.last(), Some(&last.as_ptr()));
+
+            // Safety: The caller promises nothing stored since the mark is referenced
+            unsafe { last.truncate(mark.last_len) };
+        }
+
+        self.owned_strings.truncate(mark.owned_strings);
+        self.bucket_capacity = mark.bucket_capacity;
+        self.memory_usage = mark.memory_usage;
+    }
+
+    /// Frees `string` if it's the last thing that was stored in the arena, returning `true` if
+    /// it was freed
+    ///
+    /// Strings stored in the middle of a bucket or given a bucket of their own can't be freed
+    /// on their own and are left until the arena is cleared
+    ///
+    /// # Safety
+    ///
+    /// `string` must have been stored by the current arena, it must not have been cleared
+    /// since, and no references may remain to the string
+    ///
+    pub unsafe fn release_last(&mut self, string: &str) -> bool {
+        // Empty strings are never stored
+        if string.is_empty() {
+            return true;
+        }
+
+        if self
+            .owned_strings
+            .last()
+            .is_some_and(|owned| owned.as_ptr() == string.as_ptr())
+        {
+            if let Some(owned) = self.owned_strings.pop() {
+                self.memory_usage -= owned.capacity();
+            }
+
+            return true;
+        }
+
+        let len = string.len() + usize::from(self.nul_terminated);
+        match self.buckets.last_mut() {
+            Some(last)
+                if last.len() >= len
+                    && last.as_ptr().as_ptr() as usize + (last.len() - len)
+                        == string.as_ptr() as usize =>
+            {
+                // Safety: The string was the last thing pushed to the bucket and the caller
+                //         promises nothing references it
+                unsafe { last.truncate(last.len() - len) };
+
+                true
+            }
+
+            _ => false,
+        }
     }
 
     /// Doesn't actually allocate anything, but increments `self.memory_usage` and returns `None` if
     /// the attempted amount surpasses `max_memory_usage`
     // TODO: Make this return a `Result`
     fn allocate_memory(&mut self, requested_mem: usize) -> LassoResult<()> {
-        if self.memory_usage + requested_mem > self.max_memory_usage {
-            Err(LassoError::new(LassoErrorKind::MemoryLimitReached))
-        } else {
-            self.memory_usage += requested_mem;
+        match self.memory_usage.checked_add(requested_mem) {
+            Some(memory_usage)
+                if self.budgeted_usage().saturating_add(requested_mem) <= self.max_memory_usage =>
+            {
+                self.memory_usage = memory_usage;
+
+                Ok(())
+            }
+
+            _ => Err(LassoError::memory_limit_reached(
+                requested_mem,
+                self.budgeted_usage(),
+                self.max_memory_usage,
+            )),
+        }
+    }
+
+    /// The memory used by the arena plus any memory reserved outside of it
+    fn budgeted_usage(&self) -> usize {
+        self.memory_usage.saturating_add(self.reserved_memory)
+    }
 
+    /// Returns an error if the reserved memory pushes usage past `max_memory_usage` before
+    /// storing a string of `len` bytes, always succeeds if the arena doesn't have a total budget
+    ///
+    /// Empty strings are never stored, so they always succeed too
+    pub fn check_reserved_memory(&self, len: usize) -> LassoResult<()> {
+        if len != 0 && self.total_budget && self.budgeted_usage() > self.max_memory_usage {
+            Err(LassoError::new(LassoErrorKind::MemoryLimitReached)
+                .with_memory_usage(self.budgeted_usage(), self.max_memory_usage))
+        } else {
             Ok(())
         }
     }
 
+    /// Returns `true` if a string of `len` bytes gets an allocation of its own instead of
+    /// being put in a shared bucket
+    ///
+    /// Strings that are larger than the spill threshold or that wouldn't even fit in the
+    /// next doubled bucket are spilled, so they don't distort the size of future buckets
+    fn spills(&self, len: usize) -> bool {
+        len > self.spill_threshold || len > self.bucket_capacity.get().saturating_mul(2)
+    }
+
     /// Store a slice in the Arena, returning `None` if memory is exhausted
     ///
+    /// If the arena is `nul_terminated` the stored string is followed by a NUL byte,
+    /// which isn't part of the returned string
+    ///
     /// # Safety
     ///
     /// The reference passed back must be dropped before the arena that created it is
     ///
     pub unsafe fn store_str(&mut self, string: &str) -> LassoResult<&'static str> {
-        // If the string is empty, simply return an empty string.
-        // This ensures that only strings with lengths greater
+        // Safety: The caller upholds the same contract
+        let allocated = unsafe { self.store_bytes(string.as_bytes())? };
+
+        // Safety: The bytes were copied from a string, so they're valid UTF-8
+        Ok(unsafe { core::str::from_utf8_unchecked(allocated) })
+    }
+
+    /// Store a slice of arbitrary bytes in the Arena, returning `None` if memory is exhausted
+    ///
+    /// If the arena is `nul_terminated` the stored bytes are followed by a NUL byte,
+    /// which isn't part of the returned slice
+    ///
+    /// # Safety
+    ///
+    /// The reference passed back must be dropped before the arena that created it is
+    ///
+    pub unsafe fn store_bytes(&mut self, slice: &[u8]) -> LassoResult<&'static [u8]> {
+        // If the slice is empty, simply return an empty slice.
+        // This ensures that only slices with lengths greater
         // than zero will be allocated within the arena
-        if string.is_empty() {
-            return Ok("");
+        if slice.is_empty() {
+            return Ok(&[]);
+        }
+
+        let nul_terminated = self.nul_terminated;
+        let allocated = self
+            .allocate_aligned(slice.len() + usize::from(nul_terminated), 1)?
+            .as_ptr();
+
+        // Safety: The allocation has room for the slice and its terminator, and was just
+        //         handed out so nothing else overlaps it
+        unsafe {
+            // Copy the data from the source slice into the bucket's buffer
+            ptr::copy_nonoverlapping(slice.as_ptr(), allocated, slice.len());
+            if nul_terminated {
+                allocated.add(slice.len()).write(0);
+            }
+
+            Ok(slice::from_raw_parts(allocated, slice.len()))
         }
+    }
 
-        let slice = string.as_bytes();
-        let len = slice.len();
+    /// Allocates `len` uninitialized bytes starting at an address that's a multiple of
+    /// `align`, which must be a power of two
+    ///
+    /// The memory lives for as long as the arena does, unless the arena is cleared or
+    /// rewound past it
+    pub fn allocate_aligned(&mut self, len: usize, align: usize) -> LassoResult<NonNull<u8>> {
+        debug_assert!(align.is_power_of_two());
         debug_assert_ne!(len, 0);
 
-        if let Some(bucket) = self
-            .buckets
-            .last_mut()
-            .filter(|bucket| bucket.free_elements() >= len)
-        {
-            // Safety: The bucket found has enough room for the slice
-            let allocated = unsafe { bucket.push_slice(slice) };
+        // New buckets aren't aligned to anything, so they may need up to `align - 1` bytes of
+        // padding before the allocation
+        let padded_len = len.saturating_add(align - 1);
+
+        let push = |bucket: &mut Bucket| {
+            // Safety: The bucket has room for the padding and the allocation
+            unsafe {
+                bucket.take(bucket.padding_for(align));
+                bucket.take(len)
+            }
+        };
+
+        // Spilled strings skip the shared buckets entirely, fixed arenas never spill
+        let spills = !self.fixed && self.spills(padded_len);
+        if let Some(bucket) = self.buckets.last_mut().filter(|bucket| {
+            !spills && bucket.free_elements() >= bucket.padding_for(align).saturating_add(len)
+        }) {
+            // The bucket found has enough room for the slice
+            return Ok(push(bucket));
+        }
 
-            return Ok(allocated);
+        // Fixed arenas can only use the bucket they were created with
+        if self.fixed {
+            return Err(LassoError::memory_limit_reached(
+                len,
+                self.budgeted_usage(),
+                self.max_memory_usage,
+            ));
         }
 
         // SPEED: This portion of the code could be pulled into a cold path
 
-        let next_capacity = self.bucket_capacity.get() * 2;
+        let next_capacity = self.bucket_capacity.get().saturating_mul(2);
 
-        // If the current string's length is greater than the doubled current capacity, allocate a bucket exactly the
-        // size of the large string and push it back in the buckets vector. This ensures that obscenely large strings will
-        // not permanently affect the resource consumption of the interner
-        if len > next_capacity {
+        // If the current string is too large for a shared bucket, allocate a bucket exactly the size of the large
+        // string and push it back in the buckets vector. This ensures that obscenely large strings will not
+        // permanently affect the resource consumption of the interner
+        if spills {
             // Check that we haven't exhausted our memory limit
-            self.allocate_memory(len)?;
+            self.allocate_memory(padded_len)?;
 
-            // Safety: len will always be >= 1
-            let mut bucket = Bucket::with_capacity(unsafe { NonZeroUsize::new_unchecked(len) })?;
+            // Safety: padded_len will always be >= 1
+            let mut bucket = Bucket::with_capacity(
+                unsafe { NonZeroUsize::new_unchecked(padded_len) },
+                &self.allocator,
+            )?;
 
-            // Safety: The new bucket will have exactly enough room for the string
-            let allocated_string = unsafe { bucket.push_slice(slice) };
+            // The new bucket will have exactly enough room for the string
+            let allocated = push(&mut bucket);
             self.buckets
                 .insert(self.buckets.len().saturating_sub(2), bucket);
 
-            Ok(allocated_string)
+            Ok(allocated)
 
         // If trying to use the doubled capacity will surpass our memory limit, just allocate as much as we can
-        } else if self.memory_usage + next_capacity > self.max_memory_usage {
-            let remaining_memory = self.max_memory_usage.saturating_sub(self.memory_usage);
+        } else if self.budgeted_usage().saturating_add(next_capacity) > self.max_memory_usage {
+            let (budgeted_usage, max_memory_usage) = (self.budgeted_usage(), self.max_memory_usage);
+            let remaining_memory = max_memory_usage.saturating_sub(budgeted_usage);
+
+            // The rest of the memory has to be enough to hold the string
+            if remaining_memory < padded_len {
+                return Err(LassoError::memory_limit_reached(
+                    len,
+                    budgeted_usage,
+                    max_memory_usage,
+                ));
+            }
+
             // Check that we haven't exhausted our memory limit
             self.allocate_memory(remaining_memory)?;
 
             // Set the capacity to twice of what it currently is to allow for fewer allocations as more strings are interned
             let mut bucket = Bucket::with_capacity(
-                NonZeroUsize::new(remaining_memory)
-                    .ok_or_else(|| LassoError::new(LassoErrorKind::MemoryLimitReached))?,
+                NonZeroUsize::new(remaining_memory).ok_or_else(|| {
+                    LassoError::memory_limit_reached(len, budgeted_usage, max_memory_usage)
+                })?,
+                &self.allocator,
             )?;
 
-            // Safety: The new bucket will have enough room for the string
-            let allocated_string = unsafe { bucket.push_slice(slice) };
+            // The new bucket will have enough room for the string
+            let allocated = push(&mut bucket);
             self.buckets.push(bucket);
 
-            Ok(allocated_string)
+            Ok(allocated)
 
         // Otherwise just allocate a normal doubled bucket
         } else {
@@ -129,17 +404,59 @@ impl Arena {
             // Set the capacity to twice of what it currently is to allow for fewer allocations as more strings are interned
             // Safety: capacity will always be >= 1
             self.bucket_capacity = unsafe { NonZeroUsize::new_unchecked(next_capacity) };
-            let mut bucket = Bucket::with_capacity(self.bucket_capacity)?;
+            let mut bucket = Bucket::with_capacity(self.bucket_capacity, &self.allocator)?;
 
-            // Safety: The new bucket will have enough room for the string
-            let allocated_string = unsafe { bucket.push_slice(slice) };
+            // The new bucket will have enough room for the string
+            let allocated = push(&mut bucket);
             self.buckets.push(bucket);
 
-            Ok(allocated_string)
+            Ok(allocated)
         }
     }
 }
 
+impl Arena {
+    /// Store an owned string in the Arena, adopting its buffer instead of copying it if
+    /// adoption is enabled and the string is too large to share a bucket
+    ///
+    /// # Safety
+    ///
+    /// The reference passed back must be dropped before the arena that created it is
+    ///
+    pub unsafe fn store_string(&mut self, string: String) -> LassoResult<&'static str> {
+        // Adopted buffers have no room for a terminator and fixed arenas can't hold onto them
+        if !self.adopt_large_strings
+            || self.nul_terminated
+            || self.fixed
+            || !self.spills(string.len())
+        {
+            // Safety: The caller upholds the same contract
+            return unsafe { self.store_str(&string) };
+        }
+
+        // Adopted strings count their whole buffer against the memory limit
+        self.allocate_memory(string.capacity())?;
+
+        let string = OwnedString::new(string);
+        // Safety: The string is kept until the arena is cleared or dropped
+        let adopted = unsafe { string.as_static_str() };
+        self.owned_strings.push(string);
+
+        Ok(adopted)
+    }
+}
+
+/// The state of an [`Arena`] at some point in time, see [`Arena::mark`]
+pub(crate) struct ArenaMark {
+    /// The buckets that existed when the mark was made
+    buckets: Vec<NonNull<u8>>,
+    /// How many bytes of the last bucket were used
+    last_len: usize,
+    bucket_capacity: NonZeroUsize,
+    memory_usage: usize,
+    owned_strings: usize,
+}
+
 impl Default for Arena {
     fn default() -> Self {
         Self::new(
@@ -164,6 +481,13 @@ impl fmt::Debug for Arena {
             .field("bucket_capacity", &self.bucket_capacity)
             .field("memory_usage", &self.memory_usage)
             .field("max_memory_usage", &self.max_memory_usage)
+            .field("reserved_memory", &self.reserved_memory)
+            .field("allocator", &self.allocator)
+            .field("owned_strings", &self.owned_strings.len())
+            .field("adopt_large_strings", &self.adopt_large_strings)
+            .field("spill_threshold", &self.spill_threshold)
+            .field("nul_terminated", &self.nul_terminated)
+            .field("fixed", &self.fixed)
             .finish()
     }
 }
@@ -183,6 +507,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn spill_threshold() {
+        let mut arena = Arena::new(NonZeroUsize::new(1024).unwrap(), usize::MAX).unwrap();
+        arena.spill_threshold = 100;
+
+        // Large strings get their own bucket even though they'd fit in the current one
+        let large = "a".repeat(500);
+        assert_eq!(unsafe { arena.store_str(&large) }, Ok(large.as_str()));
+        assert_eq!(arena.memory_usage(), 1024 + 500);
+        assert_eq!(arena.bucket_capacity.get(), 1024);
+        assert_eq!(arena.buckets.len(), 2);
+
+        // Smaller strings still share the existing bucket
+        assert_eq!(unsafe { arena.store_str("small") }, Ok("small"));
+        assert_eq!(arena.memory_usage(), 1024 + 500);
+        assert_eq!(arena.buckets.len(), 2);
+    }
+
+    #[test]
+    fn saturating_growth() {
+        let mut arena = Arena::new(NonZeroUsize::new(1).unwrap(), usize::MAX).unwrap();
+        arena.bucket_capacity = NonZeroUsize::new(usize::MAX).unwrap();
+
+        // Doubling the bucket capacity would overflow, so the next bucket errors out instead
+        let err = unsafe { arena.store_str("ab") }.unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::MemoryLimitReached);
+        assert_eq!(arena.memory_usage(), 1);
+    }
+
     #[test]
     fn empty_str() {
         let mut arena = Arena::default();
@@ -240,6 +593,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn allocate_aligned() {
+        let mut arena = Arena::new(NonZeroUsize::new(16).unwrap(), usize::MAX).unwrap();
+        arena.nul_terminated = true;
+
+        for align in [1, 2, 8, 64, 4096] {
+            unsafe { arena.store_str("a").unwrap() };
+            let allocated = arena.allocate_aligned(3, align).unwrap();
+            assert_eq!(allocated.as_ptr() as usize % align, 0);
+        }
+    }
+
+    #[test]
+    fn remaining_memory_too_small() {
+        let mut arena = Arena::new(NonZeroUsize::new(16).unwrap(), 30).unwrap();
+
+        unsafe {
+            assert!(arena.store_str("0123456789abcdef").is_ok());
+
+            // Only 14 bytes are left, which isn't enough for the string
+            let err = arena.store_str("0123456789abcdefghij").unwrap_err();
+            assert!(err.kind().is_memory_limit());
+            assert_eq!(arena.memory_usage(), 16);
+
+            assert_eq!(arena.store_str("0123456789"), Ok("0123456789"));
+        }
+    }
+
     #[test]
     fn allocate_more_than_double() {
         let mut arena = Arena::new(NonZeroUsize::new(1).unwrap(), 1000).unwrap();
@@ -0,0 +1,533 @@
+use crate::{hasher::RandomState, Key, LassoResult, Spur, ThreadedRodeo};
+use core::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    future::Future,
+    hash::{BuildHasher, Hash},
+    ops::Deref,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures_core::Stream;
+use std::{sync::Arc, vec::Vec};
+
+/// The number of strings interned by an [`AsyncRodeo`] before it yields back to the executor
+const DEFAULT_BATCH_SIZE: usize = 256;
+
+/// An async-friendly wrapper around a shared [`ThreadedRodeo`]
+///
+/// Interning is still done synchronously, but bulk operations like
+/// [`get_or_intern_batch`](AsyncRodeo::get_or_intern_batch) and
+/// [`intern_stream`](AsyncRodeo::intern_stream) yield back to the executor after every batch of
+/// strings so that large intern workloads don't starve other tasks.
+///
+/// `AsyncRodeo` dereferences to the underlying [`ThreadedRodeo`], so all of its synchronous
+/// methods are available as well. Cloning an `AsyncRodeo` is cheap and clones share the same
+/// interner.
+///
+/// This struct is only available with the `async` feature!
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::AsyncRodeo;
+/// # use futures::executor::block_on;
+///
+/// let rodeo = AsyncRodeo::default();
+///
+/// # block_on(async {
+/// let keys = rodeo.get_or_intern_batch(["a", "b", "c", "a"]).await.unwrap();
+/// assert_eq!(keys[0], keys[3]);
+/// assert_eq!("b", rodeo.resolve(&keys[1]));
+/// # });
+/// ```
+///
+pub struct AsyncRodeo<K = Spur, S = RandomState> {
+    rodeo: Arc<ThreadedRodeo<K, S>>,
+    batch_size: usize,
+}
+
+impl<K> AsyncRodeo<K, RandomState>
+where
+    K: Key + Hash,
+{
+    /// Create a new `AsyncRodeo` wrapping a new [`ThreadedRodeo`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{AsyncRodeo, Spur};
+    ///
+    /// let rodeo: AsyncRodeo<Spur> = AsyncRodeo::new();
+    /// let hello = rodeo.get_or_intern("Hello, ");
+    ///
+    /// assert_eq!("Hello, ", rodeo.resolve(&hello));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new() -> Self {
+        Self::from_rodeo(ThreadedRodeo::new())
+    }
+}
+
+impl<K, S> AsyncRodeo<K, S>
+where
+    K: Key + Hash,
+    S: BuildHasher + Clone,
+{
+    /// Create a new `AsyncRodeo` that takes ownership of the given [`ThreadedRodeo`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{AsyncRodeo, ThreadedRodeo};
+    ///
+    /// let threaded = ThreadedRodeo::default();
+    /// let key = threaded.get_or_intern("Hello, ");
+    ///
+    /// let rodeo = AsyncRodeo::from_rodeo(threaded);
+    /// assert_eq!("Hello, ", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn from_rodeo(rodeo: ThreadedRodeo<K, S>) -> Self {
+        Self::from_arc(Arc::new(rodeo))
+    }
+
+    /// Create a new `AsyncRodeo` that shares the given [`ThreadedRodeo`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{AsyncRodeo, ThreadedRodeo};
+    /// use std::sync::Arc;
+    ///
+    /// let threaded = Arc::new(ThreadedRodeo::default());
+    /// let rodeo = AsyncRodeo::from_arc(Arc::clone(&threaded));
+    ///
+    /// let key = rodeo.get_or_intern("Hello, ");
+    /// assert_eq!("Hello, ", threaded.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn from_arc(rodeo: Arc<ThreadedRodeo<K, S>>) -> Self {
+        Self {
+            rodeo,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Sets the number of strings that are interned before yielding back to the executor
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::AsyncRodeo;
+    ///
+    /// let rodeo = AsyncRodeo::default().with_batch_size(16);
+    /// assert_eq!(rodeo.batch_size(), 16);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size != 0, "the batch size must be non-zero");
+        self.batch_size = batch_size;
+
+        self
+    }
+
+    /// Gets the number of strings that are interned before yielding back to the executor
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::AsyncRodeo;
+    ///
+    /// let rodeo = AsyncRodeo::default();
+    /// assert_eq!(rodeo.batch_size(), 256);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Returns the shared [`ThreadedRodeo`] backing the current `AsyncRodeo`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::AsyncRodeo;
+    ///
+    /// let rodeo = AsyncRodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, ");
+    ///
+    /// let threaded = rodeo.as_arc();
+    /// assert_eq!("Hello, ", threaded.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn as_arc(&self) -> &Arc<ThreadedRodeo<K, S>> {
+        &self.rodeo
+    }
+
+    /// Consumes the current `AsyncRodeo`, returning the shared [`ThreadedRodeo`] backing it
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::AsyncRodeo;
+    ///
+    /// let rodeo = AsyncRodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, ");
+    ///
+    /// let threaded = rodeo.into_arc();
+    /// assert_eq!("Hello, ", threaded.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn into_arc(self) -> Arc<ThreadedRodeo<K, S>> {
+        self.rodeo
+    }
+
+    /// Get the keys for a batch of strings, interning them if they do not yet exist
+    ///
+    /// The keys are returned in the same order as the strings they were created from. Every
+    /// [`batch_size`](AsyncRodeo::batch_size) strings the future yields back to the executor
+    /// so that other tasks get a chance to run. If interning any string fails the error is
+    /// returned and the remaining strings are not interned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::AsyncRodeo;
+    /// # use futures::executor::block_on;
+    ///
+    /// let rodeo = AsyncRodeo::default();
+    ///
+    /// # block_on(async {
+    /// let keys = rodeo.get_or_intern_batch(vec!["a", "b", "c"]).await.unwrap();
+    /// assert_eq!(Some(keys[2]), rodeo.get("c"));
+    /// # });
+    /// ```
+    ///
+    pub async fn get_or_intern_batch<I, T>(&self, strings: I) -> LassoResult<Vec<K>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let strings = strings.into_iter();
+        let mut keys = Vec::with_capacity(strings.size_hint().0);
+
+        for (idx, string) in strings.enumerate() {
+            if idx != 0 && idx % self.batch_size == 0 {
+                YieldNow::default().await;
+            }
+
+            keys.push(self.rodeo.try_get_or_intern(string)?);
+        }
+
+        Ok(keys)
+    }
+
+    /// Interns every string produced by the given stream, producing a stream of their keys
+    ///
+    /// The returned stream yields back to the executor every
+    /// [`batch_size`](AsyncRodeo::batch_size) strings even if the underlying stream is always
+    /// ready, so draining a fast stream won't monopolize the current worker.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::AsyncRodeo;
+    /// use futures::{executor::block_on, stream::{self, TryStreamExt}};
+    ///
+    /// let rodeo = AsyncRodeo::default();
+    /// let strings = stream::iter(vec![String::from("a"), String::from("b")]);
+    ///
+    /// # block_on(async {
+    /// let keys: Vec<_> = rodeo.intern_stream(strings).try_collect().await.unwrap();
+    /// assert_eq!("a", rodeo.resolve(&keys[0]));
+    /// assert_eq!("b", rodeo.resolve(&keys[1]));
+    /// # });
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn intern_stream<St>(&self, stream: St) -> InternStream<St, K, S>
+    where
+        St: Stream,
+        St::Item: AsRef<str>,
+    {
+        InternStream {
+            stream,
+            rodeo: Arc::clone(&self.rodeo),
+            batch_size: self.batch_size,
+            interned: 0,
+        }
+    }
+}
+
+impl<K, S> Clone for AsyncRodeo<K, S> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self {
+            rodeo: Arc::clone(&self.rodeo),
+            batch_size: self.batch_size,
+        }
+    }
+}
+
+impl Default for AsyncRodeo<Spur, RandomState> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, S> Deref for AsyncRodeo<K, S> {
+    type Target = ThreadedRodeo<K, S>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn deref(&self) -> &Self::Target {
+        &self.rodeo
+    }
+}
+
+impl<K, S> From<ThreadedRodeo<K, S>> for AsyncRodeo<K, S>
+where
+    K: Key + Hash,
+    S: BuildHasher + Clone,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from(rodeo: ThreadedRodeo<K, S>) -> Self {
+        Self::from_rodeo(rodeo)
+    }
+}
+
+impl<K, S> Debug for AsyncRodeo<K, S>
+where
+    K: Key + Hash + Debug,
+    S: BuildHasher + Clone,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("AsyncRodeo")
+            .field("rodeo", &self.rodeo)
+            .field("batch_size", &self.batch_size)
+            .finish()
+    }
+}
+
+/// A stream that interns the strings of another stream, created by
+/// [`AsyncRodeo::intern_stream`]
+#[must_use = "streams do nothing unless polled"]
+pub struct InternStream<St, K = Spur, S = RandomState> {
+    stream: St,
+    rodeo: Arc<ThreadedRodeo<K, S>>,
+    batch_size: usize,
+    interned: usize,
+}
+
+impl<St, K, S> Stream for InternStream<St, K, S>
+where
+    St: Stream,
+    St::Item: AsRef<str>,
+    K: Key + Hash,
+    S: BuildHasher + Clone,
+{
+    type Item = LassoResult<K>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Safety: The inner stream is never moved out of `self` and `InternStream` has no drop
+        //         impl, so projecting the pin onto it is sound. None of the other fields are
+        //         structurally pinned
+        let this = unsafe { self.get_unchecked_mut() };
+
+        if this.interned == this.batch_size {
+            this.interned = 0;
+            cx.waker().wake_by_ref();
+
+            return Poll::Pending;
+        }
+
+        // Safety: See above
+        let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+        match stream.poll_next(cx) {
+            Poll::Ready(Some(string)) => {
+                this.interned += 1;
+                Poll::Ready(Some(this.rodeo.try_get_or_intern(string)))
+            }
+
+            Poll::Ready(None) => Poll::Ready(None),
+
+            Poll::Pending => {
+                // The inner stream is yielding for us, so we can restart the batch
+                this.interned = 0;
+                Poll::Pending
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<St, K, S> Debug for InternStream<St, K, S>
+where
+    St: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("InternStream")
+            .field("stream", &self.stream)
+            .field("batch_size", &self.batch_size)
+            .field("interned", &self.interned)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A future that yields back to the executor once before completing
+#[derive(Debug, Default)]
+struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.yielded {
+            Poll::Ready(())
+        } else {
+            self.yielded = true;
+            cx.waker().wake_by_ref();
+
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AsyncRodeo, YieldNow};
+    use crate::{Capacity, Key, LassoErrorKind, MemoryLimits, MicroSpur, Spur, ThreadedRodeo};
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+    use futures::{
+        executor::block_on,
+        stream::{self, Stream, StreamExt},
+        task::noop_waker,
+    };
+    use std::{num::NonZeroUsize, string::String, sync::Arc, vec::Vec};
+
+    #[test]
+    fn batch() {
+        let rodeo: AsyncRodeo = AsyncRodeo::new().with_batch_size(2);
+
+        let keys = block_on(rodeo.get_or_intern_batch(["a", "b", "c", "a", "d"])).unwrap();
+        let expected: Vec<_> = [0, 1, 2, 0, 3]
+            .iter()
+            .map(|&i| Spur::try_from_usize(i).unwrap())
+            .collect();
+        assert_eq!(keys, expected);
+
+        assert_eq!(rodeo.len(), 4);
+        assert_eq!("d", rodeo.resolve(&keys[4]));
+    }
+
+    #[test]
+    fn batch_yields() {
+        let rodeo: AsyncRodeo = AsyncRodeo::new().with_batch_size(2);
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut future = Box::pin(rodeo.get_or_intern_batch(["a", "b", "c", "d", "e"]));
+        assert!(future.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(rodeo.len(), 2);
+        assert!(future.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(rodeo.len(), 4);
+
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(keys) => assert_eq!(keys.unwrap().len(), 5),
+            Poll::Pending => panic!("the batch should have finished"),
+        }
+    }
+
+    #[test]
+    fn batch_error() {
+        let rodeo: AsyncRodeo<MicroSpur> = AsyncRodeo::new();
+
+        let strings: Vec<String> = (0..=u8::MAX as usize).map(|i| i.to_string()).collect();
+        let err = block_on(rodeo.get_or_intern_batch(&strings)).unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::KeySpaceExhaustion);
+
+        let rodeo = AsyncRodeo::from_rodeo(ThreadedRodeo::<Spur>::with_capacity_and_memory_limits(
+            Capacity::for_bytes(NonZeroUsize::new(1).unwrap()),
+            MemoryLimits::for_memory_usage(1),
+        ));
+        let err = block_on(rodeo.get_or_intern_batch(["a", "bbbb"])).unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::MemoryLimitReached);
+        assert!(rodeo.contains("a"));
+    }
+
+    #[test]
+    fn stream() {
+        let rodeo: AsyncRodeo = AsyncRodeo::new();
+        let strings = stream::iter(["a", "b", "a"].iter().map(|&s| String::from(s)));
+
+        let keys: Vec<_> = block_on(rodeo.intern_stream(strings).collect());
+        let keys: Vec<_> = keys.into_iter().map(Result::unwrap).collect();
+        assert_eq!(keys[0], keys[2]);
+        assert_eq!("a", rodeo.resolve(&keys[0]));
+        assert_eq!("b", rodeo.resolve(&keys[1]));
+    }
+
+    #[test]
+    fn stream_yields() {
+        let rodeo: AsyncRodeo = AsyncRodeo::new().with_batch_size(2);
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut stream = rodeo.intern_stream(stream::iter(["a", "b", "c"]));
+        let mut stream = Pin::new(&mut stream);
+        assert!(matches!(stream.as_mut().poll_next(&mut cx), Poll::Ready(Some(Ok(_)))));
+        assert!(matches!(stream.as_mut().poll_next(&mut cx), Poll::Ready(Some(Ok(_)))));
+        assert!(stream.as_mut().poll_next(&mut cx).is_pending());
+        assert!(matches!(stream.as_mut().poll_next(&mut cx), Poll::Ready(Some(Ok(_)))));
+        assert!(matches!(stream.as_mut().poll_next(&mut cx), Poll::Ready(None)));
+    }
+
+    #[test]
+    fn shared() {
+        let threaded = Arc::new(ThreadedRodeo::default());
+        let rodeo: AsyncRodeo = AsyncRodeo::from_arc(Arc::clone(&threaded));
+        let clone = rodeo.clone();
+
+        let key = block_on(clone.get_or_intern_batch(["shared"])).unwrap()[0];
+        assert_eq!(Some(key), threaded.get("shared"));
+        assert_eq!(Some(key), rodeo.get("shared"));
+        assert!(Arc::ptr_eq(rodeo.as_arc(), &threaded));
+    }
+
+    #[test]
+    fn yield_now() {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut future = YieldNow::default();
+        assert!(Pin::new(&mut future).poll(&mut cx).is_pending());
+        assert!(Pin::new(&mut future).poll(&mut cx).is_ready());
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_batch_size() {
+        let _ = AsyncRodeo::<Spur>::new().with_batch_size(0);
+    }
+}
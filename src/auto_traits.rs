@@ -0,0 +1,195 @@
+//! Checks that the interners are only `Send` and `Sync` when it's sound for them to be
+//!
+//! The negative cases are `compile_fail` doctests, since there's no other way to assert that
+//! a trait isn't implemented
+
+/// Keys made by one thread are read by every other thread sharing a `ThreadedRodeo`, so keys
+/// that are `Sync` but not `Send` can't be shared
+///
+/// ```rust,compile_fail
+/// use lasso::{Key, ThreadedRodeo};
+/// use std::{marker::PhantomData, sync::MutexGuard};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// struct SyncKey(u32, PhantomData<MutexGuard<'static, ()>>);
+///
+/// unsafe impl Key for SyncKey {
+///     fn into_usize(self) -> usize {
+///         self.0 as usize
+///     }
+///
+///     fn try_from_usize(int: usize) -> Option<Self> {
+///         u32::try_from(int).ok().map(|int| Self(int, PhantomData))
+///     }
+/// }
+///
+/// fn sync<T: Sync>() {}
+/// sync::<ThreadedRodeo<SyncKey>>();
+/// ```
+///
+/// Keys that are `Send` but not `Sync` can't be shared either
+///
+/// ```rust,compile_fail
+/// use lasso::{Key, ThreadedRodeo};
+/// use std::{cell::Cell, marker::PhantomData};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// struct SendKey(u32, PhantomData<Cell<()>>);
+///
+/// unsafe impl Key for SendKey {
+///     fn into_usize(self) -> usize {
+///         self.0 as usize
+///     }
+///
+///     fn try_from_usize(int: usize) -> Option<Self> {
+///         u32::try_from(int).ok().map(|int| Self(int, PhantomData))
+///     }
+/// }
+///
+/// fn sync<T: Sync>() {}
+/// sync::<ThreadedRodeo<SendKey>>();
+/// ```
+#[cfg(all(doctest, feature = "multi-threaded", not(feature = "no-std")))]
+pub struct ThreadedRodeoKeys;
+
+/// A `RodeoReader`'s map may be built by any thread that shares it, so it can't be shared
+/// with keys that are `Sync` but not `Send`
+///
+/// ```rust,compile_fail
+/// use lasso::{Key, RodeoReader};
+/// use std::{marker::PhantomData, sync::MutexGuard};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// struct SyncKey(u32, PhantomData<MutexGuard<'static, ()>>);
+///
+/// unsafe impl Key for SyncKey {
+///     fn into_usize(self) -> usize {
+///         self.0 as usize
+///     }
+///
+///     fn try_from_usize(int: usize) -> Option<Self> {
+///         u32::try_from(int).ok().map(|int| Self(int, PhantomData))
+///     }
+/// }
+///
+/// fn sync<T: Sync>() {}
+/// sync::<RodeoReader<SyncKey>>();
+/// ```
+#[cfg(doctest)]
+pub struct RodeoReaderKeys;
+
+/// Interners can't be sent to other threads along with hashers that can't be
+///
+/// ```rust,compile_fail
+/// use lasso::{Rodeo, Spur};
+/// use std::{
+///     collections::hash_map::{DefaultHasher, RandomState},
+///     hash::BuildHasher,
+///     rc::Rc,
+/// };
+///
+/// #[derive(Default, Clone)]
+/// struct RcState(Rc<RandomState>);
+///
+/// impl BuildHasher for RcState {
+///     type Hasher = DefaultHasher;
+///
+///     fn build_hasher(&self) -> DefaultHasher {
+///         self.0.build_hasher()
+///     }
+/// }
+///
+/// fn send<T: Send>() {}
+/// send::<Rodeo<Spur, RcState>>();
+/// ```
+///
+/// And resolvers can't be sent with keys that can't be
+///
+/// ```rust,compile_fail
+/// use lasso::{Key, RodeoResolver};
+/// use std::{marker::PhantomData, sync::MutexGuard};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// struct SyncKey(u32, PhantomData<MutexGuard<'static, ()>>);
+///
+/// unsafe impl Key for SyncKey {
+///     fn into_usize(self) -> usize {
+///         self.0 as usize
+///     }
+///
+///     fn try_from_usize(int: usize) -> Option<Self> {
+///         u32::try_from(int).ok().map(|int| Self(int, PhantomData))
+///     }
+/// }
+///
+/// fn send<T: Send>() {}
+/// send::<RodeoResolver<SyncKey>>();
+/// ```
+#[cfg(doctest)]
+pub struct SendableParts;
+
+/// `RcRodeo`s and their handles share unsynchronized reference counts
+///
+/// ```rust,compile_fail
+/// use lasso::RcRodeo;
+///
+/// fn send<T: Send>() {}
+/// send::<RcRodeo>();
+/// ```
+///
+/// ```rust,compile_fail
+/// use lasso::RcKey;
+///
+/// fn send<T: Send>() {}
+/// send::<RcKey>();
+/// ```
+#[cfg(doctest)]
+pub struct RcRodeoThreads;
+
+#[cfg(test)]
+mod tests {
+    use crate::{Key, Rodeo, RodeoReader, RodeoResolver, Spur};
+    use core::{cell::Cell, marker::PhantomData};
+
+    fn send<T: Send>() {}
+    fn sync<T: Sync>() {}
+
+    #[test]
+    fn default_interners() {
+        send::<Rodeo>();
+        sync::<Rodeo>();
+        send::<RodeoReader>();
+        sync::<RodeoReader>();
+        send::<RodeoResolver>();
+        sync::<RodeoResolver>();
+        send::<crate::StaticResolver>();
+        sync::<crate::StaticResolver>();
+
+        #[cfg(all(feature = "multi-threaded", not(feature = "no-std")))]
+        {
+            send::<crate::ThreadedRodeo>();
+            sync::<crate::ThreadedRodeo>();
+        }
+    }
+
+    #[test]
+    fn sendable_keys() {
+        // Keys that are `Send` but not `Sync` are fine for interners that aren't shared
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        struct SendKey(Spur, PhantomData<Cell<()>>);
+
+        unsafe impl Key for SendKey {
+            fn into_usize(self) -> usize {
+                self.0.into_usize()
+            }
+
+            fn try_from_usize(int: usize) -> Option<Self> {
+                Spur::try_from_usize(int).map(|key| Self(key, PhantomData))
+            }
+        }
+
+        send::<Rodeo<SendKey>>();
+        send::<RodeoReader<SendKey>>();
+        send::<RodeoResolver<SendKey>>();
+    }
+}
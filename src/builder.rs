@@ -0,0 +1,916 @@
+use crate::{
+    arenas::BucketAllocator, hasher::RandomState, Capacity, DynamicState, HasherKind, Key,
+    LassoResult, MemoryLimits, Rodeo,
+};
+use core::hash::BuildHasher;
+
+#[cfg(all(feature = "multi-threaded", not(feature = "no-std")))]
+use crate::ThreadedRodeo;
+#[cfg(all(feature = "multi-threaded", not(feature = "no-std")))]
+use core::hash::Hash;
+
+/// A builder for configuring and creating interners
+///
+/// Collects all of the options that an interner can be created with, including ones
+/// that aren't exposed through the `with_*` constructors like a custom allocator
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{Capacity, MemoryLimits, Rodeo, RodeoBuilder};
+///
+/// let mut rodeo: Rodeo = RodeoBuilder::new()
+///     .capacity(Capacity::for_strings(10))
+///     .memory_limits(MemoryLimits::for_memory_usage(4096))
+///     .build();
+///
+/// let key = rodeo.get_or_intern("Hello, world!");
+/// assert_eq!("Hello, world!", rodeo.resolve(&key));
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct RodeoBuilder<S = RandomState> {
+    capacity: Capacity,
+    memory_limits: MemoryLimits,
+    hash_builder: S,
+    allocator: BucketAllocator,
+    intern_empty: bool,
+    adopt_large_strings: bool,
+    spill_threshold: usize,
+    nul_terminated: bool,
+    shrink_on_clear: bool,
+    mix_shard_hashes: bool,
+    #[cfg(feature = "timestamps")]
+    clock: fn() -> u64,
+}
+
+impl RodeoBuilder<RandomState> {
+    /// Create a new builder with the default capacity, memory limits, hasher and allocator
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, RodeoBuilder};
+    ///
+    /// let rodeo: Rodeo = RodeoBuilder::new().build();
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new() -> Self {
+        Self {
+            capacity: Capacity::default(),
+            memory_limits: MemoryLimits::default(),
+            hash_builder: RandomState::new(),
+            allocator: BucketAllocator::Global,
+            intern_empty: false,
+            adopt_large_strings: false,
+            spill_threshold: usize::MAX,
+            nul_terminated: false,
+            shrink_on_clear: false,
+            mix_shard_hashes: false,
+            #[cfg(feature = "timestamps")]
+            clock: crate::monotonic_ticks,
+        }
+    }
+}
+
+impl<S> RodeoBuilder<S> {
+    /// Set the initial capacity of the interner
+    ///
+    /// See [`Capacity`] for more details
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Capacity, Rodeo, RodeoBuilder};
+    ///
+    /// let rodeo: Rodeo = RodeoBuilder::new()
+    ///     .capacity(Capacity::for_strings(100))
+    ///     .build();
+    /// ```
+    ///
+    /// [`Capacity`]: crate::Capacity
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn capacity(mut self, capacity: Capacity) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Set the memory limits of the interner
+    ///
+    /// See [`MemoryLimits`] for more details
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{MemoryLimits, Rodeo, RodeoBuilder};
+    ///
+    /// let rodeo: Rodeo = RodeoBuilder::new()
+    ///     .memory_limits(MemoryLimits::for_memory_usage(4096))
+    ///     .build();
+    /// assert_eq!(rodeo.max_memory_usage(), 4096);
+    /// ```
+    ///
+    /// [`MemoryLimits`]: crate::MemoryLimits
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn memory_limits(mut self, memory_limits: MemoryLimits) -> Self {
+        self.memory_limits = memory_limits;
+        self
+    }
+
+    /// Set the kind of hasher the interner will use for its internal hashmap, chosen at
+    /// runtime instead of through the hasher's type
+    ///
+    /// See [`HasherKind`] for the available hashers and [`bench_hashers`] for picking one
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{DynamicState, HasherKind, Rodeo, RodeoBuilder, Spur};
+    ///
+    /// let rodeo: Rodeo<Spur, DynamicState> = RodeoBuilder::new()
+    ///     .hasher_kind(HasherKind::default())
+    ///     .build();
+    /// assert_eq!(rodeo.hasher_kind(), HasherKind::default());
+    /// ```
+    ///
+    /// [`HasherKind`]: crate::HasherKind
+    /// [`bench_hashers`]: crate::bench_hashers
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn hasher_kind(self, kind: HasherKind) -> RodeoBuilder<DynamicState> {
+        self.hasher(DynamicState::new(kind))
+    }
+
+    /// Set the hasher the interner will use for its internal hashmap
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, RodeoBuilder, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: Rodeo<Spur, RandomState> = RodeoBuilder::new()
+    ///     .hasher(RandomState::new())
+    ///     .build();
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn hasher<H>(self, hash_builder: H) -> RodeoBuilder<H> {
+        RodeoBuilder {
+            capacity: self.capacity,
+            memory_limits: self.memory_limits,
+            hash_builder,
+            allocator: self.allocator,
+            intern_empty: self.intern_empty,
+            adopt_large_strings: self.adopt_large_strings,
+            spill_threshold: self.spill_threshold,
+            nul_terminated: self.nul_terminated,
+            shrink_on_clear: self.shrink_on_clear,
+            mix_shard_hashes: self.mix_shard_hashes,
+            #[cfg(feature = "timestamps")]
+            clock: self.clock,
+        }
+    }
+
+    /// Set the length above which strings are stored in an allocation of their own instead
+    /// of sharing one of the arena's buckets
+    ///
+    /// By default only strings too large for the arena's next bucket get their own allocation,
+    /// so a few very large strings can still fill up most of a bucket. Spilled strings never
+    /// affect the size of future buckets, which keeps bucket sizes small and predictable
+    /// when the interner sees occasional very large strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, RodeoBuilder};
+    ///
+    /// let mut rodeo: Rodeo = RodeoBuilder::new().spill_threshold(1024).build();
+    ///
+    /// let key = rodeo.get_or_intern("a".repeat(2048));
+    /// assert_eq!(rodeo.resolve(&key).len(), 2048);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn spill_threshold(mut self, spill_threshold: usize) -> Self {
+        self.spill_threshold = spill_threshold;
+        self
+    }
+
+    /// Take ownership of large owned strings instead of copying them into the arena
+    ///
+    /// Strings passed to `get_or_intern_owned()` that are too large to share an arena bucket
+    /// keep their original buffer, which is freed along with the interner. This saves copying
+    /// very large strings at the cost of keeping any excess capacity they have around, which
+    /// also counts towards the interner's memory limit
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, RodeoBuilder};
+    ///
+    /// let mut rodeo: Rodeo = RodeoBuilder::new().adopt_large_strings(true).build();
+    ///
+    /// let large = "a".repeat(64 * 1024);
+    /// let ptr = large.as_ptr();
+    ///
+    /// let key = rodeo.get_or_intern_owned(large);
+    /// assert_eq!(rodeo.resolve(&key).as_ptr(), ptr);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn adopt_large_strings(mut self, adopt_large_strings: bool) -> Self {
+        self.adopt_large_strings = adopt_large_strings;
+        self
+    }
+
+    /// Follow every interned string with a NUL byte so it can be resolved as a C string
+    ///
+    /// Strings can then be resolved with [`Rodeo::resolve_cstr()`] and
+    /// [`Rodeo::resolve_cstr_ptr()`] without copying them, at the cost of one extra byte per
+    /// string. Static strings are copied into the arena instead of being stored as-is and large
+    /// strings are never adopted, since neither has room for the terminator. This only affects
+    /// [`Rodeo`]s
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, RodeoBuilder};
+    ///
+    /// let mut rodeo: Rodeo = RodeoBuilder::new().nul_terminated(true).build();
+    ///
+    /// let key = rodeo.get_or_intern_static("Hello, world!");
+    /// assert_eq!(rodeo.resolve_cstr(&key).unwrap().to_str(), Ok("Hello, world!"));
+    /// ```
+    ///
+    /// [`Rodeo`]: crate::Rodeo
+    /// [`Rodeo::resolve_cstr()`]: crate::Rodeo::resolve_cstr
+    /// [`Rodeo::resolve_cstr_ptr()`]: crate::Rodeo::resolve_cstr_ptr
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn nul_terminated(mut self, nul_terminated: bool) -> Self {
+        self.nul_terminated = nul_terminated;
+        self
+    }
+
+    /// Shrink the interner every time it's cleared, giving back the memory it grew to hold
+    ///
+    /// By default clearing an interner keeps its maps' capacity and arena buckets around to
+    /// be reused, which is fastest when it fills up to about the same size again. Programs
+    /// that intern in bursts can shrink on clear instead so that they don't hold onto the
+    /// memory of their largest burst forever. See [`Rodeo::shrink_to_fit()`] and
+    /// [`ThreadedRodeo::shrink_to_fit()`] for what's freed
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, RodeoBuilder};
+    ///
+    /// let mut rodeo: Rodeo = RodeoBuilder::new().shrink_on_clear(true).build();
+    /// for i in 0..1000 {
+    ///     rodeo.get_or_intern(i.to_string());
+    /// }
+    ///
+    /// rodeo.clear();
+    /// assert_eq!(rodeo.current_memory_usage(), 0);
+    /// ```
+    ///
+    /// [`Rodeo::shrink_to_fit()`]: crate::Rodeo::shrink_to_fit
+    /// [`ThreadedRodeo::shrink_to_fit()`]: crate::ThreadedRodeo::shrink_to_fit
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn shrink_on_clear(mut self, shrink_on_clear: bool) -> Self {
+        self.shrink_on_clear = shrink_on_clear;
+        self
+    }
+
+    /// Mix hashes before using them to pick a shard of a [`ThreadedRodeo`]
+    ///
+    /// Threaded interners pick a string's shard with a few bits of its hash, which a weak
+    /// hasher can leave mostly the same and crowd most strings into a few shards. Mixing
+    /// spreads them out again at the cost of a few instructions per lookup. Only affects
+    /// [`RodeoBuilder::build_threaded`], see [`ThreadedRodeo::rebalance_shards`] for turning
+    /// it on later
+    ///
+    /// [`ThreadedRodeo`]: crate::ThreadedRodeo
+    /// [`RodeoBuilder::build_threaded`]: crate::RodeoBuilder::build_threaded
+    /// [`ThreadedRodeo::rebalance_shards`]: crate::ThreadedRodeo::rebalance_shards
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn mix_shard_hashes(mut self, mix_shard_hashes: bool) -> Self {
+        self.mix_shard_hashes = mix_shard_hashes;
+        self
+    }
+
+    /// Set the clock the intern time of every string is read from
+    ///
+    /// The clock is called once for every new string and can return anything that orders
+    /// the strings the way the program needs, like a generation counter or the time since
+    /// the Unix epoch. Defaults to [`monotonic_ticks`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, RodeoBuilder};
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    ///
+    /// static GENERATION: AtomicU64 = AtomicU64::new(0);
+    ///
+    /// let mut rodeo: Rodeo = RodeoBuilder::new()
+    ///     .clock(|| GENERATION.load(Ordering::Relaxed))
+    ///     .build();
+    ///
+    /// let first = rodeo.get_or_intern("first");
+    /// GENERATION.store(1, Ordering::Relaxed);
+    /// let second = rodeo.get_or_intern("second");
+    ///
+    /// assert_eq!(rodeo.interned_at(&first), Some(0));
+    /// assert_eq!(rodeo.interned_at(&second), Some(1));
+    /// assert_eq!(rodeo.iter_older_than(1).count(), 1);
+    /// ```
+    ///
+    /// [`monotonic_ticks`]: crate::monotonic_ticks
+    #[cfg(feature = "timestamps")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn clock(mut self, clock: fn() -> u64) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Intern the empty string when the interner is created
+    ///
+    /// The empty string will always be given the first key, `K::try_from_usize(0)`, so
+    /// code can compare against that key instead of resolving strings to check for emptiness.
+    /// Interners created without this still give the empty string a key when it's first
+    /// interned, which can be looked up with `empty_key()`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, Rodeo, RodeoBuilder, Spur};
+    ///
+    /// let mut rodeo: Rodeo = RodeoBuilder::new().intern_empty(true).build();
+    /// let empty = Spur::try_from_usize(0).unwrap();
+    ///
+    /// assert_eq!(rodeo.get_or_intern(""), empty);
+    /// assert_eq!(rodeo.empty_key(), Some(empty));
+    /// assert_eq!(rodeo.resolve(&empty), "");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn intern_empty(mut self, intern_empty: bool) -> Self {
+        self.intern_empty = intern_empty;
+        self
+    }
+
+    /// Set the allocator that the interner will allocate its string storage from
+    ///
+    /// Only the memory used to store interned strings comes from `allocator`, the interner's
+    /// internal maps and vectors still use the global allocator
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allocator_api2::alloc::Global;
+    /// use lasso::{Rodeo, RodeoBuilder};
+    ///
+    /// let mut rodeo: Rodeo = RodeoBuilder::new().allocator(Global).build();
+    ///
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    /// assert_eq!("Hello, world!", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg(feature = "allocator-api2")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn allocator<A>(mut self, allocator: A) -> Self
+    where
+        A: allocator_api2::alloc::Allocator + Send + Sync + 'static,
+    {
+        self.allocator = BucketAllocator::Custom(alloc::sync::Arc::new(allocator));
+        self
+    }
+
+    /// Set whether the interner should try to back its string storage with huge pages
+    ///
+    /// Large interners spend a lot of time on TLB misses when resolving keys, using huge
+    /// pages for string storage can reduce those dramatically. Only buckets that are
+    /// at least as large as a huge page (2 MiB) are mapped this way, so this works best with
+    /// a large initial [`Capacity`]. Explicit huge pages are tried first and transparent huge
+    /// pages are used when none are available.
+    ///
+    /// Enabling huge pages replaces any custom allocator that was previously set. On platforms
+    /// other than Linux this does nothing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Capacity, Rodeo, RodeoBuilder};
+    /// use core::num::NonZeroUsize;
+    ///
+    /// let mut rodeo: Rodeo = RodeoBuilder::new()
+    ///     .capacity(Capacity::for_bytes(NonZeroUsize::new(4 * 1024 * 1024).unwrap()))
+    ///     .hugepages(true)
+    ///     .build();
+    ///
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    /// assert_eq!("Hello, world!", rodeo.resolve(&key));
+    /// ```
+    ///
+    /// [`Capacity`]: crate::Capacity
+    #[cfg(feature = "hugepages")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn hugepages(mut self, enabled: bool) -> Self {
+        #[cfg(target_os = "linux")]
+        if enabled {
+            self.allocator = BucketAllocator::HugePages;
+        } else if matches!(self.allocator, BucketAllocator::HugePages) {
+            self.allocator = BucketAllocator::Global;
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        let _ = enabled;
+
+        self
+    }
+}
+
+impl<S> RodeoBuilder<S>
+where
+    S: BuildHasher,
+{
+    /// Create a [`Rodeo`] from the current configuration
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interner's initial memory could not be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, RodeoBuilder, Spur};
+    ///
+    /// let rodeo = RodeoBuilder::new().build::<Spur>();
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    /// [`Rodeo`]: crate::Rodeo
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn build<K>(self) -> Rodeo<K, S>
+    where
+        K: Key,
+    {
+        self.try_build()
+            .expect("failed to allocate memory for interner")
+    }
+
+    /// Attempt to create a [`Rodeo`] from the current configuration
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, RodeoBuilder, Spur};
+    ///
+    /// let rodeo = RodeoBuilder::new().try_build::<Spur>().unwrap();
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    /// [`Rodeo`]: crate::Rodeo
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_build<K>(self) -> LassoResult<Rodeo<K, S>>
+    where
+        K: Key,
+    {
+        let mut rodeo = Rodeo::try_with_bucket_allocator(
+            self.capacity,
+            self.memory_limits,
+            self.hash_builder,
+            self.allocator,
+        )?;
+        rodeo.set_adopt_large_strings(self.adopt_large_strings);
+        rodeo.set_spill_threshold(self.spill_threshold);
+        rodeo.set_nul_terminated(self.nul_terminated);
+        rodeo.set_shrink_on_clear(self.shrink_on_clear);
+        #[cfg(feature = "timestamps")]
+        rodeo.set_clock(self.clock);
+
+        if self.intern_empty {
+            rodeo.try_get_or_intern_static("")?;
+        }
+
+        Ok(rodeo)
+    }
+}
+
+#[cfg(all(feature = "multi-threaded", not(feature = "no-std")))]
+impl<S> RodeoBuilder<S>
+where
+    S: BuildHasher + Clone,
+{
+    /// Create a [`ThreadedRodeo`] from the current configuration
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interner's initial memory could not be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{RodeoBuilder, Spur, ThreadedRodeo};
+    ///
+    /// let rodeo = RodeoBuilder::new().build_threaded::<Spur>();
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    /// [`ThreadedRodeo`]: crate::ThreadedRodeo
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn build_threaded<K>(self) -> ThreadedRodeo<K, S>
+    where
+        K: Key + Hash,
+    {
+        self.try_build_threaded()
+            .expect("failed to allocate memory for interner")
+    }
+
+    /// Attempt to create a [`ThreadedRodeo`] from the current configuration
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{RodeoBuilder, Spur, ThreadedRodeo};
+    ///
+    /// let rodeo = RodeoBuilder::new().try_build_threaded::<Spur>().unwrap();
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    /// [`ThreadedRodeo`]: crate::ThreadedRodeo
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_build_threaded<K>(self) -> LassoResult<ThreadedRodeo<K, S>>
+    where
+        K: Key + Hash,
+    {
+        let mut rodeo = ThreadedRodeo::try_with_bucket_allocator(
+            self.capacity,
+            self.memory_limits,
+            self.hash_builder,
+            self.allocator,
+        )?;
+        rodeo.set_adopt_large_strings(self.adopt_large_strings);
+        rodeo.set_mix_shard_hashes(self.mix_shard_hashes);
+        rodeo.set_spill_threshold(self.spill_threshold);
+        rodeo.set_shrink_on_clear(self.shrink_on_clear);
+        #[cfg(feature = "timestamps")]
+        rodeo.set_clock(self.clock);
+
+        if self.intern_empty {
+            rodeo.try_get_or_intern_static("")?;
+        }
+
+        Ok(rodeo)
+    }
+}
+
+impl Default for RodeoBuilder<RandomState> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Capacity, Key, MemoryLimits, Rodeo, RodeoBuilder, Spur};
+    #[cfg(feature = "no-std")]
+    use alloc::string::String;
+
+    #[test]
+    fn build() {
+        let mut rodeo: Rodeo = RodeoBuilder::new()
+            .capacity(Capacity::for_strings(10))
+            .memory_limits(MemoryLimits::for_memory_usage(4096))
+            .build();
+
+        let key = rodeo.get_or_intern("A");
+        assert_eq!(rodeo.resolve(&key), "A");
+        assert_eq!(rodeo.max_memory_usage(), 4096);
+    }
+
+    #[test]
+    fn try_build_respects_limits() {
+        let rodeo = RodeoBuilder::new()
+            .capacity(Capacity::for_bytes(
+                core::num::NonZeroUsize::new(10).unwrap(),
+            ))
+            .memory_limits(MemoryLimits::for_memory_usage(10))
+            .try_build::<Spur>();
+        assert!(rodeo.is_ok());
+    }
+
+    #[test]
+    #[cfg(all(feature = "multi-threaded", not(feature = "no-std")))]
+    fn build_threaded() {
+        let rodeo = RodeoBuilder::new()
+            .memory_limits(MemoryLimits::for_memory_usage(4096))
+            .build_threaded::<Spur>();
+
+        let key = rodeo.get_or_intern("A");
+        assert_eq!(rodeo.resolve(&key), "A");
+        assert_eq!(rodeo.max_memory_usage(), 4096);
+    }
+
+    #[test]
+    fn nul_terminated() {
+        let mut rodeo: Rodeo = RodeoBuilder::new()
+            .nul_terminated(true)
+            .adopt_large_strings(true)
+            .build();
+
+        let a = rodeo.get_or_intern("A");
+        let b = rodeo.get_or_intern_static("B");
+        let empty = rodeo.get_or_intern("");
+        let large = rodeo.get_or_intern_owned("c".repeat(64 * 1024));
+        let nul = rodeo.get_or_intern("D\0E");
+
+        assert_eq!(rodeo.resolve_cstr(&a).unwrap().to_bytes(), b"A");
+        assert_eq!(rodeo.resolve_cstr(&b).unwrap().to_bytes(), b"B");
+        assert_eq!(rodeo.resolve_cstr(&empty).unwrap().to_bytes(), b"");
+        assert_eq!(
+            rodeo.resolve_cstr(&large).unwrap().to_bytes().len(),
+            64 * 1024
+        );
+        assert_eq!(rodeo.resolve_cstr(&nul), None);
+        assert!(rodeo.resolve_cstr_ptr(&nul).is_null());
+        assert_eq!(rodeo.resolve(&nul), "D\0E");
+
+        // Clones keep their terminators
+        let cloned = rodeo.clone();
+        assert_eq!(cloned.resolve_cstr(&a).unwrap().to_bytes(), b"A");
+        assert_eq!(
+            cloned.resolve_cstr_ptr(&b),
+            cloned.resolve(&b).as_ptr().cast()
+        );
+
+        // Interners without terminators can't resolve C strings
+        let mut rodeo: Rodeo = RodeoBuilder::new().build();
+        let a = rodeo.get_or_intern("A");
+        assert_eq!(rodeo.resolve_cstr(&a), None);
+    }
+
+    #[test]
+    fn adopt_large_strings() {
+        let mut rodeo: Rodeo = RodeoBuilder::new().adopt_large_strings(true).build();
+        let usage = rodeo.arena_memory_usage();
+
+        // Small strings are still copied into the arena
+        let small = String::from("small");
+        let small_ptr = small.as_ptr();
+        let small_key = rodeo.get_or_intern_owned(small);
+        assert_ne!(rodeo.resolve(&small_key).as_ptr(), small_ptr);
+
+        let mut large = "a".repeat(64 * 1024);
+        large.reserve(10);
+        let (large_ptr, capacity) = (large.as_ptr(), large.capacity());
+        let large_key = rodeo.get_or_intern_owned(large);
+        assert_eq!(rodeo.resolve(&large_key).as_ptr(), large_ptr);
+        assert_eq!(rodeo.resolve(&large_key), "a".repeat(64 * 1024));
+        assert_eq!(rodeo.arena_memory_usage(), usage + capacity);
+
+        // Interning it again finds the adopted string
+        assert_eq!(rodeo.get_or_intern("a".repeat(64 * 1024)), large_key);
+        assert_eq!(rodeo.get_or_intern_owned("a".repeat(64 * 1024)), large_key);
+
+        let cloned = rodeo.clone();
+        assert_eq!(cloned.resolve(&large_key), rodeo.resolve(&large_key));
+
+        rodeo.clear();
+        assert_eq!(rodeo.arena_memory_usage(), usage);
+
+        // Without adoption, large strings get copied
+        let mut rodeo: Rodeo = RodeoBuilder::new().build();
+        let large = "a".repeat(64 * 1024);
+        let large_ptr = large.as_ptr();
+        let key = rodeo.get_or_intern_owned(large);
+        assert_ne!(rodeo.resolve(&key).as_ptr(), large_ptr);
+    }
+
+    #[test]
+    #[cfg(all(feature = "multi-threaded", not(feature = "no-std")))]
+    fn adopt_large_strings_threaded() {
+        let rodeo = RodeoBuilder::new()
+            .adopt_large_strings(true)
+            .build_threaded::<Spur>();
+
+        let large = "a".repeat(64 * 1024);
+        let large_ptr = large.as_ptr();
+        let key = rodeo.get_or_intern_owned(large);
+        assert_eq!(rodeo.resolve(&key).as_ptr(), large_ptr);
+        assert_eq!(rodeo.get_or_intern_owned("a".repeat(64 * 1024)), key);
+
+        let reader = rodeo.into_reader();
+        assert_eq!(reader.resolve(&key).as_ptr(), large_ptr);
+    }
+
+    #[test]
+    fn adopt_large_strings_memory_limit() {
+        let mut rodeo: Rodeo = RodeoBuilder::new()
+            .adopt_large_strings(true)
+            .memory_limits(MemoryLimits::for_memory_usage(16 * 1024))
+            .build();
+
+        let err = rodeo
+            .try_get_or_intern_owned("a".repeat(32 * 1024))
+            .unwrap_err();
+        assert!(err.kind().is_memory_limit());
+        assert!(rodeo.is_empty());
+    }
+
+    #[test]
+    fn intern_empty() {
+        let empty = Spur::try_from_usize(0).unwrap();
+
+        let mut rodeo: Rodeo = RodeoBuilder::new().intern_empty(true).build();
+        assert_eq!(rodeo.len(), 1);
+        assert_eq!(rodeo.empty_key(), Some(empty));
+        assert_eq!(rodeo.get_or_intern(""), empty);
+        assert_ne!(rodeo.get_or_intern("A"), empty);
+
+        let reader = rodeo.into_reader();
+        assert_eq!(reader.empty_key(), Some(empty));
+        assert_eq!(reader.resolve(&empty), "");
+
+        let mut rodeo: Rodeo = RodeoBuilder::new().build();
+        assert_eq!(rodeo.empty_key(), None);
+        rodeo.get_or_intern("A");
+        let key = rodeo.get_or_intern("");
+        assert_eq!(rodeo.empty_key(), Some(key));
+    }
+
+    #[test]
+    #[cfg(all(feature = "multi-threaded", not(feature = "no-std")))]
+    fn intern_empty_threaded() {
+        let empty = Spur::try_from_usize(0).unwrap();
+
+        let rodeo = RodeoBuilder::new()
+            .intern_empty(true)
+            .build_threaded::<Spur>();
+        assert_eq!(rodeo.len(), 1);
+        assert_eq!(rodeo.empty_key(), Some(empty));
+        assert_eq!(rodeo.get_or_intern(""), empty);
+        assert_eq!(rodeo.into_reader().empty_key(), Some(empty));
+    }
+
+    #[test]
+    #[cfg(all(feature = "hugepages", not(miri)))]
+    fn hugepages() {
+        use core::num::NonZeroUsize;
+
+        let bytes = 4 * 1024 * 1024;
+        let mut rodeo: Rodeo = RodeoBuilder::new()
+            .capacity(Capacity::for_bytes(NonZeroUsize::new(bytes).unwrap()))
+            .hugepages(true)
+            .build();
+
+        let long = "a".repeat(bytes * 2);
+        let strings = ["a", "b", "c", long.as_str()];
+        let keys: [Spur; 4] = strings.map(|string| rodeo.get_or_intern(string));
+        for (key, string) in keys.iter().zip(strings) {
+            assert_eq!(rodeo.resolve(key), string);
+        }
+
+        // Small buckets come from the global allocator
+        let mut small: Rodeo = RodeoBuilder::new().hugepages(true).build();
+        let key = small.get_or_intern("small");
+        assert_eq!(small.resolve(&key), "small");
+
+        let cloned = rodeo.clone();
+        drop(rodeo);
+        assert_eq!(cloned.resolve(&keys[3]), long);
+    }
+
+    #[test]
+    #[cfg(all(feature = "hugepages", feature = "multi-threaded", not(miri)))]
+    fn hugepages_threaded() {
+        use core::num::NonZeroUsize;
+
+        let bytes = 4 * 1024 * 1024;
+        let rodeo = RodeoBuilder::new()
+            .capacity(Capacity::for_bytes(NonZeroUsize::new(bytes).unwrap()))
+            .hugepages(true)
+            .build_threaded::<Spur>();
+
+        let long = "a".repeat(bytes * 2);
+        let key = rodeo.get_or_intern("a");
+        let long_key = rodeo.get_or_intern(long.as_str());
+        assert_eq!(rodeo.resolve(&key), "a");
+        assert_eq!(rodeo.resolve(&long_key), long);
+    }
+
+    #[cfg(feature = "allocator-api2")]
+    mod allocator {
+        use crate::{Capacity, Rodeo, RodeoBuilder, Spur};
+        #[cfg(feature = "no-std")]
+        use alloc::{
+            alloc::{alloc, dealloc},
+            sync::Arc,
+        };
+        use allocator_api2::alloc::{AllocError, Allocator, Layout};
+        use core::{
+            ptr::NonNull,
+            sync::atomic::{AtomicUsize, Ordering},
+        };
+        #[cfg(not(feature = "no-std"))]
+        use std::{
+            alloc::{alloc, dealloc},
+            sync::Arc,
+        };
+
+        /// An allocator that counts the bytes that are currently allocated through it
+        #[derive(Default, Clone)]
+        struct Counting(Arc<AtomicUsize>);
+
+        unsafe impl Allocator for Counting {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                let ptr = NonNull::new(unsafe { alloc(layout) }).ok_or(AllocError)?;
+                self.0.fetch_add(layout.size(), Ordering::SeqCst);
+
+                Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                self.0.fetch_sub(layout.size(), Ordering::SeqCst);
+                unsafe { dealloc(ptr.as_ptr(), layout) };
+            }
+        }
+
+        #[test]
+        fn custom_allocator() {
+            let counter = Counting::default();
+
+            let mut rodeo: Rodeo = RodeoBuilder::new()
+                .capacity(Capacity::for_bytes(
+                    core::num::NonZeroUsize::new(16).unwrap(),
+                ))
+                .allocator(counter.clone())
+                .build();
+            assert_eq!(counter.0.load(Ordering::SeqCst), 16);
+
+            let strings = [
+                "a",
+                "bb",
+                "a very long string that won't fit in the first bucket",
+            ];
+            let keys: [Spur; 3] = strings.map(|string| rodeo.get_or_intern(string));
+            for (key, string) in keys.iter().zip(strings) {
+                assert_eq!(rodeo.resolve(key), string);
+            }
+            assert_eq!(counter.0.load(Ordering::SeqCst), rodeo.arena_memory_usage());
+
+            let cloned = rodeo.clone();
+            drop(rodeo);
+            assert_eq!(
+                counter.0.load(Ordering::SeqCst),
+                cloned.arena_memory_usage()
+            );
+            for (key, string) in keys.iter().zip(strings) {
+                assert_eq!(cloned.resolve(key), string);
+            }
+
+            let resolver = cloned.into_resolver();
+            assert_eq!(resolver.resolve(&keys[2]), strings[2]);
+            drop(resolver);
+            assert_eq!(counter.0.load(Ordering::SeqCst), 0);
+        }
+
+        #[test]
+        #[cfg(all(feature = "multi-threaded", not(feature = "no-std")))]
+        fn custom_allocator_threaded() {
+            let counter = Counting::default();
+
+            let rodeo = RodeoBuilder::new()
+                .capacity(Capacity::for_bytes(
+                    core::num::NonZeroUsize::new(16).unwrap(),
+                ))
+                .allocator(counter.clone())
+                .build_threaded::<Spur>();
+            assert!(counter.0.load(Ordering::SeqCst) >= 16);
+
+            let key = rodeo.get_or_intern("a very long string that won't fit in the first bucket");
+            assert_eq!(
+                rodeo.resolve(&key),
+                "a very long string that won't fit in the first bucket",
+            );
+            assert!(counter.0.load(Ordering::SeqCst) >= rodeo.arena_memory_usage());
+
+            drop(rodeo);
+            assert_eq!(counter.0.load(Ordering::SeqCst), 0);
+        }
+    }
+}
@@ -0,0 +1,406 @@
+use crate::{
+    hasher::RandomState,
+    rodeo::{find_string, insert_string, StringMap},
+    Key, LassoError, LassoErrorKind, LassoResult, Spur,
+};
+use alloc::vec::Vec;
+use bumpalo::Bump;
+use core::{
+    alloc::Layout,
+    fmt::{self, Debug},
+    hash::BuildHasher,
+    ptr, slice, str,
+};
+
+/// A string interner that stores its strings in a borrowed [`Bump`]
+///
+/// Programs that already allocate their other data (like an AST) from a bump allocator can
+/// intern strings into the same one, keeping them close to the data that refers to them and
+/// freeing everything at once when the bump is dropped or reset. Resolved strings borrow the
+/// bump instead of the interner, so they outlive it.
+///
+/// Only the strings are stored in the bump, the interner's map and the list of its strings
+/// still use the global allocator
+///
+/// This struct is only available with the `bumpalo` feature!
+///
+/// # Example
+///
+/// ```rust
+/// use bumpalo::Bump;
+/// use lasso::BumpRodeo;
+///
+/// let bump = Bump::new();
+/// let string = {
+///     let mut rodeo: BumpRodeo = BumpRodeo::with_arena(&bump);
+///     let key = rodeo.get_or_intern("Strings of things with wings and dings");
+///
+///     rodeo.resolve(&key)
+/// };
+///
+/// assert_eq!(string, "Strings of things with wings and dings");
+/// ```
+///
+/// [`Bump`]: https://docs.rs/bumpalo/latest/bumpalo/struct.Bump.html
+pub struct BumpRodeo<'bump, K = Spur, S = RandomState> {
+    /// Map that allows `str` -> `key` resolution
+    map: StringMap<K>,
+    /// The hasher of the map
+    hasher: S,
+    /// Vec that allows `key` -> `str` resolution
+    strings: Vec<&'bump str>,
+    /// The arena that every interned string is copied into
+    arena: &'bump Bump,
+}
+
+impl<'bump, K> BumpRodeo<'bump, K, RandomState>
+where
+    K: Key,
+{
+    /// Create an empty `BumpRodeo` that stores its strings in `arena`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bumpalo::Bump;
+    /// use lasso::{BumpRodeo, Spur};
+    ///
+    /// let bump = Bump::new();
+    /// let mut rodeo: BumpRodeo<Spur> = BumpRodeo::with_arena(&bump);
+    /// let hello = rodeo.get_or_intern("Hello, ");
+    ///
+    /// assert_eq!("Hello, ", rodeo.resolve(&hello));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_arena(arena: &'bump Bump) -> Self {
+        Self::with_arena_and_hasher(arena, RandomState::new())
+    }
+}
+
+impl<'bump, K, S> BumpRodeo<'bump, K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    /// Create an empty `BumpRodeo` that stores its strings in `arena` and uses the given hasher
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bumpalo::Bump;
+    /// use lasso::{BumpRodeo, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let bump = Bump::new();
+    /// let rodeo: BumpRodeo<Spur, RandomState> =
+    ///     BumpRodeo::with_arena_and_hasher(&bump, RandomState::new());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_arena_and_hasher(arena: &'bump Bump, hash_builder: S) -> Self {
+        Self {
+            map: StringMap::new(),
+            hasher: hash_builder,
+            strings: Vec::new(),
+            arena,
+        }
+    }
+
+    /// Get the key for a string, interning it into the arena if it does not yet exist
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique strings were interned)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bumpalo::Bump;
+    /// use lasso::BumpRodeo;
+    ///
+    /// let bump = Bump::new();
+    /// let mut rodeo: BumpRodeo = BumpRodeo::with_arena(&bump);
+    ///
+    /// // Interned the string
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    ///
+    /// // No string was interned, as it was already contained
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_or_intern<T>(&mut self, val: T) -> K
+    where
+        T: AsRef<str>,
+    {
+        self.try_get_or_intern(val)
+            .expect("Failed to get or intern string")
+    }
+
+    /// Get the key for a string, interning it into the arena if it does not yet exist
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bumpalo::Bump;
+    /// use lasso::BumpRodeo;
+    ///
+    /// let bump = Bump::new();
+    /// let mut rodeo: BumpRodeo = BumpRodeo::with_arena(&bump);
+    ///
+    /// let key = rodeo.try_get_or_intern("Strings of things with wings and dings").unwrap();
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_get_or_intern<T>(&mut self, val: T) -> LassoResult<K>
+    where
+        T: AsRef<str>,
+    {
+        let arena = self.arena;
+        self.try_intern(val.as_ref(), |string| {
+            let len = string.len();
+            let allocated = arena
+                .try_alloc_layout(Layout::for_value(string.as_bytes()))
+                .map_err(|_| LassoError::failed_allocation(len))?;
+
+            // Safety: The allocation was made for exactly `len` bytes and is fresh, so it can't
+            //         overlap the given string. The copied bytes are valid utf8 since they came
+            //         from a `str`, and the bump keeps them alive for `'bump`
+            unsafe {
+                ptr::copy_nonoverlapping(string.as_ptr(), allocated.as_ptr(), len);
+                Ok(str::from_utf8_unchecked(slice::from_raw_parts(
+                    allocated.as_ptr(),
+                    len,
+                )))
+            }
+        })
+    }
+
+    /// Get the key for a static string, interning it if it does not yet exist
+    ///
+    /// This will not copy the given string into the arena
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique strings were interned)
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_or_intern_static(&mut self, string: &'static str) -> K {
+        self.try_get_or_intern_static(string)
+            .expect("Failed to get or intern static string")
+    }
+
+    /// Get the key for a static string, interning it if it does not yet exist
+    ///
+    /// This will not copy the given string into the arena
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_get_or_intern_static(&mut self, string: &'static str) -> LassoResult<K> {
+        self.try_intern(string, Ok)
+    }
+
+    fn try_intern<'a, F>(&mut self, string: &'a str, store: F) -> LassoResult<K>
+    where
+        F: FnOnce(&'a str) -> LassoResult<&'bump str>,
+    {
+        let hash = self.hasher.hash_one(string);
+        if let Some(key) = find_string(&self.map, &self.strings, hash, string) {
+            return Ok(key);
+        }
+
+        let key = K::try_from_usize(self.strings.len())
+            .ok_or_else(|| LassoError::new(LassoErrorKind::KeySpaceExhaustion))?;
+        let allocated = store(string)?;
+
+        self.strings.push(allocated);
+        insert_string(&mut self.map, &self.strings, &self.hasher, hash, key);
+
+        Ok(key)
+    }
+
+    /// Get the key value of a string, returning `None` if it doesn't exist
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bumpalo::Bump;
+    /// use lasso::BumpRodeo;
+    ///
+    /// let bump = Bump::new();
+    /// let mut rodeo: BumpRodeo = BumpRodeo::with_arena(&bump);
+    ///
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!(Some(key), rodeo.get("Strings of things with wings and dings"));
+    ///
+    /// assert_eq!(None, rodeo.get("This string isn't interned"));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get<T>(&self, val: T) -> Option<K>
+    where
+        T: AsRef<str>,
+    {
+        let string = val.as_ref();
+        let hash = self.hasher.hash_one(string);
+
+        find_string(&self.map, &self.strings, hash, string)
+    }
+
+    /// Returns `true` if the given string has been interned
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains<T>(&self, val: T) -> bool
+    where
+        T: AsRef<str>,
+    {
+        self.get(val).is_some()
+    }
+}
+
+impl<'bump, K, S> BumpRodeo<'bump, K, S>
+where
+    K: Key,
+{
+    /// Returns `true` if the given key exists in the current interner
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains_key(&self, key: &K) -> bool {
+        key.into_usize() < self.strings.len()
+    }
+
+    /// Resolves a string by its key, the string lives as long as the arena it was
+    /// interned into
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bumpalo::Bump;
+    /// use lasso::BumpRodeo;
+    ///
+    /// let bump = Bump::new();
+    /// let mut rodeo: BumpRodeo = BumpRodeo::with_arena(&bump);
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// let string: &str = rodeo.resolve(&key);
+    /// drop(rodeo);
+    /// assert_eq!(string, "Strings of things with wings and dings");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve(&self, key: &K) -> &'bump str {
+        self.try_resolve(key).expect("Key out of bounds")
+    }
+
+    /// Resolves a string by its key, returning `None` if the key is out of bounds
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve(&self, key: &K) -> Option<&'bump str> {
+        self.strings.get(key.into_usize()).copied()
+    }
+
+    /// Resolves a string by its key without bounds checks
+    ///
+    /// # Safety
+    ///
+    /// The key must be valid for the current interner
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub unsafe fn resolve_unchecked(&self, key: &K) -> &'bump str {
+        // Safety: The caller promises the key is in bounds
+        unsafe { index_unchecked!(self.strings, key.into_usize()) }
+    }
+}
+
+impl<'bump, K, S> BumpRodeo<'bump, K, S> {
+    /// Gets the number of interned strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if there are no currently interned strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Returns the arena that strings are interned into
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn arena(&self) -> &'bump Bump {
+        self.arena
+    }
+}
+
+impl<K, S> Debug for BumpRodeo<'_, K, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BumpRodeo")
+            .field("len", &self.len())
+            .field("arena_bytes", &self.arena.allocated_bytes())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BumpRodeo, Key, MicroSpur, Spur};
+    use bumpalo::Bump;
+
+    #[cfg(feature = "no-std")]
+    use alloc::string::{String, ToString};
+
+    #[test]
+    fn intern_into_arena() {
+        let bump = Bump::new();
+        let mut rodeo: BumpRodeo = BumpRodeo::with_arena(&bump);
+
+        let a = rodeo.get_or_intern("a");
+        let b = rodeo.get_or_intern(String::from("b"));
+        assert_eq!(rodeo.get_or_intern("a"), a);
+        assert_eq!(rodeo.len(), 2);
+        assert_eq!(rodeo.get("b"), Some(b));
+        assert_eq!(rodeo.get("c"), None);
+        assert!(rodeo.contains("a"));
+
+        assert!(rodeo.contains_key(&b));
+        assert!(!rodeo.contains_key(&Spur::try_from_usize(2).unwrap()));
+        assert_eq!(rodeo.try_resolve(&Spur::try_from_usize(2).unwrap()), None);
+
+        // Strings live in the bump and outlive the interner
+        let string = rodeo.resolve(&a);
+        drop(rodeo);
+        assert_eq!(string, "a");
+        assert!(bump.allocated_bytes() > 0);
+    }
+
+    #[test]
+    fn static_strings() {
+        static STRING: &str = "static";
+
+        let bump = Bump::new();
+        let mut rodeo: BumpRodeo = BumpRodeo::with_arena(&bump);
+
+        let key = rodeo.get_or_intern_static(STRING);
+        assert_eq!(rodeo.get_or_intern("static"), key);
+        assert!(core::ptr::eq(rodeo.resolve(&key), STRING));
+    }
+
+    #[test]
+    fn key_space_exhaustion() {
+        let bump = Bump::new();
+        let mut rodeo: BumpRodeo<MicroSpur> = BumpRodeo::with_arena(&bump);
+
+        for i in 0..u8::MAX as usize {
+            rodeo.get_or_intern(i.to_string());
+        }
+        assert!(rodeo.try_get_or_intern("overflow").is_err());
+        assert_eq!(rodeo.len(), u8::MAX as usize);
+    }
+}
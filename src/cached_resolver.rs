@@ -0,0 +1,289 @@
+use crate::{Key, Resolver, Spur};
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    cell::Cell,
+    fmt::{Debug, Formatter, Result as FmtResult},
+};
+
+/// The number of keys a [`CachedResolver`] remembers by default
+const DEFAULT_SLOTS: usize = 256;
+
+/// A slot of a [`CachedResolver`], holding the last key that was resolved through it
+type Slot<'a, K> = Cell<Option<(K, &'a str)>>;
+
+/// A small cache of resolved keys in front of any [`Resolver`]
+///
+/// Keys are stored in a direct-mapped array indexed by the key, so resolving a key that was
+/// recently resolved only reads the cache instead of the underlying resolver. This is mostly
+/// useful for [`ThreadedRodeo`], where every thread can keep its own `CachedResolver` so that
+/// hot keys are resolved from memory local to the thread instead of going through the shared
+/// interner's locks
+///
+/// The cache isn't invalidated if the underlying resolver is cleared through a shared
+/// reference (like with [`ThreadedRodeo::clear`]), in that case [`CachedResolver::clear_cache`]
+/// has to be called before resolving any new keys
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{CachedResolver, Resolver, Rodeo};
+///
+/// let mut rodeo = Rodeo::default();
+/// let key = rodeo.get_or_intern("Hello, world!");
+///
+/// let resolver = CachedResolver::new(&rodeo);
+/// assert_eq!(resolver.resolve(&key), "Hello, world!");
+///
+/// // The second resolution comes from the cache
+/// assert_eq!(resolver.resolve(&key), "Hello, world!");
+/// ```
+///
+/// [`ThreadedRodeo`]: crate::ThreadedRodeo
+/// [`ThreadedRodeo::clear`]: crate::ThreadedRodeo::clear
+pub struct CachedResolver<'a, R: ?Sized, K = Spur> {
+    resolver: &'a R,
+    /// The most recently resolved key and string of every slot, keys always go into the
+    /// slot at their index modulo the number of slots
+    slots: Box<[Slot<'a, K>]>,
+}
+
+impl<'a, R, K> CachedResolver<'a, R, K>
+where
+    R: Resolver<K> + ?Sized,
+    K: Key,
+{
+    /// Create a new `CachedResolver` in front of the given resolver
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{CachedResolver, Resolver, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// let resolver = CachedResolver::new(&rodeo);
+    /// assert_eq!(resolver.resolve(&key), "Hello, world!");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new(resolver: &'a R) -> Self {
+        Self::with_slots(resolver, DEFAULT_SLOTS)
+    }
+
+    /// Create a new `CachedResolver` that remembers up to `slots` keys, which is rounded up
+    /// to the next power of two
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{CachedResolver, Resolver, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// let resolver = CachedResolver::with_slots(&rodeo, 1000);
+    /// assert_eq!(resolver.slots(), 1024);
+    /// assert_eq!(resolver.resolve(&key), "Hello, world!");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_slots(resolver: &'a R, slots: usize) -> Self {
+        let slots = slots
+            .max(1)
+            .checked_next_power_of_two()
+            .unwrap_or(DEFAULT_SLOTS);
+
+        Self {
+            resolver,
+            slots: (0..slots)
+                .map(|_| Cell::new(None))
+                .collect::<Vec<_>>()
+                .into(),
+        }
+    }
+
+    /// Returns the number of keys the cache can remember
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn slots(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the resolver behind the cache
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolver(&self) -> &'a R {
+        self.resolver
+    }
+
+    /// Forgets every cached key, which is required after the underlying resolver was cleared
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{CachedResolver, Resolver, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// let resolver = CachedResolver::new(&rodeo);
+    /// resolver.resolve(&key);
+    /// assert_eq!(resolver.cached_len(), 1);
+    ///
+    /// resolver.clear_cache();
+    /// assert_eq!(resolver.cached_len(), 0);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn clear_cache(&self) {
+        for slot in self.slots.iter() {
+            slot.set(None);
+        }
+    }
+
+    /// Returns the number of keys that are currently cached
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn cached_len(&self) -> usize {
+        self.slots
+            .iter()
+            .filter(|slot| slot.get().is_some())
+            .count()
+    }
+
+    /// Gets the slot that `key` is cached in
+    #[inline]
+    fn slot(&self, key: &K) -> &Slot<'a, K> {
+        // The number of slots is always a power of two
+        let index = key.into_usize() & (self.slots.len() - 1);
+
+        // Safety: The index was masked to be within the slots
+        unsafe { self.slots.get_unchecked(index) }
+    }
+
+    /// Gets the string of `key` from the cache, falling back to `resolve` and caching
+    /// the string it returns
+    #[inline]
+    fn cached<F>(&self, key: &K, resolve: F) -> Option<&'a str>
+    where
+        F: FnOnce(&'a R) -> Option<&'a str>,
+    {
+        let slot = self.slot(key);
+        if let Some((cached, string)) = slot.get() {
+            if cached == *key {
+                return Some(string);
+            }
+        }
+
+        let string = resolve(self.resolver)?;
+        slot.set(Some((*key, string)));
+
+        Some(string)
+    }
+}
+
+impl<'a, R, K> Resolver<K> for CachedResolver<'a, R, K>
+where
+    R: Resolver<K> + ?Sized,
+    K: Key,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn resolve<'b>(&'b self, key: &K) -> &'b str {
+        self.cached(key, |resolver| Some(resolver.resolve(key)))
+            .expect("the resolver always returns a string")
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn try_resolve<'b>(&'b self, key: &K) -> Option<&'b str> {
+        self.cached(key, |resolver| resolver.try_resolve(key))
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    unsafe fn resolve_unchecked<'b>(&'b self, key: &K) -> &'b str {
+        // Safety: The caller promises that the key is valid for the resolver
+        self.cached(key, |resolver| {
+            Some(unsafe { resolver.resolve_unchecked(key) })
+        })
+        .expect("the resolver always returns a string")
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn contains_key(&self, key: &K) -> bool {
+        self.resolver.contains_key(key)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn len(&self) -> usize {
+        self.resolver.len()
+    }
+}
+
+impl<'a, R, K> Debug for CachedResolver<'a, R, K>
+where
+    R: Debug + ?Sized,
+    K: Debug + Copy,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("CachedResolver")
+            .field("resolver", &self.resolver)
+            .field("slots", &self.slots.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Rodeo, RodeoResolver};
+
+    #[test]
+    fn resolve() {
+        let mut rodeo = Rodeo::default();
+        let keys: Vec<Spur> = ["a", "b", "c"]
+            .iter()
+            .map(|string| rodeo.get_or_intern(string))
+            .collect();
+        let resolver: RodeoResolver = rodeo.into_resolver();
+
+        // Keys 0 and 2 share the first slot
+        let cached = CachedResolver::with_slots(&resolver, 2);
+        assert_eq!(cached.slots(), 2);
+        assert_eq!(cached.resolve(&keys[0]), "a");
+        assert_eq!(cached.resolve(&keys[1]), "b");
+        assert_eq!(cached.cached_len(), 2);
+        assert_eq!(cached.resolve(&keys[2]), "c");
+        assert_eq!(cached.cached_len(), 2);
+        assert_eq!(cached.resolve(&keys[0]), "a");
+        assert_eq!(cached.resolve_many(&keys), resolver.resolve_many(&keys),);
+
+        assert_eq!(cached.try_resolve(&Spur::try_from_usize(10).unwrap()), None);
+        assert_eq!(unsafe { cached.resolve_unchecked(&keys[1]) }, "b");
+        assert!(cached.contains_key(&keys[2]));
+        assert_eq!(cached.len(), 3);
+
+        cached.clear_cache();
+        assert_eq!(cached.cached_len(), 0);
+        assert_eq!(CachedResolver::with_slots(&resolver, 0).slots(), 1);
+    }
+
+    #[test]
+    #[cfg(all(feature = "multi-threaded", not(any(miri, feature = "no-std"))))]
+    fn threaded() {
+        use crate::ThreadedRodeo;
+        use std::{sync::Arc, thread};
+
+        let rodeo: Arc<ThreadedRodeo> = Arc::new(ThreadedRodeo::default());
+        let key = rodeo.get_or_intern("hello");
+
+        let shared = Arc::clone(&rodeo);
+        thread::spawn(move || {
+            let cached = CachedResolver::new(&*shared);
+            assert_eq!(cached.resolve(&key), "hello");
+            assert_eq!(cached.resolve(&key), "hello");
+        })
+        .join()
+        .unwrap();
+
+        rodeo.clear();
+        let cached = CachedResolver::new(&*rodeo);
+        assert_eq!(cached.try_resolve(&key), None);
+    }
+}
@@ -0,0 +1,597 @@
+//! Compatibility shims for migrating from the [`string-interner`] crate
+//!
+//! [`StringInterner`] mirrors the api of `string_interner::StringInterner`, backed by a
+//! [`Rodeo`]. Along with the implementations of `string_interner::Symbol` for all of
+//! lasso's keys, this allows code written against `string-interner` to switch over by only
+//! changing its imports.
+//!
+//! This module is only available with the `string-interner` feature!
+//!
+//! # Example
+//!
+//! ```rust
+//! // Previously `use string_interner::{DefaultStringInterner, Symbol};`
+//! use lasso::compat::DefaultStringInterner;
+//! use string_interner::Symbol;
+//!
+//! let mut interner = DefaultStringInterner::default();
+//! let sym0 = interner.get_or_intern("Elephant");
+//! let sym1 = interner.get_or_intern("Tiger");
+//! let sym2 = interner.get_or_intern("Horse");
+//! let sym3 = interner.get_or_intern("Tiger");
+//!
+//! assert_ne!(sym0, sym1);
+//! assert_eq!(sym1, sym3);
+//! assert_eq!(sym2.to_usize(), 2);
+//! assert_eq!(interner.resolve(sym0), Some("Elephant"));
+//! ```
+//!
+//! [`string-interner`]: https://crates.io/crates/string-interner
+
+use crate::{hasher::RandomState, Capacity, Iter, Key, Rodeo, RodeoReader, RodeoResolver, Spur};
+use core::{
+    fmt::{self, Debug},
+    hash::BuildHasher,
+    iter::{FromIterator, IntoIterator},
+};
+
+/// The default symbol used by [`DefaultStringInterner`]
+pub type DefaultSymbol = Spur;
+
+/// A [`StringInterner`] using the default symbol and hasher
+pub type DefaultStringInterner = StringInterner<DefaultSymbol, RandomState>;
+
+/// A [`Rodeo`] with the api of `string_interner::StringInterner`
+///
+/// See the [module level documentation](crate::compat) for more information
+pub struct StringInterner<K = Spur, S = RandomState> {
+    rodeo: Rodeo<K, S>,
+}
+
+impl<K> StringInterner<K, RandomState>
+where
+    K: Key,
+{
+    /// Creates a new empty `StringInterner`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{compat::StringInterner, Spur};
+    ///
+    /// let interner: StringInterner<Spur> = StringInterner::new();
+    /// assert!(interner.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new() -> Self {
+        Self::from_rodeo(Rodeo::new())
+    }
+
+    /// Creates a new `StringInterner` with the given initial capacity for strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{compat::StringInterner, Spur};
+    ///
+    /// let interner: StringInterner<Spur> = StringInterner::with_capacity(10);
+    /// assert!(interner.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity(cap: usize) -> Self {
+        Self::from_rodeo(Rodeo::with_capacity(Capacity::for_strings(cap)))
+    }
+}
+
+impl<K, S> StringInterner<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    /// Creates a new empty `StringInterner` with the given hasher
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{compat::StringInterner, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let interner: StringInterner<Spur, _> = StringInterner::with_hasher(RandomState::new());
+    /// assert!(interner.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self::from_rodeo(Rodeo::with_hasher(hash_builder))
+    }
+
+    /// Creates a new `StringInterner` with the given initial capacity for strings and the
+    /// given hasher
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{compat::StringInterner, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let interner: StringInterner<Spur, _> =
+    ///     StringInterner::with_capacity_and_hasher(10, RandomState::new());
+    /// assert!(interner.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity_and_hasher(cap: usize, hash_builder: S) -> Self {
+        Self::from_rodeo(Rodeo::with_capacity_and_hasher(
+            Capacity::for_strings(cap),
+            hash_builder,
+        ))
+    }
+
+    /// Creates a `StringInterner` that uses the given [`Rodeo`] as its storage
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{compat::StringInterner, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Tiger");
+    ///
+    /// let interner = StringInterner::from_rodeo(rodeo);
+    /// assert_eq!(interner.resolve(key), Some("Tiger"));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn from_rodeo(rodeo: Rodeo<K, S>) -> Self {
+        Self { rodeo }
+    }
+
+    /// Returns the number of strings interned by the interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::compat::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::default();
+    /// interner.get_or_intern("Tiger");
+    ///
+    /// assert_eq!(interner.len(), 1);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.rodeo.len()
+    }
+
+    /// Returns `true` if the interner has no interned strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::compat::DefaultStringInterner;
+    ///
+    /// let interner = DefaultStringInterner::default();
+    /// assert!(interner.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.rodeo.is_empty()
+    }
+
+    /// Returns the symbol for the given string if it has been interned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::compat::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::default();
+    /// let sym = interner.get_or_intern("Tiger");
+    ///
+    /// assert_eq!(interner.get("Tiger"), Some(sym));
+    /// assert_eq!(interner.get("Horse"), None);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get<T>(&self, string: T) -> Option<K>
+    where
+        T: AsRef<str>,
+    {
+        self.rodeo.get(string)
+    }
+
+    /// Interns the given string, returning its symbol
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interner already holds the maximum number of strings its symbol can
+    /// represent or if the interner's memory limit has been reached
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::compat::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::default();
+    /// let sym = interner.get_or_intern("Tiger");
+    ///
+    /// assert_eq!(interner.get_or_intern("Tiger"), sym);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_or_intern<T>(&mut self, string: T) -> K
+    where
+        T: AsRef<str>,
+    {
+        self.rodeo.get_or_intern(string)
+    }
+
+    /// Interns the given static string, returning its symbol
+    ///
+    /// This will not copy the given string
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interner already holds the maximum number of strings its symbol can
+    /// represent
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::compat::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::default();
+    /// let sym = interner.get_or_intern_static("Tiger");
+    ///
+    /// assert_eq!(interner.get_or_intern("Tiger"), sym);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_or_intern_static(&mut self, string: &'static str) -> K {
+        self.rodeo.get_or_intern_static(string)
+    }
+
+    /// Shrinks the interner's capacity to fit its interned strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::compat::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::with_capacity(100);
+    /// interner.get_or_intern("Tiger");
+    /// interner.shrink_to_fit();
+    ///
+    /// assert_eq!(interner.len(), 1);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn shrink_to_fit(&mut self) {
+        self.rodeo.strings.shrink_to_fit();
+    }
+}
+
+impl<K, S> StringInterner<K, S>
+where
+    K: Key,
+{
+    /// Resolves the given symbol into its string, returning `None` if it isn't contained
+    /// in the interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::compat::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::default();
+    /// let sym = interner.get_or_intern("Tiger");
+    ///
+    /// assert_eq!(interner.resolve(sym), Some("Tiger"));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve(&self, symbol: K) -> Option<&str> {
+        self.rodeo.try_resolve(&symbol)
+    }
+
+    /// Resolves the given symbol into its string without checking that it's contained in the
+    /// interner
+    ///
+    /// # Safety
+    ///
+    /// The symbol must have been created by the current interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::compat::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::default();
+    /// let sym = interner.get_or_intern("Tiger");
+    ///
+    /// unsafe {
+    ///     assert_eq!(interner.resolve_unchecked(sym), "Tiger");
+    /// }
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub unsafe fn resolve_unchecked(&self, symbol: K) -> &str {
+        // Safety: The caller promises the symbol is valid
+        unsafe { self.rodeo.resolve_unchecked(&symbol) }
+    }
+
+    /// Returns an iterator over the interned strings and their symbols
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::compat::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::default();
+    /// let sym = interner.get_or_intern("Tiger");
+    ///
+    /// assert_eq!(interner.iter().collect::<Vec<_>>(), vec![(sym, "Tiger")]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter(&self) -> Iter<'_, K> {
+        self.rodeo.iter()
+    }
+}
+
+impl<K, S> StringInterner<K, S> {
+    /// Returns a reference to the [`Rodeo`] backing the interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::compat::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::default();
+    /// let sym = interner.get_or_intern("Tiger");
+    ///
+    /// assert_eq!(interner.as_rodeo().resolve(&sym), "Tiger");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn as_rodeo(&self) -> &Rodeo<K, S> {
+        &self.rodeo
+    }
+
+    /// Consumes the interner, returning the [`Rodeo`] backing it
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::compat::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::default();
+    /// let sym = interner.get_or_intern("Tiger");
+    ///
+    /// let rodeo = interner.into_rodeo();
+    /// assert_eq!(rodeo.resolve(&sym), "Tiger");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn into_rodeo(self) -> Rodeo<K, S> {
+        self.rodeo
+    }
+
+    /// Consumes the interner, turning it into a [`RodeoReader`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::compat::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::default();
+    /// let sym = interner.get_or_intern("Tiger");
+    ///
+    /// let reader = interner.into_reader();
+    /// assert_eq!(reader.get("Tiger"), Some(sym));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn into_reader(self) -> RodeoReader<K, S> {
+        self.rodeo.into_reader()
+    }
+
+    /// Consumes the interner, turning it into a [`RodeoResolver`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::compat::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::default();
+    /// let sym = interner.get_or_intern("Tiger");
+    ///
+    /// let resolver = interner.into_resolver();
+    /// assert_eq!(resolver.resolve(&sym), "Tiger");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn into_resolver(self) -> RodeoResolver<K> {
+        self.rodeo.into_resolver()
+    }
+}
+
+impl<K, S> Default for StringInterner<K, S>
+where
+    K: Key,
+    S: BuildHasher + Default,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K: Key + Debug, S> Debug for StringInterner<K, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StringInterner")
+            .field("rodeo", &self.rodeo)
+            .finish()
+    }
+}
+
+impl<K, S> Clone for StringInterner<K, S>
+where
+    K: Key,
+    S: BuildHasher + Clone,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self {
+            rodeo: self.rodeo.clone(),
+        }
+    }
+}
+
+impl<K, S> Eq for StringInterner<K, S> {}
+
+impl<K, S> PartialEq for StringInterner<K, S> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn eq(&self, other: &Self) -> bool {
+        self.rodeo == other.rodeo
+    }
+}
+
+impl<K, S> From<Rodeo<K, S>> for StringInterner<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from(rodeo: Rodeo<K, S>) -> Self {
+        Self::from_rodeo(rodeo)
+    }
+}
+
+impl<Str, K, S> FromIterator<Str> for StringInterner<K, S>
+where
+    Str: AsRef<str>,
+    K: Key,
+    S: BuildHasher + Default,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = Str>,
+    {
+        Self::from_rodeo(Rodeo::from_iter(iter))
+    }
+}
+
+impl<Str, K, S> Extend<Str> for StringInterner<K, S>
+where
+    Str: AsRef<str>,
+    K: Key,
+    S: BuildHasher,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = Str>,
+    {
+        self.rodeo.extend(iter);
+    }
+}
+
+impl<'a, K, S> IntoIterator for &'a StringInterner<K, S>
+where
+    K: Key,
+{
+    type Item = (K, &'a str);
+    type IntoIter = Iter<'a, K>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DefaultStringInterner, StringInterner};
+    use crate::{Key, LargeSpur, MicroSpur, MiniSpur, Rodeo, Spur};
+    #[cfg(feature = "no-std")]
+    use alloc::{string::String, vec, vec::Vec};
+    use string_interner::Symbol;
+
+    fn symbol_roundtrip<K: Key + Symbol + core::fmt::Debug>(max: usize) {
+        for idx in [0, 1, max / 2, max] {
+            let key = <K as Symbol>::try_from_usize(idx).unwrap();
+            assert_eq!(Symbol::to_usize(key), idx);
+            assert_eq!(Key::into_usize(key), idx);
+            assert_eq!(<K as Key>::try_from_usize(idx), Some(key));
+        }
+
+        assert_eq!(<K as Symbol>::try_from_usize(max + 1), None);
+    }
+
+    #[test]
+    fn symbol_implementations() {
+        symbol_roundtrip::<Spur>(u32::MAX as usize - 1);
+        symbol_roundtrip::<MiniSpur>(u16::MAX as usize - 1);
+        symbol_roundtrip::<MicroSpur>(u8::MAX as usize - 1);
+        symbol_roundtrip::<LargeSpur>(usize::MAX - 1);
+    }
+
+    #[test]
+    fn interning() {
+        let mut interner = DefaultStringInterner::with_capacity(4);
+        assert!(interner.is_empty());
+
+        let a = interner.get_or_intern("a");
+        let b = interner.get_or_intern_static("b");
+        assert_eq!(interner.get_or_intern("b"), b);
+        assert_eq!(interner.get_or_intern(String::from("a")), a);
+        assert_eq!(interner.len(), 2);
+
+        assert_eq!(interner.get("a"), Some(a));
+        assert_eq!(interner.get("c"), None);
+        assert_eq!(interner.resolve(a), Some("a"));
+        assert_eq!(
+            interner.resolve(<Spur as Key>::try_from_usize(10).unwrap()),
+            None
+        );
+        unsafe {
+            assert_eq!(interner.resolve_unchecked(b), "b");
+        }
+
+        assert_eq!(
+            interner.iter().collect::<Vec<_>>(),
+            vec![(a, "a"), (b, "b")]
+        );
+        assert_eq!((&interner).into_iter().count(), 2);
+    }
+
+    #[test]
+    fn conversions() {
+        let mut rodeo = Rodeo::default();
+        let key = rodeo.get_or_intern("converted");
+
+        let mut interner = StringInterner::from(rodeo);
+        interner.extend(["more", "strings"]);
+        interner.shrink_to_fit();
+        assert_eq!(interner.clone(), interner);
+
+        assert_eq!(interner.as_rodeo().resolve(&key), "converted");
+        assert_eq!(
+            interner.clone().into_reader().get("more"),
+            interner.get("more")
+        );
+        assert_eq!(interner.clone().into_resolver().len(), 3);
+        assert_eq!(interner.into_rodeo().len(), 3);
+
+        let collected: DefaultStringInterner = ["a", "b", "a"].iter().collect();
+        assert_eq!(collected.len(), 2);
+    }
+}
@@ -0,0 +1,444 @@
+use crate::{
+    keys::{Key, Spur},
+    Capacity, MemoryLimits, Rodeo,
+};
+use core::{
+    fmt::{self, Debug},
+    hash::{BuildHasher, BuildHasherDefault, Hasher},
+    ops::{Deref, DerefMut},
+};
+
+#[cfg(feature = "serialize")]
+use serde::{
+    de::{Deserialize, Deserializer},
+    ser::{Serialize, Serializer},
+};
+
+/// A marker for hashers that produce the same hashes for the same input on every run
+/// and every platform
+///
+/// Hashers that are randomly seeded to protect against HashDoS attacks, like
+/// [`std::collections::hash_map::RandomState`] or `ahash::RandomState`, must not
+/// implement this trait
+///
+/// [`std::collections::hash_map::RandomState`]: https://doc.rust-lang.org/std/collections/hash_map/struct.RandomState.html
+pub trait DeterministicHasher: BuildHasher {}
+
+impl DeterministicHasher for FixedState {}
+impl<H> DeterministicHasher for BuildHasherDefault<H> where H: Default + Hasher {}
+
+/// A hasher with a fixed seed that hashes identically on every run and every platform
+///
+/// Since the seed is known ahead of time, this hasher does nothing to protect against
+/// HashDoS attacks and shouldn't be used on untrusted input
+///
+/// # Example
+///
+/// ```rust
+/// use core::hash::BuildHasher;
+/// use lasso::FixedState;
+///
+/// assert_eq!(FixedState::new().hash_one("lasso"), FixedState::new().hash_one("lasso"));
+/// assert_ne!(FixedState::new().hash_one("lasso"), FixedState::with_seed(1).hash_one("lasso"));
+/// ```
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FixedState {
+    seed: u64,
+}
+
+impl FixedState {
+    /// The seed used by [`FixedState::new`], the first fractional digits of pi
+    const DEFAULT_SEED: u64 = 0x243F_6A88_85A3_08D3;
+
+    /// Create a new `FixedState` with the default seed
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn new() -> Self {
+        Self::with_seed(Self::DEFAULT_SEED)
+    }
+
+    /// Create a new `FixedState` with a custom seed
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn with_seed(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl Default for FixedState {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BuildHasher for FixedState {
+    type Hasher = FixedHasher;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn build_hasher(&self) -> Self::Hasher {
+        FixedHasher { hash: self.seed }
+    }
+}
+
+/// The hasher created by [`FixedState`]
+///
+/// Integers are always hashed as little endian `u64`s, so hashes don't depend
+/// on the platform's endianness or pointer width
+#[derive(Debug, Clone)]
+pub struct FixedHasher {
+    hash: u64,
+}
+
+impl FixedHasher {
+    const MULTIPLIER: u64 = 0x517C_C1B7_2722_0A95;
+
+    #[inline]
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(Self::MULTIPLIER);
+    }
+}
+
+/// Creates a `FixedHasher` with the same seed as [`FixedState::new`]
+impl Default for FixedHasher {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        FixedState::new().build_hasher()
+    }
+}
+
+impl Hasher for FixedHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+            self.add(u64::from_le_bytes(word));
+        }
+
+        let remainder = chunks.remainder();
+        if !remainder.is_empty() {
+            let mut word = [0; 8];
+            word[..remainder.len()].copy_from_slice(remainder);
+            self.add(u64::from_le_bytes(word));
+        }
+
+        self.add(bytes.len() as u64);
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.add(i as u64);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.add(i as u64);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.add(i as u64);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.add(i as u64);
+        self.add((i >> 64) as u64);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        // Mix the bits so that similar inputs don't produce similar hashes
+        let mut hash = self.hash;
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xC4CE_B9FE_1A85_EC53);
+        hash ^ (hash >> 33)
+    }
+}
+
+/// A [`Rodeo`] that's guaranteed to assign identical keys and produce identical
+/// serializations when given the same strings in the same order, no matter the
+/// run or platform
+///
+/// Keys are always assigned in insertion order, so the only thing that can differ
+/// between two runs of a normal `Rodeo` is its hasher. A `DeterministicRodeo` uses
+/// [`FixedState`] by default and refuses any hasher that doesn't implement
+/// [`DeterministicHasher`], so randomly seeded hashers are rejected at compile time.
+/// This makes it suitable for producing cacheable build artifacts, but since its
+/// hasher has a known seed it shouldn't be used on untrusted input
+///
+/// `DeterministicRodeo` dereferences to a [`Rodeo`], so all of its methods are available
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{DeterministicRodeo, Spur};
+///
+/// let mut first: DeterministicRodeo<Spur> = DeterministicRodeo::new();
+/// let mut second: DeterministicRodeo<Spur> = DeterministicRodeo::new();
+///
+/// for string in ["build", "artifact", "build"] {
+///     assert_eq!(first.get_or_intern(string), second.get_or_intern(string));
+/// }
+/// ```
+///
+/// [`Rodeo`]: crate::Rodeo
+pub struct DeterministicRodeo<K = Spur, S = FixedState> {
+    rodeo: Rodeo<K, S>,
+}
+
+impl<K> DeterministicRodeo<K, FixedState>
+where
+    K: Key,
+{
+    /// Create a new `DeterministicRodeo` that uses [`FixedState`] as its hasher
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{DeterministicRodeo, Spur};
+    ///
+    /// let mut rodeo: DeterministicRodeo<Spur> = DeterministicRodeo::new();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    /// assert_eq!(rodeo.resolve(&key), "Hello, world!");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new() -> Self {
+        Self::with_hasher(FixedState::new())
+    }
+
+    /// Create a new `DeterministicRodeo` with the specified capacity that uses [`FixedState`] as its hasher
+    ///
+    /// See [`Capacity`] for more details
+    ///
+    /// [`Capacity`]: crate::Capacity
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity(capacity: Capacity) -> Self {
+        Self::with_capacity_and_hasher(capacity, FixedState::new())
+    }
+}
+
+impl<K, S> DeterministicRodeo<K, S>
+where
+    K: Key,
+    S: DeterministicHasher,
+{
+    /// Create a new `DeterministicRodeo` with a deterministic hasher
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{DeterministicRodeo, FixedState, Spur};
+    ///
+    /// let rodeo: DeterministicRodeo<Spur> = DeterministicRodeo::with_hasher(FixedState::with_seed(42));
+    /// ```
+    ///
+    /// Randomly seeded hashers aren't allowed
+    ///
+    /// ```rust,compile_fail
+    /// use lasso::{DeterministicRodeo, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: DeterministicRodeo<Spur, RandomState> =
+    ///     DeterministicRodeo::with_hasher(RandomState::new());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            rodeo: Rodeo::with_hasher(hash_builder),
+        }
+    }
+
+    /// Create a new `DeterministicRodeo` with the specified capacity and a deterministic hasher
+    ///
+    /// See [`Capacity`] for more details
+    ///
+    /// [`Capacity`]: crate::Capacity
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity_and_hasher(capacity: Capacity, hash_builder: S) -> Self {
+        Self {
+            rodeo: Rodeo::with_capacity_and_hasher(capacity, hash_builder),
+        }
+    }
+
+    /// Create a new `DeterministicRodeo` with the specified capacity, memory limits
+    /// and a deterministic hasher
+    ///
+    /// See [`Capacity`] and [`MemoryLimits`] for more details
+    ///
+    /// [`Capacity`]: crate::Capacity
+    /// [`MemoryLimits`]: crate::MemoryLimits
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity_memory_limits_and_hasher(
+        capacity: Capacity,
+        memory_limits: MemoryLimits,
+        hash_builder: S,
+    ) -> Self {
+        Self {
+            rodeo: Rodeo::with_capacity_memory_limits_and_hasher(
+                capacity,
+                memory_limits,
+                hash_builder,
+            ),
+        }
+    }
+}
+
+impl<K, S> DeterministicRodeo<K, S> {
+    /// Consumes the `DeterministicRodeo`, returning the [`Rodeo`] it wraps
+    ///
+    /// [`Rodeo`]: crate::Rodeo
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn into_inner(self) -> Rodeo<K, S> {
+        self.rodeo
+    }
+}
+
+impl<K> Default for DeterministicRodeo<K, FixedState>
+where
+    K: Key,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Key + Debug, S> Debug for DeterministicRodeo<K, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeterministicRodeo")
+            .field("rodeo", &self.rodeo)
+            .finish()
+    }
+}
+
+impl<K, S> Deref for DeterministicRodeo<K, S> {
+    type Target = Rodeo<K, S>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn deref(&self) -> &Self::Target {
+        &self.rodeo
+    }
+}
+
+impl<K, S> DerefMut for DeterministicRodeo<K, S> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.rodeo
+    }
+}
+
+impl<K, S> From<DeterministicRodeo<K, S>> for Rodeo<K, S> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from(rodeo: DeterministicRodeo<K, S>) -> Self {
+        rodeo.rodeo
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<K, H> Serialize for DeterministicRodeo<K, H> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.rodeo.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<'de, K, S> Deserialize<'de> for DeterministicRodeo<K, S>
+where
+    K: Key,
+    S: DeterministicHasher + Default,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self {
+            rodeo: Rodeo::deserialize(deserializer)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DeterministicRodeo, FixedHasher, FixedState, Rodeo, Spur};
+    use core::hash::{BuildHasher, BuildHasherDefault};
+
+    #[test]
+    fn fixed_hashes() {
+        // These values must never change, otherwise hashes differ between versions
+        let state = FixedState::new();
+        assert_eq!(state.hash_one(""), state.hash_one(""));
+        assert_ne!(state.hash_one("a"), state.hash_one("b"));
+        assert_ne!(state.hash_one("abcdefgh"), state.hash_one("abcdefgh\0"));
+        assert_eq!(state.hash_one(0x1234_u16), state.hash_one(0x1234_u64));
+        assert_eq!(state.hash_one(7_usize), state.hash_one(7_u64));
+
+        assert_eq!(state.hash_one("lasso"), 0x31D7_8886_FF36_62B9);
+        assert_eq!(
+            FixedState::with_seed(0).hash_one("lasso"),
+            0xC038_5EA8_13E0_41D6,
+        );
+    }
+
+    #[test]
+    fn identical_keys() {
+        let strings = ["zeta", "alpha", "", "mu", "alpha", "omega", "zeta"];
+
+        let mut first: DeterministicRodeo<Spur> = DeterministicRodeo::new();
+        let mut second: DeterministicRodeo<Spur, BuildHasherDefault<FixedHasher>> =
+            DeterministicRodeo::with_hasher(BuildHasherDefault::default());
+
+        for string in strings {
+            assert_eq!(first.get_or_intern(string), second.get_or_intern(string));
+        }
+        assert_eq!(first.len(), 5);
+
+        let rodeo: Rodeo<Spur, FixedState> = first.into_inner();
+        assert_eq!(rodeo.resolve(&Spur::default()), "zeta");
+    }
+
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn snapshot_serialization() {
+        #[cfg(feature = "no-std")]
+        use alloc::string::String;
+
+        let build = || {
+            let mut rodeo: DeterministicRodeo<Spur> = DeterministicRodeo::new();
+            for string in ["src/main.rs", "src/lib.rs", "Cargo.toml", "src/main.rs"] {
+                rodeo.get_or_intern(string);
+            }
+
+            rodeo
+        };
+
+        let snapshot: String = serde_json::to_string(&build()).unwrap();
+        assert_eq!(snapshot, r#"["src/main.rs","src/lib.rs","Cargo.toml"]"#);
+        assert_eq!(serde_json::to_string(&build()).unwrap(), snapshot);
+
+        let deserialized: DeterministicRodeo<Spur> = serde_json::from_str(&snapshot).unwrap();
+        assert_eq!(deserialized.get("Cargo.toml"), build().get("Cargo.toml"));
+        assert_eq!(serde_json::to_string(&deserialized).unwrap(), snapshot);
+    }
+}
@@ -0,0 +1,378 @@
+use crate::{
+    hasher::RandomState,
+    keys::{Key, MiniSpur, Spur},
+    resolver::check_key_space,
+    Capacity, LassoError, LassoErrorKind, LassoResult, Rodeo,
+};
+use core::{
+    fmt::{self, Debug},
+    hash::BuildHasher,
+    marker::PhantomData,
+};
+
+/// A [`Rodeo`] that gives every string a key of two different types at once
+///
+/// Useful when migrating between key widths, where an old system still uses the primary keys
+/// while a new one uses the secondary keys. Keys are handed out densely, so a string's
+/// secondary key has the same index as its primary key and the two are translated with a
+/// bounds check instead of a lookup. Interning fails once the secondary key type can't
+/// represent any more strings, so every string always has both keys
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{DualKeyRodeo, MiniSpur, Spur};
+///
+/// let mut rodeo: DualKeyRodeo<Spur, MiniSpur> = DualKeyRodeo::new();
+/// let (old, new) = rodeo.get_or_intern("Strings of things");
+///
+/// assert_eq!(rodeo.to_secondary(&old), Some(new));
+/// assert_eq!(rodeo.to_primary(&new), Some(old));
+/// assert_eq!(rodeo.resolve(&old), rodeo.resolve_secondary(&new));
+/// ```
+///
+/// [`Rodeo`]: crate::Rodeo
+pub struct DualKeyRodeo<K1 = Spur, K2 = MiniSpur, S = RandomState> {
+    rodeo: Rodeo<K1, S>,
+    /// The type of the secondary keys
+    __secondary: PhantomData<K2>,
+}
+
+impl<K1, K2> DualKeyRodeo<K1, K2, RandomState>
+where
+    K1: Key,
+    K2: Key,
+{
+    /// Create an empty `DualKeyRodeo`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{DualKeyRodeo, MicroSpur, Spur};
+    ///
+    /// let rodeo: DualKeyRodeo<Spur, MicroSpur> = DualKeyRodeo::new();
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new() -> Self {
+        Self::from_rodeo_unchecked(Rodeo::new())
+    }
+
+    /// Create an empty `DualKeyRodeo` with the specified capacity
+    ///
+    /// See [`Capacity`] for more details
+    ///
+    /// [`Capacity`]: crate::Capacity
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity(capacity: Capacity) -> Self {
+        Self::from_rodeo_unchecked(Rodeo::with_capacity(capacity))
+    }
+}
+
+impl<K1, K2, S> DualKeyRodeo<K1, K2, S>
+where
+    K1: Key,
+    K2: Key,
+    S: BuildHasher,
+{
+    /// Create an empty `DualKeyRodeo` which will use the given hasher
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self::from_rodeo_unchecked(Rodeo::with_hasher(hash_builder))
+    }
+
+    /// Create an empty `DualKeyRodeo` with the specified capacity which will use the given
+    /// hasher
+    ///
+    /// See [`Capacity`] for more details
+    ///
+    /// [`Capacity`]: crate::Capacity
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity_and_hasher(capacity: Capacity, hash_builder: S) -> Self {
+        Self::from_rodeo_unchecked(Rodeo::with_capacity_and_hasher(capacity, hash_builder))
+    }
+
+    /// Gives every string of an existing [`Rodeo`] a secondary key, returning an error if
+    /// the secondary key type can't represent all of them
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{DualKeyRodeo, Key, MicroSpur, Rodeo, Spur};
+    ///
+    /// let mut rodeo: Rodeo<Spur> = Rodeo::new();
+    /// let key = rodeo.get_or_intern("Strings of things");
+    ///
+    /// let rodeo: DualKeyRodeo<Spur, MicroSpur> = DualKeyRodeo::try_from_rodeo(rodeo).unwrap();
+    /// assert_eq!(rodeo.to_secondary(&key), MicroSpur::try_from_usize(0));
+    /// ```
+    ///
+    /// [`Rodeo`]: crate::Rodeo
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_from_rodeo(rodeo: Rodeo<K1, S>) -> LassoResult<Self> {
+        check_key_space::<K2>(rodeo.len())?;
+
+        Ok(Self::from_rodeo_unchecked(rodeo))
+    }
+
+    /// Get both keys of a string, interning it if it does not yet exist
+    ///
+    /// # Panics
+    ///
+    /// Panics if either key type can't represent the new string's key, see
+    /// [`DualKeyRodeo::try_get_or_intern`] for a fallible version
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn get_or_intern<T>(&mut self, val: T) -> (K1, K2)
+    where
+        T: AsRef<str>,
+    {
+        self.try_get_or_intern(val)
+            .expect("Failed to get or intern string")
+    }
+
+    /// Get both keys of a string, interning it if it does not yet exist
+    ///
+    /// Nothing is interned if the secondary key type can't represent the new string's key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{DualKeyRodeo, MicroSpur, Spur};
+    ///
+    /// let mut rodeo: DualKeyRodeo<Spur, MicroSpur> = DualKeyRodeo::new();
+    /// for i in 0..u8::MAX {
+    ///     rodeo.try_get_or_intern(i.to_string()).unwrap();
+    /// }
+    ///
+    /// assert!(rodeo.try_get_or_intern("one too many").is_err());
+    /// assert!(rodeo.try_get_or_intern("0").is_ok());
+    /// assert_eq!(rodeo.len(), u8::MAX as usize);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn try_get_or_intern<T>(&mut self, val: T) -> LassoResult<(K1, K2)>
+    where
+        T: AsRef<str>,
+    {
+        if let Some(keys) = self.get(val.as_ref()) {
+            return Ok(keys);
+        }
+
+        // New strings get the next key, which has to fit the secondary key type as well
+        let secondary = K2::try_from_usize(self.rodeo.len())
+            .ok_or_else(|| LassoError::new(LassoErrorKind::KeySpaceExhaustion))?;
+        let primary = self.rodeo.try_get_or_intern(val)?;
+        debug_assert_eq!(primary.into_usize(), secondary.into_usize());
+
+        Ok((primary, secondary))
+    }
+
+    /// Get both keys of a string, returning `None` if it doesn't exist
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get<T>(&self, val: T) -> Option<(K1, K2)>
+    where
+        T: AsRef<str>,
+    {
+        let primary = self.rodeo.get(val)?;
+
+        Some((primary, self.to_secondary(&primary)?))
+    }
+
+    /// Returns `true` if the given string has been interned
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains<T>(&self, val: T) -> bool
+    where
+        T: AsRef<str>,
+    {
+        self.rodeo.contains(val)
+    }
+}
+
+impl<K1, K2, S> DualKeyRodeo<K1, K2, S>
+where
+    K1: Key,
+    K2: Key,
+{
+    /// Translates a primary key into the secondary key of the same string, returning `None`
+    /// if it's out of bounds
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn to_secondary(&self, key: &K1) -> Option<K2> {
+        if self.rodeo.contains_key(key) {
+            K2::try_from_usize(key.into_usize())
+        } else {
+            None
+        }
+    }
+
+    /// Translates a secondary key into the primary key of the same string, returning `None`
+    /// if it's out of bounds
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn to_primary(&self, key: &K2) -> Option<K1> {
+        let primary = K1::try_from_usize(key.into_usize())?;
+
+        self.rodeo.contains_key(&primary).then_some(primary)
+    }
+
+    /// Resolves a string by its primary key
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve<'a>(&'a self, key: &K1) -> &'a str {
+        self.rodeo.resolve(key)
+    }
+
+    /// Resolves a string by its primary key, returning `None` if it's out of bounds
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve<'a>(&'a self, key: &K1) -> Option<&'a str> {
+        self.rodeo.try_resolve(key)
+    }
+
+    /// Resolves a string by its secondary key
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve_secondary<'a>(&'a self, key: &K2) -> &'a str {
+        self.try_resolve_secondary(key).expect("Key out of bounds")
+    }
+
+    /// Resolves a string by its secondary key, returning `None` if it's out of bounds
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve_secondary<'a>(&'a self, key: &K2) -> Option<&'a str> {
+        self.rodeo.try_resolve(&self.to_primary(key)?)
+    }
+}
+
+impl<K1, K2, S> DualKeyRodeo<K1, K2, S> {
+    fn from_rodeo_unchecked(rodeo: Rodeo<K1, S>) -> Self {
+        Self {
+            rodeo,
+            __secondary: PhantomData,
+        }
+    }
+
+    /// Returns the number of strings in the interner
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.rodeo.len()
+    }
+
+    /// Returns `true` if the interner contains no strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.rodeo.is_empty()
+    }
+
+    /// Removes every string from the interner, invalidating all previously returned keys
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn clear(&mut self) {
+        self.rodeo.clear();
+    }
+
+    /// Returns the [`Rodeo`] holding the strings, which hands out the primary keys
+    ///
+    /// [`Rodeo`]: crate::Rodeo
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn rodeo(&self) -> &Rodeo<K1, S> {
+        &self.rodeo
+    }
+
+    /// Consumes the `DualKeyRodeo`, returning the [`Rodeo`] holding the strings
+    ///
+    /// Once the migration is done, a reader of the strings can be switched over to the
+    /// secondary keys with [`RodeoReader::try_rekey`]
+    ///
+    /// [`Rodeo`]: crate::Rodeo
+    /// [`RodeoReader::try_rekey`]: crate::RodeoReader::try_rekey
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn into_rodeo(self) -> Rodeo<K1, S> {
+        self.rodeo
+    }
+}
+
+impl Default for DualKeyRodeo<Spur, MiniSpur, RandomState> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K1, K2, S> Debug for DualKeyRodeo<K1, K2, S>
+where
+    K1: Key + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DualKeyRodeo")
+            .field("rodeo", &self.rodeo)
+            .finish()
+    }
+}
+
+impl<K1, K2, S> Clone for DualKeyRodeo<K1, K2, S>
+where
+    K1: Key,
+    S: BuildHasher + Clone,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self::from_rodeo_unchecked(self.rodeo.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DualKeyRodeo, Key, MicroSpur, Rodeo, Spur};
+
+    #[cfg(feature = "no-std")]
+    use alloc::string::ToString;
+
+    #[test]
+    fn translate() {
+        let mut rodeo: DualKeyRodeo<Spur, MicroSpur> = DualKeyRodeo::new();
+        let (a1, a2) = rodeo.get_or_intern("a");
+        let (b1, b2) = rodeo.get_or_intern("b");
+        assert_eq!(rodeo.get_or_intern("a"), (a1, a2));
+        assert_eq!(rodeo.get("b"), Some((b1, b2)));
+        assert_eq!(rodeo.get("c"), None);
+
+        assert_eq!(rodeo.to_secondary(&b1), Some(b2));
+        assert_eq!(rodeo.to_primary(&a2), Some(a1));
+        assert_eq!(rodeo.resolve(&b1), "b");
+        assert_eq!(rodeo.resolve_secondary(&a2), "a");
+
+        let missing = MicroSpur::try_from_usize(2).unwrap();
+        assert_eq!(rodeo.to_primary(&missing), None);
+        assert_eq!(rodeo.try_resolve_secondary(&missing), None);
+        assert_eq!(rodeo.to_secondary(&Spur::try_from_usize(2).unwrap()), None);
+
+        let reader = rodeo.clone().into_rodeo().into_reader();
+        let reader = reader.try_rekey::<MicroSpur>().unwrap();
+        assert_eq!(reader.resolve(&b2), "b");
+    }
+
+    #[test]
+    fn secondary_key_space() {
+        let mut rodeo: DualKeyRodeo<Spur, MicroSpur> = DualKeyRodeo::new();
+        for i in 0..u8::MAX {
+            rodeo.get_or_intern(i.to_string());
+        }
+
+        // Nothing is interned once the secondary keys run out
+        assert!(rodeo.try_get_or_intern("full").is_err());
+        assert!(!rodeo.contains("full"));
+        assert!(rodeo.try_get_or_intern("0").is_ok());
+
+        let mut full: Rodeo<Spur> = rodeo.into_rodeo();
+        assert!(DualKeyRodeo::<Spur, MicroSpur>::try_from_rodeo(full.clone()).is_ok());
+        full.get_or_intern("full");
+        assert!(DualKeyRodeo::<Spur, MicroSpur>::try_from_rodeo(full).is_err());
+    }
+}
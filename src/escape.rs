@@ -0,0 +1,224 @@
+#[cfg(feature = "no-std")]
+use alloc::string::String;
+
+/// How escapes are written in the strings given to [`Rodeo::get_or_intern_unescaped`]
+///
+/// The strings are the contents of a quoted token without its surrounding quotes, so for the
+/// token `"foo\nbar"` the string `foo\nbar` is unescaped into `foo`, a line break and `bar`
+///
+/// [`Rodeo::get_or_intern_unescaped`]: crate::Rodeo::get_or_intern_unescaped
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EscapeStyle {
+    /// The escapes of Rust string literals: `\n`, `\r`, `\t`, `\\`, `\0`, `\'` and `\"`,
+    /// ASCII escapes like `\x7F`, unicode escapes like `\u{1F980}` and a `\` at the end of
+    /// a line, which skips the line break and any whitespace that starts the next line
+    Rust,
+    /// The escapes of JSON strings: `\"`, `\\`, `\/`, `\b`, `\f`, `\n`, `\r`, `\t` and unicode
+    /// escapes like `\u00E9`, where chars outside the basic multilingual plane are written
+    /// as a surrogate pair like `\uD83E\uDD80`
+    Json,
+    /// A doubled quote stands for a single one, like `it''s` within `'it''s'` in SQL or
+    /// `say ""hi""` within `"say ""hi"""` in CSV. A quote that isn't doubled is invalid
+    Doubled(char),
+}
+
+impl EscapeStyle {
+    /// Appends the unescaped form of `raw` to `out`, returning the byte offset of the first
+    /// invalid escape if there is one
+    pub(crate) fn unescape_into(self, raw: &str, out: &mut String) -> Result<(), usize> {
+        let escape = match self {
+            Self::Rust | Self::Json => '\\',
+            Self::Doubled(quote) => quote,
+        };
+        out.reserve(raw.len());
+
+        // Everything between escapes is copied over as a whole
+        let mut rest = raw;
+        while let Some(idx) = rest.find(escape) {
+            out.push_str(&rest[..idx]);
+
+            let start = raw.len() - rest.len() + idx;
+            let escaped = &rest[idx + escape.len_utf8()..];
+            rest = match self {
+                Self::Rust => unescape_rust(escaped, out),
+                Self::Json => unescape_json(escaped, out),
+                Self::Doubled(quote) => unescape_doubled(quote, escaped, out),
+            }
+            .ok_or(start)?;
+        }
+        out.push_str(rest);
+
+        Ok(())
+    }
+}
+
+/// Unescapes the Rust escape at the start of `escaped`, which follows a `\`, returning the
+/// rest of the string or `None` if the escape is invalid
+fn unescape_rust<'a>(escaped: &'a str, out: &mut String) -> Option<&'a str> {
+    let mut chars = escaped.chars();
+    let unescaped = match chars.next()? {
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        '\\' => '\\',
+        '0' => '\0',
+        '\'' => '\'',
+        '"' => '"',
+
+        'x' => {
+            let code = parse_hex(escaped.get(1..3)?).filter(|&code| code < 0x80)?;
+            out.push(char::from_u32(code)?);
+
+            return Some(&escaped[3..]);
+        }
+
+        'u' => {
+            let braced = escaped[1..].strip_prefix('{')?;
+            let end = braced.find('}').filter(|&end| end <= 6)?;
+            out.push(char::from_u32(parse_hex(&braced[..end])?)?);
+
+            return Some(&braced[end + 1..]);
+        }
+
+        '\n' => return Some(chars.as_str().trim_start_matches([' ', '\t', '\n', '\r'])),
+
+        _ => return None,
+    };
+    out.push(unescaped);
+
+    Some(chars.as_str())
+}
+
+/// Unescapes the JSON escape at the start of `escaped`, which follows a `\`, returning the
+/// rest of the string or `None` if the escape is invalid
+fn unescape_json<'a>(escaped: &'a str, out: &mut String) -> Option<&'a str> {
+    let mut chars = escaped.chars();
+    let unescaped = match chars.next()? {
+        '"' => '"',
+        '\\' => '\\',
+        '/' => '/',
+        'b' => '\u{8}',
+        'f' => '\u{c}',
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+
+        'u' => {
+            let high = parse_hex(escaped.get(1..5)?)?;
+
+            // Chars outside of the basic multilingual plane are split into a surrogate pair,
+            // anything else that's a surrogate is rejected by `char::from_u32`
+            let (code, rest) = if (0xD800..0xDC00).contains(&high) {
+                let low = escaped[5..].strip_prefix("\\u")?;
+                let low = parse_hex(low.get(..4)?).filter(|low| (0xDC00..0xE000).contains(low))?;
+
+                (
+                    0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00),
+                    &escaped[11..],
+                )
+            } else {
+                (high, &escaped[5..])
+            };
+            out.push(char::from_u32(code)?);
+
+            return Some(rest);
+        }
+
+        _ => return None,
+    };
+    out.push(unescaped);
+
+    Some(chars.as_str())
+}
+
+/// Unescapes the second quote of a doubled quote at the start of `escaped`, which follows
+/// the first, returning the rest of the string or `None` if the quote wasn't doubled
+fn unescape_doubled<'a>(quote: char, escaped: &'a str, out: &mut String) -> Option<&'a str> {
+    let rest = escaped.strip_prefix(quote)?;
+    out.push(quote);
+
+    Some(rest)
+}
+
+/// Parses a non-empty string made of nothing but hex digits
+fn parse_hex(digits: &str) -> Option<u32> {
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    u32::from_str_radix(digits, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::EscapeStyle;
+    #[cfg(feature = "no-std")]
+    use alloc::string::String;
+
+    fn unescape(raw: &str, style: EscapeStyle) -> Result<String, usize> {
+        let mut out = String::new();
+        style.unescape_into(raw, &mut out).map(|()| out)
+    }
+
+    #[test]
+    fn rust() {
+        let style = EscapeStyle::Rust;
+
+        assert_eq!(unescape("plain", style).unwrap(), "plain");
+        assert_eq!(
+            unescape(r#"a\nb\tc\r\\\0\'\""#, style).unwrap(),
+            "a\nb\tc\r\\\0'\""
+        );
+        assert_eq!(unescape(r"\x41\x7f", style).unwrap(), "A\x7f");
+        assert_eq!(unescape(r"\u{e9}\u{1F980}", style).unwrap(), "é🦀");
+        assert_eq!(unescape("one \\\n    two", style).unwrap(), "one two");
+
+        assert_eq!(unescape(r"ab\", style), Err(2));
+        assert_eq!(unescape(r"\q", style), Err(0));
+        assert_eq!(unescape(r"é\x80", style), Err(2));
+        assert_eq!(unescape(r"\x+1", style), Err(0));
+        assert_eq!(unescape(r"\x4", style), Err(0));
+        assert_eq!(unescape(r"\u{}", style), Err(0));
+        assert_eq!(unescape(r"\u{1234567}", style), Err(0));
+        assert_eq!(unescape(r"\u{D800}", style), Err(0));
+        assert_eq!(unescape(r"\u{41", style), Err(0));
+        assert_eq!(unescape(r"\u41", style), Err(0));
+    }
+
+    #[test]
+    fn json() {
+        let style = EscapeStyle::Json;
+
+        assert_eq!(
+            unescape(r#"\"\\\/\b\f\n\r\t"#, style).unwrap(),
+            "\"\\/\u{8}\u{c}\n\r\t"
+        );
+        assert_eq!(unescape(r"caf\u00e9", style).unwrap(), "café");
+        assert_eq!(unescape(r"\uD83E\uDD80!", style).unwrap(), "🦀!");
+
+        assert_eq!(unescape(r"\'", style), Err(0));
+        assert_eq!(unescape(r"ab\u12", style), Err(2));
+        assert_eq!(unescape(r"\uD83E", style), Err(0));
+        assert_eq!(unescape(r"\uD83E\u0041", style), Err(0));
+        assert_eq!(unescape(r"\uDD80", style), Err(0));
+    }
+
+    #[test]
+    fn doubled() {
+        assert_eq!(
+            unescape("it''s", EscapeStyle::Doubled('\'')).unwrap(),
+            "it's"
+        );
+        assert_eq!(
+            unescape(r#"say ""hi"""#, EscapeStyle::Doubled('"')).unwrap(),
+            r#"say "hi""#
+        );
+        assert_eq!(
+            unescape(r"no \escapes", EscapeStyle::Doubled('\'')).unwrap(),
+            r"no \escapes"
+        );
+
+        assert_eq!(unescape("it's", EscapeStyle::Doubled('\'')), Err(2));
+        assert_eq!(unescape("é」", EscapeStyle::Doubled('」')), Err(2));
+    }
+}
@@ -0,0 +1,352 @@
+use core::{
+    fmt::{self, Debug},
+    hash::{BuildHasher, Hasher},
+};
+
+#[cfg(not(feature = "no-std"))]
+use crate::{Capacity, Rodeo, Spur};
+#[cfg(not(feature = "no-std"))]
+use std::{
+    collections::hash_map::{DefaultHasher, RandomState as StdState},
+    hint::black_box,
+    time::{Duration, Instant},
+    vec::Vec,
+};
+
+/// The hash functions a [`DynamicState`] can be switched between at runtime
+///
+/// Which one interns fastest depends heavily on the lengths of the strings being interned,
+/// so instead of picking one at compile time with a type parameter the choice can be made
+/// once the program is running, for example from the results of [`bench_hashers`]. `Fx`
+/// and `Wy` are only available with the `fxhasher` and `wyhasher` features
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{DynamicState, HasherKind, Rodeo, RodeoBuilder, Spur};
+///
+/// for &kind in HasherKind::ALL {
+///     let mut rodeo: Rodeo<Spur, DynamicState> = RodeoBuilder::new().hasher_kind(kind).build();
+///     let key = rodeo.get_or_intern("Hello, world!");
+///
+///     assert_eq!(rodeo.get("Hello, world!"), Some(key));
+///     assert_eq!(rodeo.hasher_kind(), kind);
+/// }
+/// ```
+///
+/// [`bench_hashers`]: crate::bench_hashers
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HasherKind {
+    /// The standard library's SipHash 1-3, which is resistant to HashDoS attacks
+    #[cfg(not(feature = "no-std"))]
+    Std,
+    /// [`ahash`](https://crates.io/crates/ahash), which is fast on strings of every length
+    #[cfg(feature = "ahasher")]
+    AHash,
+    /// [`fxhash`](https://crates.io/crates/fxhash), the hasher used by rustc, which is
+    /// usually fastest on short strings but isn't seeded
+    #[cfg(feature = "fxhasher")]
+    Fx,
+    /// [`wyhash`](https://crates.io/crates/wyhash), which does well on long strings
+    #[cfg(feature = "wyhasher")]
+    Wy,
+}
+
+impl HasherKind {
+    /// Every hasher kind enabled by the crate's features
+    pub const ALL: &'static [Self] = &[
+        #[cfg(not(feature = "no-std"))]
+        Self::Std,
+        #[cfg(feature = "ahasher")]
+        Self::AHash,
+        #[cfg(feature = "fxhasher")]
+        Self::Fx,
+        #[cfg(feature = "wyhasher")]
+        Self::Wy,
+    ];
+}
+
+impl Default for HasherKind {
+    /// The kind used by the crate's default `RandomState`
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        #[cfg(feature = "ahasher")]
+        return Self::AHash;
+
+        #[cfg(not(feature = "ahasher"))]
+        return Self::Std;
+    }
+}
+
+/// A [`BuildHasher`] whose hash function is chosen at runtime with a [`HasherKind`]
+///
+/// Interners built with different kinds still have the same type, so the choice can come
+/// from configuration or a benchmark without making the rest of the program generic over it.
+/// Usually made through [`RodeoBuilder::hasher_kind`]
+///
+/// [`RodeoBuilder::hasher_kind`]: crate::RodeoBuilder::hasher_kind
+#[derive(Clone)]
+pub struct DynamicState {
+    state: State,
+}
+
+#[derive(Clone)]
+enum State {
+    #[cfg(not(feature = "no-std"))]
+    Std(StdState),
+    #[cfg(feature = "ahasher")]
+    AHash(ahash::RandomState),
+    #[cfg(feature = "fxhasher")]
+    Fx,
+    #[cfg(feature = "wyhasher")]
+    Wy(u64),
+}
+
+impl DynamicState {
+    /// Creates a randomly seeded state for the given kind of hasher
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new(kind: HasherKind) -> Self {
+        let state = match kind {
+            #[cfg(not(feature = "no-std"))]
+            HasherKind::Std => State::Std(StdState::new()),
+            #[cfg(feature = "ahasher")]
+            HasherKind::AHash => State::AHash(ahash::RandomState::new()),
+            #[cfg(feature = "fxhasher")]
+            HasherKind::Fx => State::Fx,
+            // Borrow the seed from the default hasher, which is randomly seeded
+            #[cfg(feature = "wyhasher")]
+            HasherKind::Wy => State::Wy(crate::hasher::RandomState::new().hash_one(0_u64)),
+        };
+
+        Self { state }
+    }
+
+    /// Returns the kind of hasher the state builds
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn kind(&self) -> HasherKind {
+        match self.state {
+            #[cfg(not(feature = "no-std"))]
+            State::Std(_) => HasherKind::Std,
+            #[cfg(feature = "ahasher")]
+            State::AHash(_) => HasherKind::AHash,
+            #[cfg(feature = "fxhasher")]
+            State::Fx => HasherKind::Fx,
+            #[cfg(feature = "wyhasher")]
+            State::Wy(_) => HasherKind::Wy,
+        }
+    }
+}
+
+impl BuildHasher for DynamicState {
+    type Hasher = DynamicHasher;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn build_hasher(&self) -> Self::Hasher {
+        let hasher = match &self.state {
+            #[cfg(not(feature = "no-std"))]
+            State::Std(state) => Inner::Std(state.build_hasher()),
+            #[cfg(feature = "ahasher")]
+            State::AHash(state) => Inner::AHash(state.build_hasher()),
+            #[cfg(feature = "fxhasher")]
+            State::Fx => Inner::Fx(fxhash::FxHasher::default()),
+            #[cfg(feature = "wyhasher")]
+            State::Wy(seed) => Inner::Wy(wyhash::WyHash::with_seed(*seed)),
+        };
+
+        DynamicHasher { hasher }
+    }
+}
+
+impl Default for DynamicState {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new(HasherKind::default())
+    }
+}
+
+impl Debug for DynamicState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicState")
+            .field("kind", &self.kind())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The [`Hasher`] built by a [`DynamicState`]
+pub struct DynamicHasher {
+    hasher: Inner,
+}
+
+enum Inner {
+    #[cfg(not(feature = "no-std"))]
+    Std(DefaultHasher),
+    #[cfg(feature = "ahasher")]
+    AHash(ahash::AHasher),
+    #[cfg(feature = "fxhasher")]
+    Fx(fxhash::FxHasher),
+    #[cfg(feature = "wyhasher")]
+    Wy(wyhash::WyHash),
+}
+
+impl Hasher for DynamicHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        match &mut self.hasher {
+            #[cfg(not(feature = "no-std"))]
+            Inner::Std(hasher) => hasher.write(bytes),
+            #[cfg(feature = "ahasher")]
+            Inner::AHash(hasher) => hasher.write(bytes),
+            #[cfg(feature = "fxhasher")]
+            Inner::Fx(hasher) => hasher.write(bytes),
+            #[cfg(feature = "wyhasher")]
+            Inner::Wy(hasher) => hasher.write(bytes),
+        }
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        match &self.hasher {
+            #[cfg(not(feature = "no-std"))]
+            Inner::Std(hasher) => hasher.finish(),
+            #[cfg(feature = "ahasher")]
+            Inner::AHash(hasher) => hasher.finish(),
+            #[cfg(feature = "fxhasher")]
+            Inner::Fx(hasher) => hasher.finish(),
+            #[cfg(feature = "wyhasher")]
+            Inner::Wy(hasher) => hasher.finish(),
+        }
+    }
+}
+
+impl Debug for DynamicHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicHasher").finish_non_exhaustive()
+    }
+}
+
+/// How long each kind of hasher took to intern a sample of strings, made by [`bench_hashers`]
+///
+/// [`bench_hashers`]: crate::bench_hashers
+#[cfg(not(feature = "no-std"))]
+#[derive(Debug, Clone)]
+pub struct HasherBenchmark {
+    /// Every kind with its fastest time, fastest first
+    timings: Vec<(HasherKind, Duration)>,
+}
+
+#[cfg(not(feature = "no-std"))]
+impl HasherBenchmark {
+    /// Returns the kind of hasher that interned the sample fastest
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn recommended(&self) -> HasherKind {
+        self.timings[0].0
+    }
+
+    /// Returns the time every kind of hasher took to intern the sample and look every string
+    /// up again, fastest first
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn timings(&self) -> &[(HasherKind, Duration)] {
+        &self.timings
+    }
+}
+
+/// Measures how quickly every available [`HasherKind`] interns a sample of strings and
+/// recommends the fastest
+///
+/// Each kind interns every string of the sample into a fresh [`Rodeo`] and then looks them
+/// all up again, as interning usually sees a mix of new and repeated strings. This is
+/// repeated a few times and the fastest run is kept. The sample should look like the
+/// strings the program really interns, since the best hasher depends on their lengths
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{bench_hashers, DynamicState, Rodeo, RodeoBuilder, Spur};
+///
+/// let sample = ["fn", "main", "println", "String", "x", "y"];
+/// let benchmark = bench_hashers(&sample);
+/// assert!(!benchmark.timings().is_empty());
+///
+/// let rodeo: Rodeo<Spur, DynamicState> = RodeoBuilder::new()
+///     .hasher_kind(benchmark.recommended())
+///     .build();
+/// ```
+///
+/// [`Rodeo`]: crate::Rodeo
+#[cfg(not(feature = "no-std"))]
+#[cfg_attr(feature = "inline-more", inline)]
+pub fn bench_hashers(sample: &[&str]) -> HasherBenchmark {
+    const RUNS: usize = 5;
+
+    let mut timings: Vec<(HasherKind, Duration)> = HasherKind::ALL
+        .iter()
+        .map(|&kind| {
+            let fastest = (0..RUNS)
+                .map(|_| {
+                    let mut rodeo: Rodeo<Spur, DynamicState> = Rodeo::with_capacity_and_hasher(
+                        Capacity::for_strings(sample.len()),
+                        DynamicState::new(kind),
+                    );
+
+                    let start = Instant::now();
+                    for string in sample {
+                        black_box(rodeo.get_or_intern(string));
+                    }
+                    for string in sample {
+                        black_box(rodeo.get(string));
+                    }
+
+                    start.elapsed()
+                })
+                .min()
+                .unwrap_or_default();
+
+            (kind, fastest)
+        })
+        .collect();
+    timings.sort_by_key(|&(_, time)| time);
+
+    HasherBenchmark { timings }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DynamicState, HasherKind, Rodeo, RodeoBuilder, Spur};
+    use core::hash::BuildHasher;
+
+    #[cfg(feature = "no-std")]
+    use alloc::{string::ToString, vec::Vec};
+
+    #[test]
+    fn every_kind() {
+        for &kind in HasherKind::ALL {
+            let state = DynamicState::new(kind);
+            assert_eq!(state.kind(), kind);
+            assert_eq!(state.hash_one("a"), state.clone().hash_one("a"));
+
+            let mut rodeo: Rodeo<Spur, DynamicState> = RodeoBuilder::new().hasher(state).build();
+            let keys: Vec<Spur> = (0..100)
+                .map(|i| rodeo.get_or_intern(i.to_string()))
+                .collect();
+            for (i, key) in keys.iter().enumerate() {
+                assert_eq!(rodeo.get(i.to_string()), Some(*key));
+            }
+        }
+
+        assert_eq!(DynamicState::default().kind(), HasherKind::default());
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-std"))]
+    fn bench_hashers() {
+        let sample = ["a", "bb", "a", "ccc"];
+        let benchmark = crate::bench_hashers(&sample);
+
+        assert_eq!(benchmark.timings().len(), HasherKind::ALL.len());
+        assert_eq!(benchmark.recommended(), benchmark.timings()[0].0);
+        assert!(benchmark
+            .timings()
+            .windows(2)
+            .all(|pair| pair[0].1 <= pair[1].1));
+    }
+}
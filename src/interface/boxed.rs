@@ -1,7 +1,7 @@
 use super::{Interner, IntoReader, IntoResolver, Reader, Resolver};
 use crate::{Key, LassoResult};
 #[cfg(feature = "no-std")]
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 
 impl<K, I> Interner<K> for Box<I>
 where
@@ -25,7 +25,7 @@ where
 
     #[cfg_attr(feature = "inline-more", inline)]
     fn try_get_or_intern_static(&mut self, val: &'static str) -> LassoResult<K> {
-        self.try_get_or_intern(val)
+        (**self).try_get_or_intern_static(val)
     }
 }
 
@@ -37,7 +37,6 @@ where
     type Reader = <I as IntoReader<K>>::Reader;
 
     #[cfg_attr(feature = "inline-more", inline)]
-    #[must_use]
     fn into_reader(self) -> Self::Reader
     where
         Self: 'static,
@@ -46,7 +45,6 @@ where
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
-    #[must_use]
     fn into_reader_boxed(self: Box<Self>) -> Self::Reader
     where
         Self: 'static,
@@ -79,7 +77,6 @@ where
     type Resolver = <I as IntoResolver<K>>::Resolver;
 
     #[cfg_attr(feature = "inline-more", inline)]
-    #[must_use]
     fn into_resolver(self) -> Self::Resolver
     where
         Self: 'static,
@@ -88,7 +85,6 @@ where
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
-    #[must_use]
     fn into_resolver_boxed(self: Box<Self>) -> Self::Resolver
     where
         Self: 'static,
@@ -117,6 +113,16 @@ where
         unsafe { (**self).resolve_unchecked(key) }
     }
 
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn resolve_many<'a>(&'a self, keys: &[K]) -> Vec<&'a str> {
+        (**self).resolve_many(keys)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn try_resolve_many<'a>(&'a self, keys: &[K]) -> Option<Vec<&'a str>> {
+        (**self).try_resolve_many(keys)
+    }
+
     #[cfg_attr(feature = "inline-more", inline)]
     fn contains_key(&self, key: &K) -> bool {
         (**self).contains_key(key)
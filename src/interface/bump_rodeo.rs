@@ -0,0 +1,78 @@
+//! Implementations of [`Interner`], [`Reader`] and [`Resolver`] for [`BumpRodeo`]
+#![cfg(feature = "bumpalo")]
+
+use crate::{BumpRodeo, Interner, Key, LassoResult, Reader, Resolver};
+use core::hash::BuildHasher;
+
+impl<K, S> Interner<K> for BumpRodeo<'_, K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn get_or_intern(&mut self, val: &str) -> K {
+        self.get_or_intern(val)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn try_get_or_intern(&mut self, val: &str) -> LassoResult<K> {
+        self.try_get_or_intern(val)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn get_or_intern_static(&mut self, val: &'static str) -> K {
+        self.get_or_intern_static(val)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn try_get_or_intern_static(&mut self, val: &'static str) -> LassoResult<K> {
+        self.try_get_or_intern_static(val)
+    }
+}
+
+impl<K, S> Reader<K> for BumpRodeo<'_, K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn get(&self, val: &str) -> Option<K> {
+        self.get(val)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn contains(&self, val: &str) -> bool {
+        self.contains(val)
+    }
+}
+
+impl<K, S> Resolver<K> for BumpRodeo<'_, K, S>
+where
+    K: Key,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn resolve<'a>(&'a self, key: &K) -> &'a str {
+        self.resolve(key)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn try_resolve<'a>(&'a self, key: &K) -> Option<&'a str> {
+        self.try_resolve(key)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    unsafe fn resolve_unchecked<'a>(&'a self, key: &K) -> &'a str {
+        // Safety: The caller promises the key is valid
+        unsafe { self.resolve_unchecked(key) }
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
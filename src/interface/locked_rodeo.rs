@@ -0,0 +1,141 @@
+//! Implementations of [`Interner`], [`Reader`] and [`Resolver`] for [`LockedRodeo`]
+#![cfg(feature = "locked")]
+
+use crate::*;
+#[cfg(feature = "no-std")]
+use alloc::boxed::Box;
+use core::hash::BuildHasher;
+use lock_api::RawMutex;
+
+impl<R, K, S> Interner<K> for LockedRodeo<R, K, S>
+where
+    R: RawMutex,
+    K: Key,
+    S: BuildHasher,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn get_or_intern(&mut self, val: &str) -> K {
+        (*self).get_or_intern(val)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn try_get_or_intern(&mut self, val: &str) -> LassoResult<K> {
+        (*self).try_get_or_intern(val)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn get_or_intern_static(&mut self, val: &'static str) -> K {
+        (*self).get_or_intern_static(val)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn try_get_or_intern_static(&mut self, val: &'static str) -> LassoResult<K> {
+        (*self).try_get_or_intern_static(val)
+    }
+}
+
+impl<R, K, S> IntoReaderAndResolver<K> for LockedRodeo<R, K, S>
+where
+    R: RawMutex,
+    K: Key,
+    S: BuildHasher,
+{
+}
+
+impl<R, K, S> IntoReader<K> for LockedRodeo<R, K, S>
+where
+    R: RawMutex,
+    K: Key,
+    S: BuildHasher,
+{
+    type Reader = RodeoReader<K, S>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_reader(self) -> Self::Reader
+    where
+        Self: 'static,
+    {
+        self.into_reader()
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_reader_boxed(self: Box<Self>) -> Self::Reader
+    where
+        Self: 'static,
+    {
+        LockedRodeo::into_reader(*self)
+    }
+}
+
+impl<R, K, S> Reader<K> for LockedRodeo<R, K, S>
+where
+    R: RawMutex,
+    K: Key,
+    S: BuildHasher,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn get(&self, val: &str) -> Option<K> {
+        self.get(val)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn contains(&self, val: &str) -> bool {
+        self.contains(val)
+    }
+}
+
+impl<R, K, S> IntoResolver<K> for LockedRodeo<R, K, S>
+where
+    R: RawMutex,
+    K: Key,
+    S: BuildHasher,
+{
+    type Resolver = RodeoResolver<K>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_resolver(self) -> Self::Resolver
+    where
+        Self: 'static,
+    {
+        self.into_resolver()
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_resolver_boxed(self: Box<Self>) -> Self::Resolver
+    where
+        Self: 'static,
+    {
+        LockedRodeo::into_resolver(*self)
+    }
+}
+
+impl<R, K, S> Resolver<K> for LockedRodeo<R, K, S>
+where
+    R: RawMutex,
+    K: Key,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn resolve<'a>(&'a self, key: &K) -> &'a str {
+        self.resolve(key)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn try_resolve<'a>(&'a self, key: &K) -> Option<&'a str> {
+        self.try_resolve(key)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    unsafe fn resolve_unchecked<'a>(&'a self, key: &K) -> &'a str {
+        unsafe { self.resolve_unchecked(key) }
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
@@ -1,14 +1,72 @@
 mod boxed;
+mod bump_rodeo;
+mod locked_rodeo;
+mod persistent_rodeo;
+mod resolver_view;
 mod rodeo;
 mod rodeo_reader;
 mod rodeo_resolver;
+mod shared;
+mod static_resolver;
 mod tests;
 mod threaded_ref;
 mod threaded_rodeo;
+mod tiny_rodeo;
 
-use crate::{Key, LassoResult, Spur};
+use crate::{BufferTooSmall, DisplayKey, Key, LassoResult, Spur};
+use alloc::borrow::Cow;
 #[cfg(feature = "no-std")]
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+/// A dynamically dispatched [`Interner`], allowing interners with differing types to be used
+/// interchangeably
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{DynInterner, Rodeo};
+///
+/// let mut interner: DynInterner = Box::new(Rodeo::default());
+/// let key = interner.get_or_intern("Strings of things with wings and dings");
+///
+/// assert_eq!("Strings of things with wings and dings", interner.resolve(&key));
+/// ```
+///
+pub type DynInterner<K = Spur> = Box<dyn Interner<K>>;
+
+/// A dynamically dispatched [`Reader`], allowing readers with differing types to be used
+/// interchangeably
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{DynReader, Rodeo};
+///
+/// let mut rodeo = Rodeo::default();
+/// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+///
+/// let reader: DynReader = Box::new(rodeo.into_reader());
+/// assert_eq!(Some(key), reader.get("Strings of things with wings and dings"));
+/// ```
+///
+pub type DynReader<K = Spur> = Box<dyn Reader<K>>;
+
+/// A dynamically dispatched [`Resolver`], allowing resolvers with differing types to be used
+/// interchangeably
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{DynResolver, Rodeo};
+///
+/// let mut rodeo = Rodeo::default();
+/// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+///
+/// let resolver: DynResolver = Box::new(rodeo.into_resolver());
+/// assert_eq!("Strings of things with wings and dings", resolver.resolve(&key));
+/// ```
+///
+pub type DynResolver<K = Spur> = Box<dyn Resolver<K>>;
 
 /// A generic interface over any underlying interner, allowing storing and accessing
 /// interned strings
@@ -16,6 +74,8 @@ use alloc::boxed::Box;
 /// Note that because single-threaded [`Rodeo`](crate::Rodeo)s require mutable access to use, this
 /// trait does so as well. For use with [`ThreadedRodeo`](crate::ThreadedRodeo), the trait is
 /// implemented for `&ThreadedRodeo` as well to allow access through shared references.
+///
+/// This trait is object safe, see [`DynInterner`] for a boxed trait object
 pub trait Interner<K = Spur>: Reader<K> + Resolver<K> {
     /// Get the key for a string, interning it if it does not yet exist
     ///
@@ -73,6 +133,96 @@ where
     }
 }
 
+/// Values that can be interned into any [`Interner`], allowing generic code to accept anything
+/// internable instead of only strings
+///
+/// Implemented for `str`, `String` and `Cow<str>`, user types can implement it to intern
+/// themselves however they see fit, such as by formatting into a reused buffer
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{Interner, Rodeo, Spur, ToInterned};
+///
+/// struct ModulePath<'a>(&'a [&'a str]);
+///
+/// impl ToInterned for ModulePath<'_> {
+///     fn intern_into<I>(&self, interner: &mut I) -> Spur
+///     where
+///         I: Interner + ?Sized,
+///     {
+///         interner.get_or_intern(&self.0.join("::"))
+///     }
+/// }
+///
+/// fn intern_all<T: ToInterned>(rodeo: &mut Rodeo, values: &[T]) -> Vec<Spur> {
+///     values.iter().map(|value| value.intern_into(rodeo)).collect()
+/// }
+///
+/// let mut rodeo = Rodeo::default();
+/// let path = ModulePath(&["core", "fmt"]).intern_into(&mut rodeo);
+/// assert_eq!(rodeo.resolve(&path), "core::fmt");
+///
+/// let keys = intern_all(&mut rodeo, &["core::fmt", "core::hash"]);
+/// assert_eq!(keys[0], path);
+/// ```
+///
+pub trait ToInterned<K = Spur> {
+    /// Interns the current value into the given interner, returning its key
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interner is unable to give out any more keys, see
+    /// [`Interner::get_or_intern()`]
+    ///
+    fn intern_into<I>(&self, interner: &mut I) -> K
+    where
+        I: Interner<K> + ?Sized;
+}
+
+impl<K> ToInterned<K> for str {
+    #[inline]
+    fn intern_into<I>(&self, interner: &mut I) -> K
+    where
+        I: Interner<K> + ?Sized,
+    {
+        interner.get_or_intern(self)
+    }
+}
+
+impl<K> ToInterned<K> for String {
+    #[inline]
+    fn intern_into<I>(&self, interner: &mut I) -> K
+    where
+        I: Interner<K> + ?Sized,
+    {
+        interner.get_or_intern(self)
+    }
+}
+
+impl<K> ToInterned<K> for Cow<'_, str> {
+    #[inline]
+    fn intern_into<I>(&self, interner: &mut I) -> K
+    where
+        I: Interner<K> + ?Sized,
+    {
+        interner.get_or_intern(self)
+    }
+}
+
+impl<T, K> ToInterned<K> for &T
+where
+    T: ToInterned<K> + ?Sized,
+{
+    #[inline]
+    fn intern_into<I>(&self, interner: &mut I) -> K
+    where
+        I: Interner<K> + ?Sized,
+    {
+        <T as ToInterned<K>>::intern_into(self, interner)
+    }
+}
+
 /// A generic interface over interners that can be turned into both a [`Reader`] and a [`Resolver`]
 /// directly.
 pub trait IntoReaderAndResolver<K = Spur>: IntoReader<K> + IntoResolver<K>
@@ -193,6 +343,61 @@ pub trait Resolver<K = Spur> {
     ///
     unsafe fn resolve_unchecked<'a>(&'a self, key: &K) -> &'a str;
 
+    /// Resolves a batch of keys into their strings, in the same order as the keys
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the keys are not contained in the current [`Resolver`]
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn resolve_many<'a>(&'a self, keys: &[K]) -> Vec<&'a str> {
+        keys.iter().map(|key| self.resolve(key)).collect()
+    }
+
+    /// Resolves a batch of keys into their strings, in the same order as the keys,
+    /// returning `None` if any of them cannot be found
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn try_resolve_many<'a>(&'a self, keys: &[K]) -> Option<Vec<&'a str>> {
+        keys.iter().map(|key| self.try_resolve(key)).collect()
+    }
+
+    /// Copies the string of the given key into `buf`, returning the number of bytes written
+    ///
+    /// Nothing is allocated and no reference to the interner outlives the call, so strings can
+    /// be copied straight into stack or DMA buffers. If the string doesn't fit the buffer is left
+    /// untouched and the error holds the string's length
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not contained in the current [`Resolver`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Resolver, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// let mut buf = [0; 16];
+    /// let len = rodeo.resolve_into(&key, &mut buf).unwrap();
+    /// assert_eq!(&buf[..len], b"Hello, world!");
+    ///
+    /// let err = rodeo.resolve_into(&key, &mut [0; 4]).unwrap_err();
+    /// assert_eq!(err.required(), 13);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn resolve_into(&self, key: &K, buf: &mut [u8]) -> Result<usize, BufferTooSmall> {
+        let string = self.resolve(key).as_bytes();
+
+        buf.get_mut(..string.len())
+            .ok_or(BufferTooSmall::new(string.len()))?
+            .copy_from_slice(string);
+
+        Ok(string.len())
+    }
+
     /// Returns `true` if the current interner contains the given key
     fn contains_key(&self, key: &K) -> bool;
 
@@ -204,6 +409,61 @@ pub trait Resolver<K = Spur> {
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Returns an adaptor that formats the string of the given key
+    ///
+    /// Nothing is resolved until the adaptor is formatted, and keys that aren't contained in
+    /// the current [`Resolver`] are written as `<invalid key #N>` instead of panicking, which
+    /// makes it safe to use inside of log statements
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, Resolver, Rodeo, Spur};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// assert_eq!(format!("sym {}", rodeo.display(key)), "sym Hello, world!");
+    ///
+    /// let missing = Spur::try_from_usize(10).unwrap();
+    /// assert_eq!(rodeo.display(missing).to_string(), "<invalid key #10>");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn display(&self, key: K) -> DisplayKey<'_, K, Self>
+    where
+        Self: Sized,
+    {
+        DisplayKey::new(self, key)
+    }
+
+    /// Returns an adaptor that formats the string of the given key, returning `None` if the key
+    /// isn't contained in the current [`Resolver`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, Resolver, Rodeo, Spur};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// assert_eq!(rodeo.try_display(key).unwrap().to_string(), "Hello, world!");
+    /// assert!(rodeo.try_display(Spur::try_from_usize(10).unwrap()).is_none());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn try_display(&self, key: K) -> Option<DisplayKey<'_, K, Self>>
+    where
+        Self: Sized,
+    {
+        if self.contains_key(&key) {
+            Some(DisplayKey::new(self, key))
+        } else {
+            None
+        }
+    }
 }
 
 impl<T, K> Resolver<K> for &T
@@ -225,6 +485,16 @@ where
         unsafe { <T as Resolver<K>>::resolve_unchecked(self, key) }
     }
 
+    #[inline]
+    fn resolve_many<'a>(&'a self, keys: &[K]) -> Vec<&'a str> {
+        <T as Resolver<K>>::resolve_many(self, keys)
+    }
+
+    #[inline]
+    fn try_resolve_many<'a>(&'a self, keys: &[K]) -> Option<Vec<&'a str>> {
+        <T as Resolver<K>>::try_resolve_many(self, keys)
+    }
+
     #[inline]
     fn contains_key(&self, key: &K) -> bool {
         <T as Resolver<K>>::contains_key(self, key)
@@ -255,6 +525,16 @@ where
         unsafe { <T as Resolver<K>>::resolve_unchecked(self, key) }
     }
 
+    #[inline]
+    fn resolve_many<'a>(&'a self, keys: &[K]) -> Vec<&'a str> {
+        <T as Resolver<K>>::resolve_many(self, keys)
+    }
+
+    #[inline]
+    fn try_resolve_many<'a>(&'a self, keys: &[K]) -> Option<Vec<&'a str>> {
+        <T as Resolver<K>>::try_resolve_many(self, keys)
+    }
+
     #[inline]
     fn contains_key(&self, key: &K) -> bool {
         <T as Resolver<K>>::contains_key(self, key)
@@ -12,21 +12,25 @@ where
     S: BuildHasher,
 {
     #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
     fn get_or_intern(&mut self, val: &str) -> K {
         self.get_or_intern(val)
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
     fn try_get_or_intern(&mut self, val: &str) -> LassoResult<K> {
         self.try_get_or_intern(val)
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
     fn get_or_intern_static(&mut self, val: &'static str) -> K {
         self.get_or_intern_static(val)
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
     fn try_get_or_intern_static(&mut self, val: &'static str) -> LassoResult<K> {
         self.try_get_or_intern_static(val)
     }
@@ -47,7 +51,6 @@ where
     type Reader = RodeoReader<K, S>;
 
     #[cfg_attr(feature = "inline-more", inline)]
-    #[must_use]
     fn into_reader(self) -> Self::Reader
     where
         Self: 'static,
@@ -56,7 +59,6 @@ where
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
-    #[must_use]
     fn into_reader_boxed(self: Box<Self>) -> Self::Reader
     where
         Self: 'static,
@@ -89,7 +91,6 @@ where
     type Resolver = RodeoResolver<K>;
 
     #[cfg_attr(feature = "inline-more", inline)]
-    #[must_use]
     fn into_resolver(self) -> Self::Resolver
     where
         Self: 'static,
@@ -98,7 +99,6 @@ where
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
-    #[must_use]
     fn into_resolver_boxed(self: Box<Self>) -> Self::Resolver
     where
         Self: 'static,
@@ -29,7 +29,6 @@ where
     type Resolver = RodeoResolver<K>;
 
     #[cfg_attr(feature = "inline-more", inline)]
-    #[must_use]
     fn into_resolver(self) -> Self::Resolver
     where
         Self: 'static,
@@ -38,7 +37,6 @@ where
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
-    #[must_use]
     fn into_resolver_boxed(self: Box<Self>) -> Self::Resolver
     where
         Self: 'static,
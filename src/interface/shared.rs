@@ -0,0 +1,153 @@
+use super::{Reader, Resolver};
+use crate::Key;
+#[cfg(feature = "no-std")]
+use alloc::{rc::Rc, sync::Arc, vec::Vec};
+#[cfg(not(feature = "no-std"))]
+use std::{rc::Rc, sync::Arc};
+
+macro_rules! impl_shared {
+    ($($ptr:ident),* $(,)?) => {
+        $(
+            impl<K, R> Reader<K> for $ptr<R>
+            where
+                K: Key,
+                R: Reader<K> + ?Sized,
+            {
+                #[cfg_attr(feature = "inline-more", inline)]
+                fn get(&self, val: &str) -> Option<K> {
+                    (**self).get(val)
+                }
+
+                #[cfg_attr(feature = "inline-more", inline)]
+                fn contains(&self, val: &str) -> bool {
+                    (**self).contains(val)
+                }
+            }
+
+            impl<K, R> Resolver<K> for $ptr<R>
+            where
+                K: Key,
+                R: Resolver<K> + ?Sized,
+            {
+                #[cfg_attr(feature = "inline-more", inline)]
+                fn resolve<'a>(&'a self, key: &K) -> &'a str {
+                    (**self).resolve(key)
+                }
+
+                #[cfg_attr(feature = "inline-more", inline)]
+                fn try_resolve<'a>(&'a self, key: &K) -> Option<&'a str> {
+                    (**self).try_resolve(key)
+                }
+
+                #[cfg_attr(feature = "inline-more", inline)]
+                unsafe fn resolve_unchecked<'a>(&'a self, key: &K) -> &'a str {
+                    unsafe { (**self).resolve_unchecked(key) }
+                }
+
+                #[cfg_attr(feature = "inline-more", inline)]
+                fn resolve_many<'a>(&'a self, keys: &[K]) -> Vec<&'a str> {
+                    (**self).resolve_many(keys)
+                }
+
+                #[cfg_attr(feature = "inline-more", inline)]
+                fn try_resolve_many<'a>(&'a self, keys: &[K]) -> Option<Vec<&'a str>> {
+                    (**self).try_resolve_many(keys)
+                }
+
+                #[cfg_attr(feature = "inline-more", inline)]
+                fn contains_key(&self, key: &K) -> bool {
+                    (**self).contains_key(key)
+                }
+
+                #[cfg_attr(feature = "inline-more", inline)]
+                fn len(&self) -> usize {
+                    (**self).len()
+                }
+            }
+        )*
+    };
+}
+
+// Shared pointers only give out immutable access, so they can read and resolve
+// but interning has to go through either a unique reference or an interner
+// that supports shared interning
+impl_shared!(Arc, Rc);
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{filled_rodeo, INTERNED_STRINGS};
+    use crate::{DynInterner, DynReader, DynResolver, Key, Reader, Resolver, Spur};
+    #[cfg(feature = "no-std")]
+    use alloc::{boxed::Box, rc::Rc, sync::Arc, vec, vec::Vec};
+    #[cfg(not(feature = "no-std"))]
+    use std::{rc::Rc, sync::Arc};
+
+    fn check_resolver(resolver: &dyn Resolver<Spur>) {
+        assert_eq!(resolver.len(), INTERNED_STRINGS.len());
+        for (idx, string) in INTERNED_STRINGS.iter().copied().enumerate() {
+            let key = Spur::try_from_usize(idx).unwrap();
+
+            assert!(resolver.contains_key(&key));
+            assert_eq!(resolver.resolve(&key), string);
+            assert_eq!(resolver.try_resolve(&key), Some(string));
+            unsafe {
+                assert_eq!(resolver.resolve_unchecked(&key), string);
+            }
+        }
+    }
+
+    fn check_reader(reader: &dyn Reader<Spur>) {
+        check_resolver(reader);
+        for (idx, string) in INTERNED_STRINGS.iter().copied().enumerate() {
+            assert_eq!(reader.get(string), Spur::try_from_usize(idx));
+            assert!(reader.contains(string));
+        }
+    }
+
+    #[test]
+    fn arc_implementations() {
+        let reader = Arc::new(filled_rodeo().into_reader());
+        check_reader(&reader);
+        check_reader(&Arc::clone(&reader));
+
+        let resolver: Arc<dyn Resolver<Spur> + Send + Sync> =
+            Arc::new(filled_rodeo().into_resolver());
+        check_resolver(&resolver);
+    }
+
+    #[test]
+    fn rc_implementations() {
+        let rodeo = Rc::new(filled_rodeo());
+        check_reader(&rodeo);
+
+        let resolver: Rc<dyn Resolver<Spur>> = Rc::new(filled_rodeo().into_resolver());
+        check_resolver(&resolver);
+    }
+
+    #[test]
+    fn dyn_aliases() {
+        let mut interner: DynInterner = Box::new(filled_rodeo());
+        let key = interner.get_or_intern("dyn");
+        assert_eq!(interner.get_or_intern_static("dyn"), key);
+        assert_eq!(interner.resolve(&key), "dyn");
+
+        let readers: Vec<DynReader> = vec![
+            Box::new(filled_rodeo()),
+            Box::new(filled_rodeo().into_reader()),
+            Box::new(Arc::new(filled_rodeo().into_reader())),
+        ];
+        for reader in readers.iter() {
+            check_reader(reader);
+        }
+
+        let resolvers: Vec<DynResolver> = vec![
+            Box::new(filled_rodeo()),
+            Box::new(filled_rodeo().into_reader()),
+            Box::new(filled_rodeo().into_resolver()),
+            Box::new(Rc::new(filled_rodeo().into_resolver())),
+        ];
+        for resolver in resolvers.iter() {
+            check_resolver(resolver);
+        }
+    }
+}
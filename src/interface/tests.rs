@@ -1,7 +1,9 @@
 #![cfg(test)]
 
 use crate::{
-    interface::{Interner, IntoReader, IntoReaderAndResolver, IntoResolver, Reader, Resolver},
+    interface::{
+        Interner, IntoReader, IntoReaderAndResolver, IntoResolver, Reader, Resolver, ToInterned,
+    },
     Key, Rodeo, RodeoReader, RodeoResolver, Spur,
 };
 
@@ -10,8 +12,12 @@ compile! {
         use crate::ThreadedRodeo;
     }
 
+    if #[feature = "locked"] {
+        use crate::LockedRodeo;
+    }
+
     if #[feature = "no-std"] {
-        use alloc::{boxed::Box, vec};
+        use alloc::{borrow::Cow, boxed::Box, format, string::ToString, vec, vec::Vec};
     }
 }
 
@@ -19,7 +25,7 @@ pub(crate) const INTERNED_STRINGS: &[&str] = &["foo", "bar", "baz", "biz", "buzz
 pub(crate) const UNINTERNED_STRINGS: &[&str] =
     &["rodeo", "default", "string", "static", "unwrap", "array"];
 
-fn filled_rodeo() -> Rodeo {
+pub(crate) fn filled_rodeo() -> Rodeo {
     let mut rodeo = Rodeo::default();
     for string in INTERNED_STRINGS.iter().copied() {
         rodeo.try_get_or_intern_static(string).unwrap();
@@ -38,6 +44,11 @@ pub(crate) fn filled_threaded_rodeo() -> ThreadedRodeo {
     rodeo
 }
 
+#[cfg(feature = "locked")]
+pub(crate) fn filled_locked_rodeo() -> LockedRodeo<spin::Mutex<()>> {
+    LockedRodeo::from_rodeo(filled_rodeo())
+}
+
 mod interner {
     use super::*;
 
@@ -51,6 +62,12 @@ mod interner {
     ) -> Box<dyn IntoReaderAndResolver<Spur, Reader = RodeoReader, Resolver = RodeoResolver>> {
         Box::new(filled_threaded_rodeo())
     }
+
+    #[cfg(feature = "locked")]
+    pub fn locked_rodeo(
+    ) -> Box<dyn IntoReaderAndResolver<Spur, Reader = RodeoReader, Resolver = RodeoResolver>> {
+        Box::new(filled_locked_rodeo())
+    }
 }
 
 #[test]
@@ -59,6 +76,8 @@ fn interner_implementations() {
     let mut interners = vec![interner::rodeo()];
     #[cfg(feature = "multi-threaded")]
     interners.push(interner::threaded_rodeo());
+    #[cfg(feature = "locked")]
+    interners.push(interner::locked_rodeo());
 
     for mut interner in interners {
         for (key, string) in INTERNED_STRINGS
@@ -171,6 +190,11 @@ mod reader {
     pub fn threaded_rodeo() -> Box<dyn IntoResolver<Spur, Resolver = RodeoResolver>> {
         Box::new(filled_threaded_rodeo())
     }
+
+    #[cfg(feature = "locked")]
+    pub fn locked_rodeo() -> Box<dyn IntoResolver<Spur, Resolver = RodeoResolver>> {
+        Box::new(filled_locked_rodeo())
+    }
 }
 
 #[test]
@@ -179,6 +203,8 @@ fn reader_implementations() {
     let mut readers = vec![reader::rodeo(), reader::rodeo_reader()];
     #[cfg(feature = "multi-threaded")]
     readers.push(reader::threaded_rodeo());
+    #[cfg(feature = "locked")]
+    readers.push(reader::locked_rodeo());
 
     for reader in readers {
         for (key, string) in INTERNED_STRINGS
@@ -244,6 +270,34 @@ mod resolver {
     pub fn threaded_rodeo() -> Box<dyn Resolver<Spur>> {
         Box::new(filled_threaded_rodeo())
     }
+
+    #[cfg(feature = "locked")]
+    pub fn locked_rodeo() -> Box<dyn Resolver<Spur>> {
+        Box::new(filled_locked_rodeo())
+    }
+}
+
+#[test]
+fn to_interned() {
+    #[cfg(not(feature = "no-std"))]
+    use std::borrow::Cow;
+
+    let mut interner: Box<dyn Interner> = Box::new(Rodeo::default());
+    let key = "foo".intern_into(&mut *interner);
+
+    assert_eq!("foo".to_string().intern_into(&mut *interner), key);
+    assert_eq!(Cow::Borrowed("foo").intern_into(&mut *interner), key);
+    assert_eq!(
+        Cow::<str>::Owned("foo".to_string()).intern_into(&mut *interner),
+        key
+    );
+    assert_eq!((&&"foo").intern_into(&mut *interner), key);
+    assert_eq!(interner.resolve(&key), "foo");
+
+    let mut rodeo = Rodeo::default();
+    let key: Spur = "bar".intern_into(&mut rodeo);
+    assert_eq!(rodeo.resolve(&key), "bar");
+    assert_eq!(rodeo.len(), 1);
 }
 
 #[test]
@@ -256,6 +310,8 @@ fn resolver_implementations() {
     ];
     #[cfg(feature = "multi-threaded")]
     resolvers.push(resolver::threaded_rodeo());
+    #[cfg(feature = "locked")]
+    resolvers.push(resolver::locked_rodeo());
 
     for resolver in resolvers {
         for (key, string) in INTERNED_STRINGS
@@ -272,8 +328,40 @@ fn resolver_implementations() {
             unsafe {
                 assert_eq!(resolver.resolve_unchecked(&key), string);
             }
+
+            let mut buf = [0xFF; 32];
+            assert_eq!(resolver.resolve_into(&key, &mut buf), Ok(string.len()));
+            assert_eq!(&buf[..string.len()], string.as_bytes());
+            let err = resolver.resolve_into(&key, &mut buf[..string.len() - 1]);
+            assert_eq!(err.unwrap_err().required(), string.len());
+
+            assert_eq!(format!("{}", resolver.display(key)), string);
+            assert_eq!(
+                format!("{:>6}", resolver.display(key)),
+                format!("{:>6}", string)
+            );
+            assert_eq!(format!("{}", resolver.try_display(key).unwrap()), string);
         }
 
+        let missing = Spur::try_from_usize(100).unwrap();
+        assert_eq!(
+            format!("{}", resolver.display(missing)),
+            "<invalid key #100>"
+        );
+        assert!(resolver.try_display(missing).is_none());
+
+        let keys: Vec<Spur> = (0..INTERNED_STRINGS.len())
+            .rev()
+            .map(|i| Spur::try_from_usize(i).unwrap())
+            .collect();
+        let strings: Vec<&str> = INTERNED_STRINGS.iter().rev().copied().collect();
+        assert_eq!(resolver.resolve_many(&keys), strings);
+        assert_eq!(resolver.try_resolve_many(&keys), Some(strings));
+        assert_eq!(
+            resolver.try_resolve_many(&[Spur::try_from_usize(100).unwrap()]),
+            None
+        );
+
         assert_eq!(resolver.len(), INTERNED_STRINGS.len());
         assert!(!resolver.is_empty());
     }
@@ -1,34 +1,81 @@
 #![cfg(feature = "multi-threaded")]
 
-use crate::{Interner, Key, ThreadedRodeo};
+use crate::{Interner, Key, LassoResult, ThreadedRodeo};
 use core::hash::{BuildHasher, Hash};
+use std::{rc::Rc, sync::Arc};
 
 impl<K, S> Interner<K> for &ThreadedRodeo<K, S>
 where
     K: Key + Hash,
     S: BuildHasher + Clone,
 {
+    #[cfg_attr(feature = "profile-interning", track_caller)]
     fn get_or_intern(&mut self, val: &str) -> K {
         ThreadedRodeo::get_or_intern(self, val)
     }
 
-    fn try_get_or_intern(&mut self, val: &str) -> crate::LassoResult<K> {
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    fn try_get_or_intern(&mut self, val: &str) -> LassoResult<K> {
         ThreadedRodeo::try_get_or_intern(self, val)
     }
 
+    #[cfg_attr(feature = "profile-interning", track_caller)]
     fn get_or_intern_static(&mut self, val: &'static str) -> K {
         ThreadedRodeo::get_or_intern_static(self, val)
     }
 
-    fn try_get_or_intern_static(&mut self, val: &'static str) -> crate::LassoResult<K> {
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    fn try_get_or_intern_static(&mut self, val: &'static str) -> LassoResult<K> {
         ThreadedRodeo::try_get_or_intern_static(self, val)
     }
 }
 
+// Shared handles only need a shared reference to intern into a `ThreadedRodeo`,
+// so they can implement `Interner` directly. `Reader` and `Resolver` come from
+// the blanket implementations for `Arc<T>` and `Rc<T>`
+macro_rules! impl_shared_interner {
+    ($($ptr:ident),* $(,)?) => {
+        $(
+            impl<K, S> Interner<K> for $ptr<ThreadedRodeo<K, S>>
+            where
+                K: Key + Hash,
+                S: BuildHasher + Clone,
+            {
+                #[cfg_attr(feature = "inline-more", inline)]
+                #[cfg_attr(feature = "profile-interning", track_caller)]
+                fn get_or_intern(&mut self, val: &str) -> K {
+                    ThreadedRodeo::get_or_intern(self, val)
+                }
+
+                #[cfg_attr(feature = "inline-more", inline)]
+                #[cfg_attr(feature = "profile-interning", track_caller)]
+                fn try_get_or_intern(&mut self, val: &str) -> LassoResult<K> {
+                    ThreadedRodeo::try_get_or_intern(self, val)
+                }
+
+                #[cfg_attr(feature = "inline-more", inline)]
+                #[cfg_attr(feature = "profile-interning", track_caller)]
+                fn get_or_intern_static(&mut self, val: &'static str) -> K {
+                    ThreadedRodeo::get_or_intern_static(self, val)
+                }
+
+                #[cfg_attr(feature = "inline-more", inline)]
+                #[cfg_attr(feature = "profile-interning", track_caller)]
+                fn try_get_or_intern_static(&mut self, val: &'static str) -> LassoResult<K> {
+                    ThreadedRodeo::try_get_or_intern_static(self, val)
+                }
+            }
+        )*
+    };
+}
+
+impl_shared_interner!(Arc, Rc);
+
 #[cfg(test)]
 mod test {
     use super::super::tests::{filled_threaded_rodeo, INTERNED_STRINGS, UNINTERNED_STRINGS};
-    use crate::{Key, Resolver, Spur};
+    use crate::{Interner, Key, Resolver, Spur};
+    use std::{rc::Rc, sync::Arc, thread};
 
     #[test]
     fn threaded_rodeo_ref_trait_implementations() {
@@ -95,4 +142,62 @@ mod test {
             INTERNED_STRINGS.len() + UNINTERNED_STRINGS.len(),
         );
     }
+
+    fn intern_through<I: Interner<Spur>>(mut interner: I) {
+        for string in UNINTERNED_STRINGS.iter().copied() {
+            let key = interner.get_or_intern(string);
+            assert_eq!(interner.try_get_or_intern(string), Ok(key));
+            assert_eq!(interner.get_or_intern_static(string), key);
+            assert_eq!(interner.try_get_or_intern_static(string), Ok(key));
+
+            assert_eq!(interner.get(string), Some(key));
+            assert!(interner.contains(string));
+            assert!(interner.contains_key(&key));
+            assert_eq!(interner.resolve(&key), string);
+            assert_eq!(interner.try_resolve(&key), Some(string));
+        }
+    }
+
+    #[test]
+    fn arc_threaded_rodeo_trait_implementations() {
+        let interner = Arc::new(filled_threaded_rodeo());
+        intern_through(Arc::clone(&interner));
+
+        assert_eq!(
+            interner.len(),
+            INTERNED_STRINGS.len() + UNINTERNED_STRINGS.len(),
+        );
+    }
+
+    #[test]
+    fn rc_threaded_rodeo_trait_implementations() {
+        let interner = Rc::new(filled_threaded_rodeo());
+        intern_through(Rc::clone(&interner));
+
+        assert_eq!(
+            interner.len(),
+            INTERNED_STRINGS.len() + UNINTERNED_STRINGS.len(),
+        );
+    }
+
+    #[test]
+    #[cfg(not(miri))]
+    fn arc_threaded_rodeo_across_threads() {
+        let interner = Arc::new(filled_threaded_rodeo());
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let interner = Arc::clone(&interner);
+                thread::spawn(move || intern_through(interner))
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(
+            interner.len(),
+            INTERNED_STRINGS.len() + UNINTERNED_STRINGS.len(),
+        );
+    }
 }
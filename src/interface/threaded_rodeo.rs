@@ -3,7 +3,7 @@
 
 use crate::*;
 #[cfg(feature = "no-std")]
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use core::hash::{BuildHasher, Hash};
 
 impl<K, S> Interner<K> for ThreadedRodeo<K, S>
@@ -12,21 +12,25 @@ where
     S: BuildHasher + Clone,
 {
     #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
     fn get_or_intern(&mut self, val: &str) -> K {
         (*self).get_or_intern(val)
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
     fn try_get_or_intern(&mut self, val: &str) -> LassoResult<K> {
         (*self).try_get_or_intern(val)
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
     fn get_or_intern_static(&mut self, val: &'static str) -> K {
         (*self).get_or_intern_static(val)
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
     fn try_get_or_intern_static(&mut self, val: &'static str) -> LassoResult<K> {
         (*self).try_get_or_intern_static(val)
     }
@@ -47,7 +51,6 @@ where
     type Reader = RodeoReader<K, S>;
 
     #[cfg_attr(feature = "inline-more", inline)]
-    #[must_use]
     fn into_reader(self) -> Self::Reader
     where
         Self: 'static,
@@ -56,7 +59,6 @@ where
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
-    #[must_use]
     fn into_reader_boxed(self: Box<Self>) -> Self::Reader
     where
         Self: 'static,
@@ -89,7 +91,6 @@ where
     type Resolver = RodeoResolver<K>;
 
     #[cfg_attr(feature = "inline-more", inline)]
-    #[must_use]
     fn into_resolver(self) -> Self::Resolver
     where
         Self: 'static,
@@ -98,7 +99,6 @@ where
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
-    #[must_use]
     fn into_resolver_boxed(self: Box<Self>) -> Self::Resolver
     where
         Self: 'static,
@@ -129,6 +129,16 @@ where
         self.resolve(key)
     }
 
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn resolve_many<'a>(&'a self, keys: &[K]) -> Vec<&'a str> {
+        self.resolve_many(keys)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn try_resolve_many<'a>(&'a self, keys: &[K]) -> Option<Vec<&'a str>> {
+        self.try_resolve_many(keys)
+    }
+
     #[cfg_attr(feature = "inline-more", inline)]
     fn contains_key(&self, key: &K) -> bool {
         self.contains_key(key)
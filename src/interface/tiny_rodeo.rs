@@ -0,0 +1,76 @@
+//! Implementations of [`Interner`], [`Reader`] and [`Resolver`] for [`TinyRodeo`]
+
+use crate::{Interner, LassoResult, Reader, Resolver, SmallKey, TinyRodeo};
+
+impl<K> Interner<K> for TinyRodeo<K>
+where
+    K: SmallKey,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn get_or_intern(&mut self, val: &str) -> K {
+        self.get_or_intern(val)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn try_get_or_intern(&mut self, val: &str) -> LassoResult<K> {
+        self.try_get_or_intern(val)
+    }
+
+    /// Static strings are copied like any other, since every string shares one buffer
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn get_or_intern_static(&mut self, val: &'static str) -> K {
+        self.get_or_intern(val)
+    }
+
+    /// Static strings are copied like any other, since every string shares one buffer
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn try_get_or_intern_static(&mut self, val: &'static str) -> LassoResult<K> {
+        self.try_get_or_intern(val)
+    }
+}
+
+impl<K> Reader<K> for TinyRodeo<K>
+where
+    K: SmallKey,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn get(&self, val: &str) -> Option<K> {
+        self.get(val)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn contains(&self, val: &str) -> bool {
+        self.contains(val)
+    }
+}
+
+impl<K> Resolver<K> for TinyRodeo<K>
+where
+    K: SmallKey,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn resolve<'a>(&'a self, key: &K) -> &'a str {
+        self.resolve(key)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn try_resolve<'a>(&'a self, key: &K) -> Option<&'a str> {
+        self.try_resolve(key)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    unsafe fn resolve_unchecked<'a>(&'a self, key: &K) -> &'a str {
+        // Strings are found through their end offsets, which are bounds checked anyway
+        self.resolve(key)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
@@ -0,0 +1,586 @@
+use crate::keys::{Key, Spur};
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Debug},
+    iter::{Enumerate, FromIterator, FusedIterator},
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+/// A map from keys to values stored as an array indexed by the key
+///
+/// Interners hand out keys densely starting from zero, so values for them can be stored
+/// directly at the key's index instead of hashing every key into a `HashMap`. Lookups are a
+/// bounds check and an array access, and the map grows to fit the largest key inserted into
+/// it. Creating the map with [`KeyMap::with_capacity`] and the interner's length avoids
+/// growing it while it's filled
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{KeyMap, Rodeo};
+///
+/// let mut rodeo = Rodeo::default();
+/// let (apple, pear) = (rodeo.get_or_intern("apple"), rodeo.get_or_intern("pear"));
+///
+/// let mut counts = KeyMap::with_capacity(rodeo.len());
+/// counts.insert(apple, 3);
+/// *counts.entry(pear).or_insert(0) += 1;
+/// *counts.entry(pear).or_insert(0) += 1;
+///
+/// assert_eq!(counts.get(apple), Some(&3));
+/// assert_eq!(counts[pear], 2);
+/// ```
+///
+pub struct KeyMap<K = Spur, V = ()> {
+    values: Vec<Option<V>>,
+    len: usize,
+    __key: PhantomData<K>,
+}
+
+impl<K, V> KeyMap<K, V> {
+    /// Create an empty `KeyMap`
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn new() -> Self {
+        Self {
+            values: Vec::new(),
+            len: 0,
+            __key: PhantomData,
+        }
+    }
+
+    /// Create an empty `KeyMap` that can hold values for the keys of an interner with
+    /// `keys` strings without growing
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity(keys: usize) -> Self {
+        let mut values = Vec::with_capacity(keys);
+        values.resize_with(keys, || None);
+
+        Self {
+            values,
+            len: 0,
+            __key: PhantomData,
+        }
+    }
+
+    /// Returns the number of values in the map
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the map contains no values
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of keys the map can hold values for without growing
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn capacity(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Removes every value from the map without releasing its memory
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn clear(&mut self) {
+        self.values.iter_mut().for_each(|value| *value = None);
+        self.len = 0;
+    }
+
+    /// Returns an iterator over the keys and values of the map in ascending key order
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter(&self) -> KeyMapIter<'_, K, V> {
+        KeyMapIter {
+            iter: self.values.iter().enumerate(),
+            remaining: self.len,
+            __key: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over the keys and mutable values of the map in ascending key order
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter_mut(&mut self) -> KeyMapIterMut<'_, K, V> {
+        KeyMapIterMut {
+            iter: self.values.iter_mut().enumerate(),
+            remaining: self.len,
+            __key: PhantomData,
+        }
+    }
+}
+
+impl<K, V> KeyMap<K, V>
+where
+    K: Key,
+{
+    /// Inserts a value for a key, returning the key's previous value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{KeyMap, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// let mut map = KeyMap::new();
+    /// assert_eq!(map.insert(key, 1), None);
+    /// assert_eq!(map.insert(key, 2), Some(1));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let previous = self.slot(key).replace(value);
+        self.len += previous.is_none() as usize;
+
+        previous
+    }
+
+    /// Removes the value of a key, returning it if the key had one
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{KeyMap, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// let mut map = KeyMap::new();
+    /// map.insert(key, 1);
+    ///
+    /// assert_eq!(map.remove(key), Some(1));
+    /// assert_eq!(map.remove(key), None);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn remove(&mut self, key: K) -> Option<V> {
+        let removed = self.values.get_mut(key.into_usize())?.take();
+        self.len -= removed.is_some() as usize;
+
+        removed
+    }
+
+    /// Returns the value of a key, if it has one
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{KeyMap, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// let mut map = KeyMap::new();
+    /// assert_eq!(map.get(key), None);
+    ///
+    /// map.insert(key, 1);
+    /// assert_eq!(map.get(key), Some(&1));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get(&self, key: K) -> Option<&V> {
+        self.values.get(key.into_usize())?.as_ref()
+    }
+
+    /// Returns a mutable reference to the value of a key, if it has one
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_mut(&mut self, key: K) -> Option<&mut V> {
+        self.values.get_mut(key.into_usize())?.as_mut()
+    }
+
+    /// Returns `true` if the key has a value in the map
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains_key(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Gets the entry of a key for in-place manipulation, growing the map to fit the key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{KeyMap, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// let mut map = KeyMap::new();
+    /// map.entry(key).or_insert_with(Vec::new).push(1);
+    /// map.entry(key).and_modify(|values| values.push(2)).or_default();
+    ///
+    /// assert_eq!(map[key], vec![1, 2]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn entry(&mut self, key: K) -> KeyMapEntry<'_, K, V> {
+        let index = key.into_usize();
+        if index >= self.values.len() {
+            self.values.resize_with(index + 1, || None);
+        }
+
+        KeyMapEntry {
+            key,
+            value: &mut self.values[index],
+            len: &mut self.len,
+        }
+    }
+
+    /// Gets the slot of a key, growing the map to fit it
+    #[inline]
+    fn slot(&mut self, key: K) -> &mut Option<V> {
+        let index = key.into_usize();
+        if index >= self.values.len() {
+            self.values.resize_with(index + 1, || None);
+        }
+
+        &mut self.values[index]
+    }
+}
+
+/// An entry of a [`KeyMap`], which may or may not hold a value
+pub struct KeyMapEntry<'a, K, V> {
+    key: K,
+    value: &'a mut Option<V>,
+    len: &'a mut usize,
+}
+
+impl<'a, K, V> KeyMapEntry<'a, K, V>
+where
+    K: Key,
+{
+    /// Returns the entry's key
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn key(&self) -> K {
+        self.key
+    }
+
+    /// Returns the entry's value, if it has one
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get(&self) -> Option<&V> {
+        self.value.as_ref()
+    }
+
+    /// Inserts `default` if the entry is empty, returning a reference to the entry's value
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Inserts the value returned by `default` if the entry is empty, returning a reference
+    /// to the entry's value
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        if self.value.is_none() {
+            *self.len += 1;
+        }
+
+        self.value.get_or_insert_with(default)
+    }
+
+    /// Inserts the default value if the entry is empty, returning a reference to the
+    /// entry's value
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Calls `modify` with the entry's value if it has one
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn and_modify<F>(self, modify: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        if let Some(value) = self.value.as_mut() {
+            modify(value);
+        }
+
+        self
+    }
+}
+
+impl<K, V> Debug for KeyMapEntry<'_, K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyMapEntry")
+            .field("key", &self.key)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<K, V> Default for KeyMap<K, V> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Clone for KeyMap<K, V>
+where
+    V: Clone,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            len: self.len,
+            __key: PhantomData,
+        }
+    }
+}
+
+/// Maps are equal if they hold the same values for the same keys, no matter their capacity
+impl<K, V> PartialEq for KeyMap<K, V>
+where
+    K: Key,
+    V: PartialEq,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<K, V> Eq for KeyMap<K, V>
+where
+    K: Key,
+    V: Eq,
+{
+}
+
+impl<K, V> Debug for KeyMap<K, V>
+where
+    K: Key + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> Index<K> for KeyMap<K, V>
+where
+    K: Key,
+{
+    type Output = V;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn index(&self, key: K) -> &Self::Output {
+        self.get(key).expect("the key has no value in the map")
+    }
+}
+
+impl<K, V> IndexMut<K> for KeyMap<K, V>
+where
+    K: Key,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn index_mut(&mut self, key: K) -> &mut Self::Output {
+        self.get_mut(key).expect("the key has no value in the map")
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for KeyMap<K, V>
+where
+    K: Key,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        let mut map = Self::new();
+        map.extend(iter);
+
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for KeyMap<K, V>
+where
+    K: Key,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a KeyMap<K, V>
+where
+    K: Key,
+{
+    type Item = (K, &'a V);
+    type IntoIter = KeyMapIter<'a, K, V>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut KeyMap<K, V>
+where
+    K: Key,
+{
+    type Item = (K, &'a mut V);
+    type IntoIter = KeyMapIterMut<'a, K, V>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over the keys and values of a [`KeyMap`]
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct KeyMapIter<'a, K, V> {
+    iter: Enumerate<slice::Iter<'a, Option<V>>>,
+    remaining: usize,
+    __key: PhantomData<K>,
+}
+
+impl<'a, K, V> Iterator for KeyMapIter<'a, K, V>
+where
+    K: Key,
+{
+    type Item = (K, &'a V);
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self
+            .iter
+            .find_map(|(index, value)| Some((index, value.as_ref()?)))?;
+        self.remaining -= 1;
+
+        Some((K::try_from_usize(key)?, value))
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K: Key, V> ExactSizeIterator for KeyMapIter<'a, K, V> {}
+impl<'a, K: Key, V> FusedIterator for KeyMapIter<'a, K, V> {}
+
+/// An iterator over the keys and mutable values of a [`KeyMap`]
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct KeyMapIterMut<'a, K, V> {
+    iter: Enumerate<slice::IterMut<'a, Option<V>>>,
+    remaining: usize,
+    __key: PhantomData<K>,
+}
+
+impl<'a, K, V> Iterator for KeyMapIterMut<'a, K, V>
+where
+    K: Key,
+{
+    type Item = (K, &'a mut V);
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self
+            .iter
+            .find_map(|(index, value)| Some((index, value.as_mut()?)))?;
+        self.remaining -= 1;
+
+        Some((K::try_from_usize(key)?, value))
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K: Key, V> ExactSizeIterator for KeyMapIterMut<'a, K, V> {}
+impl<'a, K: Key, V> FusedIterator for KeyMapIterMut<'a, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rodeo;
+
+    fn key(index: usize) -> Spur {
+        Spur::try_from_usize(index).unwrap()
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut map = KeyMap::new();
+        assert_eq!(map.insert(key(3), "three"), None);
+        assert_eq!(map.insert(key(3), "drei"), Some("three"));
+        assert_eq!(map.insert(key(0), "zero"), None);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.capacity(), 4);
+
+        assert_eq!(map.get(key(3)), Some(&"drei"));
+        assert_eq!(map.get(key(1)), None);
+        assert_eq!(map.get(key(100)), None);
+        assert!(map.contains_key(key(0)));
+        *map.get_mut(key(0)).unwrap() = "null";
+        assert_eq!(map[key(0)], "null");
+
+        assert_eq!(map.remove(key(0)), Some("null"));
+        assert_eq!(map.remove(key(0)), None);
+        assert_eq!(map.remove(key(100)), None);
+        assert_eq!(map.len(), 1);
+
+        map.clear();
+        assert!(map.is_empty());
+        assert_eq!(map, KeyMap::new());
+    }
+
+    #[test]
+    fn entries_and_iteration() {
+        let mut rodeo = Rodeo::default();
+        let words = ["a", "b", "a", "c", "a", "b"];
+        let mut counts: KeyMap<Spur, usize> = KeyMap::with_capacity(3);
+        for word in words {
+            *counts.entry(rodeo.get_or_intern(word)).or_default() += 1;
+        }
+        assert_eq!(counts.capacity(), 3);
+
+        let entry = counts.entry(key(1)).and_modify(|count| *count *= 10);
+        assert_eq!((entry.key(), entry.get()), (key(1), Some(&20)));
+        assert_eq!(*counts.entry(key(5)).or_insert(7), 7);
+
+        for (_, count) in counts.iter_mut() {
+            *count += 1;
+        }
+        let counted: Vec<_> = counts.iter().map(|(key, &count)| (key, count)).collect();
+        assert_eq!(
+            counted,
+            [(key(0), 4), (key(1), 21), (key(2), 2), (key(5), 8)]
+        );
+        assert_eq!(counts.iter().len(), 4);
+
+        let collected: KeyMap<Spur, usize> = counted.into_iter().collect();
+        assert_eq!(collected, counts);
+        assert_eq!(
+            alloc::format!("{:?}", collected.iter().next()),
+            "Some((Spur(1), 4))"
+        );
+    }
+}
@@ -0,0 +1,528 @@
+use crate::keys::{Key, Spur};
+use alloc::{vec, vec::Vec};
+use core::{
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    iter::{FromIterator, FusedIterator},
+    marker::PhantomData,
+};
+
+/// The number of keys held by each word of a [`KeySet`]
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A set of keys stored as a bitset
+///
+/// Interners hand out keys densely starting from zero, so a set of keys can be stored as one
+/// bit per key instead of hashing every key into a `HashSet`. This makes membership checks a
+/// single bit test and a set of every key of an interner with a million strings only takes
+/// 125 kilobytes. Sets grow as needed to fit the largest key inserted into them, which can
+/// be avoided by creating them with [`Rodeo::key_universe`] or [`KeySet::with_capacity`]
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{KeySet, Rodeo};
+///
+/// let mut rodeo = Rodeo::default();
+/// let (a, b, c) = (rodeo.get_or_intern("a"), rodeo.get_or_intern("b"), rodeo.get_or_intern("c"));
+///
+/// let mut set = KeySet::with_capacity(rodeo.len());
+/// set.insert(a);
+/// set.insert(c);
+///
+/// assert!(set.contains(a));
+/// assert!(!set.contains(b));
+/// assert_eq!(set.iter().collect::<Vec<_>>(), vec![a, c]);
+/// ```
+///
+/// [`Rodeo::key_universe`]: crate::Rodeo::key_universe
+pub struct KeySet<K = Spur> {
+    words: Vec<u64>,
+    len: usize,
+    __key: PhantomData<K>,
+}
+
+impl<K> KeySet<K> {
+    /// Create an empty `KeySet`
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn new() -> Self {
+        Self {
+            words: Vec::new(),
+            len: 0,
+            __key: PhantomData,
+        }
+    }
+
+    /// Create an empty `KeySet` that can hold the keys of an interner with `keys` strings
+    /// without growing
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity(keys: usize) -> Self {
+        Self {
+            words: vec![0; words_for(keys)],
+            len: 0,
+            __key: PhantomData,
+        }
+    }
+
+    /// Create a `KeySet` holding the first `keys` keys
+    pub(crate) fn filled(keys: usize) -> Self {
+        let mut words = vec![u64::MAX; words_for(keys)];
+        if let Some(last) = words.last_mut() {
+            let used = keys % WORD_BITS;
+            if used != 0 {
+                *last = (1 << used) - 1;
+            }
+        }
+
+        Self {
+            words,
+            len: keys,
+            __key: PhantomData,
+        }
+    }
+
+    /// Converts the set to hold a different type of key, every key keeps its index
+    pub(crate) fn rekey<K2>(self) -> KeySet<K2> {
+        KeySet {
+            words: self.words,
+            len: self.len,
+            __key: PhantomData,
+        }
+    }
+
+    /// Returns the number of keys in the set
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the set contains no keys
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of keys the set can hold without growing
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn capacity(&self) -> usize {
+        self.words.len() * WORD_BITS
+    }
+
+    /// Removes every key from the set without releasing its memory
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+        self.len = 0;
+    }
+
+    /// Adds every key in `other` to the set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, KeySet, Spur};
+    ///
+    /// let keys: Vec<Spur> = (0..3).map(|i| Spur::try_from_usize(i).unwrap()).collect();
+    /// let mut set: KeySet = [keys[0], keys[1]].into_iter().collect();
+    /// let other: KeySet = [keys[1], keys[2]].into_iter().collect();
+    ///
+    /// set.union_with(&other);
+    /// assert_eq!(set.len(), 3);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn union_with(&mut self, other: &Self) {
+        if self.words.len() < other.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+
+        for (word, other) in self.words.iter_mut().zip(other.words.iter()) {
+            *word |= *other;
+        }
+        self.recount();
+    }
+
+    /// Removes every key from the set that isn't in `other`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, KeySet, Spur};
+    ///
+    /// let keys: Vec<Spur> = (0..3).map(|i| Spur::try_from_usize(i).unwrap()).collect();
+    /// let mut set: KeySet = [keys[0], keys[1]].into_iter().collect();
+    /// let other: KeySet = [keys[1], keys[2]].into_iter().collect();
+    ///
+    /// set.intersect_with(&other);
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![keys[1]]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn intersect_with(&mut self, other: &Self) {
+        for (i, word) in self.words.iter_mut().enumerate() {
+            *word &= other.words.get(i).copied().unwrap_or(0);
+        }
+        self.recount();
+    }
+
+    /// Removes every key in `other` from the set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, KeySet, Spur};
+    ///
+    /// let keys: Vec<Spur> = (0..3).map(|i| Spur::try_from_usize(i).unwrap()).collect();
+    /// let mut set: KeySet = [keys[0], keys[1]].into_iter().collect();
+    /// let other: KeySet = [keys[1], keys[2]].into_iter().collect();
+    ///
+    /// set.difference_with(&other);
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![keys[0]]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn difference_with(&mut self, other: &Self) {
+        for (word, other) in self.words.iter_mut().zip(other.words.iter()) {
+            *word &= !*other;
+        }
+        self.recount();
+    }
+
+    /// Returns `true` if every key in the set is also in `other`
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .enumerate()
+            .all(|(i, word)| word & !other.words.get(i).copied().unwrap_or(0) == 0)
+    }
+
+    /// Returns an iterator over the keys in the set in ascending order
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter(&self) -> KeySetIter<'_, K> {
+        KeySetIter {
+            words: &self.words,
+            base: 0,
+            current: self.words.first().copied().unwrap_or(0),
+            remaining: self.len,
+            __key: PhantomData,
+        }
+    }
+
+    fn recount(&mut self) {
+        self.len = self
+            .words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum();
+    }
+
+    /// Returns the words with any trailing empty words trimmed off
+    fn trimmed(&self) -> &[u64] {
+        let used = self
+            .words
+            .iter()
+            .rposition(|&word| word != 0)
+            .map_or(0, |last| last + 1);
+
+        &self.words[..used]
+    }
+}
+
+impl<K> KeySet<K>
+where
+    K: Key,
+{
+    /// Adds a key to the set, returning `true` if it wasn't already in the set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{KeySet, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// let mut set = KeySet::new();
+    /// assert!(set.insert(key));
+    /// assert!(!set.insert(key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn insert(&mut self, key: K) -> bool {
+        let (word, bit) = position(key);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+
+        let inserted = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        self.len += inserted as usize;
+
+        inserted
+    }
+
+    /// Removes a key from the set, returning `true` if it was in the set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{KeySet, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// let mut set = rodeo.key_universe();
+    /// assert!(set.remove(key));
+    /// assert!(!set.contains(key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn remove(&mut self, key: K) -> bool {
+        let (word, bit) = position(key);
+        let removed = self.words.get(word).is_some_and(|&word| word & bit != 0);
+        if removed {
+            self.words[word] &= !bit;
+            self.len -= 1;
+        }
+
+        removed
+    }
+
+    /// Returns `true` if the key is in the set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{KeySet, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// let mut set = KeySet::new();
+    /// assert!(!set.contains(key));
+    ///
+    /// set.insert(key);
+    /// assert!(set.contains(key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains(&self, key: K) -> bool {
+        let (word, bit) = position(key);
+        self.words.get(word).is_some_and(|&word| word & bit != 0)
+    }
+}
+
+/// Gets the word a key's bit lives in and the key's bit within that word
+#[inline]
+fn position<K: Key>(key: K) -> (usize, u64) {
+    let index = key.into_usize();
+    (index / WORD_BITS, 1 << (index % WORD_BITS))
+}
+
+/// Gets the number of words needed to hold `keys` keys
+#[inline]
+fn words_for(keys: usize) -> usize {
+    keys.saturating_add(WORD_BITS - 1) / WORD_BITS
+}
+
+impl<K> Default for KeySet<K> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> Clone for KeySet<K> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self {
+            words: self.words.clone(),
+            len: self.len,
+            __key: PhantomData,
+        }
+    }
+}
+
+/// Sets are equal if they contain the same keys, no matter their capacity
+impl<K> PartialEq for KeySet<K> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.trimmed() == other.trimmed()
+    }
+}
+
+impl<K> Eq for KeySet<K> {}
+
+impl<K> Hash for KeySet<K> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.trimmed().hash(state);
+    }
+}
+
+impl<K> Debug for KeySet<K>
+where
+    K: Key + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<K> FromIterator<K> for KeySet<K>
+where
+    K: Key,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = K>,
+    {
+        let mut set = Self::new();
+        set.extend(iter);
+
+        set
+    }
+}
+
+impl<K> Extend<K> for KeySet<K>
+where
+    K: Key,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = K>,
+    {
+        for key in iter {
+            self.insert(key);
+        }
+    }
+}
+
+impl<'a, K> IntoIterator for &'a KeySet<K>
+where
+    K: Key,
+{
+    type Item = K;
+    type IntoIter = KeySetIter<'a, K>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the keys of a [`KeySet`]
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct KeySetIter<'a, K> {
+    words: &'a [u64],
+    /// The index of the first key held by `current`
+    base: usize,
+    /// The bits of the current word that haven't been yielded yet
+    current: u64,
+    remaining: usize,
+    __key: PhantomData<K>,
+}
+
+impl<'a, K> Iterator for KeySetIter<'a, K>
+where
+    K: Key,
+{
+    type Item = K;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current == 0 {
+            self.words = self.words.get(1..).filter(|words| !words.is_empty())?;
+            self.base += WORD_BITS;
+            self.current = self.words[0];
+        }
+
+        let bit = self.current.trailing_zeros() as usize;
+        // Clear the lowest set bit
+        self.current &= self.current - 1;
+        self.remaining -= 1;
+
+        K::try_from_usize(self.base + bit)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K: Key> ExactSizeIterator for KeySetIter<'a, K> {}
+impl<'a, K: Key> FusedIterator for KeySetIter<'a, K> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MicroSpur, Rodeo};
+
+    fn key(index: usize) -> Spur {
+        Spur::try_from_usize(index).unwrap()
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut set = KeySet::new();
+        assert!(set.insert(key(0)));
+        assert!(set.insert(key(200)));
+        assert!(!set.insert(key(200)));
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.capacity(), 256);
+
+        assert!(set.contains(key(200)));
+        assert!(!set.contains(key(1000)));
+        assert!(set.remove(key(200)));
+        assert!(!set.remove(key(200)));
+        assert!(!set.remove(key(1000)));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [key(0)]);
+
+        set.clear();
+        assert!(set.is_empty());
+        assert_eq!(set, KeySet::new());
+    }
+
+    #[test]
+    fn set_operations() {
+        let mut a: KeySet = [0, 64, 65].into_iter().map(key).collect();
+        let b: KeySet = [1, 65, 500].into_iter().map(key).collect();
+
+        let mut union = a.clone();
+        union.union_with(&b);
+        let keys: Vec<_> = [0, 1, 64, 65, 500].into_iter().map(key).collect();
+        assert_eq!(union.iter().collect::<Vec<_>>(), keys);
+        assert_eq!(union.iter().len(), 5);
+        assert!(a.is_subset(&union) && b.is_subset(&union));
+        assert!(!union.is_subset(&a));
+
+        let mut intersection = union.clone();
+        intersection.intersect_with(&a);
+        assert_eq!(intersection, a);
+
+        a.difference_with(&b);
+        assert_eq!(a.iter().collect::<Vec<_>>(), [key(0), key(64)]);
+        assert_eq!(alloc::format!("{:?}", a), "{Spur(1), Spur(65)}");
+    }
+
+    #[test]
+    fn key_universe() {
+        let mut rodeo: Rodeo<MicroSpur> = Rodeo::new();
+        for i in 0..70 {
+            rodeo.get_or_intern(alloc::format!("{}", i));
+        }
+
+        let universe = rodeo.key_universe();
+        assert_eq!(universe.len(), 70);
+        assert_eq!(universe.iter().count(), 70);
+        assert!(rodeo.iter().all(|(key, _)| universe.contains(key)));
+        assert!(!universe.contains(MicroSpur::try_from_usize(70).unwrap()));
+
+        assert!(Rodeo::default().key_universe().is_empty());
+    }
+}
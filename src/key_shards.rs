@@ -0,0 +1,538 @@
+//! The key to string maps that [`ThreadedRodeo`] resolves keys through
+//!
+//! With the `dashmap` feature these are a [`DashMap`], otherwise they're a built-in table
+//! sharded the same way as the [string to key map]. Both have the same interface, so the
+//! interner doesn't depend on any particular version of dashmap
+//!
+//! [`ThreadedRodeo`]: crate::ThreadedRodeo
+//! [`DashMap`]: https://docs.rs/dashmap/latest/dashmap/struct.DashMap.html
+//! [string to key map]: crate::shard_map
+
+use core::hash::{BuildHasher, Hasher};
+
+#[cfg(feature = "dashmap")]
+pub(crate) use self::dashmap::{KeyShards, KeyShardsIter};
+#[cfg(not(feature = "dashmap"))]
+pub(crate) use self::sharded::{KeyShards, KeyShardsIter};
+
+/// Builds the [`KeyHasher`]s used by the key to string map
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct KeyState;
+
+impl BuildHasher for KeyState {
+    type Hasher = KeyHasher;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn build_hasher(&self) -> Self::Hasher {
+        KeyHasher { hash: 0 }
+    }
+}
+
+/// A hasher for keys, which are small and dense integers that don't need protection from
+/// collision attacks
+///
+/// Each word is mixed in with a rotate, xor and multiply the same way as `FxHash`, which
+/// spreads consecutive keys across both the map's shards (chosen by the upper bits) and
+/// each shard's table
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct KeyHasher {
+    hash: u64,
+}
+
+impl KeyHasher {
+    const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(Self::SEED);
+    }
+}
+
+impl Hasher for KeyHasher {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.add_to_hash(u64::from_le_bytes(word));
+        }
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn write_u8(&mut self, int: u8) {
+        self.add_to_hash(int as u64);
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn write_u16(&mut self, int: u16) {
+        self.add_to_hash(int as u64);
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn write_u32(&mut self, int: u32) {
+        self.add_to_hash(int as u64);
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn write_u64(&mut self, int: u64) {
+        self.add_to_hash(int);
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn write_usize(&mut self, int: usize) {
+        self.add_to_hash(int as u64);
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// The built-in key to string map, used unless the `dashmap` feature is enabled
+#[cfg(not(feature = "dashmap"))]
+mod sharded {
+    use super::KeyState;
+    use crate::{
+        shard_map::{read_shard, shard_count, write_shard},
+        sync::RwLock,
+        util::grown_table_capacity,
+        LassoError, LassoErrorKind, LassoResult,
+    };
+    use alloc::{boxed::Box, vec, vec::Vec};
+    use core::{
+        hash::{BuildHasher, Hash},
+        mem::size_of,
+        slice,
+    };
+    use hashbrown::{hash_table, HashTable};
+
+    /// A concurrent map from keys to their strings
+    pub(crate) struct KeyShards<K> {
+        shards: Box<[Shard<K>]>,
+        /// The number of bits a shifted hash has to be moved by to get its shard's index
+        shift: u32,
+    }
+
+    /// A single shard, padded out so that neighboring shards' locks don't share a cache line
+    #[repr(align(128))]
+    struct Shard<K> {
+        table: RwLock<HashTable<(K, &'static str)>>,
+    }
+
+    impl<K> KeyShards<K>
+    where
+        K: Copy + Eq + Hash,
+    {
+        /// Creates an empty map that hasn't allocated any of its shards' tables
+        pub(crate) fn new() -> Self {
+            Self::with_tables(|| Ok(HashTable::new())).expect("creating empty tables never fails")
+        }
+
+        /// Creates a map with room for at least `capacity` keys spread out over its shards
+        pub(crate) fn try_with_capacity(capacity: usize) -> LassoResult<Self> {
+            let per_shard = capacity.saturating_add(shard_count() - 1) / shard_count();
+
+            Self::with_tables(|| {
+                let mut table = HashTable::new();
+                table
+                    .try_reserve(per_shard, |_| {
+                        unreachable!(
+                            "the shard should be empty while reserving its initial capacity"
+                        );
+                    })
+                    .map_err(|_| LassoError::new(LassoErrorKind::FailedAllocation))?;
+
+                Ok(table)
+            })
+        }
+
+        fn with_tables<F>(mut table: F) -> LassoResult<Self>
+        where
+            F: FnMut() -> LassoResult<HashTable<(K, &'static str)>>,
+        {
+            let num_shards = shard_count();
+            let shards = (0..num_shards)
+                .map(|_| {
+                    Ok(Shard {
+                        table: RwLock::new(table()?),
+                    })
+                })
+                .collect::<LassoResult<Box<[_]>>>()?;
+
+            Ok(Self {
+                shards,
+                shift: u64::BITS - num_shards.trailing_zeros(),
+            })
+        }
+
+        fn hash(key: &K) -> u64 {
+            KeyState.hash_one(key)
+        }
+
+        /// Gets the index of the shard a hash belongs to
+        fn shard_index(&self, hash: u64) -> usize {
+            // The top seven bits are used by the shard's table, so they're skipped to keep
+            // from giving every key within a shard the same tag
+            ((hash << 7) >> self.shift) as usize
+        }
+
+        /// Sets the string of `key`
+        pub(crate) fn insert(&self, key: K, string: &'static str) {
+            let hash = Self::hash(&key);
+            let mut shard = write_shard(&self.shards[self.shard_index(hash)].table);
+
+            match shard.entry(hash, |&(other, _)| other == key, |(key, _)| Self::hash(key)) {
+                hash_table::Entry::Occupied(mut entry) => entry.get_mut().1 = string,
+                hash_table::Entry::Vacant(entry) => {
+                    entry.insert((key, string));
+                }
+            }
+        }
+
+        /// Gets the string of `key`
+        pub(crate) fn get(&self, key: &K) -> Option<&'static str> {
+            let hash = Self::hash(key);
+
+            read_shard(&self.shards[self.shard_index(hash)].table)
+                .find(hash, |(other, _)| other == key)
+                .map(|&(_, string)| string)
+        }
+
+        /// Gets the strings of every key, taking each shard's lock once
+        pub(crate) fn get_many(&self, keys: &[K]) -> Option<Vec<&'static str>> {
+            let mut lookups: Vec<(usize, u64, usize)> = keys
+                .iter()
+                .enumerate()
+                .map(|(idx, key)| {
+                    let hash = Self::hash(key);
+                    (self.shard_index(hash), hash, idx)
+                })
+                .collect();
+            lookups.sort_unstable_by_key(|&(shard, _, _)| shard);
+
+            let mut strings = vec![""; keys.len()];
+            let mut locked = None;
+
+            for (shard_idx, hash, idx) in lookups {
+                let shard = match locked {
+                    Some((locked_idx, ref shard)) if locked_idx == shard_idx => shard,
+                    _ => {
+                        &locked
+                            .insert((shard_idx, read_shard(&self.shards[shard_idx].table)))
+                            .1
+                    }
+                };
+
+                strings[idx] = shard.find(hash, |(key, _)| *key == keys[idx])?.1;
+            }
+
+            Some(strings)
+        }
+
+        pub(crate) fn len(&self) -> usize {
+            self.shards
+                .iter()
+                .map(|shard| read_shard(&shard.table).len())
+                .sum()
+        }
+
+        pub(crate) fn capacity(&self) -> usize {
+            self.shards
+                .iter()
+                .map(|shard| read_shard(&shard.table).capacity())
+                .sum()
+        }
+
+        /// Removes every key, one shard at a time
+        pub(crate) fn clear(&self) {
+            for shard in self.shards.iter() {
+                write_shard(&shard.table).clear();
+            }
+        }
+
+        /// Shrinks every shard to fit the keys it holds, one shard at a time
+        pub(crate) fn shrink_to_fit(&self) {
+            for shard in self.shards.iter() {
+                write_shard(&shard.table).shrink_to_fit(|(key, _)| Self::hash(key));
+            }
+        }
+
+        /// Removes every key, calling `f` with each of them and their strings
+        pub(crate) fn drain_with<F>(&mut self, mut f: F)
+        where
+            F: FnMut(K, &'static str),
+        {
+            for shard in self.shards.iter_mut() {
+                for (key, string) in shard.table.get_mut().drain() {
+                    f(key, string);
+                }
+            }
+        }
+
+        /// Estimates the memory used by the map, optionally including the largest amount a
+        /// single insertion could grow it by
+        pub(crate) fn memory_usage(&self, projected: bool) -> usize {
+            // Every entry also has a one byte control tag
+            let entry_size = size_of::<(K, &'static str)>() + 1;
+            let (mut total, mut largest) = (0usize, 0usize);
+
+            for shard in self.shards.iter() {
+                let shard = read_shard(&shard.table);
+                let capacity = shard.capacity();
+                total = total.saturating_add(capacity.saturating_mul(entry_size));
+
+                // Full shards grow on their next insertion
+                if shard.len() == capacity {
+                    let growth = grown_table_capacity(capacity) - capacity;
+                    largest = largest.max(growth.saturating_mul(entry_size));
+                }
+            }
+
+            if projected {
+                total.saturating_add(largest)
+            } else {
+                total
+            }
+        }
+
+        pub(crate) fn iter(&self) -> KeyShardsIter<'_, K> {
+            KeyShardsIter {
+                shards: self.shards.iter(),
+                current: Vec::new().into_iter(),
+            }
+        }
+
+        /// Gets the number of keys in each shard
+        #[cfg(test)]
+        pub(crate) fn shard_lens(&self) -> Vec<usize> {
+            self.shards
+                .iter()
+                .map(|shard| read_shard(&shard.table).len())
+                .collect()
+        }
+    }
+
+    /// An iterator over the keys and strings of a [`KeyShards`]
+    ///
+    /// Each shard's entries are copied out while its lock is held, so no lock is held
+    /// between calls to `next`
+    pub(crate) struct KeyShardsIter<'a, K> {
+        shards: slice::Iter<'a, Shard<K>>,
+        current: vec::IntoIter<(K, &'static str)>,
+    }
+
+    impl<K> Iterator for KeyShardsIter<'_, K>
+    where
+        K: Copy,
+    {
+        type Item = (K, &'static str);
+
+        fn next(&mut self) -> Option<Self::Item> {
+            loop {
+                if let Some(entry) = self.current.next() {
+                    return Some(entry);
+                }
+
+                let shard = self.shards.next()?;
+                self.current = read_shard(&shard.table)
+                    .iter()
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .into_iter();
+            }
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let upper = if self.shards.len() == 0 {
+                Some(self.current.len())
+            } else {
+                None
+            };
+
+            (self.current.len(), upper)
+        }
+    }
+}
+
+/// The key to string map backed by dashmap, used with the `dashmap` feature
+#[cfg(feature = "dashmap")]
+mod dashmap {
+    use super::KeyState;
+    use crate::{util::grown_table_capacity, LassoError, LassoErrorKind, LassoResult};
+    use ::dashmap::{iter::Iter, DashMap, SharedValue};
+    use alloc::{vec, vec::Vec};
+    use core::{
+        hash::{BuildHasher, Hash},
+        mem::size_of,
+    };
+
+    /// A concurrent map from keys to their strings
+    pub(crate) struct KeyShards<K> {
+        map: DashMap<K, &'static str, KeyState>,
+    }
+
+    impl<K> KeyShards<K>
+    where
+        K: Copy + Eq + Hash,
+    {
+        /// Creates an empty map that hasn't allocated any of its shards' tables
+        pub(crate) fn new() -> Self {
+            Self {
+                map: DashMap::with_hasher(KeyState),
+            }
+        }
+
+        /// Creates a map with room for at least `capacity` keys spread out over its shards
+        pub(crate) fn try_with_capacity(capacity: usize) -> LassoResult<Self> {
+            let map = DashMap::with_hasher(KeyState);
+            let shards = map.shards();
+            // Round up so that the map can hold at least `capacity` entries
+            let per_shard = capacity.saturating_add(shards.len() - 1) / shards.len();
+
+            for shard in shards {
+                shard
+                    .write()
+                    .try_reserve(per_shard, |(key, _)| KeyState.hash_one(key))
+                    .map_err(|_| LassoError::new(LassoErrorKind::FailedAllocation))?;
+            }
+
+            Ok(Self { map })
+        }
+
+        /// Sets the string of `key`
+        pub(crate) fn insert(&self, key: K, string: &'static str) {
+            self.map.insert(key, string);
+        }
+
+        /// Gets the string of `key`
+        pub(crate) fn get(&self, key: &K) -> Option<&'static str> {
+            self.map.get(key).map(|string| *string)
+        }
+
+        /// Gets the strings of every key, taking each shard's lock once
+        pub(crate) fn get_many(&self, keys: &[K]) -> Option<Vec<&'static str>> {
+            let mut lookups: Vec<(usize, u64, usize)> = keys
+                .iter()
+                .enumerate()
+                .map(|(idx, key)| {
+                    let hash = KeyState.hash_one(key);
+                    (self.map.determine_shard(hash as usize), hash, idx)
+                })
+                .collect();
+            lookups.sort_unstable_by_key(|&(shard, _, _)| shard);
+
+            let shards = self.map.shards();
+            let mut strings = vec![""; keys.len()];
+            let mut locked = None;
+
+            for (shard_idx, hash, idx) in lookups {
+                let shard = match locked {
+                    Some((locked_idx, ref shard)) if locked_idx == shard_idx => shard,
+                    _ => &locked.insert((shard_idx, shards[shard_idx].read())).1,
+                };
+
+                let bucket = shard.find(hash, |(key, _)| *key == keys[idx])?;
+                // Safety: The bucket was just found in the shard, which stays locked while
+                //         it's borrowed
+                strings[idx] = unsafe { *bucket.as_ref().1.get() };
+            }
+
+            Some(strings)
+        }
+
+        pub(crate) fn len(&self) -> usize {
+            self.map.len()
+        }
+
+        pub(crate) fn capacity(&self) -> usize {
+            self.map.capacity()
+        }
+
+        /// Removes every key, one shard at a time
+        pub(crate) fn clear(&self) {
+            for shard in self.map.shards() {
+                shard.write().clear();
+            }
+        }
+
+        /// Shrinks every shard to fit the keys it holds, one shard at a time
+        pub(crate) fn shrink_to_fit(&self) {
+            self.map.shrink_to_fit();
+        }
+
+        /// Removes every key, calling `f` with each of them and their strings
+        pub(crate) fn drain_with<F>(&mut self, mut f: F)
+        where
+            F: FnMut(K, &'static str),
+        {
+            for shard in self.map.shards_mut() {
+                for (key, string) in shard.get_mut().drain() {
+                    f(key, string.into_inner());
+                }
+            }
+        }
+
+        /// Estimates the memory used by the map, optionally including the largest amount a
+        /// single insertion could grow it by
+        pub(crate) fn memory_usage(&self, projected: bool) -> usize {
+            // Every entry also has a one byte control tag
+            let entry_size = size_of::<(K, SharedValue<&'static str>)>() + 1;
+            let (mut total, mut largest) = (0usize, 0usize);
+
+            for shard in self.map.shards() {
+                let shard = shard.read();
+                let capacity = shard.capacity();
+                total = total.saturating_add(capacity.saturating_mul(entry_size));
+
+                // Full shards grow on their next insertion
+                if shard.len() == capacity {
+                    let growth = grown_table_capacity(capacity) - capacity;
+                    largest = largest.max(growth.saturating_mul(entry_size));
+                }
+            }
+
+            if projected {
+                total.saturating_add(largest)
+            } else {
+                total
+            }
+        }
+
+        pub(crate) fn iter(&self) -> KeyShardsIter<'_, K> {
+            KeyShardsIter {
+                iter: self.map.iter(),
+            }
+        }
+
+        /// Gets the number of keys in each shard
+        #[cfg(test)]
+        pub(crate) fn shard_lens(&self) -> Vec<usize> {
+            self.map
+                .shards()
+                .iter()
+                .map(|shard| shard.read().len())
+                .collect()
+        }
+    }
+
+    /// An iterator over the keys and strings of a [`KeyShards`]
+    pub(crate) struct KeyShardsIter<'a, K> {
+        iter: Iter<'a, K, &'static str, KeyState, DashMap<K, &'static str, KeyState>>,
+    }
+
+    impl<K> Iterator for KeyShardsIter<'_, K>
+    where
+        K: Copy + Eq + Hash,
+    {
+        type Item = (K, &'static str);
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.iter.next().map(|entry| (*entry.key(), *entry.value()))
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.iter.size_hint()
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use crate::{
+    interface::{Interner, Reader, Resolver},
+    keys::{Key, Spur},
+    KeyMap, LassoResult,
+};
+use core::fmt::{self, Debug};
+
+/// A table translating the keys of one interner into the keys of another
+///
+/// Interners give out their own keys, so the same string will usually have different keys in
+/// different interners. A `KeyTranslator` looks every string of the first interner up in the
+/// second once, after which keys are translated with a bounds check and an array access
+/// instead of resolving and hashing their strings again.
+///
+/// Translation is checked: keys the first interner never gave out, and keys whose strings
+/// weren't in the second interner when they were looked up, translate to `None`. Strings that
+/// are missing from the second interner can instead be interned into it on demand with
+/// [`KeyTranslator::translate_or_intern`]
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{KeyTranslator, Rodeo};
+///
+/// let mut a = Rodeo::default();
+/// let (a_foo, a_bar) = (a.get_or_intern("foo"), a.get_or_intern("bar"));
+///
+/// let mut b = Rodeo::default();
+/// let b_bar = b.get_or_intern("bar");
+///
+/// let mut translator = KeyTranslator::build(&a, &b);
+/// assert_eq!(translator.translate(a_bar), Some(b_bar));
+/// assert_eq!(translator.translate(a_foo), None);
+///
+/// let b_foo = translator.translate_or_intern(a_foo, &a, &mut b).unwrap();
+/// assert_eq!(b.resolve(&b_foo), "foo");
+/// assert_eq!(translator.translate(a_foo), Some(b_foo));
+/// ```
+///
+pub struct KeyTranslator<KA = Spur, KB = Spur> {
+    keys: KeyMap<KA, KB>,
+}
+
+impl<KA, KB> KeyTranslator<KA, KB> {
+    /// Create a `KeyTranslator` that doesn't translate any keys yet, to be filled with
+    /// [`KeyTranslator::translate_or_intern`]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn new() -> Self {
+        Self {
+            keys: KeyMap::new(),
+        }
+    }
+
+    /// Returns the number of keys that can be translated
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if no keys can be translated
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+impl<KA, KB> KeyTranslator<KA, KB>
+where
+    KA: Key,
+    KB: Key,
+{
+    /// Builds the translation table from every key of `from` to the key of the same string in
+    /// `to`, if it has one
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{KeyTranslator, Rodeo};
+    ///
+    /// let mut a = Rodeo::default();
+    /// let a_key = a.get_or_intern("shared");
+    /// a.get_or_intern("only in a");
+    ///
+    /// let mut b = Rodeo::default();
+    /// b.get_or_intern("only in b");
+    /// let b_key = b.get_or_intern("shared");
+    ///
+    /// let translator = KeyTranslator::build(&a, &b);
+    /// assert_eq!(translator.translate(a_key), Some(b_key));
+    /// assert_eq!(translator.len(), 1);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn build<A, B>(from: &A, to: &B) -> Self
+    where
+        A: Resolver<KA> + ?Sized,
+        B: Reader<KB> + ?Sized,
+    {
+        let mut keys = KeyMap::with_capacity(from.len());
+
+        // Interners give out their keys in order, so every key of `from` is below its length
+        for key in (0..from.len()).filter_map(KA::try_from_usize) {
+            if let Some(translated) = from.try_resolve(&key).and_then(|string| to.get(string)) {
+                keys.insert(key, translated);
+            }
+        }
+
+        Self { keys }
+    }
+
+    /// Translates a key of the first interner into the key of the same string in the second,
+    /// returning `None` if there isn't one
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn translate(&self, key: KA) -> Option<KB> {
+        self.keys.get(key).copied()
+    }
+
+    /// Translates a key of `from` into a key of `to`, interning its string into `to` if it
+    /// can't be translated yet
+    ///
+    /// Returns `None` if `from` doesn't contain the key
+    ///
+    /// # Panics
+    ///
+    /// Panics if `to` is unable to give out any more keys, see [`Interner::get_or_intern()`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{KeyTranslator, Rodeo};
+    ///
+    /// let mut a = Rodeo::default();
+    /// let key = a.get_or_intern("Hello, world!");
+    ///
+    /// let mut b = Rodeo::default();
+    /// let mut translator = KeyTranslator::new();
+    ///
+    /// let translated = translator.translate_or_intern(key, &a, &mut b).unwrap();
+    /// assert_eq!(b.resolve(&translated), "Hello, world!");
+    /// assert_eq!(translator.translate(key), Some(translated));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn translate_or_intern<A, B>(&mut self, key: KA, from: &A, to: &mut B) -> Option<KB>
+    where
+        A: Resolver<KA> + ?Sized,
+        B: Interner<KB> + ?Sized,
+    {
+        self.try_translate_or_intern(key, from, to)
+            .expect("Failed to get or intern string")
+    }
+
+    /// Translates a key of `from` into a key of `to`, interning its string into `to` if it
+    /// can't be translated yet
+    ///
+    /// Returns `Ok(None)` if `from` doesn't contain the key
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_translate_or_intern<A, B>(
+        &mut self,
+        key: KA,
+        from: &A,
+        to: &mut B,
+    ) -> LassoResult<Option<KB>>
+    where
+        A: Resolver<KA> + ?Sized,
+        B: Interner<KB> + ?Sized,
+    {
+        if let Some(translated) = self.translate(key) {
+            return Ok(Some(translated));
+        }
+
+        let string = match from.try_resolve(&key) {
+            Some(string) => string,
+            None => return Ok(None),
+        };
+        let translated = to.try_get_or_intern(string)?;
+        self.keys.insert(key, translated);
+
+        Ok(Some(translated))
+    }
+}
+
+impl<KA, KB> Default for KeyTranslator<KA, KB> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<KA, KB> Clone for KeyTranslator<KA, KB>
+where
+    KB: Clone,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self {
+            keys: self.keys.clone(),
+        }
+    }
+}
+
+impl<KA, KB> Debug for KeyTranslator<KA, KB>
+where
+    KA: Key + Debug,
+    KB: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyTranslator")
+            .field("keys", &self.keys)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Key, KeyTranslator, MiniSpur, Rodeo, Spur};
+
+    #[test]
+    fn build() {
+        let mut a = Rodeo::default();
+        let a_keys = ["a", "b", "c"].map(|string| a.get_or_intern(string));
+
+        let mut b: Rodeo<MiniSpur> = Rodeo::new();
+        let b_keys = ["c", "a"].map(|string| b.get_or_intern(string));
+
+        let translator = KeyTranslator::build(&a, &b);
+        assert_eq!(translator.len(), 2);
+        assert_eq!(translator.translate(a_keys[0]), Some(b_keys[1]));
+        assert_eq!(translator.translate(a_keys[1]), None);
+        assert_eq!(translator.translate(a_keys[2]), Some(b_keys[0]));
+        assert_eq!(
+            translator.translate(Spur::try_from_usize(100).unwrap()),
+            None
+        );
+
+        let empty: KeyTranslator<Spur, MiniSpur> = KeyTranslator::build(&Rodeo::default(), &b);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn translate_or_intern() {
+        let mut a = Rodeo::default();
+        let a_keys = ["a", "b", "c"].map(|string| a.get_or_intern(string));
+
+        let mut b = Rodeo::default();
+        let b_c = b.get_or_intern("c");
+
+        let mut translator = KeyTranslator::build(&a, &b);
+        let b_a = translator
+            .translate_or_intern(a_keys[0], &a, &mut b)
+            .unwrap();
+        assert_eq!(b.resolve(&b_a), "a");
+        assert_eq!(translator.translate(a_keys[0]), Some(b_a));
+
+        // Already translated keys don't touch either interner
+        assert_eq!(
+            translator.translate_or_intern(a_keys[2], &a, &mut b),
+            Some(b_c)
+        );
+        assert_eq!(b.len(), 2);
+
+        let missing = Spur::try_from_usize(100).unwrap();
+        assert_eq!(translator.translate_or_intern(missing, &a, &mut b), None);
+        assert_eq!(translator.len(), 2);
+    }
+}
@@ -1,5 +1,7 @@
 use core::{
+    cmp::Ordering,
     fmt::{self, Debug, Write},
+    marker::PhantomData,
     num::{NonZeroU16, NonZeroU32, NonZeroU8, NonZeroUsize},
 };
 
@@ -11,20 +13,85 @@ use core::{
 ///
 /// [`ReadOnlyLasso`]: crate::ReadOnlyLasso
 pub unsafe trait Key: Copy + Eq {
+    /// The largest index that can be made into a key, [`Key::try_from_usize`] must return
+    /// `None` for anything above it
+    ///
+    /// Defaults to `usize::MAX`, which makes no promises about the key's range
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, MicroSpur};
+    ///
+    /// assert_eq!(MicroSpur::MAX_INDEX, 254);
+    /// assert!(MicroSpur::try_from_usize(MicroSpur::MAX_INDEX).is_some());
+    /// assert!(MicroSpur::try_from_usize(MicroSpur::MAX_INDEX + 1).is_none());
+    /// ```
+    ///
+    const MAX_INDEX: usize = usize::MAX;
+
     /// Returns the `usize` that represents the current key
     fn into_usize(self) -> usize;
 
     /// Attempts to create a key from a `usize`, returning `None` if it fails
     fn try_from_usize(int: usize) -> Option<Self>;
+
+    /// Returns the `u32` that represents the current key when passing it across an FFI boundary
+    ///
+    /// FFI keys are the key's index plus one so that [`FFI_INVALID_KEY`] (zero) can never
+    /// be a valid key. If the key's index is too large to be represented, [`FFI_INVALID_KEY`]
+    /// is returned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, Spur, FFI_INVALID_KEY};
+    ///
+    /// let key = Spur::try_from_usize(0).unwrap();
+    /// assert_eq!(key.to_ffi_u32(), 1);
+    /// assert_ne!(key.to_ffi_u32(), FFI_INVALID_KEY);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn to_ffi_u32(self) -> u32 {
+        self.into_usize()
+            .checked_add(1)
+            .and_then(|int| u32::try_from(int).ok())
+            .unwrap_or(FFI_INVALID_KEY)
+    }
+
+    /// Attempts to create a key from a `u32` produced by [`Key::to_ffi_u32`], returning `None`
+    /// if `int` is [`FFI_INVALID_KEY`] or otherwise isn't a valid key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, Spur, FFI_INVALID_KEY};
+    ///
+    /// let key = Spur::try_from_usize(0).unwrap();
+    /// assert_eq!(Spur::from_ffi_u32(key.to_ffi_u32()), Some(key));
+    /// assert_eq!(Spur::from_ffi_u32(FFI_INVALID_KEY), None);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from_ffi_u32(int: u32) -> Option<Self> {
+        let index = usize::try_from(int).ok()?.checked_sub(1)?;
+        Self::try_from_usize(index)
+    }
 }
 
+/// The FFI representation of an invalid key, see [`Key::to_ffi_u32`] and [`Key::from_ffi_u32`]
+pub const FFI_INVALID_KEY: u32 = 0;
+
 /// A key type taking up `size_of::<usize>()` bytes of space (generally 4 or 8 bytes)
 ///
-/// Internally is a `NonZeroUsize` to allow for space optimizations when stored inside of an [`Option`]
+/// Internally is a `NonZeroUsize` to allow for space optimizations when stored inside of an [`Option`].
+/// Keys are ordered, and serialized, by their index as given by [`Key::into_usize`] rather
+/// than by their internal representation
 ///
 /// [`ReadOnlyLasso`]: crate::ReadOnlyLasso
 /// [`Option`]: https://doc.rust-lang.org/std/option/enum.Option.html
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct LargeSpur {
     key: NonZeroUsize,
@@ -39,6 +106,8 @@ impl LargeSpur {
 }
 
 unsafe impl Key for LargeSpur {
+    const MAX_INDEX: usize = usize::MAX - 1;
+
     #[cfg_attr(feature = "inline-more", inline)]
     fn into_usize(self) -> usize {
         self.key.get() - 1
@@ -79,11 +148,13 @@ impl Debug for LargeSpur {
 
 /// The default key for every Rodeo, uses only 32 bits of space
 ///
-/// Internally is a `NonZeroU32` to allow for space optimizations when stored inside of an [`Option`]
+/// Internally is a `NonZeroU32` to allow for space optimizations when stored inside of an [`Option`].
+/// Keys are ordered, and serialized, by their index as given by [`Key::into_usize`] rather
+/// than by their internal representation
 ///
 /// [`ReadOnlyLasso`]: crate::ReadOnlyLasso
 /// [`Option`]: https://doc.rust-lang.org/std/option/enum.Option.html
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct Spur {
     key: NonZeroU32,
@@ -98,6 +169,8 @@ impl Spur {
 }
 
 unsafe impl Key for Spur {
+    const MAX_INDEX: usize = u32::MAX as usize - 1;
+
     #[cfg_attr(feature = "inline-more", inline)]
     fn into_usize(self) -> usize {
         self.key.get() as usize - 1
@@ -138,11 +211,13 @@ impl Debug for Spur {
 
 /// A miniature Key utilizing only 16 bits of space
 ///
-/// Internally is a `NonZeroU16` to allow for space optimizations when stored inside of an [`Option`]
+/// Internally is a `NonZeroU16` to allow for space optimizations when stored inside of an [`Option`].
+/// Keys are ordered, and serialized, by their index as given by [`Key::into_usize`] rather
+/// than by their internal representation
 ///
 /// [`ReadOnlyLasso`]: crate::ReadOnlyLasso
 /// [`Option`]: https://doc.rust-lang.org/std/option/enum.Option.html
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct MiniSpur {
     key: NonZeroU16,
@@ -157,6 +232,8 @@ impl MiniSpur {
 }
 
 unsafe impl Key for MiniSpur {
+    const MAX_INDEX: usize = u16::MAX as usize - 1;
+
     #[cfg_attr(feature = "inline-more", inline)]
     fn into_usize(self) -> usize {
         self.key.get() as usize - 1
@@ -197,11 +274,13 @@ impl Debug for MiniSpur {
 
 /// A miniature Key utilizing only 8 bits of space
 ///
-/// Internally is a `NonZeroU8` to allow for space optimizations when stored inside of an [`Option`]
+/// Internally is a `NonZeroU8` to allow for space optimizations when stored inside of an [`Option`].
+/// Keys are ordered, and serialized, by their index as given by [`Key::into_usize`] rather
+/// than by their internal representation
 ///
 /// [`ReadOnlyLasso`]: crate::ReadOnlyLasso
 /// [`Option`]: https://doc.rust-lang.org/std/option/enum.Option.html
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct MicroSpur {
     key: NonZeroU8,
@@ -216,6 +295,8 @@ impl MicroSpur {
 }
 
 unsafe impl Key for MicroSpur {
+    const MAX_INDEX: usize = u8::MAX as usize - 1;
+
     #[cfg_attr(feature = "inline-more", inline)]
     fn into_usize(self) -> usize {
         self.key.get() as usize - 1
@@ -254,16 +335,317 @@ impl Debug for MicroSpur {
     }
 }
 
+/// The keys of a table of strings that were interned when creating an interner
+///
+/// Seed strings are interned in order before anything else, so the string at index `i` of
+/// the table always has the key `K::try_from_usize(i)`. This lets well-known strings like
+/// keywords be looked up in constant time without hashing or storing their keys separately
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{Rodeo, Spur};
+///
+/// const KEYWORDS: &[&str] = &["fn", "let", "struct"];
+///
+/// let (mut rodeo, keywords) = Rodeo::<Spur>::with_seed_strings(KEYWORDS);
+///
+/// let key = rodeo.get_or_intern("let");
+/// assert_eq!(keywords.get(1), Some(key));
+/// assert_eq!(keywords.index_of(key), Some(1));
+/// ```
+///
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SeededKeys<K> {
+    len: usize,
+    __key: PhantomData<K>,
+}
+
+impl<K> SeededKeys<K>
+where
+    K: Key,
+{
+    /// Create the keys for a table of `len` strings, which must have been interned
+    /// in order into an empty interner
+    pub(crate) fn new(len: usize) -> Self {
+        Self {
+            len,
+            __key: PhantomData,
+        }
+    }
+
+    /// Get the key of the seed string at `index`, returning `None` if it is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, Spur};
+    ///
+    /// let (rodeo, seeds) = Rodeo::<Spur>::with_seed_strings(&["true", "false"]);
+    ///
+    /// assert_eq!(rodeo.resolve(&seeds.get(0).unwrap()), "true");
+    /// assert_eq!(seeds.get(2), None);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get(&self, index: usize) -> Option<K> {
+        if index < self.len {
+            K::try_from_usize(index)
+        } else {
+            None
+        }
+    }
+
+    /// Get the index of `key` within the seed strings, returning `None` if it isn't
+    /// one of the seeds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, Spur};
+    ///
+    /// let (mut rodeo, seeds) = Rodeo::<Spur>::with_seed_strings(&["true", "false"]);
+    ///
+    /// assert_eq!(seeds.index_of(rodeo.get_or_intern("false")), Some(1));
+    /// assert_eq!(seeds.index_of(rodeo.get_or_intern("maybe")), None);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn index_of(&self, key: K) -> Option<usize> {
+        let index = key.into_usize();
+        (index < self.len).then_some(index)
+    }
+
+    /// Returns `true` if `key` belongs to one of the seed strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains(&self, key: K) -> bool {
+        key.into_usize() < self.len
+    }
+
+    /// Gets the number of seed strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no seed strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the keys of the seed strings, in table order
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter(&self) -> impl Iterator<Item = K> {
+        (0..self.len).filter_map(K::try_from_usize)
+    }
+}
+
+impl<K> Debug for SeededKeys<K> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SeededKeys")
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+macro_rules! impl_ord {
+    ($($type:ident),* $(,)?) => {
+        $(
+            impl PartialOrd for $type {
+                #[cfg_attr(feature = "inline-more", inline)]
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl Ord for $type {
+                #[cfg_attr(feature = "inline-more", inline)]
+                fn cmp(&self, other: &Self) -> Ordering {
+                    self.into_usize().cmp(&other.into_usize())
+                }
+            }
+        )*
+    };
+}
+
+// Order keys by their index
+impl_ord! {
+    Spur,
+    MiniSpur,
+    MicroSpur,
+    LargeSpur,
+}
+
+macro_rules! impl_raw {
+    ($($type:ident => $int:ident, $nonzero:ident),* $(,)?) => {
+        $(
+            impl $type {
+                #[doc = concat!("Returns the raw `", stringify!($int), "` backing the current `", stringify!($type), "`")]
+                ///
+                /// The raw value is the key's index plus one, so it's never zero. Unlike
+                /// [`Key::into_usize`] it takes no arithmetic to produce, which makes it
+                /// suitable for packing keys into other structures
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                #[doc = concat!("use lasso::{Key, ", stringify!($type), "};")]
+                ///
+                #[doc = concat!("let key = ", stringify!($type), "::try_from_usize(0).unwrap();")]
+                /// assert_eq!(key.into_raw(), 1);
+                #[doc = concat!("assert_eq!(", stringify!($type), "::from_raw(key.into_raw()), Some(key));")]
+                /// ```
+                ///
+                #[cfg_attr(feature = "inline-more", inline)]
+                pub const fn into_raw(self) -> $int {
+                    self.key.get()
+                }
+
+                #[doc = concat!("Creates a `", stringify!($type), "` from a raw value produced by [`", stringify!($type), "::into_raw`],")]
+                /// returning `None` if it's zero
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                #[doc = concat!("use lasso::", stringify!($type), ";")]
+                ///
+                #[doc = concat!("assert!(", stringify!($type), "::from_raw(0).is_none());")]
+                #[doc = concat!("assert!(", stringify!($type), "::from_raw(1).is_some());")]
+                /// ```
+                ///
+                #[cfg_attr(feature = "inline-more", inline)]
+                pub const fn from_raw(raw: $int) -> Option<Self> {
+                    match $nonzero::new(raw) {
+                        Some(key) => Some(Self { key }),
+                        None => None,
+                    }
+                }
+
+                #[doc = concat!("Creates a `", stringify!($type), "` from a raw value produced by [`", stringify!($type), "::into_raw`]")]
+                /// without checking it
+                ///
+                /// # Safety
+                ///
+                /// `raw` must not be zero
+                ///
+                #[cfg_attr(feature = "inline-more", inline)]
+                pub const unsafe fn from_raw_unchecked(raw: $int) -> Self {
+                    // Safety: The caller promises that `raw` isn't zero
+                    Self {
+                        key: unsafe { $nonzero::new_unchecked(raw) },
+                    }
+                }
+            }
+        )*
+    };
+}
+
+// Expose the raw representation of keys
+impl_raw! {
+    Spur => u32, NonZeroU32,
+    MiniSpur => u16, NonZeroU16,
+    MicroSpur => u8, NonZeroU8,
+    LargeSpur => usize, NonZeroUsize,
+}
+
+macro_rules! impl_wire {
+    ($($type:ident => $wire:ident, $size:literal),* $(,)?) => {
+        $(
+            impl $type {
+                #[doc = concat!("The number of bytes a `", stringify!($type), "` takes up when written with [`", stringify!($type), "::write_le`]")]
+                #[doc = concat!("or [`", stringify!($type), "::write_be`]")]
+                ///
+                /// Keys are written as their index (the same value as [`Key::into_usize`]) in a
+                #[doc = concat!("`", stringify!($wire), "`, so the written bytes are the same on every target no matter")]
+                /// its pointer width or endianness and will stay the same in future versions
+                pub const WIRE_SIZE: usize = $size;
+
+                #[doc = concat!("Writes the key's index into `buf` as a little-endian `", stringify!($wire), "`")]
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                #[doc = concat!("use lasso::{Key, ", stringify!($type), "};")]
+                ///
+                #[doc = concat!("let key = ", stringify!($type), "::try_from_usize(42).unwrap();")]
+                #[doc = concat!("let mut buf = [0; ", stringify!($type), "::WIRE_SIZE];")]
+                /// key.write_le(&mut buf);
+                ///
+                #[doc = concat!("assert_eq!(buf, 42", stringify!($wire), ".to_le_bytes());")]
+                #[doc = concat!("assert_eq!(", stringify!($type), "::read_le(&buf), Some(key));")]
+                /// ```
+                ///
+                #[cfg_attr(feature = "inline-more", inline)]
+                pub fn write_le(&self, buf: &mut [u8; $size]) {
+                    *buf = self.into_wire().to_le_bytes();
+                }
+
+                #[doc = concat!("Writes the key's index into `buf` as a big-endian `", stringify!($wire), "`")]
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                #[doc = concat!("use lasso::{Key, ", stringify!($type), "};")]
+                ///
+                #[doc = concat!("let key = ", stringify!($type), "::try_from_usize(42).unwrap();")]
+                #[doc = concat!("let mut buf = [0; ", stringify!($type), "::WIRE_SIZE];")]
+                /// key.write_be(&mut buf);
+                ///
+                #[doc = concat!("assert_eq!(buf, 42", stringify!($wire), ".to_be_bytes());")]
+                #[doc = concat!("assert_eq!(", stringify!($type), "::read_be(&buf), Some(key));")]
+                /// ```
+                ///
+                #[cfg_attr(feature = "inline-more", inline)]
+                pub fn write_be(&self, buf: &mut [u8; $size]) {
+                    *buf = self.into_wire().to_be_bytes();
+                }
+
+                #[doc = concat!("Reads a key written by [`", stringify!($type), "::write_le`], returning `None` if the index")]
+                /// can't be represented by the key on the current target
+                #[cfg_attr(feature = "inline-more", inline)]
+                pub fn read_le(buf: &[u8; $size]) -> Option<Self> {
+                    Self::from_wire($wire::from_le_bytes(*buf))
+                }
+
+                #[doc = concat!("Reads a key written by [`", stringify!($type), "::write_be`], returning `None` if the index")]
+                /// can't be represented by the key on the current target
+                #[cfg_attr(feature = "inline-more", inline)]
+                pub fn read_be(buf: &[u8; $size]) -> Option<Self> {
+                    Self::from_wire($wire::from_be_bytes(*buf))
+                }
+
+                fn into_wire(self) -> $wire {
+                    // Every index fits since the wire type is at least as large as the raw one
+                    self.into_usize() as $wire
+                }
+
+                fn from_wire(index: $wire) -> Option<Self> {
+                    usize::try_from(index).ok().and_then(Self::try_from_usize)
+                }
+            }
+        )*
+    };
+}
+
+// Expose a target independent representation of keys
+impl_wire! {
+    Spur => u32, 4,
+    MiniSpur => u16, 2,
+    MicroSpur => u8, 1,
+    LargeSpur => u64, 8,
+}
+
 macro_rules! impl_serde {
     ($($key:ident => $ty:ident),* $(,)?) => {
         #[cfg(feature = "serialize")]
         mod __serde {
-            use super::{$($key),*};
+            use super::{$($key),*, Key};
             use serde::{
-                de::{Deserialize, Deserializer},
+                de::{Deserialize, Deserializer, Error, Unexpected},
                 ser::{Serialize, Serializer},
             };
-            use core::num::{$($ty),*};
 
             $(
                 impl Serialize for $key {
@@ -272,7 +654,8 @@ macro_rules! impl_serde {
                     where
                         S: Serializer,
                     {
-                        self.key.serialize(serializer)
+                        // Serialize the key's index instead of the offset value stored inside of it
+                        (self.into_usize() as $ty).serialize(serializer)
                     }
                 }
 
@@ -282,8 +665,14 @@ macro_rules! impl_serde {
                     where
                         D: Deserializer<'de>,
                     {
-                        let key = <$ty>::deserialize(deserializer)?;
-                        Ok(Self { key })
+                        let index = <$ty>::deserialize(deserializer)?;
+
+                        <$key as Key>::try_from_usize(index as usize).ok_or_else(|| {
+                            D::Error::invalid_value(
+                                Unexpected::Unsigned(index as u64),
+                                &concat!("a ", stringify!($key), " index"),
+                            )
+                        })
                     }
                 }
             )*
@@ -293,10 +682,10 @@ macro_rules! impl_serde {
 
 // Implement `Serialize` and `Deserialize` when the `serde` feature is enabled
 impl_serde! {
-    Spur => NonZeroU32,
-    MiniSpur => NonZeroU16,
-    MicroSpur => NonZeroU8,
-    LargeSpur => NonZeroUsize,
+    Spur => u32,
+    MiniSpur => u16,
+    MicroSpur => u8,
+    LargeSpur => usize,
 }
 
 macro_rules! impl_deepsize {
@@ -395,10 +784,45 @@ impl_abomonation! {
     LargeSpur,
 }
 
+macro_rules! impl_string_interner {
+    ($($type:ident),* $(,)?) => {
+        #[cfg(feature = "string-interner")]
+        mod __string_interner {
+            use super::{$($type),*, Key};
+            use string_interner::Symbol;
+
+            $(
+                impl Symbol for $type {
+                    #[cfg_attr(feature = "inline-more", inline)]
+                    fn try_from_usize(index: usize) -> Option<Self> {
+                        <Self as Key>::try_from_usize(index)
+                    }
+
+                    #[cfg_attr(feature = "inline-more", inline)]
+                    fn to_usize(self) -> usize {
+                        self.into_usize()
+                    }
+                }
+            )*
+        }
+    };
+}
+
+// Implement `string_interner::Symbol` when the `string-interner` feature is enabled
+impl_string_interner! {
+    Spur,
+    MiniSpur,
+    MicroSpur,
+    LargeSpur,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(all(feature = "serialize", feature = "no-std"))]
+    use alloc::string::ToString;
+
     #[test]
     fn large() {
         let zero = LargeSpur::try_from_usize(0).unwrap();
@@ -422,6 +846,49 @@ mod tests {
         LargeSpur::try_from_usize(usize::MAX).unwrap();
     }
 
+    #[test]
+    fn raw() {
+        let key = Spur::try_from_usize(41).unwrap();
+        assert_eq!(key.into_raw(), 42);
+        assert_eq!(Spur::from_raw(42), Some(key));
+        assert_eq!(unsafe { Spur::from_raw_unchecked(42) }, key);
+        assert_eq!(Spur::from_raw(0), None);
+
+        let max = MicroSpur::try_from_usize(MicroSpur::MAX_INDEX).unwrap();
+        assert_eq!(max.into_raw(), u8::MAX);
+        assert_eq!(MicroSpur::from_raw(u8::MAX), Some(max));
+        assert_eq!(MiniSpur::from_raw(1).unwrap().into_usize(), 0);
+        assert_eq!(
+            LargeSpur::from_raw(usize::MAX).unwrap().into_usize(),
+            usize::MAX - 1
+        );
+    }
+
+    #[test]
+    fn wire() {
+        let key = Spur::try_from_usize(0x0102_0304).unwrap();
+        let mut buf = [0; Spur::WIRE_SIZE];
+        key.write_le(&mut buf);
+        assert_eq!(buf, [4, 3, 2, 1]);
+        assert_eq!(Spur::read_le(&buf), Some(key));
+        key.write_be(&mut buf);
+        assert_eq!(buf, [1, 2, 3, 4]);
+        assert_eq!(Spur::read_be(&buf), Some(key));
+
+        // The largest index of a key is one less than its wire type's max
+        assert_eq!(Spur::read_le(&[0xFF; 4]), None);
+        assert_eq!(MicroSpur::read_be(&[0xFF]), None);
+        assert_eq!(MiniSpur::read_le(&[0, 0]).unwrap().into_usize(), 0);
+
+        // Large keys are always eight bytes, no matter the pointer width
+        let large = LargeSpur::try_from_usize(7).unwrap();
+        let mut buf = [0; LargeSpur::WIRE_SIZE];
+        large.write_be(&mut buf);
+        assert_eq!(buf, [0, 0, 0, 0, 0, 0, 0, 7]);
+        assert_eq!(LargeSpur::read_be(&buf), Some(large));
+        assert_eq!(LargeSpur::read_le(&[0xFF; 8]), None);
+    }
+
     #[test]
     fn spur() {
         let zero = Spur::try_from_usize(0).unwrap();
@@ -491,19 +958,91 @@ mod tests {
         MicroSpur::try_from_usize(u8::MAX as usize).unwrap();
     }
 
+    fn ffi_round_trip<K: Key + Debug>(max_index: usize) {
+        for index in [0, 1, max_index / 2, max_index] {
+            let key = K::try_from_usize(index).unwrap();
+            let ffi = key.to_ffi_u32();
+
+            assert_ne!(ffi, FFI_INVALID_KEY);
+            assert_eq!(ffi as usize, index + 1);
+            assert_eq!(K::from_ffi_u32(ffi), Some(key));
+        }
+
+        assert_eq!(K::from_ffi_u32(FFI_INVALID_KEY), None);
+    }
+
     #[test]
-    #[cfg(feature = "serialize")]
-    fn all_serialize() {
-        let large = LargeSpur::try_from_usize(0).unwrap();
-        let _ = serde_json::to_string(&large).unwrap();
+    fn ffi_keys() {
+        ffi_round_trip::<LargeSpur>(u32::MAX as usize - 1);
+        ffi_round_trip::<Spur>(u32::MAX as usize - 1);
+        ffi_round_trip::<MiniSpur>(u16::MAX as usize - 1);
+        ffi_round_trip::<MicroSpur>(u8::MAX as usize - 1);
+
+        assert_eq!(MiniSpur::from_ffi_u32(u16::MAX as u32 + 1), None);
+        assert_eq!(MicroSpur::from_ffi_u32(u8::MAX as u32 + 1), None);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn ffi_large_keys_out_of_range() {
+        let key = LargeSpur::try_from_usize(u32::MAX as usize).unwrap();
+        assert_eq!(key.to_ffi_u32(), FFI_INVALID_KEY);
+    }
+
+    fn index_semantics<K: Key + Ord + Debug>(max_index: usize) {
+        assert_eq!(K::MAX_INDEX, max_index);
+        assert_eq!(
+            K::try_from_usize(K::MAX_INDEX).unwrap().into_usize(),
+            max_index
+        );
+        assert_eq!(K::try_from_usize(K::MAX_INDEX + 1), None);
+
+        let indices = [0, 1, max_index / 2, max_index - 1, max_index];
+        for &left in &indices {
+            for &right in &indices {
+                let (left_key, right_key) = (
+                    K::try_from_usize(left).unwrap(),
+                    K::try_from_usize(right).unwrap(),
+                );
+                assert_eq!(left_key.cmp(&right_key), left.cmp(&right));
+                assert_eq!(left_key.partial_cmp(&right_key), Some(left.cmp(&right)));
+            }
+        }
+    }
 
-        let normal = Spur::try_from_usize(0).unwrap();
-        let _ = serde_json::to_string(&normal).unwrap();
+    #[test]
+    fn key_index_semantics() {
+        index_semantics::<LargeSpur>(usize::MAX - 1);
+        index_semantics::<Spur>(u32::MAX as usize - 1);
+        index_semantics::<MiniSpur>(u16::MAX as usize - 1);
+        index_semantics::<MicroSpur>(u8::MAX as usize - 1);
+    }
 
-        let mini = MiniSpur::try_from_usize(0).unwrap();
-        let _ = serde_json::to_string(&mini).unwrap();
+    #[cfg(feature = "serialize")]
+    fn serializes_index<K>(max_index: usize)
+    where
+        K: Key + Debug + serde::Serialize + serde::de::DeserializeOwned,
+    {
+        for index in [0, 1, max_index / 2, max_index] {
+            let key = K::try_from_usize(index).unwrap();
+            let json = serde_json::to_string(&key).unwrap();
+
+            assert_eq!(json, index.to_string());
+            assert_eq!(serde_json::from_str::<K>(&json).unwrap(), key);
+        }
+    }
 
-        let micro = MicroSpur::try_from_usize(0).unwrap();
-        let _ = serde_json::to_string(&micro).unwrap();
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn all_serialize() {
+        serializes_index::<LargeSpur>(usize::MAX - 1);
+        serializes_index::<Spur>(u32::MAX as usize - 1);
+        serializes_index::<MiniSpur>(u16::MAX as usize - 1);
+        serializes_index::<MicroSpur>(u8::MAX as usize - 1);
+
+        assert!(serde_json::from_str::<LargeSpur>(&usize::MAX.to_string()).is_err());
+        assert!(serde_json::from_str::<Spur>(&u32::MAX.to_string()).is_err());
+        assert!(serde_json::from_str::<MiniSpur>(&u16::MAX.to_string()).is_err());
+        assert!(serde_json::from_str::<MicroSpur>(&u8::MAX.to_string()).is_err());
     }
 }
@@ -0,0 +1,305 @@
+use crate::{Resolver, Spur};
+use core::{
+    cmp::Ordering,
+    fmt::{self, Debug, Display},
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+/// An interned string, made of its key and the resolver it belongs to
+///
+/// Comparing and hashing `LassoString`s only looks at their keys, which is much cheaper than
+/// looking at their strings. This is only correct for strings from the same resolver, so
+/// strings from different resolvers must never be compared with each other. Ordering is done
+/// by the strings themselves so that sorting gives the same results as sorting `str`s
+///
+/// Since it's hashed by key, `LassoString` can't implement `Borrow<str>` and maps keyed by it
+/// have to be looked up with another `LassoString`
+///
+/// The resolver can be any [`Resolver`], by default it's a trait object so that strings
+/// from different kinds of interners have the same type
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{LassoString, Rodeo};
+/// use std::collections::HashSet;
+///
+/// let mut rodeo = Rodeo::default();
+/// let hello = rodeo.get_or_intern("Hello");
+/// let world = rodeo.get_or_intern("World");
+///
+/// let hello = LassoString::new(hello, &rodeo);
+/// let world = LassoString::new(world, &rodeo);
+///
+/// // Strings deref to `str` and can be displayed
+/// assert_eq!(hello.len(), 5);
+/// assert_eq!(format!("{}, {}!", hello, world), "Hello, World!");
+///
+/// // Equality and hashing use keys
+/// let set: HashSet<_> = [hello, world, hello].into_iter().collect();
+/// assert_eq!(set.len(), 2);
+///
+/// // And ordering uses the strings
+/// assert!(hello < world);
+/// ```
+pub struct LassoString<'r, K = Spur, R: ?Sized = dyn Resolver<K> + 'r> {
+    key: K,
+    resolver: &'r R,
+}
+
+impl<'r, K, R> LassoString<'r, K, R>
+where
+    R: Resolver<K> + ?Sized,
+{
+    /// Creates a new `LassoString` from a key and the resolver it belongs to
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key isn't contained in the resolver
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{LassoString, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// let string = LassoString::new(key, &rodeo);
+    /// assert_eq!(&*string, "Hello, world!");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new(key: K, resolver: &'r R) -> Self {
+        Self::try_new(key, resolver).expect("the key isn't contained in the resolver")
+    }
+
+    /// Creates a new `LassoString` from a key and the resolver it belongs to, returning
+    /// `None` if the key isn't contained in the resolver
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, LassoString, Rodeo, Spur};
+    ///
+    /// let rodeo = Rodeo::default();
+    /// let key = Spur::try_from_usize(0).unwrap();
+    ///
+    /// assert!(LassoString::try_new(key, &rodeo).is_none());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_new(key: K, resolver: &'r R) -> Option<Self> {
+        if resolver.contains_key(&key) {
+            Some(Self { key, resolver })
+        } else {
+            None
+        }
+    }
+
+    /// Get the string's key
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Get the resolver that the string belongs to
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolver(&self) -> &'r R {
+        self.resolver
+    }
+
+    /// Get the string, borrowed from the resolver
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{LassoString, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// let string: &str = LassoString::new(key, &rodeo).as_str();
+    /// assert_eq!(string, "Hello, world!");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn as_str(&self) -> &'r str {
+        self.resolver.resolve(&self.key)
+    }
+}
+
+impl<K: Copy, R: ?Sized> Clone for LassoString<'_, K, R> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: Copy, R: ?Sized> Copy for LassoString<'_, K, R> {}
+
+impl<K, R> Deref for LassoString<'_, K, R>
+where
+    R: Resolver<K> + ?Sized,
+{
+    type Target = str;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<K, R> AsRef<str> for LassoString<'_, K, R>
+where
+    R: Resolver<K> + ?Sized,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<K: PartialEq, R: ?Sized> PartialEq for LassoString<'_, K, R> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K: Eq, R: ?Sized> Eq for LassoString<'_, K, R> {}
+
+impl<K, R> PartialEq<str> for LassoString<'_, K, R>
+where
+    R: Resolver<K> + ?Sized,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<K, R> PartialEq<&str> for LassoString<'_, K, R>
+where
+    R: Resolver<K> + ?Sized,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<K: Hash, R: ?Sized> Hash for LassoString<'_, K, R> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
+
+impl<K, R> PartialOrd for LassoString<'_, K, R>
+where
+    K: Eq,
+    R: Resolver<K> + ?Sized,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K, R> Ord for LassoString<'_, K, R>
+where
+    K: Eq,
+    R: Resolver<K> + ?Sized,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.key == other.key {
+            Ordering::Equal
+        } else {
+            self.as_str().cmp(other.as_str())
+        }
+    }
+}
+
+impl<K, R> Display for LassoString<'_, K, R>
+where
+    R: Resolver<K> + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<K, R> Debug for LassoString<'_, K, R>
+where
+    R: Resolver<K> + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LassoString, Resolver, Rodeo, Spur};
+    #[cfg(feature = "no-std")]
+    use alloc::{format, vec::Vec};
+
+    #[test]
+    fn eq_and_hash_by_key() {
+        let mut rodeo = Rodeo::default();
+        let a = rodeo.get_or_intern("a");
+        let b = rodeo.get_or_intern("b");
+
+        let (a1, a2) = (LassoString::new(a, &rodeo), LassoString::new(a, &rodeo));
+        let b = LassoString::new(b, &rodeo);
+        assert_eq!(a1, a2);
+        assert_ne!(a1, b);
+        assert_eq!(a1, "a");
+        assert_eq!(*a1, *"a");
+
+        let mut set = hashbrown::HashSet::new();
+        assert!(set.insert(a1));
+        assert!(!set.insert(a2));
+        assert!(set.insert(b));
+    }
+
+    #[test]
+    fn ord_by_string() {
+        let mut rodeo = Rodeo::default();
+        let keys: Vec<Spur> = ["c", "a", "b"]
+            .iter()
+            .map(|s| rodeo.get_or_intern(s))
+            .collect();
+
+        let mut strings: Vec<LassoString<'_>> = keys
+            .iter()
+            .map(|&key| LassoString::new(key, &rodeo as &dyn Resolver))
+            .collect();
+        strings.sort();
+
+        let sorted: Vec<&str> = strings.iter().map(|s| s.as_str()).collect();
+        assert_eq!(sorted, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn formatting() {
+        let mut rodeo = Rodeo::default();
+        let key = rodeo.get_or_intern("Hello");
+        let string = LassoString::new(key, &rodeo);
+
+        assert_eq!(format!("{}", string), "Hello");
+        assert_eq!(format!("{:?}", string), "\"Hello\"");
+        assert_eq!(format!("{:>7}", string), "  Hello");
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(not(miri))]
+    fn invalid_key() {
+        let rodeo = Rodeo::default();
+        LassoString::new(crate::Key::try_from_usize(0).unwrap(), &rodeo);
+    }
+}
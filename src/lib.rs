@@ -3,6 +3,9 @@
 #![cfg_attr(feature = "inline-more", warn(clippy::missing_inline_in_public_items))]
 // `.copied()` was unstable in 1.34
 #![allow(clippy::map_clone)]
+// The `Key` example marks functions with `#[test]` to show how they'd be tested in a crate,
+// they're called by hand since doctests don't run unit tests
+#![allow(clippy::test_attr_in_doctest)]
 #![deny(
     missing_docs,
     unsafe_op_in_unsafe_fn,
@@ -40,18 +43,46 @@
 //!
 //! ## Cargo Features
 //!
-//! By default `lasso` has one dependency, `hashbrown`, and only [`struct@Rodeo`] is exposed. Hashbrown is used since the
-//! [`raw_entry` api] is currently unstable in the standard library's hashmap.
-//! The raw hashmap API is used for custom hashing within the hashmaps, which works to dramatically reduce memory usage
+//! By default `lasso` has one dependency, `hashbrown`, and only [`struct@Rodeo`] is exposed. Hashbrown is used for its
+//! `HashTable`, which the standard library doesn't have an equivalent of.
+//! Tables let the interners store only keys and hash them as the strings they stand for, which works to dramatically
+//! reduce memory usage
 //! To make use of [`struct@ThreadedRodeo`], you must enable the `multi-threaded` feature.
 //!
 //! * `multi-threaded` - Enables [`struct@ThreadedRodeo`], the interner for multi-threaded tasks
+//! * `dashmap` - Makes [`struct@ThreadedRodeo`] resolve keys through a [`dashmap`] map instead of its built-in
+//!   sharded one
 //! * `ahasher` - Use [`ahash`]'s `RandomState` as the default hasher
+//! * `fxhasher` - Adds [`fxhash`] as [`HasherKind::Fx`] for interners built with [`RodeoBuilder::hasher_kind`]
+//! * `wyhasher` - Adds [`wyhash`] as [`HasherKind::Wy`] for interners built with [`RodeoBuilder::hasher_kind`]
 //! * `no-std` - Enables `no_std` + `alloc` support for [`struct@Rodeo`] and [`struct@ThreadedRodeo`]
 //!   * Automatically enables the following required features:
 //!     * `ahasher` - `no_std` hashing function
 //! * `serialize` - Implements `Serialize` and `Deserialize` for all [`struct@Spur`] types and all interners
 //! * `inline-more` - Annotate external apis with `#[inline]`
+//! * `string-interner` - Enables the [`compat`] module for migrating from the [`string-interner`] crate
+//! * `async` - Enables [`struct@AsyncRodeo`], a wrapper around [`struct@ThreadedRodeo`] that cooperates with async executors
+//!   * Automatically enables the following required features:
+//!     * `multi-threaded` - [`struct@AsyncRodeo`] is built on top of [`struct@ThreadedRodeo`]
+//! * `allocator-api2` - Allows supplying a custom [`Allocator`] for string storage through [`struct@RodeoBuilder`]
+//! * `hugepages` - Allows backing string storage with huge pages on Linux through [`struct@RodeoBuilder`]
+//! * `test-utils` - Enables the [`test_util`] module for checking interners against a reference model
+//! * `proptest` - Adds [`proptest`] strategies for the operations in the [`test_util`] module, which also requires
+//!   the `test-utils` feature
+//! * `locked` - Enables [`struct@LockedRodeo`], a thread-safe interner that works with `no-std` by using a
+//!   user-supplied [`lock_api`] mutex
+//! * `deepsize` - Implements [`DeepSizeOf`] for all interners so memory profilers can measure the memory they
+//!   own, which is the same as their `current_memory_usage()`
+//! * `compact_str` - Adds `resolve_compact()` to all interners for resolving strings into a
+//!   [`CompactString`], which keeps short strings inline
+//! * `profile-interning` - Counts the strings and bytes interned from every call site of [`struct@Rodeo`] and
+//!   [`struct@ThreadedRodeo`], which can be read with `profiling_report()`
+//! * `timestamps` - Records when every string of [`struct@Rodeo`] and [`struct@ThreadedRodeo`] was interned,
+//!   which can be read with `interned_at()` and `iter_older_than()`
+//! * `raw` - Enables the [`raw`] module, which documents how interners hash strings and adds `get_hashed()` for
+//!   looking strings up with hashes that were already made
+//! * `bumpalo` - Enables [`struct@BumpRodeo`], an interner that stores its strings in a borrowed [`bumpalo`]
+//!   `Bump` so they live as long as it does
 //!
 //! ## Example: Using Rodeo
 //!
@@ -280,6 +311,24 @@
 //! assert!(rodeo.contains("blue string"));
 //! ```
 //!
+//! ## Pointer stability
+//!
+//! Interned strings are never moved once they've been stored, the arenas backing [`struct@Rodeo`],
+//! [`struct@ThreadedRodeo`], [`RodeoReader`] and [`RodeoResolver`] only ever add new storage. Turning
+//! an interner into a reader or resolver hands its storage over as-is, so a string's address stays
+//! the same for as long as it's owned by any of them. Raw pointers to interned strings can be taken
+//! with the `resolve_ptr` methods and stay valid until the string is freed, which only happens when
+//! the interner is dropped or cleared. See each type's `resolve_ptr` for the details
+//!
+//! ## Thread safety
+//!
+//! None of the interners implement `Send` or `Sync` by hand, they're derived from their keys and
+//! hashers like any other type. [`struct@Rodeo`], [`RodeoReader`] and [`RodeoResolver`] are `Send`
+//! when their keys and hashers are `Send` and `Sync` when they're `Sync`, except that sharing a
+//! [`RodeoReader`] also needs `Send` keys since its lookup map may be built by whichever thread
+//! gets to it first. [`struct@ThreadedRodeo`] hands keys made on one thread to every other one, so
+//! it's only `Sync` when its keys are both `Send` and `Sync`. [`RcRodeo`] is neither
+//!
 //! ## Benchmarks
 //!
 //! Benchmarks were gathered with [Criterion.rs](https://github.com/bheisler/criterion.rs)  
@@ -423,34 +472,145 @@
 //! [8]: https://img.shields.io/crates/v/lasso.svg
 //! [9]: https://crates.io/crates/lasso
 //! [key]: crate::Key
+//! [`lock_api`]: https://crates.io/crates/lock_api
+//! [`proptest`]: https://crates.io/crates/proptest
+//! [`dashmap`]: https://crates.io/crates/dashmap
+//! [`bumpalo`]: https://crates.io/crates/bumpalo
+//! [`DeepSizeOf`]: https://docs.rs/deepsize/latest/deepsize/trait.DeepSizeOf.html
+//! [`CompactString`]: https://docs.rs/compact_str/latest/compact_str/struct.CompactString.html
 //! [niches]: https://rust-lang.github.io/unsafe-code-guidelines/layout/enums.html#discriminant-elision-on-option-like-enums
 //! [`hashbrown`]: https://crates.io/crates/hashbrown
 //! [`ahash`]: https://crates.io/crates/ahash
-//! [`raw_entry` api]: https://github.com/rust-lang/rust/issues/56167
+//! [`fxhash`]: https://crates.io/crates/fxhash
+//! [`wyhash`]: https://crates.io/crates/wyhash
+//! [`HasherKind::Fx`]: crate::HasherKind
+//! [`HasherKind::Wy`]: crate::HasherKind
+//! [`RodeoBuilder::hasher_kind`]: crate::RodeoBuilder::hasher_kind
+//! [`string-interner`]: https://crates.io/crates/string-interner
+//! [`Allocator`]: https://docs.rs/allocator-api2/latest/allocator_api2/alloc/trait.Allocator.html
 
 extern crate alloc;
 
 #[macro_use]
 mod util;
+
 mod arenas;
+mod builder;
+#[cfg(feature = "bumpalo")]
+mod bump_rodeo;
+mod cached_resolver;
+mod deterministic;
+mod dual_key_rodeo;
+mod escape;
+mod hasher_kind;
 mod interface;
+mod key_map;
+mod key_set;
+#[cfg(feature = "multi-threaded")]
+mod key_shards;
+mod key_translator;
 mod keys;
+mod lasso_string;
+mod multi_rodeo;
+mod offset_rodeo;
+mod persistent_rodeo;
+#[cfg(feature = "profile-interning")]
+mod profile;
+mod rc_rodeo;
 mod reader;
 mod resolver;
+mod resolver_view;
 mod rodeo;
+mod rodeo_map;
+mod rodeo_with_data;
+mod session;
+#[cfg(feature = "multi-threaded")]
+mod shard_map;
+mod sharded_rodeo;
+mod static_resolver;
+#[cfg(feature = "multi-threaded")]
+mod sync;
+#[cfg(feature = "timestamps")]
+mod timestamps;
+mod tiny_rodeo;
+
+#[cfg(feature = "string-interner")]
+pub mod compat;
 
-pub use interface::{Interner, IntoReader, IntoReaderAndResolver, IntoResolver, Reader, Resolver};
-pub use keys::{Key, LargeSpur, MicroSpur, MiniSpur, Spur};
+#[cfg(feature = "test-utils")]
+pub mod test_util;
+
+#[cfg(feature = "raw")]
+pub mod raw;
+
+#[cfg(feature = "serialize")]
+pub mod with_keys;
+
+#[cfg(feature = "locked")]
+mod locked_rodeo;
+
+#[cfg(any(test, doctest))]
+mod auto_traits;
+
+pub use builder::RodeoBuilder;
+#[cfg(feature = "bumpalo")]
+pub use bump_rodeo::BumpRodeo;
+pub use cached_resolver::CachedResolver;
+pub use deterministic::{DeterministicHasher, DeterministicRodeo, FixedHasher, FixedState};
+pub use dual_key_rodeo::DualKeyRodeo;
+pub use escape::EscapeStyle;
+#[cfg(not(feature = "no-std"))]
+pub use hasher_kind::{bench_hashers, HasherBenchmark};
+pub use hasher_kind::{DynamicHasher, DynamicState, HasherKind};
+pub use interface::{
+    DynInterner, DynReader, DynResolver, Interner, IntoReader, IntoReaderAndResolver, IntoResolver,
+    Reader, Resolver, ToInterned,
+};
+pub use key_map::{KeyMap, KeyMapEntry, KeyMapIter, KeyMapIterMut};
+pub use key_set::{KeySet, KeySetIter};
+pub use key_translator::KeyTranslator;
+pub use keys::{Key, LargeSpur, MicroSpur, MiniSpur, SeededKeys, Spur, FFI_INVALID_KEY};
+pub use lasso_string::LassoString;
+#[cfg(feature = "locked")]
+pub use locked_rodeo::LockedRodeo;
+pub use multi_rodeo::{MultiKey, MultiRodeo, MultiValue};
+pub use offset_rodeo::{OffsetRodeo, OffsetSpur};
+pub use persistent_rodeo::{PersistentIter, PersistentRodeo};
+#[cfg(feature = "profile-interning")]
+pub use profile::CallSiteProfile;
+pub use rc_rodeo::{RcKey, RcRodeo};
 pub use reader::RodeoReader;
-pub use resolver::RodeoResolver;
+pub use resolver::{Diff, RodeoResolver};
+pub use resolver_view::ResolverView;
 pub use rodeo::Rodeo;
-pub use util::{Capacity, Iter, LassoError, LassoErrorKind, LassoResult, MemoryLimits, Strings};
+pub use rodeo_map::{RodeoMap, RodeoMapIter, RodeoMapValues};
+pub use rodeo_with_data::{RodeoWithData, RodeoWithDataIter, RodeoWithDataIterMut};
+pub use session::RodeoSession;
+pub use sharded_rodeo::{ShardRemap, ShardedRodeo};
+pub use static_resolver::StaticResolver;
+#[cfg(feature = "timestamps")]
+pub use timestamps::{monotonic_ticks, OlderThan};
+pub use tiny_rodeo::{SmallKey, TinyIter, TinyRodeo};
+pub use util::{
+    BufferTooSmall, Capacity, DedupStats, DisplayKey, FixedArena, Iter, LassoError, LassoErrorKind,
+    LassoResult, MemoryLimits, MemoryStats, MetricsSnapshot, Strings,
+};
 
 compile! {
     if #[all(feature = "multi-threaded", not(feature = "no-std"))] {
+        mod local_handle;
+        mod rodeo_cell;
         mod threaded_rodeo;
 
-        pub use threaded_rodeo::ThreadedRodeo;
+        pub use local_handle::LocalRodeoHandle;
+        pub use rodeo_cell::RodeoCell;
+        pub use threaded_rodeo::{SnapshotIter, ThreadedRodeo};
+
+        #[cfg(feature = "async")]
+        mod async_rodeo;
+
+        #[cfg(feature = "async")]
+        pub use async_rodeo::{AsyncRodeo, InternStream};
 
     // If the `multi-threaded` and `no-std` features are both active
     } else if #[all(feature = "multi-threaded", feature = "no-std")] {
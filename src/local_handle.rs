@@ -0,0 +1,469 @@
+use crate::{hasher::RandomState, key_shards::KeyState, Key, LassoResult, Spur, ThreadedRodeo};
+use core::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    hash::{BuildHasher, Hash},
+};
+use hashbrown::HashMap;
+use std::sync::Arc;
+
+/// A cache of strings and keys that sits in front of a shared [`ThreadedRodeo`]
+///
+/// Strings that the handle has already seen are looked up in its own maps without touching
+/// the shared interner, while new strings are interned into the shared interner so every
+/// handle hands out the same keys. Handles are meant to be owned by a single thread or task,
+/// with one handle made for each of them by [`ThreadedRodeo::local_handle`]
+///
+/// If the shared interner is [cleared] the handle's cache is thrown away the next time it's
+/// used, so stale keys are never handed out from it
+///
+/// # Example
+///
+/// ```rust
+/// # // This keeps this doctest from running under miri since
+/// # // miri doesn't support threading
+/// # #[cfg(not(miri))]
+/// # {
+/// use lasso::ThreadedRodeo;
+/// use std::{sync::Arc, thread};
+///
+/// let rodeo = Arc::new(ThreadedRodeo::default());
+///
+/// let shared = Arc::clone(&rodeo);
+/// let key = thread::spawn(move || {
+///     let mut handle = shared.local_handle();
+///     for _ in 0..100 {
+///         handle.get_or_intern("Hello, world!");
+///     }
+///
+///     handle.get_or_intern("Hello, world!")
+/// })
+/// .join()
+/// .unwrap();
+///
+/// let mut handle = rodeo.local_handle();
+/// assert_eq!(handle.get_or_intern("Hello, world!"), key);
+/// assert_eq!(handle.resolve(&key), "Hello, world!");
+/// # }
+/// ```
+///
+/// [cleared]: ThreadedRodeo::clear
+pub struct LocalRodeoHandle<K = Spur, S = RandomState> {
+    /// The interner that strings the handle hasn't seen yet are interned into
+    shared: Arc<ThreadedRodeo<K, S>>,
+    /// Map that allows str to key resolution of cached strings
+    keys: HashMap<&'static str, K, S>,
+    /// Map that allows key to str resolution of cached strings
+    strings: HashMap<K, &'static str, KeyState>,
+    /// The number of times the shared interner had been cleared when the cache was last used
+    clears: usize,
+}
+
+impl<K, S> LocalRodeoHandle<K, S>
+where
+    K: Key + Hash,
+    S: BuildHasher + Clone,
+{
+    pub(crate) fn new(shared: Arc<ThreadedRodeo<K, S>>) -> Self {
+        let keys = HashMap::with_hasher(shared.hasher().clone());
+        let clears = shared.clears();
+
+        Self {
+            shared,
+            keys,
+            strings: HashMap::with_hasher(KeyState),
+            clears,
+        }
+    }
+
+    /// Get the key for a string, interning it into the shared interner if it does not yet exist
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique strings were interned)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    /// use std::sync::Arc;
+    ///
+    /// let rodeo = Arc::new(ThreadedRodeo::default());
+    /// let mut handle = rodeo.local_handle();
+    ///
+    /// // Interned the string
+    /// let key = handle.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    ///
+    /// // No string was interned, and the shared interner wasn't touched
+    /// let key = handle.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", handle.resolve(&key));
+    /// ```
+    ///
+    /// [`Spur`]: crate::Spur
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_or_intern<T>(&mut self, val: T) -> K
+    where
+        T: AsRef<str>,
+    {
+        self.try_get_or_intern(val)
+            .expect("Failed to get or intern string")
+    }
+
+    /// Get the key for a string, interning it into the shared interner if it does not yet exist
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    /// use std::sync::Arc;
+    ///
+    /// let rodeo = Arc::new(ThreadedRodeo::default());
+    /// let mut handle = rodeo.local_handle();
+    ///
+    /// let key = handle.try_get_or_intern("Strings of things with wings and dings").unwrap();
+    /// assert_eq!("Strings of things with wings and dings", handle.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_get_or_intern<T>(&mut self, val: T) -> LassoResult<K>
+    where
+        T: AsRef<str>,
+    {
+        let string = val.as_ref();
+        self.try_intern(string, |shared| shared.try_get_or_intern(string))
+    }
+
+    /// Get the key for a static string, interning it into the shared interner if it does not
+    /// yet exist
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique strings were interned)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    /// use std::sync::Arc;
+    ///
+    /// let rodeo = Arc::new(ThreadedRodeo::default());
+    /// let mut handle = rodeo.local_handle();
+    ///
+    /// let key = handle.get_or_intern_static("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", handle.resolve(&key));
+    /// ```
+    ///
+    /// [`Spur`]: crate::Spur
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_or_intern_static(&mut self, string: &'static str) -> K {
+        self.try_get_or_intern_static(string)
+            .expect("Failed to get or intern static string")
+    }
+
+    /// Get the key for a static string, interning it into the shared interner if it does not
+    /// yet exist
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    /// use std::sync::Arc;
+    ///
+    /// let rodeo = Arc::new(ThreadedRodeo::default());
+    /// let mut handle = rodeo.local_handle();
+    ///
+    /// let key = handle.try_get_or_intern_static("Strings of things with wings and dings").unwrap();
+    /// assert_eq!("Strings of things with wings and dings", handle.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_get_or_intern_static(&mut self, string: &'static str) -> LassoResult<K> {
+        self.try_intern(string, |shared| shared.try_get_or_intern_static(string))
+    }
+
+    /// Get the key for a string from the cache, falling back to `intern` and caching the
+    /// string it interned
+    #[inline]
+    fn try_intern<F>(&mut self, string: &str, intern: F) -> LassoResult<K>
+    where
+        F: FnOnce(&ThreadedRodeo<K, S>) -> LassoResult<K>,
+    {
+        self.sync();
+
+        if let Some(&key) = self.keys.get(string) {
+            return Ok(key);
+        }
+
+        let key = intern(&self.shared)?;
+
+        // The shared interner can be cleared between interning and looking up the string, in
+        // which case the key's string is stale and it's not cached. The handle notices the clear
+        // next time it's used, so whatever it does cache is thrown away then
+        // Note: The strings live as long as the shared interner and we hold onto it, so they
+        //       live as long as the cache does
+        let cached = self.shared.strings.get(&key);
+        if let Some(cached) = cached.filter(|&cached| cached == string) {
+            self.keys.insert(cached, key);
+            self.strings.insert(key, cached);
+        }
+
+        Ok(key)
+    }
+
+    /// Throws away the cache if the shared interner was cleared since it was last used
+    #[inline]
+    fn sync(&mut self) {
+        let clears = self.shared.clears();
+        if clears != self.clears {
+            self.clear_cache();
+            self.clears = clears;
+        }
+    }
+
+    /// Get the key for a string, returning `None` if it's not interned in the shared interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    /// use std::sync::Arc;
+    ///
+    /// let rodeo = Arc::new(ThreadedRodeo::default());
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// let handle = rodeo.local_handle();
+    /// assert_eq!(Some(key), handle.get("Strings of things with wings and dings"));
+    /// assert_eq!(None, handle.get("This string isn't interned"));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get<T>(&self, val: T) -> Option<K>
+    where
+        T: AsRef<str>,
+    {
+        let string = val.as_ref();
+        if self.is_synced() {
+            if let Some(&key) = self.keys.get(string) {
+                return Some(key);
+            }
+        }
+
+        self.shared.get(string)
+    }
+
+    /// Returns `true` if the given string is interned in the shared interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    /// use std::sync::Arc;
+    ///
+    /// let rodeo = Arc::new(ThreadedRodeo::default());
+    /// let mut handle = rodeo.local_handle();
+    ///
+    /// handle.get_or_intern("Strings of things with wings and dings");
+    /// assert!(handle.contains("Strings of things with wings and dings"));
+    /// assert!(!handle.contains("This string isn't interned"));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains<T>(&self, val: T) -> bool
+    where
+        T: AsRef<str>,
+    {
+        self.get(val).is_some()
+    }
+
+    /// Resolves a string by its key
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    /// use std::sync::Arc;
+    ///
+    /// let rodeo = Arc::new(ThreadedRodeo::default());
+    /// let mut handle = rodeo.local_handle();
+    ///
+    /// let key = handle.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", handle.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve<'a>(&'a self, key: &K) -> &'a str {
+        self.try_resolve(key)
+            .expect("Key out of bounds of LocalRodeoHandle")
+    }
+
+    /// Resolves a string by its key, returning `None` if it is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    /// use std::sync::Arc;
+    ///
+    /// let rodeo = Arc::new(ThreadedRodeo::default());
+    /// let mut handle = rodeo.local_handle();
+    ///
+    /// let key = handle.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!(Some("Strings of things with wings and dings"), handle.try_resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve<'a>(&'a self, key: &K) -> Option<&'a str> {
+        if self.is_synced() {
+            if let Some(&string) = self.strings.get(key) {
+                return Some(string);
+            }
+        }
+
+        self.shared.try_resolve(key)
+    }
+
+    /// Returns `true` if the cache is still valid for the shared interner
+    #[inline]
+    fn is_synced(&self) -> bool {
+        self.shared.clears() == self.clears
+    }
+
+    /// Gets the number of strings cached by the handle
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    /// use std::sync::Arc;
+    ///
+    /// let rodeo = Arc::new(ThreadedRodeo::default());
+    /// rodeo.get_or_intern("Documentation often has little hidden bits in it");
+    ///
+    /// let mut handle = rodeo.local_handle();
+    /// assert_eq!(handle.cached_len(), 0);
+    ///
+    /// handle.get_or_intern("Documentation often has little hidden bits in it");
+    /// assert_eq!(handle.cached_len(), 1);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn cached_len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Throws away every cached string, the strings stay interned in the shared interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    /// use std::sync::Arc;
+    ///
+    /// let rodeo = Arc::new(ThreadedRodeo::default());
+    /// let mut handle = rodeo.local_handle();
+    ///
+    /// let key = handle.get_or_intern("Documentation often has little hidden bits in it");
+    /// handle.clear_cache();
+    ///
+    /// assert_eq!(handle.cached_len(), 0);
+    /// assert_eq!(handle.get("Documentation often has little hidden bits in it"), Some(key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn clear_cache(&mut self) {
+        self.keys.clear();
+        self.strings.clear();
+    }
+
+    /// Returns the shared interner that the handle interns into
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    /// use std::sync::Arc;
+    ///
+    /// let rodeo = Arc::new(ThreadedRodeo::default());
+    /// let handle = rodeo.local_handle();
+    ///
+    /// assert!(Arc::ptr_eq(handle.shared(), &rodeo));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn shared(&self) -> &Arc<ThreadedRodeo<K, S>> {
+        &self.shared
+    }
+}
+
+impl<K, S> Debug for LocalRodeoHandle<K, S>
+where
+    K: Key + Hash + Debug,
+    S: BuildHasher + Clone,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("LocalRodeoHandle")
+            .field("shared", &self.shared)
+            .field("cached", &self.keys)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FixedState;
+
+    #[test]
+    fn caches_strings() {
+        let rodeo = Arc::new(ThreadedRodeo::<Spur, FixedState>::with_hasher(FixedState::new()));
+        let mut handle = rodeo.local_handle();
+
+        let a = handle.get_or_intern("a");
+        let b = handle.get_or_intern_static("b");
+        assert_eq!(handle.cached_len(), 2);
+        assert_eq!(handle.get_or_intern("a"), a);
+        assert_eq!(handle.get("b"), Some(b));
+        assert_eq!(handle.resolve(&a), "a");
+
+        // Strings interned by someone else are only cached once the handle interns them
+        let c = rodeo.get_or_intern("c");
+        assert_eq!(handle.get("c"), Some(c));
+        assert_eq!(handle.try_resolve(&c), Some("c"));
+        assert_eq!(handle.cached_len(), 2);
+        assert_eq!(handle.get_or_intern("c"), c);
+        assert_eq!(handle.cached_len(), 3);
+
+        // Other handles share the same keys
+        let mut other = rodeo.local_handle();
+        assert_eq!(other.get_or_intern("b"), b);
+        assert_eq!(other.cached_len(), 1);
+    }
+
+    #[test]
+    fn shared_clear() {
+        let rodeo: Arc<ThreadedRodeo> = Arc::new(ThreadedRodeo::default());
+        let mut handle = rodeo.local_handle();
+
+        let a = handle.get_or_intern("a");
+        rodeo.clear();
+
+        // The stale cache isn't used, even before the handle is used mutably again
+        assert_eq!(handle.get("a"), None);
+        assert_eq!(handle.try_resolve(&a), None);
+
+        let b = handle.get_or_intern("b");
+        assert_eq!(b, a);
+        assert_eq!(handle.cached_len(), 1);
+        assert_eq!(handle.get("a"), None);
+        assert_eq!(handle.resolve(&b), "b");
+        assert_eq!(rodeo.resolve(&b), "b");
+    }
+}
@@ -0,0 +1,607 @@
+use crate::{hasher::RandomState, Key, LassoResult, Rodeo, RodeoReader, RodeoResolver, Spur};
+use core::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    hash::BuildHasher,
+};
+use lock_api::{Mutex, RawMutex};
+
+/// A thread-safe interner that guards a [`Rodeo`] with a user-supplied mutex
+///
+/// Unlike [`ThreadedRodeo`], which needs the `multi-threaded` feature and so can't be used
+/// with `no-std`, `LockedRodeo` works anywhere a [`lock_api::RawMutex`] is available, like the spin
+/// locks of an embedded RTOS. Every operation takes the lock, so it's best suited for interners
+/// with low contention.
+///
+/// Strings resolved from a `LockedRodeo` stay valid for as long as the interner is borrowed, since
+/// interning never moves or frees previously interned strings.
+///
+/// This struct is only available with the `locked` feature!
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::LockedRodeo;
+///
+/// let rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+///
+/// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+/// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+/// ```
+///
+/// [`ThreadedRodeo`]: crate::ThreadedRodeo
+/// [`lock_api::RawMutex`]: https://docs.rs/lock_api/latest/lock_api/trait.RawMutex.html
+pub struct LockedRodeo<R, K = Spur, S = RandomState> {
+    rodeo: Mutex<R, Rodeo<K, S>>,
+}
+
+impl<R, K> LockedRodeo<R, K, RandomState>
+where
+    R: RawMutex,
+    K: Key,
+{
+    /// Create an empty `LockedRodeo`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{LockedRodeo, Spur};
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>, Spur> = LockedRodeo::new();
+    /// let hello = rodeo.get_or_intern("Hello, ");
+    ///
+    /// assert_eq!("Hello, ", rodeo.resolve(&hello));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new() -> Self {
+        Self::from_rodeo(Rodeo::new())
+    }
+}
+
+impl<R, K, S> LockedRodeo<R, K, S>
+where
+    R: RawMutex,
+    K: Key,
+    S: BuildHasher,
+{
+    /// Create a `LockedRodeo` that takes ownership of the given [`Rodeo`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{LockedRodeo, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, ");
+    ///
+    /// let locked: LockedRodeo<spin::Mutex<()>> = LockedRodeo::from_rodeo(rodeo);
+    /// assert_eq!("Hello, ", locked.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn from_rodeo(rodeo: Rodeo<K, S>) -> Self {
+        Self {
+            rodeo: Mutex::new(rodeo),
+        }
+    }
+
+    /// Create an empty `LockedRodeo` which will use the given hasher for its internal hashmap
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{FixedState, LockedRodeo, Spur};
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>, Spur, FixedState> =
+    ///     LockedRodeo::with_hasher(FixedState::default());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self::from_rodeo(Rodeo::with_hasher(hash_builder))
+    }
+
+    /// Get the key for a string, interning it if it does not yet exist
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's [`try_from_usize`](Key::try_from_usize) function fails. With the default
+    /// keys, this means that you've interned more strings than it can handle. (For [`Spur`] this
+    /// means that `u32::MAX - 1` unique strings were interned)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::LockedRodeo;
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+    ///
+    /// // Interned the string
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    ///
+    /// // No string was interned, as it was already contained
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_or_intern<T>(&self, val: T) -> K
+    where
+        T: AsRef<str>,
+    {
+        self.rodeo.lock().get_or_intern(val)
+    }
+
+    /// Get the key for a string, interning it if it does not yet exist
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::LockedRodeo;
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+    ///
+    /// let key = rodeo.try_get_or_intern("Strings of things with wings and dings").unwrap();
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_get_or_intern<T>(&self, val: T) -> LassoResult<K>
+    where
+        T: AsRef<str>,
+    {
+        self.rodeo.lock().try_get_or_intern(val)
+    }
+
+    /// Get the key for a static string, interning it if it does not yet exist
+    ///
+    /// This will not reallocate or copy the given string
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's [`try_from_usize`](Key::try_from_usize) function fails. With the default
+    /// keys, this means that you've interned more strings than it can handle. (For [`Spur`] this
+    /// means that `u32::MAX - 1` unique strings were interned)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::LockedRodeo;
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+    ///
+    /// let key = rodeo.get_or_intern_static("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_or_intern_static(&self, string: &'static str) -> K {
+        self.rodeo.lock().get_or_intern_static(string)
+    }
+
+    /// Get the key for a static string, interning it if it does not yet exist
+    ///
+    /// This will not reallocate or copy the given string
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::LockedRodeo;
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+    ///
+    /// let key = rodeo.try_get_or_intern_static("Strings of things with wings and dings").unwrap();
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_get_or_intern_static(&self, string: &'static str) -> LassoResult<K> {
+        self.rodeo.lock().try_get_or_intern_static(string)
+    }
+
+    /// Get the key value of a string, returning `None` if it doesn't exist
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::LockedRodeo;
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+    ///
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!(Some(key), rodeo.get("Strings of things with wings and dings"));
+    ///
+    /// assert_eq!(None, rodeo.get("This string isn't interned"));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get<T>(&self, val: T) -> Option<K>
+    where
+        T: AsRef<str>,
+    {
+        self.rodeo.lock().get(val)
+    }
+
+    /// Returns `true` if the given string has been interned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::LockedRodeo;
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+    ///
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert!(rodeo.contains("Strings of things with wings and dings"));
+    ///
+    /// assert!(!rodeo.contains("This string isn't interned"));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains<T>(&self, val: T) -> bool
+    where
+        T: AsRef<str>,
+    {
+        self.get(val).is_some()
+    }
+}
+
+impl<R, K, S> LockedRodeo<R, K, S>
+where
+    R: RawMutex,
+    K: Key,
+{
+    /// Returns `true` if the given key exists in the current interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, LockedRodeo, Spur};
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+    ///
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert!(rodeo.contains_key(&key));
+    ///
+    /// assert!(!rodeo.contains_key(&Spur::try_from_usize(10).unwrap()));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.rodeo.lock().contains_key(key)
+    }
+
+    /// Resolves a string by its key. Only keys made by the current interner may be used
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::LockedRodeo;
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+    ///
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve<'a>(&'a self, key: &K) -> &'a str {
+        let string: *const str = self.rodeo.lock().resolve(key);
+
+        // Safety: Interned strings are never moved or freed while the rodeo is shared, since
+        //         only `get_mut()` and `into_inner()` allow removing them and they require
+        //         exclusive access
+        unsafe { &*string }
+    }
+
+    /// Resolves a string by its key, returning `None` if it's out of bounds. Only keys made by the
+    /// current interner may be used
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, LockedRodeo, Spur};
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+    ///
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!(Some("Strings of things with wings and dings"), rodeo.try_resolve(&key));
+    ///
+    /// assert_eq!(None, rodeo.try_resolve(&Spur::try_from_usize(10).unwrap()));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve<'a>(&'a self, key: &K) -> Option<&'a str> {
+        let string: *const str = self.rodeo.lock().try_resolve(key)?;
+
+        // Safety: Interned strings are never moved or freed while the rodeo is shared, since
+        //         only `get_mut()` and `into_inner()` allow removing them and they require
+        //         exclusive access
+        Some(unsafe { &*string })
+    }
+
+    /// Resolves a string by its key without bounds checks
+    ///
+    /// # Safety
+    ///
+    /// The key must be valid for the current interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::LockedRodeo;
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+    ///
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// unsafe {
+    ///     assert_eq!("Strings of things with wings and dings", rodeo.resolve_unchecked(&key));
+    /// }
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub unsafe fn resolve_unchecked<'a>(&'a self, key: &K) -> &'a str {
+        // Safety: The caller promises the key is valid
+        let string: *const str = unsafe { self.rodeo.lock().resolve_unchecked(key) };
+
+        // Safety: Interned strings are never moved or freed while the rodeo is shared, since
+        //         only `get_mut()` and `into_inner()` allow removing them and they require
+        //         exclusive access
+        unsafe { &*string }
+    }
+
+    /// Gets the number of interned strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::LockedRodeo;
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+    /// rodeo.get_or_intern("Documentation often has little hidden bits in it");
+    ///
+    /// assert_eq!(rodeo.len(), 1);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.rodeo.lock().len()
+    }
+
+    /// Returns `true` if there are no currently interned strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::LockedRodeo;
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get a mutable reference to the underlying [`Rodeo`]
+    ///
+    /// No locking is needed since this requires exclusive access to the interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::LockedRodeo;
+    ///
+    /// let mut rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+    /// rodeo.get_or_intern("Hello, ");
+    ///
+    /// rodeo.get_mut().clear();
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_mut(&mut self) -> &mut Rodeo<K, S> {
+        self.rodeo.get_mut()
+    }
+
+    /// Consumes the current `LockedRodeo`, returning the underlying [`Rodeo`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::LockedRodeo;
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+    /// let key = rodeo.get_or_intern("Hello, ");
+    ///
+    /// let rodeo = rodeo.into_inner();
+    /// assert_eq!("Hello, ", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn into_inner(self) -> Rodeo<K, S> {
+        self.rodeo.into_inner()
+    }
+
+    /// Consumes the current `LockedRodeo`, returning a [`RodeoReader`] to allow contention-free
+    /// access of the interner from multiple threads
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::LockedRodeo;
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+    /// let key = rodeo.get_or_intern("Appear weak when you are strong, and strong when you are weak.");
+    ///
+    /// let reader = rodeo.into_reader();
+    /// assert_eq!(
+    ///     "Appear weak when you are strong, and strong when you are weak.",
+    ///     reader.resolve(&key),
+    /// );
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn into_reader(self) -> RodeoReader<K, S> {
+        self.into_inner().into_reader()
+    }
+
+    /// Consumes the current `LockedRodeo`, returning a [`RodeoResolver`] to allow contention-free
+    /// access of the interner from multiple threads with the lowest possible memory consumption
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::LockedRodeo;
+    ///
+    /// let rodeo: LockedRodeo<spin::Mutex<()>> = LockedRodeo::new();
+    /// let key = rodeo.get_or_intern("Appear weak when you are strong, and strong when you are weak.");
+    ///
+    /// let resolver = rodeo.into_resolver();
+    /// assert_eq!(
+    ///     "Appear weak when you are strong, and strong when you are weak.",
+    ///     resolver.resolve(&key),
+    /// );
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn into_resolver(self) -> RodeoResolver<K> {
+        self.into_inner().into_resolver()
+    }
+}
+
+impl<R, K, S> From<Rodeo<K, S>> for LockedRodeo<R, K, S>
+where
+    R: RawMutex,
+    K: Key,
+    S: BuildHasher,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from(rodeo: Rodeo<K, S>) -> Self {
+        Self::from_rodeo(rodeo)
+    }
+}
+
+impl<R, K, S> Default for LockedRodeo<R, K, S>
+where
+    R: RawMutex,
+    K: Key,
+    S: BuildHasher + Default,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<R, K, S> Debug for LockedRodeo<R, K, S>
+where
+    R: RawMutex,
+    K: Key + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("LockedRodeo")
+            .field("rodeo", &self.rodeo)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Key, LockedRodeo, MicroSpur, Rodeo, Spur};
+
+    #[cfg(feature = "no-std")]
+    use alloc::string::ToString;
+
+    #[cfg(not(any(miri, feature = "no-std")))]
+    use std::{sync::Arc, thread};
+
+    type SpinRodeo<K = Spur> = LockedRodeo<spin::Mutex<()>, K>;
+
+    #[test]
+    fn get_or_intern() {
+        let rodeo: SpinRodeo = SpinRodeo::new();
+
+        let a = rodeo.get_or_intern("A");
+        assert_eq!(a, rodeo.get_or_intern("A"));
+        let b = rodeo.get_or_intern_static("B");
+        assert_ne!(a, b);
+
+        assert_eq!(rodeo.get("A"), Some(a));
+        assert_eq!(rodeo.get("C"), None);
+        assert!(rodeo.contains("B"));
+        assert!(rodeo.contains_key(&b));
+        assert_eq!(rodeo.len(), 2);
+    }
+
+    #[test]
+    fn resolved_strings_outlive_the_lock() {
+        let rodeo: SpinRodeo = SpinRodeo::new();
+
+        let key = rodeo.get_or_intern("A");
+        let string = rodeo.resolve(&key);
+        for i in 0..1000 {
+            rodeo.get_or_intern(i.to_string());
+        }
+
+        assert_eq!(string, "A");
+        assert_eq!(
+            rodeo.try_resolve(&Spur::try_from_usize(1001).unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn key_space_exhaustion() {
+        let rodeo: SpinRodeo<MicroSpur> = SpinRodeo::new();
+
+        for i in 0..u8::MAX as usize - 1 {
+            rodeo.get_or_intern(i.to_string());
+        }
+
+        assert!(rodeo.try_get_or_intern("A").is_ok());
+        assert!(rodeo.try_get_or_intern("B").is_err());
+    }
+
+    #[test]
+    fn into_inner() {
+        let mut rodeo = Rodeo::default();
+        let key = rodeo.get_or_intern("A");
+
+        let locked: SpinRodeo = SpinRodeo::from(rodeo.clone());
+        assert_eq!(locked.resolve(&key), "A");
+        assert_eq!(locked.into_inner(), rodeo);
+    }
+
+    #[test]
+    #[cfg(not(any(miri, feature = "no-std")))]
+    fn threaded() {
+        let rodeo: Arc<SpinRodeo> = Arc::new(SpinRodeo::new());
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let rodeo = Arc::clone(&rodeo);
+                thread::spawn(move || {
+                    (0..100)
+                        .map(|i| rodeo.get_or_intern(i.to_string()))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let keys: Vec<_> = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect();
+        assert!(keys.windows(2).all(|keys| keys[0] == keys[1]));
+        assert_eq!(rodeo.len(), 100);
+    }
+}
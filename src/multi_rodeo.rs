@@ -0,0 +1,677 @@
+use crate::{
+    arenas::Arena, hasher::RandomState, Capacity, Key, LassoError, LassoErrorKind, LassoResult,
+    MemoryLimits, Spur,
+};
+use alloc::vec::Vec;
+use core::{
+    cmp::Ordering,
+    fmt::{self, Debug},
+    hash::{BuildHasher, Hash, Hasher},
+    marker::PhantomData,
+    str,
+};
+use hashbrown::HashTable;
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for str {}
+    impl Sealed for [u8] {}
+}
+
+/// A type of value that can be interned into a [`MultiRodeo`], either `str` or `[u8]`
+///
+/// This trait is sealed and can't be implemented outside of `lasso`
+pub trait MultiValue: sealed::Sealed {
+    /// Tags stored values so that a string and a byte slice with the same contents are
+    /// interned separately
+    #[doc(hidden)]
+    const TAG: u8;
+
+    #[doc(hidden)]
+    fn as_bytes(&self) -> &[u8];
+
+    /// # Safety
+    ///
+    /// `bytes` must have come from [`MultiValue::as_bytes`] of the same type
+    #[doc(hidden)]
+    unsafe fn from_bytes(bytes: &[u8]) -> &Self;
+}
+
+impl MultiValue for str {
+    const TAG: u8 = 0;
+
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+
+    #[inline]
+    unsafe fn from_bytes(bytes: &[u8]) -> &Self {
+        // Safety: The bytes came from a string, so they're valid UTF-8
+        unsafe { str::from_utf8_unchecked(bytes) }
+    }
+}
+
+impl MultiValue for [u8] {
+    const TAG: u8 = 1;
+
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    #[inline]
+    unsafe fn from_bytes(bytes: &[u8]) -> &Self {
+        bytes
+    }
+}
+
+/// A key for a value of type `T` interned into a [`MultiRodeo`]
+///
+/// Keys for strings and byte slices share one key space, but they can't be mixed up since
+/// a `MultiKey<str>` only ever resolves to a `str` and a `MultiKey<[u8]>` to a `[u8]`
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{MultiKey, MultiRodeo};
+///
+/// let mut rodeo = MultiRodeo::default();
+/// let text: MultiKey<str> = rodeo.get_or_intern("atom");
+/// let blob: MultiKey<[u8]> = rodeo.get_or_intern(&b"atom"[..]);
+///
+/// assert_ne!(text.into_inner(), blob.into_inner());
+/// ```
+///
+#[repr(transparent)]
+pub struct MultiKey<T: ?Sized, K = Spur> {
+    key: K,
+    __value: PhantomData<fn() -> *const T>,
+}
+
+impl<T: ?Sized, K> MultiKey<T, K> {
+    const fn new(key: K) -> Self {
+        Self {
+            key,
+            __value: PhantomData,
+        }
+    }
+
+    /// Returns the untyped key, which is unique across every value type
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn into_inner(self) -> K {
+        self.key
+    }
+}
+
+impl<T: ?Sized, K: Copy> Copy for MultiKey<T, K> {}
+
+impl<T: ?Sized, K: Copy> Clone for MultiKey<T, K> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized, K: PartialEq> PartialEq for MultiKey<T, K> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<T: ?Sized, K: Eq> Eq for MultiKey<T, K> {}
+
+impl<T: ?Sized, K: PartialOrd> PartialOrd for MultiKey<T, K> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.key.partial_cmp(&other.key)
+    }
+}
+
+impl<T: ?Sized, K: Ord> Ord for MultiKey<T, K> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl<T: ?Sized, K: Hash> Hash for MultiKey<T, K> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
+
+impl<T: ?Sized, K: Debug> Debug for MultiKey<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MultiKey").field(&self.key).finish()
+    }
+}
+
+/// A value stored in a [`MultiRodeo`] along with the tag of its type
+#[derive(Debug, Clone, Copy)]
+struct Stored {
+    bytes: &'static [u8],
+    tag: u8,
+}
+
+/// An interner that stores both strings and byte slices in a single arena
+///
+/// Both kinds of values share one key space and one memory limit, so the memory used by
+/// either counts towards the same budget. Keys are typed by the value they point to,
+/// see [`MultiKey`]
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::MultiRodeo;
+///
+/// let mut rodeo = MultiRodeo::default();
+/// let text = rodeo.get_or_intern("header");
+/// let blob = rodeo.get_or_intern(&[0xDE, 0xAD, 0xBE, 0xEF][..]);
+///
+/// assert_eq!(rodeo.resolve(&text), "header");
+/// assert_eq!(rodeo.resolve(&blob), &[0xDE, 0xAD, 0xBE, 0xEF]);
+///
+/// // Strings and bytes with the same contents are interned separately
+/// assert_eq!(rodeo.get(&b"header"[..]), None);
+/// ```
+///
+pub struct MultiRodeo<K = Spur, S = RandomState> {
+    /// Map that allows value to key resolution, keys are hashed by their values and tags
+    map: HashTable<K>,
+    hasher: S,
+    /// Vec that allows key to value resolution
+    values: Vec<Stored>,
+    /// The arena that holds every interned value
+    arena: Arena,
+}
+
+impl<K> MultiRodeo<K, RandomState>
+where
+    K: Key,
+{
+    /// Create a new `MultiRodeo`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{MultiRodeo, Spur};
+    ///
+    /// let mut rodeo: MultiRodeo<Spur> = MultiRodeo::new();
+    /// let hello = rodeo.get_or_intern("Hello, ");
+    /// let world = rodeo.get_or_intern(&b"World!"[..]);
+    ///
+    /// assert_eq!("Hello, ", rodeo.resolve(&hello));
+    /// assert_eq!(b"World!", rodeo.resolve(&world));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new() -> Self {
+        Self::with_capacity(Capacity::default())
+    }
+
+    /// Create a new `MultiRodeo` with the specified capacity
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Capacity, MultiRodeo, Spur};
+    ///
+    /// let rodeo: MultiRodeo<Spur> = MultiRodeo::with_capacity(Capacity::for_strings(10));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity(capacity: Capacity) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K, S> MultiRodeo<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    /// Create a new `MultiRodeo` that uses the given hasher
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{MultiRodeo, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: MultiRodeo<Spur, RandomState> = MultiRodeo::with_hasher(RandomState::new());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self::with_capacity_and_hasher(Capacity::default(), hash_builder)
+    }
+
+    /// Create a new `MultiRodeo` with the specified capacity that uses the given hasher
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Capacity, MultiRodeo, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: MultiRodeo<Spur, RandomState> =
+    ///     MultiRodeo::with_capacity_and_hasher(Capacity::for_strings(10), RandomState::new());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity_and_hasher(capacity: Capacity, hash_builder: S) -> Self {
+        Self::try_with_capacity_memory_limits_and_hasher(
+            capacity,
+            MemoryLimits::default(),
+            hash_builder,
+        )
+        .expect("failed to allocate memory for interner")
+    }
+
+    /// Attempts to create a new `MultiRodeo` with the specified capacity and memory limits
+    /// that uses the given hasher
+    ///
+    /// Strings and byte slices both count towards the same memory limit
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Capacity, MemoryLimits, MultiRodeo, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: MultiRodeo<Spur, RandomState> = MultiRodeo::try_with_capacity_memory_limits_and_hasher(
+    ///     Capacity::for_strings(10),
+    ///     MemoryLimits::for_memory_usage(4096),
+    ///     RandomState::new(),
+    /// )
+    /// .unwrap();
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_with_capacity_memory_limits_and_hasher(
+        capacity: Capacity,
+        memory_limits: MemoryLimits,
+        hash_builder: S,
+    ) -> LassoResult<Self> {
+        let mut map = HashTable::new();
+        map.try_reserve(capacity.strings, |_| {
+            unreachable!("the map should be empty while reserving its initial capacity");
+        })
+        .map_err(|_| LassoError::new(LassoErrorKind::FailedAllocation))?;
+
+        let mut values = Vec::new();
+        values
+            .try_reserve(capacity.strings)
+            .map_err(|_| LassoError::new(LassoErrorKind::FailedAllocation))?;
+
+        Ok(Self {
+            map,
+            hasher: hash_builder,
+            values,
+            arena: Arena::new(capacity.bytes, memory_limits.max_memory_usage)?,
+        })
+    }
+
+    /// Get the key for a string or byte slice, interning it if it does not yet exist
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more values than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique values were interned)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::MultiRodeo;
+    ///
+    /// let mut rodeo = MultiRodeo::default();
+    ///
+    /// // Interned the string
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    ///
+    /// // No string was interned, as it was already contained
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    /// [`Spur`]: crate::Spur
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_or_intern<T>(&mut self, val: &T) -> MultiKey<T, K>
+    where
+        T: MultiValue + ?Sized,
+    {
+        self.try_get_or_intern(val)
+            .expect("Failed to get or intern value")
+    }
+
+    /// Get the key for a string or byte slice, interning it if it does not yet exist
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::MultiRodeo;
+    ///
+    /// let mut rodeo = MultiRodeo::default();
+    ///
+    /// let key = rodeo.try_get_or_intern(&b"\x00\x01\x02"[..]).unwrap();
+    /// assert_eq!(b"\x00\x01\x02", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_get_or_intern<T>(&mut self, val: &T) -> LassoResult<MultiKey<T, K>>
+    where
+        T: MultiValue + ?Sized,
+    {
+        let Self {
+            map,
+            hasher,
+            values,
+            arena,
+        } = self;
+
+        let bytes = val.as_bytes();
+        let hash = hasher.hash_one((T::TAG, bytes));
+
+        let existing = map.find(hash, |key: &K| {
+            let stored = values[key.into_usize()];
+            stored.tag == T::TAG && stored.bytes == bytes
+        });
+
+        let key = match existing {
+            Some(&key) => key,
+            None => {
+                let key = K::try_from_usize(values.len()).ok_or_else(|| {
+                    LassoError::new(LassoErrorKind::KeySpaceExhaustion).with_string_len(bytes.len())
+                })?;
+
+                // Safety: The returned slices will be dropped before the arena that created them is
+                let allocated = unsafe { arena.store_bytes(bytes) }
+                    .map_err(|err| err.with_string_len(bytes.len()))?;
+                values.push(Stored {
+                    bytes: allocated,
+                    tag: T::TAG,
+                });
+
+                let values = &*values;
+                map.insert_unique(hash, key, |key| {
+                    let stored = values[key.into_usize()];
+                    hasher.hash_one((stored.tag, stored.bytes))
+                });
+
+                key
+            }
+        };
+
+        Ok(MultiKey::new(key))
+    }
+
+    /// Get the key for a string or byte slice, returning `None` if it hasn't been interned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::MultiRodeo;
+    ///
+    /// let mut rodeo = MultiRodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// assert_eq!(Some(key), rodeo.get("Strings of things with wings and dings"));
+    /// assert_eq!(None, rodeo.get(&b"Strings of things with wings and dings"[..]));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get<T>(&self, val: &T) -> Option<MultiKey<T, K>>
+    where
+        T: MultiValue + ?Sized,
+    {
+        let bytes = val.as_bytes();
+        let hash = self.hasher.hash_one((T::TAG, bytes));
+
+        self.map
+            .find(hash, |key: &K| {
+                let stored = self.values[key.into_usize()];
+                stored.tag == T::TAG && stored.bytes == bytes
+            })
+            .map(|&key| MultiKey::new(key))
+    }
+
+    /// Returns `true` if the given string or byte slice has been interned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::MultiRodeo;
+    ///
+    /// let mut rodeo = MultiRodeo::default();
+    /// rodeo.get_or_intern(&b"blob"[..]);
+    ///
+    /// assert!(rodeo.contains(&b"blob"[..]));
+    /// assert!(!rodeo.contains("blob"));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains<T>(&self, val: &T) -> bool
+    where
+        T: MultiValue + ?Sized,
+    {
+        self.get(val).is_some()
+    }
+
+    /// Returns `true` if the given key exists in the current interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::MultiRodeo;
+    ///
+    /// let mut rodeo = MultiRodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// assert!(rodeo.contains_key(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains_key<T>(&self, key: &MultiKey<T, K>) -> bool
+    where
+        T: MultiValue + ?Sized,
+    {
+        self.try_resolve(key).is_some()
+    }
+
+    /// Resolves a string or byte slice by its key
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds or came from another interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::MultiRodeo;
+    ///
+    /// let mut rodeo = MultiRodeo::default();
+    /// let key = rodeo.get_or_intern(&[1, 2, 3][..]);
+    ///
+    /// assert_eq!(&[1, 2, 3], rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve<'a, T>(&'a self, key: &MultiKey<T, K>) -> &'a T
+    where
+        T: MultiValue + ?Sized,
+    {
+        self.try_resolve(key)
+            .expect("Key out of bounds of MultiRodeo")
+    }
+
+    /// Resolves a string or byte slice by its key, returning `None` if it's out of bounds
+    /// or points to a value of another type
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::MultiRodeo;
+    ///
+    /// let mut rodeo = MultiRodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// assert_eq!(Some("Strings of things with wings and dings"), rodeo.try_resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve<'a, T>(&'a self, key: &MultiKey<T, K>) -> Option<&'a T>
+    where
+        T: MultiValue + ?Sized,
+    {
+        // Keys from other interners can point to values of another type
+        self.values
+            .get(key.key.into_usize())
+            .filter(|stored| stored.tag == T::TAG)
+            // Safety: The value was stored from a `T`
+            .map(|stored| unsafe { T::from_bytes(stored.bytes) })
+    }
+
+    /// Gets the number of interned strings and byte slices
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::MultiRodeo;
+    ///
+    /// let mut rodeo = MultiRodeo::default();
+    /// rodeo.get_or_intern("atom");
+    /// rodeo.get_or_intern(&b"atom"[..]);
+    ///
+    /// assert_eq!(rodeo.len(), 2);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if there are no interned values
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::MultiRodeo;
+    ///
+    /// let rodeo = MultiRodeo::default();
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the memory that the `MultiRodeo`'s arena has allocated for strings and byte slices
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn arena_memory_usage(&self) -> usize {
+        self.arena.memory_usage()
+    }
+
+    /// Get the `MultiRodeo`'s current maximum of allocated memory
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn max_memory_usage(&self) -> usize {
+        self.arena.max_memory_usage
+    }
+}
+
+impl Default for MultiRodeo<Spur, RandomState> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Debug, S: Debug> Debug for MultiRodeo<K, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultiRodeo")
+            .field("map", &self.map)
+            .field("hasher", &self.hasher)
+            .field("values", &self.values)
+            .field("arena", &self.arena)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LassoErrorKind, MicroSpur};
+    use core::num::NonZeroUsize;
+
+    #[test]
+    fn strings_and_bytes() {
+        let mut rodeo = MultiRodeo::default();
+        let text = rodeo.get_or_intern("text");
+        let blob = rodeo.get_or_intern(&b"text"[..]);
+        let invalid = rodeo.get_or_intern(&[0xFF, 0xFE][..]);
+        let empty = rodeo.get_or_intern("");
+
+        assert_eq!(rodeo.len(), 4);
+        assert_ne!(text.into_inner(), blob.into_inner());
+        assert_eq!(rodeo.get_or_intern("text"), text);
+        assert_eq!(rodeo.get_or_intern(&b"text"[..]), blob);
+        assert_eq!(rodeo.get(&[0xFF, 0xFE][..]), Some(invalid));
+        assert_eq!(rodeo.get(&b""[..]), None);
+        assert_eq!(rodeo.len(), 4);
+
+        assert_eq!(rodeo.resolve(&text), "text");
+        assert_eq!(rodeo.resolve(&blob), b"text");
+        assert_eq!(rodeo.resolve(&invalid), &[0xFF, 0xFE]);
+        assert_eq!(rodeo.resolve(&empty), "");
+    }
+
+    #[test]
+    fn mismatched_keys() {
+        let mut other = MultiRodeo::default();
+        let blob = other.get_or_intern(&[0xFF][..]);
+
+        let mut rodeo = MultiRodeo::default();
+        rodeo.get_or_intern("text");
+
+        // Keys from another interner never resolve to a value of the wrong type
+        assert_eq!(rodeo.try_resolve(&blob), None);
+        assert!(!rodeo.contains_key(&blob));
+        assert!(other.contains_key(&blob));
+    }
+
+    #[test]
+    fn shared_limits() {
+        let mut rodeo: MultiRodeo = MultiRodeo::try_with_capacity_memory_limits_and_hasher(
+            Capacity::for_bytes(NonZeroUsize::new(8).unwrap()),
+            MemoryLimits::for_memory_usage(8),
+            RandomState::new(),
+        )
+        .unwrap();
+
+        rodeo.get_or_intern("abcd");
+        rodeo.get_or_intern(&b"efgh"[..]);
+        assert_eq!(rodeo.arena_memory_usage(), 8);
+
+        let err = rodeo.try_get_or_intern(&b"i"[..]).unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::MemoryLimitReached);
+        let err = rodeo.try_get_or_intern("i").unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::MemoryLimitReached);
+    }
+
+    #[test]
+    fn key_space() {
+        let mut rodeo: MultiRodeo<MicroSpur> = MultiRodeo::new();
+        for i in 0..u8::MAX {
+            rodeo.get_or_intern(&[i][..]);
+        }
+
+        let err = rodeo.try_get_or_intern("full").unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::KeySpaceExhaustion);
+    }
+}
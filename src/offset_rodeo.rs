@@ -0,0 +1,727 @@
+use crate::{hasher::RandomState, Capacity, LassoError, LassoErrorKind, LassoResult, MemoryLimits};
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt::{self, Debug},
+    hash::BuildHasher,
+    num::NonZeroU64,
+};
+use hashbrown::HashTable;
+
+/// A key for an [`OffsetRodeo`] that holds the location of its string instead of an index
+///
+/// The key packs the index of the bucket its string lives in, the string's offset within
+/// that bucket and the string's length into a single `u64`, so resolving it never has to
+/// look the string up in a table. Keys are only meaningful to the interner that created them
+///
+/// [`OffsetRodeo`]: crate::OffsetRodeo
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct OffsetSpur {
+    key: NonZeroU64,
+}
+
+impl OffsetSpur {
+    /// The longest string an `OffsetSpur` can point to
+    pub const MAX_LEN: usize = (1 << Self::FIELD_BITS) - 1;
+
+    /// The largest bucket an `OffsetRodeo` will allocate, every offset and length fits in
+    /// [`OffsetSpur::FIELD_BITS`] bits
+    const MAX_BUCKET_SIZE: usize = 1 << Self::FIELD_BITS;
+    /// The number of buckets an `OffsetRodeo` can allocate, bucket indices are stored
+    /// plus one so that keys are never zero
+    const MAX_BUCKETS: usize = (1 << 16) - 1;
+    /// The number of bits taken by a key's offset and by its length
+    const FIELD_BITS: u32 = 24;
+
+    fn new(bucket: usize, offset: usize, len: usize) -> Self {
+        debug_assert!(bucket < Self::MAX_BUCKETS);
+        debug_assert!(offset < Self::MAX_BUCKET_SIZE && len <= Self::MAX_LEN);
+
+        let packed = ((bucket as u64 + 1) << (2 * Self::FIELD_BITS))
+            | ((offset as u64) << Self::FIELD_BITS)
+            | len as u64;
+
+        Self {
+            // Safety: The bucket index is incremented by one and placed in the top bits,
+            //         so the packed key can't be zero
+            key: unsafe { NonZeroU64::new_unchecked(packed) },
+        }
+    }
+
+    /// Returns the [`NonZeroU64`] backing the current `OffsetSpur`
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn into_inner(self) -> NonZeroU64 {
+        self.key
+    }
+
+    /// Returns the length of the string the current `OffsetSpur` points to
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::OffsetRodeo;
+    ///
+    /// let mut rodeo = OffsetRodeo::default();
+    /// let key = rodeo.get_or_intern("Offsets");
+    ///
+    /// assert_eq!(key.len(), 7);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(self) -> usize {
+        (self.key.get() & Self::MAX_LEN as u64) as usize
+    }
+
+    fn offset(self) -> usize {
+        ((self.key.get() >> Self::FIELD_BITS) & Self::MAX_LEN as u64) as usize
+    }
+
+    fn bucket(self) -> usize {
+        (self.key.get() >> (2 * Self::FIELD_BITS)) as usize - 1
+    }
+}
+
+impl Debug for OffsetSpur {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OffsetSpur")
+            .field("bucket", &self.bucket())
+            .field("offset", &self.offset())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// A string interner whose keys hold the location of their strings, so resolving a key
+/// doesn't need any lookups
+///
+/// Resolving an [`OffsetSpur`] only reads the start of the bucket that its string lives in
+/// and slices it, unlike [`Rodeo`] which has to index into a vec of every interned string.
+/// In exchange keys take up eight bytes, no string can be longer than
+/// [`OffsetSpur::MAX_LEN`] and keys aren't dense so they can't index into other collections
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::OffsetRodeo;
+///
+/// let mut rodeo = OffsetRodeo::default();
+/// let key = rodeo.get_or_intern("It is the mark of an educated mind");
+///
+/// assert_eq!(rodeo.resolve(&key), "It is the mark of an educated mind");
+/// assert_eq!(rodeo.get_or_intern("It is the mark of an educated mind"), key);
+/// ```
+///
+/// [`Rodeo`]: crate::Rodeo
+pub struct OffsetRodeo<S = RandomState> {
+    /// Map that allows str to key resolution, keys are hashed by their strings
+    map: HashTable<OffsetSpur>,
+    hasher: S,
+    /// The buckets that strings are stored in, they're never grown past their initial
+    /// capacity so that offsets stay within the bucket's first allocation
+    buckets: Vec<String>,
+    /// The capacity of the next bucket
+    bucket_capacity: usize,
+    memory_usage: usize,
+    max_memory_usage: usize,
+}
+
+impl OffsetRodeo<RandomState> {
+    /// Create a new `OffsetRodeo`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::OffsetRodeo;
+    ///
+    /// let mut rodeo = OffsetRodeo::new();
+    /// let hello = rodeo.get_or_intern("Hello, ");
+    /// let world = rodeo.get_or_intern("World!");
+    ///
+    /// assert_eq!("Hello, ", rodeo.resolve(&hello));
+    /// assert_eq!("World!", rodeo.resolve(&world));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new() -> Self {
+        Self::with_capacity(Capacity::default())
+    }
+
+    /// Create a new `OffsetRodeo` with the specified capacity
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Capacity, OffsetRodeo};
+    ///
+    /// let rodeo = OffsetRodeo::with_capacity(Capacity::for_strings(10));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity(capacity: Capacity) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<S> OffsetRodeo<S>
+where
+    S: BuildHasher,
+{
+    /// Create a new `OffsetRodeo` that uses the given hasher
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::OffsetRodeo;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: OffsetRodeo<RandomState> = OffsetRodeo::with_hasher(RandomState::new());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self::with_capacity_and_hasher(Capacity::default(), hash_builder)
+    }
+
+    /// Create a new `OffsetRodeo` with the specified capacity that uses the given hasher
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Capacity, OffsetRodeo};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: OffsetRodeo<RandomState> =
+    ///     OffsetRodeo::with_capacity_and_hasher(Capacity::for_strings(10), RandomState::new());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity_and_hasher(capacity: Capacity, hash_builder: S) -> Self {
+        Self::try_with_capacity_memory_limits_and_hasher(
+            capacity,
+            MemoryLimits::default(),
+            hash_builder,
+        )
+        .expect("failed to allocate memory for interner")
+    }
+
+    /// Attempts to create a new `OffsetRodeo` with the specified capacity and memory limits
+    /// that uses the given hasher
+    ///
+    /// Only the memory used to store strings counts towards the memory limits
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Capacity, MemoryLimits, OffsetRodeo};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: OffsetRodeo<RandomState> = OffsetRodeo::try_with_capacity_memory_limits_and_hasher(
+    ///     Capacity::for_strings(10),
+    ///     MemoryLimits::for_memory_usage(4096),
+    ///     RandomState::new(),
+    /// )
+    /// .unwrap();
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_with_capacity_memory_limits_and_hasher(
+        capacity: Capacity,
+        memory_limits: MemoryLimits,
+        hash_builder: S,
+    ) -> LassoResult<Self> {
+        let mut map = HashTable::new();
+        map.try_reserve(capacity.strings, |_| {
+            unreachable!("the map should be empty while reserving its initial capacity");
+        })
+        .map_err(|_| LassoError::new(LassoErrorKind::FailedAllocation))?;
+
+        let mut rodeo = Self {
+            map,
+            hasher: hash_builder,
+            buckets: Vec::new(),
+            bucket_capacity: capacity.bytes.get().min(OffsetSpur::MAX_BUCKET_SIZE),
+            memory_usage: 0,
+            max_memory_usage: memory_limits.max_memory_usage,
+        };
+        rodeo.allocate_bucket(0)?;
+
+        Ok(rodeo)
+    }
+
+    /// Get the key for a string, interning it if it does not yet exist
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is longer than [`OffsetSpur::MAX_LEN`] or if the interner runs
+    /// out of memory or buckets
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::OffsetRodeo;
+    ///
+    /// let mut rodeo = OffsetRodeo::default();
+    ///
+    /// // Interned the string
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    ///
+    /// // No string was interned, as it was already contained
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_or_intern<T>(&mut self, val: T) -> OffsetSpur
+    where
+        T: AsRef<str>,
+    {
+        self.try_get_or_intern(val)
+            .expect("Failed to get or intern string")
+    }
+
+    /// Get the key for a string, interning it if it does not yet exist
+    ///
+    /// Strings longer than [`OffsetSpur::MAX_LEN`] fail with
+    /// [`LassoErrorKind::KeySpaceExhaustion`] since no key can point to them
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{LassoErrorKind, OffsetRodeo, OffsetSpur};
+    ///
+    /// let mut rodeo = OffsetRodeo::default();
+    ///
+    /// let key = rodeo.try_get_or_intern("Strings of things with wings and dings").unwrap();
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    ///
+    /// let long = "a".repeat(OffsetSpur::MAX_LEN + 1);
+    /// let err = rodeo.try_get_or_intern(long).unwrap_err();
+    /// assert_eq!(err.kind(), LassoErrorKind::KeySpaceExhaustion);
+    /// ```
+    ///
+    /// [`LassoErrorKind::KeySpaceExhaustion`]: crate::LassoErrorKind::KeySpaceExhaustion
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_get_or_intern<T>(&mut self, val: T) -> LassoResult<OffsetSpur>
+    where
+        T: AsRef<str>,
+    {
+        let string = val.as_ref();
+        let hash = self.hasher.hash_one(string);
+
+        let buckets = &self.buckets;
+        let existing = self
+            .map
+            .find(hash, |key| resolve_in(buckets, *key) == Some(string));
+
+        let key = match existing {
+            Some(&key) => key,
+            None => {
+                let key = store_in(
+                    &mut self.buckets,
+                    &mut self.bucket_capacity,
+                    &mut self.memory_usage,
+                    self.max_memory_usage,
+                    string,
+                )
+                .map_err(|err| err.with_string_len(string.len()))?;
+
+                let (buckets, hasher) = (&self.buckets, &self.hasher);
+                self.map.insert_unique(hash, key, |key| {
+                    hasher.hash_one(resolve_in(buckets, *key).unwrap_or_default())
+                });
+
+                key
+            }
+        };
+
+        Ok(key)
+    }
+
+    /// Get the key value of a string, returning `None` if it doesn't exist
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::OffsetRodeo;
+    ///
+    /// let mut rodeo = OffsetRodeo::default();
+    ///
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!(Some(key), rodeo.get("Strings of things with wings and dings"));
+    ///
+    /// assert_eq!(None, rodeo.get("This string isn't interned"));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get<T>(&self, val: T) -> Option<OffsetSpur>
+    where
+        T: AsRef<str>,
+    {
+        let string = val.as_ref();
+        let hash = self.hasher.hash_one(string);
+
+        self.map
+            .find(hash, |key| resolve_in(&self.buckets, *key) == Some(string))
+            .copied()
+    }
+
+    /// Returns `true` if the given string has been interned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::OffsetRodeo;
+    ///
+    /// let mut rodeo = OffsetRodeo::default();
+    ///
+    /// rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert!(rodeo.contains("Strings of things with wings and dings"));
+    /// assert!(!rodeo.contains("This string isn't interned"));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains<T>(&self, val: T) -> bool
+    where
+        T: AsRef<str>,
+    {
+        self.get(val).is_some()
+    }
+}
+
+impl<S> OffsetRodeo<S> {
+    /// Allocates a new bucket that can hold at least `min_capacity` bytes
+    fn allocate_bucket(&mut self, min_capacity: usize) -> LassoResult<()> {
+        allocate_bucket_in(
+            &mut self.buckets,
+            &mut self.bucket_capacity,
+            &mut self.memory_usage,
+            self.max_memory_usage,
+            min_capacity,
+        )
+    }
+
+    /// Resolves a key to its string
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key wasn't made by the current interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::OffsetRodeo;
+    ///
+    /// let mut rodeo = OffsetRodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve(&self, key: &OffsetSpur) -> &str {
+        self.try_resolve(key)
+            .expect("Key does not exist in interner")
+    }
+
+    /// Resolves a key to its string, returning `None` if the key wasn't made by the
+    /// current interner
+    ///
+    /// Keys from other interners are caught when they point outside of the current
+    /// interner's strings or split a character, but a key that happens to point to valid
+    /// text will resolve to it
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::OffsetRodeo;
+    ///
+    /// let mut rodeo = OffsetRodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// assert_eq!(Some("Strings of things with wings and dings"), rodeo.try_resolve(&key));
+    /// assert_eq!(None, OffsetRodeo::new().try_resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve(&self, key: &OffsetSpur) -> Option<&str> {
+        resolve_in(&self.buckets, *key)
+    }
+
+    /// Resolves a key to its string without checking that it points to a valid string
+    ///
+    /// # Safety
+    ///
+    /// The key must have been made by the current interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::OffsetRodeo;
+    ///
+    /// let mut rodeo = OffsetRodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// unsafe {
+    ///     assert_eq!("Strings of things with wings and dings", rodeo.resolve_unchecked(&key));
+    /// }
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub unsafe fn resolve_unchecked(&self, key: &OffsetSpur) -> &str {
+        let offset = key.offset();
+
+        // Safety: The caller promises the key came from this interner, so its bucket exists
+        //         and its string lies on character boundaries within the bucket
+        unsafe {
+            self.buckets
+                .get_unchecked(key.bucket())
+                .get_unchecked(offset..offset + key.len())
+        }
+    }
+
+    /// Gets the number of interned strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::OffsetRodeo;
+    ///
+    /// let mut rodeo = OffsetRodeo::default();
+    /// rodeo.get_or_intern("Documentation often has little hidden bits in it");
+    ///
+    /// assert_eq!(rodeo.len(), 1);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if there are no currently interned strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::OffsetRodeo;
+    ///
+    /// let rodeo = OffsetRodeo::default();
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the interned strings and their keys, in no particular order
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter(&self) -> impl Iterator<Item = (OffsetSpur, &str)> + '_ {
+        self.map
+            .iter()
+            .map(move |key| (*key, resolve_in(&self.buckets, *key).unwrap_or_default()))
+    }
+
+    /// Get the memory allocated to hold the `OffsetRodeo`'s interned strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn arena_memory_usage(&self) -> usize {
+        self.memory_usage
+    }
+
+    /// Get the `OffsetRodeo`'s current maximum of allocated memory
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn max_memory_usage(&self) -> usize {
+        self.max_memory_usage
+    }
+}
+
+/// Resolves a key into the string it points to within `buckets`, returning `None` if it
+/// doesn't point to a valid string
+fn resolve_in(buckets: &[String], key: OffsetSpur) -> Option<&str> {
+    let offset = key.offset();
+
+    // Every bucket holds valid UTF-8, so any slice of it on character boundaries does too
+    buckets.get(key.bucket())?.get(offset..offset + key.len())
+}
+
+/// Stores a string within the last bucket, allocating a new bucket if it doesn't fit
+fn store_in(
+    buckets: &mut Vec<String>,
+    bucket_capacity: &mut usize,
+    memory_usage: &mut usize,
+    max_memory_usage: usize,
+    string: &str,
+) -> LassoResult<OffsetSpur> {
+    if string.len() > OffsetSpur::MAX_LEN {
+        return Err(LassoError::new(LassoErrorKind::KeySpaceExhaustion));
+    }
+
+    // Empty strings don't take up any space, so they can point to the start of the first
+    // bucket no matter how full it is
+    if string.is_empty() {
+        return Ok(OffsetSpur::new(0, 0, 0));
+    }
+
+    // Buckets are never allowed to grow, since they could move past where offsets can point
+    let fits = |bucket: &String| {
+        bucket.capacity().min(OffsetSpur::MAX_BUCKET_SIZE) - bucket.len() >= string.len()
+    };
+    if !buckets.last().is_some_and(fits) {
+        allocate_bucket_in(
+            buckets,
+            bucket_capacity,
+            memory_usage,
+            max_memory_usage,
+            string.len(),
+        )?;
+    }
+
+    let idx = buckets.len() - 1;
+    let bucket = &mut buckets[idx];
+    let offset = bucket.len();
+    bucket.push_str(string);
+
+    Ok(OffsetSpur::new(idx, offset, string.len()))
+}
+
+/// Allocates a new bucket that can hold at least `min_capacity` bytes, doubling the
+/// capacity of the next bucket up to the largest size an offset can point into
+fn allocate_bucket_in(
+    buckets: &mut Vec<String>,
+    bucket_capacity: &mut usize,
+    memory_usage: &mut usize,
+    max_memory_usage: usize,
+    min_capacity: usize,
+) -> LassoResult<()> {
+    if buckets.len() == OffsetSpur::MAX_BUCKETS {
+        return Err(LassoError::new(LassoErrorKind::KeySpaceExhaustion));
+    }
+
+    let capacity = (*bucket_capacity).max(min_capacity);
+    let new_usage = memory_usage.saturating_add(capacity);
+    if new_usage > max_memory_usage {
+        return Err(LassoError::memory_limit_reached(
+            capacity,
+            *memory_usage,
+            max_memory_usage,
+        ));
+    }
+
+    let mut bucket = String::new();
+    bucket
+        .try_reserve_exact(capacity)
+        .map_err(|_| LassoError::failed_allocation(capacity))?;
+    buckets
+        .try_reserve(1)
+        .map_err(|_| LassoError::new(LassoErrorKind::FailedAllocation))?;
+    buckets.push(bucket);
+
+    *memory_usage = new_usage;
+    *bucket_capacity = bucket_capacity
+        .saturating_mul(2)
+        .min(OffsetSpur::MAX_BUCKET_SIZE);
+
+    Ok(())
+}
+
+/// Creates an `OffsetRodeo` using [`RandomState`] as its hasher
+///
+/// [`RandomState`]: index.html#cargo-features
+impl Default for OffsetRodeo<RandomState> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Debug for OffsetRodeo<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OffsetRodeo")
+            .field("len", &self.len())
+            .field("buckets", &self.buckets.len())
+            .field("memory_usage", &self.memory_usage)
+            .field("max_memory_usage", &self.max_memory_usage)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::num::NonZeroUsize;
+
+    #[cfg(feature = "no-std")]
+    use alloc::string::ToString;
+
+    #[test]
+    fn packing() {
+        let key = OffsetSpur::new(3, 1000, 42);
+        assert_eq!(key.bucket(), 3);
+        assert_eq!(key.offset(), 1000);
+        assert_eq!(key.len(), 42);
+
+        let max = OffsetSpur::new(
+            OffsetSpur::MAX_BUCKETS - 1,
+            OffsetSpur::MAX_BUCKET_SIZE - 1,
+            OffsetSpur::MAX_LEN,
+        );
+        assert_eq!(max.bucket(), OffsetSpur::MAX_BUCKETS - 1);
+        assert_eq!(max.offset(), OffsetSpur::MAX_BUCKET_SIZE - 1);
+        assert_eq!(max.len(), OffsetSpur::MAX_LEN);
+        assert_eq!(max.into_inner().get(), u64::MAX);
+    }
+
+    #[test]
+    fn intern_and_resolve() {
+        let mut rodeo =
+            OffsetRodeo::with_capacity(Capacity::for_bytes(NonZeroUsize::new(4).unwrap()));
+
+        let keys: Vec<_> = (0..100)
+            .map(|i| rodeo.get_or_intern(i.to_string()))
+            .collect();
+        assert_eq!(rodeo.len(), 100);
+        assert!(rodeo.buckets.len() > 1);
+
+        for (i, key) in keys.iter().enumerate() {
+            assert_eq!(rodeo.resolve(key), i.to_string());
+            assert_eq!(unsafe { rodeo.resolve_unchecked(key) }, i.to_string());
+            assert_eq!(rodeo.get(i.to_string()), Some(*key));
+            assert_eq!(rodeo.get_or_intern(i.to_string()), *key);
+        }
+
+        let empty = rodeo.get_or_intern("");
+        assert_eq!(rodeo.resolve(&empty), "");
+        assert_eq!(rodeo.get(""), Some(empty));
+        assert_eq!(rodeo.iter().count(), 101);
+    }
+
+    #[test]
+    fn foreign_keys() {
+        let mut rodeo = OffsetRodeo::new();
+        let key = rodeo.get_or_intern("ü");
+
+        let mut other = OffsetRodeo::new();
+        other.get_or_intern("aü");
+        // Points into the middle of the other interner's `ü`
+        assert_eq!(other.try_resolve(&key), None);
+        assert_eq!(OffsetRodeo::new().try_resolve(&key), None);
+    }
+
+    #[test]
+    fn memory_limits() {
+        let mut rodeo = OffsetRodeo::try_with_capacity_memory_limits_and_hasher(
+            Capacity::for_bytes(NonZeroUsize::new(8).unwrap()),
+            MemoryLimits::for_memory_usage(8),
+            RandomState::new(),
+        )
+        .unwrap();
+
+        rodeo.get_or_intern("01234567");
+        let err = rodeo.try_get_or_intern("8").unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::MemoryLimitReached);
+        assert_eq!(err.string_len(), Some(1));
+        assert_eq!(rodeo.len(), 1);
+        assert_eq!(rodeo.arena_memory_usage(), 8);
+    }
+}
@@ -0,0 +1,752 @@
+//! An immutable interner whose versions share structure with each other
+//!
+//! Strings are kept in two persistent trees, a 32-way trie indexed by key that resolves
+//! keys and a hash array mapped trie that finds the key of a string. Interning a string
+//! copies only the nodes on the path to it, so every version shares all but a handful
+//! of nodes with the version it was made from
+
+use crate::{
+    hasher::RandomState,
+    keys::{Key, Spur},
+    LassoError, LassoErrorKind, LassoResult,
+};
+use alloc::{sync::Arc, vec, vec::Vec};
+use core::{
+    fmt::{self, Debug},
+    hash::BuildHasher,
+    iter::{FromIterator, FusedIterator},
+    marker::PhantomData,
+};
+
+/// The number of hash or index bits consumed by each level of the tries
+const BITS: u32 = 5;
+/// The number of children of each trie node
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+/// An immutable interner where interning a string creates a new version of the interner
+///
+/// Every version stays fully usable after newer versions have been made from it, which
+/// gives cheap snapshots of the interner's contents. Versions share nearly all of their
+/// memory, so interning a string only allocates the few trie nodes on the path to it and
+/// cloning a version is just bumping a few reference counts. Lookups and resolutions walk
+/// a trie that's at most a handful of levels deep
+///
+/// Keys are assigned in insertion order per version, so two versions made from the same
+/// ancestor can give different strings the same key. Keys should only be resolved by the
+/// version that created them or a version derived from it
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::PersistentRodeo;
+///
+/// let empty = PersistentRodeo::default();
+/// let (hello, hello_key) = empty.intern("hello");
+/// let (world, world_key) = hello.intern("world");
+///
+/// // Older versions are untouched by newer ones
+/// assert!(empty.is_empty());
+/// assert_eq!(hello.len(), 1);
+/// assert_eq!(hello.get("world"), None);
+///
+/// assert_eq!(world.resolve(&hello_key), "hello");
+/// assert_eq!(world.resolve(&world_key), "world");
+/// ```
+///
+pub struct PersistentRodeo<K = Spur, S = RandomState> {
+    /// The interned strings, indexed by key
+    strings: PersistentVec,
+    /// The keys of the interned strings, found by hash
+    map: Option<Arc<MapNode<K>>>,
+    hasher: Arc<S>,
+}
+
+impl<K> PersistentRodeo<K, RandomState>
+where
+    K: Key,
+{
+    /// Create an empty `PersistentRodeo`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{PersistentRodeo, Spur};
+    ///
+    /// let rodeo: PersistentRodeo<Spur> = PersistentRodeo::new();
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, S> PersistentRodeo<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    /// Create an empty `PersistentRodeo` which will use the given hasher for every one of
+    /// its versions
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{PersistentRodeo, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: PersistentRodeo<Spur, RandomState> = PersistentRodeo::with_hasher(RandomState::new());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            strings: PersistentVec::new(),
+            map: None,
+            hasher: Arc::new(hash_builder),
+        }
+    }
+
+    /// Interns a string, returning the version of the interner that contains it and the
+    /// string's key
+    ///
+    /// If the string was already interned the returned version is a clone of the current one
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this happens after `u32::MAX - 1` unique strings)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::PersistentRodeo;
+    ///
+    /// let rodeo = PersistentRodeo::default();
+    /// let (rodeo, key) = rodeo.intern("Strings of things with wings and dings");
+    ///
+    /// let (rodeo, again) = rodeo.intern("Strings of things with wings and dings");
+    /// assert_eq!(key, again);
+    /// assert_eq!(rodeo.len(), 1);
+    /// ```
+    ///
+    /// [`Spur`]: crate::Spur
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn intern<T>(&self, val: T) -> (Self, K)
+    where
+        T: AsRef<str>,
+    {
+        self.try_intern(val)
+            .expect("Failed to get or intern string")
+    }
+
+    /// Interns a string, returning the version of the interner that contains it and the
+    /// string's key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::PersistentRodeo;
+    ///
+    /// let rodeo = PersistentRodeo::default();
+    /// let (rodeo, key) = rodeo.try_intern("Strings of things with wings and dings").unwrap();
+    ///
+    /// assert_eq!(rodeo.resolve(&key), "Strings of things with wings and dings");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_intern<T>(&self, val: T) -> LassoResult<(Self, K)>
+    where
+        T: AsRef<str>,
+    {
+        let string = val.as_ref();
+        let hash = self.hasher.hash_one(string);
+
+        if let Some(key) = self.find(string, hash) {
+            return Ok((self.clone(), key));
+        }
+
+        let key = K::try_from_usize(self.strings.len).ok_or_else(|| {
+            LassoError::new(LassoErrorKind::KeySpaceExhaustion).with_string_len(string.len())
+        })?;
+        let string: Arc<str> = Arc::from(string);
+
+        let leaf = MapEntry {
+            hash,
+            string: Arc::clone(&string),
+            key,
+        };
+        let map = match &self.map {
+            Some(root) => root.insert(0, leaf),
+            None => MapNode::single(0, leaf),
+        };
+
+        let version = Self {
+            strings: self.strings.push(string),
+            map: Some(Arc::new(map)),
+            hasher: Arc::clone(&self.hasher),
+        };
+
+        Ok((version, key))
+    }
+
+    /// Returns the key of a string, if it's interned in this version
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::PersistentRodeo;
+    ///
+    /// let (rodeo, key) = PersistentRodeo::default().intern("Strings of things with wings and dings");
+    ///
+    /// assert_eq!(rodeo.get("Strings of things with wings and dings"), Some(key));
+    /// assert_eq!(rodeo.get("This string isn't interned"), None);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get<T>(&self, val: T) -> Option<K>
+    where
+        T: AsRef<str>,
+    {
+        let string = val.as_ref();
+        self.find(string, self.hasher.hash_one(string))
+    }
+
+    /// Returns `true` if the string is interned in this version
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains<T>(&self, val: T) -> bool
+    where
+        T: AsRef<str>,
+    {
+        self.get(val).is_some()
+    }
+
+    fn find(&self, string: &str, hash: u64) -> Option<K> {
+        self.map.as_ref()?.find(0, hash, string)
+    }
+}
+
+impl<K, S> PersistentRodeo<K, S>
+where
+    K: Key,
+{
+    /// Returns `true` if the key was made by this version or one of its ancestors
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains_key(&self, key: &K) -> bool {
+        key.into_usize() < self.strings.len
+    }
+
+    /// Resolves a key into a string
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::PersistentRodeo;
+    ///
+    /// let (rodeo, key) = PersistentRodeo::default().intern("Strings of things with wings and dings");
+    /// assert_eq!(rodeo.resolve(&key), "Strings of things with wings and dings");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve<'a>(&'a self, key: &K) -> &'a str {
+        self.try_resolve(key)
+            .expect("Key out of bounds, did you use a key from another version?")
+    }
+
+    /// Resolves a key into a string, returning `None` if the key is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::PersistentRodeo;
+    ///
+    /// let empty = PersistentRodeo::default();
+    /// let (rodeo, key) = empty.intern("Strings of things with wings and dings");
+    ///
+    /// assert_eq!(rodeo.try_resolve(&key), Some("Strings of things with wings and dings"));
+    /// assert_eq!(empty.try_resolve(&key), None);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve<'a>(&'a self, key: &K) -> Option<&'a str> {
+        self.strings.get(key.into_usize())
+    }
+}
+
+impl<K, S> PersistentRodeo<K, S> {
+    /// Returns the number of strings interned in this version
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.strings.len
+    }
+
+    /// Returns `true` if no strings are interned in this version
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the strings of this version and their keys, in key order
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter(&self) -> PersistentIter<'_, K> {
+        PersistentIter {
+            strings: &self.strings,
+            index: 0,
+            __key: PhantomData,
+        }
+    }
+}
+
+impl<K, S> Clone for PersistentRodeo<K, S> {
+    /// Clones the version, which only bumps the reference counts of its tries
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self {
+            strings: self.strings.clone(),
+            map: self.map.clone(),
+            hasher: Arc::clone(&self.hasher),
+        }
+    }
+}
+
+impl Default for PersistentRodeo<Spur, RandomState> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, S> Debug for PersistentRodeo<K, S>
+where
+    K: Key + Debug,
+    S: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PersistentRodeo")
+            .field("strings", &DebugStrings(self.iter()))
+            .field("hasher", &self.hasher)
+            .finish()
+    }
+}
+
+/// Formats the strings of a [`PersistentRodeo`] as a map from keys to strings
+struct DebugStrings<'a, K>(PersistentIter<'a, K>);
+
+impl<K> Debug for DebugStrings<'_, K>
+where
+    K: Key + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.0.clone()).finish()
+    }
+}
+
+impl<Str, K, S> FromIterator<Str> for PersistentRodeo<K, S>
+where
+    Str: AsRef<str>,
+    K: Key,
+    S: BuildHasher + Default,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = Str>,
+    {
+        iter.into_iter()
+            .fold(Self::with_hasher(S::default()), |rodeo, string| {
+                rodeo.intern(string).0
+            })
+    }
+}
+
+impl<'a, K: Key, S> IntoIterator for &'a PersistentRodeo<K, S> {
+    type Item = (K, &'a str);
+    type IntoIter = PersistentIter<'a, K>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the strings and keys of a [`PersistentRodeo`]
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct PersistentIter<'a, K> {
+    strings: &'a PersistentVec,
+    index: usize,
+    __key: PhantomData<K>,
+}
+
+impl<K> Clone for PersistentIter<'_, K> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self {
+            strings: self.strings,
+            index: self.index,
+            __key: PhantomData,
+        }
+    }
+}
+
+impl<'a, K> Iterator for PersistentIter<'a, K>
+where
+    K: Key,
+{
+    type Item = (K, &'a str);
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let string = self.strings.get(self.index)?;
+        let key = K::try_from_usize(self.index)?;
+        self.index += 1;
+
+        Some((key, string))
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.strings.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, K: Key> ExactSizeIterator for PersistentIter<'a, K> {}
+impl<'a, K: Key> FusedIterator for PersistentIter<'a, K> {}
+
+/// A persistent vector of strings, stored as a trie with [`WIDTH`] children per node
+#[derive(Debug, Clone)]
+struct PersistentVec {
+    root: Option<Arc<VecNode>>,
+    len: usize,
+    /// The number of index bits consumed above the leaves
+    shift: u32,
+}
+
+#[derive(Debug, Clone)]
+enum VecNode {
+    Branch(Vec<Arc<VecNode>>),
+    Leaf(Vec<Arc<str>>),
+}
+
+impl PersistentVec {
+    const fn new() -> Self {
+        Self {
+            root: None,
+            len: 0,
+            shift: 0,
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<&str> {
+        if index >= self.len {
+            return None;
+        }
+
+        let (mut node, mut shift) = (self.root.as_deref()?, self.shift);
+        loop {
+            match node {
+                VecNode::Branch(children) => {
+                    node = children.get((index >> shift) & MASK)?;
+                    shift -= BITS;
+                }
+                VecNode::Leaf(strings) => return strings.get(index & MASK).map(|s| &**s),
+            }
+        }
+    }
+
+    /// Returns a new vector with `string` appended
+    fn push(&self, string: Arc<str>) -> Self {
+        let (root, shift) = match &self.root {
+            None => (VecNode::Leaf(vec![string]), 0),
+
+            // The trie is full, so it grows a level with the old root as its first child
+            Some(root) if self.len >> self.shift >= WIDTH => (
+                VecNode::Branch(vec![
+                    Arc::clone(root),
+                    Arc::new(VecNode::path(self.shift, string)),
+                ]),
+                self.shift + BITS,
+            ),
+
+            Some(root) => (root.push(self.shift, self.len, string), self.shift),
+        };
+
+        Self {
+            root: Some(Arc::new(root)),
+            len: self.len + 1,
+            shift,
+        }
+    }
+}
+
+impl VecNode {
+    /// Creates a chain of nodes from `shift` down to a leaf holding only `string`
+    fn path(shift: u32, string: Arc<str>) -> Self {
+        if shift == 0 {
+            Self::Leaf(vec![string])
+        } else {
+            Self::Branch(vec![Arc::new(Self::path(shift - BITS, string))])
+        }
+    }
+
+    /// Copies the node with `string` stored at `index`, which must be the vector's length
+    fn push(&self, shift: u32, index: usize, string: Arc<str>) -> Self {
+        match self {
+            Self::Leaf(strings) => {
+                let mut strings = strings.clone();
+                strings.push(string);
+
+                Self::Leaf(strings)
+            }
+
+            Self::Branch(children) => {
+                let mut children = children.clone();
+                let child = (index >> shift) & MASK;
+
+                if let Some(existing) = children.get_mut(child) {
+                    *existing = Arc::new(existing.push(shift - BITS, index, string));
+                } else {
+                    children.push(Arc::new(Self::path(shift - BITS, string)));
+                }
+
+                Self::Branch(children)
+            }
+        }
+    }
+}
+
+/// A node of the hash array mapped trie that maps strings to keys
+///
+/// Each branch consumes [`BITS`] bits of a string's hash and only holds the children that
+/// are present, marked by `bitmap`. Strings whose full hashes are equal end up in a
+/// collision node once the hash runs out
+#[derive(Debug, Clone)]
+enum MapNode<K> {
+    Branch {
+        bitmap: u32,
+        children: Vec<MapChild<K>>,
+    },
+    Collision(Vec<MapEntry<K>>),
+}
+
+#[derive(Debug, Clone)]
+enum MapChild<K> {
+    Entry(MapEntry<K>),
+    Node(Arc<MapNode<K>>),
+}
+
+#[derive(Debug, Clone)]
+struct MapEntry<K> {
+    hash: u64,
+    string: Arc<str>,
+    key: K,
+}
+
+impl<K> MapNode<K>
+where
+    K: Copy,
+{
+    /// Gets the bit of a branch at `shift` that `hash` belongs to
+    fn bit(shift: u32, hash: u64) -> u32 {
+        1 << ((hash >> shift) as usize & MASK)
+    }
+
+    /// Creates a branch at `shift` holding only `entry`
+    fn single(shift: u32, entry: MapEntry<K>) -> Self {
+        Self::Branch {
+            bitmap: Self::bit(shift, entry.hash),
+            children: vec![MapChild::Entry(entry)],
+        }
+    }
+
+    /// Creates a node at `shift` holding two entries
+    fn pair(shift: u32, first: MapEntry<K>, second: MapEntry<K>) -> Self {
+        if shift >= u64::BITS {
+            return Self::Collision(vec![first, second]);
+        }
+
+        let (first_bit, second_bit) = (Self::bit(shift, first.hash), Self::bit(shift, second.hash));
+        let children = if first_bit == second_bit {
+            vec![MapChild::Node(Arc::new(Self::pair(
+                shift + BITS,
+                first,
+                second,
+            )))]
+        } else if first_bit < second_bit {
+            vec![MapChild::Entry(first), MapChild::Entry(second)]
+        } else {
+            vec![MapChild::Entry(second), MapChild::Entry(first)]
+        };
+
+        Self::Branch {
+            bitmap: first_bit | second_bit,
+            children,
+        }
+    }
+
+    fn find(&self, shift: u32, hash: u64, string: &str) -> Option<K> {
+        let (mut node, mut shift) = (self, shift);
+        loop {
+            match node {
+                Self::Branch { bitmap, children } => {
+                    let bit = Self::bit(shift, hash);
+                    if bitmap & bit == 0 {
+                        return None;
+                    }
+
+                    match &children[(bitmap & (bit - 1)).count_ones() as usize] {
+                        MapChild::Entry(entry) => {
+                            return (entry.hash == hash && &*entry.string == string)
+                                .then_some(entry.key);
+                        }
+                        MapChild::Node(child) => {
+                            node = child;
+                            shift += BITS;
+                        }
+                    }
+                }
+
+                Self::Collision(entries) => {
+                    return entries
+                        .iter()
+                        .find(|entry| &*entry.string == string)
+                        .map(|entry| entry.key);
+                }
+            }
+        }
+    }
+
+    /// Copies the node with `entry` added to it, the entry's string must not be in the trie
+    fn insert(&self, shift: u32, entry: MapEntry<K>) -> Self {
+        match self {
+            Self::Branch { bitmap, children } => {
+                let bit = Self::bit(shift, entry.hash);
+                let index = (bitmap & (bit - 1)).count_ones() as usize;
+                let mut children = children.clone();
+
+                if bitmap & bit == 0 {
+                    children.insert(index, MapChild::Entry(entry));
+                } else {
+                    let child = match &children[index] {
+                        MapChild::Entry(existing) => {
+                            Self::pair(shift + BITS, existing.clone(), entry)
+                        }
+                        MapChild::Node(node) => node.insert(shift + BITS, entry),
+                    };
+                    children[index] = MapChild::Node(Arc::new(child));
+                }
+
+                Self::Branch {
+                    bitmap: bitmap | bit,
+                    children,
+                }
+            }
+
+            Self::Collision(entries) => {
+                let mut entries = entries.clone();
+                entries.push(entry);
+
+                Self::Collision(entries)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MicroSpur;
+    use alloc::{format, string::String};
+    use core::hash::{BuildHasherDefault, Hasher};
+
+    #[test]
+    fn versions() {
+        let base: PersistentRodeo = PersistentRodeo::new();
+        let (one, a) = base.intern("a");
+        let (two, b) = one.intern("b");
+        let (branch, c) = one.intern("c");
+
+        assert_eq!(b, c);
+        assert_eq!(two.resolve(&b), "b");
+        assert_eq!(branch.resolve(&c), "c");
+        assert_eq!(two.get("c"), None);
+        assert_eq!(branch.get("b"), None);
+        assert_eq!(branch.get("a"), Some(a));
+
+        assert!(base.is_empty());
+        assert_eq!(one.len(), 1);
+        assert!(!one.contains("b"));
+        assert!(!one.contains_key(&b));
+        assert_eq!(one.try_resolve(&b), None);
+
+        let (same, again) = two.intern("a");
+        assert_eq!(again, a);
+        assert_eq!(same.len(), 2);
+    }
+
+    #[test]
+    fn many_strings() {
+        let mut rodeo: PersistentRodeo = PersistentRodeo::new();
+        let mut snapshots = Vec::new();
+        for i in 0..5000 {
+            if i % 1000 == 0 {
+                snapshots.push(rodeo.clone());
+            }
+            rodeo = rodeo.intern(format!("{}", i)).0;
+        }
+
+        assert_eq!(rodeo.len(), 5000);
+        for (i, (key, string)) in rodeo.iter().enumerate() {
+            assert_eq!(key.into_usize(), i);
+            assert_eq!(string, format!("{}", i));
+            assert_eq!(rodeo.get(string), Some(key));
+        }
+
+        for (i, snapshot) in snapshots.iter().enumerate() {
+            assert_eq!(snapshot.len(), i * 1000);
+            assert!(!snapshot.contains(format!("{}", i * 1000)));
+            assert_eq!(snapshot.iter().len(), i * 1000);
+        }
+    }
+
+    #[test]
+    fn hash_collisions() {
+        #[derive(Default)]
+        struct Colliding;
+
+        impl Hasher for Colliding {
+            fn write(&mut self, _: &[u8]) {}
+
+            fn finish(&self) -> u64 {
+                0
+            }
+        }
+
+        let strings: Vec<String> = (0..40).map(|i| format!("{}", i)).collect();
+        let rodeo: PersistentRodeo<Spur, BuildHasherDefault<Colliding>> = strings.iter().collect();
+
+        for (i, string) in strings.iter().enumerate() {
+            assert_eq!(rodeo.get(string), Spur::try_from_usize(i));
+        }
+        assert_eq!(rodeo.get("40"), None);
+    }
+
+    #[test]
+    fn key_space_exhaustion() {
+        let mut rodeo: PersistentRodeo<MicroSpur> = PersistentRodeo::new();
+        for i in 0..u8::MAX {
+            rodeo = rodeo.intern(format!("{}", i)).0;
+        }
+
+        let err = rodeo.try_intern("too many").unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::KeySpaceExhaustion);
+        assert!(rodeo.try_intern("0").is_ok());
+    }
+}
@@ -0,0 +1,180 @@
+//! Per call site interning counts, enabled with the `profile-interning` feature
+
+use crate::hasher::RandomState;
+#[cfg(feature = "multi-threaded")]
+use crate::sync::RwLock;
+use alloc::vec::Vec;
+use core::panic::Location;
+use hashbrown::HashMap;
+
+/// How much a single call site has interned
+///
+/// Interners keep one of these for every location that calls one of their interning methods,
+/// every call is counted whether or not the string was already interned
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::Rodeo;
+///
+/// let mut rodeo = Rodeo::default();
+/// for _ in 0..3 {
+///     rodeo.get_or_intern("hello");
+/// }
+/// rodeo.get_or_intern("world!");
+///
+/// let report = rodeo.profiling_report();
+/// assert_eq!(report.len(), 2);
+///
+/// // The busiest call site comes first
+/// assert_eq!(report[0].interns(), 3);
+/// assert_eq!(report[0].bytes(), 15);
+/// assert_eq!(report[0].location().file(), file!());
+/// assert_eq!(report[1].interns(), 1);
+/// assert_eq!(report[1].bytes(), 6);
+/// ```
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CallSiteProfile {
+    /// The location that called the interner
+    pub(crate) location: &'static Location<'static>,
+    /// The number of strings interned from the location
+    pub(crate) interns: usize,
+    /// The total length of every string interned from the location
+    pub(crate) bytes: usize,
+}
+
+impl CallSiteProfile {
+    pub(crate) const fn new(location: &'static Location<'static>) -> Self {
+        Self {
+            location,
+            interns: 0,
+            bytes: 0,
+        }
+    }
+
+    /// Records a string of `len` bytes being interned
+    #[inline]
+    pub(crate) fn record(&mut self, len: usize) {
+        self.interns = self.interns.saturating_add(1);
+        self.bytes = self.bytes.saturating_add(len);
+    }
+
+    /// Returns the location that called the interner
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Returns the number of strings interned from the call site
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn interns(&self) -> usize {
+        self.interns
+    }
+
+    /// Returns the total length of every string interned from the call site
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+}
+
+/// The call sites that have interned strings into a single-threaded interner
+#[derive(Debug, Clone, Default)]
+pub(crate) struct InternProfile {
+    sites: HashMap<&'static Location<'static>, CallSiteProfile, RandomState>,
+}
+
+impl InternProfile {
+    /// Records `location` interning a string of `len` bytes
+    #[inline]
+    pub(crate) fn record(&mut self, location: &'static Location<'static>, len: usize) {
+        self.sites
+            .entry(location)
+            .or_insert_with(|| CallSiteProfile::new(location))
+            .record(len);
+    }
+
+    pub(crate) fn report(&self) -> Vec<CallSiteProfile> {
+        sorted_report(self.sites.values().copied().collect())
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.sites.clear();
+    }
+}
+
+/// The call sites that have interned strings into a concurrent interner
+#[cfg(feature = "multi-threaded")]
+#[derive(Debug, Default)]
+pub(crate) struct ConcurrentInternProfile {
+    sites: RwLock<HashMap<&'static Location<'static>, CallSiteProfile, RandomState>>,
+}
+
+#[cfg(feature = "multi-threaded")]
+impl ConcurrentInternProfile {
+    /// Records `location` interning a string of `len` bytes
+    #[inline]
+    pub(crate) fn record(&self, location: &'static Location<'static>, len: usize) {
+        self.sites
+            .write()
+            .entry(location)
+            .or_insert_with(|| CallSiteProfile::new(location))
+            .record(len);
+    }
+
+    pub(crate) fn report(&self) -> Vec<CallSiteProfile> {
+        sorted_report(self.sites.read().values().copied().collect())
+    }
+
+    pub(crate) fn clear(&self) {
+        self.sites.write().clear();
+    }
+}
+
+/// Sorts call sites so that the ones that interned the most bytes come first, ties are
+/// broken by the number of interns and then by location so reports are deterministic
+pub(crate) fn sorted_report(mut report: Vec<CallSiteProfile>) -> Vec<CallSiteProfile> {
+    report.sort_unstable_by(|a, b| {
+        b.bytes
+            .cmp(&a.bytes)
+            .then(b.interns.cmp(&a.interns))
+            .then(a.location.cmp(b.location))
+    });
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn sorted() {
+        let (first, second) = (Location::caller(), Location::caller());
+        let mut profile = InternProfile::default();
+
+        profile.record(second, 4);
+        profile.record(first, 2);
+        profile.record(first, 2);
+        profile.record(second, 0);
+
+        let expected = vec![
+            CallSiteProfile {
+                location: first,
+                interns: 2,
+                bytes: 4,
+            },
+            CallSiteProfile {
+                location: second,
+                interns: 2,
+                bytes: 4,
+            },
+        ];
+        assert_eq!(profile.report(), expected);
+
+        profile.clear();
+        assert!(profile.report().is_empty());
+    }
+}
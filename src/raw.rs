@@ -0,0 +1,88 @@
+//! The hashing scheme behind lasso's interners, for building indices that agree with them
+//!
+//! Interners don't store strings in their maps, only keys. Each key is placed at the hash of
+//! the string it stands for, made by handing the string to the interner's [`BuildHasher`]
+//! as a `str`, exactly as [`hash_str`] does. Looking a string up hashes it the same way, then
+//! compares it against the strings of the keys found at that hash.
+//!
+//! Since the hash only depends on the hasher and the string, it can be made once and reused:
+//! `get_hashed()` on [`Rodeo`], [`RodeoReader`] and [`ThreadedRodeo`] takes a hash made with
+//! the interner's [`hasher()`](Rodeo::hasher) instead of hashing the string again, and custom
+//! indices keyed by interned strings can share hashes with the interner that owns them. A
+//! hash made with any other hasher won't find anything.
+//!
+//! This module is only available with the `raw` feature!
+//!
+//! # Example
+//!
+//! ```rust
+//! use lasso::{raw, Rodeo};
+//!
+//! let mut rodeo = Rodeo::default();
+//! let key = rodeo.get_or_intern("Hello, world!");
+//!
+//! let hash = raw::hash_str(rodeo.hasher(), "Hello, world!");
+//! assert_eq!(rodeo.get_hashed(hash, "Hello, world!"), Some(key));
+//! assert_eq!(rodeo.get_hashed(hash, "Goodbye, world!"), None);
+//! ```
+//!
+//! [`BuildHasher`]: core::hash::BuildHasher
+//! [`Rodeo`]: crate::Rodeo
+//! [`RodeoReader`]: crate::RodeoReader
+//! [`ThreadedRodeo`]: crate::ThreadedRodeo
+
+use core::hash::BuildHasher;
+
+/// Hashes a string the same way as the interners built with `hasher` do
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{raw, Rodeo};
+/// use std::hash::BuildHasher;
+///
+/// let rodeo = Rodeo::default();
+/// assert_eq!(
+///     raw::hash_str(rodeo.hasher(), "Hello, world!"),
+///     rodeo.hasher().hash_one("Hello, world!"),
+/// );
+/// ```
+///
+#[cfg_attr(feature = "inline-more", inline)]
+pub fn hash_str<S>(hasher: &S, string: &str) -> u64
+where
+    S: BuildHasher,
+{
+    hasher.hash_one(string)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{raw, Rodeo};
+
+    #[test]
+    fn hashes_agree() {
+        let mut rodeo = Rodeo::default();
+        let keys = ["a", "b", "c"].map(|string| rodeo.get_or_intern(string));
+
+        for (key, string) in keys.iter().zip(["a", "b", "c"]) {
+            let hash = raw::hash_str(rodeo.hasher(), string);
+            assert_eq!(rodeo.get_hashed(hash, string), Some(*key));
+            assert_eq!(rodeo.get_hashed(hash, "d"), None);
+        }
+
+        #[cfg(all(feature = "multi-threaded", not(feature = "no-std")))]
+        {
+            let threaded = crate::ThreadedRodeo::default();
+            let key = threaded.get_or_intern("a");
+            let hash = raw::hash_str(threaded.hasher(), "a");
+            assert_eq!(threaded.get_hashed(hash, "a"), Some(key));
+        }
+
+        let reader = rodeo.into_reader();
+        for (key, string) in keys.iter().zip(["a", "b", "c"]) {
+            let hash = raw::hash_str(reader.hasher(), string);
+            assert_eq!(reader.get_hashed(hash, string), Some(*key));
+        }
+    }
+}
@@ -0,0 +1,710 @@
+use crate::{
+    hasher::RandomState,
+    keys::{Key, Spur},
+    LassoError, LassoErrorKind, LassoResult,
+};
+use alloc::{rc::Rc, string::String, vec::Vec};
+use core::{
+    cell::RefCell,
+    fmt::{self, Debug, Display},
+    hash::{BuildHasher, Hash, Hasher},
+    ops::Deref,
+};
+use hashbrown::HashTable;
+
+/// A reference counted interner that reclaims strings once nothing uses them anymore
+///
+/// Interning a string returns an [`RcKey`], a handle that keeps the string alive. Once the
+/// last handle to a string is dropped the string is removed from the interner and its slot
+/// is put on a free list, the next string that's interned takes over the slot and reuses its
+/// buffer if it's large enough. Memory use is bounded by the number of strings that are alive
+/// at once instead of by every string ever interned, at the cost of some speed and of keys
+/// that can't be trusted once their handles are gone
+///
+/// The interner's storage is shared between it and every handle, so handles can outlive the
+/// `RcRodeo` that created them
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::RcRodeo;
+///
+/// let rodeo = RcRodeo::default();
+///
+/// let tag = rodeo.get_or_intern("rust");
+/// let same = rodeo.get_or_intern("rust");
+/// assert_eq!(tag, same);
+/// assert_eq!(&*tag, "rust");
+/// assert_eq!(tag.ref_count(), 2);
+///
+/// // Dropping every handle reclaims the string
+/// drop((tag, same));
+/// assert!(!rodeo.contains("rust"));
+///
+/// // And its slot is reused by the next string
+/// let other = rodeo.get_or_intern("zig");
+/// assert_eq!(rodeo.len(), 1);
+/// assert_eq!(rodeo.free_slots(), 0);
+/// # drop(other);
+/// ```
+///
+pub struct RcRodeo<K = Spur, S = RandomState> {
+    shared: Rc<RefCell<Shared<K, S>>>,
+}
+
+impl<K> RcRodeo<K, RandomState>
+where
+    K: Key,
+{
+    /// Create an empty `RcRodeo`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{RcRodeo, Spur};
+    ///
+    /// let rodeo: RcRodeo<Spur> = RcRodeo::new();
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, S> RcRodeo<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    /// Create an empty `RcRodeo` which will use the given hasher for its internal hashmap
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{RcRodeo, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: RcRodeo<Spur, RandomState> = RcRodeo::with_hasher(RandomState::new());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            shared: Rc::new(RefCell::new(Shared {
+                map: HashTable::new(),
+                hasher: hash_builder,
+                slots: Vec::new(),
+                free: Vec::new(),
+            })),
+        }
+    }
+
+    /// Get a handle to a string, interning it if it does not yet exist
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// more than `u32::MAX - 1` unique strings were alive at once. Also panics if the memory for
+    /// the string couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::RcRodeo;
+    ///
+    /// let rodeo = RcRodeo::default();
+    ///
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", key.as_str());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_or_intern<T>(&self, val: T) -> RcKey<K, S>
+    where
+        T: AsRef<str>,
+    {
+        self.try_get_or_intern(val)
+            .expect("Failed to get or intern string")
+    }
+
+    /// Get a handle to a string, interning it if it does not yet exist
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::RcRodeo;
+    ///
+    /// let rodeo = RcRodeo::default();
+    ///
+    /// let key = rodeo.try_get_or_intern("Strings of things with wings and dings").unwrap();
+    /// assert_eq!("Strings of things with wings and dings", key.as_str());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_get_or_intern<T>(&self, val: T) -> LassoResult<RcKey<K, S>>
+    where
+        T: AsRef<str>,
+    {
+        let key = self.shared.borrow_mut().get_or_intern(val.as_ref())?;
+
+        Ok(self.handle(key))
+    }
+
+    /// Get a handle to a string if it's currently interned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::RcRodeo;
+    ///
+    /// let rodeo = RcRodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// assert_eq!(Some(key), rodeo.get("Strings of things with wings and dings"));
+    /// assert_eq!(None, rodeo.get("This string isn't interned"));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get<T>(&self, val: T) -> Option<RcKey<K, S>>
+    where
+        T: AsRef<str>,
+    {
+        let key = self.shared.borrow_mut().get(val.as_ref())?;
+
+        Some(self.handle(key))
+    }
+
+    /// Returns `true` if the given string is currently interned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::RcRodeo;
+    ///
+    /// let rodeo = RcRodeo::default();
+    ///
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert!(rodeo.contains("Strings of things with wings and dings"));
+    ///
+    /// drop(key);
+    /// assert!(!rodeo.contains("Strings of things with wings and dings"));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains<T>(&self, val: T) -> bool
+    where
+        T: AsRef<str>,
+    {
+        self.shared.borrow().find(val.as_ref()).is_some()
+    }
+
+    /// Makes a handle to a key that was just referenced by the caller
+    fn handle(&self, key: K) -> RcKey<K, S> {
+        let string: *const str = self.shared.borrow().slots[key.into_usize()].string.as_str();
+
+        RcKey {
+            key,
+            string,
+            shared: Rc::clone(&self.shared),
+        }
+    }
+}
+
+impl<K, S> RcRodeo<K, S> {
+    /// Gets the number of strings that are currently interned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::RcRodeo;
+    ///
+    /// let rodeo = RcRodeo::default();
+    /// let key = rodeo.get_or_intern("Documentation often has little hidden bits in it");
+    /// assert_eq!(rodeo.len(), 1);
+    ///
+    /// drop(key);
+    /// assert_eq!(rodeo.len(), 0);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.shared.borrow().map.len()
+    }
+
+    /// Returns `true` if there are no strings currently interned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::RcRodeo;
+    ///
+    /// let rodeo = RcRodeo::default();
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the number of reclaimed slots that are waiting to be reused by new strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::RcRodeo;
+    ///
+    /// let rodeo = RcRodeo::default();
+    /// drop(rodeo.get_or_intern("reclaimed"));
+    /// assert_eq!(rodeo.free_slots(), 1);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn free_slots(&self) -> usize {
+        self.shared.borrow().free.len()
+    }
+}
+
+impl Default for RcRodeo<Spur, RandomState> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, S> Debug for RcRodeo<K, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let shared = self.shared.borrow();
+
+        f.debug_struct("RcRodeo")
+            .field("strings", &shared.map.len())
+            .field("free_slots", &shared.free.len())
+            .finish()
+    }
+}
+
+/// A handle to a string interned in an [`RcRodeo`], the string is kept alive until the last
+/// handle to it is dropped
+///
+/// Handles compare and hash by key, so handles from different interners must never be
+/// compared with each other
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::RcRodeo;
+///
+/// let rodeo = RcRodeo::default();
+/// let hello = rodeo.get_or_intern("Hello");
+///
+/// let clone = hello.clone();
+/// assert_eq!(hello.ref_count(), 2);
+/// assert_eq!(format!("{}, world!", clone), "Hello, world!");
+/// ```
+///
+pub struct RcKey<K = Spur, S = RandomState>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    key: K,
+    /// The handle's string, which can't be moved or changed while the handle exists
+    string: *const str,
+    shared: Rc<RefCell<Shared<K, S>>>,
+}
+
+impl<K, S> RcKey<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    /// Get the handle's key
+    ///
+    /// The key is only meaningful while a handle to it exists, once the string is reclaimed
+    /// its key is given to another string
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn key(&self) -> K {
+        self.key
+    }
+
+    /// Get the handle's string
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn as_str(&self) -> &str {
+        // Safety: A slot's string is only changed once every handle to it has been dropped,
+        // and this handle keeps the storage of every slot alive
+        unsafe { &*self.string }
+    }
+
+    /// Gets the number of handles to the string, including this one
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::RcRodeo;
+    ///
+    /// let rodeo = RcRodeo::default();
+    /// let key = rodeo.get_or_intern("counted");
+    /// assert_eq!(key.ref_count(), 1);
+    ///
+    /// let other = rodeo.get("counted").unwrap();
+    /// assert_eq!(key.ref_count(), 2);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn ref_count(&self) -> usize {
+        self.shared.borrow().slots[self.key.into_usize()].refs
+    }
+}
+
+impl<K, S> Clone for RcKey<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        self.shared.borrow_mut().slots[self.key.into_usize()].acquire();
+
+        Self {
+            key: self.key,
+            string: self.string,
+            shared: Rc::clone(&self.shared),
+        }
+    }
+}
+
+impl<K, S> Drop for RcKey<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn drop(&mut self) {
+        self.shared.borrow_mut().release(self.key);
+    }
+}
+
+impl<K, S> Deref for RcKey<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    type Target = str;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<K, S> AsRef<str> for RcKey<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<K, S> PartialEq for RcKey<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K, S> Eq for RcKey<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+}
+
+impl<K, S> PartialEq<str> for RcKey<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<K, S> Hash for RcKey<K, S>
+where
+    K: Key + Hash,
+    S: BuildHasher,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
+
+impl<K, S> Display for RcKey<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<K, S> Debug for RcKey<K, S>
+where
+    K: Key + Debug,
+    S: BuildHasher,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RcKey")
+            .field("key", &self.key)
+            .field("string", &self.as_str())
+            .finish()
+    }
+}
+
+/// The storage shared by an [`RcRodeo`] and all of its handles
+struct Shared<K, S> {
+    /// Map that allows `str` -> `key` resolution for the live strings
+    map: HashTable<K>,
+    hasher: S,
+    /// Every slot, indexed by key
+    slots: Vec<Slot>,
+    /// The keys of the slots that were reclaimed and can be reused
+    free: Vec<K>,
+}
+
+impl<K, S> Shared<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    /// Finds the key of a live string
+    fn find(&self, string: &str) -> Option<K> {
+        let hash = self.hasher.hash_one(string);
+
+        self.map
+            .find(hash, |key| self.slots[key.into_usize()].string == string)
+            .copied()
+    }
+
+    /// Finds the key of a live string and takes a reference to it
+    fn get(&mut self, string: &str) -> Option<K> {
+        let key = self.find(string)?;
+        self.slots[key.into_usize()].acquire();
+
+        Some(key)
+    }
+
+    /// Takes a reference to a string, interning it if it isn't live
+    fn get_or_intern(&mut self, string: &str) -> LassoResult<K> {
+        let Self {
+            map,
+            hasher,
+            slots,
+            free,
+        } = self;
+
+        let hash = hasher.hash_one(string);
+        let existing = map.find(hash, |key| slots[key.into_usize()].string == string);
+
+        let key = match existing {
+            Some(&key) => {
+                slots[key.into_usize()].acquire();
+
+                key
+            }
+
+            None => {
+                let len = string.len();
+
+                // Reclaimed slots are reused before new ones are made
+                let key = if let Some(key) = free.pop() {
+                    let slot = &mut slots[key.into_usize()];
+                    slot.string.clear();
+                    if let Err(err) = slot.fill(string) {
+                        free.push(key);
+                        return Err(err);
+                    }
+
+                    key
+                } else {
+                    let key = K::try_from_usize(slots.len()).ok_or_else(|| {
+                        LassoError::new(LassoErrorKind::KeySpaceExhaustion).with_string_len(len)
+                    })?;
+
+                    let mut slot = Slot {
+                        string: String::new(),
+                        refs: 0,
+                    };
+                    slot.fill(string)?;
+                    slots
+                        .try_reserve(1)
+                        .map_err(|_| LassoError::new(LassoErrorKind::FailedAllocation))?;
+                    slots.push(slot);
+
+                    key
+                };
+
+                map.insert_unique(hash, key, |key| {
+                    hasher.hash_one(slots[key.into_usize()].string.as_str())
+                });
+
+                key
+            }
+        };
+
+        Ok(key)
+    }
+
+    /// Drops a reference to a string, reclaiming it if it was the last one
+    fn release(&mut self, key: K) {
+        let slot = &mut self.slots[key.into_usize()];
+        slot.refs -= 1;
+        if slot.refs != 0 {
+            return;
+        }
+
+        let hash = self.hasher.hash_one(slot.string.as_str());
+        if let Ok(entry) = self.map.find_entry(hash, |&live| live == key) {
+            entry.remove();
+        } else {
+            unreachable!("a live string was missing from the map");
+        }
+
+        // The slot keeps its buffer so that the next string can reuse it
+        self.free.push(key);
+    }
+}
+
+/// A string and the number of handles to it, slots without any handles are free
+struct Slot {
+    string: String,
+    refs: usize,
+}
+
+impl Slot {
+    /// Takes another reference to the slot's string
+    fn acquire(&mut self) {
+        self.refs = self
+            .refs
+            .checked_add(1)
+            .expect("too many handles to an interned string");
+    }
+
+    /// Copies a string into the empty slot and takes the first reference to it
+    fn fill(&mut self, string: &str) -> LassoResult<()> {
+        self.string
+            .try_reserve(string.len())
+            .map_err(|_| LassoError::failed_allocation(string.len()))?;
+        self.string.push_str(string);
+        self.refs = 1;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MicroSpur, RcRodeo};
+    #[cfg(feature = "no-std")]
+    use alloc::{string::ToString, vec::Vec};
+
+    #[test]
+    fn reclaims_strings() {
+        let rodeo = RcRodeo::default();
+        let a = rodeo.get_or_intern("a");
+        let b = rodeo.get_or_intern("b");
+        let a2 = rodeo.get("a").unwrap();
+
+        assert_eq!(a, a2);
+        assert_ne!(a, b);
+        assert_eq!(a.ref_count(), 2);
+        assert_eq!(rodeo.len(), 2);
+
+        drop(a);
+        assert!(rodeo.contains("a"));
+        drop(a2);
+        assert!(!rodeo.contains("a"));
+        assert!(rodeo.get("a").is_none());
+        assert_eq!(rodeo.len(), 1);
+        assert_eq!(rodeo.free_slots(), 1);
+        assert_eq!(&*b, "b");
+    }
+
+    #[test]
+    fn reuses_slots() {
+        let rodeo = RcRodeo::default();
+        let first = rodeo.get_or_intern("a much longer string than the next");
+        let key = first.key();
+        drop(first);
+
+        let second = rodeo.get_or_intern("short");
+        assert_eq!(second.key(), key);
+        assert_eq!(second.as_str(), "short");
+        assert_eq!(rodeo.free_slots(), 0);
+
+        // Longer strings grow the reused buffer
+        drop(second);
+        let third = rodeo.get_or_intern("a string that's even longer than the very first one");
+        assert_eq!(third.key(), key);
+        assert_eq!(
+            third.as_str(),
+            "a string that's even longer than the very first one"
+        );
+    }
+
+    #[test]
+    fn clones_and_outlives_rodeo() {
+        let rodeo = RcRodeo::default();
+        let a = rodeo.get_or_intern("a");
+        let clone = a.clone();
+        assert_eq!(clone.ref_count(), 2);
+
+        drop(rodeo);
+        assert_eq!(clone.as_str(), "a");
+        drop(a);
+        assert_eq!(clone.ref_count(), 1);
+    }
+
+    #[test]
+    fn bounded_by_live_strings() {
+        let rodeo: RcRodeo<MicroSpur> = RcRodeo::new();
+
+        // Far more unique strings than the key can address, but never many at once
+        let mut live = Vec::new();
+        for i in 0..1000_usize {
+            live.push(rodeo.get_or_intern(i.to_string()));
+            if live.len() == 10 {
+                live.clear();
+            }
+        }
+
+        assert!(rodeo.shared.borrow().slots.len() <= 10);
+    }
+
+    #[test]
+    fn key_space_exhaustion() {
+        let rodeo: RcRodeo<MicroSpur> = RcRodeo::new();
+        let live: Vec<_> = (0..=u8::MAX as usize)
+            .map(|i| rodeo.try_get_or_intern(i.to_string()))
+            .collect();
+
+        assert!(live[..live.len() - 1].iter().all(Result::is_ok));
+        assert!(live[live.len() - 1]
+            .as_ref()
+            .unwrap_err()
+            .kind()
+            .is_keyspace_exhaustion());
+    }
+}
@@ -2,13 +2,29 @@ use crate::{
     arenas::AnyArena,
     hasher::RandomState,
     keys::{Key, Spur},
-    resolver::RodeoResolver,
-    util::{Iter, Strings},
-    Rodeo,
+    resolver::{self, RodeoResolver},
+    rodeo::{find_string, insert_string, StringMap},
+    util::{self, Iter, MemoryStats, Strings},
+    Capacity, KeySet, LassoResult, Reader, Rodeo,
 };
-use alloc::vec::Vec;
-use core::{hash::BuildHasher, ops::Index};
-use hashbrown::HashMap;
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "compact_str")]
+use compact_str::CompactString;
+use core::{
+    cell::UnsafeCell,
+    fmt::{self, Debug},
+    hash::BuildHasher,
+    hint,
+    mem::{self, size_of},
+    ops::Index,
+    ptr,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+#[cfg(feature = "multi-threaded")]
+use crate::ThreadedRodeo;
+#[cfg(feature = "multi-threaded")]
+use core::hash::Hash;
 
 /// A read-only view of a [`Rodeo`] or [`ThreadedRodeo`] that allows contention-free access to interned strings,
 /// both key to string resolution and string to key lookups
@@ -18,13 +34,15 @@ use hashbrown::HashMap;
 ///
 /// [`Rodeo`]: crate::Rodeo
 /// [`ThreadedRodeo`]: crate::ThreadedRodeo
-#[derive(Debug)]
 pub struct RodeoReader<K = Spur, S = RandomState> {
     // The logic behind this arrangement is more heavily documented inside of
     // `Rodeo` itself
-    map: HashMap<K, (), ()>,
+    map: LazyMap<K>,
     hasher: S,
     pub(crate) strings: Vec<&'static str>,
+    /// Keys below `strings.len()` that were handed out without ever being given a string,
+    /// their slots in `strings` are empty strings that must never be resolved
+    gaps: KeySet<K>,
     __arena: AnyArena,
 }
 
@@ -37,19 +55,138 @@ impl<K, S> RodeoReader<K, S> {
     /// that no other references to those strings exist
     ///
     pub(crate) unsafe fn new(
-        map: HashMap<K, (), ()>,
+        map: StringMap<K>,
         hasher: S,
         strings: Vec<&'static str>,
+        gaps: KeySet<K>,
         arena: AnyArena,
     ) -> Self {
         Self {
-            map,
+            map: LazyMap::built(map),
             hasher,
             strings,
+            gaps,
             __arena: arena,
         }
     }
 
+    /// Creates a `RodeoReader` directly from a vector of strings using the given hasher,
+    /// where each string is given the key of its index
+    ///
+    /// All of the strings are copied into a single arena bucket that's exactly the size
+    /// of their combined length and the lookup map is built from them directly, without
+    /// an intermediate [`Rodeo`]. The strings are expected to be unique, duplicates each
+    /// get their own key but looking them up returns the first one's
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{RodeoReader, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let strings = vec![String::from("foo"), String::from("bar")];
+    /// let reader: RodeoReader<Spur, RandomState> =
+    ///     RodeoReader::from_strings(strings, RandomState::new()).unwrap();
+    ///
+    /// let bar = reader.get("bar").unwrap();
+    /// assert_eq!(reader.resolve(&bar), "bar");
+    /// assert_eq!(reader.get("baz"), None);
+    /// ```
+    ///
+    /// [`Rodeo`]: crate::Rodeo
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn from_strings(strings: Vec<String>, hasher: S) -> LassoResult<Self>
+    where
+        K: Key,
+        S: BuildHasher,
+    {
+        let (strings, arena) = resolver::store_exact::<K>(&strings)?;
+        let map = build_map(&strings, &hasher);
+
+        // Safety: The strings were all just allocated in the arena
+        Ok(unsafe { Self::new(map, hasher, strings, KeySet::new(), AnyArena::Arena(arena)) })
+    }
+
+    /// Creates a `RodeoReader` from an iterator of sorted and unique strings using the given
+    /// hasher, where each string is given the key of its position
+    ///
+    /// The strings are copied into the arena as they're streamed in without being hashed,
+    /// the index used to look strings up is only built the first time it's needed. Readers
+    /// that are only used to resolve keys never pay for it
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the strings aren't sorted and unique
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{RodeoReader, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let reader: RodeoReader<Spur, RandomState> =
+    ///     RodeoReader::from_sorted_unique(["apple", "banana"], RandomState::new()).unwrap();
+    ///
+    /// // The first lookup builds the index
+    /// let banana = reader.get("banana").unwrap();
+    /// assert_eq!(reader.resolve(&banana), "banana");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn from_sorted_unique<I>(strings: I, hasher: S) -> LassoResult<Self>
+    where
+        K: Key,
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let (strings, arena) = resolver::store_sorted_unique::<K, I>(strings)?;
+
+        Ok(Self {
+            map: LazyMap::unbuilt(),
+            hasher,
+            strings,
+            gaps: KeySet::new(),
+            __arena: AnyArena::Arena(arena),
+        })
+    }
+
+    /// Creates a `RodeoReader` from `(key, string)` pairs that were already given their keys
+    /// using the given hasher
+    ///
+    /// The pairs may come in any order, but their keys must cover every index from zero up to
+    /// the number of pairs exactly once and no string may appear twice, see
+    /// [`Rodeo::extend_with_keys`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, RodeoReader, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let key = |index| Spur::try_from_usize(index).unwrap();
+    /// let reader: RodeoReader<Spur, RandomState> =
+    ///     RodeoReader::from_keyed([(key(1), "bar"), (key(0), "foo")], RandomState::new())
+    ///         .unwrap();
+    ///
+    /// assert_eq!(reader.get("foo"), Some(key(0)));
+    /// assert_eq!(reader.resolve(&key(1)), "bar");
+    /// ```
+    ///
+    /// [`Rodeo::extend_with_keys`]: crate::Rodeo::extend_with_keys
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn from_keyed<I, T>(pairs: I, hasher: S) -> LassoResult<Self>
+    where
+        K: Key,
+        S: BuildHasher,
+        I: IntoIterator<Item = (K, T)>,
+        T: AsRef<str>,
+    {
+        let mut rodeo = Rodeo::try_with_capacity_and_hasher(Capacity::minimal(), hasher)?;
+        rodeo.extend_with_keys(pairs)?;
+
+        Ok(rodeo.into_reader())
+    }
+
     /// Get the key value of a string, returning `None` if it doesn't exist
     ///
     /// # Example
@@ -79,16 +216,86 @@ impl<K, S> RodeoReader<K, S> {
         // Make a hash of the requested string
         let hash = self.hasher.hash_one(string_slice);
 
-        // Get the map's entry that the string should occupy
-        let entry = self.map.raw_entry().from_hash(hash, |key| {
-            // Safety: The index given by `key` will be in bounds of the strings vector
-            let key_string: &str = unsafe { index_unchecked!(self.strings, key.into_usize()) };
+        // Look for the string within the map
+        let map = self
+            .map
+            .get_or_build(|| build_map(&self.strings, &self.hasher));
+
+        find_string(map, &self.strings, hash, string_slice)
+    }
+
+    /// Get the key for a string using a hash made with [`RodeoReader::hasher`], returning
+    /// `None` if it hasn't been interned
+    ///
+    /// See the [`raw`] module for how strings are hashed, a hash that wasn't made that way
+    /// won't find anything
+    ///
+    /// This method is only available with the `raw` feature!
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{raw, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// let reader = rodeo.into_reader();
+    ///
+    /// let hash = raw::hash_str(reader.hasher(), "Strings of things with wings and dings");
+    /// assert_eq!(Some(key), reader.get_hashed(hash, "Strings of things with wings and dings"));
+    /// ```
+    ///
+    /// [`raw`]: crate::raw
+    #[cfg(feature = "raw")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_hashed(&self, hash: u64, string: &str) -> Option<K>
+    where
+        S: BuildHasher,
+        K: Key,
+    {
+        let map = self
+            .map
+            .get_or_build(|| build_map(&self.strings, &self.hasher));
+
+        find_string(map, &self.strings, hash, string)
+    }
 
-            // Compare the requested string against the key's string
-            string_slice == key_string
-        });
+    /// Gets the hasher that the `RodeoReader` hashes strings with
+    ///
+    /// This method is only available with the `raw` feature!
+    #[cfg(feature = "raw")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn hasher(&self) -> &S {
+        &self.hasher
+    }
 
-        entry.map(|(key, ())| *key)
+    /// Get the key of the empty string, returning `None` if it hasn't been interned
+    ///
+    /// Interners built with [`RodeoBuilder::intern_empty()`] always give the empty
+    /// string the first key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// assert_eq!(None, rodeo.empty_key());
+    ///
+    /// let key = rodeo.get_or_intern("");
+    ///
+    /// let rodeo = rodeo.into_reader();
+    /// assert_eq!(Some(key), rodeo.empty_key());
+    /// ```
+    ///
+    /// [`RodeoBuilder::intern_empty()`]: crate::RodeoBuilder::intern_empty
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn empty_key(&self) -> Option<K>
+    where
+        S: BuildHasher,
+        K: Key,
+    {
+        self.get("")
     }
 
     /// Returns `true` if the given string has been interned
@@ -118,6 +325,84 @@ impl<K, S> RodeoReader<K, S> {
         self.get(val).is_some()
     }
 
+    /// Gets the keys of a batch of strings, in the same order as the strings, with `None`
+    /// for every string that hasn't been interned
+    ///
+    /// Every string is hashed before any of them are looked up, so the hasher runs as one
+    /// tight loop and the lookups that follow don't wait on each other's hashes
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let id = rodeo.get_or_intern("id");
+    /// let name = rodeo.get_or_intern("name");
+    ///
+    /// let rodeo = rodeo.into_reader();
+    /// assert_eq!(
+    ///     rodeo.get_many(["name", "email", "id"]),
+    ///     [Some(name), None, Some(id)],
+    /// );
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_many<'a, I>(&self, vals: I) -> Vec<Option<K>>
+    where
+        I: IntoIterator<Item = &'a str>,
+        S: BuildHasher,
+        K: Key,
+    {
+        let hashed: Vec<(u64, &str)> = vals
+            .into_iter()
+            .map(|string| (self.hasher.hash_one(string), string))
+            .collect();
+
+        let map = self
+            .map
+            .get_or_build(|| build_map(&self.strings, &self.hasher));
+
+        hashed
+            .into_iter()
+            .map(|(hash, string)| find_string(map, &self.strings, hash, string))
+            .collect()
+    }
+
+    /// Returns `true` if every string of a batch has been interned, stopping at the first
+    /// one that hasn't
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo.get_or_intern("id");
+    /// rodeo.get_or_intern("name");
+    ///
+    /// let rodeo = rodeo.into_reader();
+    /// assert!(rodeo.contains_all(["name", "id"]));
+    /// assert!(!rodeo.contains_all(["name", "email", "id"]));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains_all<'a, I>(&self, vals: I) -> bool
+    where
+        I: IntoIterator<Item = &'a str>,
+        S: BuildHasher,
+        K: Key,
+    {
+        let map = self
+            .map
+            .get_or_build(|| build_map(&self.strings, &self.hasher));
+
+        vals.into_iter().all(|string| {
+            let hash = self.hasher.hash_one(string);
+            find_string(map, &self.strings, hash, string).is_some()
+        })
+    }
+
     /// Returns `true` if the given key exists in the current interner
     ///
     /// # Example
@@ -140,7 +425,7 @@ impl<K, S> RodeoReader<K, S> {
     where
         K: Key,
     {
-        key.into_usize() < self.strings.len()
+        key.into_usize() < self.strings.len() && !self.gaps.contains(*key)
     }
 
     /// Resolves a string by its key. Only keys made by the current Resolver or the creator
@@ -174,7 +459,7 @@ impl<K, S> RodeoReader<K, S> {
         // and symmetric, then it will succeed, as the usize used to create it is a valid
         // index into self.strings
         unsafe {
-            assert!(key.into_usize() < self.strings.len());
+            assert!(self.contains_key(key));
             self.strings.get_unchecked(key.into_usize())
         }
     }
@@ -187,64 +472,379 @@ impl<K, S> RodeoReader<K, S> {
     /// ```rust
     /// use lasso::Rodeo;
     ///
-    /// // ThreadedRodeo is interchangeable for Rodeo here
+    /// // ThreadedRodeo is interchangeable for Rodeo here
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// let rodeo = rodeo.into_resolver();
+    /// assert_eq!(Some("Strings of things with wings and dings"), rodeo.try_resolve(&key));
+    /// ```
+    ///
+    /// [`Key`]: crate::Key
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve<'a>(&'a self, key: &K) -> Option<&'a str>
+    where
+        K: Key,
+    {
+        // Safety: The call to get_unchecked's safety relies on the Key::into_usize impl
+        // being symmetric and the caller having not fabricated a key. If the impl is sound
+        // and symmetric, then it will succeed, as the usize used to create it is a valid
+        // index into self.strings
+        unsafe {
+            if self.contains_key(key) {
+                Some(self.strings.get_unchecked(key.into_usize()))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Resolves a string by its key into a [`CompactString`], which stores short strings
+    /// inline instead of allocating
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things");
+    /// let rodeo = rodeo.into_reader();
+    ///
+    /// let string = rodeo.resolve_compact(&key);
+    /// assert_eq!(string, "Strings of things");
+    /// assert!(!string.is_heap_allocated());
+    /// ```
+    ///
+    /// [`CompactString`]: compact_str::CompactString
+    #[cfg(feature = "compact_str")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve_compact(&self, key: &K) -> CompactString
+    where
+        K: Key,
+    {
+        CompactString::new(self.resolve(key))
+    }
+
+    /// Resolves a string by its key into a [`CompactString`], returning `None` if it's out
+    /// of bounds
+    ///
+    /// [`CompactString`]: compact_str::CompactString
+    #[cfg(feature = "compact_str")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve_compact(&self, key: &K) -> Option<CompactString>
+    where
+        K: Key,
+    {
+        self.try_resolve(key).map(CompactString::new)
+    }
+
+    /// Resolves a string by its key without bounds checks
+    ///
+    /// # Safety
+    ///
+    /// The key must be valid for the current Reader
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// // ThreadedRodeo is interchangeable for Rodeo here
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// let rodeo = rodeo.into_resolver();
+    /// unsafe {
+    ///     assert_eq!("Strings of things with wings and dings", rodeo.resolve_unchecked(&key));
+    /// }
+    /// ```
+    ///
+    /// [`Key`]: crate::Key
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub unsafe fn resolve_unchecked<'a>(&'a self, key: &K) -> &'a str
+    where
+        K: Key,
+    {
+        unsafe { self.strings.get_unchecked(key.into_usize()) }
+    }
+
+    /// Resolves a string by its key into a pointer to its first byte and its length in bytes,
+    /// returning a null pointer and a length of zero if the key is out of bounds
+    ///
+    /// The string's storage never moves, so the pointer stays valid until the reader is
+    /// dropped, even after it's turned into a [`RodeoResolver`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// let reader = rodeo.into_reader();
+    /// let (ptr, len) = reader.resolve_ptr(&key);
+    ///
+    /// let bytes = unsafe { std::slice::from_raw_parts(ptr, len) };
+    /// assert_eq!(bytes, b"Hello, world!");
+    /// ```
+    ///
+    /// [`RodeoResolver`]: crate::RodeoResolver
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve_ptr(&self, key: &K) -> (*const u8, usize)
+    where
+        K: Key,
+    {
+        self.try_resolve(key)
+            .map_or((ptr::null(), 0), |string| (string.as_ptr(), string.len()))
+    }
+
+    /// Resolves a batch of keys into their strings, in the same order as the keys
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the keys are out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let a = rodeo.get_or_intern("Foo");
+    /// let b = rodeo.get_or_intern("Bar");
+    ///
+    /// let rodeo = rodeo.into_reader();
+    ///
+    /// assert_eq!(rodeo.resolve_many(&[b, a, b]), ["Bar", "Foo", "Bar"]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve_many<'a>(&'a self, keys: &[K]) -> Vec<&'a str>
+    where
+        K: Key,
+    {
+        keys.iter().map(|key| self.resolve(key)).collect()
+    }
+
+    /// Resolves a batch of keys into their strings, in the same order as the keys, returning
+    /// `None` if any of the keys are out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let a = rodeo.get_or_intern("Foo");
+    /// let b = rodeo.get_or_intern("Bar");
+    ///
+    /// let rodeo = rodeo.into_reader();
+    ///
+    /// assert_eq!(rodeo.try_resolve_many(&[b, a]), Some(vec!["Bar", "Foo"]));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve_many<'a>(&'a self, keys: &[K]) -> Option<Vec<&'a str>>
+    where
+        K: Key,
+    {
+        keys.iter().map(|key| self.try_resolve(key)).collect()
+    }
+
+    /// Gets the number of interned strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// // ThreadedRodeo is interchangeable for Rodeo here
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo.get_or_intern("Documentation often has little hidden bits in it");
+    ///
+    /// let rodeo = rodeo.into_reader();
+    /// assert_eq!(rodeo.len(), 1);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if there are no currently interned strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// // ThreadedRodeo is interchangeable for Rodeo here
+    /// let rodeo = Rodeo::default();
+    ///
+    /// let rodeo = rodeo.into_reader();
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the `RodeoReader`'s currently allocated memory
+    ///
+    /// This includes the estimated size of the reader's map and strings vec, see
+    /// [`RodeoReader::arena_memory_usage`] for the memory used only by interned strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo.get_or_intern("Documentation often has little hidden bits in it");
+    ///
+    /// let rodeo = rodeo.into_reader();
+    /// assert!(rodeo.current_memory_usage() > rodeo.arena_memory_usage());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn current_memory_usage(&self) -> usize {
+        self.arena_memory_usage()
+            .saturating_add(self.overhead_memory_usage())
+    }
+
+    /// Get the memory allocated to hold the `RodeoReader`'s interned strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn arena_memory_usage(&self) -> usize {
+        self.__arena.memory_usage()
+    }
+
+    /// Estimates the memory used by the reader's map and strings vec
+    fn overhead_memory_usage(&self) -> usize {
+        // Every map entry also has a one byte control tag
+        self.map
+            .get()
+            .map_or(0, StringMap::capacity)
+            .saturating_mul(size_of::<K>() + 1)
+            .saturating_add(
+                self.strings
+                    .capacity()
+                    .saturating_mul(size_of::<&'static str>()),
+            )
+    }
+
+    /// Get a breakdown of the `RodeoReader`'s memory usage
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn memory_stats(&self) -> MemoryStats {
+        MemoryStats::new(
+            self.arena_memory_usage(),
+            self.overhead_memory_usage(),
+            self.strings.iter().copied(),
+        )
+    }
+
+    /// Returns an iterator over the interned strings and their key values
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter(&self) -> Iter<'_, K> {
+        Iter::from_reader(self)
+    }
+
+    /// Returns an iterator over the interned strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn strings(&self) -> Strings<'_, K> {
+        Strings::from_reader(self)
+    }
+
+    /// Counts the interned strings by length, `bucket_bounds` must be sorted and give the
+    /// inclusive upper bound of each bucket
+    ///
+    /// The returned histogram has one more bucket than there are bounds, holding every
+    /// string longer than the final bound
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo.get_or_intern("a");
+    /// rodeo.get_or_intern("bcd");
+    /// rodeo.get_or_intern("efghijklmn");
+    /// let rodeo = rodeo.into_reader();
+    ///
+    /// assert_eq!(rodeo.length_histogram(&[1, 4]), vec![1, 1, 1]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn length_histogram(&self, bucket_bounds: &[usize]) -> Vec<usize> {
+        util::length_histogram(&self.strings, bucket_bounds)
+    }
+
+    /// Returns the `n` longest interned strings and their keys, longest first with
+    /// ties going to the lowest key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
     /// let mut rodeo = Rodeo::default();
-    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// rodeo.get_or_intern("a");
+    /// let long = rodeo.get_or_intern("efghijklmn");
+    /// let medium = rodeo.get_or_intern("bcd");
+    /// let rodeo = rodeo.into_reader();
     ///
-    /// let rodeo = rodeo.into_resolver();
-    /// assert_eq!(Some("Strings of things with wings and dings"), rodeo.try_resolve(&key));
+    /// assert_eq!(rodeo.top_longest(2), vec![(long, "efghijklmn"), (medium, "bcd")]);
     /// ```
     ///
-    /// [`Key`]: crate::Key
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn try_resolve<'a>(&'a self, key: &K) -> Option<&'a str>
+    pub fn top_longest(&self, n: usize) -> Vec<(K, &str)>
     where
         K: Key,
     {
-        // Safety: The call to get_unchecked's safety relies on the Key::into_usize impl
-        // being symmetric and the caller having not fabricated a key. If the impl is sound
-        // and symmetric, then it will succeed, as the usize used to create it is a valid
-        // index into self.strings
-        unsafe {
-            if key.into_usize() < self.strings.len() {
-                Some(self.strings.get_unchecked(key.into_usize()))
-            } else {
-                None
-            }
-        }
+        util::top_longest(&self.strings, n)
     }
 
-    /// Resolves a string by its key without bounds checks
-    ///
-    /// # Safety
+    /// Returns `true` if both interners hold exactly the same strings, no matter which
+    /// keys those strings were given
     ///
-    /// The key must be valid for the current Reader
+    /// Unlike `==`, which requires every key to resolve to the same string, this only
+    /// compares the sets of interned strings
     ///
     /// # Example
     ///
     /// ```rust
     /// use lasso::Rodeo;
     ///
-    /// // ThreadedRodeo is interchangeable for Rodeo here
-    /// let mut rodeo = Rodeo::default();
-    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// let mut left = Rodeo::default();
+    /// left.get_or_intern("foo");
+    /// left.get_or_intern("bar");
+    /// let left = left.into_reader();
     ///
-    /// let rodeo = rodeo.into_resolver();
-    /// unsafe {
-    ///     assert_eq!("Strings of things with wings and dings", rodeo.resolve_unchecked(&key));
-    /// }
+    /// let mut right = Rodeo::default();
+    /// right.get_or_intern("bar");
+    /// right.get_or_intern("foo");
+    ///
+    /// assert!(left != right);
+    /// assert!(left.equivalent_content(&right));
     /// ```
     ///
-    /// [`Key`]: crate::Key
     #[cfg_attr(feature = "inline-more", inline)]
-    pub unsafe fn resolve_unchecked<'a>(&'a self, key: &K) -> &'a str
+    pub fn equivalent_content<K2, R>(&self, other: &R) -> bool
     where
-        K: Key,
+        R: Reader<K2> + ?Sized,
     {
-        unsafe { self.strings.get_unchecked(key.into_usize()) }
+        self.len() == other.len() && self.strings.iter().all(|string| other.contains(string))
     }
 
-    /// Gets the number of interned strings
+    /// Consumes the current rodeo and makes it into a [`RodeoResolver`], allowing
+    /// contention-free access from multiple threads with the lowest possible memory consumption
     ///
     /// # Example
     ///
@@ -253,84 +853,329 @@ impl<K, S> RodeoReader<K, S> {
     ///
     /// // ThreadedRodeo is interchangeable for Rodeo here
     /// let mut rodeo = Rodeo::default();
-    /// rodeo.get_or_intern("Documentation often has little hidden bits in it");
+    /// let key = rodeo.get_or_intern("Appear weak when you are strong, and strong when you are weak.");
+    /// let reader_rodeo = rodeo.into_reader();
     ///
-    /// let rodeo = rodeo.into_reader();
-    /// assert_eq!(rodeo.len(), 1);
+    /// let resolver_rodeo = reader_rodeo.into_resolver();
+    /// assert_eq!(
+    ///     "Appear weak when you are strong, and strong when you are weak.",
+    ///     resolver_rodeo.resolve(&key),
+    /// );
     /// ```
     ///
+    /// [`RodeoResolver`]: crate::RodeoResolver
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn len(&self) -> usize {
-        self.strings.len()
+    #[must_use]
+    pub fn into_resolver(self) -> RodeoResolver<K> {
+        let RodeoReader {
+            strings,
+            gaps,
+            __arena,
+            ..
+        } = self;
+
+        // Safety: The current reader no longer contains references to the strings
+        // in the vec given to RodeoResolver
+        unsafe { RodeoResolver::new(strings, gaps, __arena) }
     }
 
-    /// Returns `true` if there are no currently interned strings
+    /// Consumes the current RodeoReader, returning a [`RodeoResolver`] whose strings were all
+    /// copied into a single arena bucket that fits them exactly
+    ///
+    /// See [`Rodeo::into_resolver_compact`] for details
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new arena could not be allocated
     ///
     /// # Example
     ///
     /// ```rust
     /// use lasso::Rodeo;
     ///
-    /// // ThreadedRodeo is interchangeable for Rodeo here
-    /// let rodeo = Rodeo::default();
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Appear weak when you are strong, and strong when you are weak.");
+    /// let reader = rodeo.into_reader();
     ///
-    /// let rodeo = rodeo.into_reader();
-    /// assert!(rodeo.is_empty());
+    /// let resolver = reader.into_resolver_compact();
+    /// assert_eq!(
+    ///     "Appear weak when you are strong, and strong when you are weak.",
+    ///     resolver.resolve(&key),
+    /// );
     /// ```
     ///
+    /// [`RodeoResolver`]: crate::RodeoResolver
+    /// [`Rodeo::into_resolver_compact`]: crate::Rodeo::into_resolver_compact
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+    #[must_use]
+    pub fn into_resolver_compact(self) -> RodeoResolver<K>
+    where
+        K: Key,
+    {
+        let RodeoReader { strings, gaps, .. } = self;
+        RodeoResolver::compacted(&strings, gaps)
     }
 
-    /// Returns an iterator over the interned strings and their key values
+    /// Converts the reader to use a different key type, returning an error if the
+    /// new key type can't represent every string's key
+    ///
+    /// Keys keep their index, so a key of the old type can be converted into the new one
+    /// with [`Key::into_usize`] and [`Key::try_from_usize`]. No strings are copied, but
+    /// the lookup index is rebuilt for the new keys if it had already been built
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, MiniSpur, Rodeo, RodeoReader, Spur};
+    ///
+    /// let mut rodeo: Rodeo<Spur> = Rodeo::new();
+    /// rodeo.get_or_intern("small");
+    ///
+    /// let reader: RodeoReader<MiniSpur> = rodeo.into_reader().try_rekey().unwrap();
+    /// let key = reader.get("small").unwrap();
+    /// assert_eq!(key.into_usize(), 0);
+    /// ```
+    ///
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn iter(&self) -> Iter<'_, K> {
-        Iter::from_reader(self)
+    pub fn try_rekey<K2>(self) -> LassoResult<RodeoReader<K2, S>>
+    where
+        K2: Key,
+        S: BuildHasher,
+    {
+        resolver::check_key_space::<K2>(self.strings.len())?;
+
+        let map = if self.map.get().is_some() {
+            LazyMap::built(build_map(&self.strings, &self.hasher))
+        } else {
+            LazyMap::unbuilt()
+        };
+
+        Ok(RodeoReader {
+            map,
+            hasher: self.hasher,
+            strings: self.strings,
+            gaps: self.gaps.rekey(),
+            __arena: self.__arena,
+        })
     }
+}
 
-    /// Returns an iterator over the interned strings
+#[cfg(feature = "multi-threaded")]
+impl<K, S> RodeoReader<K, S>
+where
+    K: Key + Hash,
+    S: BuildHasher + Clone,
+{
+    /// Consumes the current reader and makes it into a [`ThreadedRodeo`] so that strings can
+    /// be concurrently interned again, every string keeps the key it had
+    ///
+    /// The arena of a reader made by a `ThreadedRodeo` is reused, while the strings of a reader
+    /// made by a `Rodeo` are copied into a new one
+    ///
+    /// # Panics
+    ///
+    /// Panics if the memory for the interner couldn't be allocated, see
+    /// [`RodeoReader::try_into_threaded`] for a fallible version
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// let key = rodeo.get_or_intern("Appear weak when you are strong, and strong when you are weak.");
+    ///
+    /// let rodeo = rodeo.into_reader().into_threaded();
+    /// assert_eq!(
+    ///     "Appear weak when you are strong, and strong when you are weak.",
+    ///     rodeo.resolve(&key),
+    /// );
+    /// assert_ne!(key, rodeo.get_or_intern("Let your plans be dark and impenetrable as night"));
+    /// ```
+    ///
+    /// [`ThreadedRodeo`]: crate::ThreadedRodeo
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn strings(&self) -> Strings<'_, K> {
-        Strings::from_reader(self)
+    #[must_use]
+    pub fn into_threaded(self) -> ThreadedRodeo<K, S> {
+        self.try_into_threaded()
+            .expect("failed to allocate memory for interner")
     }
 
-    /// Consumes the current rodeo and makes it into a [`RodeoResolver`], allowing
-    /// contention-free access from multiple threads with the lowest possible memory consumption
+    /// Consumes the current reader and makes it into a [`ThreadedRodeo`], returning an error
+    /// if the memory for the interner couldn't be allocated
     ///
     /// # Example
     ///
     /// ```rust
     /// use lasso::Rodeo;
     ///
-    /// // ThreadedRodeo is interchangeable for Rodeo here
     /// let mut rodeo = Rodeo::default();
     /// let key = rodeo.get_or_intern("Appear weak when you are strong, and strong when you are weak.");
-    /// let reader_rodeo = rodeo.into_reader();
     ///
-    /// let resolver_rodeo = reader_rodeo.into_resolver();
+    /// let rodeo = rodeo.into_reader().try_into_threaded().unwrap();
     /// assert_eq!(
     ///     "Appear weak when you are strong, and strong when you are weak.",
-    ///     resolver_rodeo.resolve(&key),
+    ///     rodeo.resolve(&key),
     /// );
     /// ```
     ///
-    /// [`RodeoResolver`]: crate::RodeoResolver
+    /// [`ThreadedRodeo`]: crate::ThreadedRodeo
     #[cfg_attr(feature = "inline-more", inline)]
-    #[must_use]
-    pub fn into_resolver(self) -> RodeoResolver<K> {
+    pub fn try_into_threaded(self) -> LassoResult<ThreadedRodeo<K, S>> {
         let RodeoReader {
-            strings, __arena, ..
+            hasher,
+            strings,
+            gaps,
+            __arena,
+            ..
         } = self;
 
-        // Safety: The current reader no longer contains references to the strings
-        // in the vec given to RodeoResolver
-        unsafe { RodeoResolver::new(strings, __arena) }
+        // Safety: Every string was stored in the reader's arena and is indexed by its key
+        unsafe { ThreadedRodeo::try_from_reader_parts(hasher, strings, &gaps, __arena) }
+    }
+}
+
+/// Builds the map used to look up `strings`, where each string's key is its index
+///
+/// Duplicate strings keep the key of their first occurrence
+fn build_map<K, S>(strings: &[&'static str], hasher: &S) -> StringMap<K>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    let mut map = StringMap::with_capacity(strings.len());
+
+    for (idx, &string) in strings.iter().enumerate() {
+        let hash = hasher.hash_one(string);
+
+        // Only keys of strings before the current one have been inserted, so every key in the
+        // map is in bounds of the strings that come before it
+        if find_string::<K>(&map, strings, hash, string).is_none() {
+            // The strings' constructors already made sure that every index can be a key
+            let key = K::try_from_usize(idx).unwrap_or_else(|| unreachable!());
+            insert_string(&mut map, strings, hasher, hash, key);
+        }
+    }
+
+    map
+}
+
+/// The map a [`RodeoReader`] looks strings up with, which can be left unbuilt until the
+/// first lookup
+///
+/// Once it's built the map is never touched mutably again, so shared references to it
+/// can be handed out freely
+struct LazyMap<K> {
+    state: AtomicU8,
+    map: UnsafeCell<StringMap<K>>,
+}
+
+impl<K> LazyMap<K> {
+    const UNBUILT: u8 = 0;
+    const BUILDING: u8 = 1;
+    const BUILT: u8 = 2;
+
+    fn built(map: StringMap<K>) -> Self {
+        Self {
+            state: AtomicU8::new(Self::BUILT),
+            map: UnsafeCell::new(map),
+        }
+    }
+
+    fn unbuilt() -> Self {
+        Self {
+            state: AtomicU8::new(Self::UNBUILT),
+            map: UnsafeCell::new(StringMap::new()),
+        }
+    }
+
+    /// Get the map if it's been built
+    #[inline]
+    fn get(&self) -> Option<&StringMap<K>> {
+        if self.state.load(Ordering::Acquire) == Self::BUILT {
+            // Safety: The map is never mutated once it's been built
+            Some(unsafe { &*self.map.get() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the map, building it with `build` if it hasn't been already
+    ///
+    /// If another thread is building the map this waits for it to finish
+    #[inline]
+    fn get_or_build<F>(&self, build: F) -> &StringMap<K>
+    where
+        F: FnOnce() -> StringMap<K>,
+    {
+        if let Some(map) = self.get() {
+            return map;
+        }
+
+        self.build_cold(build)
+    }
+
+    #[cold]
+    fn build_cold<F>(&self, build: F) -> &StringMap<K>
+    where
+        F: FnOnce() -> StringMap<K>,
+    {
+        /// Lets other threads try building the map if `build` panics
+        struct Reset<'a>(&'a AtomicU8);
+
+        impl Drop for Reset<'_> {
+            fn drop(&mut self) {
+                self.0.store(LazyMap::<()>::UNBUILT, Ordering::Release);
+            }
+        }
+
+        let mut build = Some(build);
+        loop {
+            match self.state.compare_exchange_weak(
+                Self::UNBUILT,
+                Self::BUILDING,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    let reset = Reset(&self.state);
+                    let map = (build.take().unwrap_or_else(|| unreachable!()))();
+
+                    // Safety: Only the thread that moved the state to `BUILDING` can touch
+                    //         the map, and no references to it exist until it's built
+                    unsafe { *self.map.get() = map };
+
+                    mem::forget(reset);
+                    self.state.store(Self::BUILT, Ordering::Release);
+                }
+
+                Err(Self::BUILT) => {
+                    // Safety: The map is never mutated once it's been built
+                    return unsafe { &*self.map.get() };
+                }
+
+                Err(_) => hint::spin_loop(),
+            }
+        }
     }
 }
 
-unsafe impl<K: Sync, S: Sync> Sync for RodeoReader<K, S> {}
-unsafe impl<K: Send, S: Send> Send for RodeoReader<K, S> {}
+// Safety: The map is only written to by the thread that wins the race to build it, and
+// everyone else waits for it to be built before reading it. Keys made by that thread are
+// read by all the others, so they have to be sendable as well as shareable
+unsafe impl<K: Send + Sync> Sync for LazyMap<K> {}
+
+impl<K: Key + Debug, S> Debug for RodeoReader<K, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        util::debug_interner(
+            f,
+            "RodeoReader",
+            self.len(),
+            self.current_memory_usage(),
+            |idx| Some((K::try_from_usize(idx)?, *self.strings.get(idx)?)),
+        )
+    }
+}
 
 impl<'a, K: Key, S> IntoIterator for &'a RodeoReader<K, S> {
     type Item = (K, &'a str);
@@ -357,33 +1202,31 @@ where
 
 impl<K, S> Eq for RodeoReader<K, S> {}
 
-impl<K, S> PartialEq<Self> for RodeoReader<K, S> {
+impl<K, S, K2, S2> PartialEq<RodeoReader<K2, S2>> for RodeoReader<K, S> {
     #[cfg_attr(feature = "inline-more", inline)]
-    fn eq(&self, other: &Self) -> bool {
+    fn eq(&self, other: &RodeoReader<K2, S2>) -> bool {
         self.strings == other.strings
     }
 }
 
-impl<K, S> PartialEq<RodeoResolver<K>> for RodeoReader<K, S> {
+impl<K, S, K2> PartialEq<RodeoResolver<K2>> for RodeoReader<K, S> {
     #[cfg_attr(feature = "inline-more", inline)]
-    fn eq(&self, other: &RodeoResolver<K>) -> bool {
+    fn eq(&self, other: &RodeoResolver<K2>) -> bool {
         self.strings == other.strings
     }
 }
 
-impl<K, S> PartialEq<Rodeo<K, S>> for RodeoReader<K, S> {
+impl<K, S, K2, S2> PartialEq<Rodeo<K2, S2>> for RodeoReader<K, S> {
     #[cfg_attr(feature = "inline-more", inline)]
-    fn eq(&self, other: &Rodeo<K, S>) -> bool {
+    fn eq(&self, other: &Rodeo<K2, S2>) -> bool {
         self.strings == other.strings
     }
 }
 
 compile! {
     if #[feature = "serialize"] {
-        use crate::{Capacity, arenas::Arena};
-        use alloc::string::String;
+        use crate::arenas::Arena;
         use core::num::NonZeroUsize;
-        use hashbrown::hash_map::RawEntryMut;
         use serde::{
             de::{Deserialize, Deserializer},
             ser::{Serialize, Serializer},
@@ -391,6 +1234,13 @@ compile! {
     }
 }
 
+#[cfg(feature = "deepsize")]
+impl<K, S> deepsize::DeepSizeOf for RodeoReader<K, S> {
+    fn deep_size_of_children(&self, _context: &mut deepsize::Context) -> usize {
+        self.current_memory_usage()
+    }
+}
+
 #[cfg(feature = "serialize")]
 impl<K, H> Serialize for RodeoReader<K, H> {
     #[cfg_attr(feature = "inline-more", inline)]
@@ -421,7 +1271,7 @@ impl<'de, K: Key, S: BuildHasher + Default> Deserialize<'de> for RodeoReader<K,
 
         let hasher: S = Default::default();
         let mut strings = Vec::with_capacity(capacity.strings);
-        let mut map = HashMap::with_capacity_and_hasher(capacity.strings, ());
+        let mut map = StringMap::with_capacity(capacity.strings);
         let mut arena =
             Arena::new(capacity.bytes, usize::MAX).expect("failed to allocate memory for interner");
 
@@ -434,42 +1284,26 @@ impl<'de, K: Key, S: BuildHasher + Default> Deserialize<'de> for RodeoReader<K,
 
             let hash = hasher.hash_one(allocated);
 
-            // Get the map's entry that the string should occupy
-            let entry = map.raw_entry_mut().from_hash(hash, |key: &K| {
-                // Safety: The index given by `key` will be in bounds of the strings vector
-                let key_string: &str = unsafe { index_unchecked!(strings, key.into_usize()) };
-
-                // Compare the requested string against the key's string
-                allocated == key_string
-            });
-
-            match entry {
-                RawEntryMut::Occupied(..) => {
-                    debug_assert!(false, "re-interned a key while deserializing");
-                }
-                RawEntryMut::Vacant(entry) => {
-                    // Create the key from the vec's index that the string will hold
-                    let key =
-                        K::try_from_usize(key).expect("failed to create key while deserializing");
+            if find_string::<K>(&map, &strings, hash, allocated).is_some() {
+                debug_assert!(false, "re-interned a key while deserializing");
+                continue;
+            }
 
-                    // Push the allocated string to the strings vector
-                    strings.push(allocated);
+            // Create the key from the vec's index that the string will hold
+            let key = K::try_from_usize(key).expect("failed to create key while deserializing");
 
-                    // Insert the key with the hash of the string that it points to, reusing the hash we made earlier
-                    entry.insert_with_hasher(hash, key, (), |key| {
-                        let key_string: &str =
-                            unsafe { index_unchecked!(strings, key.into_usize()) };
+            // Push the allocated string to the strings vector
+            strings.push(allocated);
 
-                        hasher.hash_one(key_string)
-                    });
-                }
-            }
+            // Insert the key with the hash of the string that it points to, reusing the hash we made earlier
+            insert_string(&mut map, &strings, &hasher, hash, key);
         }
 
         Ok(Self {
-            map,
+            map: LazyMap::built(map),
             hasher,
             strings,
+            gaps: KeySet::new(),
             __arena: AnyArena::Arena(arena),
         })
     }
@@ -481,6 +1315,80 @@ mod tests {
         #[cfg(feature = "serialize")]
         use crate::RodeoReader;
         use crate::{Key, Rodeo, Spur};
+        #[cfg(feature = "no-std")]
+        use alloc::{
+            string::{String, ToString},
+            vec::Vec,
+        };
+
+        #[test]
+        fn try_rekey() {
+            let mut rodeo: Rodeo<Spur> = Rodeo::new();
+            for i in 0..300_usize {
+                rodeo.get_or_intern(i.to_string());
+            }
+
+            let err = rodeo.clone().into_reader().try_rekey::<crate::MicroSpur>();
+            assert_eq!(
+                err.unwrap_err().kind(),
+                crate::LassoErrorKind::KeySpaceExhaustion,
+            );
+
+            let reader = rodeo.into_reader().try_rekey::<crate::MiniSpur>().unwrap();
+            assert!(reader.map.get().is_some());
+            for i in 0..300_usize {
+                let key = reader.get(i.to_string()).unwrap();
+                assert_eq!(key.into_usize(), i);
+                assert_eq!(reader.resolve(&key), i.to_string());
+            }
+        }
+
+        #[test]
+        fn from_sorted_unique() {
+            let reader =
+                crate::RodeoReader::<Spur>::from_sorted_unique(["a", "b", "c"], Default::default())
+                    .unwrap();
+            assert!(reader.map.get().is_none());
+            assert_eq!(reader.resolve(&Spur::try_from_usize(1).unwrap()), "b");
+            assert!(reader.map.get().is_none());
+
+            assert_eq!(reader.get("c"), Spur::try_from_usize(2));
+            assert_eq!(reader.get("d"), None);
+            assert_eq!(reader.map.get().unwrap().len(), 3);
+        }
+
+        #[test]
+        #[cfg(not(feature = "no-std"))]
+        fn from_sorted_unique_concurrent_lookups() {
+            let strings: Vec<String> = (0..1000).map(|i| format!("{:04}", i)).collect();
+            let reader =
+                crate::RodeoReader::<Spur>::from_sorted_unique(&strings, Default::default())
+                    .unwrap();
+
+            std::thread::scope(|scope| {
+                for _ in 0..4 {
+                    scope.spawn(|| {
+                        for (i, string) in strings.iter().enumerate() {
+                            assert_eq!(reader.get(string), Spur::try_from_usize(i));
+                        }
+                    });
+                }
+            });
+        }
+
+        #[test]
+        fn from_strings() {
+            let strings = ["a", "b", "", "a"].iter().map(|&s| s.into()).collect();
+            let reader =
+                crate::RodeoReader::<Spur>::from_strings(strings, Default::default()).unwrap();
+
+            assert_eq!(reader.len(), 4);
+            assert_eq!(reader.get("a"), Spur::try_from_usize(0));
+            assert_eq!(reader.get("b"), Spur::try_from_usize(1));
+            assert_eq!(reader.get(""), Spur::try_from_usize(2));
+            assert_eq!(reader.get("c"), None);
+            assert_eq!(reader.resolve(&Spur::try_from_usize(3).unwrap()), "a");
+        }
 
         #[test]
         fn get() {
@@ -590,6 +1498,22 @@ mod tests {
             let _ = rodeo.into_reader();
         }
 
+        #[test]
+        fn memory_stats() {
+            let mut rodeo = Rodeo::default();
+            rodeo.get_or_intern("abc");
+            rodeo.get_or_intern("defgh");
+
+            let usage = rodeo.current_memory_usage();
+            let stats = rodeo.memory_stats();
+            let reader = rodeo.into_reader();
+
+            assert_eq!(reader.current_memory_usage(), usage);
+            assert_eq!(reader.arena_memory_usage(), stats.arena_bytes());
+            assert_eq!(reader.memory_stats().string_bytes(), 8);
+            assert_eq!(reader.memory_stats().total_bytes(), usage);
+        }
+
         #[test]
         fn into_resolver() {
             let mut rodeo = Rodeo::default();
@@ -599,6 +1523,39 @@ mod tests {
             assert_eq!("A", resolver.resolve(&key));
         }
 
+        #[test]
+        fn get_many() {
+            let mut rodeo = Rodeo::default();
+            let keys: Vec<Spur> = (0..100)
+                .map(|i| rodeo.get_or_intern(i.to_string()))
+                .collect();
+            let reader = rodeo.into_reader();
+
+            let strings: Vec<String> = (0..200).map(|i| i.to_string()).collect();
+            let found = reader.get_many(strings.iter().map(String::as_str));
+            assert_eq!(found.len(), 200);
+            for (i, key) in found.iter().enumerate() {
+                assert_eq!(*key, keys.get(i).copied());
+            }
+
+            assert!(reader.get_many([]).is_empty());
+            assert!(reader.contains_all(strings[..100].iter().map(String::as_str)));
+            assert!(!reader.contains_all(strings.iter().map(String::as_str)));
+            assert!(reader.contains_all([]));
+        }
+
+        #[test]
+        fn into_resolver_compact() {
+            let mut rodeo = Rodeo::default();
+            let a = rodeo.get_or_intern("A");
+            let bc = rodeo.get_or_intern("BC");
+
+            let resolver = rodeo.into_reader().into_resolver_compact();
+            assert_eq!(resolver.resolve(&a), "A");
+            assert_eq!(resolver.resolve(&bc), "BC");
+            assert_eq!(resolver.arena_memory_usage(), 3);
+        }
+
         #[test]
         #[cfg(not(any(feature = "no-std", feature = "ahasher")))]
         fn debug() {
@@ -737,9 +1694,11 @@ mod tests {
 
     #[cfg(all(not(any(miri, feature = "no-std")), feature = "multi-threaded"))]
     mod multi_threaded {
-        use crate::{Key, RodeoReader, Spur, ThreadedRodeo};
-        use std::thread;
+        #[cfg(feature = "serialize")]
+        use crate::RodeoReader;
+        use crate::{Key, Rodeo, Spur, ThreadedRodeo};
         use std::sync::Arc;
+        use std::thread;
 
         #[test]
         fn get() {
@@ -870,6 +1829,44 @@ mod tests {
             assert_eq!("A", resolver.resolve(&key));
         }
 
+        #[test]
+        fn into_threaded() {
+            let rodeo = ThreadedRodeo::default();
+            let a = rodeo.get_or_intern("A");
+            let b = rodeo.get_or_intern("B");
+            let arena_usage = rodeo.arena_memory_usage();
+
+            let rodeo = Arc::new(rodeo.into_reader().into_threaded());
+            assert_eq!(rodeo.len(), 2);
+            assert_eq!(rodeo.get("A"), Some(a));
+            assert_eq!(rodeo.resolve(&b), "B");
+            // The arena was reused instead of copied
+            assert_eq!(rodeo.arena_memory_usage(), arena_usage);
+
+            let moved = Arc::clone(&rodeo);
+            let c = thread::spawn(move || moved.get_or_intern("C"))
+                .join()
+                .unwrap();
+            assert_eq!(c.into_usize(), 2);
+            assert_eq!(rodeo.get_or_intern("A"), a);
+        }
+
+        #[test]
+        fn into_threaded_from_rodeo() {
+            let mut rodeo: Rodeo = Rodeo::new();
+            let keys = ["A", "BC", "DEF"].map(|string| rodeo.get_or_intern(string));
+
+            let rodeo = rodeo.into_reader().into_threaded();
+            for (key, string) in keys.iter().zip(["A", "BC", "DEF"]) {
+                assert_eq!(rodeo.resolve(key), string);
+                assert_eq!(rodeo.get(string), Some(*key));
+            }
+            assert_eq!(rodeo.get_or_intern("G").into_usize(), 3);
+
+            let empty = Rodeo::default().into_reader().into_threaded();
+            assert!(empty.is_empty());
+        }
+
         #[test]
         #[cfg(not(feature = "no-std"))]
         fn debug() {
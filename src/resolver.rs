@@ -1,11 +1,21 @@
 use crate::{
-    arenas::AnyArena,
+    arenas::{AnyArena, Arena},
     keys::{Key, Spur},
-    util::{Iter, Strings},
-    Rodeo, RodeoReader,
+    util::{self, Iter, MemoryStats, Strings},
+    Capacity, KeySet, LassoError, LassoErrorKind, LassoResult, Reader, Rodeo, RodeoReader,
+    StaticResolver,
+};
+use alloc::{boxed::Box, string::String, vec::Vec};
+#[cfg(feature = "compact_str")]
+use compact_str::CompactString;
+use core::{
+    fmt::{self, Debug},
+    marker::PhantomData,
+    mem::size_of,
+    num::NonZeroUsize,
+    ops::{Index, Range},
+    ptr,
 };
-use alloc::vec::Vec;
-use core::{marker::PhantomData, ops::Index};
 
 /// A read-only view of a [`Rodeo`] or [`ThreadedRodeo`] that allows contention-free access to interned strings
 /// with only key to string resolution
@@ -14,10 +24,12 @@ use core::{marker::PhantomData, ops::Index};
 ///
 /// [`Rodeo`]: crate::Rodeo
 /// [`ThreadedRodeo`]: crate::ThreadedRodeo
-#[derive(Debug)]
 pub struct RodeoResolver<K = Spur> {
     /// Vector of strings mapped to key indexes that allows key to string resolution
     pub(crate) strings: Vec<&'static str>,
+    /// Keys below `strings.len()` that were handed out without ever being given a string,
+    /// their slots in `strings` are empty strings that must never be resolved
+    gaps: KeySet<K>,
     /// The arena that contains all the strings
     ///
     /// This is not touched, but *must* be kept since every string in `self.strings`
@@ -35,14 +47,136 @@ impl<K> RodeoResolver<K> {
     /// The references inside of `strings` must be absolutely unique, meaning
     /// that no other references to those strings exist
     ///
-    pub(crate) unsafe fn new(strings: Vec<&'static str>, arena: AnyArena) -> Self {
+    pub(crate) unsafe fn new(strings: Vec<&'static str>, gaps: KeySet<K>, arena: AnyArena) -> Self {
         Self {
             strings,
+            gaps,
             __arena: arena,
             __key: PhantomData,
         }
     }
 
+    /// Creates a `RodeoResolver` directly from a vector of strings, where each string is
+    /// given the key of its index
+    ///
+    /// All of the strings are copied into a single arena bucket that's exactly the size
+    /// of their combined length, so no intermediate [`Rodeo`] or hashing is involved.
+    /// The strings are expected to be unique, duplicates each get their own key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{RodeoResolver, Spur};
+    ///
+    /// let strings = vec![String::from("foo"), String::from("bar")];
+    /// let resolver: RodeoResolver<Spur> = RodeoResolver::from_strings(strings).unwrap();
+    ///
+    /// assert_eq!(resolver.len(), 2);
+    /// assert_eq!(resolver.strings().collect::<Vec<_>>(), ["foo", "bar"]);
+    /// ```
+    ///
+    /// [`Rodeo`]: crate::Rodeo
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn from_strings(strings: Vec<String>) -> LassoResult<Self>
+    where
+        K: Key,
+    {
+        let (strings, arena) = store_exact::<K>(&strings)?;
+
+        // Safety: The strings were all just allocated in the arena
+        Ok(unsafe { Self::new(strings, KeySet::new(), AnyArena::Arena(arena)) })
+    }
+
+    /// Creates a `RodeoResolver` from an iterator of sorted and unique strings, where each
+    /// string is given the key of its position
+    ///
+    /// The strings are copied into the arena as they're streamed in, so the input never
+    /// has to be collected up front. Since a resolver only maps keys to strings nothing is
+    /// hashed, see [`RodeoReader::from_sorted_unique`] for a reader that still allows
+    /// looking strings up
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the strings aren't sorted and unique
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{RodeoResolver, Spur};
+    ///
+    /// let resolver: RodeoResolver<Spur> =
+    ///     RodeoResolver::from_sorted_unique(["apple", "banana", "cherry"]).unwrap();
+    ///
+    /// assert_eq!(resolver.strings().collect::<Vec<_>>(), ["apple", "banana", "cherry"]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn from_sorted_unique<I>(strings: I) -> LassoResult<Self>
+    where
+        K: Key,
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let (strings, arena) = store_sorted_unique::<K, I>(strings)?;
+
+        // Safety: The strings were all just allocated in the arena
+        Ok(unsafe { Self::new(strings, KeySet::new(), AnyArena::Arena(arena)) })
+    }
+
+    /// Creates a `RodeoResolver` from `(key, string)` pairs that were already given their keys
+    ///
+    /// The pairs may come in any order, but their keys must cover every index from zero up to
+    /// the number of pairs exactly once and no string may appear twice, see
+    /// [`Rodeo::extend_with_keys`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, RodeoResolver, Spur};
+    ///
+    /// let key = |index| Spur::try_from_usize(index).unwrap();
+    /// let resolver: RodeoResolver<Spur> =
+    ///     RodeoResolver::from_keyed([(key(1), "bar"), (key(0), "foo")]).unwrap();
+    ///
+    /// assert_eq!(resolver.strings().collect::<Vec<_>>(), ["foo", "bar"]);
+    ///
+    /// // "foo" can't be given two keys
+    /// assert!(RodeoResolver::<Spur>::from_keyed([(key(0), "foo"), (key(1), "foo")]).is_err());
+    /// ```
+    ///
+    /// [`Rodeo::extend_with_keys`]: crate::Rodeo::extend_with_keys
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn from_keyed<I, T>(pairs: I) -> LassoResult<Self>
+    where
+        K: Key,
+        I: IntoIterator<Item = (K, T)>,
+        T: AsRef<str>,
+    {
+        let mut rodeo: Rodeo<K> =
+            Rodeo::try_with_capacity_and_hasher(Capacity::minimal(), Default::default())?;
+        rodeo.extend_with_keys(pairs)?;
+
+        Ok(rodeo.into_resolver())
+    }
+
+    /// Creates a resolver holding copies of `strings` in a single arena bucket that fits
+    /// them exactly, laid out in key order
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new arena could not be allocated
+    ///
+    pub(crate) fn compacted(strings: &[&str], gaps: KeySet<K>) -> Self
+    where
+        K: Key,
+    {
+        let (strings, arena) =
+            store_exact::<K>(strings).expect("failed to allocate memory for interner");
+
+        // Safety: The strings were all just allocated in the arena
+        unsafe { Self::new(strings, gaps, AnyArena::Arena(arena)) }
+    }
+
     /// Resolves a string by its key. Only keys made by the current Resolver or the creator
     /// of the current Resolver may be used
     ///
@@ -74,7 +208,7 @@ impl<K> RodeoResolver<K> {
         // and symmetric, then it will succeed, as the usize used to create it is a valid
         // index into self.strings
         unsafe {
-            assert!(key.into_usize() < self.strings.len());
+            assert!(self.contains_key(key));
 
             self.strings.get_unchecked(key.into_usize())
         }
@@ -107,7 +241,7 @@ impl<K> RodeoResolver<K> {
         // and symmetric, then it will succeed, as the usize used to create it is a valid
         // index into self.strings
         unsafe {
-            if key.into_usize() < self.strings.len() {
+            if self.contains_key(key) {
                 Some(self.strings.get_unchecked(key.into_usize()))
             } else {
                 None
@@ -115,6 +249,50 @@ impl<K> RodeoResolver<K> {
         }
     }
 
+    /// Resolves a string by its key into a [`CompactString`], which stores short strings
+    /// inline instead of allocating
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things");
+    /// let rodeo = rodeo.into_resolver();
+    ///
+    /// let string = rodeo.resolve_compact(&key);
+    /// assert_eq!(string, "Strings of things");
+    /// assert!(!string.is_heap_allocated());
+    /// ```
+    ///
+    /// [`CompactString`]: compact_str::CompactString
+    #[cfg(feature = "compact_str")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve_compact(&self, key: &K) -> CompactString
+    where
+        K: Key,
+    {
+        CompactString::new(self.resolve(key))
+    }
+
+    /// Resolves a string by its key into a [`CompactString`], returning `None` if it's out
+    /// of bounds
+    ///
+    /// [`CompactString`]: compact_str::CompactString
+    #[cfg(feature = "compact_str")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve_compact(&self, key: &K) -> Option<CompactString>
+    where
+        K: Key,
+    {
+        self.try_resolve(key).map(CompactString::new)
+    }
+
     /// Resolves a string by its key without preforming bounds checks
     ///
     /// # Safety
@@ -145,6 +323,89 @@ impl<K> RodeoResolver<K> {
         unsafe { self.strings.get_unchecked(key.into_usize()) }
     }
 
+    /// Resolves a string by its key into a pointer to its first byte and its length in bytes,
+    /// returning a null pointer and a length of zero if the key is out of bounds
+    ///
+    /// The string's storage never moves, so the pointer stays valid until the resolver is
+    /// dropped, even while diffs are applied to it
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// let resolver = rodeo.into_resolver();
+    /// let (ptr, len) = resolver.resolve_ptr(&key);
+    ///
+    /// let bytes = unsafe { std::slice::from_raw_parts(ptr, len) };
+    /// assert_eq!(bytes, b"Hello, world!");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve_ptr(&self, key: &K) -> (*const u8, usize)
+    where
+        K: Key,
+    {
+        self.try_resolve(key)
+            .map_or((ptr::null(), 0), |string| (string.as_ptr(), string.len()))
+    }
+
+    /// Resolves a batch of keys into their strings, in the same order as the keys
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the keys are out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let a = rodeo.get_or_intern("Foo");
+    /// let b = rodeo.get_or_intern("Bar");
+    ///
+    /// let rodeo = rodeo.into_resolver();
+    ///
+    /// assert_eq!(rodeo.resolve_many(&[b, a, b]), ["Bar", "Foo", "Bar"]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve_many<'a>(&'a self, keys: &[K]) -> Vec<&'a str>
+    where
+        K: Key,
+    {
+        keys.iter().map(|key| self.resolve(key)).collect()
+    }
+
+    /// Resolves a batch of keys into their strings, in the same order as the keys, returning
+    /// `None` if any of the keys are out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let a = rodeo.get_or_intern("Foo");
+    /// let b = rodeo.get_or_intern("Bar");
+    ///
+    /// let rodeo = rodeo.into_resolver();
+    ///
+    /// assert_eq!(rodeo.try_resolve_many(&[b, a]), Some(vec!["Bar", "Foo"]));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve_many<'a>(&'a self, keys: &[K]) -> Option<Vec<&'a str>>
+    where
+        K: Key,
+    {
+        keys.iter().map(|key| self.try_resolve(key)).collect()
+    }
+
     /// Returns `true` if the given key exists in the current interner
     ///
     /// # Example
@@ -167,7 +428,7 @@ impl<K> RodeoResolver<K> {
     where
         K: Key,
     {
-        key.into_usize() < self.strings.len()
+        key.into_usize() < self.strings.len() && !self.gaps.contains(*key)
     }
 
     /// Gets the number of interned strings
@@ -209,6 +470,82 @@ impl<K> RodeoResolver<K> {
         self.len() == 0
     }
 
+    /// Get the `RodeoResolver`'s currently allocated memory
+    ///
+    /// This includes the estimated size of the resolver's strings vec, see
+    /// [`RodeoResolver::arena_memory_usage`] for the memory used only by interned strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo.get_or_intern("Documentation often has little hidden bits in it");
+    ///
+    /// let rodeo = rodeo.into_resolver();
+    /// assert!(rodeo.current_memory_usage() > rodeo.arena_memory_usage());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn current_memory_usage(&self) -> usize {
+        self.arena_memory_usage()
+            .saturating_add(self.overhead_memory_usage())
+    }
+
+    /// Get the memory allocated to hold the `RodeoResolver`'s interned strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn arena_memory_usage(&self) -> usize {
+        self.__arena.memory_usage()
+    }
+
+    /// Estimates the memory used by the resolver's strings vec
+    fn overhead_memory_usage(&self) -> usize {
+        self.strings
+            .capacity()
+            .saturating_mul(size_of::<&'static str>())
+    }
+
+    /// Leaks the resolver, returning a handle to it that lives for the rest of the program
+    ///
+    /// Strings resolved through the handle are `&'static str`, so they can be stored anywhere
+    /// without borrowing the resolver. The resolver's memory is never freed, so this should
+    /// only be used for resolvers that would otherwise be kept until the program exits
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(miri)]
+    /// # fn main() {}
+    /// #
+    /// # #[cfg(not(miri))]
+    /// # fn main() {
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// let resolver = rodeo.into_resolver().leak();
+    /// let string: &'static str = resolver.resolve(&key);
+    /// assert_eq!(string, "Strings of things with wings and dings");
+    /// # }
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn leak(self) -> &'static StaticResolver<K> {
+        Box::leak(Box::new(StaticResolver::new(self)))
+    }
+
+    /// Get a breakdown of the `RodeoResolver`'s memory usage
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn memory_stats(&self) -> MemoryStats {
+        MemoryStats::new(
+            self.arena_memory_usage(),
+            self.overhead_memory_usage(),
+            self.strings.iter().copied(),
+        )
+    }
+
     /// Returns an iterator over the interned strings and their key values
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn iter(&self) -> Iter<'_, K> {
@@ -220,10 +557,371 @@ impl<K> RodeoResolver<K> {
     pub fn strings(&self) -> Strings<'_, K> {
         Strings::from_resolver(self)
     }
+
+    /// Counts the interned strings by length, `bucket_bounds` must be sorted and give the
+    /// inclusive upper bound of each bucket
+    ///
+    /// The returned histogram has one more bucket than there are bounds, holding every
+    /// string longer than the final bound
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo.get_or_intern("a");
+    /// rodeo.get_or_intern("bcd");
+    /// rodeo.get_or_intern("efghijklmn");
+    /// let rodeo = rodeo.into_resolver();
+    ///
+    /// assert_eq!(rodeo.length_histogram(&[1, 4]), vec![1, 1, 1]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn length_histogram(&self, bucket_bounds: &[usize]) -> Vec<usize> {
+        util::length_histogram(&self.strings, bucket_bounds)
+    }
+
+    /// Returns the `n` longest interned strings and their keys, longest first with
+    /// ties going to the lowest key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo.get_or_intern("a");
+    /// let long = rodeo.get_or_intern("efghijklmn");
+    /// let medium = rodeo.get_or_intern("bcd");
+    /// let rodeo = rodeo.into_resolver();
+    ///
+    /// assert_eq!(rodeo.top_longest(2), vec![(long, "efghijklmn"), (medium, "bcd")]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn top_longest(&self, n: usize) -> Vec<(K, &str)>
+    where
+        K: Key,
+    {
+        util::top_longest(&self.strings, n)
+    }
+
+    /// Returns `true` if both interners hold exactly the same strings, no matter which
+    /// keys those strings were given
+    ///
+    /// Unlike `==`, which requires every key to resolve to the same string, this only
+    /// compares the sets of interned strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut left = Rodeo::default();
+    /// left.get_or_intern("foo");
+    /// left.get_or_intern("bar");
+    /// let left = left.into_resolver();
+    ///
+    /// let mut right = Rodeo::default();
+    /// right.get_or_intern("bar");
+    /// right.get_or_intern("foo");
+    ///
+    /// assert!(left != right);
+    /// assert!(left.equivalent_content(&right));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn equivalent_content<K2, R>(&self, other: &R) -> bool
+    where
+        R: Reader<K2> + ?Sized,
+    {
+        self.len() == other.len() && self.strings.iter().all(|string| other.contains(string))
+    }
+
+    /// Copies every interned string into one contiguous buffer, returning it along with
+    /// the offsets of each string in key order
+    ///
+    /// There's one more offset than there are strings, so the string of a key is
+    /// `buffer[offsets[key]..offsets[key + 1]]`. This is the same layout Apache Arrow
+    /// uses for string arrays, which makes it cheap to build dictionaries from
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interned strings take up more than `u32::MAX` bytes in total
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo.get_or_intern("Foo");
+    /// rodeo.get_or_intern("Ba");
+    ///
+    /// let rodeo = rodeo.into_resolver();
+    /// let (buffer, offsets) = rodeo.as_contiguous();
+    ///
+    /// assert_eq!(buffer, b"FooBa");
+    /// assert_eq!(offsets, [0, 3, 5]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn as_contiguous(&self) -> (Vec<u8>, Vec<u32>) {
+        let total: usize = self.strings.iter().map(|string| string.len()).sum();
+        assert!(
+            u32::try_from(total).is_ok(),
+            "interned strings are too large for 32-bit offsets",
+        );
+
+        let mut buffer = Vec::with_capacity(total);
+        let mut offsets = Vec::with_capacity(self.strings.len() + 1);
+        offsets.push(0);
+
+        for string in self.strings.iter() {
+            buffer.extend_from_slice(string.as_bytes());
+            // The total length fits in a u32, so every partial length does as well
+            offsets.push(buffer.len() as u32);
+        }
+
+        (buffer, offsets)
+    }
+
+    /// Get the strings that were interned after `older` was taken, assuming both resolvers are
+    /// snapshots of the same interner and that `older` is the earlier one
+    ///
+    /// Keys are handed out in order, so the difference is always the keys from `older.len()` up
+    /// to `self.len()`. The returned [`Diff`] can be sent elsewhere and applied to a copy
+    /// of `older` with [`RodeoResolver::apply_diff`]
+    ///
+    /// Only the lengths of the resolvers are compared, so every string of `older` has to be
+    /// the string the current resolver has for the same key. If it isn't, the diff is still
+    /// made but applying it to `older` won't produce a copy of the current resolver
+    ///
+    /// # Panics
+    ///
+    /// Panics if `older` has more strings than the current resolver. Debug builds also panic
+    /// if the strings of `older` aren't the first strings of the current resolver
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo.get_or_intern("Foo");
+    /// let older = rodeo.clone().into_resolver();
+    ///
+    /// let bar = rodeo.get_or_intern("Bar");
+    /// let newer = rodeo.into_resolver();
+    ///
+    /// let diff = newer.diff(&older);
+    /// assert_eq!(diff.key_range(), 1..2);
+    /// assert_eq!(diff.iter().collect::<Vec<_>>(), [(bar, "Bar")]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn diff(&self, older: &RodeoResolver<K>) -> Diff<K>
+    where
+        K: Key,
+    {
+        assert!(
+            older.len() <= self.len(),
+            "the older resolver has more strings than the newer one",
+        );
+        debug_assert!(
+            self.strings.starts_with(&older.strings),
+            "the older resolver isn't a snapshot of the newer one",
+        );
+
+        Diff {
+            start: older.len(),
+            strings: self.strings[older.len()..]
+                .iter()
+                .map(|&string| string.into())
+                .collect(),
+            __key: PhantomData,
+        }
+    }
+
+    /// Interns the strings of a [`Diff`] made by [`RodeoResolver::diff`], giving them
+    /// the same keys they had in the resolver the diff was made from
+    ///
+    /// Returns an [`InvalidKey`] error if the diff doesn't start right after the current
+    /// resolver's last key, which means it wasn't made against a resolver with as many
+    /// strings as this one. No strings are added to the resolver if an error is returned,
+    /// even if the arena ran out of memory partway through the diff
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo.get_or_intern("Foo");
+    /// let mut older = rodeo.clone().into_resolver();
+    ///
+    /// let bar = rodeo.get_or_intern("Bar");
+    /// let newer = rodeo.into_resolver();
+    ///
+    /// older.apply_diff(&newer.diff(&older)).unwrap();
+    /// assert_eq!(older.resolve(&bar), "Bar");
+    /// assert_eq!(older, newer);
+    /// ```
+    ///
+    /// [`InvalidKey`]: crate::LassoErrorKind::InvalidKey
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn apply_diff(&mut self, diff: &Diff<K>) -> LassoResult<()>
+    where
+        K: Key,
+    {
+        // Diffs can be deserialized from anywhere, so a mismatched one is an error
+        // rather than a bug
+        if diff.start != self.len() {
+            return Err(LassoError::invalid_key(diff.start));
+        }
+
+        // Make sure every key of the diff can be created before interning anything
+        if let Some(last) = diff.key_range().last() {
+            K::try_from_usize(last)
+                .ok_or_else(|| LassoError::new(LassoErrorKind::KeySpaceExhaustion))?;
+        }
+
+        let len = self.strings.len();
+        self.strings.reserve(diff.strings.len());
+        for string in &diff.strings {
+            // Safety: The string is only handed out with the lifetime of the resolver,
+            //         which owns the arena
+            match unsafe { self.__arena.store_str(string) } {
+                Ok(allocated) => self.strings.push(allocated),
+                Err(err) => {
+                    // Forget the part of the diff that was already stored so the diff is
+                    // applied either completely or not at all
+                    self.strings.truncate(len);
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Converts the resolver to use a different key type, returning an error if the
+    /// new key type can't represent every string's key
+    ///
+    /// Keys keep their index, so a key of the old type can be converted into the new one
+    /// with [`Key::into_usize`] and [`Key::try_from_usize`]. No strings are copied
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, MiniSpur, Rodeo, RodeoResolver, Spur};
+    ///
+    /// let mut rodeo: Rodeo<Spur> = Rodeo::new();
+    /// let key = rodeo.get_or_intern("small");
+    ///
+    /// let resolver: RodeoResolver<MiniSpur> = rodeo.into_resolver().try_rekey().unwrap();
+    /// let key = MiniSpur::try_from_usize(key.into_usize()).unwrap();
+    /// assert_eq!(resolver.resolve(&key), "small");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_rekey<K2>(self) -> LassoResult<RodeoResolver<K2>>
+    where
+        K2: Key,
+    {
+        check_key_space::<K2>(self.strings.len())?;
+
+        Ok(RodeoResolver {
+            strings: self.strings,
+            gaps: self.gaps.rekey(),
+            __arena: self.__arena,
+            __key: PhantomData,
+        })
+    }
+}
+
+/// Returns an error if `len` strings can't all be given a key of type `K`
+pub(crate) fn check_key_space<K: Key>(len: usize) -> LassoResult<()> {
+    match len.checked_sub(1) {
+        Some(last) if K::try_from_usize(last).is_none() => {
+            Err(LassoError::new(LassoErrorKind::KeySpaceExhaustion))
+        }
+        _ => Ok(()),
+    }
 }
 
-unsafe impl<K: Send> Send for RodeoResolver<K> {}
-unsafe impl<K: Sync> Sync for RodeoResolver<K> {}
+/// Copies `strings` into an arena with a single bucket that fits them exactly, failing
+/// if any of their indices can't be turned into a key
+pub(crate) fn store_exact<K: Key>(
+    strings: &[impl AsRef<str>],
+) -> LassoResult<(Vec<&'static str>, Arena)> {
+    // Make sure every key can be created before allocating anything
+    check_key_space::<K>(strings.len())?;
+
+    let total_bytes = strings
+        .iter()
+        .map(|string| string.as_ref().len())
+        .sum::<usize>();
+    let mut arena = Arena::new(
+        NonZeroUsize::new(total_bytes).unwrap_or(NonZeroUsize::MIN),
+        usize::MAX,
+    )?;
+
+    let mut allocated = Vec::with_capacity(strings.len());
+    for string in strings {
+        // Safety: The strings are only handed out with the lifetime of whatever
+        //         takes ownership of the arena
+        allocated.push(unsafe { arena.store_str(string.as_ref())? });
+    }
+
+    Ok((allocated, arena))
+}
+
+/// Copies sorted and unique `strings` into an arena as they're streamed in, failing if
+/// there are more of them than there are keys
+pub(crate) fn store_sorted_unique<K, I>(strings: I) -> LassoResult<(Vec<&'static str>, Arena)>
+where
+    K: Key,
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let strings = strings.into_iter();
+    let mut arena = Arena::new(Capacity::default().bytes(), usize::MAX)?;
+
+    let mut allocated: Vec<&'static str> = Vec::with_capacity(strings.size_hint().0);
+    for string in strings {
+        let string = string.as_ref();
+        if let Some(&last) = allocated.last() {
+            debug_assert!(
+                last < string,
+                "the strings given to `from_sorted_unique` must be sorted and unique",
+            );
+        }
+
+        K::try_from_usize(allocated.len())
+            .ok_or_else(|| LassoError::new(LassoErrorKind::KeySpaceExhaustion))?;
+
+        // Safety: The strings are only handed out with the lifetime of whatever
+        //         takes ownership of the arena
+        allocated.push(unsafe { arena.store_str(string)? });
+    }
+
+    Ok((allocated, arena))
+}
+
+impl<K: Key + Debug> Debug for RodeoResolver<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        util::debug_interner(
+            f,
+            "RodeoResolver",
+            self.len(),
+            self.current_memory_usage(),
+            |idx| Some((K::try_from_usize(idx)?, *self.strings.get(idx)?)),
+        )
+    }
+}
 
 impl<'a, K: Key> IntoIterator for &'a RodeoResolver<K> {
     type Item = (K, &'a str);
@@ -246,32 +944,97 @@ impl<K: Key> Index<K> for RodeoResolver<K> {
 
 impl<K> Eq for RodeoResolver<K> {}
 
-impl<K> PartialEq<Self> for RodeoResolver<K> {
+impl<K, K2> PartialEq<RodeoResolver<K2>> for RodeoResolver<K> {
     #[cfg_attr(feature = "inline-more", inline)]
-    fn eq(&self, other: &Self) -> bool {
+    fn eq(&self, other: &RodeoResolver<K2>) -> bool {
         self.strings == other.strings
     }
 }
 
-impl<K, S> PartialEq<RodeoReader<K, S>> for RodeoResolver<K> {
+impl<K, K2, S2> PartialEq<RodeoReader<K2, S2>> for RodeoResolver<K> {
     #[cfg_attr(feature = "inline-more", inline)]
-    fn eq(&self, other: &RodeoReader<K, S>) -> bool {
+    fn eq(&self, other: &RodeoReader<K2, S2>) -> bool {
         self.strings == other.strings
     }
 }
 
-impl<K, S> PartialEq<Rodeo<K, S>> for RodeoResolver<K> {
+impl<K, K2, S2> PartialEq<Rodeo<K2, S2>> for RodeoResolver<K> {
     #[cfg_attr(feature = "inline-more", inline)]
-    fn eq(&self, other: &Rodeo<K, S>) -> bool {
+    fn eq(&self, other: &Rodeo<K2, S2>) -> bool {
         self.strings == other.strings
     }
 }
 
+/// The strings interned between two snapshots of an interner, made by [`RodeoResolver::diff`]
+/// and applied with [`RodeoResolver::apply_diff`]
+///
+/// With the `serialize` feature a diff can be serialized on its own, so only the new strings
+/// need to be sent to whoever holds the older snapshot
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::Rodeo;
+///
+/// let mut rodeo = Rodeo::default();
+/// let older = rodeo.clone().into_resolver();
+///
+/// rodeo.get_or_intern("Foo");
+/// rodeo.get_or_intern("Bar");
+/// let diff = rodeo.into_resolver().diff(&older);
+///
+/// assert_eq!(diff.len(), 2);
+/// assert_eq!(diff.strings().collect::<Vec<_>>(), ["Foo", "Bar"]);
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diff<K = Spur> {
+    /// The key value of the first string
+    start: usize,
+    /// The strings in key order
+    strings: Vec<String>,
+    __key: PhantomData<K>,
+}
+
+impl<K> Diff<K>
+where
+    K: Key,
+{
+    /// Gets the range of key values the diff's strings were given
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn key_range(&self) -> Range<usize> {
+        self.start..self.start + self.strings.len()
+    }
+
+    /// Returns an iterator over the diff's keys and strings, in key order
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter(&self) -> impl Iterator<Item = (K, &str)> + '_ {
+        self.key_range()
+            .zip(&self.strings)
+            .filter_map(|(key, string)| Some((K::try_from_usize(key)?, string.as_str())))
+    }
+
+    /// Returns an iterator over the diff's strings, in key order
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn strings(&self) -> impl Iterator<Item = &str> + '_ {
+        self.strings.iter().map(String::as_str)
+    }
+
+    /// Gets the number of strings in the diff
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if no strings were interned between the two snapshots
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
 compile! {
     if #[feature = "serialize"] {
-        use crate::{Capacity, arenas::Arena};
-        use alloc::string::String;
-        use core::num::NonZeroUsize;
         use serde::{
             de::{Deserialize, Deserializer},
             ser::{Serialize, Serializer},
@@ -279,6 +1042,13 @@ compile! {
     }
 }
 
+#[cfg(feature = "deepsize")]
+impl<K> deepsize::DeepSizeOf for RodeoResolver<K> {
+    fn deep_size_of_children(&self, _context: &mut deepsize::Context) -> usize {
+        self.current_memory_usage()
+    }
+}
+
 #[cfg(feature = "serialize")]
 impl<K> Serialize for RodeoResolver<K> {
     #[cfg_attr(feature = "inline-more", inline)]
@@ -323,18 +1093,57 @@ impl<'de, K: Key> Deserialize<'de> for RodeoResolver<K> {
 
         Ok(Self {
             strings,
+            gaps: KeySet::new(),
             __arena: AnyArena::Arena(arena),
             __key: PhantomData,
         })
     }
 }
 
+#[cfg(feature = "serialize")]
+impl<K> Serialize for Diff<K> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Serialize the diff as a `(usize, Vec<String>)`
+        (self.start, &self.strings).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<'de, K> Deserialize<'de> for Diff<K> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (start, strings) = <(usize, Vec<String>)>::deserialize(deserializer)?;
+
+        Ok(Self {
+            start,
+            strings,
+            __key: PhantomData,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     mod single_threaded {
         #[cfg(feature = "serialize")]
         use crate::RodeoResolver;
-        use crate::{Key, Rodeo, Spur};
+        use crate::{
+            resolver::Diff, Capacity, Key, LassoErrorKind, MemoryLimits, MicroSpur, Rodeo, Spur,
+        };
+        #[cfg(feature = "no-std")]
+        use alloc::{
+            string::{String, ToString},
+            vec,
+            vec::Vec,
+        };
+        use core::{marker::PhantomData, num::NonZeroUsize};
 
         #[test]
         fn resolve() {
@@ -353,6 +1162,238 @@ mod tests {
             resolver.resolve(&Spur::try_from_usize(10).unwrap());
         }
 
+        #[test]
+        fn from_keyed() {
+            let key = |index| Spur::try_from_usize(index).unwrap();
+
+            let resolver =
+                crate::RodeoResolver::from_keyed([(key(2), "c"), (key(0), "a"), (key(1), "b")])
+                    .unwrap();
+            assert_eq!(resolver.strings().collect::<Vec<_>>(), ["a", "b", "c"]);
+
+            let err = crate::RodeoResolver::from_keyed([(key(1), "b")]).unwrap_err();
+            assert_eq!(err.kind(), LassoErrorKind::InvalidKey);
+            assert_eq!(err.key(), Some(0));
+        }
+
+        #[test]
+        fn from_strings() {
+            let strings = vec!["a".to_string(), String::new(), "bcd".to_string()];
+            let resolver = crate::RodeoResolver::<Spur>::from_strings(strings).unwrap();
+            assert_eq!(resolver.len(), 3);
+            assert_eq!(resolver.resolve(&Spur::try_from_usize(0).unwrap()), "a");
+            assert_eq!(resolver.resolve(&Spur::try_from_usize(1).unwrap()), "");
+            assert_eq!(resolver.resolve(&Spur::try_from_usize(2).unwrap()), "bcd");
+            assert_eq!(resolver.arena_memory_usage(), 4);
+
+            let empty = crate::RodeoResolver::<Spur>::from_strings(Vec::new()).unwrap();
+            assert!(empty.is_empty());
+
+            let too_many = (0..=u8::MAX as usize).map(|i| i.to_string()).collect();
+            assert_eq!(
+                crate::RodeoResolver::<MicroSpur>::from_strings(too_many)
+                    .unwrap_err()
+                    .kind(),
+                LassoErrorKind::KeySpaceExhaustion,
+            );
+        }
+
+        #[test]
+        fn from_sorted_unique() {
+            let resolver =
+                crate::RodeoResolver::<Spur>::from_sorted_unique(["", "a", "ab", "b"]).unwrap();
+            assert_eq!(resolver.strings().collect::<Vec<_>>(), ["", "a", "ab", "b"],);
+
+            let too_many = (0..=u8::MAX).map(|i| [b'a' + i / 26, b'a' + i % 26]);
+            let too_many = too_many.map(|bytes| String::from_utf8(bytes.to_vec()).unwrap());
+            assert_eq!(
+                crate::RodeoResolver::<MicroSpur>::from_sorted_unique(too_many)
+                    .unwrap_err()
+                    .kind(),
+                LassoErrorKind::KeySpaceExhaustion,
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        #[cfg(debug_assertions)]
+        fn from_sorted_unique_unsorted() {
+            let _ = crate::RodeoResolver::<Spur>::from_sorted_unique(["b", "a"]);
+        }
+
+        #[test]
+        fn try_rekey() {
+            let mut rodeo: Rodeo<Spur> = Rodeo::new();
+            let keys: Vec<Spur> = (0..300)
+                .map(|i| rodeo.get_or_intern(i.to_string()))
+                .collect();
+
+            assert_eq!(
+                rodeo
+                    .clone()
+                    .into_resolver()
+                    .try_rekey::<MicroSpur>()
+                    .unwrap_err()
+                    .kind(),
+                LassoErrorKind::KeySpaceExhaustion,
+            );
+
+            let resolver = rodeo
+                .into_resolver()
+                .try_rekey::<crate::MiniSpur>()
+                .unwrap();
+            for (i, key) in keys.iter().enumerate() {
+                let key = crate::MiniSpur::try_from_usize(key.into_usize()).unwrap();
+                assert_eq!(resolver.resolve(&key), i.to_string());
+            }
+
+            let empty = crate::RodeoResolver::<Spur>::from_strings(Vec::new()).unwrap();
+            assert!(empty.try_rekey::<MicroSpur>().unwrap().is_empty());
+        }
+
+        #[test]
+        fn as_contiguous() {
+            let mut rodeo = Rodeo::default();
+            let (buffer, offsets) = rodeo.clone().into_resolver().as_contiguous();
+            assert!(buffer.is_empty());
+            assert_eq!(offsets, [0]);
+
+            let keys: Vec<_> = ["abc", "", "de", "\u{1F980}"]
+                .iter()
+                .map(|string| rodeo.get_or_intern(string))
+                .collect();
+
+            let resolver = rodeo.into_resolver();
+            let (buffer, offsets) = resolver.as_contiguous();
+            assert_eq!(offsets.len(), resolver.len() + 1);
+            for key in keys {
+                let (start, end) = (offsets[key.into_usize()], offsets[key.into_usize() + 1]);
+                assert_eq!(
+                    &buffer[start as usize..end as usize],
+                    resolver.resolve(&key).as_bytes(),
+                );
+            }
+        }
+
+        #[test]
+        fn diff() {
+            let mut rodeo: Rodeo<MicroSpur> = Rodeo::new();
+            rodeo.get_or_intern("a");
+            let mut older = rodeo.clone().into_resolver();
+            assert!(older.diff(&older).is_empty());
+
+            let b = rodeo.get_or_intern("b");
+            let c = rodeo.get_or_intern("c");
+            let newer = rodeo.into_resolver();
+
+            let diff = newer.diff(&older);
+            assert_eq!(diff.key_range(), 1..3);
+            assert_eq!(diff.len(), 2);
+            assert_eq!(diff.iter().collect::<Vec<_>>(), [(b, "b"), (c, "c")]);
+
+            older.apply_diff(&diff).unwrap();
+            assert_eq!(older, newer);
+            assert_eq!(older.resolve(&c), "c");
+        }
+
+        #[test]
+        #[should_panic]
+        #[cfg(not(miri))]
+        fn diff_against_newer() {
+            let mut rodeo = Rodeo::default();
+            let older = rodeo.clone().into_resolver();
+            rodeo.get_or_intern("a");
+
+            older.diff(&rodeo.into_resolver());
+        }
+
+        #[test]
+        fn apply_mismatched_diff() {
+            let mut rodeo = Rodeo::default();
+            let mut older = rodeo.clone().into_resolver();
+            rodeo.get_or_intern("a");
+            let mut middle = rodeo.clone().into_resolver();
+            rodeo.get_or_intern("b");
+            let newer = rodeo.into_resolver();
+
+            // The diff starts past the end of the resolver
+            let err = older.apply_diff(&newer.diff(&middle)).unwrap_err();
+            assert_eq!(err.kind(), LassoErrorKind::InvalidKey);
+            assert_eq!(err.key(), Some(1));
+            assert!(older.is_empty());
+
+            // The diff starts before the end of the resolver
+            let diff = Diff {
+                start: 0,
+                strings: vec!["b".to_string()],
+                __key: PhantomData,
+            };
+            let err = middle.apply_diff(&diff).unwrap_err();
+            assert_eq!(err.kind(), LassoErrorKind::InvalidKey);
+            assert_eq!(err.key(), Some(0));
+            assert_eq!(middle.len(), 1);
+        }
+
+        #[test]
+        fn apply_diff_key_exhaustion() {
+            let mut rodeo: Rodeo<MicroSpur> = Rodeo::new();
+            for i in 0..u8::MAX as usize {
+                rodeo.get_or_intern(i.to_string());
+            }
+            let mut full = rodeo.clone().into_resolver();
+
+            let diff = Diff {
+                start: full.len(),
+                strings: vec!["a".to_string()],
+                __key: PhantomData,
+            };
+            assert_eq!(
+                full.apply_diff(&diff).unwrap_err().kind(),
+                LassoErrorKind::KeySpaceExhaustion,
+            );
+            assert_eq!(full, rodeo);
+        }
+
+        #[test]
+        fn apply_diff_memory_limit() {
+            let mut rodeo: Rodeo = Rodeo::with_capacity_and_memory_limits(
+                Capacity::for_bytes(NonZeroUsize::new(16).unwrap()),
+                MemoryLimits::for_memory_usage(16),
+            );
+            rodeo.get_or_intern("a");
+            let mut older = rodeo.clone().into_resolver();
+
+            // The first string fits in the arena's only bucket but the second doesn't
+            let diff = Diff {
+                start: older.len(),
+                strings: vec!["b".to_string(), "c".repeat(32)],
+                __key: PhantomData,
+            };
+            assert_eq!(
+                older.apply_diff(&diff).unwrap_err().kind(),
+                LassoErrorKind::MemoryLimitReached,
+            );
+            assert_eq!(older, rodeo);
+            assert_eq!(older.try_resolve(&Spur::try_from_usize(1).unwrap()), None);
+        }
+
+        #[test]
+        #[cfg(feature = "serialize")]
+        fn diff_serialize() {
+            let mut rodeo = Rodeo::default();
+            rodeo.get_or_intern("a");
+            let mut older = rodeo.clone().into_resolver();
+            rodeo.get_or_intern("b");
+            let newer = rodeo.into_resolver();
+
+            let ser = serde_json::to_string(&newer.diff(&older)).unwrap();
+            assert_eq!(ser, r#"[1,["b"]]"#);
+
+            let diff: Diff = serde_json::from_str(&ser).unwrap();
+            older.apply_diff(&diff).unwrap();
+            assert_eq!(older, newer);
+        }
+
         #[test]
         fn try_resolve() {
             let mut rodeo = Rodeo::default();
@@ -402,6 +1443,26 @@ mod tests {
             let _ = rodeo.into_resolver();
         }
 
+        #[test]
+        fn memory_stats() {
+            let mut rodeo = Rodeo::default();
+            rodeo.get_or_intern("abc");
+            rodeo.get_or_intern("defgh");
+
+            let usage = rodeo.current_memory_usage();
+            let stats = rodeo.memory_stats();
+            let resolver = rodeo.into_resolver();
+
+            // The resolver drops the interner's map
+            assert!(resolver.current_memory_usage() < usage);
+            assert_eq!(resolver.arena_memory_usage(), stats.arena_bytes());
+            assert_eq!(resolver.memory_stats().string_bytes(), 8);
+            assert_eq!(
+                resolver.memory_stats().total_bytes(),
+                resolver.current_memory_usage(),
+            );
+        }
+
         #[test]
         fn iter() {
             let mut rodeo = Rodeo::default();
@@ -562,8 +1623,8 @@ mod tests {
     #[cfg(all(not(any(miri, feature = "no-std")), feature = "multi-threaded"))]
     mod multi_threaded {
         use crate::{Key, Spur, ThreadedRodeo};
-        use std::thread;
         use std::sync::Arc;
+        use std::thread;
 
         #[test]
         fn resolve() {
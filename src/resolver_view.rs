@@ -0,0 +1,201 @@
+use crate::keys::{Key, Spur};
+use core::{
+    fmt::{self, Debug},
+    marker::PhantomData,
+};
+
+/// A borrowed view of a [`Rodeo`]'s strings that only allows key to string resolution,
+/// created by [`Rodeo::as_resolver_view`]
+///
+/// The view is just a reference to the interner's strings, so it's cheap to make and to copy.
+/// It implements [`Resolver`] without carrying the interner's hasher type around, so helper
+/// functions can take `&impl Resolver` or a `ResolverView` no matter how the interner was
+/// built. Strings resolved through the view live as long as the borrow of the interner, not
+/// just as long as the view itself
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{Resolver, Rodeo, Spur};
+///
+/// fn describe(resolver: &impl Resolver, keys: &[Spur]) -> String {
+///     resolver.resolve_many(keys).join(" ")
+/// }
+///
+/// let mut rodeo = Rodeo::default();
+/// let keys = [rodeo.get_or_intern("hello"), rodeo.get_or_intern("world")];
+/// assert_eq!(describe(&rodeo.as_resolver_view(), &keys), "hello world");
+///
+/// // The interner can be used again once the view is gone
+/// let more = rodeo.get_or_intern("!");
+/// assert_eq!(rodeo.as_resolver_view().resolve(&more), "!");
+/// ```
+///
+/// [`Rodeo`]: crate::Rodeo
+/// [`Rodeo::as_resolver_view`]: crate::Rodeo::as_resolver_view
+/// [`Resolver`]: crate::Resolver
+pub struct ResolverView<'a, K = Spur> {
+    /// The interner's strings, where each string's index is the value of its key
+    strings: &'a [&'static str],
+    /// The type of the key
+    __key: PhantomData<K>,
+}
+
+impl<'a, K> ResolverView<'a, K> {
+    pub(crate) fn new(strings: &'a [&'static str]) -> Self {
+        Self {
+            strings,
+            __key: PhantomData,
+        }
+    }
+
+    /// Resolves a string by its key
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// let view = rodeo.as_resolver_view();
+    /// assert_eq!("Strings of things with wings and dings", view.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve(&self, key: &K) -> &'a str
+    where
+        K: Key,
+    {
+        self.try_resolve(key).expect("Key out of bounds")
+    }
+
+    /// Resolves a string by its key, returning `None` if it's out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    /// # use lasso::{Key, Spur};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// let view = rodeo.as_resolver_view();
+    /// assert_eq!(Some("Strings of things with wings and dings"), view.try_resolve(&key));
+    /// # let key = Spur::try_from_usize(1).unwrap();
+    /// assert_eq!(None, view.try_resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve(&self, key: &K) -> Option<&'a str>
+    where
+        K: Key,
+    {
+        self.strings.get(key.into_usize()).copied()
+    }
+
+    /// Resolves a string by its key without preforming bounds checks
+    ///
+    /// # Safety
+    ///
+    /// The key must be valid for the interner the view was made from
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub unsafe fn resolve_unchecked(&self, key: &K) -> &'a str
+    where
+        K: Key,
+    {
+        // Safety: The caller promises the key is in bounds
+        unsafe { self.strings.get_unchecked(key.into_usize()) }
+    }
+
+    /// Returns `true` if the given key exists in the view
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains_key(&self, key: &K) -> bool
+    where
+        K: Key,
+    {
+        key.into_usize() < self.strings.len()
+    }
+
+    /// Gets the number of strings in the view
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if there are no strings in the view
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+impl<K> Clone for ResolverView<'_, K> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K> Copy for ResolverView<'_, K> {}
+
+impl<K> Debug for ResolverView<'_, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResolverView")
+            .field("strings", &self.strings)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Key, Resolver, ResolverView, Rodeo, Spur};
+
+    #[test]
+    fn resolve() {
+        let mut rodeo = Rodeo::default();
+        let a = rodeo.get_or_intern("a");
+        let b = rodeo.get_or_intern_static("b");
+        let missing = Spur::try_from_usize(2).unwrap();
+
+        let view: ResolverView<'_> = rodeo.as_resolver_view();
+        let copied = view;
+        assert_eq!(view.len(), 2);
+        assert!(!view.is_empty());
+        assert_eq!(view.resolve(&a), "a");
+        assert_eq!(copied.try_resolve(&b), Some("b"));
+        assert_eq!(view.try_resolve(&missing), None);
+        assert!(view.contains_key(&b));
+        assert!(!view.contains_key(&missing));
+        assert_eq!(unsafe { view.resolve_unchecked(&a) }, "a");
+
+        // Strings outlive the view they were resolved through
+        let resolved = {
+            let view = rodeo.as_resolver_view();
+            view.resolve(&a)
+        };
+        assert_eq!(resolved, "a");
+
+        fn resolve_all(resolver: &impl Resolver<Spur>, keys: &[Spur]) -> usize {
+            keys.iter().map(|key| resolver.resolve(key).len()).sum()
+        }
+        assert_eq!(resolve_all(&view, &[a, b]), 2);
+        assert_eq!(Resolver::try_resolve(&view, &missing), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn resolve_missing() {
+        let rodeo: Rodeo = Rodeo::default();
+        rodeo
+            .as_resolver_view()
+            .resolve(&Spur::try_from_usize(0).unwrap());
+    }
+}
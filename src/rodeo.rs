@@ -1,22 +1,39 @@
+#[cfg(feature = "profile-interning")]
+use crate::profile::{CallSiteProfile, InternProfile};
+#[cfg(feature = "timestamps")]
+use crate::timestamps::{OlderThan, Timestamps};
 use crate::{
-    arenas::{AnyArena, Arena},
+    arenas::{AnyArena, Arena, ArenaMark, BucketAllocator},
     hasher::RandomState,
-    keys::{Key, Spur},
+    key_set::KeySet,
+    keys::{Key, SeededKeys, Spur},
     reader::RodeoReader,
     resolver::RodeoResolver,
-    util::{Iter, Strings},
-    Capacity, LassoError, LassoErrorKind, LassoResult, MemoryLimits,
+    resolver_view::ResolverView,
+    session::RodeoSession,
+    util::{self, grown_table_capacity, DedupStats, Iter, MemoryStats, MetricsSnapshot, Strings},
+    Capacity, DynamicState, EscapeStyle, FixedArena, HasherKind, LassoError, LassoErrorKind,
+    LassoResult, MemoryLimits, Reader,
 };
-use alloc::vec::Vec;
-use core::{cmp::max, hash::BuildHasher, iter::FromIterator, num::NonZeroUsize, ops::Index};
-use hashbrown::{
-    hash_map::{RawEntryMut, RawVacantEntryMut},
-    HashMap,
+use alloc::{borrow::Cow, string::String, vec::Vec};
+#[cfg(feature = "compact_str")]
+use compact_str::CompactString;
+use core::{
+    cmp::max,
+    ffi::{c_char, CStr},
+    fmt::{self, Debug},
+    hash::{BuildHasher, Hasher},
+    iter::FromIterator,
+    mem::{self, size_of},
+    num::NonZeroUsize,
+    ops::{Index, Range},
+    ptr::{self, NonNull},
+    slice,
 };
+use hashbrown::HashTable;
 
 compile! {
     if #[feature = "serialize"] {
-        use alloc::string::String;
         use serde::{
             de::{Deserialize, Deserializer},
             ser::{Serialize, Serializer},
@@ -24,8 +41,8 @@ compile! {
     }
 }
 
-/// The map we use to associate keys to strings by the string's hash
-type StringMap<K> = HashMap<K, (), ()>;
+/// The table we use to associate keys to strings by the string's hash
+pub(crate) type StringMap<K> = HashTable<K>;
 
 /// A string interner that caches strings quickly with a minimal memory footprint,
 /// returning a unique key to re-access it with `O(1)` times.
@@ -34,13 +51,12 @@ type StringMap<K> = HashMap<K, (), ()>;
 ///
 /// [`Spur`]: crate::Spur
 /// [`RandomState`]: https://doc.rust-lang.org/std/collections/hash_map/struct.RandomState.html
-#[derive(Debug)]
 pub struct Rodeo<K = Spur, S = RandomState> {
     /// Map that allows `str` -> `key` resolution
     ///
-    /// This must be a `HashMap` (for now) since `raw_api`s are only available for maps and not sets.
-    /// The value of the map is `()` since the key is symbolically hashed as the string it represents and
-    /// the hasher is also `()` so that we only store one hasher, the custom one contained in the `Rodeo` itself
+    /// This is a bare `HashTable` holding only keys, since each key is symbolically hashed as the
+    /// string it represents. The table doesn't have a hasher of its own so that we only store one
+    /// hasher, the custom one contained in the `Rodeo` itself
     ///
     /// The keys stored in this map are not hashed as keys, they're inserted
     /// with the hashes of the strings that they point to
@@ -58,6 +74,25 @@ pub struct Rodeo<K = Spur, S = RandomState> {
     pub(crate) strings: Vec<&'static str>,
     /// The arena that holds all allocated strings
     arena: Arena,
+    /// Keys whose strings weren't stored in the arena, like static strings and slices of
+    /// other interned strings, which must never be freed from the arena
+    borrowed: KeySet<K>,
+    /// How many interned strings were duplicates of already interned ones
+    stats: DedupStats,
+    /// How much every call site has interned
+    #[cfg(feature = "profile-interning")]
+    profile: InternProfile,
+    /// When every string was interned
+    #[cfg(feature = "timestamps")]
+    timestamps: Timestamps,
+    /// A reusable buffer that [`Rodeo::try_get_or_intern_chars`] collects strings into
+    scratch: String,
+    /// Map that allows case-insensitive `str` -> `key` resolution for the strings interned
+    /// with [`Rodeo::get_or_intern_preserving`]
+    ///
+    /// Keys are inserted with the hashes of the ASCII-lowercased forms of their strings,
+    /// only the first key of every lowercased form is kept
+    folded: StringMap<K>,
 }
 
 impl<K> Rodeo<K, RandomState>
@@ -88,6 +123,64 @@ where
         )
     }
 
+    /// Attempts to create a new Rodeo
+    ///
+    /// Returns an error instead of panicking if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, Spur};
+    ///
+    /// let mut rodeo: Rodeo<Spur> = Rodeo::try_new().unwrap();
+    /// let hello = rodeo.get_or_intern("Hello, ");
+    /// let world = rodeo.get_or_intern("World!");
+    ///
+    /// assert_eq!("Hello, ", rodeo.resolve(&hello));
+    /// assert_eq!("World!", rodeo.resolve(&world));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_new() -> LassoResult<Self> {
+        Self::try_with_capacity_memory_limits_and_hasher(
+            Capacity::default(),
+            MemoryLimits::default(),
+            RandomState::new(),
+        )
+    }
+
+    /// Create a new Rodeo with a table of static strings interned up front, returning
+    /// the interner along with the [`SeededKeys`] of the table
+    ///
+    /// Strings are interned in order, so the string at index `i` of the table always has
+    /// the key `K::try_from_usize(i)`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the table contains duplicate strings or if the key's `try_from_usize`
+    /// function fails for any of its strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, Spur};
+    ///
+    /// let (mut rodeo, keywords) = Rodeo::<Spur>::with_seed_strings(&["if", "else", "while"]);
+    ///
+    /// let key = rodeo.get_or_intern("while");
+    /// assert_eq!(keywords.get(2), Some(key));
+    /// assert_eq!("else", rodeo.resolve(&keywords.get(1).unwrap()));
+    /// ```
+    ///
+    /// [`SeededKeys`]: crate::SeededKeys
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_seed_strings(strings: &[&'static str]) -> (Self, SeededKeys<K>) {
+        let mut rodeo = Self::with_capacity(Capacity::for_strings(strings.len()));
+        let seeds = rodeo.intern_seed_strings(strings);
+
+        (rodeo, seeds)
+    }
+
     /// Create a new Rodeo with the specified capacity. The interner will be able to hold `capacity`
     /// strings without reallocating
     ///
@@ -111,6 +204,28 @@ where
         )
     }
 
+    /// Attempts to create a new Rodeo with the specified capacity
+    ///
+    /// Returns an error instead of panicking if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, Capacity, Spur};
+    ///
+    /// let rodeo: Rodeo<Spur> = Rodeo::try_with_capacity(Capacity::for_strings(10)).unwrap();
+    /// ```
+    ///
+    /// [`Capacity`]: crate::Capacity
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_with_capacity(capacity: Capacity) -> LassoResult<Self> {
+        Self::try_with_capacity_memory_limits_and_hasher(
+            capacity,
+            MemoryLimits::default(),
+            RandomState::new(),
+        )
+    }
+
     /// Create a new Rodeo with the specified memory limits. The interner will be able to hold `max_memory_usage`
     /// bytes of interned strings until it will start returning `None` from `try_get_or_intern` or panicking from
     /// `get_or_intern`.
@@ -138,6 +253,28 @@ where
         )
     }
 
+    /// Attempts to create a new Rodeo with the specified memory limits
+    ///
+    /// Returns an error instead of panicking if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, MemoryLimits, Spur};
+    ///
+    /// let rodeo: Rodeo<Spur> = Rodeo::try_with_memory_limits(MemoryLimits::for_memory_usage(4096)).unwrap();
+    /// ```
+    ///
+    /// [`MemoryLimits`]: crate::MemoryLimits
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_with_memory_limits(memory_limits: MemoryLimits) -> LassoResult<Self> {
+        Self::try_with_capacity_memory_limits_and_hasher(
+            Capacity::default(),
+            memory_limits,
+            RandomState::new(),
+        )
+    }
+
     /// Create a new Rodeo with the specified capacity and memory limits. The interner will be able to hold `max_memory_usage`
     /// bytes of interned strings until it will start returning `None` from `try_get_or_intern` or panicking from
     /// `get_or_intern`.
@@ -164,6 +301,87 @@ where
     ) -> Self {
         Self::with_capacity_memory_limits_and_hasher(capacity, memory_limits, RandomState::new())
     }
+
+    /// Attempts to create a new Rodeo with the specified capacity and memory limits
+    ///
+    /// Returns an error instead of panicking if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, Capacity, MemoryLimits, Spur};
+    ///
+    /// let rodeo: Rodeo<Spur> = Rodeo::try_with_capacity_and_memory_limits(
+    ///     Capacity::for_strings(10),
+    ///     MemoryLimits::for_memory_usage(4096),
+    /// )
+    /// .unwrap();
+    /// ```
+    ///
+    /// [`Capacity`]: crate::Capacity
+    /// [`MemoryLimits`]: crate::MemoryLimits
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_with_capacity_and_memory_limits(
+        capacity: Capacity,
+        memory_limits: MemoryLimits,
+    ) -> LassoResult<Self> {
+        Self::try_with_capacity_memory_limits_and_hasher(
+            capacity,
+            memory_limits,
+            RandomState::new(),
+        )
+    }
+
+    /// Create a new Rodeo that preallocates all of its memory up front and never allocates
+    /// again, see [`FixedArena`] for more information
+    ///
+    /// Once either the arena or the room for strings is used up, interning new strings fails
+    /// with [`LassoErrorKind::MemoryLimitReached`] while already interned strings can still be
+    /// looked up
+    ///
+    /// # Panics
+    ///
+    /// Panics if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{FixedArena, Rodeo};
+    ///
+    /// let mut rodeo: Rodeo = Rodeo::with_fixed_arena(FixedArena::<1024>::new(16));
+    /// let memory_usage = rodeo.current_memory_usage();
+    ///
+    /// for i in 0..16 {
+    ///     rodeo.get_or_intern(i.to_string());
+    /// }
+    ///
+    /// assert!(rodeo.try_get_or_intern("16").is_err());
+    /// assert_eq!(rodeo.current_memory_usage(), memory_usage);
+    /// ```
+    ///
+    /// [`FixedArena`]: crate::FixedArena
+    /// [`LassoErrorKind::MemoryLimitReached`]: crate::LassoErrorKind::MemoryLimitReached
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_fixed_arena<const BYTES: usize>(arena: FixedArena<BYTES>) -> Self {
+        Self::with_fixed_arena_and_hasher(arena, RandomState::new())
+    }
+
+    /// Attempts to create a new Rodeo that preallocates all of its memory up front
+    ///
+    /// Returns an error instead of panicking if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{FixedArena, Rodeo};
+    ///
+    /// let rodeo: Rodeo = Rodeo::try_with_fixed_arena(FixedArena::<1024>::new(16)).unwrap();
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_with_fixed_arena<const BYTES: usize>(arena: FixedArena<BYTES>) -> LassoResult<Self> {
+        Self::try_with_fixed_arena_and_hasher(arena, RandomState::new())
+    }
 }
 
 impl<K, S> Rodeo<K, S>
@@ -191,6 +409,28 @@ where
         )
     }
 
+    /// Attempts to create an empty Rodeo which will use the given hasher for its internal hashmap
+    ///
+    /// Returns an error instead of panicking if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Spur, Rodeo};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: Rodeo<Spur, RandomState> = Rodeo::try_with_hasher(RandomState::new()).unwrap();
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_with_hasher(hash_builder: S) -> LassoResult<Self> {
+        Self::try_with_capacity_memory_limits_and_hasher(
+            Capacity::default(),
+            MemoryLimits::default(),
+            hash_builder,
+        )
+    }
+
     /// Creates a new Rodeo with the specified capacity that will use the given hasher for its internal hashmap
     ///
     /// See [`Capacity`] for more information
@@ -214,6 +454,29 @@ where
         )
     }
 
+    /// Attempts to create a new Rodeo with the specified capacity that will use the given hasher for its internal hashmap
+    ///
+    /// Returns an error instead of panicking if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Spur, Capacity, Rodeo};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: Rodeo<Spur, RandomState> = Rodeo::try_with_capacity_and_hasher(Capacity::for_strings(10), RandomState::new()).unwrap();
+    /// ```
+    ///
+    /// [`Capacity`]: crate::Capacity
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_with_capacity_and_hasher(capacity: Capacity, hash_builder: S) -> LassoResult<Self> {
+        Self::try_with_capacity_memory_limits_and_hasher(
+            capacity,
+            MemoryLimits::default(),
+            hash_builder,
+        )
+    }
+
     /// Creates a new Rodeo with the specified capacity and memory limits that will use the given hasher for its internal hashmap
     ///
     /// See [`Capacity`] and [`MemoryLimits`] for more information
@@ -239,16 +502,170 @@ where
         memory_limits: MemoryLimits,
         hash_builder: S,
     ) -> Self {
+        Self::try_with_capacity_memory_limits_and_hasher(capacity, memory_limits, hash_builder)
+            .expect("failed to allocate memory for interner")
+    }
+
+    /// Attempts to create a new Rodeo with the specified capacity and memory limits that will use the given hasher for its internal hashmap
+    ///
+    /// Returns an error instead of panicking if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Spur, Capacity, MemoryLimits, Rodeo};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: Rodeo<Spur, RandomState> = Rodeo::try_with_capacity_memory_limits_and_hasher(
+    ///     Capacity::for_strings(10),
+    ///     MemoryLimits::for_memory_usage(4096),
+    ///     RandomState::new(),
+    /// )
+    /// .unwrap();
+    /// ```
+    ///
+    /// [`Capacity`]: crate::Capacity
+    /// [`MemoryLimits`]: crate::MemoryLimits
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_with_capacity_memory_limits_and_hasher(
+        capacity: Capacity,
+        memory_limits: MemoryLimits,
+        hash_builder: S,
+    ) -> LassoResult<Self> {
+        Self::try_with_bucket_allocator(
+            capacity,
+            memory_limits,
+            hash_builder,
+            BucketAllocator::Global,
+        )
+    }
+
+    /// Create a new Rodeo that preallocates all of its memory up front and uses the given
+    /// hasher for its internal hashmap, see [`Rodeo::with_fixed_arena`] for more information
+    ///
+    /// # Panics
+    ///
+    /// Panics if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{FixedArena, Rodeo, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: Rodeo<Spur, RandomState> =
+    ///     Rodeo::with_fixed_arena_and_hasher(FixedArena::<1024>::new(16), RandomState::new());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_fixed_arena_and_hasher<const BYTES: usize>(
+        arena: FixedArena<BYTES>,
+        hash_builder: S,
+    ) -> Self {
+        Self::try_with_fixed_arena_and_hasher(arena, hash_builder)
+            .expect("failed to allocate memory for interner")
+    }
+
+    /// Attempts to create a new Rodeo that preallocates all of its memory up front and uses
+    /// the given hasher for its internal hashmap
+    ///
+    /// Returns an error instead of panicking if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{FixedArena, Rodeo, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: Rodeo<Spur, RandomState> =
+    ///     Rodeo::try_with_fixed_arena_and_hasher(FixedArena::<1024>::new(16), RandomState::new())
+    ///         .unwrap();
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_with_fixed_arena_and_hasher<const BYTES: usize>(
+        arena: FixedArena<BYTES>,
+        hash_builder: S,
+    ) -> LassoResult<Self> {
+        let mut rodeo = Self::try_with_bucket_allocator(
+            Capacity::new(arena.strings, FixedArena::<BYTES>::BYTES),
+            MemoryLimits::for_memory_usage(BYTES),
+            hash_builder,
+            BucketAllocator::Global,
+        )?;
+        rodeo.arena.fixed = true;
+
+        Ok(rodeo)
+    }
+
+    /// Creates a new Rodeo whose arena allocates all of its memory from `allocator`
+    pub(crate) fn try_with_bucket_allocator(
+        capacity: Capacity,
+        memory_limits: MemoryLimits,
+        hash_builder: S,
+        allocator: BucketAllocator,
+    ) -> LassoResult<Self> {
         let Capacity { strings, bytes } = capacity;
-        let MemoryLimits { max_memory_usage } = memory_limits;
+        let MemoryLimits {
+            max_memory_usage,
+            total_budget,
+        } = memory_limits;
+
+        let mut map = StringMap::new();
+        map.try_reserve(strings, |_| {
+            unreachable!("the map should be empty while reserving its initial capacity");
+        })
+        .map_err(|_| LassoError::new(LassoErrorKind::FailedAllocation))?;
+
+        let mut string_vec = Vec::new();
+        string_vec
+            .try_reserve_exact(strings)
+            .map_err(|_| LassoError::new(LassoErrorKind::FailedAllocation))?;
+
+        let mut arena = Arena::with_allocator(bytes, max_memory_usage, allocator)?;
+        arena.total_budget = total_budget;
 
-        Self {
-            map: HashMap::with_capacity_and_hasher(strings, ()),
+        Ok(Self {
+            map,
             hasher: hash_builder,
-            strings: Vec::with_capacity(strings),
-            arena: Arena::new(bytes, max_memory_usage)
-                .expect("failed to allocate memory for interner"),
-        }
+            strings: string_vec,
+            arena,
+            borrowed: KeySet::new(),
+            stats: DedupStats::default(),
+            #[cfg(feature = "profile-interning")]
+            profile: InternProfile::default(),
+            #[cfg(feature = "timestamps")]
+            timestamps: Timestamps::new(),
+            scratch: String::new(),
+            folded: StringMap::new(),
+        })
+    }
+
+    /// Sets the clock that intern times are recorded with
+    #[cfg(feature = "timestamps")]
+    pub(crate) fn set_clock(&mut self, clock: fn() -> u64) {
+        self.timestamps.set_clock(clock);
+    }
+
+    /// Sets whether owned strings too large to share an arena bucket are adopted
+    /// instead of copied
+    pub(crate) fn set_adopt_large_strings(&mut self, adopt_large_strings: bool) {
+        self.arena.adopt_large_strings = adopt_large_strings;
+    }
+
+    /// Sets the length above which strings are given an arena allocation of their own
+    pub(crate) fn set_spill_threshold(&mut self, spill_threshold: usize) {
+        self.arena.spill_threshold = spill_threshold;
+    }
+
+    /// Sets whether every stored string is followed by a NUL byte
+    pub(crate) fn set_nul_terminated(&mut self, nul_terminated: bool) {
+        self.arena.nul_terminated = nul_terminated;
+    }
+
+    /// Sets whether [`Rodeo::clear`] also shrinks the interner
+    pub(crate) fn set_shrink_on_clear(&mut self, shrink_on_clear: bool) {
+        self.arena.shrink_on_clear = shrink_on_clear;
     }
 
     /// Get the key for a string, interning it if it does not yet exist
@@ -277,6 +694,7 @@ where
     ///
     /// [`Spur`]: crate::Spur
     #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
     pub fn get_or_intern<T>(&mut self, val: T) -> K
     where
         T: AsRef<str>,
@@ -304,58 +722,953 @@ where
     /// ```
     ///
     #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
     pub fn try_get_or_intern<T>(&mut self, val: T) -> LassoResult<K>
     where
         T: AsRef<str>,
     {
-        let Self {
-            map,
-            hasher,
-            strings,
-            arena,
-        } = self;
-
-        let string_slice: &str = val.as_ref();
-
-        // Make a hash of the requested string
-        let hash = hasher.hash_one(string_slice);
-
-        // Get the map's entry that the string should occupy
-        let key = match get_string_entry_mut(map, strings, hash, string_slice) {
-            // The string already exists, so return its key
-            RawEntryMut::Occupied(entry) => *entry.into_key(),
-
-            // The string does not yet exist, so insert it and create its key
-            RawEntryMut::Vacant(entry) => {
-                // Create the key from the vec's index that the string will hold
-                let key = K::try_from_usize(strings.len())
-                    .ok_or_else(|| LassoError::new(LassoErrorKind::KeySpaceExhaustion))?;
-
-                // Allocate the string in the arena
-                // Safety: The returned strings will be dropped before the arena that created them is
-                let allocated = unsafe { arena.store_str(string_slice)? };
-
-                // Push the allocated string to the strings vector
-                strings.push(allocated);
+        // Safety: The returned strings will be dropped before the arena that created them is
+        self.try_intern(val, |arena, val| unsafe { arena.store_str(val.as_ref()) })
+    }
 
-                // Insert the key with the hash of the string that it points to, reusing the hash we made earlier
-                insert_string(entry, strings, hasher, hash, key);
+    /// Get the key for an owned string, interning it if it does not yet exist
+    ///
+    /// Interners built with [`RodeoBuilder::adopt_large_strings()`] take over the buffer
+    /// of strings that are too large to share an arena bucket instead of copying them
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique strings were interned)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    ///
+    /// let key = rodeo.get_or_intern_owned(String::from("Strings of things with wings and dings"));
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    /// [`RodeoBuilder::adopt_large_strings()`]: crate::RodeoBuilder::adopt_large_strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn get_or_intern_owned(&mut self, string: String) -> K {
+        self.try_get_or_intern_owned(string)
+            .expect("Failed to get or intern string")
+    }
+
+    /// Get the key for an owned string, interning it if it does not yet exist
+    ///
+    /// Interners built with [`RodeoBuilder::adopt_large_strings()`] take over the buffer
+    /// of strings that are too large to share an arena bucket instead of copying them
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    ///
+    /// let key = rodeo.try_get_or_intern_owned(String::from("Strings of things with wings and dings")).unwrap();
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    /// [`RodeoBuilder::adopt_large_strings()`]: crate::RodeoBuilder::adopt_large_strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn try_get_or_intern_owned(&mut self, string: String) -> LassoResult<K> {
+        // Safety: The returned strings will be dropped before the arena that created them is
+        self.try_intern(string, |arena, string| unsafe {
+            arena.store_string(string)
+        })
+    }
+
+    /// Get the key for a string made from a sequence of chars, interning it if it does not
+    /// yet exist
+    ///
+    /// The chars are encoded into a buffer that's reused between calls, so no new string
+    /// is allocated unless the string has to be stored
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique strings were interned)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    ///
+    /// let key = rodeo.get_or_intern_chars("Strings of things".chars().rev());
+    /// assert_eq!("sgniht fo sgnirtS", rodeo.resolve(&key));
+    /// assert_eq!(Some(key), rodeo.get("sgniht fo sgnirtS"));
+    /// ```
+    ///
+    /// [`Spur`]: crate::Spur
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn get_or_intern_chars<I>(&mut self, chars: I) -> K
+    where
+        I: IntoIterator<Item = char>,
+    {
+        self.try_get_or_intern_chars(chars)
+            .expect("Failed to get or intern string")
+    }
+
+    /// Get the key for a string made from a sequence of chars, interning it if it does not
+    /// yet exist
+    ///
+    /// The chars are encoded into a buffer that's reused between calls, so no new string
+    /// is allocated unless the string has to be stored
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    ///
+    /// let key = rodeo.try_get_or_intern_chars(['a', 'b', 'c']).unwrap();
+    /// assert_eq!("abc", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn try_get_or_intern_chars<I>(&mut self, chars: I) -> LassoResult<K>
+    where
+        I: IntoIterator<Item = char>,
+    {
+        // Take the buffer so it can be borrowed while the rest of the interner is mutated
+        let mut scratch = mem::take(&mut self.scratch);
+        scratch.clear();
+        scratch.extend(chars);
+
+        let key = self.try_get_or_intern(scratch.as_str());
+        self.scratch = scratch;
+
+        key
+    }
+
+    /// Get the key for a string given as bytes, interning it if it does not yet exist
+    ///
+    /// The bytes are validated as UTF-8 before the string is looked up. Bytes that aren't
+    /// valid UTF-8 return an error of kind [`LassoErrorKind::InvalidUtf8`] and are never
+    /// interned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{LassoErrorKind, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    ///
+    /// let key = rodeo.get_or_intern_utf8(b"Hello, world!").unwrap();
+    /// assert_eq!("Hello, world!", rodeo.resolve(&key));
+    ///
+    /// let err = rodeo.get_or_intern_utf8(b"Hello, \xFFworld!").unwrap_err();
+    /// assert_eq!(err.kind(), LassoErrorKind::InvalidUtf8);
+    /// assert_eq!(err.valid_up_to(), Some(7));
+    /// assert_eq!(rodeo.len(), 1);
+    /// ```
+    ///
+    /// [`LassoErrorKind::InvalidUtf8`]: crate::LassoErrorKind::InvalidUtf8
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn get_or_intern_utf8(&mut self, bytes: &[u8]) -> LassoResult<K> {
+        let string = core::str::from_utf8(bytes)
+            .map_err(|err| LassoError::invalid_utf8(err.valid_up_to(), bytes.len()))?;
+
+        self.try_get_or_intern(string)
+    }
+
+    /// Get the key for the unescaped form of a string, interning it if it does not yet exist
+    ///
+    /// `raw` is the contents of a quoted token without its quotes, whose escapes are written
+    /// in the given [`EscapeStyle`]. It's unescaped into a buffer that's reused between calls,
+    /// so no new string is allocated unless the unescaped string has to be stored. Strings
+    /// with an invalid escape return an error of kind [`LassoErrorKind::InvalidEscape`] holding
+    /// the escape's byte offset in [`LassoError::valid_up_to`] and are never interned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{EscapeStyle, LassoErrorKind, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    ///
+    /// let key = rodeo.get_or_intern_unescaped(r"foo\nbar", EscapeStyle::Rust).unwrap();
+    /// assert_eq!("foo\nbar", rodeo.resolve(&key));
+    ///
+    /// let key = rodeo.get_or_intern_unescaped("it''s", EscapeStyle::Doubled('\'')).unwrap();
+    /// assert_eq!("it's", rodeo.resolve(&key));
+    ///
+    /// let err = rodeo.get_or_intern_unescaped(r"foo\qbar", EscapeStyle::Json).unwrap_err();
+    /// assert_eq!(err.kind(), LassoErrorKind::InvalidEscape);
+    /// assert_eq!(err.valid_up_to(), Some(3));
+    /// assert_eq!(rodeo.len(), 2);
+    /// ```
+    ///
+    /// [`EscapeStyle`]: crate::EscapeStyle
+    /// [`LassoErrorKind::InvalidEscape`]: crate::LassoErrorKind::InvalidEscape
+    /// [`LassoError::valid_up_to`]: crate::LassoError::valid_up_to
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn get_or_intern_unescaped(&mut self, raw: &str, style: EscapeStyle) -> LassoResult<K> {
+        // Take the buffer so it can be borrowed while the rest of the interner is mutated
+        let mut scratch = mem::take(&mut self.scratch);
+        scratch.clear();
+
+        let key = match style.unescape_into(raw, &mut scratch) {
+            Ok(()) => self.try_get_or_intern(scratch.as_str()),
+            Err(valid_up_to) => Err(LassoError::invalid_escape(valid_up_to, raw.len())),
+        };
+        self.scratch = scratch;
+
+        key
+    }
+
+    /// Get the key for a string, interning it if it does not yet exist, and make it
+    /// findable by [`Rodeo::get_ignore_ascii_case`]
+    ///
+    /// The string is stored exactly as given, only the lookup ignores ASCII case. If
+    /// several strings differ only in their ASCII case, the first one interned with this
+    /// method is the one that's found
+    ///
+    /// The case-insensitive index is separate from the interner's preallocated memory, so it
+    /// grows as needed even in interners created with [`Rodeo::with_fixed_arena`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique strings were interned)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern_preserving("Makefile");
+    ///
+    /// assert_eq!(rodeo.resolve(&key), "Makefile");
+    /// assert_eq!(rodeo.get_ignore_ascii_case("MAKEFILE"), Some(key));
+    /// assert_eq!(rodeo.get("makefile"), None);
+    /// ```
+    ///
+    /// [`Spur`]: crate::Spur
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn get_or_intern_preserving<T>(&mut self, val: T) -> K
+    where
+        T: AsRef<str>,
+    {
+        self.try_get_or_intern_preserving(val)
+            .expect("Failed to get or intern string")
+    }
+
+    /// Get the key for a string, interning it if it does not yet exist, and make it
+    /// findable by [`Rodeo::get_ignore_ascii_case`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.try_get_or_intern_preserving("README.md").unwrap();
+    ///
+    /// // Interning a different casing gives a new key, but lookups still find the first one
+    /// let other = rodeo.try_get_or_intern_preserving("readme.md").unwrap();
+    /// assert_ne!(key, other);
+    /// assert_eq!(rodeo.get_ignore_ascii_case("readme.md"), Some(key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn try_get_or_intern_preserving<T>(&mut self, val: T) -> LassoResult<K>
+    where
+        T: AsRef<str>,
+    {
+        let string = val.as_ref();
+        let key = self.try_get_or_intern(string)?;
+
+        let Self {
+            hasher,
+            strings,
+            folded,
+            ..
+        } = self;
+
+        let hash = hash_ascii_folded(hasher, string);
+        let existing = folded.find(hash, |key| {
+            // Safety: The index given by `key` will be in bounds of the strings vector
+            let key_string: &str = unsafe { index_unchecked!(strings, key.into_usize()) };
+
+            string.eq_ignore_ascii_case(key_string)
+        });
+
+        // Only the first casing of a string is kept
+        if existing.is_none() {
+            folded.insert_unique(hash, key, |key| {
+                // Safety: The index given by `key` will be in bounds of the strings vector
+                let key_string: &str = unsafe { index_unchecked!(strings, key.into_usize()) };
+
+                hash_ascii_folded(hasher, key_string)
+            });
+        }
+
+        Ok(key)
+    }
+
+    /// Interns a string that isn't in the interner yet, skipping the lookup that would find
+    /// an existing copy of it
+    ///
+    /// This saves a hash map probe per string when loading strings that are already known to
+    /// be unique, such as a deduplicated snapshot. In debug builds the string is still looked up
+    /// and a duplicate panics
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique strings were interned)
+    ///
+    /// # Safety
+    ///
+    /// The string must not already be interned. Interning it again gives the same string a
+    /// second key, breaking the interner's guarantee that every string has exactly one key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    ///
+    /// // Safety: The strings are unique and the interner is empty
+    /// let keys = ["apple", "banana"].map(|string| unsafe { rodeo.push_unchecked(string) });
+    /// assert_eq!(rodeo.resolve(&keys[1]), "banana");
+    /// assert_eq!(rodeo.get("apple"), Some(keys[0]));
+    /// ```
+    ///
+    /// [`Spur`]: crate::Spur
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub unsafe fn push_unchecked(&mut self, string: &str) -> K {
+        // Safety: The caller promises that the string isn't interned yet
+        unsafe { self.try_push_unchecked(string) }.expect("Failed to intern string")
+    }
+
+    /// Interns a string that isn't in the interner yet, skipping the lookup that would find
+    /// an existing copy of it
+    ///
+    /// In debug builds the string is still looked up and a duplicate panics
+    ///
+    /// # Safety
+    ///
+    /// The string must not already be interned. Interning it again gives the same string a
+    /// second key, breaking the interner's guarantee that every string has exactly one key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    ///
+    /// // Safety: The interner is empty, so the string can't be interned yet
+    /// let key = unsafe { rodeo.try_push_unchecked("Strings of things with wings and dings") }.unwrap();
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub unsafe fn try_push_unchecked(&mut self, string: &str) -> LassoResult<K> {
+        #[cfg(feature = "profile-interning")]
+        self.profile
+            .record(core::panic::Location::caller(), string.len());
+
+        let hash = self.hasher.hash_one(string);
+        debug_assert!(
+            find_string(&self.map, &self.strings, hash, string).is_none(),
+            "pushed a string that was already interned",
+        );
+
+        // Safety: The returned strings will be dropped before the arena that created them is
+        self.try_insert_new(string, hash, |arena, string| unsafe {
+            arena.store_str(string)
+        })
+    }
+
+    /// Get the key for a string, storing it with `store` if it does not yet exist
+    #[inline]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    fn try_intern<T, F>(&mut self, val: T, store: F) -> LassoResult<K>
+    where
+        T: AsRef<str>,
+        F: FnOnce(&mut Arena, T) -> LassoResult<&'static str>,
+    {
+        #[cfg(feature = "profile-interning")]
+        self.profile
+            .record(core::panic::Location::caller(), val.as_ref().len());
+
+        let string_slice: &str = val.as_ref();
+
+        // Make a hash of the requested string
+        let hash = self.hasher.hash_one(string_slice);
+
+        // Look for the string within the map, returning its key if it already exists
+        if let Some(key) = find_string(&self.map, &self.strings, hash, string_slice) {
+            self.stats.record_hit(string_slice.len());
+            return Ok(key);
+        }
+
+        // The string does not yet exist, so insert it and create its key
+        self.try_insert_new(val, hash, store)
+    }
+
+    /// Stores a string that isn't in the interner yet with `store` and gives it the next key,
+    /// using the given hash of the string
+    #[inline]
+    fn try_insert_new<T, F>(&mut self, val: T, hash: u64, store: F) -> LassoResult<K>
+    where
+        T: AsRef<str>,
+        F: FnOnce(&mut Arena, T) -> LassoResult<&'static str>,
+    {
+        // Leave room for the map and strings vec to grow if they count towards the memory limit
+        if self.arena.total_budget {
+            self.arena.reserved_memory = self.overhead_memory_usage(true);
+        }
+
+        let Self {
+            map,
+            hasher,
+            strings,
+            arena,
+            stats,
+            #[cfg(feature = "timestamps")]
+            timestamps,
+            ..
+        } = self;
+
+        let len = val.as_ref().len();
+
+        // Create the key from the vec's index that the string will hold
+        let key = K::try_from_usize(strings.len()).ok_or_else(|| {
+            LassoError::new(LassoErrorKind::KeySpaceExhaustion).with_string_len(len)
+        })?;
+
+        arena
+            .check_reserved_memory(len)
+            .map_err(|err| err.with_string_len(len))?;
+
+        // Fixed arenas preallocate room for all of their strings and never grow it
+        if arena.fixed && strings.len() == strings.capacity() {
+            return Err(LassoError::new(LassoErrorKind::MemoryLimitReached).with_string_len(len));
+        }
+
+        // Allocate the string in the arena
+        let allocated = store(arena, val).map_err(|err| err.with_string_len(len))?;
+
+        // Push the allocated string to the strings vector
+        strings.push(allocated);
+        #[cfg(feature = "timestamps")]
+        timestamps.record(key.into_usize());
+
+        // Insert the key with the hash of the string that it points to, reusing the hash we made earlier
+        insert_string(map, strings, hasher, hash, key);
+        stats.record_miss(len);
+
+        Ok(key)
+    }
+
+    /// Starts a session that interns a batch of strings which can be undone as a whole
+    ///
+    /// Every string the session interns is removed again and its memory freed when the
+    /// session is rolled back or dropped, unless [`RodeoSession::commit`] is called first.
+    /// Strings that were already interned before the session started are never touched
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let existing = rodeo.get_or_intern("existing");
+    ///
+    /// let mut session = rodeo.session();
+    /// assert_eq!(session.get_or_intern("existing"), existing);
+    /// session.get_or_intern("temporary");
+    /// session.rollback();
+    ///
+    /// assert_eq!(rodeo.len(), 1);
+    /// assert_eq!(rodeo.get("temporary"), None);
+    ///
+    /// let mut session = rodeo.session();
+    /// let kept = session.get_or_intern("kept");
+    /// session.commit();
+    ///
+    /// assert_eq!(rodeo.resolve(&kept), "kept");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn session(&mut self) -> RodeoSession<'_, K, S> {
+        RodeoSession::new(self)
+    }
+
+    /// Records the interner's current state so that every string interned after it can be
+    /// removed with [`Rodeo::rollback_to`]
+    pub(crate) fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            len: self.strings.len(),
+            arena: self.arena.mark(),
+            stats: self.stats,
+        }
+    }
+
+    /// Removes every string interned since `checkpoint` was made
+    ///
+    /// # Safety
+    ///
+    /// `checkpoint` must have been made by the current interner, which can't have been
+    /// cleared since, and no references may remain to strings interned after it was made
+    ///
+    pub(crate) unsafe fn rollback_to(&mut self, checkpoint: Checkpoint) {
+        self.unmap_from(checkpoint.len);
+        for index in checkpoint.len..self.strings.len() {
+            if let Some(key) = K::try_from_usize(index) {
+                self.borrowed.remove(key);
+            }
+        }
+        self.strings.truncate(checkpoint.len);
+        #[cfg(feature = "timestamps")]
+        self.timestamps.truncate(checkpoint.len);
+
+        // Safety: The strings interned since the checkpoint were all just removed, and the
+        //         caller promises that nothing else references them
+        unsafe { self.arena.rewind(checkpoint.arena) };
+        self.stats = checkpoint.stats;
+    }
+
+    /// Removes every string whose key is `len` or higher, keeping the first `len` strings
+    ///
+    /// Keys are given out in order, so these are the most recently interned strings and their
+    /// keys are given out again to the next strings interned. The arena memory of the removed
+    /// strings is reused if they were the last strings stored in it. Nothing happens if the
+    /// interner holds `len` strings or fewer
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let keep = rodeo.get_or_intern("keep");
+    /// let len = rodeo.len();
+    ///
+    /// let speculative = rodeo.get_or_intern("speculative");
+    /// rodeo.truncate(len);
+    ///
+    /// assert_eq!(rodeo.get("speculative"), None);
+    /// assert_eq!(rodeo.resolve(&keep), "keep");
+    ///
+    /// // The removed key is given out again
+    /// assert_eq!(rodeo.get_or_intern("other"), speculative);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.strings.len() {
+            return;
+        }
+
+        self.unmap_from(len);
+
+        // Strings are released newest first, so a run of them stored one after another at the
+        // end of the arena is freed as a whole
+        for (index, &string) in self.strings.iter().enumerate().skip(len).rev() {
+            // Strings the arena didn't store may point into the bytes of a string that's
+            // still interned, so only the arena's own allocations are released
+            if K::try_from_usize(index).is_some_and(|key| self.borrowed.remove(key)) {
+                continue;
+            }
+
+            // Safety: The string's key was just removed, and any references to the string
+            //         would borrow the interner
+            unsafe { self.arena.release_last(string) };
+        }
+        self.strings.truncate(len);
+        #[cfg(feature = "timestamps")]
+        self.timestamps.truncate(len);
+    }
+
+    /// Shrinks the interner's maps and strings vec to fit the strings it holds and frees
+    /// every arena bucket that no strings are stored in
+    ///
+    /// Interners keep the capacity they grew to even after being cleared or truncated, so
+    /// this lets long-running programs give memory back after a burst of interning. The
+    /// only bucket of a fixed arena is never freed. Interners built with
+    /// [`RodeoBuilder::shrink_on_clear()`] do this every time they're cleared
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// for i in 0..1000 {
+    ///     rodeo.get_or_intern(i.to_string());
+    /// }
+    /// let peak = rodeo.current_memory_usage();
+    ///
+    /// rodeo.clear();
+    /// assert_eq!(rodeo.current_memory_usage(), peak);
+    ///
+    /// rodeo.shrink_to_fit();
+    /// assert_eq!(rodeo.current_memory_usage(), 0);
+    /// assert_eq!(rodeo.capacity(), 0);
+    /// ```
+    ///
+    /// [`RodeoBuilder::shrink_on_clear()`]: crate::RodeoBuilder::shrink_on_clear
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn shrink_to_fit(&mut self) {
+        let Self {
+            map,
+            hasher,
+            strings,
+            folded,
+            arena,
+            scratch,
+            ..
+        } = self;
+
+        map.shrink_to_fit(|key| {
+            // Safety: The index given by `key` will be in bounds of the strings vector
+            let key_string: &str = unsafe { index_unchecked!(strings, key.into_usize()) };
+
+            hasher.hash_one(key_string)
+        });
+        folded.shrink_to_fit(|key| {
+            // Safety: The index given by `key` will be in bounds of the strings vector
+            let key_string: &str = unsafe { index_unchecked!(strings, key.into_usize()) };
+
+            hash_ascii_folded(hasher, key_string)
+        });
+
+        strings.shrink_to_fit();
+        scratch.shrink_to_fit();
+        #[cfg(feature = "timestamps")]
+        self.timestamps.shrink_to_fit();
+        arena.release_empty_buckets();
+    }
+
+    /// Interns strings that were already given their keys, such as ones loaded from another
+    /// system that hands out keys the same way
+    ///
+    /// The pairs may come in any order. Once sorted, their keys must continue on from the
+    /// interner's current keys without skipping or repeating any, and each string must not
+    /// already have a different key. If a pair doesn't line up an [`InvalidKey`] error holding
+    /// the offending key is returned and none of the pairs are interned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Key, Rodeo, Spur};
+    ///
+    /// let key = |index| Spur::try_from_usize(index).unwrap();
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo
+    ///     .extend_with_keys([(key(1), "world"), (key(0), "hello")])
+    ///     .unwrap();
+    /// assert_eq!(rodeo.get("hello"), Some(key(0)));
+    /// assert_eq!(rodeo.resolve(&key(1)), "world");
+    ///
+    /// // Key 2 was skipped, so nothing is interned
+    /// let err = rodeo.extend_with_keys([(key(3), "!")]).unwrap_err();
+    /// assert_eq!(err.key(), Some(2));
+    /// assert_eq!(rodeo.len(), 2);
+    /// ```
+    ///
+    /// [`InvalidKey`]: crate::LassoErrorKind::InvalidKey
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn extend_with_keys<I, T>(&mut self, pairs: I) -> LassoResult<()>
+    where
+        I: IntoIterator<Item = (K, T)>,
+        T: AsRef<str>,
+    {
+        let mut pairs: Vec<(K, T)> = pairs.into_iter().collect();
+        pairs.sort_unstable_by_key(|(key, _)| key.into_usize());
+
+        let checkpoint = self.checkpoint();
+        for (expected, (key, string)) in (self.strings.len()..).zip(pairs) {
+            // The pairs are sorted, so a key that's lower than expected was repeated or already
+            // taken and one that's higher means a key before it was skipped
+            let index = key.into_usize();
+            let result = if index != expected {
+                Err(LassoError::invalid_key(index.min(expected)))
+            } else {
+                self.try_get_or_intern(string).and_then(|interned| {
+                    if interned == key {
+                        Ok(())
+                    } else {
+                        Err(LassoError::invalid_key(index))
+                    }
+                })
+            };
+
+            if let Err(err) = result {
+                // Safety: The checkpoint was just made and the interner is borrowed mutably, so
+                //         nothing can reference the strings interned since
+                unsafe { self.rollback_to(checkpoint) };
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes the map entries of every key from `len` onwards, leaving their strings in place
+    fn unmap_from(&mut self, len: usize) {
+        let Self {
+            map,
+            hasher,
+            strings,
+            folded,
+            ..
+        } = self;
+
+        for (index, &string) in strings.iter().enumerate().skip(len) {
+            if let Ok(entry) =
+                map.find_entry(hasher.hash_one(string), |key| key.into_usize() == index)
+            {
+                entry.remove();
+            }
+
+            // Only the first key of each folded form is in the map, so a kept key can't be
+            // found through the folded form of a removed one
+            if !folded.is_empty() {
+                if let Ok(entry) = folded.find_entry(hash_ascii_folded(&*hasher, string), |key| {
+                    key.into_usize() == index
+                }) {
+                    entry.remove();
+                }
+            }
+        }
+    }
+
+    /// Get the key for a static string, interning it if it does not yet exist
+    ///
+    /// This will not reallocate or copy the given string, unless the interner was built with
+    /// [`RodeoBuilder::nul_terminated()`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique strings were interned)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    ///
+    /// // Interned the string
+    /// let key = rodeo.get_or_intern_static("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    ///
+    /// // No string was interned, as it was already contained
+    /// let key = rodeo.get_or_intern_static("Strings of things with wings and dings");
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    /// [`RodeoBuilder::nul_terminated()`]: crate::RodeoBuilder::nul_terminated
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn get_or_intern_static(&mut self, string: &'static str) -> K {
+        self.try_get_or_intern_static(string)
+            .expect("Failed to get or intern static string")
+    }
+
+    /// Get the key for a static string, interning it if it does not yet exist
+    ///
+    /// This will not reallocate or copy the given string, unless the interner was built with
+    /// [`RodeoBuilder::nul_terminated()`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    ///
+    /// // Interned the string
+    /// let key = rodeo.try_get_or_intern_static("Strings of things with wings and dings").unwrap();
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    ///
+    /// // No string was interned, as it was already contained
+    /// let key = rodeo.try_get_or_intern_static("Strings of things with wings and dings").unwrap();
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    /// [`RodeoBuilder::nul_terminated()`]: crate::RodeoBuilder::nul_terminated
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn try_get_or_intern_static(&mut self, string: &'static str) -> LassoResult<K> {
+        // Static strings aren't followed by a NUL byte, so they have to be copied
+        if self.arena.nul_terminated {
+            return self.try_get_or_intern(string);
+        }
+
+        #[cfg(feature = "profile-interning")]
+        self.profile
+            .record(core::panic::Location::caller(), string.len());
+
+        // Leave room for the map and strings vec to grow if they count towards the memory limit
+        if self.arena.total_budget {
+            self.arena.reserved_memory = self.overhead_memory_usage(true);
+        }
+
+        let Self {
+            map,
+            hasher,
+            strings,
+            arena,
+            borrowed,
+            stats,
+            #[cfg(feature = "timestamps")]
+            timestamps,
+            ..
+        } = self;
+
+        // Make a hash of the requested string
+        let hash = hasher.hash_one(string);
+
+        // Look for the string within the map
+        let key = match find_string(map, strings, hash, string) {
+            // The string already exists, so return its key
+            Some(key) => {
+                stats.record_hit(string.len());
+                key
+            }
+
+            // The string does not yet exist, so insert it and create its key
+            None => {
+                // Create the key from the vec's index that the string will hold
+                let key = K::try_from_usize(strings.len()).ok_or_else(|| {
+                    LassoError::new(LassoErrorKind::KeySpaceExhaustion)
+                        .with_string_len(string.len())
+                })?;
+
+                arena
+                    .check_reserved_memory(string.len())
+                    .map_err(|err| err.with_string_len(string.len()))?;
+
+                // Fixed arenas preallocate room for all of their strings and never grow it
+                if arena.fixed && strings.len() == strings.capacity() {
+                    return Err(LassoError::new(LassoErrorKind::MemoryLimitReached)
+                        .with_string_len(string.len()));
+                }
+
+                // Push the static string to the strings vector
+                strings.push(string);
+                borrowed.insert(key);
+                #[cfg(feature = "timestamps")]
+                timestamps.record(key.into_usize());
+
+                // Insert the key with the hash of the string that it points to, reusing the hash we made earlier
+                insert_string(map, strings, hasher, hash, key);
+                stats.record_miss(string.len());
 
                 key
             }
         };
 
-        Ok(key)
+        Ok(key)
+    }
+
+    /// Get the key for a string that's either static or owned, interning it if it does not
+    /// yet exist
+    ///
+    /// Borrowed strings are interned with [`Rodeo::get_or_intern_static`] so they're never
+    /// copied, and owned strings with [`Rodeo::get_or_intern_owned`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique strings were interned)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    /// use std::borrow::Cow;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    ///
+    /// let key = rodeo.get_or_intern_cow(Cow::Borrowed("Strings of things"));
+    /// assert_eq!(rodeo.get_or_intern_cow(Cow::Owned("Strings of things".to_owned())), key);
+    /// assert_eq!("Strings of things", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn get_or_intern_cow(&mut self, string: Cow<'static, str>) -> K {
+        self.try_get_or_intern_cow(string)
+            .expect("Failed to get or intern string")
+    }
+
+    /// Get the key for a string that's either static or owned, interning it if it does not
+    /// yet exist
+    ///
+    /// Borrowed strings are interned with [`Rodeo::try_get_or_intern_static`] so they're never
+    /// copied, and owned strings with [`Rodeo::try_get_or_intern_owned`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    /// use std::borrow::Cow;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    ///
+    /// let key = rodeo.try_get_or_intern_cow(Cow::Borrowed("Strings of things")).unwrap();
+    /// assert_eq!("Strings of things", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn try_get_or_intern_cow(&mut self, string: Cow<'static, str>) -> LassoResult<K> {
+        match string {
+            Cow::Borrowed(string) => self.try_get_or_intern_static(string),
+            Cow::Owned(string) => self.try_get_or_intern_owned(string),
+        }
     }
 
-    /// Get the key for a static string, interning it if it does not yet exist
+    /// Get the key for a substring of an already interned string, interning it if it does not
+    /// yet exist
     ///
-    /// This will not reallocate or copy the given string
+    /// The new string points into the parent's bytes instead of copying them into the arena,
+    /// so interning many pieces of a larger string takes up no extra arena space
     ///
     /// # Panics
     ///
-    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
-    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// Panics if `parent` is out of bounds, if `range` is out of bounds of the parent string
+    /// or if either end of `range` doesn't lie on a char boundary. Also panics if the key's
+    /// `try_from_usize` function fails. With the default keys, this means that you've
+    /// interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
     /// unique strings were interned)
     ///
     /// # Example
@@ -365,75 +1678,128 @@ where
     ///
     /// let mut rodeo = Rodeo::default();
     ///
-    /// // Interned the string
-    /// let key = rodeo.get_or_intern_static("Strings of things with wings and dings");
-    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// let line = rodeo.get_or_intern("let x = 10;");
+    /// let arena_usage = rodeo.arena_memory_usage();
     ///
-    /// // No string was interned, as it was already contained
-    /// let key = rodeo.get_or_intern_static("Strings of things with wings and dings");
-    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// let ident = rodeo.get_or_intern_slice_of(line, 4..5);
+    /// assert_eq!("x", rodeo.resolve(&ident));
+    /// assert_eq!(Some(ident), rodeo.get("x"));
+    /// assert_eq!(arena_usage, rodeo.arena_memory_usage());
     /// ```
     ///
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn get_or_intern_static(&mut self, string: &'static str) -> K {
-        self.try_get_or_intern_static(string)
-            .expect("Failed to get or intern static string")
+    pub fn get_or_intern_slice_of(&mut self, parent: K, range: Range<usize>) -> K {
+        self.try_get_or_intern_slice_of(parent, range)
+            .expect("Failed to get or intern string slice")
     }
 
-    /// Get the key for a static string, interning it if it does not yet exist
+    /// Get the key for a substring of an already interned string, interning it if it does not
+    /// yet exist
     ///
-    /// This will not reallocate or copy the given string
+    /// The new string points into the parent's bytes instead of copying them into the arena,
+    /// so interning many pieces of a larger string takes up no extra arena space. Returns an
+    /// [`InvalidKey`] error if `parent` is out of bounds and an [`InvalidRange`] error if
+    /// `range` is out of bounds of the parent string or if either end of it doesn't lie on
+    /// a char boundary
     ///
     /// # Example
     ///
     /// ```rust
-    /// use lasso::Rodeo;
+    /// use lasso::{LassoErrorKind, Rodeo};
     ///
     /// let mut rodeo = Rodeo::default();
     ///
-    /// // Interned the string
-    /// let key = rodeo.try_get_or_intern_static("Strings of things with wings and dings").unwrap();
-    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// let line = rodeo.get_or_intern("let x = 10;");
+    /// let number = rodeo.try_get_or_intern_slice_of(line, 8..10).unwrap();
+    /// assert_eq!("10", rodeo.resolve(&number));
     ///
-    /// // No string was interned, as it was already contained
-    /// let key = rodeo.try_get_or_intern_static("Strings of things with wings and dings").unwrap();
-    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// let err = rodeo.try_get_or_intern_slice_of(line, 8..20).unwrap_err();
+    /// assert_eq!(err.kind(), LassoErrorKind::InvalidRange);
     /// ```
     ///
+    /// [`InvalidKey`]: crate::LassoErrorKind::InvalidKey
+    /// [`InvalidRange`]: crate::LassoErrorKind::InvalidRange
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn try_get_or_intern_static(&mut self, string: &'static str) -> LassoResult<K> {
-        let Self {
-            map,
-            hasher,
-            strings,
-            ..
-        } = self;
-
-        // Make a hash of the requested string
-        let hash = hasher.hash_one(string);
+    pub fn try_get_or_intern_slice_of(&mut self, parent: K, range: Range<usize>) -> LassoResult<K> {
+        // Interned strings live either in the arena or in static memory, both of which
+        // outlive the interner's own references to them
+        let parent: &'static str = self
+            .strings
+            .get(parent.into_usize())
+            .copied()
+            .ok_or_else(|| LassoError::invalid_key(parent.into_usize()))?;
+        let slice = parent
+            .get(range)
+            .ok_or_else(|| LassoError::new(LassoErrorKind::InvalidRange))?;
+
+        self.try_get_or_intern_static(slice)
+    }
 
-        // Get the map's entry that the string should occupy
-        let key = match get_string_entry_mut(map, strings, hash, string) {
-            // The string already exists, so return its key
-            RawEntryMut::Occupied(entry) => *entry.into_key(),
+    /// Interns a table of seed strings into an empty interner, making sure that every
+    /// string gets the key of its index
+    fn intern_seed_strings(&mut self, strings: &[&'static str]) -> SeededKeys<K> {
+        debug_assert!(self.is_empty());
+
+        for (index, string) in strings.iter().enumerate() {
+            let key = self.get_or_intern_static(string);
+            assert_eq!(
+                key.into_usize(),
+                index,
+                "seed string {:?} was already in the table",
+                string,
+            );
+        }
 
-            // The string does not yet exist, so insert it and create its key
-            RawEntryMut::Vacant(entry) => {
-                // Create the key from the vec's index that the string will hold
-                let key = K::try_from_usize(strings.len())
-                    .ok_or_else(|| LassoError::new(LassoErrorKind::KeySpaceExhaustion))?;
+        SeededKeys::new(strings.len())
+    }
 
-                // Push the static string to the strings vector
-                strings.push(string);
+    /// Interns every string in `iter`, returning the keys of newly interned strings along with
+    /// the number of strings that were duplicates and the number of bytes that deduplication saved
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique strings were interned)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo.get_or_intern("a");
+    ///
+    /// let (new_keys, duplicates, bytes_saved) = rodeo.extend_with_stats(["a", "bc", "bc", "d"]);
+    /// assert_eq!(new_keys, [rodeo.get("bc").unwrap(), rodeo.get("d").unwrap()]);
+    /// assert_eq!(duplicates, 2);
+    /// assert_eq!(bytes_saved, 3);
+    /// ```
+    ///
+    /// [`Spur`]: crate::Spur
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn extend_with_stats<I, T>(&mut self, iter: I) -> (Vec<K>, usize, usize)
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let before = self.stats;
+        let mut new_keys = Vec::new();
 
-                // Insert the key with the hash of the string that it points to, reusing the hash we made earlier
-                insert_string(entry, strings, hasher, hash, key);
+        for string in iter {
+            let misses = self.stats.misses;
+            let key = self.get_or_intern(string);
 
-                key
+            if self.stats.misses != misses {
+                new_keys.push(key);
             }
-        };
+        }
 
-        Ok(key)
+        (
+            new_keys,
+            self.stats.hits - before.hits,
+            self.stats.bytes_saved() - before.bytes_saved(),
+        )
     }
 
     /// Get the key value of a string, returning `None` if it doesn't exist
@@ -461,17 +1827,103 @@ where
         // Make a hash of the requested string
         let hash = self.hasher.hash_one(string_slice);
 
-        // Get the map's entry that the string should occupy
-        self.map
-            .raw_entry()
-            .from_hash(hash, |key| {
+        find_string(&self.map, &self.strings, hash, string_slice)
+    }
+
+    /// Get the key for a string using a hash made with [`Rodeo::hasher`], returning `None` if
+    /// it hasn't been interned
+    ///
+    /// See the [`raw`] module for how strings are hashed, a hash that wasn't made that way
+    /// won't find anything
+    ///
+    /// This method is only available with the `raw` feature!
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{raw, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// let hash = raw::hash_str(rodeo.hasher(), "Strings of things with wings and dings");
+    /// assert_eq!(Some(key), rodeo.get_hashed(hash, "Strings of things with wings and dings"));
+    /// ```
+    ///
+    /// [`raw`]: crate::raw
+    #[cfg(feature = "raw")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_hashed(&self, hash: u64, string: &str) -> Option<K> {
+        find_string(&self.map, &self.strings, hash, string)
+    }
+
+    /// Gets the hasher that the `Rodeo` hashes strings with
+    ///
+    /// This method is only available with the `raw` feature!
+    #[cfg(feature = "raw")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn hasher(&self) -> &S {
+        &self.hasher
+    }
+
+    /// Get the key for a string while ignoring ASCII case, only strings interned with
+    /// [`Rodeo::get_or_intern_preserving`] are found
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern_preserving("Content-Type");
+    ///
+    /// assert_eq!(rodeo.get_ignore_ascii_case("content-type"), Some(key));
+    /// assert_eq!(rodeo.get_ignore_ascii_case("CONTENT-TYPE"), Some(key));
+    ///
+    /// // Strings interned normally aren't part of the case-insensitive index
+    /// rodeo.get_or_intern("Accept");
+    /// assert_eq!(rodeo.get_ignore_ascii_case("Accept"), None);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_ignore_ascii_case<T>(&self, val: T) -> Option<K>
+    where
+        T: AsRef<str>,
+    {
+        let string_slice: &str = val.as_ref();
+        let hash = hash_ascii_folded(&self.hasher, string_slice);
+
+        self.folded
+            .find(hash, |key| {
                 // Safety: The index given by `key` will be in bounds of the strings vector
                 let key_string: &str = unsafe { index_unchecked!(self.strings, key.into_usize()) };
 
-                // Compare the requested string against the key's string
-                string_slice == key_string
+                string_slice.eq_ignore_ascii_case(key_string)
             })
-            .map(|(&key, _)| key)
+            .copied()
+    }
+
+    /// Get the key of the empty string, returning `None` if it hasn't been interned
+    ///
+    /// Interners built with [`RodeoBuilder::intern_empty()`] always give the empty
+    /// string the first key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// assert_eq!(None, rodeo.empty_key());
+    ///
+    /// let key = rodeo.get_or_intern("");
+    /// assert_eq!(Some(key), rodeo.empty_key());
+    /// ```
+    ///
+    /// [`RodeoBuilder::intern_empty()`]: crate::RodeoBuilder::intern_empty
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn empty_key(&self) -> Option<K> {
+        self.get("")
     }
 
     /// Returns `true` if the given string has been interned
@@ -498,30 +1950,57 @@ where
     }
 }
 
-/// Gets a mutable entry for the given string using its hash
+/// The state of a [`Rodeo`] at some point in time, see [`Rodeo::checkpoint`]
+pub(crate) struct Checkpoint {
+    /// The number of strings that were interned
+    pub(crate) len: usize,
+    arena: ArenaMark,
+    stats: DedupStats,
+}
+
+/// Gets the key of the given string using its hash
 #[inline]
-fn get_string_entry_mut<'a, K>(
-    map: &'a mut StringMap<K>,
+pub(crate) fn find_string<K>(
+    map: &StringMap<K>,
     strings: &[&str],
     hash: u64,
     target: &str,
-) -> RawEntryMut<'a, K, (), ()>
+) -> Option<K>
 where
     K: Key,
 {
-    map.raw_entry_mut().from_hash(hash, |key| {
+    map.find(hash, |key| {
         // Safety: The index given by `key` will be in bounds of the strings vector
         let key_string: &str = unsafe { index_unchecked!(strings, key.into_usize()) };
 
         // Compare the requested string against the key's string
         target == key_string
     })
+    .copied()
+}
+
+/// Hashes the ASCII-lowercased form of a string without allocating it
+#[inline]
+fn hash_ascii_folded<S: BuildHasher>(hasher: &S, string: &str) -> u64 {
+    let mut state = hasher.build_hasher();
+
+    // Strings that are equal when folded have the same length, so they're always
+    // split into the same chunks and get the same hash
+    let mut buffer = [0; 64];
+    for chunk in string.as_bytes().chunks(buffer.len()) {
+        let folded = &mut buffer[..chunk.len()];
+        folded.copy_from_slice(chunk);
+        folded.make_ascii_lowercase();
+        state.write(folded);
+    }
+
+    state.finish()
 }
 
-/// Inserts a string into a vacant entry using its given hash
+/// Inserts the key of a string that isn't in the map yet using its given hash
 #[inline]
-fn insert_string<K, S>(
-    entry: RawVacantEntryMut<K, (), ()>,
+pub(crate) fn insert_string<K, S>(
+    map: &mut StringMap<K>,
     strings: &[&str],
     hasher: &S,
     hash: u64,
@@ -530,7 +2009,7 @@ fn insert_string<K, S>(
     K: Key,
     S: BuildHasher,
 {
-    entry.insert_with_hasher(hash, key, (), |key| {
+    map.insert_unique(hash, key, |key| {
         // Safety: The index given by `key` will be in bounds of the strings vector
         let key_string: &str = unsafe { index_unchecked!(strings, key.into_usize()) };
 
@@ -594,6 +2073,30 @@ where
         }
     }
 
+    /// Borrows the interner's strings as a [`ResolverView`], which implements [`Resolver`]
+    ///
+    /// The view is cheap to make and can be handed to code that only resolves keys without
+    /// that code having to know the interner's hasher type. See [`ResolverView`] for more
+    /// information
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Resolver, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// let view = rodeo.as_resolver_view();
+    /// assert_eq!(Resolver::resolve(&view, &key), "Strings of things with wings and dings");
+    /// ```
+    ///
+    /// [`Resolver`]: crate::Resolver
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn as_resolver_view(&self) -> ResolverView<'_, K> {
+        ResolverView::new(&self.strings)
+    }
+
     /// Resolves a string by its key, returning `None` if it's out of bounds. Only keys made by the
     /// current Rodeo may be used
     ///
@@ -623,11 +2126,191 @@ where
         }
     }
 
+    /// Resolves a string by its key into a [`CompactString`], which stores short strings
+    /// inline instead of allocating
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things");
+    ///
+    /// let string = rodeo.resolve_compact(&key);
+    /// assert_eq!(string, "Strings of things");
+    /// assert!(!string.is_heap_allocated());
+    /// ```
+    ///
+    /// [`CompactString`]: compact_str::CompactString
+    #[cfg(feature = "compact_str")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve_compact(&self, key: &K) -> CompactString {
+        CompactString::new(self.resolve(key))
+    }
+
+    /// Resolves a string by its key into a [`CompactString`], returning `None` if it's out
+    /// of bounds
+    ///
+    /// [`CompactString`]: compact_str::CompactString
+    #[cfg(feature = "compact_str")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve_compact(&self, key: &K) -> Option<CompactString> {
+        self.try_resolve(key).map(CompactString::new)
+    }
+
     /// Resolves a string by its key, without bounds checks
     ///
-    /// # Safety
+    /// # Safety
+    ///
+    /// The key must be valid for the current interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    ///
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// unsafe {
+    ///     assert_eq!("Strings of things with wings and dings", rodeo.resolve_unchecked(&key));
+    /// }
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub unsafe fn resolve_unchecked<'a>(&'a self, key: &K) -> &'a str {
+        unsafe { self.strings.get_unchecked(key.into_usize()) }
+    }
+
+    /// Resolves a string by its key as a NUL-terminated [`CStr`], returning `None` if the key is
+    /// out of bounds, the string contains an interior NUL byte or the interner wasn't built with
+    /// [`RodeoBuilder::nul_terminated()`]
+    ///
+    /// The returned string points into the interner's own storage, so no copy is made
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, RodeoBuilder};
+    ///
+    /// let mut rodeo: Rodeo = RodeoBuilder::new().nul_terminated(true).build();
+    ///
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    /// assert_eq!(rodeo.resolve_cstr(&key).unwrap().to_bytes(), b"Hello, world!");
+    ///
+    /// // Strings with interior NULs can't be represented
+    /// let key = rodeo.get_or_intern("Hello\0world!");
+    /// assert_eq!(rodeo.resolve_cstr(&key), None);
+    /// ```
+    ///
+    /// [`RodeoBuilder::nul_terminated()`]: crate::RodeoBuilder::nul_terminated
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve_cstr(&self, key: &K) -> Option<&CStr> {
+        if !self.arena.nul_terminated {
+            return None;
+        }
+
+        let string = self.try_resolve(key)?;
+        let bytes = if string.is_empty() {
+            // Empty strings aren't stored in the arena
+            &b"\0"[..]
+        } else {
+            // Safety: Every string stored in a `nul_terminated` arena is followed by a NUL byte
+            unsafe { slice::from_raw_parts(string.as_ptr(), string.len() + 1) }
+        };
+
+        CStr::from_bytes_with_nul(bytes).ok()
+    }
+
+    /// Resolves a string by its key as a pointer to a NUL-terminated string, returning a null
+    /// pointer in every case where [`Rodeo::resolve_cstr()`] returns `None`
+    ///
+    /// The pointer is valid until the interner is cleared or dropped, and can be handed directly
+    /// to C functions expecting a `const char*`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, RodeoBuilder};
+    ///
+    /// let mut rodeo: Rodeo = RodeoBuilder::new().nul_terminated(true).build();
+    ///
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    /// assert!(!rodeo.resolve_cstr_ptr(&key).is_null());
+    ///
+    /// let rodeo = Rodeo::default();
+    /// assert!(rodeo.resolve_cstr_ptr(&key).is_null());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve_cstr_ptr(&self, key: &K) -> *const c_char {
+        self.resolve_cstr(key).map_or(ptr::null(), CStr::as_ptr)
+    }
+
+    /// Resolves a string by its key into a pointer to its first byte and its length in bytes,
+    /// returning a null pointer and a length of zero if the key is out of bounds
+    ///
+    /// The string's storage never moves, so the pointer stays valid while more strings are
+    /// interned and after the interner is turned into a [`RodeoReader`] or [`RodeoResolver`].
+    /// It's only invalidated when the interner is cleared or dropped
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    /// let (ptr, len) = rodeo.resolve_ptr(&key);
+    ///
+    /// // Interning more strings doesn't move the ones already interned
+    /// for i in 0..1000 {
+    ///     rodeo.get_or_intern(i.to_string());
+    /// }
+    ///
+    /// let resolver = rodeo.into_resolver();
+    /// let bytes = unsafe { std::slice::from_raw_parts(ptr, len) };
+    /// assert_eq!(bytes, resolver.resolve(&key).as_bytes());
+    /// ```
+    ///
+    /// [`RodeoReader`]: crate::RodeoReader
+    /// [`RodeoResolver`]: crate::RodeoResolver
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve_ptr(&self, key: &K) -> (*const u8, usize) {
+        self.try_resolve(key)
+            .map_or((ptr::null(), 0), |string| (string.as_ptr(), string.len()))
+    }
+
+    /// Resolves a batch of keys into their strings, in the same order as the keys
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the keys are out of bounds
     ///
-    /// The key must be valid for the current interner
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let a = rodeo.get_or_intern("Foo");
+    /// let b = rodeo.get_or_intern("Bar");
+    ///
+    /// assert_eq!(rodeo.resolve_many(&[b, a, b]), ["Bar", "Foo", "Bar"]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve_many<'a>(&'a self, keys: &[K]) -> Vec<&'a str> {
+        keys.iter().map(|key| self.resolve(key)).collect()
+    }
+
+    /// Resolves a batch of keys into their strings, in the same order as the keys, returning
+    /// `None` if any of the keys are out of bounds
     ///
     /// # Example
     ///
@@ -635,16 +2318,15 @@ where
     /// use lasso::Rodeo;
     ///
     /// let mut rodeo = Rodeo::default();
+    /// let a = rodeo.get_or_intern("Foo");
+    /// let b = rodeo.get_or_intern("Bar");
     ///
-    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
-    /// unsafe {
-    ///     assert_eq!("Strings of things with wings and dings", rodeo.resolve_unchecked(&key));
-    /// }
+    /// assert_eq!(rodeo.try_resolve_many(&[b, a]), Some(vec!["Bar", "Foo"]));
     /// ```
     ///
     #[cfg_attr(feature = "inline-more", inline)]
-    pub unsafe fn resolve_unchecked<'a>(&'a self, key: &K) -> &'a str {
-        unsafe { self.strings.get_unchecked(key.into_usize()) }
+    pub fn try_resolve_many<'a>(&'a self, keys: &[K]) -> Option<Vec<&'a str>> {
+        keys.iter().map(|key| self.try_resolve(key)).collect()
     }
 }
 
@@ -685,6 +2367,10 @@ impl<K, S> Rodeo<K, S> {
 
     /// Clears the current interner, invalidating all previously interned keys
     ///
+    /// The interner keeps its capacity to be reused, unless it was built with
+    /// [`RodeoBuilder::shrink_on_clear()`] in which case it's also shrunk like
+    /// [`Rodeo::shrink_to_fit`] does
+    ///
     /// # Example
     ///
     /// ```rust
@@ -707,11 +2393,26 @@ impl<K, S> Rodeo<K, S> {
     /// assert_eq!(rodeo.try_resolve(&key), None);
     /// ```
     ///
+    /// [`RodeoBuilder::shrink_on_clear()`]: crate::RodeoBuilder::shrink_on_clear
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn clear(&mut self) {
         self.map.clear();
+        self.folded.clear();
         self.strings.clear();
+        self.borrowed.clear();
         self.arena.clear();
+        #[cfg(feature = "timestamps")]
+        self.timestamps.clear(self.arena.shrink_on_clear);
+
+        if self.arena.shrink_on_clear {
+            // Everything was just removed, so nothing has to be rehashed to shrink the maps
+            self.map = StringMap::new();
+            self.folded = StringMap::new();
+            self.strings = Vec::new();
+            self.borrowed = KeySet::new();
+            self.scratch = String::new();
+            self.arena.release_empty_buckets();
+        }
     }
 
     /// Returns the number of strings that can be interned without a reallocation
@@ -744,6 +2445,177 @@ impl<K, S> Rodeo<K, S> {
         Strings::from_rodeo(self)
     }
 
+    /// Returns the time the string of a key was interned at, or `None` if the key isn't
+    /// in the interner
+    ///
+    /// Times are read from the interner's clock, which is [`monotonic_ticks`] unless the
+    /// interner was built with [`RodeoBuilder::clock`]. Interning a string that's already in
+    /// the interner doesn't change its time
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{monotonic_ticks, Rodeo};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let before = monotonic_ticks();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// let interned_at = rodeo.interned_at(&key).unwrap();
+    /// assert!(before <= interned_at && interned_at <= monotonic_ticks());
+    /// ```
+    ///
+    /// [`monotonic_ticks`]: crate::monotonic_ticks
+    /// [`RodeoBuilder::clock`]: crate::RodeoBuilder::clock
+    #[cfg(feature = "timestamps")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn interned_at(&self, key: &K) -> Option<u64>
+    where
+        K: Key,
+    {
+        if self.contains_key(key) {
+            self.timestamps.get(key.into_usize())
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the keys and strings that were interned before `ticks`,
+    /// in the order they were interned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{RodeoBuilder, Rodeo};
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    ///
+    /// static GENERATION: AtomicU64 = AtomicU64::new(0);
+    ///
+    /// let mut rodeo: Rodeo = RodeoBuilder::new()
+    ///     .clock(|| GENERATION.load(Ordering::Relaxed))
+    ///     .build();
+    ///
+    /// let old = rodeo.get_or_intern("old");
+    /// GENERATION.store(1, Ordering::Relaxed);
+    /// rodeo.get_or_intern("new");
+    ///
+    /// let older: Vec<_> = rodeo.iter_older_than(1).collect();
+    /// assert_eq!(older, [(old, "old")]);
+    /// ```
+    ///
+    #[cfg(feature = "timestamps")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter_older_than(&self, ticks: u64) -> OlderThan<'_, K>
+    where
+        K: Key,
+    {
+        OlderThan::new(self.iter(), &self.timestamps, ticks)
+    }
+
+    /// Returns a [`KeySet`] holding every key of the interner
+    ///
+    /// The set has room for every current key, so removing keys from it or intersecting it
+    /// with other sets never has to grow it
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let hello = rodeo.get_or_intern("hello");
+    /// let world = rodeo.get_or_intern("world");
+    ///
+    /// let mut unused = rodeo.key_universe();
+    /// unused.remove(hello);
+    /// assert_eq!(unused.iter().collect::<Vec<_>>(), vec![world]);
+    /// ```
+    ///
+    /// [`KeySet`]: crate::KeySet
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn key_universe(&self) -> KeySet<K> {
+        KeySet::filled(self.strings.len())
+    }
+
+    /// Counts the interned strings by length, `bucket_bounds` must be sorted and give the
+    /// inclusive upper bound of each bucket
+    ///
+    /// The returned histogram has one more bucket than there are bounds, holding every
+    /// string longer than the final bound
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo.get_or_intern("a");
+    /// rodeo.get_or_intern("bcd");
+    /// rodeo.get_or_intern("efghijklmn");
+    ///
+    /// assert_eq!(rodeo.length_histogram(&[1, 4]), vec![1, 1, 1]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn length_histogram(&self, bucket_bounds: &[usize]) -> Vec<usize> {
+        util::length_histogram(&self.strings, bucket_bounds)
+    }
+
+    /// Returns the `n` longest interned strings and their keys, longest first with
+    /// ties going to the lowest key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo.get_or_intern("a");
+    /// let long = rodeo.get_or_intern("efghijklmn");
+    /// let medium = rodeo.get_or_intern("bcd");
+    ///
+    /// assert_eq!(rodeo.top_longest(2), vec![(long, "efghijklmn"), (medium, "bcd")]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn top_longest(&self, n: usize) -> Vec<(K, &str)>
+    where
+        K: Key,
+    {
+        util::top_longest(&self.strings, n)
+    }
+
+    /// Returns `true` if both interners hold exactly the same strings, no matter which
+    /// keys those strings were given
+    ///
+    /// Unlike `==`, which requires every key to resolve to the same string, this only
+    /// compares the sets of interned strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut left = Rodeo::default();
+    /// left.get_or_intern("foo");
+    /// left.get_or_intern("bar");
+    ///
+    /// let mut right = Rodeo::default();
+    /// right.get_or_intern("bar");
+    /// right.get_or_intern("foo");
+    ///
+    /// assert!(left != right);
+    /// assert!(left.equivalent_content(&right));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn equivalent_content<K2, R>(&self, other: &R) -> bool
+    where
+        R: Reader<K2> + ?Sized,
+    {
+        self.len() == other.len() && self.strings.iter().all(|string| other.contains(string))
+    }
+
     /// Set the `Rodeo`'s maximum memory usage while in-flight
     ///
     /// Note that setting the maximum memory usage to below the currently allocated
@@ -751,14 +2623,251 @@ impl<K, S> Rodeo<K, S> {
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn set_memory_limits(&mut self, memory_limits: MemoryLimits) {
         self.arena.max_memory_usage = memory_limits.max_memory_usage;
+        self.arena.total_budget = memory_limits.total_budget;
+        if !memory_limits.total_budget {
+            self.arena.reserved_memory = 0;
+        }
     }
 
     /// Get the `Rodeo`'s currently allocated memory
+    ///
+    /// This includes the estimated size of the interner's internal map and strings
+    /// vec, see [`Rodeo::arena_memory_usage`] for the memory used only by interned strings
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn current_memory_usage(&self) -> usize {
+        self.arena
+            .memory_usage()
+            .saturating_add(self.overhead_memory_usage(false))
+    }
+
+    /// Get the memory allocated to hold the `Rodeo`'s interned strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn arena_memory_usage(&self) -> usize {
         self.arena.memory_usage()
     }
 
+    /// Copies bytes into the interner's arena, returning a slice of the copy
+    ///
+    /// This is meant for small pieces of data that belong with the interned strings, like
+    /// encoded spans, so that they don't need an arena of their own. The bytes count against
+    /// the interner's memory limits just like strings do and are freed once the interner
+    /// is cleared or dropped
+    ///
+    /// The returned slice borrows the interner mutably like any other `&mut self` method,
+    /// so it has to be used before the interner is used again, even just to resolve a key
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interner's memory limit is reached or the memory couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let span = rodeo.alloc_bytes(&[0x10, 0x04]);
+    /// assert_eq!(span, [0x10, 0x04]);
+    ///
+    /// // The slice has been used, so the interner can be used again
+    /// let key = rodeo.get_or_intern("span");
+    /// assert_eq!(rodeo.resolve(&key), "span");
+    /// ```
+    ///
+    /// The slice can't be held while the interner is used
+    ///
+    /// ```rust,compile_fail
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let span = rodeo.alloc_bytes(&[0x10, 0x04]);
+    ///
+    /// let key = rodeo.get_or_intern("span");
+    /// assert_eq!(span, [0x10, 0x04]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn alloc_bytes(&mut self, bytes: &[u8]) -> &[u8] {
+        self.try_alloc_bytes(bytes)
+            .expect("failed to allocate bytes in the interner's arena")
+    }
+
+    /// Copies bytes into the interner's arena, returning a slice of the copy
+    ///
+    /// See [`Rodeo::alloc_bytes`] for more information
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{MemoryLimits, Rodeo};
+    ///
+    /// let mut rodeo: Rodeo = Rodeo::with_memory_limits(MemoryLimits::for_memory_usage(4096));
+    /// assert!(rodeo.try_alloc_bytes(&[0; 8192]).is_err());
+    /// assert_eq!(rodeo.try_alloc_bytes(&[1, 2, 3]).unwrap(), [1, 2, 3]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_alloc_bytes(&mut self, bytes: &[u8]) -> LassoResult<&[u8]> {
+        self.try_alloc_slice(bytes)
+    }
+
+    /// Copies a slice into the interner's arena, returning the copy
+    ///
+    /// The copy is properly aligned for `T`, which costs up to `align_of::<T>() - 1` bytes of
+    /// padding. See [`Rodeo::alloc_bytes`] for more information
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interner's memory limit is reached or the memory couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo.get_or_intern("x");
+    /// let offsets = rodeo.alloc_slice(&[4_u64, 8, 15, 16]);
+    ///
+    /// assert_eq!(offsets, [4, 8, 15, 16]);
+    /// assert_eq!(offsets.as_ptr() as usize % core::mem::align_of::<u64>(), 0);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn alloc_slice<T: Copy>(&mut self, slice: &[T]) -> &[T] {
+        self.try_alloc_slice(slice)
+            .expect("failed to allocate a slice in the interner's arena")
+    }
+
+    /// Copies a slice into the interner's arena, returning the copy
+    ///
+    /// See [`Rodeo::alloc_slice`] for more information
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_alloc_slice<T: Copy>(&mut self, slice: &[T]) -> LassoResult<&[T]> {
+        let size = mem::size_of_val(slice);
+
+        // Empty slices and slices of zero-sized types don't take up any memory
+        if size == 0 {
+            // Safety: A dangling pointer is valid for a slice that's zero bytes long
+            return Ok(unsafe { slice::from_raw_parts(NonNull::dangling().as_ptr(), slice.len()) });
+        }
+
+        self.arena.check_reserved_memory(size)?;
+        let allocated = self
+            .arena
+            .allocate_aligned(size, mem::align_of::<T>())?
+            .cast::<T>()
+            .as_ptr();
+
+        // Safety: The allocation is aligned for `T`, has room for the whole slice and was just
+        //         handed out, so nothing else uses it. `T` is `Copy`, so copying its bytes
+        //         makes a valid value. The copy lives in the arena, which outlives the mutable
+        //         borrow of the interner that the returned slice is tied to
+        unsafe {
+            ptr::copy_nonoverlapping(slice.as_ptr(), allocated, slice.len());
+
+            Ok(slice::from_raw_parts(allocated, slice.len()))
+        }
+    }
+
+    /// Get a breakdown of the `Rodeo`'s memory usage
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn memory_stats(&self) -> MemoryStats {
+        MemoryStats::new(
+            self.arena_memory_usage(),
+            self.overhead_memory_usage(false),
+            self.strings.iter().copied(),
+        )
+    }
+
+    /// Get a snapshot of the `Rodeo`'s size and memory usage for exporting to monitoring
+    /// systems, see [`MetricsSnapshot::to_prometheus`]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn metrics(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            interned_total: self.len(),
+            bytes_allocated: self.current_memory_usage(),
+            map_entries: self.map.len(),
+            memory_limit: self.max_memory_usage(),
+        }
+    }
+
+    /// Estimates the memory used by the interner's map and strings vec, optionally
+    /// including the amount they'd grow by to make room for one more string
+    fn overhead_memory_usage(&self, projected: bool) -> usize {
+        let (mut map, mut strings) = (self.map.capacity(), self.strings.capacity());
+        if projected && self.map.len() == map {
+            map = grown_table_capacity(map);
+        }
+        if projected && self.strings.len() == strings {
+            // Vecs double their capacity when they run out of room
+            strings = max(strings.saturating_mul(2), 4);
+        }
+
+        // Every map entry also has a one byte control tag
+        map.saturating_add(self.folded.capacity())
+            .saturating_mul(size_of::<K>() + 1)
+            .saturating_add(strings.saturating_mul(size_of::<&'static str>()))
+    }
+
+    /// Get statistics on how much deduplication the `Rodeo` has achieved
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo.get_or_intern("duplicate");
+    /// rodeo.get_or_intern("duplicate");
+    ///
+    /// let stats = rodeo.dedup_stats();
+    /// assert_eq!(stats.hits(), 1);
+    /// assert_eq!(stats.misses(), 1);
+    /// assert_eq!(stats.bytes_saved(), 9);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn dedup_stats(&self) -> DedupStats {
+        self.stats
+    }
+
+    /// Resets the `Rodeo`'s deduplication statistics without touching any interned strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn reset_dedup_stats(&mut self) {
+        self.stats = DedupStats::default();
+    }
+
+    /// Get how much every call site has interned into the `Rodeo`, sorted so that the call
+    /// sites that interned the most bytes come first
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// rodeo.get_or_intern("hello");
+    ///
+    /// let report = rodeo.profiling_report();
+    /// assert_eq!(report[0].location().line(), line!() - 3);
+    /// assert_eq!(report[0].interns(), 1);
+    /// assert_eq!(report[0].bytes(), 5);
+    /// ```
+    ///
+    #[cfg(feature = "profile-interning")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn profiling_report(&self) -> Vec<CallSiteProfile> {
+        self.profile.report()
+    }
+
+    /// Resets the `Rodeo`'s profiling report without touching any interned strings
+    #[cfg(feature = "profile-interning")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn reset_profiling(&mut self) {
+        self.profile.clear();
+    }
+
     /// Get the `Rodeo`'s current maximum of allocated memory
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn max_memory_usage(&self) -> usize {
@@ -794,10 +2903,11 @@ impl<K, S> Rodeo<K, S> {
             hasher,
             strings,
             arena,
+            ..
         } = self;
 
         // Safety: No other references outside of `map` and `strings` to the interned strings exist
-        unsafe { RodeoReader::new(map, hasher, strings, AnyArena::Arena(arena)) }
+        unsafe { RodeoReader::new(map, hasher, strings, KeySet::new(), AnyArena::Arena(arena)) }
     }
 
     /// Consumes the current Rodeo, returning a [`RodeoResolver`] to allow contention-free access of the interner
@@ -811,21 +2921,80 @@ impl<K, S> Rodeo<K, S> {
     /// let mut rodeo = Rodeo::default();
     /// let key = rodeo.get_or_intern("Appear weak when you are strong, and strong when you are weak.");
     ///
-    /// let resolver_rodeo = rodeo.into_resolver();
+    /// let resolver_rodeo = rodeo.into_resolver();
+    /// assert_eq!(
+    ///     "Appear weak when you are strong, and strong when you are weak.",
+    ///     resolver_rodeo.resolve(&key),
+    /// );
+    /// ```
+    ///
+    /// [`RodeoResolver`]: crate::RodeoResolver
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn into_resolver(self) -> RodeoResolver<K> {
+        let Rodeo { strings, arena, .. } = self;
+
+        // Safety: No other references to the strings exist
+        unsafe { RodeoResolver::new(strings, KeySet::new(), AnyArena::Arena(arena)) }
+    }
+
+    /// Consumes the current Rodeo, returning a [`RodeoResolver`] whose strings were all copied
+    /// into a single arena bucket that fits them exactly
+    ///
+    /// [`Rodeo::into_resolver`] keeps the interner's arena as-is, including the unused tails
+    /// of its buckets. This copies every string once, in key order, so the resolver holds
+    /// no wasted memory and strings with neighbouring keys sit next to each other. The old
+    /// arena is freed once the copy is made
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new arena could not be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Appear weak when you are strong, and strong when you are weak.");
+    ///
+    /// let resolver = rodeo.into_resolver_compact();
     /// assert_eq!(
     ///     "Appear weak when you are strong, and strong when you are weak.",
-    ///     resolver_rodeo.resolve(&key),
+    ///     resolver.resolve(&key),
     /// );
+    /// assert_eq!(resolver.arena_memory_usage(), resolver.resolve(&key).len());
     /// ```
     ///
     /// [`RodeoResolver`]: crate::RodeoResolver
+    /// [`Rodeo::into_resolver`]: crate::Rodeo::into_resolver
     #[cfg_attr(feature = "inline-more", inline)]
     #[must_use]
-    pub fn into_resolver(self) -> RodeoResolver<K> {
-        let Rodeo { strings, arena, .. } = self;
+    pub fn into_resolver_compact(self) -> RodeoResolver<K>
+    where
+        K: Key,
+    {
+        RodeoResolver::compacted(&self.strings, KeySet::new())
+    }
+}
 
-        // Safety: No other references to the strings exist
-        unsafe { RodeoResolver::new(strings, AnyArena::Arena(arena)) }
+impl<K> Rodeo<K, DynamicState> {
+    /// Gets the kind of hasher the `Rodeo` was built with
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{DynamicState, HasherKind, Rodeo, RodeoBuilder, Spur};
+    ///
+    /// let rodeo: Rodeo<Spur, DynamicState> = RodeoBuilder::new()
+    ///     .hasher_kind(HasherKind::default())
+    ///     .build();
+    /// assert_eq!(rodeo.hasher_kind(), HasherKind::default());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn hasher_kind(&self) -> HasherKind {
+        self.hasher.kind()
     }
 }
 
@@ -840,7 +3009,13 @@ impl Default for Rodeo<Spur, RandomState> {
     }
 }
 
-unsafe impl<K: Send, S: Send> Send for Rodeo<K, S> {}
+impl<K: Key + Debug, S> Debug for Rodeo<K, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        util::debug_interner(f, "Rodeo", self.len(), self.current_memory_usage(), |idx| {
+            Some((K::try_from_usize(idx)?, *self.strings.get(idx)?))
+        })
+    }
+}
 
 impl<Str, K, S> FromIterator<Str> for Rodeo<K, S>
 where
@@ -910,23 +3085,23 @@ impl<'a, K: Key, S> IntoIterator for &'a Rodeo<K, S> {
 
 impl<K, S> Eq for Rodeo<K, S> {}
 
-impl<K, S> PartialEq<Self> for Rodeo<K, S> {
+impl<K, S, K2, S2> PartialEq<Rodeo<K2, S2>> for Rodeo<K, S> {
     #[cfg_attr(feature = "inline-more", inline)]
-    fn eq(&self, other: &Self) -> bool {
+    fn eq(&self, other: &Rodeo<K2, S2>) -> bool {
         self.strings == other.strings
     }
 }
 
-impl<K, S> PartialEq<RodeoReader<K, S>> for Rodeo<K, S> {
+impl<K, S, K2, S2> PartialEq<RodeoReader<K2, S2>> for Rodeo<K, S> {
     #[cfg_attr(feature = "inline-more", inline)]
-    fn eq(&self, other: &RodeoReader<K, S>) -> bool {
+    fn eq(&self, other: &RodeoReader<K2, S2>) -> bool {
         self.strings == other.strings
     }
 }
 
-impl<K, S> PartialEq<RodeoResolver<K>> for Rodeo<K, S> {
+impl<K, S, K2> PartialEq<RodeoResolver<K2>> for Rodeo<K, S> {
     #[cfg_attr(feature = "inline-more", inline)]
-    fn eq(&self, other: &RodeoResolver<K>) -> bool {
+    fn eq(&self, other: &RodeoResolver<K2>) -> bool {
         self.strings == other.strings
     }
 }
@@ -954,17 +3129,13 @@ where
         let hash = hasher.hash_one(allocated);
 
         // Insert the allocated string into the string map
-        match get_string_entry_mut(map, strings, hash, allocated) {
-            RawEntryMut::Vacant(vacant) => {
-                let key = K::try_from_usize(idx)
-                    .ok_or_else(|| LassoError::new(LassoErrorKind::KeySpaceExhaustion))?;
-                insert_string(vacant, strings, hasher, hash, key);
-            }
-
-            RawEntryMut::Occupied(_) => {
-                unreachable!("keys should be unique within cloned Rodeos")
-            }
-        }
+        debug_assert!(
+            find_string(map, strings, hash, allocated).is_none(),
+            "keys should be unique within cloned Rodeos",
+        );
+        let key = K::try_from_usize(idx)
+            .ok_or_else(|| LassoError::new(LassoErrorKind::KeySpaceExhaustion))?;
+        insert_string(map, strings, hasher, hash, key);
     }
 
     Ok(())
@@ -982,21 +3153,32 @@ where
         // We can figure out the exact size of all strings contained in the current interner
         // which will allow us to allocate a single bucket that exactly fits those strings,
         // minimizing allocations
-        let required_capacity =
-            NonZeroUsize::new(self.strings.iter().copied().map(str::len).sum::<usize>())
-                .unwrap_or(Capacity::default().bytes);
+        let terminator = usize::from(self.arena.nul_terminated);
+        let required_capacity = NonZeroUsize::new(
+            self.strings
+                .iter()
+                .map(|string| string.len() + terminator)
+                .sum::<usize>(),
+        )
+        .unwrap_or(Capacity::default().bytes);
 
         // Allocate a new arena to fit all strings in
-        let mut arena = Arena::new(
+        let mut arena = Arena::with_allocator(
             required_capacity,
             max(self.arena.max_memory_usage, required_capacity.get()),
+            self.arena.allocator().clone(),
         )?;
+        arena.total_budget = self.arena.total_budget;
+        arena.adopt_large_strings = self.arena.adopt_large_strings;
+        arena.spill_threshold = self.arena.spill_threshold;
+        arena.nul_terminated = self.arena.nul_terminated;
+        arena.shrink_on_clear = self.arena.shrink_on_clear;
 
         // Allocate all strings contained within the interner within the new arena while
         // also inserting the allocated strings into the new map
         let (mut strings, mut map, hasher) = (
             Vec::with_capacity(self.strings.len()),
-            StringMap::<K>::with_capacity_and_hasher(self.map.len(), ()),
+            StringMap::<K>::with_capacity(self.map.len()),
             self.hasher.clone(),
         );
         clone_strings_into(&self.strings, &mut arena, &mut strings, &mut map, &hasher)?;
@@ -1006,6 +3188,16 @@ where
             hasher,
             strings,
             arena,
+            // Every string was copied into the new arena
+            borrowed: KeySet::new(),
+            stats: self.stats,
+            #[cfg(feature = "profile-interning")]
+            profile: self.profile.clone(),
+            #[cfg(feature = "timestamps")]
+            timestamps: self.timestamps.clone(),
+            scratch: String::new(),
+            // The hasher is cloned, so the folded hashes are still correct
+            folded: self.folded.clone(),
         })
     }
 
@@ -1018,6 +3210,11 @@ where
         // Clear the current interner
         self.clear();
         self.hasher = source.hasher.clone();
+        self.stats = source.stats;
+        #[cfg(feature = "profile-interning")]
+        self.profile.clone_from(&source.profile);
+        #[cfg(feature = "timestamps")]
+        self.timestamps.clone_from(&source.timestamps);
 
         // Reserve capacity for the cloned-over strings
         self.strings
@@ -1025,7 +3222,6 @@ where
             .map_err(|_| LassoError::new(LassoErrorKind::FailedAllocation))?;
 
         self.map
-            .raw_table_mut()
             .try_reserve(source.map.len(), |_| {
                 unreachable!("the target Rodeo's map should be empty while resizing");
             })
@@ -1039,6 +3235,7 @@ where
             &mut self.map,
             &self.hasher,
         )?;
+        self.folded.clone_from(&source.folded);
 
         Ok(())
     }
@@ -1061,6 +3258,13 @@ where
     }
 }
 
+#[cfg(feature = "deepsize")]
+impl<K, S> deepsize::DeepSizeOf for Rodeo<K, S> {
+    fn deep_size_of_children(&self, _context: &mut deepsize::Context) -> usize {
+        self.current_memory_usage()
+    }
+}
+
 #[cfg(feature = "serialize")]
 impl<K, H> Serialize for Rodeo<K, H> {
     #[cfg_attr(feature = "inline-more", inline)]
@@ -1068,7 +3272,8 @@ impl<K, H> Serialize for Rodeo<K, H> {
     where
         S: Serializer,
     {
-        // Serialize all of self as a `Vec<String>`
+        // Serialize all of self as a `Vec<String>`, see `crate::with_keys` for a format that
+        // includes the keys
         self.strings.serialize(serializer)
     }
 }
@@ -1091,7 +3296,7 @@ impl<'de, K: Key, S: BuildHasher + Default> Deserialize<'de> for Rodeo<K, S> {
 
         let hasher: S = Default::default();
         let mut strings = Vec::with_capacity(capacity.strings);
-        let mut map = HashMap::with_capacity_and_hasher(capacity.strings, ());
+        let mut map = StringMap::with_capacity(capacity.strings);
         let mut arena =
             Arena::new(capacity.bytes, usize::MAX).expect("failed to allocate memory for interner");
 
@@ -1104,55 +3309,58 @@ impl<'de, K: Key, S: BuildHasher + Default> Deserialize<'de> for Rodeo<K, S> {
 
             let hash = hasher.hash_one(allocated);
 
-            // Get the map's entry that the string should occupy
-            let entry = map.raw_entry_mut().from_hash(hash, |key: &K| {
-                // Safety: The index given by `key` will be in bounds of the strings vector
-                let key_string: &str = unsafe { index_unchecked!(strings, key.into_usize()) };
-
-                // Compare the requested string against the key's string
-                allocated == key_string
-            });
-
-            match entry {
-                RawEntryMut::Occupied(..) => {
-                    debug_assert!(false, "re-interned a key while deserializing");
-                }
-                RawEntryMut::Vacant(entry) => {
-                    // Create the key from the vec's index that the string will hold
-                    let key =
-                        K::try_from_usize(key).expect("failed to create key while deserializing");
+            if find_string::<K>(&map, &strings, hash, allocated).is_some() {
+                debug_assert!(false, "re-interned a key while deserializing");
+                continue;
+            }
 
-                    // Push the allocated string to the strings vector
-                    strings.push(allocated);
+            // Create the key from the vec's index that the string will hold
+            let key = K::try_from_usize(key).expect("failed to create key while deserializing");
 
-                    // Insert the key with the hash of the string that it points to, reusing the hash we made earlier
-                    entry.insert_with_hasher(hash, key, (), |key| {
-                        let key_string: &str =
-                            unsafe { index_unchecked!(strings, key.into_usize()) };
+            // Push the allocated string to the strings vector
+            strings.push(allocated);
 
-                        hasher.hash_one(key_string)
-                    });
-                }
-            }
+            // Insert the key with the hash of the string that it points to, reusing the hash we made earlier
+            insert_string(&mut map, &strings, &hasher, hash, key);
         }
 
+        // The times strings were interned aren't serialized, so they count from now
+        #[cfg(feature = "timestamps")]
+        let timestamps = {
+            let mut timestamps = Timestamps::new();
+            timestamps.fill(strings.len());
+            timestamps
+        };
+
         Ok(Self {
             map,
             hasher,
             strings,
             arena,
+            borrowed: KeySet::new(),
+            stats: DedupStats::default(),
+            #[cfg(feature = "profile-interning")]
+            profile: InternProfile::default(),
+            #[cfg(feature = "timestamps")]
+            timestamps,
+            scratch: String::new(),
+            folded: StringMap::new(),
         })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{hasher::RandomState, keys::MicroSpur, Capacity, Key, MemoryLimits, Rodeo, Spur};
+    use crate::{
+        hasher::RandomState, keys::MicroSpur, Capacity, DedupStats, EscapeStyle, FixedArena,
+        FixedState, Key, LassoErrorKind, LassoResult, MemoryLimits, Rodeo, RodeoBuilder, Spur,
+    };
+    use alloc::borrow::Cow;
     use core::num::NonZeroUsize;
 
     compile! {
         if #[feature = "no-std"] {
-            use alloc::{string::ToString, vec::Vec, boxed::Box};
+            use alloc::{format, string::ToString, vec, vec::Vec, boxed::Box};
         }
     }
 
@@ -1178,130 +3386,686 @@ mod tests {
         rodeo.get_or_intern("Test8");
         rodeo.get_or_intern("Test9");
 
-        assert_eq!(rodeo.len(), rodeo.capacity());
+        assert_eq!(rodeo.len(), rodeo.capacity());
+    }
+
+    #[test]
+    fn with_hasher() {
+        let mut rodeo: Rodeo<Spur, RandomState> = Rodeo::with_hasher(RandomState::new());
+        let key = rodeo.get_or_intern("Test");
+        assert_eq!("Test", rodeo.resolve(&key));
+
+        #[cfg(not(miri))]
+        {
+            let mut rodeo: Rodeo<Spur, ahash::RandomState> =
+                Rodeo::with_hasher(ahash::RandomState::new());
+            let key = rodeo.get_or_intern("Test");
+            assert_eq!("Test", rodeo.resolve(&key));
+        }
+    }
+
+    #[test]
+    fn with_capacity_and_hasher() {
+        let mut rodeo: Rodeo<Spur, RandomState> =
+            Rodeo::with_capacity_and_hasher(Capacity::for_strings(10), RandomState::new());
+        assert_eq!(rodeo.capacity(), 10);
+
+        rodeo.get_or_intern("Test");
+        rodeo.get_or_intern("Test1");
+        rodeo.get_or_intern("Test2");
+        rodeo.get_or_intern("Test3");
+        rodeo.get_or_intern("Test4");
+        rodeo.get_or_intern("Test5");
+        rodeo.get_or_intern("Test6");
+        rodeo.get_or_intern("Test7");
+        rodeo.get_or_intern("Test8");
+        rodeo.get_or_intern("Test9");
+
+        assert_eq!(rodeo.len(), rodeo.capacity());
+
+        #[cfg(not(miri))]
+        {
+            let mut rodeo: Rodeo<Spur, ahash::RandomState> = Rodeo::with_capacity_and_hasher(
+                Capacity::for_strings(10),
+                ahash::RandomState::new(),
+            );
+            assert_eq!(rodeo.capacity(), 10);
+
+            rodeo.get_or_intern("Test");
+            rodeo.get_or_intern("Test1");
+            rodeo.get_or_intern("Test2");
+            rodeo.get_or_intern("Test3");
+            rodeo.get_or_intern("Test4");
+            rodeo.get_or_intern("Test5");
+            rodeo.get_or_intern("Test6");
+            rodeo.get_or_intern("Test7");
+            rodeo.get_or_intern("Test8");
+            rodeo.get_or_intern("Test9");
+
+            assert_eq!(rodeo.len(), rodeo.capacity());
+        }
+    }
+
+    #[test]
+    fn try_with_capacity() {
+        let rodeo: LassoResult<Rodeo<Spur>> = Rodeo::try_with_capacity(Capacity::for_strings(10));
+        assert!(rodeo.is_ok());
+
+        let rodeo: LassoResult<Rodeo<Spur>> =
+            Rodeo::try_with_capacity(Capacity::for_strings(usize::MAX));
+        assert_eq!(rodeo.unwrap_err().kind(), LassoErrorKind::FailedAllocation,);
+
+        let rodeo: LassoResult<Rodeo<Spur>> =
+            Rodeo::try_with_capacity(Capacity::for_bytes(NonZeroUsize::new(usize::MAX).unwrap()));
+        assert_eq!(rodeo.unwrap_err().kind(), LassoErrorKind::FailedAllocation,);
+    }
+
+    #[test]
+    fn try_constructors() {
+        let mut rodeos: [Rodeo<Spur>; 6] = [
+            Rodeo::try_new().unwrap(),
+            Rodeo::try_with_memory_limits(MemoryLimits::for_memory_usage(4096)).unwrap(),
+            Rodeo::try_with_capacity_and_memory_limits(
+                Capacity::for_strings(10),
+                MemoryLimits::for_memory_usage(4096),
+            )
+            .unwrap(),
+            Rodeo::try_with_hasher(RandomState::new()).unwrap(),
+            Rodeo::try_with_capacity_and_hasher(Capacity::for_strings(10), RandomState::new())
+                .unwrap(),
+            Rodeo::try_with_capacity_memory_limits_and_hasher(
+                Capacity::for_strings(10),
+                MemoryLimits::for_memory_usage(4096),
+                RandomState::new(),
+            )
+            .unwrap(),
+        ];
+
+        for rodeo in rodeos.iter_mut() {
+            let key = rodeo.get_or_intern("Test");
+            assert_eq!("Test", rodeo.resolve(&key));
+        }
+    }
+
+    #[test]
+    fn get_or_intern() {
+        let mut rodeo = Rodeo::default();
+        let a = rodeo.get_or_intern("A");
+        assert_eq!(a, rodeo.get_or_intern("A"));
+
+        let b = rodeo.get_or_intern("B");
+        assert_eq!(b, rodeo.get_or_intern("B"));
+
+        let c = rodeo.get_or_intern("C");
+        assert_eq!(c, rodeo.get_or_intern("C"));
+    }
+
+    #[test]
+    fn try_get_or_intern() {
+        let mut rodeo: Rodeo<MicroSpur> = Rodeo::new();
+
+        for i in 0..u8::MAX as usize - 1 {
+            rodeo.get_or_intern(i.to_string());
+        }
+
+        let space = rodeo.try_get_or_intern("A").unwrap();
+        assert_eq!(Ok(space), rodeo.try_get_or_intern("A"));
+        assert_eq!("A", rodeo.resolve(&space));
+
+        assert!(rodeo.try_get_or_intern("C").is_err());
+    }
+
+    #[test]
+    fn get_or_intern_chars() {
+        let mut rodeo: Rodeo<MicroSpur> = Rodeo::new();
+
+        let a = rodeo.get_or_intern("héllo");
+        assert_eq!(a, rodeo.get_or_intern_chars("héllo".chars()));
+        let b = rodeo.get_or_intern_chars("🦀 crab".chars());
+        assert_eq!(rodeo.get("🦀 crab"), Some(b));
+
+        // The scratch buffer doesn't leak into the next string
+        let c = rodeo.get_or_intern_chars(['x']);
+        assert_eq!(rodeo.resolve(&c), "x");
+        let empty = rodeo.get_or_intern_chars(core::iter::empty());
+        assert_eq!(rodeo.resolve(&empty), "");
+        assert_eq!(rodeo.len(), 4);
+
+        for i in 0..u8::MAX as usize - 4 {
+            rodeo.get_or_intern(i.to_string());
+        }
+        assert!(rodeo.try_get_or_intern_chars("full".chars()).is_err());
+        assert_eq!(rodeo.try_get_or_intern_chars(['x']), Ok(c));
+    }
+
+    #[test]
+    fn get_or_intern_static() {
+        let mut rodeo = Rodeo::default();
+        let a = rodeo.get_or_intern_static("A");
+        assert_eq!(a, rodeo.get_or_intern_static("A"));
+
+        let b = rodeo.get_or_intern_static("B");
+        assert_eq!(b, rodeo.get_or_intern_static("B"));
+
+        let c = rodeo.get_or_intern_static("C");
+        assert_eq!(c, rodeo.get_or_intern_static("C"));
+    }
+
+    #[test]
+    fn get_or_intern_cow() {
+        let mut rodeo = Rodeo::default();
+        let usage = rodeo.arena_memory_usage();
+
+        // Borrowed strings aren't copied into the arena
+        let string: &'static str = "borrowed";
+        let a = rodeo.get_or_intern_cow(Cow::Borrowed(string));
+        assert_eq!(rodeo.resolve(&a).as_ptr(), string.as_ptr());
+        assert_eq!(rodeo.get_or_intern_cow(Cow::Owned(string.to_string())), a);
+
+        let b = rodeo.get_or_intern_cow(Cow::Owned("owned".to_string()));
+        assert_eq!(rodeo.resolve(&b), "owned");
+        assert_eq!(rodeo.try_get_or_intern_cow(Cow::Borrowed("owned")), Ok(b));
+        assert_eq!(rodeo.arena_memory_usage(), usage);
+    }
+
+    #[test]
+    fn try_get_or_intern_static() {
+        let mut strings = Vec::new();
+        let mut rodeo: Rodeo<MicroSpur> = Rodeo::new();
+
+        for i in 0..u8::MAX as usize - 1 {
+            let ptr = Box::into_raw(i.to_string().into_boxed_str());
+            strings.push(ptr);
+
+            let static_ref = unsafe { &*ptr };
+            rodeo.get_or_intern_static(static_ref);
+        }
+
+        let space = rodeo.try_get_or_intern_static("A").unwrap();
+        assert_eq!(Ok(space), rodeo.try_get_or_intern_static("A"));
+        assert_eq!("A", rodeo.resolve(&space));
+
+        assert!(rodeo.try_get_or_intern_static("C").is_err());
+
+        drop(rodeo);
+        for string in strings {
+            unsafe { drop(Box::from_raw(string)) };
+        }
+    }
+
+    #[test]
+    fn debug_elides() {
+        let mut rodeo: Rodeo<MicroSpur> = Rodeo::new();
+        rodeo.get_or_intern("a");
+        rodeo.get_or_intern("b");
+        assert_eq!(
+            format!("{:?}", rodeo),
+            format!(
+                "Rodeo {{ len: 2, memory_usage: {}, strings: {{MicroSpur(1): \"a\", MicroSpur(2): \"b\"}} }}",
+                rodeo.current_memory_usage(),
+            ),
+        );
+
+        for i in 0..100_usize {
+            rodeo.get_or_intern(i.to_string());
+        }
+        let debug = format!("{:?}", rodeo);
+        assert!(debug.contains("MicroSpur(16): \"13\", ...: 86 more}"));
+        assert!(!debug.contains("\"14\""));
+
+        let pretty = format!("{:#?}", rodeo.into_resolver());
+        assert!(pretty.starts_with("RodeoResolver {\n    len: 102,\n"));
+        assert!(pretty.contains("\n        ...: 86 more,\n"));
+    }
+
+    #[test]
+    fn get_or_intern_utf8() {
+        let mut rodeo = Rodeo::default();
+        let a = rodeo.get_or_intern("ünïcödé");
+
+        assert_eq!(rodeo.get_or_intern_utf8("ünïcödé".as_bytes()), Ok(a));
+        let b = rodeo.get_or_intern_utf8(b"bytes").unwrap();
+        assert_eq!(rodeo.resolve(&b), "bytes");
+
+        // A truncated multi-byte character
+        let err = rodeo.get_or_intern_utf8(&"aü".as_bytes()[..2]).unwrap_err();
+        assert!(err.kind().is_invalid_utf8());
+        assert_eq!(err.valid_up_to(), Some(1));
+        assert_eq!(err.string_len(), Some(2));
+        assert_eq!(rodeo.len(), 2);
+    }
+
+    #[test]
+    fn get_or_intern_unescaped() {
+        let mut rodeo = Rodeo::default();
+        let a = rodeo.get_or_intern("a\"b");
+
+        assert_eq!(
+            rodeo.get_or_intern_unescaped(r#"a\"b"#, EscapeStyle::Json),
+            Ok(a)
+        );
+        assert_eq!(
+            rodeo.get_or_intern_unescaped(r"a\u{22}b", EscapeStyle::Rust),
+            Ok(a)
+        );
+        assert_eq!(
+            rodeo.get_or_intern_unescaped(r#"a""b"#, EscapeStyle::Doubled('"')),
+            Ok(a)
+        );
+
+        // The buffer is cleared between calls
+        let b = rodeo
+            .get_or_intern_unescaped("b", EscapeStyle::Rust)
+            .unwrap();
+        assert_eq!(rodeo.resolve(&b), "b");
+
+        let err = rodeo
+            .get_or_intern_unescaped(r"ok\u{110000}", EscapeStyle::Rust)
+            .unwrap_err();
+        assert!(err.kind().is_invalid_escape());
+        assert_eq!(err.valid_up_to(), Some(2));
+        assert_eq!(err.string_len(), Some(12));
+        assert_eq!(rodeo.len(), 2);
+    }
+
+    #[test]
+    fn get_ignore_ascii_case() {
+        let mut rodeo = Rodeo::default();
+        let long = "Some-Rather-Long-Header-Name-That-Spans-Multiple-Chunks-When-Hashed";
+
+        let a = rodeo.get_or_intern_preserving("Foo");
+        let b = rodeo.get_or_intern_preserving(long);
+        let c = rodeo.get_or_intern("bar");
+        assert_eq!(
+            rodeo.get_or_intern_preserving("fOO"),
+            rodeo.get("fOO").unwrap()
+        );
+
+        assert_eq!(rodeo.resolve(&a), "Foo");
+        assert_eq!(rodeo.get_ignore_ascii_case("foo"), Some(a));
+        assert_eq!(rodeo.get_ignore_ascii_case("FOO"), Some(a));
+        assert_eq!(
+            rodeo.get_ignore_ascii_case(long.to_ascii_uppercase()),
+            Some(b)
+        );
+        assert_eq!(rodeo.get_ignore_ascii_case("bar"), None);
+        assert_eq!(rodeo.get_ignore_ascii_case("fo"), None);
+
+        // Strings that are already interned can be added to the index
+        assert_eq!(rodeo.get_or_intern_preserving("bar"), c);
+        assert_eq!(rodeo.get_ignore_ascii_case("BAR"), Some(c));
+
+        // Only ASCII is folded
+        let d = rodeo.get_or_intern_preserving("ÄBC");
+        assert_eq!(rodeo.get_ignore_ascii_case("Äbc"), Some(d));
+        assert_eq!(rodeo.get_ignore_ascii_case("äbc"), None);
+
+        let clone = rodeo.clone();
+        assert_eq!(clone.get_ignore_ascii_case("foo"), Some(a));
+
+        rodeo.clear();
+        assert_eq!(rodeo.get_ignore_ascii_case("foo"), None);
+    }
+
+    #[test]
+    fn into_resolver_compact() {
+        let mut rodeo = Rodeo::default();
+        let keys: Vec<Spur> = (0..1000)
+            .map(|i| rodeo.get_or_intern(i.to_string()))
+            .collect();
+        let empty = rodeo.get_or_intern("");
+        let bytes = rodeo.strings().map(str::len).sum::<usize>();
+        assert!(rodeo.arena_memory_usage() > bytes);
+
+        let resolver = rodeo.into_resolver_compact();
+        assert_eq!(resolver.len(), 1001);
+        assert_eq!(resolver.arena_memory_usage(), bytes);
+        for (i, key) in keys.iter().enumerate() {
+            assert_eq!(resolver.resolve(key), i.to_string());
+        }
+        assert_eq!(resolver.resolve(&empty), "");
+
+        // Strings are laid out in key order
+        let (first, second) = (resolver.resolve(&keys[0]), resolver.resolve(&keys[1]));
+        assert_eq!(first.as_ptr().wrapping_add(first.len()), second.as_ptr());
+
+        let resolver = Rodeo::<Spur>::default().into_resolver_compact();
+        assert!(resolver.is_empty());
+    }
+
+    #[test]
+    fn truncate() {
+        let mut rodeo = Rodeo::default();
+        let a = rodeo.get_or_intern("a");
+        let b = rodeo.get_or_intern_preserving("Bee");
+        let b_ptr = rodeo.resolve(&b).as_ptr() as usize;
+        rodeo.get_or_intern("c");
+        rodeo.get_or_intern_static("static");
+
+        rodeo.truncate(1);
+        assert_eq!(rodeo.len(), 1);
+        assert_eq!(rodeo.get("a"), Some(a));
+        assert_eq!(rodeo.get("Bee"), None);
+        assert_eq!(rodeo.get("c"), None);
+        assert_eq!(rodeo.get("static"), None);
+        assert_eq!(rodeo.get_ignore_ascii_case("bee"), None);
+
+        // The freed keys and arena memory are reused
+        let d = rodeo.get_or_intern("d");
+        assert_eq!(d, b);
+        assert_eq!(rodeo.resolve(&d).as_ptr() as usize, b_ptr);
+
+        // Truncating to the current length or more does nothing
+        rodeo.truncate(2);
+        rodeo.truncate(10);
+        assert_eq!(rodeo.len(), 2);
+
+        rodeo.truncate(0);
+        assert!(rodeo.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "timestamps")]
+    fn timestamps() {
+        use core::sync::atomic::{AtomicU64, Ordering};
+
+        fn clock() -> u64 {
+            static TICKS: AtomicU64 = AtomicU64::new(10);
+            TICKS.fetch_add(10, Ordering::Relaxed)
+        }
+
+        let mut rodeo: Rodeo = RodeoBuilder::new().clock(clock).build();
+        let a = rodeo.get_or_intern("a");
+        let b = rodeo.get_or_intern_static("b");
+        let c = rodeo.get_or_intern("c");
+
+        // Interning a string again keeps its original time
+        assert_eq!(rodeo.get_or_intern("a"), a);
+        assert_eq!(rodeo.interned_at(&a), Some(10));
+        assert_eq!(rodeo.interned_at(&b), Some(20));
+        assert_eq!(rodeo.interned_at(&c), Some(30));
+        assert_eq!(rodeo.interned_at(&Spur::try_from_usize(3).unwrap()), None);
+
+        assert_eq!(rodeo.iter_older_than(10).count(), 0);
+        assert_eq!(
+            rodeo.iter_older_than(30).collect::<Vec<_>>(),
+            [(a, "a"), (b, "b")],
+        );
+
+        // Truncated strings lose their times and the strings reusing their keys get new ones
+        rodeo.truncate(1);
+        assert_eq!(rodeo.interned_at(&b), None);
+        assert_eq!(rodeo.get_or_intern("d"), b);
+        assert_eq!(rodeo.interned_at(&b), Some(40));
+
+        let cloned = rodeo.clone();
+        assert_eq!(cloned.interned_at(&b), Some(40));
+
+        rodeo.clear();
+        assert_eq!(rodeo.interned_at(&a), None);
+        assert_eq!(rodeo.iter_older_than(u64::MAX).count(), 0);
+        assert_eq!(rodeo.get_or_intern("e"), a);
+        assert_eq!(rodeo.interned_at(&a), Some(50));
     }
 
     #[test]
-    fn with_hasher() {
-        let mut rodeo: Rodeo<Spur, RandomState> = Rodeo::with_hasher(RandomState::new());
-        let key = rodeo.get_or_intern("Test");
-        assert_eq!("Test", rodeo.resolve(&key));
+    fn truncate_slice_of_parent() {
+        let mut rodeo = Rodeo::default();
+        let parent = rodeo.get_or_intern("hello world");
+        let len = rodeo.len();
+
+        // The slice ends at the tail of the arena along with its parent, but it was never
+        // allocated by the arena so truncating it can't free the parent's bytes
+        let world = rodeo.get_or_intern_slice_of(parent, 6..11);
+        assert_eq!(rodeo.resolve(&world), "world");
+        rodeo.truncate(len);
+
+        rodeo.get_or_intern("XXXXX");
+        assert_eq!(rodeo.resolve(&parent), "hello world");
+        assert_eq!(rodeo.get("hello world"), Some(parent));
+
+        // Static strings are skipped as well, while the arena's own strings are still freed
+        let usage = rodeo.arena_memory_usage();
+        rodeo.get_or_intern_static("static");
+        let owned = rodeo.get_or_intern("owned");
+        let owned_ptr = rodeo.resolve(&owned).as_ptr() as usize;
+        rodeo.truncate(len + 1);
+        let reused = rodeo.get_or_intern("again");
+        assert_eq!(rodeo.resolve(&reused).as_ptr() as usize, owned_ptr);
+        assert_eq!(rodeo.arena_memory_usage(), usage);
+    }
 
-        #[cfg(not(miri))]
-        {
-            let mut rodeo: Rodeo<Spur, ahash::RandomState> =
-                Rodeo::with_hasher(ahash::RandomState::new());
-            let key = rodeo.get_or_intern("Test");
-            assert_eq!("Test", rodeo.resolve(&key));
-        }
+    #[test]
+    fn truncate_nul_terminated() {
+        let mut rodeo: Rodeo = RodeoBuilder::new().nul_terminated(true).build();
+        rodeo.get_or_intern("a");
+        let b = rodeo.get_or_intern("bb");
+        let b_ptr = rodeo.resolve(&b).as_ptr() as usize;
+
+        rodeo.truncate(1);
+        let c = rodeo.get_or_intern("ccc");
+        assert_eq!(rodeo.resolve(&c).as_ptr() as usize, b_ptr);
+        assert_eq!(rodeo.resolve_cstr(&c).unwrap().to_bytes(), b"ccc");
     }
 
     #[test]
-    fn with_capacity_and_hasher() {
-        let mut rodeo: Rodeo<Spur, RandomState> =
-            Rodeo::with_capacity_and_hasher(Capacity::for_strings(10), RandomState::new());
+    fn shrink_to_fit() {
+        let mut rodeo: Rodeo =
+            Rodeo::with_capacity(Capacity::for_bytes(NonZeroUsize::new(16).unwrap()));
+        let keys: Vec<Spur> = (0..1000)
+            .map(|i| rodeo.get_or_intern_preserving(i.to_string()))
+            .collect();
+        rodeo.truncate(10);
+        let peak = rodeo.current_memory_usage();
+
+        // Shrinking keeps every remaining string findable
+        rodeo.shrink_to_fit();
+        assert!(rodeo.current_memory_usage() < peak);
         assert_eq!(rodeo.capacity(), 10);
+        for (i, key) in keys.iter().take(10).enumerate() {
+            assert_eq!(rodeo.get(i.to_string()), Some(*key));
+            assert_eq!(rodeo.get_ignore_ascii_case(i.to_string()), Some(*key));
+            assert_eq!(rodeo.resolve(key), i.to_string());
+        }
 
-        rodeo.get_or_intern("Test");
-        rodeo.get_or_intern("Test1");
-        rodeo.get_or_intern("Test2");
-        rodeo.get_or_intern("Test3");
-        rodeo.get_or_intern("Test4");
-        rodeo.get_or_intern("Test5");
-        rodeo.get_or_intern("Test6");
-        rodeo.get_or_intern("Test7");
-        rodeo.get_or_intern("Test8");
-        rodeo.get_or_intern("Test9");
+        rodeo.clear();
+        rodeo.shrink_to_fit();
+        assert_eq!(rodeo.current_memory_usage(), 0);
 
-        assert_eq!(rodeo.len(), rodeo.capacity());
+        // Buckets grow from the initial capacity again
+        let key = rodeo.get_or_intern("a");
+        assert_eq!(rodeo.resolve(&key), "a");
+        assert_eq!(rodeo.arena_memory_usage(), 32);
+    }
 
-        #[cfg(not(miri))]
-        {
-            let mut rodeo: Rodeo<Spur, ahash::RandomState> = Rodeo::with_capacity_and_hasher(
-                Capacity::for_strings(10),
-                ahash::RandomState::new(),
-            );
-            assert_eq!(rodeo.capacity(), 10);
+    #[test]
+    fn shrink_on_clear() {
+        let mut rodeo: Rodeo = RodeoBuilder::new().shrink_on_clear(true).build();
+        for i in 0..1000 {
+            rodeo.get_or_intern(i.to_string());
+        }
 
-            rodeo.get_or_intern("Test");
-            rodeo.get_or_intern("Test1");
-            rodeo.get_or_intern("Test2");
-            rodeo.get_or_intern("Test3");
-            rodeo.get_or_intern("Test4");
-            rodeo.get_or_intern("Test5");
-            rodeo.get_or_intern("Test6");
-            rodeo.get_or_intern("Test7");
-            rodeo.get_or_intern("Test8");
-            rodeo.get_or_intern("Test9");
+        rodeo.clear();
+        assert_eq!(rodeo.current_memory_usage(), 0);
+        assert!(rodeo.is_empty());
 
-            assert_eq!(rodeo.len(), rodeo.capacity());
-        }
+        let key = rodeo.get_or_intern("a");
+        assert_eq!(rodeo.resolve(&key), "a");
+
+        // Clones shrink on clear too
+        let mut cloned = rodeo.clone();
+        cloned.clear();
+        assert_eq!(cloned.current_memory_usage(), 0);
     }
 
     #[test]
-    fn get_or_intern() {
-        let mut rodeo = Rodeo::default();
-        let a = rodeo.get_or_intern("A");
-        assert_eq!(a, rodeo.get_or_intern("A"));
+    fn alloc_slice() {
+        let mut rodeo: Rodeo =
+            Rodeo::with_capacity(Capacity::for_bytes(NonZeroUsize::new(16).unwrap()));
+
+        let mut expected = Vec::new();
+        for i in 0..100_u64 {
+            let key = rodeo.get_or_intern(i.to_string());
+            let bytes = rodeo.alloc_bytes(&[i as u8; 3]).as_ptr() as usize;
+            let values = rodeo.alloc_slice(&[i, i * 2]).as_ptr() as usize;
+            assert_eq!(values % core::mem::align_of::<u64>(), 0);
+            let wide = rodeo.alloc_slice(&[u128::from(i)]).as_ptr() as usize;
+            assert_eq!(wide % core::mem::align_of::<u128>(), 0);
+
+            expected.push((key, bytes, values, wide));
+        }
 
-        let b = rodeo.get_or_intern("B");
-        assert_eq!(b, rodeo.get_or_intern("B"));
+        // Nothing allocated overwrote anything else
+        for (i, &(key, bytes, values, wide)) in expected.iter().enumerate() {
+            assert_eq!(rodeo.resolve(&key), i.to_string());
+            unsafe {
+                assert_eq!(*(bytes as *const [u8; 3]), [i as u8; 3]);
+                assert_eq!(*(values as *const [u64; 2]), [i as u64, i as u64 * 2]);
+                assert_eq!(*(wide as *const u128), i as u128);
+            }
+        }
 
-        let c = rodeo.get_or_intern("C");
-        assert_eq!(c, rodeo.get_or_intern("C"));
+        assert!(rodeo.alloc_bytes(&[]).is_empty());
+        assert_eq!(rodeo.alloc_slice(&[(); 3]), [(); 3]);
     }
 
     #[test]
-    fn try_get_or_intern() {
-        let mut rodeo: Rodeo<MicroSpur> = Rodeo::new();
-
-        for i in 0..u8::MAX as usize - 1 {
-            rodeo.get_or_intern(i.to_string());
-        }
+    fn alloc_slice_memory_limits() {
+        let mut rodeo: Rodeo = Rodeo::with_capacity_and_memory_limits(
+            Capacity::for_bytes(NonZeroUsize::new(8).unwrap()),
+            MemoryLimits::for_memory_usage(8),
+        );
 
-        let space = rodeo.try_get_or_intern("A").unwrap();
-        assert_eq!(Ok(space), rodeo.try_get_or_intern("A"));
-        assert_eq!("A", rodeo.resolve(&space));
+        assert!(rodeo.try_alloc_bytes(&[0; 9]).is_err());
+        assert_eq!(rodeo.try_alloc_bytes(&[1; 4]).unwrap(), [1; 4]);
+        assert!(rodeo.try_get_or_intern("12345").is_err());
+        assert!(rodeo.try_get_or_intern("1234").is_ok());
 
-        assert!(rodeo.try_get_or_intern("C").is_err());
+        // Fixed arenas can hold slices too
+        let mut rodeo: Rodeo = Rodeo::with_fixed_arena(FixedArena::<64>::new(4));
+        assert_eq!(rodeo.alloc_slice(&[1_u32, 2]), [1, 2]);
+        assert!(rodeo.try_alloc_bytes(&[0; 64]).is_err());
     }
 
     #[test]
-    fn get_or_intern_static() {
+    fn push_unchecked() {
         let mut rodeo = Rodeo::default();
-        let a = rodeo.get_or_intern_static("A");
-        assert_eq!(a, rodeo.get_or_intern_static("A"));
+        let a = rodeo.get_or_intern("a");
 
-        let b = rodeo.get_or_intern_static("B");
-        assert_eq!(b, rodeo.get_or_intern_static("B"));
+        // Safety: None of the strings are interned yet
+        let keys = ["b", "", "c"].map(|string| unsafe { rodeo.push_unchecked(string) });
+        assert_eq!(rodeo.len(), 4);
+        assert_eq!(rodeo.get("a"), Some(a));
+        for (key, string) in keys.iter().zip(["b", "", "c"]) {
+            assert_eq!(rodeo.resolve(key), string);
+            assert_eq!(rodeo.get(string), Some(*key));
+        }
+        assert_eq!(rodeo.get_or_intern("b"), keys[0]);
+        assert_eq!(rodeo.dedup_stats().misses(), 4);
 
-        let c = rodeo.get_or_intern_static("C");
-        assert_eq!(c, rodeo.get_or_intern_static("C"));
+        let mut rodeo: Rodeo = Rodeo::with_capacity_and_memory_limits(
+            Capacity::for_bytes(NonZeroUsize::new(4).unwrap()),
+            MemoryLimits::for_memory_usage(4),
+        );
+        // Safety: None of the strings are interned yet
+        unsafe {
+            assert!(rodeo.try_push_unchecked("1234").is_ok());
+            assert_eq!(
+                rodeo.try_push_unchecked("5").unwrap_err().kind(),
+                LassoErrorKind::MemoryLimitReached,
+            );
+        }
     }
 
     #[test]
-    fn try_get_or_intern_static() {
-        let mut strings = Vec::new();
-        let mut rodeo: Rodeo<MicroSpur> = Rodeo::new();
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "pushed a string that was already interned")]
+    fn push_unchecked_duplicate() {
+        let mut rodeo: Rodeo = Rodeo::new();
+        rodeo.get_or_intern("a");
 
-        for i in 0..u8::MAX as usize - 1 {
-            let ptr = Box::into_raw(i.to_string().into_boxed_str());
-            strings.push(ptr);
+        // Safety: It isn't, but debug builds catch it before anything happens
+        unsafe { rodeo.push_unchecked("a") };
+    }
 
-            let static_ref = unsafe { &*ptr };
-            rodeo.get_or_intern_static(static_ref);
-        }
+    #[test]
+    fn extend_with_keys() {
+        let key = |index| Spur::try_from_usize(index).unwrap();
 
-        let space = rodeo.try_get_or_intern_static("A").unwrap();
-        assert_eq!(Ok(space), rodeo.try_get_or_intern_static("A"));
-        assert_eq!("A", rodeo.resolve(&space));
+        let mut rodeo = Rodeo::default();
+        rodeo.get_or_intern("a");
+        rodeo
+            .extend_with_keys([(key(2), "c"), (key(1), "b")])
+            .unwrap();
+        assert_eq!(rodeo.get("b"), Some(key(1)));
+        assert_eq!(rodeo.resolve(&key(2)), "c");
+        rodeo.extend_with_keys(Vec::<(Spur, &str)>::new()).unwrap();
+        let usage = rodeo.arena_memory_usage();
+
+        // Every failure leaves the interner as it was
+        let invalid = [
+            (vec![(key(3), "d"), (key(5), "f")], 4),
+            (vec![(key(3), "d"), (key(3), "e")], 3),
+            (vec![(key(2), "d")], 2),
+            (vec![(key(3), "d"), (key(4), "a")], 4),
+            (vec![(key(3), "d"), (key(4), "d")], 4),
+        ];
+        for (pairs, invalid_key) in invalid {
+            let err = rodeo.extend_with_keys(pairs).unwrap_err();
+            assert_eq!(err.kind(), LassoErrorKind::InvalidKey);
+            assert_eq!(err.key(), Some(invalid_key));
+
+            assert_eq!(rodeo.len(), 3);
+            assert_eq!(rodeo.get("d"), None);
+            assert_eq!(rodeo.arena_memory_usage(), usage);
+        }
+    }
 
-        assert!(rodeo.try_get_or_intern_static("C").is_err());
+    #[test]
+    fn fixed_arena_bytes() {
+        let mut rodeo: Rodeo = Rodeo::with_fixed_arena(FixedArena::<16>::new(100));
+        let memory_usage = rodeo.current_memory_usage();
+
+        let a = rodeo.get_or_intern("0123456789");
+        assert_eq!(rodeo.try_get_or_intern("0123456789"), Ok(a));
+
+        // Strings too large for the remaining space are never spilled into a new bucket
+        let err = rodeo.try_get_or_intern("abcdefghij").unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::MemoryLimitReached);
+        let err = rodeo.try_get_or_intern("a".repeat(100)).unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::MemoryLimitReached);
+
+        // But smaller strings still fit
+        let b = rodeo.get_or_intern("abcdef");
+        assert!(rodeo.try_get_or_intern("g").is_err());
+
+        assert_eq!(rodeo.current_memory_usage(), memory_usage);
+        assert_eq!(rodeo.resolve(&a), "0123456789");
+        assert_eq!(rodeo.resolve(&b), "abcdef");
+    }
 
-        drop(rodeo);
-        for string in strings {
-            unsafe { drop(Box::from_raw(string)) };
-        }
+    #[test]
+    fn fixed_arena_strings() {
+        let mut rodeo: Rodeo = Rodeo::with_fixed_arena(FixedArena::<1024>::new(3));
+        let strings_capacity = rodeo.strings.capacity();
+        let map_capacity = rodeo.map.capacity();
+
+        let keys: Vec<Spur> = ["a", "b", "c"]
+            .iter()
+            .map(|s| rodeo.get_or_intern(s))
+            .collect();
+
+        let err = rodeo.try_get_or_intern("d").unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::MemoryLimitReached);
+        let err = rodeo.try_get_or_intern_static("d").unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::MemoryLimitReached);
+
+        // Existing strings can still be looked up
+        assert_eq!(rodeo.try_get_or_intern("b"), Ok(keys[1]));
+        assert_eq!(rodeo.try_get_or_intern_static("c"), Ok(keys[2]));
+
+        assert_eq!(rodeo.strings.capacity(), strings_capacity);
+        assert_eq!(rodeo.map.capacity(), map_capacity);
     }
 
     #[test]
@@ -1337,6 +4101,119 @@ mod tests {
         assert_eq!(None, rodeo.try_resolve(&Spur::try_from_usize(100).unwrap()));
     }
 
+    #[test]
+    fn with_seed_strings() {
+        let table = ["fn", "let", "", "struct"];
+        let (mut rodeo, seeds) = Rodeo::<Spur>::with_seed_strings(&table);
+        assert_eq!(rodeo.len(), table.len());
+        assert_eq!(seeds.len(), table.len());
+
+        for (index, string) in table.iter().enumerate() {
+            let key = seeds.get(index).unwrap();
+            assert_eq!(rodeo.resolve(&key), *string);
+            assert_eq!(rodeo.get_or_intern(string), key);
+            assert_eq!(seeds.index_of(key), Some(index));
+        }
+        assert_eq!(seeds.get(table.len()), None);
+        assert_eq!(
+            seeds.iter().collect::<Vec<_>>(),
+            rodeo.iter().map(|(key, _)| key).collect::<Vec<_>>()
+        );
+
+        let other = rodeo.get_or_intern("impl");
+        assert!(!seeds.contains(other));
+        assert_eq!(seeds.index_of(other), None);
+
+        let (rodeo, seeds) = Rodeo::<Spur>::with_seed_strings(&[]);
+        assert!(rodeo.is_empty() && seeds.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(not(miri))]
+    fn with_seed_strings_duplicates() {
+        Rodeo::<Spur>::with_seed_strings(&["a", "b", "a"]);
+    }
+
+    #[test]
+    fn get_or_intern_slice_of() {
+        let mut rodeo = Rodeo::default();
+        let line = rodeo.get_or_intern("fn main() {}");
+        let existing = rodeo.get_or_intern("main");
+        let arena_usage = rodeo.arena_memory_usage();
+
+        // Slices that are already interned get their existing key
+        assert_eq!(rodeo.get_or_intern_slice_of(line, 3..7), existing);
+
+        let keyword = rodeo.get_or_intern_slice_of(line, 0..2);
+        assert_eq!(rodeo.resolve(&keyword), "fn");
+        assert_eq!(rodeo.get("fn"), Some(keyword));
+        assert_eq!(rodeo.get_or_intern("fn"), keyword);
+
+        // Slices of slices work as well
+        let letter = rodeo.get_or_intern_slice_of(keyword, 1..2);
+        assert_eq!(rodeo.resolve(&letter), "n");
+
+        let empty = rodeo.get_or_intern_slice_of(line, 5..5);
+        assert_eq!(rodeo.resolve(&empty), "");
+
+        assert_eq!(rodeo.len(), 5);
+        assert_eq!(rodeo.arena_memory_usage(), arena_usage);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(not(miri))]
+    fn get_or_intern_slice_of_out_of_bounds() {
+        let mut rodeo = Rodeo::default();
+        let key = rodeo.get_or_intern("abc");
+        rodeo.get_or_intern_slice_of(key, 2..4);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(not(miri))]
+    fn get_or_intern_slice_of_char_boundary() {
+        let mut rodeo = Rodeo::default();
+        let key = rodeo.get_or_intern("\u{1F980}");
+        rodeo.get_or_intern_slice_of(key, 0..1);
+    }
+
+    #[test]
+    fn try_get_or_intern_slice_of_errors() {
+        let mut rodeo = Rodeo::default();
+        let crab = rodeo.get_or_intern("\u{1F980}");
+
+        let err = rodeo
+            .try_get_or_intern_slice_of(Spur::try_from_usize(1).unwrap(), 0..1)
+            .unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::InvalidKey);
+        assert_eq!(err.key(), Some(1));
+
+        let err = rodeo.try_get_or_intern_slice_of(crab, 2..5).unwrap_err();
+        assert!(err.kind().is_invalid_range());
+        let err = rodeo.try_get_or_intern_slice_of(crab, 0..1).unwrap_err();
+        assert!(err.kind().is_invalid_range());
+
+        // Nothing was interned
+        assert_eq!(rodeo.len(), 1);
+    }
+
+    #[test]
+    fn resolve_many() {
+        let mut rodeo = Rodeo::default();
+        let a = rodeo.get_or_intern("A");
+        let b = rodeo.get_or_intern("B");
+
+        assert_eq!(rodeo.resolve_many(&[b, a, a]), ["B", "A", "A"]);
+        assert!(rodeo.resolve_many(&[]).is_empty());
+        assert_eq!(rodeo.try_resolve_many(&[a, b]), Some(vec!["A", "B"]));
+        assert_eq!(
+            rodeo.try_resolve_many(&[a, Spur::try_from_usize(100).unwrap()]),
+            None
+        );
+    }
+
     #[test]
     fn resolve_unchecked() {
         let mut rodeo = Rodeo::default();
@@ -1604,10 +4481,227 @@ mod tests {
 
         rodeo.get_or_intern("0123456789");
 
-        assert_eq!(rodeo.current_memory_usage(), 10);
+        assert_eq!(rodeo.arena_memory_usage(), 10);
         assert_eq!(rodeo.max_memory_usage(), 10);
     }
 
+    #[test]
+    fn memory_stats() {
+        let mut rodeo = Rodeo::default();
+        rodeo.get_or_intern("abc");
+        rodeo.get_or_intern_static("defgh");
+
+        let stats = rodeo.memory_stats();
+        assert_eq!(stats.arena_bytes(), rodeo.arena_memory_usage());
+        assert_eq!(stats.string_bytes(), 8);
+        assert_eq!(stats.total_bytes(), rodeo.current_memory_usage());
+    }
+
+    #[test]
+    #[cfg(feature = "deepsize")]
+    fn deepsize() {
+        use deepsize::DeepSizeOf;
+
+        let mut rodeo = Rodeo::default();
+        rodeo.get_or_intern("abc");
+
+        assert_eq!(
+            rodeo.deep_size_of(),
+            core::mem::size_of::<Rodeo>() + rodeo.current_memory_usage(),
+        );
+    }
+
+    #[test]
+    fn length_histogram() {
+        let mut rodeo = Rodeo::default();
+        assert_eq!(rodeo.length_histogram(&[1, 2]), vec![0, 0, 0]);
+
+        for string in ["", "a", "bc", "def", "ghij", "kl"] {
+            rodeo.get_or_intern(string);
+        }
+
+        assert_eq!(rodeo.length_histogram(&[]), vec![6]);
+        assert_eq!(rodeo.length_histogram(&[0, 2, 3]), vec![1, 3, 1, 1]);
+        assert_eq!(rodeo.length_histogram(&[2, 2]), vec![4, 0, 2]);
+    }
+
+    #[test]
+    fn top_longest() {
+        let mut rodeo = Rodeo::default();
+        assert!(rodeo.top_longest(3).is_empty());
+
+        let keys = ["ab", "cdef", "gh", "ijk", "l"].map(|string| rodeo.get_or_intern(string));
+
+        assert!(rodeo.top_longest(0).is_empty());
+        assert_eq!(
+            rodeo.top_longest(3),
+            vec![(keys[1], "cdef"), (keys[3], "ijk"), (keys[0], "ab")],
+        );
+        assert_eq!(rodeo.top_longest(10).len(), 5);
+        assert_eq!(rodeo.top_longest(10)[4], (keys[4], "l"));
+    }
+
+    #[test]
+    #[cfg(feature = "compact_str")]
+    fn compact_str() {
+        use compact_str::CompactString;
+
+        let strings = [CompactString::new("a"), CompactString::new("b".repeat(100))];
+        let mut rodeo: Rodeo = strings.iter().cloned().collect();
+        rodeo.extend([CompactString::new("c")]);
+
+        let keys: Vec<Spur> = ["a", "b".repeat(100).as_str(), "c"]
+            .iter()
+            .map(|string| rodeo.get(string).unwrap())
+            .collect();
+        assert_eq!(rodeo.resolve_compact(&keys[0]), strings[0]);
+        assert_eq!(rodeo.resolve_compact(&keys[1]), strings[1]);
+        assert!(rodeo.resolve_compact(&keys[1]).is_heap_allocated());
+        assert_eq!(rodeo.try_resolve_compact(&keys[2]).as_deref(), Some("c"));
+        assert_eq!(
+            rodeo.try_resolve_compact(&Spur::try_from_usize(10).unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "profile-interning")]
+    fn profiling_report() {
+        let mut rodeo: Rodeo = Rodeo::default();
+        for string in ["a", "bc", "a"] {
+            rodeo.get_or_intern(string);
+        }
+        let line = line!() - 2;
+        rodeo.get_or_intern_chars("def".chars());
+        crate::Interner::get_or_intern(&mut rodeo, "ghij");
+
+        let report = rodeo.profiling_report();
+        assert_eq!(report.len(), 3);
+        // Ties in bytes go to the call site that interned more often
+        assert_eq!((report[0].interns(), report[0].bytes()), (3, 4));
+        assert_eq!(report[0].location().line(), line);
+        assert_eq!(report[0].location().file(), file!());
+        assert_eq!((report[1].interns(), report[1].bytes()), (1, 4));
+        assert_eq!((report[2].interns(), report[2].bytes()), (1, 3));
+
+        let cloned = rodeo.clone();
+        rodeo.reset_profiling();
+        assert!(rodeo.profiling_report().is_empty());
+        assert_eq!(cloned.profiling_report(), report);
+    }
+
+    #[test]
+    fn dedup_stats() {
+        let mut rodeo = Rodeo::default();
+        assert_eq!(rodeo.dedup_stats(), DedupStats::default());
+
+        rodeo.get_or_intern("abc");
+        rodeo.get_or_intern("abc");
+        rodeo.get_or_intern_static("abc");
+        rodeo.get_or_intern_static("static");
+        rodeo.get_or_intern("static");
+
+        let stats = rodeo.dedup_stats();
+        assert_eq!(stats.hits(), 3);
+        assert_eq!(stats.misses(), 2);
+        assert_eq!(stats.bytes_submitted(), 21);
+        assert_eq!(stats.bytes_stored(), 9);
+        assert_eq!(stats.bytes_saved(), 12);
+
+        // Lookups and failed interning aren't counted
+        let mut limited: Rodeo = Rodeo::with_capacity_and_memory_limits(
+            Capacity::for_bytes(NonZeroUsize::new(1).unwrap()),
+            MemoryLimits::for_memory_usage(1),
+        );
+        limited.get("abc");
+        assert!(limited.try_get_or_intern("abc").is_err());
+        assert_eq!(limited.dedup_stats(), DedupStats::default());
+
+        assert_eq!(rodeo.clone().dedup_stats(), stats);
+        rodeo.reset_dedup_stats();
+        assert_eq!(rodeo.dedup_stats(), DedupStats::default());
+    }
+
+    #[test]
+    fn extend_with_stats() {
+        let mut rodeo = Rodeo::default();
+        let (new_keys, duplicates, bytes_saved) = rodeo.extend_with_stats(Vec::<&str>::new());
+        assert!(new_keys.is_empty());
+        assert_eq!((duplicates, bytes_saved), (0, 0));
+
+        let (new_keys, duplicates, bytes_saved) =
+            rodeo.extend_with_stats(["one", "two", "one", "three", "two", "one"]);
+        assert_eq!(
+            new_keys,
+            ["one", "two", "three"].map(|string| rodeo.get(string).unwrap()),
+        );
+        assert_eq!(duplicates, 3);
+        assert_eq!(bytes_saved, 9);
+
+        let (new_keys, duplicates, bytes_saved) = rodeo.extend_with_stats(["four", "one"]);
+        assert_eq!(new_keys, [rodeo.get("four").unwrap()]);
+        assert_eq!((duplicates, bytes_saved), (1, 3));
+        assert_eq!(rodeo.dedup_stats().hits(), 4);
+    }
+
+    #[test]
+    fn total_budget() {
+        let mut rodeo: Rodeo<Spur> =
+            Rodeo::with_memory_limits(MemoryLimits::total_budget(16 * 1024));
+        assert!(rodeo.current_memory_usage() > rodeo.arena_memory_usage());
+
+        let mut interned = 0;
+        while rodeo.try_get_or_intern(interned.to_string()).is_ok() {
+            interned += 1;
+        }
+
+        let err = rodeo.try_get_or_intern("new").unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::MemoryLimitReached);
+        assert!(rodeo.try_get_or_intern_static("static").is_err());
+        assert!(rodeo.current_memory_usage() <= 16 * 1024);
+        assert_eq!(rodeo.len(), interned);
+
+        // Lifting the total budget only counts the arena again
+        rodeo.set_memory_limits(MemoryLimits::for_memory_usage(16 * 1024));
+        assert!(rodeo.try_get_or_intern("new").is_ok());
+    }
+
+    #[test]
+    fn strings_at_memory_limit() {
+        let mut rodeo: Rodeo = Rodeo::with_capacity_and_memory_limits(
+            Capacity::for_bytes(NonZeroUsize::new(4).unwrap()),
+            MemoryLimits::for_memory_usage(4),
+        );
+        rodeo.get_or_intern("abc");
+
+        // A single byte fits exactly into the last byte of the arena
+        let d = rodeo.try_get_or_intern("d").unwrap();
+        let err = rodeo.try_get_or_intern("e").unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::MemoryLimitReached);
+
+        // Empty strings take no memory, so they can always be interned
+        let empty = rodeo.try_get_or_intern("").unwrap();
+        assert_eq!(rodeo.try_get_or_intern(""), Ok(empty));
+        assert_eq!(rodeo.try_get_or_intern_static(""), Ok(empty));
+        assert_eq!(rodeo.resolve(&empty), "");
+        assert_eq!(rodeo.try_get_or_intern("d"), Ok(d));
+        assert_eq!(rodeo.arena_memory_usage(), 4);
+    }
+
+    #[test]
+    fn empty_strings_within_total_budget() {
+        let mut rodeo: Rodeo = Rodeo::with_memory_limits(MemoryLimits::total_budget(16 * 1024));
+        let mut interned = 0;
+        while rodeo.try_get_or_intern(interned.to_string()).is_ok() {
+            interned += 1;
+        }
+
+        let empty = rodeo.try_get_or_intern("").unwrap();
+        assert_eq!(rodeo.try_get_or_intern_static(""), Ok(empty));
+        assert_eq!(rodeo.len(), interned + 1);
+        assert!(rodeo.try_get_or_intern("a").is_err());
+    }
+
     #[test]
     fn contains() {
         let mut rodeo = Rodeo::default();
@@ -1774,4 +4868,41 @@ mod tests {
         b.get_or_intern("c");
         assert_eq!(a, b.into_reader());
     }
+
+    #[test]
+    fn eq_across_hashers_and_keys() {
+        let mut a: Rodeo<Spur> = Rodeo::new();
+        a.get_or_intern("a");
+        a.get_or_intern("b");
+        let mut b: Rodeo<MicroSpur, FixedState> = Rodeo::with_hasher(FixedState::default());
+        b.get_or_intern("a");
+        b.get_or_intern("b");
+
+        assert_eq!(a, b);
+        assert_eq!(a, b.clone().into_reader());
+        assert_eq!(a.clone().into_resolver(), b.clone().into_reader());
+        assert_eq!(a.into_reader(), b.into_resolver());
+    }
+
+    #[test]
+    fn equivalent_content() {
+        let mut a = Rodeo::default();
+        a.get_or_intern("a");
+        a.get_or_intern("b");
+        let mut b: Rodeo<MicroSpur, FixedState> = Rodeo::with_hasher(FixedState::default());
+        b.get_or_intern("b");
+        b.get_or_intern("a");
+
+        assert_ne!(a, b);
+        assert!(a.equivalent_content(&b));
+        assert!(b.equivalent_content(&a));
+        assert!(a.clone().into_resolver().equivalent_content(&b));
+        assert!(a.clone().into_reader().equivalent_content(&b));
+
+        b.get_or_intern("c");
+        assert!(!a.equivalent_content(&b));
+        a.get_or_intern("d");
+        assert!(!a.equivalent_content(&b));
+        assert!(Rodeo::default().equivalent_content(&Rodeo::default()));
+    }
 }
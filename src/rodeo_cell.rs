@@ -0,0 +1,284 @@
+use crate::RodeoResolver;
+use core::fmt::{Debug, Formatter, Result as FmtResult};
+use crate::sync::RwLock;
+use std::sync::Arc;
+
+/// A shared slot holding the current snapshot of an interner, which can be atomically
+/// replaced while other threads are reading from it
+///
+/// Meant for interners that are periodically rebuilt from scratch, like a [`RodeoReader`]
+/// built from fresh reference data. [`RodeoCell::load`] hands out the current snapshot as an
+/// [`Arc`], which implements [`Reader`] and [`Resolver`] whenever the interner does, so call
+/// sites don't need to know which snapshot they're using. [`RodeoCell::store`] swaps in a new
+/// snapshot without waiting for the old one to stop being used, threads that loaded it keep
+/// using it until they drop their `Arc`
+///
+/// Loading and storing only hold a lock for as long as it takes to clone or replace the `Arc`,
+/// so request threads are never stalled by a rebuild
+///
+/// Keys are only valid for the snapshot that created them, keys from an old snapshot may
+/// resolve to different strings in a new one
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{Reader, Resolver, Rodeo, RodeoCell};
+///
+/// let rodeo: Rodeo = ["foo", "bar"].into_iter().collect();
+/// let cell = RodeoCell::new(rodeo.into_reader());
+///
+/// let snapshot = cell.load();
+/// let key = snapshot.get("foo").unwrap();
+///
+/// // Swap in a rebuilt interner
+/// let rodeo: Rodeo = ["foo", "bar", "baz"].into_iter().collect();
+/// cell.store(rodeo.into_reader());
+///
+/// // The old snapshot is still usable
+/// assert_eq!(snapshot.resolve(&key), "foo");
+/// assert!(!snapshot.contains("baz"));
+///
+/// // And new loads see the new interner
+/// assert!(cell.load().contains("baz"));
+/// ```
+///
+/// [`RodeoReader`]: crate::RodeoReader
+/// [`Reader`]: crate::Reader
+/// [`Resolver`]: crate::Resolver
+pub struct RodeoCell<R = RodeoResolver> {
+    current: RwLock<Arc<R>>,
+}
+
+impl<R> RodeoCell<R> {
+    /// Creates a new `RodeoCell` holding `rodeo`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, RodeoCell};
+    ///
+    /// let cell = RodeoCell::new(Rodeo::default().into_resolver());
+    /// assert!(cell.load().is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new(rodeo: R) -> Self {
+        Self::from_arc(Arc::new(rodeo))
+    }
+
+    /// Creates a new `RodeoCell` holding an already shared interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, RodeoCell};
+    /// use std::sync::Arc;
+    ///
+    /// let resolver = Arc::new(Rodeo::default().into_resolver());
+    /// let cell = RodeoCell::from_arc(Arc::clone(&resolver));
+    /// assert!(Arc::ptr_eq(&cell.load(), &resolver));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn from_arc(rodeo: Arc<R>) -> Self {
+        Self {
+            current: RwLock::new(rodeo),
+        }
+    }
+
+    /// Get the current snapshot of the interner
+    ///
+    /// The snapshot stays alive for as long as the returned `Arc` does, even if a new
+    /// interner is stored in the meantime
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Resolver, Rodeo, RodeoCell};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    ///
+    /// let cell = RodeoCell::new(rodeo.into_resolver());
+    /// assert_eq!(cell.load().resolve(&key), "Hello, world!");
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn load(&self) -> Arc<R> {
+        Arc::clone(&self.current.read())
+    }
+
+    /// Replaces the current interner with `rodeo`
+    ///
+    /// Snapshots that were already loaded keep using the old interner, which is dropped
+    /// once the last of them is
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, RodeoCell};
+    ///
+    /// let cell = RodeoCell::new(Rodeo::default().into_resolver());
+    ///
+    /// let rodeo: Rodeo = ["foo"].into_iter().collect();
+    /// cell.store(rodeo.into_resolver());
+    /// assert_eq!(cell.load().len(), 1);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn store(&self, rodeo: R) {
+        drop(self.swap(Arc::new(rodeo)));
+    }
+
+    /// Replaces the current interner with `rodeo`, returning the interner that was replaced
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, RodeoCell};
+    /// use std::sync::Arc;
+    ///
+    /// let cell = RodeoCell::new(Rodeo::default().into_resolver());
+    ///
+    /// let rodeo: Rodeo = ["foo"].into_iter().collect();
+    /// let old = cell.swap(Arc::new(rodeo.into_resolver()));
+    /// assert!(old.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn swap(&self, rodeo: Arc<R>) -> Arc<R> {
+        // The old interner is returned instead of dropped so that dropping it doesn't
+        // happen while holding the lock
+        core::mem::replace(&mut *self.current.write(), rodeo)
+    }
+
+    /// Consumes the cell, returning the current interner
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, RodeoCell};
+    ///
+    /// let cell = RodeoCell::new(Rodeo::default().into_resolver());
+    /// assert!(cell.into_inner().is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn into_inner(self) -> Arc<R> {
+        self.current.into_inner()
+    }
+}
+
+impl<R: Default> Default for RodeoCell<R> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new(R::default())
+    }
+}
+
+impl<R> From<R> for RodeoCell<R> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from(rodeo: R) -> Self {
+        Self::new(rodeo)
+    }
+}
+
+impl<R> From<Arc<R>> for RodeoCell<R> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from(rodeo: Arc<R>) -> Self {
+        Self::from_arc(rodeo)
+    }
+}
+
+impl<R: Debug> Debug for RodeoCell<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("RodeoCell")
+            .field("current", &self.load())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Reader, Resolver, Rodeo, RodeoCell, RodeoReader};
+    use std::{sync::Arc, thread};
+
+    fn reader(strings: &[&str]) -> RodeoReader {
+        strings.iter().copied().collect::<Rodeo>().into_reader()
+    }
+
+    #[test]
+    fn store_and_load() {
+        let cell = RodeoCell::new(reader(&["a", "b"]));
+        let old = cell.load();
+        assert_eq!(old.len(), 2);
+
+        cell.store(reader(&["c"]));
+        let new = cell.load();
+        assert_eq!(new.len(), 1);
+        assert!(new.contains("c"));
+
+        // The old snapshot is untouched
+        assert!(old.contains("a"));
+        assert!(!old.contains("c"));
+        assert_eq!(Arc::strong_count(&old), 1);
+    }
+
+    #[test]
+    fn swap() {
+        let cell = RodeoCell::new(reader(&["a"]));
+        let first = cell.load();
+
+        let old = cell.swap(Arc::new(reader(&["b"])));
+        assert!(Arc::ptr_eq(&old, &first));
+        assert!(cell.into_inner().contains("b"));
+    }
+
+    #[test]
+    fn generic_resolver() {
+        fn resolve_first<R: Resolver>(resolver: &R) -> &str {
+            resolver.resolve(&crate::Key::try_from_usize(0).unwrap())
+        }
+
+        let cell = RodeoCell::new(reader(&["a"]));
+        assert_eq!(resolve_first(&cell.load()), "a");
+
+        cell.store(reader(&["b"]));
+        assert_eq!(resolve_first(&cell.load()), "b");
+    }
+
+    #[test]
+    #[cfg(not(miri))]
+    fn concurrent_store() {
+        let cell = Arc::new(RodeoCell::new(reader(&["0"])));
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let cell = Arc::clone(&cell);
+                thread::spawn(move || {
+                    let mut last = 0;
+                    for _ in 0..1000 {
+                        // Every snapshot is internally consistent and snapshots never go back
+                        let snapshot = cell.load();
+                        let generation: usize = snapshot.strings().next_back().unwrap().parse().unwrap();
+                        assert_eq!(snapshot.len(), generation + 1);
+                        assert!(generation >= last);
+                        last = generation;
+                    }
+                })
+            })
+            .collect();
+
+        let mut strings = vec![String::from("0")];
+        for generation in 1..100 {
+            strings.push(generation.to_string());
+            let strings: Vec<&str> = strings.iter().map(String::as_str).collect();
+            cell.store(reader(&strings));
+        }
+
+        for reader in readers {
+            reader.join().unwrap();
+        }
+    }
+}
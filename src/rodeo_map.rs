@@ -0,0 +1,471 @@
+use crate::{
+    hasher::RandomState,
+    keys::{Key, Spur},
+    util::{Iter, Strings},
+    Capacity, LassoResult, Rodeo,
+};
+use core::{
+    fmt::{self, Debug},
+    hash::BuildHasher,
+    iter::{FromIterator, FusedIterator},
+};
+
+/// A [`Rodeo`] with an API shaped like a `HashMap<String, K>`
+///
+/// Code that uses a `HashMap` from strings to ids as a makeshift interner can switch to a
+/// `RodeoMap` without rewriting every call site at once, since it offers the familiar
+/// `insert`, `get`, `contains_key` and `iter` methods. The only difference is that values
+/// are assigned by the interner instead of by the caller, so [`RodeoMap::insert`] takes
+/// just the string and returns its key
+///
+/// Once call sites have been migrated the underlying [`Rodeo`] can be taken out with
+/// [`RodeoMap::into_inner`] or borrowed with [`RodeoMap::rodeo`]
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::RodeoMap;
+///
+/// let mut map = RodeoMap::default();
+/// let key = map.insert("Hello, world!");
+///
+/// assert_eq!(map.insert("Hello, world!"), key);
+/// assert_eq!(map.get("Hello, world!"), Some(key));
+/// assert!(map.contains_key("Hello, world!"));
+/// assert_eq!(map.iter().collect::<Vec<_>>(), vec![("Hello, world!", key)]);
+/// ```
+///
+/// [`Rodeo`]: crate::Rodeo
+pub struct RodeoMap<K = Spur, S = RandomState> {
+    rodeo: Rodeo<K, S>,
+}
+
+impl<K> RodeoMap<K, RandomState>
+where
+    K: Key,
+{
+    /// Create an empty `RodeoMap`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{RodeoMap, Spur};
+    ///
+    /// let map: RodeoMap<Spur> = RodeoMap::new();
+    /// assert!(map.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new() -> Self {
+        Self {
+            rodeo: Rodeo::new(),
+        }
+    }
+
+    /// Create an empty `RodeoMap` with the specified capacity
+    ///
+    /// See [`Capacity`] for more details
+    ///
+    /// [`Capacity`]: crate::Capacity
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity(capacity: Capacity) -> Self {
+        Self {
+            rodeo: Rodeo::with_capacity(capacity),
+        }
+    }
+}
+
+impl<K, S> RodeoMap<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    /// Create an empty `RodeoMap` which will use the given hasher
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{RodeoMap, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let map: RodeoMap<Spur, RandomState> = RodeoMap::with_hasher(RandomState::new());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            rodeo: Rodeo::with_hasher(hash_builder),
+        }
+    }
+
+    /// Create an empty `RodeoMap` with the specified capacity which will use the given hasher
+    ///
+    /// See [`Capacity`] for more details
+    ///
+    /// [`Capacity`]: crate::Capacity
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity_and_hasher(capacity: Capacity, hash_builder: S) -> Self {
+        Self {
+            rodeo: Rodeo::with_capacity_and_hasher(capacity, hash_builder),
+        }
+    }
+
+    /// Inserts a string into the map, returning its key
+    ///
+    /// Unlike `HashMap::insert` the key is chosen by the map, inserting a string that's
+    /// already in the map returns the key it was given the first time
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this happens after `u32::MAX - 1` unique strings)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::RodeoMap;
+    ///
+    /// let mut map = RodeoMap::default();
+    /// let key = map.insert("Strings of things with wings and dings");
+    ///
+    /// assert_eq!(map.insert("Strings of things with wings and dings"), key);
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    ///
+    /// [`Spur`]: crate::Spur
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn insert<T>(&mut self, string: T) -> K
+    where
+        T: AsRef<str>,
+    {
+        self.rodeo.get_or_intern(string)
+    }
+
+    /// Inserts a string into the map, returning its key or an error if the key space or
+    /// memory limits were exhausted
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::RodeoMap;
+    ///
+    /// let mut map = RodeoMap::default();
+    /// let key = map.try_insert("Strings of things with wings and dings").unwrap();
+    ///
+    /// assert_eq!(map.get("Strings of things with wings and dings"), Some(key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn try_insert<T>(&mut self, string: T) -> LassoResult<K>
+    where
+        T: AsRef<str>,
+    {
+        self.rodeo.try_get_or_intern(string)
+    }
+
+    /// Returns the key of a string, if it's in the map
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::RodeoMap;
+    ///
+    /// let mut map = RodeoMap::default();
+    /// let key = map.insert("Strings of things with wings and dings");
+    ///
+    /// assert_eq!(map.get("Strings of things with wings and dings"), Some(key));
+    /// assert_eq!(map.get("This string isn't in the map"), None);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get<T>(&self, string: T) -> Option<K>
+    where
+        T: AsRef<str>,
+    {
+        self.rodeo.get(string)
+    }
+
+    /// Returns `true` if the string is in the map
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::RodeoMap;
+    ///
+    /// let mut map = RodeoMap::default();
+    /// map.insert("Strings of things with wings and dings");
+    ///
+    /// assert!(map.contains_key("Strings of things with wings and dings"));
+    /// assert!(!map.contains_key("This string isn't in the map"));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains_key<T>(&self, string: T) -> bool
+    where
+        T: AsRef<str>,
+    {
+        self.rodeo.contains(string)
+    }
+}
+
+impl<K, S> RodeoMap<K, S> {
+    /// Returns the number of strings in the map
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.rodeo.len()
+    }
+
+    /// Returns `true` if the map contains no strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.rodeo.is_empty()
+    }
+
+    /// Removes every string from the map, invalidating all previously returned keys
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn clear(&mut self) {
+        self.rodeo.clear();
+    }
+
+    /// Returns an iterator over the strings in the map and their keys, in insertion order
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter(&self) -> RodeoMapIter<'_, K> {
+        RodeoMapIter {
+            iter: self.rodeo.iter(),
+        }
+    }
+
+    /// Returns an iterator over the strings in the map, in insertion order
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn keys(&self) -> Strings<'_, K> {
+        self.rodeo.strings()
+    }
+
+    /// Returns an iterator over the keys of the map, in insertion order
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn values(&self) -> RodeoMapValues<'_, K> {
+        RodeoMapValues {
+            iter: self.rodeo.iter(),
+        }
+    }
+
+    /// Returns the [`Rodeo`] backing the map, which can be used to resolve keys
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::RodeoMap;
+    ///
+    /// let mut map = RodeoMap::default();
+    /// let key = map.insert("Strings of things with wings and dings");
+    ///
+    /// assert_eq!(map.rodeo().resolve(&key), "Strings of things with wings and dings");
+    /// ```
+    ///
+    /// [`Rodeo`]: crate::Rodeo
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn rodeo(&self) -> &Rodeo<K, S> {
+        &self.rodeo
+    }
+
+    /// Consumes the `RodeoMap`, returning the [`Rodeo`] it wraps
+    ///
+    /// [`Rodeo`]: crate::Rodeo
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn into_inner(self) -> Rodeo<K, S> {
+        self.rodeo
+    }
+}
+
+impl Default for RodeoMap<Spur, RandomState> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Key + Debug, S> Debug for RodeoMap<K, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RodeoMap")
+            .field("rodeo", &self.rodeo)
+            .finish()
+    }
+}
+
+impl<K, S> Clone for RodeoMap<K, S>
+where
+    K: Key,
+    S: BuildHasher + Clone,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self {
+            rodeo: self.rodeo.clone(),
+        }
+    }
+}
+
+impl<K, S> From<Rodeo<K, S>> for RodeoMap<K, S> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from(rodeo: Rodeo<K, S>) -> Self {
+        Self { rodeo }
+    }
+}
+
+impl<K, S> From<RodeoMap<K, S>> for Rodeo<K, S> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from(map: RodeoMap<K, S>) -> Self {
+        map.rodeo
+    }
+}
+
+impl<Str, K, S> FromIterator<Str> for RodeoMap<K, S>
+where
+    Str: AsRef<str>,
+    K: Key,
+    S: BuildHasher + Default,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = Str>,
+    {
+        Self {
+            rodeo: Rodeo::from_iter(iter),
+        }
+    }
+}
+
+impl<K, S, T> Extend<T> for RodeoMap<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+    T: AsRef<str>,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.rodeo.extend(iter);
+    }
+}
+
+impl<'a, K: Key, S> IntoIterator for &'a RodeoMap<K, S> {
+    type Item = (&'a str, K);
+    type IntoIter = RodeoMapIter<'a, K>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the strings and keys of a [`RodeoMap`]
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RodeoMapIter<'a, K> {
+    iter: Iter<'a, K>,
+}
+
+impl<'a, K> Iterator for RodeoMapIter<'a, K>
+where
+    K: Key,
+{
+    type Item = (&'a str, K);
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, string)| (string, key))
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K> DoubleEndedIterator for RodeoMapIter<'a, K>
+where
+    K: Key,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(key, string)| (string, key))
+    }
+}
+
+impl<'a, K: Key> ExactSizeIterator for RodeoMapIter<'a, K> {}
+impl<'a, K: Key> FusedIterator for RodeoMapIter<'a, K> {}
+
+/// An iterator over the keys of a [`RodeoMap`]
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RodeoMapValues<'a, K> {
+    iter: Iter<'a, K>,
+}
+
+impl<'a, K> Iterator for RodeoMapValues<'a, K>
+where
+    K: Key,
+{
+    type Item = K;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, _)| key)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K> DoubleEndedIterator for RodeoMapValues<'a, K>
+where
+    K: Key,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(key, _)| key)
+    }
+}
+
+impl<'a, K: Key> ExactSizeIterator for RodeoMapValues<'a, K> {}
+impl<'a, K: Key> FusedIterator for RodeoMapValues<'a, K> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn hashmap_api() {
+        let mut map: RodeoMap = ["a", "b"].iter().collect();
+        let c = map.insert("c");
+        assert_eq!(map.insert("c"), c);
+        assert_eq!(map.try_insert("a").unwrap(), map.get("a").unwrap());
+        assert!(map.contains_key("b"));
+        assert!(!map.contains_key("d"));
+        assert_eq!(map.len(), 3);
+
+        let pairs: Vec<(&str, Spur)> = map.iter().collect();
+        assert_eq!(pairs[2], ("c", c));
+        assert_eq!(map.keys().collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert_eq!(map.values().next_back(), Some(c));
+        assert_eq!((&map).into_iter().len(), 3);
+
+        map.extend(["d"]);
+        let rodeo: Rodeo = map.into_inner();
+        assert_eq!(rodeo.resolve(&c), "c");
+
+        let mut map = RodeoMap::from(rodeo);
+        assert_eq!(map.rodeo().len(), 4);
+        map.clear();
+        assert!(map.is_empty());
+    }
+}
@@ -0,0 +1,538 @@
+use crate::{
+    hasher::RandomState,
+    keys::{Key, Spur},
+    util::Iter,
+    Capacity, LassoResult, Rodeo,
+};
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Debug},
+    hash::BuildHasher,
+    iter::FusedIterator,
+    slice,
+};
+
+/// A [`Rodeo`] that keeps a value alongside every interned string
+///
+/// Keys are dense, so the values are kept in a vec indexed by key instead of a parallel
+/// structure that has to be kept in sync by hand. Every string gets its value when it's first
+/// interned, after which the value can be read and changed through the string's key with
+/// [`RodeoWithData::data`] and [`RodeoWithData::data_mut`]
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::RodeoWithData;
+///
+/// let mut rodeo: RodeoWithData<_, usize> = RodeoWithData::default();
+///
+/// for word in "the cat saw the dog".split(' ') {
+///     let key = rodeo.get_or_intern(word, || 0);
+///     *rodeo.data_mut(&key) += 1;
+/// }
+///
+/// let the = rodeo.get("the").unwrap();
+/// assert_eq!(*rodeo.data(&the), 2);
+/// assert_eq!(rodeo.iter().map(|(_, _, &count)| count).sum::<usize>(), 5);
+/// ```
+///
+/// [`Rodeo`]: crate::Rodeo
+pub struct RodeoWithData<K = Spur, V = (), S = RandomState> {
+    rodeo: Rodeo<K, S>,
+    /// The value of every key, indexed by the key
+    data: Vec<V>,
+}
+
+impl<K, V> RodeoWithData<K, V, RandomState>
+where
+    K: Key,
+{
+    /// Create an empty `RodeoWithData`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{RodeoWithData, Spur};
+    ///
+    /// let rodeo: RodeoWithData<Spur, u32> = RodeoWithData::new();
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new() -> Self {
+        Self {
+            rodeo: Rodeo::new(),
+            data: Vec::new(),
+        }
+    }
+
+    /// Create an empty `RodeoWithData` with the specified capacity
+    ///
+    /// See [`Capacity`] for more details
+    ///
+    /// [`Capacity`]: crate::Capacity
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity(capacity: Capacity) -> Self {
+        Self {
+            data: Vec::with_capacity(capacity.strings),
+            rodeo: Rodeo::with_capacity(capacity),
+        }
+    }
+}
+
+impl<K, V, S> RodeoWithData<K, V, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    /// Create an empty `RodeoWithData` which will use the given hasher
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{RodeoWithData, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: RodeoWithData<Spur, u32, RandomState> =
+    ///     RodeoWithData::with_hasher(RandomState::new());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            rodeo: Rodeo::with_hasher(hash_builder),
+            data: Vec::new(),
+        }
+    }
+
+    /// Create an empty `RodeoWithData` with the specified capacity which will use the given
+    /// hasher
+    ///
+    /// See [`Capacity`] for more details
+    ///
+    /// [`Capacity`]: crate::Capacity
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity_and_hasher(capacity: Capacity, hash_builder: S) -> Self {
+        Self {
+            data: Vec::with_capacity(capacity.strings),
+            rodeo: Rodeo::with_capacity_and_hasher(capacity, hash_builder),
+        }
+    }
+
+    /// Get the key for a string, interning it if it does not yet exist and giving it the
+    /// value returned by `init`
+    ///
+    /// `init` is only called if the string wasn't interned yet, strings that already exist
+    /// keep their current value
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique strings were interned)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::RodeoWithData;
+    ///
+    /// let mut rodeo = RodeoWithData::default();
+    ///
+    /// let key = rodeo.get_or_intern("Strings of things", || "first");
+    /// assert_eq!(rodeo.get_or_intern("Strings of things", || "second"), key);
+    /// assert_eq!(*rodeo.data(&key), "first");
+    /// ```
+    ///
+    /// [`Spur`]: crate::Spur
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn get_or_intern<T, F>(&mut self, val: T, init: F) -> K
+    where
+        T: AsRef<str>,
+        F: FnOnce() -> V,
+    {
+        self.try_get_or_intern(val, init)
+            .expect("Failed to get or intern string")
+    }
+
+    /// Get the key for a string, interning it if it does not yet exist and giving it the
+    /// value returned by `init`
+    ///
+    /// `init` is only called if the string was interned, strings that already exist keep
+    /// their current value and strings that fail to be interned never get one
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::RodeoWithData;
+    ///
+    /// let mut rodeo = RodeoWithData::default();
+    ///
+    /// let key = rodeo.try_get_or_intern("Strings of things", Vec::new).unwrap();
+    /// rodeo.data_mut(&key).push(1);
+    /// assert_eq!(rodeo.data(&key), &[1]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn try_get_or_intern<T, F>(&mut self, val: T, init: F) -> LassoResult<K>
+    where
+        T: AsRef<str>,
+        F: FnOnce() -> V,
+    {
+        let key = self.rodeo.try_get_or_intern(val)?;
+
+        // New strings get the next key, so they're the only ones without a value
+        if key.into_usize() == self.data.len() {
+            self.data.push(init());
+        }
+
+        Ok(key)
+    }
+
+    /// Get the key value of a string, returning `None` if it doesn't exist
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get<T>(&self, val: T) -> Option<K>
+    where
+        T: AsRef<str>,
+    {
+        self.rodeo.get(val)
+    }
+
+    /// Returns `true` if the given string has been interned
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains<T>(&self, val: T) -> bool
+    where
+        T: AsRef<str>,
+    {
+        self.rodeo.contains(val)
+    }
+}
+
+impl<K, V, S> RodeoWithData<K, V, S>
+where
+    K: Key,
+{
+    /// Resolves a string by its key. Only keys made by the current interner may be used
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve<'a>(&'a self, key: &K) -> &'a str {
+        self.rodeo.resolve(key)
+    }
+
+    /// Resolves a string by its key, returning `None` if it's out of bounds
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve<'a>(&'a self, key: &K) -> Option<&'a str> {
+        self.rodeo.try_resolve(key)
+    }
+
+    /// Gets the value of a key. Only keys made by the current interner may be used
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn data(&self, key: &K) -> &V {
+        self.try_data(key).expect("Key out of bounds")
+    }
+
+    /// Gets the value of a key, returning `None` if it's out of bounds
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_data(&self, key: &K) -> Option<&V> {
+        self.data.get(key.into_usize())
+    }
+
+    /// Gets the value of a key mutably. Only keys made by the current interner may be used
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn data_mut(&mut self, key: &K) -> &mut V {
+        self.try_data_mut(key).expect("Key out of bounds")
+    }
+
+    /// Gets the value of a key mutably, returning `None` if it's out of bounds
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_data_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.data.get_mut(key.into_usize())
+    }
+
+    /// Returns an iterator over the keys, strings and values of the interner, in key order
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter(&self) -> RodeoWithDataIter<'_, K, V> {
+        RodeoWithDataIter {
+            strings: self.rodeo.iter(),
+            data: self.data.iter(),
+        }
+    }
+
+    /// Returns an iterator over the keys, strings and mutable values of the interner, in
+    /// key order
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter_mut(&mut self) -> RodeoWithDataIterMut<'_, K, V> {
+        RodeoWithDataIterMut {
+            strings: self.rodeo.iter(),
+            data: self.data.iter_mut(),
+        }
+    }
+}
+
+impl<K, V, S> RodeoWithData<K, V, S> {
+    /// Returns the number of strings in the interner
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the interner contains no strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Removes every string and value from the interner, invalidating all previously
+    /// returned keys
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn clear(&mut self) {
+        self.rodeo.clear();
+        self.data.clear();
+    }
+
+    /// Returns the [`Rodeo`] holding the strings
+    ///
+    /// [`Rodeo`]: crate::Rodeo
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn rodeo(&self) -> &Rodeo<K, S> {
+        &self.rodeo
+    }
+
+    /// Returns the value of every key, indexed by the key
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn values(&self) -> &[V] {
+        &self.data
+    }
+
+    /// Returns the value of every key mutably, indexed by the key
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn values_mut(&mut self) -> &mut [V] {
+        &mut self.data
+    }
+
+    /// Consumes the `RodeoWithData`, returning the [`Rodeo`] holding the strings and the
+    /// value of every key, indexed by the key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::RodeoWithData;
+    ///
+    /// let mut rodeo = RodeoWithData::default();
+    /// let key = rodeo.get_or_intern("Strings of things", || 1.5);
+    ///
+    /// let (rodeo, values) = rodeo.into_parts();
+    /// assert_eq!(rodeo.resolve(&key), "Strings of things");
+    /// assert_eq!(values, [1.5]);
+    /// ```
+    ///
+    /// [`Rodeo`]: crate::Rodeo
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn into_parts(self) -> (Rodeo<K, S>, Vec<V>) {
+        (self.rodeo, self.data)
+    }
+}
+
+impl<V> Default for RodeoWithData<Spur, V, RandomState> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, S> Debug for RodeoWithData<K, V, S>
+where
+    K: Key + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RodeoWithData")
+            .field("rodeo", &self.rodeo)
+            .field("data", &self.data)
+            .finish()
+    }
+}
+
+impl<K, V, S> Clone for RodeoWithData<K, V, S>
+where
+    K: Key,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self {
+            rodeo: self.rodeo.clone(),
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<'a, K: Key, V, S> IntoIterator for &'a RodeoWithData<K, V, S> {
+    type Item = (K, &'a str, &'a V);
+    type IntoIter = RodeoWithDataIter<'a, K, V>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: Key, V, S> IntoIterator for &'a mut RodeoWithData<K, V, S> {
+    type Item = (K, &'a str, &'a mut V);
+    type IntoIter = RodeoWithDataIterMut<'a, K, V>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over the keys, strings and values of a [`RodeoWithData`]
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RodeoWithDataIter<'a, K, V> {
+    strings: Iter<'a, K>,
+    data: slice::Iter<'a, V>,
+}
+
+impl<'a, K, V> Iterator for RodeoWithDataIter<'a, K, V>
+where
+    K: Key,
+{
+    type Item = (K, &'a str, &'a V);
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, string) = self.strings.next()?;
+        Some((key, string, self.data.next()?))
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.data.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for RodeoWithDataIter<'a, K, V>
+where
+    K: Key,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (key, string) = self.strings.next_back()?;
+        Some((key, string, self.data.next_back()?))
+    }
+}
+
+impl<'a, K: Key, V> ExactSizeIterator for RodeoWithDataIter<'a, K, V> {}
+impl<'a, K: Key, V> FusedIterator for RodeoWithDataIter<'a, K, V> {}
+
+/// An iterator over the keys, strings and mutable values of a [`RodeoWithData`]
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RodeoWithDataIterMut<'a, K, V> {
+    strings: Iter<'a, K>,
+    data: slice::IterMut<'a, V>,
+}
+
+impl<'a, K, V> Iterator for RodeoWithDataIterMut<'a, K, V>
+where
+    K: Key,
+{
+    type Item = (K, &'a str, &'a mut V);
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, string) = self.strings.next()?;
+        Some((key, string, self.data.next()?))
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.data.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for RodeoWithDataIterMut<'a, K, V>
+where
+    K: Key,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (key, string) = self.strings.next_back()?;
+        Some((key, string, self.data.next_back()?))
+    }
+}
+
+impl<'a, K: Key, V> ExactSizeIterator for RodeoWithDataIterMut<'a, K, V> {}
+impl<'a, K: Key, V> FusedIterator for RodeoWithDataIterMut<'a, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryLimits;
+    use core::num::NonZeroUsize;
+
+    #[test]
+    fn data() {
+        let mut rodeo: RodeoWithData<Spur, Vec<usize>> = RodeoWithData::new();
+        for (i, word) in ["a", "b", "a", "c", "b", "a"].iter().enumerate() {
+            let key = rodeo.get_or_intern(word, Vec::new);
+            rodeo.data_mut(&key).push(i);
+        }
+
+        let a = rodeo.get("a").unwrap();
+        assert_eq!(rodeo.len(), 3);
+        assert_eq!(rodeo.data(&a), &[0, 2, 5]);
+        assert_eq!(rodeo.try_data(&Spur::try_from_usize(3).unwrap()), None);
+
+        for (_, string, positions) in &mut rodeo {
+            if string == "b" {
+                positions.clear();
+            }
+        }
+        let entries: Vec<_> = rodeo
+            .iter()
+            .map(|(key, string, positions)| (key.into_usize(), string, positions.len()))
+            .collect();
+        assert_eq!(entries, [(0, "a", 3), (1, "b", 0), (2, "c", 1)]);
+        assert_eq!(rodeo.iter().next_back().unwrap().1, "c");
+
+        let (inner, values) = rodeo.clone().into_parts();
+        assert_eq!(inner.len(), values.len());
+        rodeo.clear();
+        assert!(rodeo.is_empty() && rodeo.rodeo().is_empty());
+    }
+
+    #[test]
+    fn failed_intern_gets_no_value() {
+        let mut rodeo: RodeoWithData<Spur, u8> = RodeoWithData {
+            rodeo: Rodeo::with_capacity_and_memory_limits(
+                Capacity::for_bytes(NonZeroUsize::new(4).unwrap()),
+                MemoryLimits::for_memory_usage(4),
+            ),
+            data: Vec::new(),
+        };
+
+        let key = rodeo.try_get_or_intern("1234", || 1).unwrap();
+        assert!(rodeo.try_get_or_intern("5", || panic!()).is_err());
+        assert_eq!(rodeo.values(), [1]);
+
+        *rodeo.values_mut().first_mut().unwrap() = 2;
+        assert_eq!(*rodeo.data(&key), 2);
+    }
+}
@@ -0,0 +1,283 @@
+use crate::{
+    hasher::RandomState,
+    rodeo::{Checkpoint, Rodeo},
+    Key, LassoResult, Spur,
+};
+use core::{
+    fmt::{self, Debug},
+    hash::BuildHasher,
+    ops::Deref,
+};
+
+/// A batch of strings being interned into a [`Rodeo`] that can be undone as a whole,
+/// created by [`Rodeo::session`]
+///
+/// Strings interned through the session are kept if it's committed with
+/// [`RodeoSession::commit`]. If it's rolled back with [`RodeoSession::rollback`] or dropped
+/// without being committed, every string it added is removed from the interner, its key is
+/// given out again to the next string interned and the arena memory it took is reused. The
+/// interner can be read through the session while it's open
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::Rodeo;
+///
+/// fn load_config(rodeo: &mut Rodeo, lines: &[&str]) -> Result<(), String> {
+///     let mut session = rodeo.session();
+///     for line in lines {
+///         if line.is_empty() {
+///             // Returning early drops the session, which rolls it back
+///             return Err("empty line".to_owned());
+///         }
+///         session.try_get_or_intern(line).map_err(|err| err.to_string())?;
+///     }
+///
+///     session.commit();
+///     Ok(())
+/// }
+///
+/// let mut rodeo = Rodeo::default();
+/// assert!(load_config(&mut rodeo, &["name", "", "value"]).is_err());
+/// assert!(rodeo.is_empty());
+///
+/// load_config(&mut rodeo, &["name", "value"]).unwrap();
+/// assert_eq!(rodeo.len(), 2);
+/// ```
+///
+pub struct RodeoSession<'a, K = Spur, S = RandomState>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    rodeo: &'a mut Rodeo<K, S>,
+    /// The state to roll back to, `None` once the session has been committed or rolled back
+    checkpoint: Option<Checkpoint>,
+}
+
+impl<'a, K, S> RodeoSession<'a, K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    pub(crate) fn new(rodeo: &'a mut Rodeo<K, S>) -> Self {
+        let checkpoint = Some(rodeo.checkpoint());
+
+        Self { rodeo, checkpoint }
+    }
+
+    /// Get the key for a string, interning it if it does not yet exist
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique strings were interned)
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn get_or_intern<T>(&mut self, val: T) -> K
+    where
+        T: AsRef<str>,
+    {
+        self.rodeo.get_or_intern(val)
+    }
+
+    /// Get the key for a string, interning it if it does not yet exist
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn try_get_or_intern<T>(&mut self, val: T) -> LassoResult<K>
+    where
+        T: AsRef<str>,
+    {
+        self.rodeo.try_get_or_intern(val)
+    }
+
+    /// Get the key for a static string, interning it if it does not yet exist
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique strings were interned)
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn get_or_intern_static(&mut self, string: &'static str) -> K {
+        self.rodeo.get_or_intern_static(string)
+    }
+
+    /// Get the key for a static string, interning it if it does not yet exist
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn try_get_or_intern_static(&mut self, string: &'static str) -> LassoResult<K> {
+        self.rodeo.try_get_or_intern_static(string)
+    }
+
+    /// Returns the number of strings the session has interned that weren't interned before
+    /// it started
+    ///
+    /// Keys are given out in order, so they're the last keys of the interner
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn added(&self) -> usize {
+        self.checkpoint
+            .as_ref()
+            .map_or(0, |checkpoint| self.rodeo.len() - checkpoint.len)
+    }
+
+    /// Keeps every string interned during the session
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn commit(mut self) {
+        self.checkpoint = None;
+    }
+
+    /// Removes every string interned during the session, the same as dropping it
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn rollback(self) {}
+}
+
+/// Sessions can be read from like the interner they're interning into
+impl<K, S> Deref for RodeoSession<'_, K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    type Target = Rodeo<K, S>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn deref(&self) -> &Self::Target {
+        self.rodeo
+    }
+}
+
+impl<K, S> Drop for RodeoSession<'_, K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    fn drop(&mut self) {
+        if let Some(checkpoint) = self.checkpoint.take() {
+            // Safety: The checkpoint was made by this interner, which the session has borrowed
+            //         mutably ever since so it couldn't have been cleared. Strings resolved
+            //         through the session borrow it, so none of them are still alive
+            unsafe { self.rodeo.rollback_to(checkpoint) };
+        }
+    }
+}
+
+impl<K, S> Debug for RodeoSession<'_, K, S>
+where
+    K: Key + Debug,
+    S: BuildHasher,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RodeoSession")
+            .field("rodeo", &self.rodeo)
+            .field("added", &self.added())
+            .field("committed", &self.checkpoint.is_none())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Capacity, Key, MemoryLimits, Rodeo, Spur};
+    use core::num::NonZeroUsize;
+
+    #[cfg(feature = "no-std")]
+    use alloc::{
+        string::{String, ToString},
+        vec::Vec,
+    };
+
+    #[test]
+    fn rollback_removes_new_strings() {
+        let mut rodeo = Rodeo::default();
+        let existing = rodeo.get_or_intern("existing");
+        let usage = rodeo.arena_memory_usage();
+
+        let mut session = rodeo.session();
+        assert_eq!(session.get_or_intern("existing"), existing);
+        let temporary = session.get_or_intern("temporary");
+        session.get_or_intern_static("static");
+        assert_eq!(session.added(), 2);
+        assert_eq!(session.resolve(&temporary), "temporary");
+        session.rollback();
+
+        assert_eq!(rodeo.len(), 1);
+        assert_eq!(rodeo.get("temporary"), None);
+        assert_eq!(rodeo.get("static"), None);
+        assert_eq!(rodeo.get("existing"), Some(existing));
+        assert_eq!(rodeo.arena_memory_usage(), usage);
+        assert_eq!(rodeo.dedup_stats().hits(), 0);
+
+        // The rolled back key is given out again
+        assert_eq!(rodeo.get_or_intern("other"), temporary);
+        assert_eq!(rodeo.resolve(&temporary), "other");
+    }
+
+    #[test]
+    fn commit_keeps_new_strings() {
+        let mut rodeo = Rodeo::default();
+
+        let mut session = rodeo.session();
+        let keys: [Spur; 2] = [session.get_or_intern("a"), session.get_or_intern("b")];
+        session.commit();
+
+        assert_eq!(rodeo.len(), 2);
+        assert_eq!(rodeo.resolve(&keys[0]), "a");
+        assert_eq!(rodeo.resolve(&keys[1]), "b");
+    }
+
+    #[test]
+    fn drop_rolls_back_new_buckets() {
+        let mut rodeo: Rodeo =
+            Rodeo::with_capacity(Capacity::for_bytes(NonZeroUsize::new(16).unwrap()));
+        rodeo.get_or_intern("0123456789");
+        let usage = rodeo.arena_memory_usage();
+
+        {
+            let mut session = rodeo.session();
+            for i in 0..100 {
+                session.get_or_intern(i.to_string());
+            }
+
+            // Large enough to be given a bucket of its own
+            session.get_or_intern("a".repeat(100));
+            assert!(session.arena_memory_usage() > usage);
+        }
+
+        assert_eq!(rodeo.len(), 1);
+        assert_eq!(rodeo.arena_memory_usage(), usage);
+
+        // The arena is left usable
+        let strings: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+        for string in &strings {
+            rodeo.get_or_intern(string);
+        }
+        for (i, string) in strings.iter().enumerate() {
+            let key = Spur::try_from_usize(i + 1).unwrap();
+            assert_eq!(rodeo.resolve(&key), string);
+        }
+        assert_eq!(
+            rodeo.resolve(&Spur::try_from_usize(0).unwrap()),
+            "0123456789"
+        );
+    }
+
+    #[test]
+    fn failed_batch() {
+        let mut rodeo: Rodeo = Rodeo::with_capacity_and_memory_limits(
+            Capacity::for_bytes(NonZeroUsize::new(8).unwrap()),
+            MemoryLimits::for_memory_usage(8),
+        );
+
+        let mut session = rodeo.session();
+        assert!(session.try_get_or_intern("1234").is_ok());
+        assert!(session.try_get_or_intern("too long for the arena").is_err());
+        drop(session);
+
+        assert!(rodeo.is_empty());
+        assert!(rodeo.try_get_or_intern("12345678").is_ok());
+    }
+}
@@ -0,0 +1,801 @@
+//! The sharded string to key map that [`ThreadedRodeo`] interns through
+//!
+//! Each shard is a bare hash table behind its own lock, with strings hashed once by the
+//! interner and reused for both picking a shard and probing it. Entries only hold what's
+//! needed to compare strings and hand out their keys, which makes them smaller than a
+//! general purpose map's `(&str, K)` pairs
+//!
+//! [`ThreadedRodeo`]: crate::ThreadedRodeo
+
+use crate::{
+    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+    util::grown_table_capacity,
+    LassoError, LassoErrorKind, LassoResult,
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    fmt::{self, Debug},
+    hash::BuildHasher,
+    mem::size_of,
+    ptr::NonNull,
+    slice, str,
+};
+use hashbrown::{hash_table, HashTable};
+
+/// A concurrent map from interned strings to their keys
+pub(crate) struct ShardMap<K, S> {
+    shards: Box<[Shard<K>]>,
+    /// The number of bits a shifted hash has to be moved by to get its shard's index
+    shift: u32,
+    /// Strings too long for an [`Entry`] to hold, which are so rare that they're
+    /// kept out of the way of everything else
+    oversized: RwLock<Vec<(&'static str, K)>>,
+    hasher: S,
+    /// Whether hashes are mixed before picking their shard, which spreads strings out evenly
+    /// even when the hasher leaves the bits used to pick a shard mostly the same
+    mix_hashes: bool,
+}
+
+/// A single shard, padded out so that neighboring shards' locks don't share a cache line
+#[repr(align(128))]
+struct Shard<K> {
+    table: RwLock<HashTable<Entry<K>>>,
+}
+
+/// An interned string and its key
+///
+/// The string's length is stored as a `u32` so that on 64-bit targets the entries of the
+/// common 32-bit or smaller keys pack into two words instead of three
+pub(crate) struct Entry<K> {
+    ptr: NonNull<u8>,
+    len: u32,
+    key: K,
+}
+
+impl<K> Entry<K> {
+    /// Returns `true` if `string` is short enough to be held by an entry
+    fn fits(string: &str) -> bool {
+        u32::try_from(string.len()).is_ok()
+    }
+
+    /// Creates an entry, `string` must [fit](Entry::fits) within it
+    fn new(string: &'static str, key: K) -> Self {
+        debug_assert!(Self::fits(string));
+
+        Self {
+            // Safety: A string's pointer is never null, even when it's empty
+            ptr: unsafe { NonNull::new_unchecked(string.as_ptr().cast_mut()) },
+            len: string.len() as u32,
+            key,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        // Safety: The pointer and length were taken from a `&'static str`
+        unsafe {
+            str::from_utf8_unchecked(slice::from_raw_parts(self.ptr.as_ptr(), self.len as usize))
+        }
+    }
+}
+
+// Safety: Entries are only a `&'static str` and a key in disguise
+unsafe impl<K: Send> Send for Entry<K> {}
+// Safety: Entries are only a `&'static str` and a key in disguise
+unsafe impl<K: Sync> Sync for Entry<K> {}
+
+impl<K, S> ShardMap<K, S>
+where
+    K: Copy,
+    S: BuildHasher,
+{
+    /// Creates a map with room for at least `capacity` strings spread out over its shards
+    pub(crate) fn try_with_capacity_and_hasher(capacity: usize, hasher: S) -> LassoResult<Self> {
+        let num_shards = shard_count();
+        // Round up so that the map can hold at least `capacity` strings
+        let per_shard = capacity.saturating_add(num_shards - 1) / num_shards;
+
+        let shards = (0..num_shards)
+            .map(|_| {
+                let mut table = HashTable::new();
+                table
+                    .try_reserve(per_shard, |_| {
+                        unreachable!(
+                            "the shard should be empty while reserving its initial capacity"
+                        );
+                    })
+                    .map_err(|_| LassoError::new(LassoErrorKind::FailedAllocation))?;
+
+                Ok(Shard {
+                    table: RwLock::new(table),
+                })
+            })
+            .collect::<LassoResult<Box<[_]>>>()?;
+
+        Ok(Self {
+            shards,
+            shift: u64::BITS - num_shards.trailing_zeros(),
+            oversized: RwLock::new(Vec::new()),
+            hasher,
+            mix_hashes: false,
+        })
+    }
+
+    pub(crate) fn hasher(&self) -> &S {
+        &self.hasher
+    }
+
+    /// Hashes a string with the map's hasher
+    pub(crate) fn hash(&self, string: &str) -> u64 {
+        self.hasher.hash_one(string)
+    }
+
+    /// Gets the shard a hash belongs to
+    fn shard(&self, hash: u64) -> &RwLock<HashTable<Entry<K>>> {
+        &self.shards[self.shard_index(hash)].table
+    }
+
+    /// Gets the index of the shard a hash belongs to
+    fn shard_index(&self, hash: u64) -> usize {
+        // Only the shard is picked with the mixed hash, tables keep using the hasher's own
+        // hashes so that the hashes given to `get` and `entry` can be used as-is
+        let hash = if self.mix_hashes { mix(hash) } else { hash };
+
+        // The top seven bits are used by the shard's table, so they're skipped to keep from
+        // giving every string within a shard the same tag
+        ((hash << 7) >> self.shift) as usize
+    }
+
+    /// Sets whether hashes are mixed before picking their shard, moving every string over
+    /// to its new shard if that changes
+    pub(crate) fn set_mix_hashes(&mut self, mix_hashes: bool) {
+        if self.mix_hashes == mix_hashes {
+            return;
+        }
+        self.mix_hashes = mix_hashes;
+
+        let mut entries = Vec::with_capacity(self.len());
+        for shard in self.shards.iter_mut() {
+            entries.extend(shard.table.get_mut().drain());
+        }
+
+        for entry in entries {
+            let hash = self.hasher.hash_one(entry.as_str());
+            let index = self.shard_index(hash);
+            let hasher = &self.hasher;
+
+            self.shards[index]
+                .table
+                .get_mut()
+                .insert_unique(hash, entry, |entry| hasher.hash_one(entry.as_str()));
+        }
+    }
+
+    /// Returns `true` if hashes are mixed before picking their shard
+    pub(crate) fn mixes_hashes(&self) -> bool {
+        self.mix_hashes
+    }
+
+    /// Gets the key of `string`, whose hash is `hash`
+    pub(crate) fn get(&self, string: &str, hash: u64) -> Option<K> {
+        Reentry::check(self);
+
+        if !Entry::<K>::fits(string) {
+            return read_shard(&self.oversized)
+                .iter()
+                .find(|(oversized, _)| *oversized == string)
+                .map(|&(_, key)| key);
+        }
+
+        read_shard(self.shard(hash))
+            .find(hash, |entry| entry.as_str() == string)
+            .map(|entry| entry.key)
+    }
+
+    /// Gets the entry for `string`, whose hash is `hash`
+    ///
+    /// The string's shard stays locked until the entry is dropped, so no other thread can
+    /// insert the same string while a vacant entry is held
+    pub(crate) fn entry(&self, string: &str, hash: u64) -> ShardEntry<'_, K> {
+        Reentry::check(self);
+
+        if !Entry::<K>::fits(string) {
+            let oversized = match lock_for_insert(&self.oversized, |oversized| {
+                oversized
+                    .iter()
+                    .find(|(oversized, _)| *oversized == string)
+                    .map(|&(_, key)| key)
+            }) {
+                Ok(key) => return ShardEntry::Occupied(key),
+                Err(oversized) => oversized,
+            };
+
+            return match oversized.iter().find(|(oversized, _)| *oversized == string) {
+                Some(&(_, key)) => ShardEntry::Occupied(key),
+                None => ShardEntry::Vacant(VacantEntry::Oversized {
+                    oversized,
+                    _reentry: Reentry::enter(self),
+                }),
+            };
+        }
+
+        let mut shard = match lock_for_insert(self.shard(hash), |shard| {
+            shard
+                .find(hash, |entry| entry.as_str() == string)
+                .map(|entry| entry.key)
+        }) {
+            Ok(key) => return ShardEntry::Occupied(key),
+            Err(shard) => shard,
+        };
+
+        if let Some(entry) = shard.find(hash, |entry| entry.as_str() == string) {
+            return ShardEntry::Occupied(entry.key);
+        }
+
+        // Room for the string is made now so that inserting it can't grow the shard, growing
+        // calls the hasher while it's locked
+        let reentry = Reentry::enter(self);
+        shard.reserve(1, |entry| self.hasher.hash_one(entry.as_str()));
+
+        ShardEntry::Vacant(VacantEntry::Table {
+            shard,
+            hash,
+            _reentry: reentry,
+        })
+    }
+
+    /// Adds a string if it isn't already in the map
+    pub(crate) fn insert(&self, string: &'static str, key: K) {
+        if let ShardEntry::Vacant(entry) = self.entry(string, self.hash(string)) {
+            entry.insert(string, key);
+        }
+    }
+
+    /// Takes every shard's lock and calls `with_locked` before emptying the map
+    pub(crate) fn clear_with<F>(&self, with_locked: F)
+    where
+        F: FnOnce(),
+    {
+        Reentry::check(self);
+
+        let mut shards: Vec<_> = self
+            .shards
+            .iter()
+            .map(|shard| write_shard(&shard.table))
+            .collect();
+        let mut oversized = write_shard(&self.oversized);
+
+        with_locked();
+
+        for shard in shards.iter_mut() {
+            shard.clear();
+        }
+        oversized.clear();
+    }
+
+    /// Shrinks every shard to fit the strings it holds, one shard at a time
+    pub(crate) fn shrink_to_fit(&self) {
+        Reentry::check(self);
+
+        for shard in self.shards.iter() {
+            let mut shard = write_shard(&shard.table);
+            // Shrinking rehashes the shard's strings while it's locked
+            let _reentry = Reentry::enter(self);
+            shard.shrink_to_fit(|entry| self.hasher.hash_one(entry.as_str()));
+        }
+        write_shard(&self.oversized).shrink_to_fit();
+    }
+
+    /// The number of entries in the map
+    pub(crate) fn len(&self) -> usize {
+        let tables: usize = self
+            .shards
+            .iter()
+            .map(|shard| read_shard(&shard.table).len())
+            .sum();
+
+        tables + read_shard(&self.oversized).len()
+    }
+
+    /// Estimates the memory used by the map, optionally including the largest amount a
+    /// single insertion could grow it by
+    pub(crate) fn memory_usage(&self, projected: bool) -> usize {
+        // Every entry also has a one byte control tag
+        let entry_size = size_of::<Entry<K>>() + 1;
+        let (mut total, mut largest) = (0usize, 0usize);
+
+        for shard in self.shards.iter() {
+            let shard = read_shard(&shard.table);
+            let capacity = shard.capacity();
+            total = total.saturating_add(capacity.saturating_mul(entry_size));
+
+            // Full shards grow on their next insertion
+            if shard.len() == capacity {
+                let growth = grown_table_capacity(capacity) - capacity;
+                largest = largest.max(growth.saturating_mul(entry_size));
+            }
+        }
+
+        let oversized = read_shard(&self.oversized).capacity();
+        total = total.saturating_add(oversized.saturating_mul(size_of::<(&str, K)>()));
+
+        if projected {
+            total.saturating_add(largest)
+        } else {
+            total
+        }
+    }
+}
+
+impl<K, S> ShardMap<K, S>
+where
+    K: Copy,
+{
+    /// Calls `f` with every string and its key
+    pub(crate) fn for_each<F>(&self, mut f: F)
+    where
+        F: FnMut(&'static str, K),
+    {
+        for shard in self.shards.iter() {
+            let shard = read_shard(&shard.table);
+
+            for entry in shard.iter() {
+                f(entry.as_str(), entry.key);
+            }
+        }
+
+        for &(string, key) in read_shard(&self.oversized).iter() {
+            f(string, key);
+        }
+    }
+
+    /// The number of strings in each shard, not counting the oversized strings
+    pub(crate) fn shard_lens(&self) -> Vec<usize> {
+        self.shards
+            .iter()
+            .map(|shard| read_shard(&shard.table).len())
+            .collect()
+    }
+
+    /// The number of shards, counting the oversized strings as a shard of their own
+    pub(crate) fn shard_count(&self) -> usize {
+        self.shards.len() + 1
+    }
+
+    /// Calls `f` with the entries of each shard, one at a time
+    ///
+    /// Each shard stays read locked while `f` is looking at it, other shards can be written
+    /// to in the meantime
+    pub(crate) fn for_each_shard<F>(&self, mut f: F)
+    where
+        F: FnMut(ShardEntries<'_, K>),
+    {
+        Reentry::check(self);
+
+        for shard in self.shards.iter() {
+            let shard = read_shard(&shard.table);
+
+            f(ShardEntries::Table(shard.iter()));
+        }
+
+        let oversized = read_shard(&self.oversized);
+        f(ShardEntries::Oversized(oversized.iter()));
+    }
+
+    /// Empties the shard at `index`, returning its strings and their keys
+    ///
+    /// An index of [`ShardMap::shard_count`] minus one refers to the oversized strings
+    pub(crate) fn drain_shard(
+        &mut self,
+        index: usize,
+    ) -> impl Iterator<Item = (&'static str, K)> + '_ {
+        let (table, oversized) = match self.shards.get_mut(index) {
+            Some(shard) => (Some(shard.table.get_mut().drain()), None),
+            None => (None, Some(self.oversized.get_mut().drain(..))),
+        };
+
+        table
+            .into_iter()
+            .flatten()
+            .map(|entry| (entry.as_str(), entry.key))
+            .chain(oversized.into_iter().flatten())
+    }
+
+    /// Empties the map, returning every string and its key
+    pub(crate) fn drain(&mut self) -> impl Iterator<Item = (&'static str, K)> + '_ {
+        self.shards
+            .iter_mut()
+            .flat_map(|shard| shard.table.get_mut().drain())
+            .map(|entry| (entry.as_str(), entry.key))
+            .chain(self.oversized.get_mut().drain(..))
+    }
+}
+
+/// The strings and keys of a single shard, which is locked for as long as this is alive
+pub(crate) enum ShardEntries<'a, K> {
+    Table(hash_table::Iter<'a, Entry<K>>),
+    Oversized(slice::Iter<'a, (&'static str, K)>),
+}
+
+impl<K: Copy> Iterator for ShardEntries<'_, K> {
+    type Item = (&'static str, K);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Table(iter) => iter.next().map(|entry| (entry.as_str(), entry.key)),
+            Self::Oversized(iter) => iter.next().copied(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Table(iter) => iter.size_hint(),
+            Self::Oversized(iter) => iter.size_hint(),
+        }
+    }
+}
+
+/// An entry of a [`ShardMap`]
+pub(crate) enum ShardEntry<'a, K> {
+    /// The string was already in the map and has this key
+    Occupied(K),
+    /// The string isn't in the map yet
+    Vacant(VacantEntry<'a, K>),
+}
+
+/// A place to insert a string that isn't in a [`ShardMap`], which holds the lock on the
+/// string's shard
+pub(crate) enum VacantEntry<'a, K> {
+    Table {
+        shard: RwLockWriteGuard<'a, HashTable<Entry<K>>>,
+        hash: u64,
+        _reentry: Reentry,
+    },
+    Oversized {
+        oversized: RwLockWriteGuard<'a, Vec<(&'static str, K)>>,
+        _reentry: Reentry,
+    },
+}
+
+impl<K> VacantEntry<'_, K> {
+    /// Inserts the string, which must be the same one that the entry was made for
+    pub(crate) fn insert(self, string: &'static str, key: K) {
+        match self {
+            Self::Table {
+                mut shard, hash, ..
+            } => {
+                // The shard has stayed locked since room was made for the string, so it
+                // doesn't need to grow and the hasher is never called
+                shard.insert_unique(hash, Entry::new(string, key), |_| {
+                    unreachable!("the shard had room reserved for the string");
+                });
+            }
+            Self::Oversized { mut oversized, .. } => oversized.push((string, key)),
+        }
+    }
+}
+
+impl<K, S> Debug for ShardMap<K, S>
+where
+    K: Copy + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut map = f.debug_map();
+        self.for_each(|string, key| {
+            map.entry(&string, &key);
+        });
+
+        map.finish()
+    }
+}
+
+/// Marks the current thread as holding one of a [`ShardMap`]'s write locks
+///
+/// Anything that calls back into the interner while it's locked on the same thread, like a
+/// hasher or key type that interns strings itself, would deadlock. In debug builds this is
+/// caught and turned into a panic that explains what happened instead
+pub(crate) struct Reentry {
+    /// The map that was locked before this one, which is restored once it's unlocked
+    #[cfg(all(debug_assertions, not(loom)))]
+    previous: *const (),
+}
+
+#[cfg(all(debug_assertions, not(loom)))]
+std::thread_local! {
+    /// The map that the current thread holds a write lock on, if any
+    static LOCKED: core::cell::Cell<*const ()> = const { core::cell::Cell::new(core::ptr::null()) };
+}
+
+impl Reentry {
+    /// Panics if the current thread holds one of `map`'s write locks
+    #[inline]
+    fn check<K, S>(map: &ShardMap<K, S>) {
+        #[cfg(all(debug_assertions, not(loom)))]
+        if LOCKED.with(core::cell::Cell::get) == (map as *const ShardMap<K, S>).cast() {
+            panic!(
+                "re-entered a `ThreadedRodeo` from the thread that's inserting into it, which \
+                 would deadlock. This is usually caused by a hasher or key type that uses the \
+                 interner it belongs to"
+            );
+        }
+
+        #[cfg(not(all(debug_assertions, not(loom))))]
+        let _ = map;
+    }
+
+    /// Marks `map` as write locked by the current thread until the returned value is dropped
+    #[inline]
+    fn enter<K, S>(map: &ShardMap<K, S>) -> Self {
+        #[cfg(all(debug_assertions, not(loom)))]
+        {
+            let map = (map as *const ShardMap<K, S>).cast();
+            Self {
+                previous: LOCKED.with(|locked| locked.replace(map)),
+            }
+        }
+
+        #[cfg(not(all(debug_assertions, not(loom))))]
+        {
+            let _ = map;
+            Self {}
+        }
+    }
+}
+
+#[cfg(all(debug_assertions, not(loom)))]
+impl Drop for Reentry {
+    fn drop(&mut self) {
+        LOCKED.with(|locked| locked.set(self.previous));
+    }
+}
+
+/// Takes a write lock on a shard to insert a string that wasn't found in it, unless `find`
+/// sees the string while waiting for the lock
+///
+/// A contended shard is usually being written to by another thread interning the same
+/// string, which is common with duplicate-heavy input. Rather than queueing up for the write
+/// lock one after another, contending threads wait for a shared lock and look again so that
+/// they can all find the string at once. The string still has to be looked for again under
+/// the write lock, since it can be inserted between the two locks
+fn lock_for_insert<T, R, F>(shard: &RwLock<T>, find: F) -> Result<R, RwLockWriteGuard<'_, T>>
+where
+    F: FnOnce(&T) -> Option<R>,
+{
+    if let Some(guard) = shard.try_write() {
+        return Err(guard);
+    }
+
+    if let Some(found) = find(&read_shard(shard)) {
+        return Ok(found);
+    }
+
+    Err(write_shard(shard))
+}
+
+/// Scrambles the bits of a hash so that every bit of the input affects every bit of the
+/// output, using the finalizer of MurmurHash3
+fn mix(mut hash: u64) -> u64 {
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^ (hash >> 33)
+}
+
+/// The number of shards the concurrent maps are split into, enough that threads rarely
+/// contend on the same shard
+pub(crate) fn shard_count() -> usize {
+    (std::thread::available_parallelism().map_or(1, usize::from) * 4).next_power_of_two()
+}
+
+/// Takes a read lock on a shard
+///
+/// Loom can't see into the shards' locks, so under loom this yields to the model's other
+/// threads while the shard is locked instead of blocking on it
+#[inline]
+pub(crate) fn read_shard<T>(shard: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    #[cfg(loom)]
+    loop {
+        if let Some(guard) = shard.try_read() {
+            return guard;
+        }
+
+        loom::thread::yield_now();
+    }
+
+    #[cfg(not(loom))]
+    shard.read()
+}
+
+/// Takes a write lock on a shard
+///
+/// Loom can't see into the shards' locks, so under loom this yields to the model's other
+/// threads while the shard is locked instead of blocking on it
+#[inline]
+pub(crate) fn write_shard<T>(shard: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    #[cfg(loom)]
+    loop {
+        if let Some(guard) = shard.try_write() {
+            return guard;
+        }
+
+        loom::thread::yield_now();
+    }
+
+    #[cfg(not(loom))]
+    shard.write()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hasher::RandomState;
+    use alloc::string::ToString;
+    use core::hash::{BuildHasherDefault, Hasher};
+
+    fn map() -> ShardMap<u32, RandomState> {
+        ShardMap::try_with_capacity_and_hasher(0, RandomState::default()).unwrap()
+    }
+
+    /// A hasher that leaves the high bits of the hashes of short strings empty
+    #[derive(Default)]
+    struct WeakHasher(u64);
+
+    impl Hasher for WeakHasher {
+        fn write(&mut self, bytes: &[u8]) {
+            for &byte in bytes {
+                self.0 = self.0.wrapping_mul(31).wrapping_add(byte as u64);
+            }
+        }
+
+        fn finish(&self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn entry_size() {
+        assert_eq!(size_of::<Entry<u32>>(), 2 * size_of::<usize>());
+        assert!(size_of::<Entry<u32>>() < size_of::<(&str, u32)>());
+    }
+
+    #[test]
+    fn entry() {
+        let map = map();
+        let hash = map.hash("a");
+        assert_eq!(map.get("a", hash), None);
+
+        match map.entry("a", hash) {
+            ShardEntry::Vacant(entry) => entry.insert("a", 0),
+            ShardEntry::Occupied(_) => panic!("the string wasn't inserted yet"),
+        }
+        assert!(matches!(map.entry("a", hash), ShardEntry::Occupied(0)));
+        assert_eq!(map.get("a", hash), Some(0));
+
+        // Dropping a vacant entry leaves the map untouched
+        drop(map.entry("b", map.hash("b")));
+        assert_eq!(map.get("b", map.hash("b")), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "re-entered a `ThreadedRodeo`")]
+    #[cfg(debug_assertions)]
+    fn reentrant_lookup() {
+        let map = map();
+        let hash = map.hash("a");
+
+        let _entry = map.entry("a", hash);
+        map.get("a", hash);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn reentrant_other_map() {
+        let (first, second) = (map(), map());
+        let hash = first.hash("a");
+
+        // Holding one map's lock doesn't keep the thread from using another map
+        let entry = first.entry("a", hash);
+        second.insert("b", 0);
+        assert_eq!(second.get("b", second.hash("b")), Some(0));
+        drop(entry);
+
+        assert_eq!(first.get("a", hash), None);
+    }
+
+    #[test]
+    fn shards() {
+        let mut map = map();
+        for (key, string) in ["a", "b", "c"].into_iter().enumerate() {
+            map.insert(string, key as u32);
+        }
+
+        let mut shards = 0;
+        let mut entries = Vec::new();
+        map.for_each_shard(|shard| {
+            shards += 1;
+            entries.extend(shard);
+        });
+        entries.sort_unstable();
+        assert_eq!(shards, map.shard_count());
+        assert_eq!(entries, [("a", 0), ("b", 1), ("c", 2)]);
+
+        let mut drained: Vec<_> = (0..map.shard_count())
+            .flat_map(|index| map.drain_shard(index).collect::<Vec<_>>())
+            .collect();
+        drained.sort_unstable();
+        assert_eq!(drained, entries);
+        assert_eq!(map.get("a", map.hash("a")), None);
+    }
+
+    #[test]
+    fn contended_entry() {
+        let map = map();
+        let hash = map.hash("a");
+        map.insert("a", 0);
+
+        // Strings are found without the write lock while the shard is being read
+        let reader = read_shard(map.shard(hash));
+        assert!(matches!(map.entry("a", hash), ShardEntry::Occupied(0)));
+        drop(reader);
+
+        match map.entry("b", map.hash("b")) {
+            ShardEntry::Vacant(entry) => entry.insert("b", 1),
+            ShardEntry::Occupied(_) => panic!("the string wasn't inserted yet"),
+        }
+        assert_eq!(map.get("b", map.hash("b")), Some(1));
+    }
+
+    #[test]
+    fn mix_hashes() {
+        let mut map: ShardMap<u32, BuildHasherDefault<WeakHasher>> =
+            ShardMap::try_with_capacity_and_hasher(0, BuildHasherDefault::default()).unwrap();
+        let strings: Vec<&'static str> = (0..1000)
+            .map(|i| &*Box::leak(i.to_string().into_boxed_str()))
+            .collect();
+        for (key, &string) in strings.iter().enumerate() {
+            map.insert(string, key as u32);
+        }
+
+        let used =
+            |map: &ShardMap<u32, _>| map.shard_lens().iter().filter(|&&len| len != 0).count();
+        assert_eq!(used(&map), 1);
+
+        map.set_mix_hashes(true);
+        assert!(map.mixes_hashes());
+        // Mixed hashes are spread out over most of the shards
+        assert!(used(&map) * 2 > map.shard_lens().len());
+        assert_eq!(map.len(), strings.len());
+        for (key, &string) in strings.iter().enumerate() {
+            assert_eq!(map.get(string, map.hash(string)), Some(key as u32));
+        }
+
+        map.set_mix_hashes(false);
+        assert_eq!(used(&map), 1);
+        assert_eq!(map.get("999", map.hash("999")), Some(999));
+    }
+
+    #[test]
+    fn clear_and_drain() {
+        let mut map = map();
+        for (key, string) in ["a", "b", "c"].into_iter().enumerate() {
+            map.insert(string, key as u32);
+        }
+
+        let mut seen = Vec::new();
+        map.for_each(|string, key| seen.push((string, key)));
+        seen.sort_unstable();
+        assert_eq!(seen, [("a", 0), ("b", 1), ("c", 2)]);
+
+        let mut cleared = false;
+        map.clear_with(|| cleared = true);
+        assert!(cleared);
+        assert_eq!(map.get("a", map.hash("a")), None);
+
+        map.insert("d", 3);
+        assert_eq!(map.drain().collect::<Vec<_>>(), [("d", 3)]);
+        assert_eq!(map.get("d", map.hash("d")), None);
+    }
+}
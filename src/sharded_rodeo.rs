@@ -0,0 +1,379 @@
+use crate::{
+    hasher::RandomState,
+    keys::{Key, Spur},
+    reader::RodeoReader,
+    Capacity, LassoResult, Rodeo,
+};
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Debug},
+    hash::BuildHasher,
+    slice,
+};
+
+/// A set of independent [`Rodeo`]s that can be handed out to worker threads and later
+/// merged into a single interner
+///
+/// Each shard is a normal `Rodeo`, so interning into them doesn't involve any locking.
+/// Once all workers are done, [`ShardedRodeo::into_reader`] merges every shard into one
+/// [`RodeoReader`] and returns a [`ShardRemap`] that translates each shard's keys into the
+/// keys of the merged interner
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{ShardedRodeo, Spur};
+///
+/// let mut sharded: ShardedRodeo<Spur> = ShardedRodeo::new(2);
+///
+/// std::thread::scope(|scope| {
+///     for (idx, shard) in sharded.shards_mut().iter_mut().enumerate() {
+///         scope.spawn(move || {
+///             shard.get_or_intern("shared");
+///             shard.get_or_intern(format!("shard {}", idx));
+///         });
+///     }
+/// });
+///
+/// let local = sharded.shard(1).get("shard 1").unwrap();
+/// let (reader, remap) = sharded.into_reader();
+///
+/// let merged = remap.remap(1, local).unwrap();
+/// assert_eq!(reader.resolve(&merged), "shard 1");
+/// assert_eq!(reader.len(), 3);
+/// ```
+///
+/// [`Rodeo`]: crate::Rodeo
+/// [`RodeoReader`]: crate::RodeoReader
+pub struct ShardedRodeo<K = Spur, S = RandomState> {
+    shards: Vec<Rodeo<K, S>>,
+    /// The hasher given to every shard, kept around for the merged interner
+    hasher: S,
+}
+
+impl<K> ShardedRodeo<K, RandomState>
+where
+    K: Key,
+{
+    /// Create a new `ShardedRodeo` with `shards` empty shards
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{ShardedRodeo, Spur};
+    ///
+    /// let sharded: ShardedRodeo<Spur> = ShardedRodeo::new(4);
+    /// assert_eq!(sharded.num_shards(), 4);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new(shards: usize) -> Self {
+        Self::with_hasher(shards, RandomState::new())
+    }
+}
+
+impl<K, S> ShardedRodeo<K, S>
+where
+    K: Key,
+    S: BuildHasher + Clone,
+{
+    /// Create a new `ShardedRodeo` with `shards` empty shards that all use a clone of `hash_builder`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{ShardedRodeo, Spur};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let sharded: ShardedRodeo<Spur, RandomState> =
+    ///     ShardedRodeo::with_hasher(4, RandomState::new());
+    /// assert_eq!(sharded.num_shards(), 4);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_hasher(shards: usize, hash_builder: S) -> Self {
+        let shards = (0..shards)
+            .map(|_| Rodeo::with_hasher(hash_builder.clone()))
+            .collect();
+
+        Self {
+            shards,
+            hasher: hash_builder,
+        }
+    }
+
+    /// Merges every shard into a single [`RodeoReader`], returning it along with the
+    /// [`ShardRemap`] that translates each shard's keys into the reader's keys
+    ///
+    /// Shards are merged in order, so the keys of the first shard stay the same
+    /// in the merged interner
+    ///
+    /// # Panics
+    ///
+    /// Panics if the merged interner runs out of keys or memory
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{ShardedRodeo, Spur};
+    ///
+    /// let mut sharded: ShardedRodeo<Spur> = ShardedRodeo::new(2);
+    /// let first = sharded.shard_mut(0).get_or_intern("first");
+    /// let second = sharded.shard_mut(1).get_or_intern("second");
+    ///
+    /// let (reader, remap) = sharded.into_reader();
+    /// assert_eq!(remap.remap(0, first), Some(first));
+    /// assert_eq!(reader.resolve(&remap.remap(1, second).unwrap()), "second");
+    /// ```
+    ///
+    /// [`RodeoReader`]: crate::RodeoReader
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn into_reader(self) -> (RodeoReader<K, S>, ShardRemap<K>) {
+        self.try_into_reader()
+            .expect("failed to merge ShardedRodeo shards")
+    }
+
+    /// Merges every shard into a single [`RodeoReader`], returning it along with the
+    /// [`ShardRemap`] that translates each shard's keys into the reader's keys
+    ///
+    /// Returns an error if the merged interner runs out of keys or memory
+    ///
+    /// [`RodeoReader`]: crate::RodeoReader
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_into_reader(self) -> LassoResult<(RodeoReader<K, S>, ShardRemap<K>)> {
+        let (rodeo, remap) = self.try_into_rodeo()?;
+
+        Ok((rodeo.into_reader(), remap))
+    }
+
+    /// Merges every shard into a single [`Rodeo`] that can still be interned into, returning
+    /// it along with the [`ShardRemap`] that translates each shard's keys into the rodeo's keys
+    ///
+    /// Returns an error if the merged interner runs out of keys or memory
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{ShardedRodeo, Spur};
+    ///
+    /// let mut sharded: ShardedRodeo<Spur> = ShardedRodeo::new(2);
+    /// sharded.shard_mut(1).get_or_intern("merged");
+    ///
+    /// let (mut rodeo, remap) = sharded.try_into_rodeo().unwrap();
+    /// let key = rodeo.get_or_intern("merged");
+    /// assert_eq!(remap.shard(1), Some(&[key][..]));
+    /// ```
+    ///
+    /// [`Rodeo`]: crate::Rodeo
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_into_rodeo(self) -> LassoResult<(Rodeo<K, S>, ShardRemap<K>)> {
+        let Self { shards, hasher } = self;
+
+        let strings = shards
+            .iter()
+            .fold(0, |total: usize, shard| total.saturating_add(shard.len()));
+        let mut merged =
+            Rodeo::try_with_capacity_and_hasher(Capacity::for_strings(strings), hasher)?;
+
+        let mut remap = Vec::with_capacity(shards.len());
+        for shard in shards {
+            let keys = shard
+                .strings()
+                .map(|string| merged.try_get_or_intern(string))
+                .collect::<LassoResult<Vec<K>>>()?;
+
+            remap.push(keys);
+        }
+
+        Ok((merged, ShardRemap { shards: remap }))
+    }
+}
+
+impl<K, S> ShardedRodeo<K, S> {
+    /// Returns the number of shards
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn num_shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Gets a reference to the shard at `idx`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn shard(&self, idx: usize) -> &Rodeo<K, S> {
+        &self.shards[idx]
+    }
+
+    /// Gets a mutable reference to the shard at `idx`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn shard_mut(&mut self, idx: usize) -> &mut Rodeo<K, S> {
+        &mut self.shards[idx]
+    }
+
+    /// Gets every shard, they can be split up and handed out to separate threads
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn shards_mut(&mut self) -> &mut [Rodeo<K, S>] {
+        &mut self.shards
+    }
+
+    /// Returns an iterator over every shard
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn shards(&self) -> slice::Iter<'_, Rodeo<K, S>> {
+        self.shards.iter()
+    }
+}
+
+impl<K: Key + Debug, S: Debug> Debug for ShardedRodeo<K, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShardedRodeo")
+            .field("shards", &self.shards)
+            .field("hasher", &self.hasher)
+            .finish()
+    }
+}
+
+/// Translates the keys of each of a [`ShardedRodeo`]'s shards into the keys of the
+/// interner they were merged into
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShardRemap<K> {
+    shards: Vec<Vec<K>>,
+}
+
+impl<K> ShardRemap<K>
+where
+    K: Key,
+{
+    /// Translates a key from the shard at `shard` into its key in the merged interner,
+    /// returning `None` if the shard or key doesn't exist
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn remap(&self, shard: usize, key: K) -> Option<K> {
+        self.shards.get(shard)?.get(key.into_usize()).copied()
+    }
+
+    /// Gets the merged keys for every key of the shard at `shard`, indexed by the shard's keys
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn shard(&self, shard: usize) -> Option<&[K]> {
+        self.shards.get(shard).map(Vec::as_slice)
+    }
+
+    /// Returns the number of shards that were merged
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn num_shards(&self) -> usize {
+        self.shards.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Key, LassoErrorKind, MicroSpur, ShardedRodeo, Spur};
+    #[cfg(feature = "no-std")]
+    use alloc::{string::ToString, vec::Vec};
+
+    #[test]
+    fn empty() {
+        let sharded: ShardedRodeo<Spur> = ShardedRodeo::new(0);
+        assert_eq!(sharded.num_shards(), 0);
+
+        let (reader, remap) = sharded.into_reader();
+        assert!(reader.is_empty());
+        assert_eq!(remap.num_shards(), 0);
+        assert_eq!(remap.remap(0, Spur::default()), None);
+    }
+
+    #[test]
+    fn key_stability() {
+        let mut sharded: ShardedRodeo<Spur> = ShardedRodeo::new(3);
+        let words = [["a", "b", "c"], ["c", "d", "a"], ["e", "b", "f"]];
+
+        let local: Vec<Vec<Spur>> = sharded
+            .shards_mut()
+            .iter_mut()
+            .zip(words.iter())
+            .map(|(shard, words)| words.iter().map(|w| shard.get_or_intern(w)).collect())
+            .collect();
+
+        let (reader, remap) = sharded.into_reader();
+        assert_eq!(reader.len(), 6);
+        assert_eq!(remap.num_shards(), 3);
+
+        for (shard, (words, keys)) in words.iter().zip(local.iter()).enumerate() {
+            for (word, key) in words.iter().zip(keys.iter()) {
+                let merged = remap.remap(shard, *key).unwrap();
+                assert_eq!(reader.resolve(&merged), *word);
+                assert_eq!(reader.get(word), Some(merged));
+            }
+
+            assert_eq!(remap.shard(shard).unwrap().len(), keys.len());
+        }
+
+        // The first shard's keys don't change, and duplicates share a merged key
+        assert_eq!(remap.shard(0).unwrap(), &local[0][..]);
+        assert_eq!(remap.remap(1, local[1][0]), remap.remap(0, local[0][2]));
+        assert_eq!(remap.remap(2, local[2][1]), remap.remap(0, local[0][1]));
+
+        // Keys that don't exist in their shard can't be remapped
+        assert_eq!(remap.remap(0, Spur::try_from_usize(3).unwrap()), None);
+        assert_eq!(remap.remap(3, local[0][0]), None);
+    }
+
+    #[test]
+    fn merge_into_rodeo() {
+        let mut sharded: ShardedRodeo<Spur> = ShardedRodeo::new(2);
+        let key = sharded.shard_mut(1).get_or_intern("shard one");
+        assert_eq!(sharded.shards().map(|shard| shard.len()).sum::<usize>(), 1);
+
+        let (mut rodeo, remap) = sharded.try_into_rodeo().unwrap();
+        let merged = remap.remap(1, key).unwrap();
+        assert_eq!(rodeo.get_or_intern("shard one"), merged);
+        assert_eq!(rodeo.resolve(&merged), "shard one");
+    }
+
+    #[test]
+    fn merge_key_exhaustion() {
+        let mut sharded: ShardedRodeo<MicroSpur> = ShardedRodeo::new(2);
+        for i in 0..200 {
+            sharded.shard_mut(0).get_or_intern(i.to_string());
+            sharded.shard_mut(1).get_or_intern((i + 200).to_string());
+        }
+
+        let err = sharded.try_into_reader().unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::KeySpaceExhaustion);
+    }
+
+    #[test]
+    #[cfg(not(any(miri, feature = "no-std")))]
+    fn threaded_shards() {
+        let mut sharded: ShardedRodeo<Spur> = ShardedRodeo::new(4);
+
+        std::thread::scope(|scope| {
+            for (idx, shard) in sharded.shards_mut().iter_mut().enumerate() {
+                scope.spawn(move || {
+                    for i in 0..100 {
+                        shard.get_or_intern((i * (idx + 1)).to_string());
+                    }
+                });
+            }
+        });
+
+        let locals: Vec<Vec<(Spur, String)>> = sharded
+            .shards()
+            .map(|shard| shard.iter().map(|(k, s)| (k, s.to_string())).collect())
+            .collect();
+
+        let (reader, remap) = sharded.into_reader();
+        for (shard, strings) in locals.iter().enumerate() {
+            for (key, string) in strings {
+                assert_eq!(reader.resolve(&remap.remap(shard, *key).unwrap()), string);
+            }
+        }
+    }
+}
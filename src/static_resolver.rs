@@ -0,0 +1,227 @@
+use crate::{
+    keys::{Key, Spur},
+    util::{Iter, Strings},
+    RodeoResolver,
+};
+use core::fmt::{self, Debug};
+
+/// A [`RodeoResolver`] that lives for the rest of the program, created by
+/// [`RodeoResolver::leak`]
+///
+/// Since the resolver is never dropped, every string it resolves is `&'static str` instead
+/// of being tied to a borrow of the resolver. This makes it easy to hand resolved strings to
+/// code that needs `'static` data without reaching for `transmute`. The resolver's memory is
+/// never freed, so it's meant for interners that are built once and kept until the program
+/// exits
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(miri)]
+/// # fn main() {}
+/// #
+/// # #[cfg(not(miri))]
+/// # fn main() {
+/// use lasso::{Rodeo, StaticResolver};
+///
+/// let mut rodeo = Rodeo::default();
+/// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+///
+/// let resolver: &'static StaticResolver = rodeo.into_resolver().leak();
+/// let string: &'static str = resolver.resolve(&key);
+///
+/// // The string can be moved into a thread without the resolver
+/// let handle = std::thread::spawn(move || string.len());
+/// assert_eq!(handle.join().unwrap(), 38);
+/// # }
+/// ```
+///
+/// [`RodeoResolver`]: crate::RodeoResolver
+/// [`RodeoResolver::leak`]: crate::RodeoResolver::leak
+pub struct StaticResolver<K = Spur> {
+    resolver: RodeoResolver<K>,
+}
+
+impl<K> StaticResolver<K> {
+    pub(crate) fn new(resolver: RodeoResolver<K>) -> Self {
+        Self { resolver }
+    }
+
+    /// Resolves a string by its key
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(miri)]
+    /// # fn main() {}
+    /// #
+    /// # #[cfg(not(miri))]
+    /// # fn main() {
+    /// use lasso::Rodeo;
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// let resolver = rodeo.into_resolver().leak();
+    /// assert_eq!("Strings of things with wings and dings", resolver.resolve(&key));
+    /// # }
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve(&'static self, key: &K) -> &'static str
+    where
+        K: Key,
+    {
+        self.resolver.resolve(key)
+    }
+
+    /// Resolves a string by its key, returning `None` if it's out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(miri)]
+    /// # fn main() {}
+    /// #
+    /// # #[cfg(not(miri))]
+    /// # fn main() {
+    /// use lasso::Rodeo;
+    /// # use lasso::{Key, Spur};
+    ///
+    /// let mut rodeo = Rodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// let resolver = rodeo.into_resolver().leak();
+    /// assert_eq!(Some("Strings of things with wings and dings"), resolver.try_resolve(&key));
+    /// # let key = Spur::try_from_usize(1).unwrap();
+    /// assert_eq!(None, resolver.try_resolve(&key));
+    /// # }
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve(&'static self, key: &K) -> Option<&'static str>
+    where
+        K: Key,
+    {
+        self.resolver.try_resolve(key)
+    }
+
+    /// Resolves a string by its key without preforming bounds checks
+    ///
+    /// # Safety
+    ///
+    /// The key must be valid for the interner the resolver was made from
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub unsafe fn resolve_unchecked(&'static self, key: &K) -> &'static str
+    where
+        K: Key,
+    {
+        // Safety: The caller promises the key is in bounds
+        unsafe { self.resolver.resolve_unchecked(key) }
+    }
+
+    /// Returns `true` if the given key exists in the resolver
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains_key(&self, key: &K) -> bool
+    where
+        K: Key,
+    {
+        self.resolver.contains_key(key)
+    }
+
+    /// Gets the number of strings in the resolver
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.resolver.len()
+    }
+
+    /// Returns `true` if there are no strings in the resolver
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.resolver.is_empty()
+    }
+
+    /// Returns an iterator over the strings and their keys, which all live for the rest
+    /// of the program
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter(&'static self) -> Iter<'static, K> {
+        self.resolver.iter()
+    }
+
+    /// Returns an iterator over the strings, which all live for the rest of the program
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn strings(&'static self) -> Strings<'static, K> {
+        self.resolver.strings()
+    }
+
+    /// Gets the leaked [`RodeoResolver`]
+    ///
+    /// [`RodeoResolver`]: crate::RodeoResolver
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolver(&self) -> &RodeoResolver<K> {
+        &self.resolver
+    }
+}
+
+impl<K: Key + Debug> Debug for StaticResolver<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticResolver")
+            .field("resolver", &self.resolver)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Key, Resolver, Rodeo, Spur, StaticResolver};
+    #[cfg(feature = "no-std")]
+    use alloc::vec::Vec;
+
+    // Miri reports the leaked resolvers as memory leaks
+    #[test]
+    #[cfg(not(miri))]
+    fn leak() {
+        let mut rodeo = Rodeo::default();
+        let a = rodeo.get_or_intern("a");
+        let b = rodeo.get_or_intern_static("b");
+        let missing = Spur::try_from_usize(2).unwrap();
+
+        let resolver: &'static StaticResolver = rodeo.into_resolver().leak();
+        assert_eq!(resolver.len(), 2);
+        assert!(!resolver.is_empty());
+        assert_eq!(resolver.resolve(&a), "a");
+        assert_eq!(resolver.try_resolve(&b), Some("b"));
+        assert_eq!(resolver.try_resolve(&missing), None);
+        assert!(resolver.contains_key(&b));
+        assert!(!resolver.contains_key(&missing));
+        assert_eq!(unsafe { resolver.resolve_unchecked(&a) }, "a");
+        assert_eq!(resolver.strings().collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(resolver.iter().count(), 2);
+        assert_eq!(resolver.resolver().len(), 2);
+
+        // Resolved strings are 'static
+        let string: &'static str = resolver.resolve(&a);
+        assert_eq!(string, "a");
+
+        fn resolve_all(resolver: &impl Resolver<Spur>, keys: &[Spur]) -> usize {
+            keys.iter().map(|key| resolver.resolve(key).len()).sum()
+        }
+        assert_eq!(resolve_all(resolver, &[a, b]), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(not(miri))]
+    fn resolve_missing() {
+        let rodeo: Rodeo = Rodeo::default();
+        rodeo
+            .into_resolver()
+            .leak()
+            .resolve(&Spur::try_from_usize(0).unwrap());
+    }
+}
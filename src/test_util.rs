@@ -0,0 +1,646 @@
+//! Utilities for testing interners against a simple reference model
+//!
+//! [`ModelInterner`] is a deliberately naive interner whose behavior is easy to verify
+//! by hand. Sequences of [`Op`]s can be run against both it and any real [`Interner`],
+//! checking that every operation returns the same result. [`OpGenerator`] produces
+//! random operations from a seed, and with the `proptest` feature [`any_op`] and
+//! [`op_sequence`] produce them as [`proptest`] strategies that shrink failing sequences
+//!
+//! This module is only available with the `test-utils` feature
+//!
+//! # Example
+//!
+//! ```rust
+//! use lasso::{
+//!     test_util::{check_interner, check_rodeo, OpGenerator},
+//!     FixedState, MicroSpur, Rodeo, Spur,
+//! };
+//!
+//! let ops = OpGenerator::new(0xC0FFEE).ops(1000);
+//!
+//! // Check an interner directly
+//! let mut rodeo: Rodeo<MicroSpur> = Rodeo::new();
+//! check_interner(&mut rodeo, &ops).unwrap();
+//!
+//! // Check a `Rodeo` along with the reader and resolver it converts into
+//! check_rodeo::<Spur, _>(FixedState::new(), &ops).unwrap();
+//! ```
+//!
+//! [`Interner`]: crate::Interner
+//! [`proptest`]: https://crates.io/crates/proptest
+
+use crate::{Interner, IntoReader, IntoResolver, Key, LassoErrorKind, Reader, Resolver, Rodeo};
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt::{self, Debug, Display},
+    hash::BuildHasher,
+    marker::PhantomData,
+};
+#[cfg(feature = "proptest")]
+use proptest::{
+    arbitrary::Arbitrary,
+    collection::{self, SizeRange},
+    prop_oneof, sample,
+    strategy::{BoxedStrategy, Strategy},
+};
+
+/// A single operation to run against an interner
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Op {
+    /// Intern a string with [`Interner::try_get_or_intern`]
+    ///
+    /// [`Interner::try_get_or_intern`]: crate::Interner::try_get_or_intern
+    Intern(String),
+    /// Intern a static string with [`Interner::try_get_or_intern_static`]
+    ///
+    /// [`Interner::try_get_or_intern_static`]: crate::Interner::try_get_or_intern_static
+    InternStatic(&'static str),
+    /// Get the key of a string with [`Reader::get`] and [`Reader::contains`]
+    ///
+    /// [`Reader::get`]: crate::Reader::get
+    /// [`Reader::contains`]: crate::Reader::contains
+    Get(String),
+    /// Resolve the key created from an index with [`Resolver::try_resolve`] and [`Resolver::contains_key`]
+    ///
+    /// [`Resolver::try_resolve`]: crate::Resolver::try_resolve
+    /// [`Resolver::contains_key`]: crate::Resolver::contains_key
+    Resolve(usize),
+}
+
+/// A naive reference interner that every other interner should behave identically to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelInterner<K> {
+    strings: Vec<String>,
+    keys: BTreeMap<String, usize>,
+    __key: PhantomData<K>,
+}
+
+impl<K> ModelInterner<K>
+where
+    K: Key,
+{
+    /// Create a new, empty `ModelInterner`
+    pub fn new() -> Self {
+        Self {
+            strings: Vec::new(),
+            keys: BTreeMap::new(),
+            __key: PhantomData,
+        }
+    }
+
+    /// Get the key for a string, interning it if it does not yet exist
+    ///
+    /// Returns an error if the key type has run out of keys
+    pub fn try_get_or_intern(&mut self, val: &str) -> Result<K, LassoErrorKind> {
+        if let Some(key) = self.get(val) {
+            return Ok(key);
+        }
+
+        let key =
+            K::try_from_usize(self.strings.len()).ok_or(LassoErrorKind::KeySpaceExhaustion)?;
+        self.keys.insert(val.to_string(), self.strings.len());
+        self.strings.push(val.to_string());
+
+        Ok(key)
+    }
+
+    /// Get the key value of a string, returning `None` if it doesn't exist
+    pub fn get(&self, val: &str) -> Option<K> {
+        self.keys.get(val).and_then(|&idx| K::try_from_usize(idx))
+    }
+
+    /// Resolves a string by its key, returning `None` if it doesn't exist
+    pub fn try_resolve(&self, key: &K) -> Option<&str> {
+        self.strings.get(key.into_usize()).map(String::as_str)
+    }
+
+    /// Gets the number of interned strings
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if there are no interned strings
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Returns an iterator over every interned string and its key
+    pub fn iter(&self) -> impl Iterator<Item = (K, &str)> + '_ {
+        self.strings
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, string)| Some((K::try_from_usize(idx)?, string.as_str())))
+    }
+}
+
+impl<K> Default for ModelInterner<K>
+where
+    K: Key,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The error returned when an interner disagrees with the [`ModelInterner`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelMismatch {
+    /// The index of the operation that failed, or `None` if the failure happened
+    /// while comparing the interner's final state
+    pub op_index: Option<usize>,
+    /// A description of how the interner differed from the model
+    pub message: String,
+}
+
+impl ModelMismatch {
+    fn new(op_index: Option<usize>, message: String) -> Self {
+        Self { op_index, message }
+    }
+}
+
+impl Display for ModelMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.op_index {
+            Some(idx) => write!(
+                f,
+                "operation {} diverged from the model: {}",
+                idx, self.message
+            ),
+            None => write!(f, "final state diverged from the model: {}", self.message),
+        }
+    }
+}
+
+#[cfg(not(feature = "no-std"))]
+impl std::error::Error for ModelMismatch {}
+
+macro_rules! ensure_eq {
+    ($idx:expr, $actual:expr, $expected:expr, $what:literal $(,)?) => {{
+        let (actual, expected) = ($actual, $expected);
+        if actual != expected {
+            return Err(ModelMismatch::new(
+                $idx,
+                format!("{}: expected {:?}, got {:?}", $what, expected, actual),
+            ));
+        }
+    }};
+}
+
+/// Runs every operation against `interner` and a fresh [`ModelInterner`], returning the
+/// model afterwards or the first place the two disagreed
+///
+/// Interning failures are only allowed when the key type has run out of keys or
+/// the interner has reached its memory limit
+pub fn check_interner<K, I>(interner: &mut I, ops: &[Op]) -> Result<ModelInterner<K>, ModelMismatch>
+where
+    K: Key + Debug,
+    I: Interner<K> + ?Sized,
+{
+    let mut model = ModelInterner::new();
+
+    for (idx, op) in ops.iter().enumerate() {
+        let at = Some(idx);
+
+        match op {
+            Op::Intern(string) => {
+                check_intern(at, &mut model, string, interner.try_get_or_intern(string))?
+            }
+            Op::InternStatic(string) => check_intern(
+                at,
+                &mut model,
+                string,
+                interner.try_get_or_intern_static(string),
+            )?,
+            Op::Get(_) | Op::Resolve(_) => check_read_op(at, &*interner, &model, op)?,
+        }
+
+        ensure_eq!(at, interner.len(), model.len(), "interner length");
+    }
+
+    check_reader(&*interner, &model)?;
+
+    Ok(model)
+}
+
+fn check_intern<K>(
+    at: Option<usize>,
+    model: &mut ModelInterner<K>,
+    string: &str,
+    result: crate::LassoResult<K>,
+) -> Result<(), ModelMismatch>
+where
+    K: Key + Debug,
+{
+    match (result, model.get(string)) {
+        (Ok(key), Some(expected)) => ensure_eq!(at, key, expected, "key of existing string"),
+        (Ok(key), None) => {
+            let expected = model.try_get_or_intern(string).map_err(|kind| {
+                ModelMismatch::new(
+                    at,
+                    format!("interned {:?} with no keys left: {:?}", string, kind),
+                )
+            })?;
+            ensure_eq!(at, key, expected, "key of new string");
+        }
+        (Err(err), Some(_)) => {
+            return Err(ModelMismatch::new(
+                at,
+                format!("failed to get existing string {:?}: {}", string, err),
+            ))
+        }
+        (Err(err), None) => match err.kind() {
+            LassoErrorKind::KeySpaceExhaustion => ensure_eq!(
+                at,
+                K::try_from_usize(model.len()).map(Key::into_usize),
+                None,
+                "key space exhaustion with keys left",
+            ),
+            LassoErrorKind::MemoryLimitReached => {}
+            kind => {
+                return Err(ModelMismatch::new(
+                    at,
+                    format!("unexpected error interning {:?}: {:?}", string, kind),
+                ))
+            }
+        },
+    }
+
+    Ok(())
+}
+
+fn check_read_op<K, R>(
+    at: Option<usize>,
+    reader: &R,
+    model: &ModelInterner<K>,
+    op: &Op,
+) -> Result<(), ModelMismatch>
+where
+    K: Key + Debug,
+    R: Reader<K> + ?Sized,
+{
+    match op {
+        Op::Get(string) => {
+            ensure_eq!(at, reader.get(string), model.get(string), "key of string");
+            ensure_eq!(
+                at,
+                reader.contains(string),
+                model.get(string).is_some(),
+                "contains string",
+            );
+        }
+        Op::Resolve(idx) => check_resolve(at, reader, model, *idx)?,
+        Op::Intern(_) | Op::InternStatic(_) => {}
+    }
+
+    Ok(())
+}
+
+fn check_resolve<K, R>(
+    at: Option<usize>,
+    resolver: &R,
+    model: &ModelInterner<K>,
+    idx: usize,
+) -> Result<(), ModelMismatch>
+where
+    K: Key + Debug,
+    R: Resolver<K> + ?Sized,
+{
+    if let Some(key) = K::try_from_usize(idx) {
+        ensure_eq!(
+            at,
+            resolver.try_resolve(&key),
+            model.try_resolve(&key),
+            "resolved string",
+        );
+        ensure_eq!(
+            at,
+            resolver.contains_key(&key),
+            model.try_resolve(&key).is_some(),
+            "contains key",
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks that every string interned in the model can be looked up and resolved
+/// through `reader`
+pub fn check_reader<K, R>(reader: &R, model: &ModelInterner<K>) -> Result<(), ModelMismatch>
+where
+    K: Key + Debug,
+    R: Reader<K> + ?Sized,
+{
+    check_resolver(reader, model)?;
+    for (key, string) in model.iter() {
+        ensure_eq!(None, reader.get(string), Some(key), "key of string");
+    }
+
+    Ok(())
+}
+
+/// Checks that every key in the model resolves to the same string through `resolver`
+pub fn check_resolver<K, R>(resolver: &R, model: &ModelInterner<K>) -> Result<(), ModelMismatch>
+where
+    K: Key + Debug,
+    R: Resolver<K> + ?Sized,
+{
+    ensure_eq!(None, resolver.len(), model.len(), "interner length");
+    for (key, string) in model.iter() {
+        ensure_eq!(
+            None,
+            resolver.try_resolve(&key),
+            Some(string),
+            "resolved string"
+        );
+    }
+
+    Ok(())
+}
+
+/// Runs every operation against a [`Rodeo`] with the given hasher, then converts it into
+/// a [`RodeoReader`] and a [`RodeoResolver`], checking each against the model
+///
+/// [`Rodeo`]: crate::Rodeo
+/// [`RodeoReader`]: crate::RodeoReader
+/// [`RodeoResolver`]: crate::RodeoResolver
+pub fn check_rodeo<K, S>(hash_builder: S, ops: &[Op]) -> Result<ModelInterner<K>, ModelMismatch>
+where
+    K: Key + Debug + 'static,
+    S: BuildHasher + Clone + 'static,
+{
+    let mut rodeo: Rodeo<K, S> = Rodeo::with_hasher(hash_builder);
+    let model = check_interner(&mut rodeo, ops)?;
+    check_conversions(rodeo, ops).map(|_| model)
+}
+
+/// Checks an interner's reader and resolver against the model built by running `ops`
+/// against it, re-running every read operation against the reader
+pub fn check_conversions<K, I>(interner: I, ops: &[Op]) -> Result<(), ModelMismatch>
+where
+    K: Key + Debug,
+    I: IntoReader<K> + 'static,
+    I::Reader: IntoResolver<K> + 'static,
+{
+    let mut model = ModelInterner::<K>::new();
+    for (key, string) in (0..interner.len()).filter_map(|idx| {
+        let key = K::try_from_usize(idx)?;
+        Some((key, interner.try_resolve(&key)?.to_string()))
+    }) {
+        ensure_eq!(
+            None,
+            model.try_get_or_intern(&string).ok(),
+            Some(key),
+            "key order of interned strings",
+        );
+    }
+
+    let reader = interner.into_reader();
+    check_reader(&reader, &model)?;
+    for (idx, op) in ops.iter().enumerate() {
+        check_read_op(Some(idx), &reader, &model, op)?;
+    }
+
+    let resolver = reader.into_resolver();
+    check_resolver(&resolver, &model)?;
+    for (idx, op) in ops.iter().enumerate() {
+        if let Op::Resolve(key) = op {
+            check_resolve(Some(idx), &resolver, &model, *key)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates random [`Op`]s from a seed
+///
+/// Strings are drawn from a small alphabet so that operations frequently hit
+/// already interned strings, with the occasional long string to force the
+/// interner's arena to grow
+#[derive(Debug, Clone)]
+pub struct OpGenerator {
+    state: u64,
+}
+
+impl OpGenerator {
+    /// Static strings that [`Op::InternStatic`] operations are created from
+    const STATIC_STRINGS: &'static [&'static str] = &["", "a", "ab", "static", "lasso", "🦀"];
+    /// The characters that generated strings are made of
+    const ALPHABET: &'static [char] = &['a', 'b', 'c', 'd'];
+
+    /// Create a new `OpGenerator`, the same seed always generates the same operations
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Generates the next operation
+    pub fn next_op(&mut self) -> Op {
+        match self.next_u64() % 8 {
+            0..=2 => Op::Intern(self.next_string()),
+            3 => Op::InternStatic(
+                Self::STATIC_STRINGS[self.below(Self::STATIC_STRINGS.len() as u64) as usize],
+            ),
+            4 | 5 => Op::Get(self.next_string()),
+            _ => Op::Resolve(self.below(300) as usize),
+        }
+    }
+
+    /// Generates `len` operations
+    pub fn ops(&mut self, len: usize) -> Vec<Op> {
+        (0..len).map(|_| self.next_op()).collect()
+    }
+
+    fn next_string(&mut self) -> String {
+        let len = if self.below(32) == 0 {
+            // Occasionally make strings too long to fit in the arena's first bucket
+            4096 + self.below(4096)
+        } else {
+            self.below(4)
+        };
+
+        (0..len)
+            .map(|_| Self::ALPHABET[self.below(Self::ALPHABET.len() as u64) as usize])
+            .collect()
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// splitmix64
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Creates a [`proptest`] strategy for a single [`Op`]
+///
+/// Operations are weighted and made from the same strings as the ones generated by
+/// [`OpGenerator`], so they frequently hit already interned strings
+///
+/// This function is only available with the `proptest` feature
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{
+///     test_util::{any_op, check_rodeo},
+///     FixedState, Spur,
+/// };
+/// use proptest::{collection, proptest};
+///
+/// proptest!(|(ops in collection::vec(any_op(), 0..100))| {
+///     check_rodeo::<Spur, _>(FixedState::new(), &ops).unwrap();
+/// });
+/// ```
+///
+/// [`proptest`]: https://crates.io/crates/proptest
+#[cfg(feature = "proptest")]
+pub fn any_op() -> impl Strategy<Value = Op> {
+    let string = || {
+        prop_oneof![
+            31 => collection::vec(sample::select(OpGenerator::ALPHABET), 0..4),
+            // Occasionally make strings too long to fit in the arena's first bucket
+            1 => collection::vec(sample::select(OpGenerator::ALPHABET), 4096..8192),
+        ]
+        .prop_map(|chars| chars.into_iter().collect::<String>())
+    };
+
+    prop_oneof![
+        3 => string().prop_map(Op::Intern),
+        1 => sample::select(OpGenerator::STATIC_STRINGS).prop_map(Op::InternStatic),
+        2 => string().prop_map(Op::Get),
+        2 => (0..300_usize).prop_map(Op::Resolve),
+    ]
+}
+
+/// Creates a [`proptest`] strategy for sequences of [`Op`]s whose length is within `len`
+///
+/// This function is only available with the `proptest` feature
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{
+///     test_util::{check_interner, op_sequence},
+///     MicroSpur, Rodeo,
+/// };
+/// use proptest::proptest;
+///
+/// proptest!(|(ops in op_sequence(0..100))| {
+///     let mut rodeo: Rodeo<MicroSpur> = Rodeo::new();
+///     check_interner(&mut rodeo, &ops).unwrap();
+/// });
+/// ```
+///
+/// [`proptest`]: https://crates.io/crates/proptest
+#[cfg(feature = "proptest")]
+pub fn op_sequence(len: impl Into<SizeRange>) -> impl Strategy<Value = Vec<Op>> {
+    collection::vec(any_op(), len)
+}
+
+/// Generates operations with [`any_op`]
+#[cfg(feature = "proptest")]
+impl Arbitrary for Op {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        any_op().boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_interner, check_rodeo, ModelInterner, Op, OpGenerator};
+    use crate::{FixedState, MicroSpur, MiniSpur, Resolver, Rodeo, Spur};
+    use alloc::string::ToString;
+
+    #[test]
+    fn generator_is_deterministic() {
+        assert_eq!(OpGenerator::new(1).ops(100), OpGenerator::new(1).ops(100));
+        assert_ne!(OpGenerator::new(1).ops(100), OpGenerator::new(2).ops(100));
+    }
+
+    #[test]
+    fn model() {
+        let mut model: ModelInterner<MicroSpur> = ModelInterner::new();
+        assert!(model.is_empty());
+
+        let a = model.try_get_or_intern("a").unwrap();
+        assert_eq!(model.try_get_or_intern("a"), Ok(a));
+        assert_eq!(model.get("a"), Some(a));
+        assert_eq!(model.try_resolve(&a), Some("a"));
+        assert_eq!(model.len(), 1);
+
+        for i in 0..u8::MAX as usize {
+            let _ = model.try_get_or_intern(&i.to_string());
+        }
+        assert_eq!(model.len(), u8::MAX as usize);
+        assert!(model.try_get_or_intern("full").is_err());
+    }
+
+    #[test]
+    fn rodeo_matches_model() {
+        for seed in 0..20 {
+            let ops = OpGenerator::new(seed).ops(500);
+
+            check_rodeo::<Spur, _>(FixedState::new(), &ops).unwrap();
+            check_rodeo::<MiniSpur, _>(crate::hasher::RandomState::new(), &ops).unwrap();
+            check_rodeo::<MicroSpur, _>(FixedState::with_seed(seed), &ops).unwrap();
+        }
+    }
+
+    #[test]
+    fn detects_mismatches() {
+        // An interner that was already used doesn't match a fresh model
+        let mut rodeo: Rodeo<Spur> = Rodeo::new();
+        rodeo.get_or_intern("already here");
+
+        let err = check_interner(&mut rodeo, &[Op::Intern("a".to_string())]).unwrap_err();
+        assert_eq!(err.op_index, Some(0));
+
+        let mut boxed: crate::DynInterner = alloc::boxed::Box::<Rodeo<Spur>>::default();
+        let ops = [Op::Intern("a".to_string()), Op::Resolve(0)];
+        let model = check_interner(&mut boxed, &ops).unwrap();
+        assert_eq!(model.len(), boxed.len());
+    }
+
+    #[test]
+    #[cfg(all(feature = "multi-threaded", not(feature = "no-std")))]
+    fn threaded_rodeo_matches_model() {
+        use crate::ThreadedRodeo;
+
+        for seed in 0..10 {
+            let ops = OpGenerator::new(seed).ops(500);
+
+            let mut rodeo: ThreadedRodeo<MicroSpur> = ThreadedRodeo::new();
+            let model = check_interner(&mut rodeo, &ops).unwrap();
+            super::check_conversions(rodeo, &ops).unwrap();
+            assert!(model.len() <= u8::MAX as usize);
+        }
+    }
+
+    #[cfg(all(feature = "proptest", not(miri)))]
+    proptest::proptest! {
+        #[test]
+        fn rodeo_matches_model_proptest(ops in super::op_sequence(0..200)) {
+            check_rodeo::<MicroSpur, _>(FixedState::new(), &ops).unwrap();
+        }
+
+        #[test]
+        fn arbitrary_ops(ops in proptest::collection::vec(proptest::arbitrary::any::<Op>(), 0..50)) {
+            let mut rodeo: Rodeo<Spur> = Rodeo::new();
+            check_interner(&mut rodeo, &ops).unwrap();
+        }
+    }
+}
@@ -1,20 +1,33 @@
 use crate::{
-    arenas::{AnyArena, LockfreeArena},
+    arenas::{AnyArena, BucketAllocator, LockfreeArena},
     hasher::RandomState,
+    key_shards::{KeyShards, KeyShardsIter},
     keys::{Key, Spur},
+    local_handle::LocalRodeoHandle,
     reader::RodeoReader,
     resolver::RodeoResolver,
-    Capacity, LassoError, LassoErrorKind, LassoResult, MemoryLimits, Rodeo,
+    rodeo::{find_string, insert_string, StringMap},
+    shard_map::{ShardEntries, ShardEntry, ShardMap},
+    sync::{AtomicUsize, Ordering},
+    util::{self, MemoryStats, MetricsSnapshot},
+    Capacity, KeySet, LassoError, LassoErrorKind, LassoResult, MemoryLimits, Reader, Rodeo,
 };
+#[cfg(feature = "profile-interning")]
+use crate::profile::{CallSiteProfile, ConcurrentInternProfile};
+#[cfg(feature = "timestamps")]
+use crate::timestamps::ConcurrentTimestamps;
+#[cfg(feature = "compact_str")]
+use compact_str::CompactString;
 use core::{
     fmt::{Debug, Formatter, Result as FmtResult},
     hash::{BuildHasher, Hash},
-    iter::{self, FromIterator},
+    iter::{FromIterator, FusedIterator},
+    marker::PhantomData,
+    num::NonZeroUsize,
     ops::Index,
-    sync::atomic::{AtomicUsize, Ordering},
+    ptr,
 };
-use dashmap::{mapref::entry::Entry, DashMap, SharedValue};
-use hashbrown::{hash_map::RawEntryMut, HashMap};
+use std::{borrow::Cow, sync::Arc};
 
 macro_rules! index_unchecked_mut {
     ($slice:expr, $idx:expr) => {{
@@ -32,23 +45,41 @@ macro_rules! index_unchecked_mut {
 /// returning a unique key to re-access it with `O(1)` internment and resolution.
 ///
 /// This struct is only available with the `multi-threaded` feature!
-/// By default ThreadedRodeo uses the [`Spur`] type for keys and [`RandomState`] as the hasher
+/// By default ThreadedRodeo uses the [`Spur`] type for keys and [`RandomState`] as the hasher.
+/// The hasher is only used for hashing strings, keys are hashed with a small built-in hasher
+/// since they're dense integers
+///
+/// The hasher and key type must not use the interner they belong to. Both can be called while
+/// one of the interner's locks is held, so using the interner from them would deadlock. Debug
+/// builds catch this and panic instead
 ///
 /// [`Spur`]: crate::Spur
 /// [`ahash::RandomState`]: https://docs.rs/ahash/0.3.2/ahash/struct.RandomState.html
 /// [`RandomState`]: index.html#cargo-features
 pub struct ThreadedRodeo<K = Spur, S = RandomState> {
     // TODO: Should this be migrated over to the scheme that `Rodeo` uses for string storage?
-    //       Need benchmarks to see the perf impact of two dashmap lookups and see if that's worth
+    //       Need benchmarks to see the perf impact of two map lookups and see if that's worth
     //       the storage impact of extra string pointers lying around
     /// Map that allows str to key resolution
-    map: DashMap<&'static str, K, S>,
+    map: ShardMap<K, S>,
     /// Map that allows key to str resolution
-    pub(crate) strings: DashMap<K, &'static str, S>,
+    pub(crate) strings: KeyShards<K>,
+    /// The strings interned with [`ThreadedRodeo::get_or_intern_static`], which borrow their
+    /// `'static` data instead of living in the arena
+    statics: KeyShards<K>,
     /// The current key value
     key: AtomicUsize,
+    /// The number of times the interner has been cleared, which lets
+    /// [`LocalRodeoHandle`]s notice when their cached keys have gone stale
+    clears: AtomicUsize,
     /// The arena where all strings are stored
     arena: LockfreeArena,
+    /// How much every call site has interned
+    #[cfg(feature = "profile-interning")]
+    profile: ConcurrentInternProfile,
+    /// The time every string was interned at
+    #[cfg(feature = "timestamps")]
+    timestamps: ConcurrentTimestamps,
 }
 
 // TODO: More parity functions with std::HashMap
@@ -94,6 +125,30 @@ where
         )
     }
 
+    /// Attempts to create a new ThreadedRodeo
+    ///
+    /// Returns an error instead of panicking if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{ThreadedRodeo, Spur};
+    ///
+    /// let rodeo: ThreadedRodeo<Spur> = ThreadedRodeo::try_new().unwrap();
+    /// let hello = rodeo.get_or_intern("Hello, ");
+    ///
+    /// assert_eq!("Hello, ", rodeo.resolve(&hello));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_new() -> LassoResult<Self> {
+        Self::try_with_capacity_memory_limits_and_hasher(
+            Capacity::default(),
+            MemoryLimits::default(),
+            RandomState::new(),
+        )
+    }
+
     /// Create a new ThreadedRodeo with the specified capacity. The interner will be able to hold `capacity`
     /// strings without reallocating. If capacity is 0, the interner will not allocate.
     ///
@@ -117,6 +172,28 @@ where
         )
     }
 
+    /// Attempts to create a new ThreadedRodeo with the specified capacity
+    ///
+    /// Returns an error instead of panicking if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{ThreadedRodeo, Capacity, Spur};
+    ///
+    /// let rodeo: ThreadedRodeo<Spur> = ThreadedRodeo::try_with_capacity(Capacity::for_strings(10)).unwrap();
+    /// ```
+    ///
+    /// [`Capacity`]: crate::Capacity
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_with_capacity(capacity: Capacity) -> LassoResult<Self> {
+        Self::try_with_capacity_memory_limits_and_hasher(
+            capacity,
+            MemoryLimits::default(),
+            RandomState::new(),
+        )
+    }
+
     /// Create a new ThreadedRodeo with the specified memory limits. The interner will be able to hold `max_memory_usage`
     /// bytes of interned strings until it will start returning `None` from `try_get_or_intern` or panicking from
     /// `get_or_intern`.
@@ -144,6 +221,28 @@ where
         )
     }
 
+    /// Attempts to create a new ThreadedRodeo with the specified memory limits
+    ///
+    /// Returns an error instead of panicking if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{ThreadedRodeo, MemoryLimits, Spur};
+    ///
+    /// let rodeo: ThreadedRodeo<Spur> = ThreadedRodeo::try_with_memory_limits(MemoryLimits::for_memory_usage(4096)).unwrap();
+    /// ```
+    ///
+    /// [`MemoryLimits`]: crate::MemoryLimits
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_with_memory_limits(memory_limits: MemoryLimits) -> LassoResult<Self> {
+        Self::try_with_capacity_memory_limits_and_hasher(
+            Capacity::default(),
+            memory_limits,
+            RandomState::new(),
+        )
+    }
+
     /// Create a new ThreadedRodeo with the specified capacity and memory limits. The interner will be able to hold `max_memory_usage`
     /// bytes of interned strings until it will start returning `None` from `try_get_or_intern` or panicking from
     /// `get_or_intern`.
@@ -170,6 +269,32 @@ where
     ) -> Self {
         Self::with_capacity_memory_limits_and_hasher(capacity, memory_limits, RandomState::new())
     }
+
+    /// Attempts to create a new ThreadedRodeo with the specified capacity and memory limits
+    ///
+    /// Returns an error instead of panicking if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{ThreadedRodeo, Capacity, MemoryLimits, Spur};
+    ///
+    /// let rodeo: ThreadedRodeo<Spur> = ThreadedRodeo::try_with_capacity_and_memory_limits(
+    ///     Capacity::for_strings(10),
+    ///     MemoryLimits::for_memory_usage(4096),
+    /// )
+    /// .unwrap();
+    /// ```
+    ///
+    /// [`Capacity`]: crate::Capacity
+    /// [`MemoryLimits`]: crate::MemoryLimits
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_with_capacity_and_memory_limits(
+        capacity: Capacity,
+        memory_limits: MemoryLimits,
+    ) -> LassoResult<Self> {
+        Self::try_with_capacity_memory_limits_and_hasher(capacity, memory_limits, RandomState::new())
+    }
 }
 
 impl<K, S> ThreadedRodeo<K, S>
@@ -197,6 +322,28 @@ where
         )
     }
 
+    /// Attempts to create an empty ThreadedRodeo which will use the given hasher for its internal hashmap
+    ///
+    /// Returns an error instead of panicking if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Spur, ThreadedRodeo};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: ThreadedRodeo<Spur, RandomState> = ThreadedRodeo::try_with_hasher(RandomState::new()).unwrap();
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_with_hasher(hash_builder: S) -> LassoResult<Self> {
+        Self::try_with_capacity_memory_limits_and_hasher(
+            Capacity::default(),
+            MemoryLimits::default(),
+            hash_builder,
+        )
+    }
+
     /// Creates a new ThreadedRodeo with the specified capacity that will use the given hasher for its internal hashmap
     ///
     /// See [`Capacity`] for more details
@@ -220,6 +367,29 @@ where
         )
     }
 
+    /// Attempts to create a new ThreadedRodeo with the specified capacity that will use the given hasher for its internal hashmap
+    ///
+    /// Returns an error instead of panicking if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Spur, Capacity, ThreadedRodeo};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: ThreadedRodeo<Spur, RandomState> = ThreadedRodeo::try_with_capacity_and_hasher(Capacity::for_strings(10), RandomState::new()).unwrap();
+    /// ```
+    ///
+    /// [`Capacity`]: crate::Capacity
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_with_capacity_and_hasher(capacity: Capacity, hash_builder: S) -> LassoResult<Self> {
+        Self::try_with_capacity_memory_limits_and_hasher(
+            capacity,
+            MemoryLimits::default(),
+            hash_builder,
+        )
+    }
+
     /// Creates a new ThreadedRodeo with the specified capacity and memory limits that will use the given hasher for its internal hashmap
     ///
     /// See [`Capacity`] and [`MemoryLimits`] for more information
@@ -245,16 +415,183 @@ where
         memory_limits: MemoryLimits,
         hash_builder: S,
     ) -> Self {
+        Self::try_with_capacity_memory_limits_and_hasher(capacity, memory_limits, hash_builder)
+            .expect("failed to allocate memory for interner")
+    }
+
+    /// Attempts to create a new ThreadedRodeo with the specified capacity and memory limits that will use the given hasher for its internal hashmap
+    ///
+    /// Returns an error instead of panicking if the memory for the interner couldn't be allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Spur, Capacity, MemoryLimits, ThreadedRodeo};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let rodeo: ThreadedRodeo<Spur, RandomState> = ThreadedRodeo::try_with_capacity_memory_limits_and_hasher(
+    ///     Capacity::for_strings(10),
+    ///     MemoryLimits::for_memory_usage(4096),
+    ///     RandomState::new(),
+    /// )
+    /// .unwrap();
+    /// ```
+    ///
+    /// [`Capacity`]: crate::Capacity
+    /// [`MemoryLimits`]: crate::MemoryLimits
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_with_capacity_memory_limits_and_hasher(
+        capacity: Capacity,
+        memory_limits: MemoryLimits,
+        hash_builder: S,
+    ) -> LassoResult<Self> {
+        Self::try_with_bucket_allocator(
+            capacity,
+            memory_limits,
+            hash_builder,
+            BucketAllocator::Global,
+        )
+    }
+
+    /// Creates a new ThreadedRodeo whose arena allocates all of its memory from `allocator`
+    pub(crate) fn try_with_bucket_allocator(
+        capacity: Capacity,
+        memory_limits: MemoryLimits,
+        hash_builder: S,
+        allocator: BucketAllocator,
+    ) -> LassoResult<Self> {
         let Capacity { strings, bytes } = capacity;
-        let MemoryLimits { max_memory_usage } = memory_limits;
+        let MemoryLimits {
+            max_memory_usage,
+            total_budget,
+        } = memory_limits;
 
-        Self {
-            map: DashMap::with_capacity_and_hasher(strings, hash_builder.clone()),
-            strings: DashMap::with_capacity_and_hasher(strings, hash_builder),
+        let map = ShardMap::try_with_capacity_and_hasher(strings, hash_builder)?;
+
+        let string_map = KeyShards::try_with_capacity(strings)?;
+
+        let arena = LockfreeArena::with_allocator(bytes, max_memory_usage, allocator)?;
+        arena.set_total_budget(total_budget);
+
+        Ok(Self {
+            map,
+            strings: string_map,
+            statics: KeyShards::new(),
             key: AtomicUsize::new(0),
-            arena: LockfreeArena::new(bytes, max_memory_usage)
-                .expect("failed to allocate memory for interner"),
+            clears: AtomicUsize::new(0),
+            arena,
+            #[cfg(feature = "profile-interning")]
+            profile: ConcurrentInternProfile::default(),
+            #[cfg(feature = "timestamps")]
+            timestamps: ConcurrentTimestamps::new(),
+        })
+    }
+
+    /// Creates a ThreadedRodeo that holds the strings of a [`RodeoReader`], keeping their keys
+    ///
+    /// A lock-free arena is reused as-is, but the strings of a single-threaded arena have to
+    /// be copied into a fresh one
+    ///
+    /// # Safety
+    ///
+    /// Every string within `strings` must be stored in `arena`, and the index of each string
+    /// must be a valid key. Keys in `gaps` are left without a string
+    ///
+    pub(crate) unsafe fn try_from_reader_parts(
+        hasher: S,
+        strings: Vec<&'static str>,
+        gaps: &KeySet<K>,
+        arena: AnyArena,
+    ) -> LassoResult<Self> {
+        let map = ShardMap::try_with_capacity_and_hasher(strings.len(), hasher)?;
+
+        let string_map = KeyShards::try_with_capacity(strings.len())?;
+
+        let (arena, strings) = match arena {
+            AnyArena::Lockfree(arena) => (arena, strings),
+            AnyArena::Arena(old) => {
+                let bytes = strings.iter().map(|string| string.len()).sum::<usize>();
+                let bytes =
+                    NonZeroUsize::new(bytes).unwrap_or_else(|| Capacity::default().bytes());
+
+                let arena = LockfreeArena::new(bytes, old.max_memory_usage.max(bytes.get()))?;
+                arena.set_total_budget(old.total_budget);
+                arena.set_adopt_large_strings(old.adopt_large_strings);
+                arena.set_spill_threshold(old.spill_threshold);
+                arena.set_shrink_on_clear(old.shrink_on_clear);
+
+                let strings = strings
+                    .into_iter()
+                    // Safety: The copied strings are dropped along with the new arena
+                    .map(|string| unsafe { arena.store_str(string) })
+                    .collect::<LassoResult<Vec<_>>>()?;
+
+                // The old strings were all copied over, so their arena can be freed
+                drop(old);
+
+                (arena, strings)
+            }
+        };
+
+        for (idx, &string) in strings.iter().enumerate() {
+            let key = K::try_from_usize(idx).expect("reader keys are always valid");
+            if gaps.contains(key) {
+                continue;
+            }
+
+            map.insert(string, key);
+            string_map.insert(key, string);
         }
+
+        // The reader doesn't know when its strings were interned, so they're all given
+        // the current time
+        #[cfg(feature = "timestamps")]
+        let timestamps = {
+            let mut timestamps = ConcurrentTimestamps::new();
+            timestamps.fill(strings.len());
+            timestamps
+        };
+
+        Ok(Self {
+            map,
+            strings: string_map,
+            statics: KeyShards::new(),
+            key: AtomicUsize::new(strings.len()),
+            clears: AtomicUsize::new(0),
+            arena,
+            #[cfg(feature = "profile-interning")]
+            profile: ConcurrentInternProfile::default(),
+            #[cfg(feature = "timestamps")]
+            timestamps,
+        })
+    }
+
+    /// Sets whether owned strings too large to share an arena bucket are adopted
+    /// instead of copied
+    pub(crate) fn set_adopt_large_strings(&self, adopt_large_strings: bool) {
+        self.arena.set_adopt_large_strings(adopt_large_strings);
+    }
+
+    /// Sets the length above which strings are given an arena allocation of their own
+    pub(crate) fn set_spill_threshold(&self, spill_threshold: usize) {
+        self.arena.set_spill_threshold(spill_threshold);
+    }
+
+    /// Sets whether hashes are mixed before picking their shard, moving every string over
+    /// to its new shard if that changes
+    pub(crate) fn set_mix_shard_hashes(&mut self, mix: bool) {
+        self.map.set_mix_hashes(mix);
+    }
+
+    /// Sets the clock the intern time of every string is read from
+    #[cfg(feature = "timestamps")]
+    pub(crate) fn set_clock(&mut self, clock: fn() -> u64) {
+        self.timestamps.set_clock(clock);
+    }
+
+    /// Sets whether [`ThreadedRodeo::clear`] also shrinks the interner's maps
+    pub(crate) fn set_shrink_on_clear(&self, shrink_on_clear: bool) {
+        self.arena.set_shrink_on_clear(shrink_on_clear);
     }
 
     /// Get the key for a string, interning it if it does not yet exist
@@ -282,6 +619,7 @@ where
     /// ```
     ///
     #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
     pub fn get_or_intern<T>(&self, val: T) -> K
     where
         T: AsRef<str>,
@@ -309,43 +647,140 @@ where
     /// ```
     ///
     #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
     pub fn try_get_or_intern<T>(&self, val: T) -> LassoResult<K>
     where
         T: AsRef<str>,
+    {
+        // Safety: The drop impl removes all references before the arena is dropped
+        self.try_intern(val, false, |arena, val| unsafe {
+            arena.store_str(val.as_ref())
+        })
+    }
+
+    /// Get the key for an owned string, interning it if it does not yet exist
+    ///
+    /// Interners built with [`RodeoBuilder::adopt_large_strings()`] take over the buffer
+    /// of strings that are too large to share an arena bucket instead of copying them
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique strings were interned)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    ///
+    /// let key = rodeo.get_or_intern_owned(String::from("Strings of things with wings and dings"));
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    /// [`RodeoBuilder::adopt_large_strings()`]: crate::RodeoBuilder::adopt_large_strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn get_or_intern_owned(&self, string: String) -> K {
+        self.try_get_or_intern_owned(string)
+            .expect("Failed to get or intern string")
+    }
+
+    /// Get the key for an owned string, interning it if it does not yet exist
+    ///
+    /// Interners built with [`RodeoBuilder::adopt_large_strings()`] take over the buffer
+    /// of strings that are too large to share an arena bucket instead of copying them
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    ///
+    /// let key = rodeo.try_get_or_intern_owned(String::from("Strings of things with wings and dings")).unwrap();
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    /// [`RodeoBuilder::adopt_large_strings()`]: crate::RodeoBuilder::adopt_large_strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn try_get_or_intern_owned(&self, string: String) -> LassoResult<K> {
+        // Safety: The drop impl removes all references before the arena is dropped
+        self.try_intern(string, false, |arena, string| unsafe {
+            arena.store_string(string)
+        })
+    }
+
+    /// Get the key for a string, storing it with `store` if it does not yet exist
+    ///
+    /// Every string goes through here no matter where its data lives, `is_static` records
+    /// that the stored string borrows `'static` data rather than the arena
+    #[inline]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    fn try_intern<T, F>(&self, val: T, is_static: bool, store: F) -> LassoResult<K>
+    where
+        T: AsRef<str>,
+        F: FnOnce(&LockfreeArena, T) -> LassoResult<&'static str>,
+    {
+        let len = val.as_ref().len();
+        #[cfg(feature = "profile-interning")]
+        self.profile.record(core::panic::Location::caller(), len);
+
+        self.try_intern_inner(val, is_static, store)
+            .map_err(|err| err.with_string_len(len))
+    }
+
+    /// The body of [`ThreadedRodeo::try_intern`], which adds the string's length to any error
+    #[inline]
+    fn try_intern_inner<T, F>(&self, val: T, is_static: bool, store: F) -> LassoResult<K>
+    where
+        T: AsRef<str>,
+        F: FnOnce(&LockfreeArena, T) -> LassoResult<&'static str>,
     {
         let string_slice = val.as_ref();
+        let hash = self.map.hash(string_slice);
 
-        if let Some(key) = self.map.get(string_slice) {
-            Ok(*key)
+        if let Some(key) = self.map.get(string_slice, hash) {
+            Ok(key)
+        } else if !self.has_key_space() {
+            self.exhausted_key(string_slice, hash)
         } else {
-            // Determine which shard will have our `string_slice` key.
-            let hash = self.map.hasher().hash_one(string_slice);
-            let shard_key = self.map.determine_shard(hash as usize);
-            // Grab the shard and a write lock on it.
-            let mut shard = self.map.shards().get(shard_key).unwrap().write();
-            // Try getting the value for the `string_slice` key. If we get `Some`, nothing to do.
-            // Just return the value, which is the key go to use to resolve the string. If we
-            // get `None`, an entry for the string doesn't exist yet. Store string in the arena,
-            // update the maps accordingly, and return the key.
-            let key = match shard.find_or_find_insert_slot(
-                hash,
-                |(k, _)| *k == string_slice,
-                |(k, _)| self.map.hasher().hash_one(k),
-            ) {
-                // Safety: occupied_bucket is valid to borrow, which we keep short
-                Ok(occupied_bucket) => unsafe { *occupied_bucket.as_ref().1.get() },
-                Err(insert_slot) => {
-                    // Safety: The drop impl removes all references before the arena is dropped
-                    let string: &'static str = unsafe { self.arena.store_str(string_slice)? };
-
-                    let key = K::try_from_usize(self.key.fetch_add(1, Ordering::SeqCst))
-                        .ok_or_else(|| LassoError::new(LassoErrorKind::KeySpaceExhaustion))?;
+            // Leave room for the maps to grow if they count towards the memory limit, this
+            // has to happen before taking a shard lock since it reads every shard. Finding
+            // an insertion slot can grow the shard, so the limit is also checked up front
+            if self.arena.is_total_budget() {
+                self.arena.set_reserved_memory(self.overhead_memory_usage(true));
+                self.arena.check_reserved_memory(string_slice.len())?;
+            }
+
+            // The entry holds the lock on the string's shard, so if the string doesn't exist
+            // yet nobody else can intern it until it's been stored and both maps are updated
+            let key = match self.map.entry(string_slice, hash) {
+                ShardEntry::Occupied(key) => key,
+                ShardEntry::Vacant(entry) => {
+                    // Bail early if there's no keys left so we don't waste arena space
+                    if !self.has_key_space() {
+                        return Err(LassoError::new(LassoErrorKind::KeySpaceExhaustion));
+                    }
+
+                    let string = store(&self.arena, val)?;
+
+                    // Only take a key once the string was successfully stored
+                    let key = self.reserve_key()?;
+
+                    // The time is recorded before the string can be resolved, so every
+                    // string that can be seen already has its time
+                    #[cfg(feature = "timestamps")]
+                    self.timestamps.record(key.into_usize());
 
                     self.strings.insert(key, string);
-                    // Safety: insert_slot was just returned by find_insert_slot and we have not mutated the shard.
-                    unsafe {
-                        shard.insert_in_slot(hash, insert_slot, (string, SharedValue::new(key)));
+                    if is_static {
+                        self.statics.insert(key, string);
                     }
+                    entry.insert(string, key);
 
                     key
                 }
@@ -382,6 +817,7 @@ where
     /// ```
     ///
     #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
     pub fn get_or_intern_static(&self, string: &'static str) -> K {
         self.try_get_or_intern_static(string)
             .expect("Failed to get or intern static string")
@@ -408,47 +844,185 @@ where
     /// ```
     ///
     #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
     pub fn try_get_or_intern_static(&self, string: &'static str) -> LassoResult<K> {
-        if let Some(key) = self.map.get(string) {
-            Ok(*key)
-        } else {
-            let key = match self.map.entry(string) {
-                Entry::Occupied(o) => *o.get(),
-                Entry::Vacant(v) => {
-                    let key = K::try_from_usize(self.key.fetch_add(1, Ordering::SeqCst))
-                        .ok_or_else(|| LassoError::new(LassoErrorKind::KeySpaceExhaustion))?;
-                    self.strings.insert(key, string);
-                    v.insert(key);
-
-                    key
-                }
-            };
-
-            Ok(key)
-        }
+        // Static strings are interned just like any other, they're just never copied
+        self.try_intern(string, true, |_, string| Ok(string))
     }
 
-    /// Get the key value of a string, returning `None` if it doesn't exist
+    /// Get the key for a string that's either static or owned, interning it if it does not
+    /// yet exist
+    ///
+    /// Borrowed strings are interned with [`ThreadedRodeo::get_or_intern_static`] so they're never
+    /// copied, and owned strings with [`ThreadedRodeo::get_or_intern_owned`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key's `try_from_usize` function fails. With the default keys, this means that
+    /// you've interned more strings than it can handle. (For [`Spur`] this means that `u32::MAX - 1`
+    /// unique strings were interned)
     ///
     /// # Example
     ///
     /// ```rust
     /// use lasso::ThreadedRodeo;
+    /// use std::borrow::Cow;
     ///
     /// let rodeo = ThreadedRodeo::default();
     ///
-    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
-    /// assert_eq!(Some(key), rodeo.get("Strings of things with wings and dings"));
-    ///
-    /// assert_eq!(None, rodeo.get("This string isn't interned"));
+    /// let key = rodeo.get_or_intern_cow(Cow::Borrowed("Strings of things"));
+    /// assert_eq!(rodeo.get_or_intern_cow(Cow::Owned("Strings of things".to_owned())), key);
+    /// assert_eq!("Strings of things", rodeo.resolve(&key));
     /// ```
     ///
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn get<T>(&self, val: T) -> Option<K>
-    where
-        T: AsRef<str>,
-    {
-        self.map.get(val.as_ref()).map(|k| *k)
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn get_or_intern_cow(&self, string: Cow<'static, str>) -> K {
+        self.try_get_or_intern_cow(string)
+            .expect("Failed to get or intern string")
+    }
+
+    /// Get the key for a string that's either static or owned, interning it if it does not
+    /// yet exist
+    ///
+    /// Borrowed strings are interned with [`ThreadedRodeo::try_get_or_intern_static`] so they're never
+    /// copied, and owned strings with [`ThreadedRodeo::try_get_or_intern_owned`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    /// use std::borrow::Cow;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    ///
+    /// let key = rodeo.try_get_or_intern_cow(Cow::Borrowed("Strings of things")).unwrap();
+    /// assert_eq!("Strings of things", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[cfg_attr(feature = "profile-interning", track_caller)]
+    pub fn try_get_or_intern_cow(&self, string: Cow<'static, str>) -> LassoResult<K> {
+        match string {
+            Cow::Borrowed(string) => self.try_get_or_intern_static(string),
+            Cow::Owned(string) => self.try_get_or_intern_owned(string),
+        }
+    }
+
+    /// Fails with [`LassoErrorKind::KeySpaceExhaustion`] unless `string` was interned
+    ///
+    /// Finding a slot for a new string can grow its shard, so once the key space is spent
+    /// this is checked before touching the map. Keys are only taken while holding a shard
+    /// lock, so a thread that took the last key for this same string has either finished
+    /// inserting it or is holding the lock the lookup waits on
+    fn exhausted_key(&self, string: &str, hash: u64) -> LassoResult<K> {
+        self.map.get(string, hash).ok_or_else(|| {
+            LassoError::new(LassoErrorKind::KeySpaceExhaustion).with_string_len(string.len())
+        })
+    }
+
+    /// Takes the next available key, only advancing the key counter if a key could be created
+    ///
+    /// This keeps the counter from running past the end of the key space, so once it's
+    /// exhausted every future attempt fails the same way and no keys are skipped
+    fn reserve_key(&self) -> LassoResult<K> {
+        let mut key = None;
+        let _ = self
+            .key
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| {
+                key = K::try_from_usize(current);
+                current.checked_add(1).filter(|_| key.is_some())
+            });
+
+        key.ok_or_else(|| LassoError::new(LassoErrorKind::KeySpaceExhaustion))
+    }
+
+    /// Returns `true` if there are keys left to hand out
+    fn has_key_space(&self) -> bool {
+        K::try_from_usize(self.key.load(Ordering::SeqCst)).is_some()
+    }
+
+    /// Get the key value of a string, returning `None` if it doesn't exist
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    ///
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    /// assert_eq!(Some(key), rodeo.get("Strings of things with wings and dings"));
+    ///
+    /// assert_eq!(None, rodeo.get("This string isn't interned"));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get<T>(&self, val: T) -> Option<K>
+    where
+        T: AsRef<str>,
+    {
+        let string = val.as_ref();
+        self.map.get(string, self.map.hash(string))
+    }
+
+    /// Get the key for a string using a hash made with [`ThreadedRodeo::hasher`], returning
+    /// `None` if it hasn't been interned
+    ///
+    /// See the [`raw`] module for how strings are hashed, a hash that wasn't made that way
+    /// won't find anything
+    ///
+    /// This method is only available with the `raw` feature!
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{raw, ThreadedRodeo};
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// let hash = raw::hash_str(rodeo.hasher(), "Strings of things with wings and dings");
+    /// assert_eq!(Some(key), rodeo.get_hashed(hash, "Strings of things with wings and dings"));
+    /// ```
+    ///
+    /// [`raw`]: crate::raw
+    #[cfg(feature = "raw")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_hashed(&self, hash: u64, string: &str) -> Option<K> {
+        self.map.get(string, hash)
+    }
+
+    /// Gets the hasher that the `ThreadedRodeo` hashes strings with
+    ///
+    /// This method is only available with the `raw` feature!
+    #[cfg(feature = "raw")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn hasher(&self) -> &S {
+        self.map.hasher()
+    }
+
+    /// Get the key of the empty string, returning `None` if it hasn't been interned
+    ///
+    /// Interners built with [`RodeoBuilder::intern_empty()`] always give the empty
+    /// string the first key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// assert_eq!(None, rodeo.empty_key());
+    ///
+    /// let key = rodeo.get_or_intern("");
+    /// assert_eq!(Some(key), rodeo.empty_key());
+    /// ```
+    ///
+    /// [`RodeoBuilder::intern_empty()`]: crate::RodeoBuilder::intern_empty
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn empty_key(&self) -> Option<K> {
+        self.get("")
     }
 
     /// Returns `true` if the given string has been interned
@@ -515,7 +1089,7 @@ where
     ///
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn resolve<'a>(&'a self, key: &K) -> &'a str {
-        *self.strings.get(key).expect("Key out of bounds")
+        self.strings.get(key).expect("Key out of bounds")
     }
 
     /// Resolves a string by its key, returning `None` if it is out of bounds. Only keys made by the current
@@ -534,7 +1108,124 @@ where
     ///
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn try_resolve<'a>(&'a self, key: &K) -> Option<&'a str> {
-        self.strings.get(key).map(|s| *s)
+        self.strings.get(key)
+    }
+
+    /// Resolves a string by its key into a [`CompactString`], which stores short strings
+    /// inline instead of allocating
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things");
+    ///
+    /// let string = rodeo.resolve_compact(&key);
+    /// assert_eq!(string, "Strings of things");
+    /// assert!(!string.is_heap_allocated());
+    /// ```
+    ///
+    /// [`CompactString`]: compact_str::CompactString
+    #[cfg(feature = "compact_str")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve_compact(&self, key: &K) -> CompactString {
+        CompactString::new(self.resolve(key))
+    }
+
+    /// Resolves a string by its key into a [`CompactString`], returning `None` if it's out
+    /// of bounds
+    ///
+    /// [`CompactString`]: compact_str::CompactString
+    #[cfg(feature = "compact_str")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve_compact(&self, key: &K) -> Option<CompactString> {
+        self.try_resolve(key).map(CompactString::new)
+    }
+
+    /// Resolves a string by its key into a pointer to its first byte and its length in bytes,
+    /// returning a null pointer and a length of zero if the key is out of bounds
+    ///
+    /// The string's storage never moves, so the pointer stays valid while more strings are
+    /// interned and after the interner is turned into a [`RodeoReader`] or [`RodeoResolver`].
+    /// Since cleared strings are kept around, it's only invalidated by
+    /// [`ThreadedRodeo::free_cleared`] or when the interner is dropped
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// let key = rodeo.get_or_intern("Hello, world!");
+    /// let (ptr, len) = rodeo.resolve_ptr(&key);
+    ///
+    /// rodeo.clear();
+    ///
+    /// let bytes = unsafe { std::slice::from_raw_parts(ptr, len) };
+    /// assert_eq!(bytes, b"Hello, world!");
+    /// ```
+    ///
+    /// [`RodeoReader`]: crate::RodeoReader
+    /// [`RodeoResolver`]: crate::RodeoResolver
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve_ptr(&self, key: &K) -> (*const u8, usize) {
+        self.try_resolve(key)
+            .map_or((ptr::null(), 0), |string| (string.as_ptr(), string.len()))
+    }
+
+    /// Resolves a batch of keys into their strings, in the same order as the keys
+    ///
+    /// Keys are grouped by the shard they live in so that each shard only has to be
+    /// locked once, instead of once per key
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the keys are out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// let a = rodeo.get_or_intern("Foo");
+    /// let b = rodeo.get_or_intern("Bar");
+    ///
+    /// assert_eq!(rodeo.resolve_many(&[b, a, b]), ["Bar", "Foo", "Bar"]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve_many<'a>(&'a self, keys: &[K]) -> Vec<&'a str> {
+        self.try_resolve_many(keys).expect("Key out of bounds")
+    }
+
+    /// Resolves a batch of keys into their strings, in the same order as the keys, returning
+    /// `None` if any of the keys are out of bounds
+    ///
+    /// Keys are grouped by the shard they live in so that each shard only has to be
+    /// locked once, instead of once per key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// let a = rodeo.get_or_intern("Foo");
+    /// let b = rodeo.get_or_intern("Bar");
+    ///
+    /// assert_eq!(rodeo.try_resolve_many(&[b, a]), Some(vec!["Bar", "Foo"]));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve_many<'a>(&'a self, keys: &[K]) -> Option<Vec<&'a str>> {
+        self.strings.get_many(keys)
     }
 
     /// Gets the number of interned strings
@@ -571,6 +1262,172 @@ where
         self.len() == 0
     }
 
+    /// Returns the number of times the interner has been cleared
+    pub(crate) fn clears(&self) -> usize {
+        self.clears.load(Ordering::Acquire)
+    }
+
+    /// Returns the hasher used for strings, which is public with the `raw` feature
+    #[cfg(not(feature = "raw"))]
+    pub(crate) fn hasher(&self) -> &S {
+        self.map.hasher()
+    }
+
+    /// Creates a [`LocalRodeoHandle`] that caches the strings and keys it sees in front of
+    /// the shared interner
+    ///
+    /// Every thread or task can have a handle of its own, so strings that they intern often
+    /// are found without touching the shared interner's locks while keys stay the same for
+    /// every handle
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    /// use std::sync::Arc;
+    ///
+    /// let rodeo = Arc::new(ThreadedRodeo::default());
+    /// let mut handle = rodeo.local_handle();
+    ///
+    /// let key = handle.get_or_intern("Somewhere over the rainbow...");
+    /// assert_eq!(rodeo.get("Somewhere over the rainbow..."), Some(key));
+    /// assert_eq!(handle.resolve(&key), "Somewhere over the rainbow...");
+    /// ```
+    ///
+    #[must_use]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn local_handle(self: &Arc<Self>) -> LocalRodeoHandle<K, S> {
+        LocalRodeoHandle::new(Arc::clone(self))
+    }
+
+    /// Clears the current interner, invalidating all previously interned keys
+    ///
+    /// Unlike [`Rodeo::clear`] this only needs shared access, so it can be called while other
+    /// threads are using the interner. It waits for every in-progress interning to finish,
+    /// then atomically swaps in empty maps and a fresh arena and restarts keys from zero
+    ///
+    /// Strings that were resolved before clearing stay valid for as long as the interner is
+    /// borrowed, so the old arena isn't freed right away. Its memory keeps counting towards
+    /// the interner's memory usage until [`ThreadedRodeo::free_cleared`] is called or the
+    /// interner is dropped
+    ///
+    /// Keys from before the clear are stale: resolving them may return `None` or panic, but
+    /// once new strings are interned the same keys will be handed out again and resolve to the
+    /// new strings. Keys that another thread got while the interner was being cleared are
+    /// equally stale
+    ///
+    /// The maps keep their capacity to be reused, unless the interner was built with
+    /// [`RodeoBuilder::shrink_on_clear()`] in which case they're also shrunk like
+    /// [`ThreadedRodeo::shrink_to_fit`] does
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// let key = rodeo.get_or_intern("Somewhere over the rainbow...");
+    /// let string = rodeo.resolve(&key);
+    ///
+    /// rodeo.clear();
+    ///
+    /// // The interner is now empty
+    /// assert_eq!(rodeo.len(), 0);
+    /// assert_eq!(rodeo.try_resolve(&key), None);
+    ///
+    /// // But previously resolved strings are still valid
+    /// assert_eq!(string, "Somewhere over the rainbow...");
+    /// ```
+    ///
+    /// [`Rodeo::clear`]: crate::Rodeo::clear
+    /// [`RodeoBuilder::shrink_on_clear()`]: crate::RodeoBuilder::shrink_on_clear
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn clear(&self) {
+        // Every key is reserved and every string is stored while holding one of the string
+        // map's shard locks, so holding all of them keeps anything from being interned
+        // until the interner is fully cleared
+        self.map.clear_with(|| {
+            self.strings.clear();
+            self.statics.clear();
+
+            self.key.store(0, Ordering::SeqCst);
+            self.clears.fetch_add(1, Ordering::Release);
+            self.arena.retire();
+
+            #[cfg(feature = "timestamps")]
+            self.timestamps.clear(self.arena.is_shrinking_on_clear());
+        });
+
+        if self.arena.is_shrinking_on_clear() {
+            self.shrink_to_fit();
+        }
+    }
+
+    /// Shrinks the interner's maps to fit the strings they hold, one shard at a time
+    ///
+    /// The maps keep the capacity they grew to even after the interner is cleared, so this
+    /// lets long-running programs give memory back after a burst of interning. Strings can
+    /// still be interned from other threads while the interner is shrunk. The arena never
+    /// holds empty buckets, the ones that held strings from before the last
+    /// [`ThreadedRodeo::clear`] are freed by [`ThreadedRodeo::free_cleared`]. Interners built
+    /// with [`RodeoBuilder::shrink_on_clear()`] do this every time they're cleared
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let mut rodeo = ThreadedRodeo::default();
+    /// for i in 0..1000 {
+    ///     rodeo.get_or_intern(i.to_string());
+    /// }
+    ///
+    /// rodeo.clear();
+    /// rodeo.free_cleared();
+    /// let cleared = rodeo.current_memory_usage();
+    ///
+    /// rodeo.shrink_to_fit();
+    /// assert!(rodeo.current_memory_usage() < cleared);
+    /// ```
+    ///
+    /// [`RodeoBuilder::shrink_on_clear()`]: crate::RodeoBuilder::shrink_on_clear
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn shrink_to_fit(&self) {
+        self.map.shrink_to_fit();
+        self.strings.shrink_to_fit();
+        self.statics.shrink_to_fit();
+    }
+
+    /// Frees the memory of every string that was interned before the last call to
+    /// [`ThreadedRodeo::clear`]
+    ///
+    /// This requires exclusive access to prove that none of those strings are still in use,
+    /// interners shared with an [`Arc`] can use [`Arc::get_mut`] to get it
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let mut rodeo = ThreadedRodeo::default();
+    /// rodeo.get_or_intern("Somewhere over the rainbow...");
+    ///
+    /// rodeo.clear();
+    /// let before = rodeo.arena_memory_usage();
+    ///
+    /// rodeo.free_cleared();
+    /// assert!(rodeo.arena_memory_usage() < before);
+    /// ```
+    ///
+    /// [`Arc`]: alloc::sync::Arc
+    /// [`Arc::get_mut`]: alloc::sync::Arc::get_mut
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn free_cleared(&mut self) {
+        // Safety: Resolved strings borrow the interner, so none can exist while we have
+        //         exclusive access to it
+        unsafe { self.arena.free_retired() };
+    }
+
     /// Returns the number of strings that can be interned without a reallocation
     ///
     /// This is an unreliable measurement since the underlying hashmap is unreliable in its
@@ -602,6 +1459,352 @@ where
         Strings::new(self)
     }
 
+    /// Returns an iterator over the strings interned with
+    /// [`ThreadedRodeo::get_or_intern_static`] and their keys
+    ///
+    /// These strings borrow their `'static` data instead of being copied into the interner.
+    /// A string that was already interned when it was interned again as a static string keeps
+    /// its copy, and isn't yielded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// let copied = rodeo.get_or_intern("copied");
+    /// let borrowed = rodeo.get_or_intern_static("borrowed");
+    ///
+    /// // Already interned, so this keeps using the copy
+    /// assert_eq!(rodeo.get_or_intern_static("copied"), copied);
+    ///
+    /// let statics: Vec<_> = rodeo.static_strings().collect();
+    /// assert_eq!(statics, [(borrowed, "borrowed")]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn static_strings(&self) -> Iter<'_, K, S> {
+        Iter::statics(self)
+    }
+
+    /// Returns `true` if both interners hold exactly the same strings, no matter which
+    /// keys those strings were given
+    ///
+    /// Unlike `==`, which requires every key to resolve to the same string, this only
+    /// compares the sets of interned strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Rodeo, ThreadedRodeo};
+    ///
+    /// let left = ThreadedRodeo::default();
+    /// left.get_or_intern("foo");
+    /// left.get_or_intern("bar");
+    ///
+    /// let mut right = Rodeo::default();
+    /// right.get_or_intern("bar");
+    /// right.get_or_intern("foo");
+    ///
+    /// assert!(left != right);
+    /// assert!(left.equivalent_content(&right));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn equivalent_content<K2, R>(&self, other: &R) -> bool
+    where
+        R: Reader<K2> + ?Sized,
+    {
+        self.len() == other.len() && self
+                .strings
+                .iter()
+                .all(|(_, string)| other.contains(string))
+    }
+
+    /// Returns an iterator over the interned strings and their key values, in the order of
+    /// their keys
+    ///
+    /// Unlike [`ThreadedRodeo::iter()`], which yields strings in whatever order the underlying
+    /// map stores them, this always yields them in the same order as [`Rodeo::iter()`] does.
+    /// Strings interned while iterating may or may not be yielded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// let a = rodeo.get_or_intern("A");
+    /// let b = rodeo.get_or_intern("B");
+    /// let c = rodeo.get_or_intern("C");
+    ///
+    /// let ordered: Vec<_> = rodeo.iter_ordered().collect();
+    /// assert_eq!(ordered, [(a, "A"), (b, "B"), (c, "C")]);
+    /// ```
+    ///
+    /// [`Rodeo::iter()`]: crate::Rodeo::iter
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter_ordered(&self) -> OrderedIter<'_, K, S> {
+        OrderedIter::new(self)
+    }
+
+    /// Returns an iterator over a point-in-time copy of the interned strings and their keys,
+    /// in the order of their keys
+    ///
+    /// Every string interned when this is called is copied into the snapshot up front, so
+    /// strings interned afterwards are never yielded no matter how long the snapshot is kept
+    /// around. No locks are held once this returns, so unlike [`ThreadedRodeo::iter()`] the
+    /// snapshot can't deadlock with threads that are interning and is safe to hold across
+    /// `.await` points. Only the keys and the references to the strings are copied, never
+    /// the strings themselves
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// let a = rodeo.get_or_intern("A");
+    /// let b = rodeo.get_or_intern("B");
+    ///
+    /// let snapshot = rodeo.snapshot_iter();
+    /// rodeo.get_or_intern("C");
+    ///
+    /// assert_eq!(snapshot.len(), 2);
+    /// assert_eq!(snapshot.collect::<Vec<_>>(), [(a, "A"), (b, "B")]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn snapshot_iter(&self) -> SnapshotIter<'_, K> {
+        SnapshotIter::new(self)
+    }
+
+    /// Returns the time the string of a key was interned at, or `None` if the key isn't
+    /// in the interner
+    ///
+    /// Times are read from the interner's clock, which is [`monotonic_ticks`] unless the
+    /// interner was built with [`RodeoBuilder::clock`]. Interning a string that's already in
+    /// the interner doesn't change its time
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{monotonic_ticks, ThreadedRodeo};
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// let before = monotonic_ticks();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// let interned_at = rodeo.interned_at(&key).unwrap();
+    /// assert!(before <= interned_at && interned_at <= monotonic_ticks());
+    /// ```
+    ///
+    /// [`monotonic_ticks`]: crate::monotonic_ticks
+    /// [`RodeoBuilder::clock`]: crate::RodeoBuilder::clock
+    #[cfg(feature = "timestamps")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn interned_at(&self, key: &K) -> Option<u64> {
+        if self.contains_key(key) {
+            self.timestamps.get(key.into_usize())
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over a point-in-time copy of the keys and strings that were
+    /// interned before `ticks`, in the order of their keys
+    ///
+    /// Like [`ThreadedRodeo::snapshot_iter()`], no locks are held once this returns
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{RodeoBuilder, ThreadedRodeo};
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    ///
+    /// static GENERATION: AtomicU64 = AtomicU64::new(0);
+    ///
+    /// let rodeo: ThreadedRodeo = RodeoBuilder::new()
+    ///     .clock(|| GENERATION.load(Ordering::Relaxed))
+    ///     .build_threaded();
+    ///
+    /// let old = rodeo.get_or_intern("old");
+    /// GENERATION.store(1, Ordering::Relaxed);
+    /// rodeo.get_or_intern("new");
+    ///
+    /// let older: Vec<_> = rodeo.iter_older_than(1).collect();
+    /// assert_eq!(older, [(old, "old")]);
+    /// ```
+    ///
+    #[cfg(feature = "timestamps")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter_older_than(&self, ticks: u64) -> SnapshotIter<'_, K> {
+        // Strings are collected before their times are read so no string shard is locked
+        // while the times are
+        let mut pairs: Vec<(K, &str)> = OrderedIter::new(self).collect();
+        self.timestamps.with_ticks(|times| {
+            pairs.retain(|(key, _)| {
+                times
+                    .get(key.into_usize())
+                    .is_some_and(|&interned_at| interned_at < ticks)
+            });
+        });
+
+        SnapshotIter {
+            iter: pairs.into_iter(),
+        }
+    }
+
+    /// Returns an iterator over the interned strings, in the order of their keys
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// rodeo.get_or_intern("A");
+    /// rodeo.get_or_intern("B");
+    /// rodeo.get_or_intern("C");
+    ///
+    /// let ordered: Vec<_> = rodeo.strings_ordered().collect();
+    /// assert_eq!(ordered, ["A", "B", "C"]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn strings_ordered(&self) -> OrderedStrings<'_, K, S> {
+        OrderedStrings::new(self)
+    }
+
+    /// Returns the number of strings in each shard of the interner's string to key map
+    ///
+    /// Strings are spread over the shards by their hash, so with a good hasher every shard
+    /// holds about as many strings as the others. A few shards holding most of the strings
+    /// means threads are contending on those shards' locks, which can be fixed with
+    /// [`ThreadedRodeo::rebalance_shards`]. Strings longer than `u32::MAX` bytes are kept
+    /// apart from the shards and aren't counted
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// for i in 0..1000 {
+    ///     rodeo.get_or_intern(i.to_string());
+    /// }
+    ///
+    /// let distribution = rodeo.shard_distribution();
+    /// assert_eq!(distribution.iter().sum::<usize>(), 1000);
+    ///
+    /// let largest = distribution.iter().max().unwrap();
+    /// assert!(largest * distribution.len() < 1000 * 4);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn shard_distribution(&self) -> Vec<usize> {
+        self.map.shard_lens()
+    }
+
+    /// Spreads strings evenly over the interner's shards, even when its hasher doesn't
+    ///
+    /// Strings are normally given a shard by a few bits of their hash, so a weak hasher
+    /// that leaves those bits mostly the same puts most strings into a few shards, as can
+    /// happen with short numeric strings and a fast unseeded hasher. After rebalancing
+    /// hashes are mixed before picking a shard, which every string already interned is
+    /// moved over to. Strings keep their keys and the hasher isn't changed, hashes made with
+    /// [`ThreadedRodeo::hasher`] still find their strings. Interners can start out mixing
+    /// hashes with [`RodeoBuilder::mix_shard_hashes`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let mut rodeo = ThreadedRodeo::default();
+    /// let key = rodeo.get_or_intern("Strings of things");
+    ///
+    /// rodeo.rebalance_shards();
+    /// assert!(rodeo.is_mixing_shard_hashes());
+    /// assert_eq!(rodeo.get("Strings of things"), Some(key));
+    /// ```
+    ///
+    /// [`RodeoBuilder::mix_shard_hashes`]: crate::RodeoBuilder::mix_shard_hashes
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn rebalance_shards(&mut self) {
+        self.set_mix_shard_hashes(true);
+    }
+
+    /// Returns `true` if hashes are mixed before picking their shard, see
+    /// [`ThreadedRodeo::rebalance_shards`]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_mixing_shard_hashes(&self) -> bool {
+        self.map.mixes_hashes()
+    }
+
+    /// Calls `f` with an iterator over the keys and strings of each of the interner's shards
+    ///
+    /// Only the shard being iterated over is locked, so other threads can keep interning
+    /// strings into the rest of the interner. Strings interned while this is running may or
+    /// may not be seen
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// rodeo.get_or_intern("A");
+    /// rodeo.get_or_intern("B");
+    ///
+    /// let mut strings = Vec::new();
+    /// rodeo.for_each_shard(|shard| {
+    ///     strings.extend(shard.map(|(_, string)| string.to_owned()));
+    /// });
+    ///
+    /// strings.sort();
+    /// assert_eq!(strings, ["A", "B"]);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn for_each_shard<F>(&self, mut f: F)
+    where
+        F: FnMut(ShardIter<'_, K>),
+    {
+        self.map
+            .for_each_shard(|entries| f(ShardIter { entries }));
+    }
+
+    /// Empties the interner, returning an iterator over owned batches of keys and strings,
+    /// one for each non-empty shard
+    ///
+    /// Each batch owns its strings, so batches can be handed off to other threads to be
+    /// written out in parallel. Once the iterator is dropped the interner is left empty,
+    /// even if not every batch was taken from it
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let mut rodeo = ThreadedRodeo::default();
+    /// let a = rodeo.get_or_intern("A");
+    /// let b = rodeo.get_or_intern("B");
+    ///
+    /// let mut drained: Vec<_> = rodeo.drain_shards().flatten().collect();
+    /// drained.sort();
+    /// assert_eq!(drained, [(a, "A".to_owned()), (b, "B".to_owned())]);
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn drain_shards(&mut self) -> DrainShards<'_, K, S> {
+        DrainShards {
+            rodeo: self,
+            shard: 0,
+        }
+    }
+
     /// Set the `ThreadedRodeo`'s maximum memory usage while in-flight
     ///
     /// Note that setting the maximum memory usage to below the currently allocated
@@ -610,14 +1813,90 @@ where
     pub fn set_memory_limits(&self, memory_limits: MemoryLimits) {
         self.arena
             .set_max_memory_usage(memory_limits.max_memory_usage);
+        self.arena.set_total_budget(memory_limits.total_budget);
+        if !memory_limits.total_budget {
+            self.arena.set_reserved_memory(0);
+        }
     }
 
     /// Get the `ThreadedRodeo`'s currently allocated memory
+    ///
+    /// This includes the estimated size of the interner's internal maps, see
+    /// [`ThreadedRodeo::arena_memory_usage`] for the memory used only by interned strings
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn current_memory_usage(&self) -> usize {
+        self.arena
+            .current_memory_usage()
+            .saturating_add(self.overhead_memory_usage(false))
+    }
+
+    /// Get the memory allocated to hold the `ThreadedRodeo`'s interned strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn arena_memory_usage(&self) -> usize {
         self.arena.current_memory_usage()
     }
 
+    /// Get a breakdown of the `ThreadedRodeo`'s memory usage
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn memory_stats(&self) -> MemoryStats {
+        MemoryStats::new(
+            self.arena_memory_usage(),
+            self.overhead_memory_usage(false),
+            self.strings(),
+        )
+    }
+
+    /// Get a snapshot of the `ThreadedRodeo`'s size and memory usage for exporting to
+    /// monitoring systems, see [`MetricsSnapshot::to_prometheus`]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn metrics(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            interned_total: self.len(),
+            bytes_allocated: self.current_memory_usage(),
+            map_entries: self.map.len(),
+            memory_limit: self.max_memory_usage(),
+        }
+    }
+
+    /// Estimates the memory used by the interner's maps, optionally including the
+    /// largest amount a single insertion could grow them by
+    fn overhead_memory_usage(&self, projected: bool) -> usize {
+        self.map
+            .memory_usage(projected)
+            .saturating_add(self.strings.memory_usage(projected))
+            .saturating_add(self.statics.memory_usage(projected))
+    }
+
+    /// Get how much every call site has interned into the `ThreadedRodeo`, sorted so that
+    /// the call sites that interned the most bytes come first
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// rodeo.get_or_intern("hello");
+    ///
+    /// let report = rodeo.profiling_report();
+    /// assert_eq!(report[0].location().line(), line!() - 3);
+    /// assert_eq!(report[0].interns(), 1);
+    /// assert_eq!(report[0].bytes(), 5);
+    /// ```
+    ///
+    #[cfg(feature = "profile-interning")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn profiling_report(&self) -> Vec<CallSiteProfile> {
+        self.profile.report()
+    }
+
+    /// Resets the `ThreadedRodeo`'s profiling report without touching any interned strings
+    #[cfg(feature = "profile-interning")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn reset_profiling(&self) {
+        self.profile.clear();
+    }
+
     /// Get the `ThreadedRodeo`'s current maximum of allocated memory
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn max_memory_usage(&self) -> usize {
@@ -645,74 +1924,77 @@ where
     /// [`RodeoReader`]: crate::RodeoReader
     #[cfg_attr(feature = "inline-more", inline)]
     #[must_use]
-    pub fn into_reader(self) -> RodeoReader<K, S> {
-        // Take the strings vec from the old lasso
-        let strings: Vec<&'static str> = {
-            let mut strings = iter::from_fn(|| Some(None))
-                .take(self.strings.len())
-                .collect::<Vec<Option<&'static str>>>();
-
-            for shard in self.strings.shards() {
-                for (key, val) in shard.write().drain() {
-                    unsafe {
-                        // Safety: The keys of the dashmap are valid indices
-                        *index_unchecked_mut!(strings, key.into_usize()) = Some(val.into_inner());
-                    }
-                }
-            }
+    pub fn into_reader(mut self) -> RodeoReader<K, S> {
+        // Strings from before the last clear can't be resolved by the reader
+        self.free_cleared();
 
-            strings.into_iter().map(|s| s.unwrap()).collect()
-        };
+        // Take the strings vec from the old lasso
+        let (strings, gaps) = self.drain_strings();
 
-        // Drain the DashMap by draining each of its buckets and creating a new hashmap to store their values
+        // Drain the string map and create a new hashmap to store its values
         let (map, hasher) = {
-            let mut map: HashMap<K, (), ()> = HashMap::with_capacity_and_hasher(strings.len(), ());
+            let mut map: StringMap<K> = StringMap::with_capacity(strings.len());
             let hasher = self.map.hasher().clone();
 
-            for shard in self.map.shards() {
-                for (string, key) in shard.write().drain() {
-                    let string: &str = string;
-
-                    // Hash the string to use as the key's hash (See `Rodeo`'s documentation for details)
-                    let hash = hasher.hash_one(string);
-
-                    // Get the entry of the hashmap and insert the key with our new, custom hash
-                    let entry = map.raw_entry_mut().from_hash(hash, |key| {
-                        // Safety: The index given by `key` will be in bounds of the strings vector
-                        let key_string: &str =
-                            unsafe { index_unchecked!(strings, key.into_usize()) };
-
-                        // Compare the requested string against the key's string
-                        string == key_string
-                    });
-
-                    match entry {
-                        RawEntryMut::Occupied(_) => {
-                            unreachable!("Keys in the hashmap are unique, so entries should never be occupied");
-                        }
+            for (string, key) in self.map.drain() {
+                // Hash the string to use as the key's hash (See `Rodeo`'s documentation for details)
+                let hash = hasher.hash_one(string);
 
-                        RawEntryMut::Vacant(entry) => {
-                            // Insert the key with the hash of the string that it points to, reusing the hash we made earlier
-                            entry.insert_with_hasher(hash, *key.get(), (), |key| {
-                                let key_string: &str =
-                                    unsafe { index_unchecked!(strings, key.into_usize()) };
+                // Keys in the map are unique, so the string is never already there
+                debug_assert!(find_string(&map, &strings, hash, string).is_none());
 
-                                hasher.hash_one(key_string)
-                            });
-                        }
-                    }
-                }
+                // Insert the key with the hash of the string that it points to, reusing the hash we made earlier
+                insert_string(&mut map, &strings, &hasher, hash, key);
             }
 
             (map, hasher)
         };
 
-        // Safety: No other references outside of `map` and `strings` to the interned strings exist
-        unsafe { RodeoReader::new(map, hasher, strings, AnyArena::Lockfree(self.arena)) }
+        // Safety: No other references outside of `map` and `strings` to the interned strings exist
+        unsafe { RodeoReader::new(map, hasher, strings, gaps, AnyArena::Lockfree(self.arena)) }
+    }
+
+    /// Consumes the current ThreadedRodeo, returning a [`RodeoResolver`] to allow contention-free access of the interner
+    /// from multiple threads with the lowest possible memory consumption
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::ThreadedRodeo;
+    ///
+    /// let rodeo = ThreadedRodeo::default();
+    /// let key = rodeo.get_or_intern("Appear weak when you are strong, and strong when you are weak.");
+    ///
+    /// let rodeo_resolver = rodeo.into_resolver();
+    /// assert_eq!(
+    ///     "Appear weak when you are strong, and strong when you are weak.",
+    ///     rodeo_resolver.resolve(&key),
+    /// );
+    /// ```
+    ///
+    /// [`RodeoResolver`]: crate::RodeoResolver
+    #[cfg_attr(feature = "inline-more", inline)]
+    #[must_use]
+    pub fn into_resolver(mut self) -> RodeoResolver<K> {
+        // Strings from before the last clear can't be resolved by the resolver
+        self.free_cleared();
+
+        let (strings, gaps) = self.drain_strings();
+
+        // Safety: No other references to the strings exist
+        unsafe { RodeoResolver::new(strings, gaps, AnyArena::Lockfree(self.arena)) }
     }
 
-    /// Consumes the current ThreadedRodeo, returning a [`RodeoResolver`] to allow contention-free access of the interner
-    /// from multiple threads with the lowest possible memory consumption
+    /// Consumes the current ThreadedRodeo, returning a [`RodeoResolver`] whose strings were
+    /// all copied into a single arena bucket that fits them exactly
+    ///
+    /// Besides the unused tails of its buckets, a lock-free arena leaves some memory behind
+    /// in the bucket of every thread that interned strings. See
+    /// [`Rodeo::into_resolver_compact`] for details
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new arena could not be allocated
     ///
     /// # Example
     ///
@@ -722,37 +2004,47 @@ where
     /// let rodeo = ThreadedRodeo::default();
     /// let key = rodeo.get_or_intern("Appear weak when you are strong, and strong when you are weak.");
     ///
-    /// let rodeo_resolver = rodeo.into_resolver();
+    /// let resolver = rodeo.into_resolver_compact();
     /// assert_eq!(
     ///     "Appear weak when you are strong, and strong when you are weak.",
-    ///     rodeo_resolver.resolve(&key),
+    ///     resolver.resolve(&key),
     /// );
     /// ```
     ///
     /// [`RodeoResolver`]: crate::RodeoResolver
+    /// [`Rodeo::into_resolver_compact`]: crate::Rodeo::into_resolver_compact
     #[cfg_attr(feature = "inline-more", inline)]
     #[must_use]
-    pub fn into_resolver(self) -> RodeoResolver<K> {
-        let mut strings = iter::from_fn(|| Some(None))
-            .take(self.strings.len())
-            .collect::<Vec<Option<&'static str>>>();
-
-        for shard in self.strings.shards() {
-            for (key, val) in shard.write().drain() {
-                unsafe {
-                    // Safety: The keys of the dashmap are valid indices
-                    *index_unchecked_mut!(strings, key.into_usize()) = Some(val.into_inner());
-                }
-            }
-        }
+    pub fn into_resolver_compact(mut self) -> RodeoResolver<K> {
+        // Strings from before the last clear can't be resolved by the resolver
+        self.free_cleared();
 
-        // Safety: No other references to the strings exist
-        unsafe {
-            RodeoResolver::new(
-                strings.into_iter().map(|s| s.unwrap()).collect(),
-                AnyArena::Lockfree(self.arena),
-            )
+        let (strings, gaps) = self.drain_strings();
+        RodeoResolver::compacted(&strings, gaps)
+    }
+
+    /// Takes every string out of the interner, placing each at the index of its key
+    ///
+    /// Keys are only taken once their string has been stored, but a thread that panicked in
+    /// between leaves a key without a string. Those keys are returned as gaps and given an
+    /// empty string, so that every other key still indexes its own string
+    fn drain_strings(&mut self) -> (Vec<&'static str>, KeySet<K>) {
+        let len = self.key.load(Ordering::SeqCst);
+        let mut strings = vec![""; len];
+        let mut gaps = KeySet::filled(len);
+
+        self.strings.drain_with(|key, string| {
+            // Safety: Keys are taken from the key counter, so they're all below it
+            unsafe { *index_unchecked_mut!(strings, key.into_usize()) = string };
+            gaps.remove(key);
+        });
+
+        // Don't hold onto the bitset when every key was given a string
+        if gaps.is_empty() {
+            gaps = KeySet::new();
         }
+
+        (strings, gaps)
     }
 }
 
@@ -774,17 +2066,19 @@ where
 {
     #[cfg_attr(feature = "inline-more", inline)]
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        f.debug_struct("ThreadedRodeo")
-            .field("map", &self.map)
-            .field("strings", &self.strings)
-            .field("arena", &self.arena)
-            .finish()
+        util::debug_interner(
+            f,
+            "ThreadedRodeo",
+            self.len(),
+            self.current_memory_usage(),
+            |idx| {
+                let key = K::try_from_usize(idx)?;
+                Some((key, self.try_resolve(&key)?))
+            },
+        )
     }
 }
 
-unsafe impl<K: Sync, S: Sync> Sync for ThreadedRodeo<K, S> {}
-unsafe impl<K: Send, S: Send> Send for ThreadedRodeo<K, S> {}
-
 impl<Str, K, S> FromIterator<Str> for ThreadedRodeo<K, S>
 where
     Str: AsRef<str>,
@@ -843,84 +2137,81 @@ where
 
 impl<K, S> Eq for ThreadedRodeo<K, S>
 where
-    K: Eq + Hash,
+    K: Eq + Hash + Key,
     S: Clone + BuildHasher,
 {
 }
 
-impl<K, S> PartialEq<Self> for ThreadedRodeo<K, S>
+impl<K, S, K2, S2> PartialEq<ThreadedRodeo<K2, S2>> for ThreadedRodeo<K, S>
 where
-    K: Eq + Hash,
+    K: Eq + Hash + Key,
     S: Clone + BuildHasher,
+    K2: Eq + Hash + Key,
+    S2: Clone + BuildHasher,
 {
     #[cfg_attr(feature = "inline-more", inline)]
-    fn eq(&self, other: &Self) -> bool {
+    fn eq(&self, other: &ThreadedRodeo<K2, S2>) -> bool {
         self.strings.len() == other.strings.len()
-            && self.strings.iter().all(|left| {
-                other
-                    .strings
-                    .get(left.key())
-                    .map(|s| s.value() == left.value())
-                    == Some(true)
+            && self.strings.iter().all(|(key, string)| {
+                K2::try_from_usize(key.into_usize())
+                    .and_then(|key| other.strings.get(&key))
+                    == Some(string)
             })
     }
 }
 
-impl<K, S> PartialEq<Rodeo<K, S>> for ThreadedRodeo<K, S>
+impl<K, S, K2, S2> PartialEq<Rodeo<K2, S2>> for ThreadedRodeo<K, S>
 where
     K: Eq + Hash + Key,
     S: Clone + BuildHasher,
 {
     #[cfg_attr(feature = "inline-more", inline)]
-    fn eq(&self, other: &Rodeo<K, S>) -> bool {
+    fn eq(&self, other: &Rodeo<K2, S2>) -> bool {
         self.strings.len() == other.strings.len()
             && other.strings.iter().enumerate().all(|(key, string)| {
                 K::try_from_usize(key)
                     .and_then(|key| self.strings.get(&key))
-                    .map(|s| s.value() == string)
-                    == Some(true)
+                    == Some(string)
             })
     }
 }
 
-impl<K, S> PartialEq<RodeoReader<K, S>> for ThreadedRodeo<K, S>
+impl<K, S, K2, S2> PartialEq<RodeoReader<K2, S2>> for ThreadedRodeo<K, S>
 where
     K: Eq + Hash + Key,
     S: Clone + BuildHasher,
 {
     #[cfg_attr(feature = "inline-more", inline)]
-    fn eq(&self, other: &RodeoReader<K, S>) -> bool {
+    fn eq(&self, other: &RodeoReader<K2, S2>) -> bool {
         self.strings.len() == other.strings.len()
             && other.strings.iter().enumerate().all(|(key, string)| {
                 K::try_from_usize(key)
                     .and_then(|key| self.strings.get(&key))
-                    .map(|s| s.value() == string)
-                    == Some(true)
+                    == Some(string)
             })
     }
 }
 
-impl<K, S> PartialEq<RodeoResolver<K>> for ThreadedRodeo<K, S>
+impl<K, S, K2> PartialEq<RodeoResolver<K2>> for ThreadedRodeo<K, S>
 where
     K: Eq + Hash + Key,
     S: Clone + BuildHasher,
 {
     #[cfg_attr(feature = "inline-more", inline)]
-    fn eq(&self, other: &RodeoResolver<K>) -> bool {
+    fn eq(&self, other: &RodeoResolver<K2>) -> bool {
         self.strings.len() == other.strings.len()
             && other.strings.iter().enumerate().all(|(key, string)| {
                 K::try_from_usize(key)
                     .and_then(|key| self.strings.get(&key))
-                    .map(|s| s.value() == string)
-                    == Some(true)
+                    == Some(string)
             })
     }
 }
 
 compile! {
     if #[feature = "serialize"] {
-        use alloc::string::String;
-        use core::num::NonZeroUsize;
+        use alloc::{collections::BTreeMap, string::String};
+        use core::cmp::max;
         use serde::{
             de::{Deserialize, Deserializer},
             ser::{Serialize, Serializer},
@@ -928,6 +2219,17 @@ compile! {
     }
 }
 
+#[cfg(feature = "deepsize")]
+impl<K, S> deepsize::DeepSizeOf for ThreadedRodeo<K, S>
+where
+    K: Key + Hash,
+    S: BuildHasher + Clone,
+{
+    fn deep_size_of_children(&self, _context: &mut deepsize::Context) -> usize {
+        self.current_memory_usage()
+    }
+}
+
 #[cfg(feature = "serialize")]
 impl<K, H> Serialize for ThreadedRodeo<K, H>
 where
@@ -939,13 +2241,11 @@ where
     where
         S: Serializer,
     {
-        // Serialize all of self as a `HashMap<String, K>`
-        let mut map = HashMap::with_capacity(self.map.len());
-        for entry in self.map.iter() {
-            map.insert(*entry.key(), entry.value().to_owned());
-        }
+        // Serialize all of self as a map of strings to their keys
+        let mut entries = Vec::with_capacity(self.strings.len());
+        self.map.for_each(|string, key| entries.push((string, key)));
 
-        map.serialize(serializer)
+        serializer.collect_map(entries)
     }
 }
 
@@ -960,7 +2260,8 @@ where
     where
         D: Deserializer<'de>,
     {
-        let deser_map: HashMap<String, K> = HashMap::deserialize(deserializer)?;
+        // Deserialized into a `BTreeMap` so that it doesn't need a hasher of its own
+        let deser_map: BTreeMap<String, K> = BTreeMap::deserialize(deserializer)?;
         let capacity = {
             let total_bytes = deser_map.keys().map(|s| s.len()).sum::<usize>();
             let total_bytes =
@@ -970,16 +2271,17 @@ where
         };
 
         let hasher = S::default();
-        let map = DashMap::with_capacity_and_hasher(capacity.strings, hasher.clone());
-        let strings = DashMap::with_capacity_and_hasher(capacity.strings, hasher);
-        let mut highest = 0;
+        let map = ShardMap::try_with_capacity_and_hasher(capacity.strings, hasher)
+            .expect("failed to allocate memory for interner");
+        let strings = KeyShards::try_with_capacity(capacity.strings)
+            .expect("failed to allocate memory for interner");
+        let mut next_key = 0;
         let arena = LockfreeArena::new(capacity.bytes, usize::MAX)
             .expect("failed to allocate memory for interner");
 
         for (string, key) in deser_map {
-            if key.into_usize() > highest {
-                highest = key.into_usize();
-            }
+            // The next key to be handed out is the one directly after the highest key
+            next_key = max(next_key, key.into_usize() + 1);
 
             let allocated = unsafe {
                 arena
@@ -991,11 +2293,24 @@ where
             strings.insert(key, allocated);
         }
 
+        #[cfg(feature = "timestamps")]
+        let timestamps = {
+            let mut timestamps = ConcurrentTimestamps::new();
+            timestamps.fill(next_key);
+            timestamps
+        };
+
         Ok(Self {
             map,
             strings,
-            key: AtomicUsize::new(highest),
+            statics: KeyShards::new(),
+            key: AtomicUsize::new(next_key),
+            clears: AtomicUsize::new(0),
             arena,
+            #[cfg(feature = "profile-interning")]
+            profile: ConcurrentInternProfile::default(),
+            #[cfg(feature = "timestamps")]
+            timestamps,
         })
     }
 }
@@ -1003,7 +2318,8 @@ where
 /// An iterator over an interner's strings and keys
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 pub struct Iter<'a, K, S> {
-    iter: dashmap::iter::Iter<'a, K, &'static str, S, DashMap<K, &'static str, S>>,
+    iter: KeyShardsIter<'a, K>,
+    __hasher: PhantomData<S>,
 }
 
 impl<'a, K, S> Iter<'a, K, S>
@@ -1015,6 +2331,16 @@ where
     pub(crate) fn new(rodeo: &'a ThreadedRodeo<K, S>) -> Self {
         Self {
             iter: rodeo.strings.iter(),
+            __hasher: PhantomData,
+        }
+    }
+
+    /// Creates an iterator over only the interner's static strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub(crate) fn statics(rodeo: &'a ThreadedRodeo<K, S>) -> Self {
+        Self {
+            iter: rodeo.statics.iter(),
+            __hasher: PhantomData,
         }
     }
 }
@@ -1028,7 +2354,7 @@ where
 
     #[cfg_attr(feature = "inline-more", inline)]
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|r| (*r.key(), *r.value()))
+        self.iter.next()
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
@@ -1081,11 +2407,263 @@ where
     }
 }
 
+/// An iterator over an interner's strings and keys, in key order
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct OrderedIter<'a, K, S> {
+    rodeo: &'a ThreadedRodeo<K, S>,
+    /// The key value of the next string
+    next: usize,
+    /// The number of keys that had been handed out when the iterator was created
+    end: usize,
+}
+
+impl<'a, K, S> OrderedIter<'a, K, S>
+where
+    K: Key + Hash,
+    S: BuildHasher + Clone,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub(crate) fn new(rodeo: &'a ThreadedRodeo<K, S>) -> Self {
+        Self {
+            rodeo,
+            next: 0,
+            end: rodeo.key.load(Ordering::Acquire),
+        }
+    }
+}
+
+impl<'a, K, S> Iterator for OrderedIter<'a, K, S>
+where
+    K: Key + Hash,
+    S: BuildHasher + Clone,
+{
+    type Item = (K, &'a str);
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < self.end {
+            let key = K::try_from_usize(self.next)?;
+            self.next += 1;
+
+            // A key can be taken a moment before its string is inserted, so skip over
+            // any strings that aren't there yet
+            if let Some(string) = self.rodeo.try_resolve(&key) {
+                return Some((key, string));
+            }
+        }
+
+        None
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end - self.next))
+    }
+}
+
+impl<K, S> Debug for OrderedIter<'_, K, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("OrderedIter")
+            .field("next", &self.next)
+            .field("end", &self.end)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An iterator over a point-in-time copy of an interner's strings and keys, in key order,
+/// made by [`ThreadedRodeo::snapshot_iter()`]
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SnapshotIter<'a, K> {
+    iter: std::vec::IntoIter<(K, &'a str)>,
+}
+
+impl<'a, K> SnapshotIter<'a, K>
+where
+    K: Key + Hash,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub(crate) fn new<S>(rodeo: &'a ThreadedRodeo<K, S>) -> Self
+    where
+        S: BuildHasher + Clone,
+    {
+        // Every string is resolved on its own, so no shard is locked for longer than a
+        // single lookup. A key can be taken a moment before its string is inserted, so any
+        // strings that aren't there yet are left out
+        let pairs: Vec<(K, &'a str)> = OrderedIter::new(rodeo).collect();
+
+        Self {
+            iter: pairs.into_iter(),
+        }
+    }
+}
+
+impl<'a, K> Iterator for SnapshotIter<'a, K> {
+    type Item = (K, &'a str);
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K> DoubleEndedIterator for SnapshotIter<'_, K> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<K> ExactSizeIterator for SnapshotIter<'_, K> {}
+
+impl<K> FusedIterator for SnapshotIter<'_, K> {}
+
+/// An iterator over an interner's strings, in key order
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct OrderedStrings<'a, K, S> {
+    iter: OrderedIter<'a, K, S>,
+}
+
+impl<'a, K, S> OrderedStrings<'a, K, S>
+where
+    K: Key + Hash,
+    S: BuildHasher + Clone,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub(crate) fn new(rodeo: &'a ThreadedRodeo<K, S>) -> Self {
+        Self {
+            iter: OrderedIter::new(rodeo),
+        }
+    }
+}
+
+impl<'a, K, S> Iterator for OrderedStrings<'a, K, S>
+where
+    K: Key + Hash,
+    S: BuildHasher + Clone,
+{
+    type Item = &'a str;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, v)| v)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// An iterator over the keys and strings of one of a [`ThreadedRodeo`]'s shards, which is
+/// locked while the iterator is alive
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ShardIter<'a, K> {
+    entries: ShardEntries<'a, K>,
+}
+
+impl<'a, K: Copy> Iterator for ShardIter<'a, K> {
+    type Item = (K, &'a str);
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|(string, key)| (key, string))
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<K> Debug for ShardIter<'_, K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ShardIter").finish_non_exhaustive()
+    }
+}
+
+/// An iterator over owned batches of a [`ThreadedRodeo`]'s keys and strings, one for
+/// each non-empty shard
+///
+/// The interner is cleared once the iterator is dropped
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct DrainShards<'a, K, S>
+where
+    K: Key + Hash,
+    S: BuildHasher + Clone,
+{
+    rodeo: &'a mut ThreadedRodeo<K, S>,
+    /// The index of the next shard to drain
+    shard: usize,
+}
+
+impl<K, S> Iterator for DrainShards<'_, K, S>
+where
+    K: Key + Hash,
+    S: BuildHasher + Clone,
+{
+    type Item = Vec<(K, String)>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.shard < self.rodeo.map.shard_count() {
+            let batch: Vec<_> = self
+                .rodeo
+                .map
+                .drain_shard(self.shard)
+                .map(|(string, key)| (key, string.to_owned()))
+                .collect();
+            self.shard += 1;
+
+            if !batch.is_empty() {
+                return Some(batch);
+            }
+        }
+
+        None
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.rodeo.map.shard_count() - self.shard))
+    }
+}
+
+impl<K, S> Drop for DrainShards<'_, K, S>
+where
+    K: Key + Hash,
+    S: BuildHasher + Clone,
+{
+    fn drop(&mut self) {
+        self.rodeo.clear();
+        self.rodeo.free_cleared();
+    }
+}
+
+impl<K, S> Debug for DrainShards<'_, K, S>
+where
+    K: Key + Hash,
+    S: BuildHasher + Clone,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("DrainShards")
+            .field("shard", &self.shard)
+            .finish_non_exhaustive()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{hasher::RandomState, Capacity, MemoryLimits, MicroSpur};
-    use core::num::NonZeroUsize;
+    use crate::{
+        hasher::RandomState, Capacity, FixedState, MemoryLimits, MicroSpur, MiniSpur, RodeoBuilder,
+    };
 
     #[cfg(not(any(miri, feature = "no-std")))]
     use std::{
@@ -1131,6 +2709,52 @@ mod tests {
         assert_eq!("Test", rodeo.resolve(&key));
     }
 
+    #[test]
+    fn try_with_capacity() {
+        let rodeo: LassoResult<ThreadedRodeo<Spur>> = ThreadedRodeo::try_with_capacity(Capacity::for_strings(10));
+        assert!(rodeo.is_ok());
+
+        let rodeo: LassoResult<ThreadedRodeo<Spur>> = ThreadedRodeo::try_with_capacity(Capacity::for_strings(usize::MAX));
+        assert_eq!(
+            rodeo.unwrap_err().kind(),
+            LassoErrorKind::FailedAllocation,
+        );
+
+        let rodeo: LassoResult<ThreadedRodeo<Spur>> =
+            ThreadedRodeo::try_with_capacity(Capacity::for_bytes(NonZeroUsize::new(usize::MAX).unwrap()));
+        assert_eq!(
+            rodeo.unwrap_err().kind(),
+            LassoErrorKind::FailedAllocation,
+        );
+    }
+
+    #[test]
+    fn try_constructors() {
+        let mut rodeos: [ThreadedRodeo<Spur>; 6] = [
+            ThreadedRodeo::try_new().unwrap(),
+            ThreadedRodeo::try_with_memory_limits(MemoryLimits::for_memory_usage(4096)).unwrap(),
+            ThreadedRodeo::try_with_capacity_and_memory_limits(
+                Capacity::for_strings(10),
+                MemoryLimits::for_memory_usage(4096),
+            )
+            .unwrap(),
+            ThreadedRodeo::try_with_hasher(RandomState::new()).unwrap(),
+            ThreadedRodeo::try_with_capacity_and_hasher(Capacity::for_strings(10), RandomState::new())
+                .unwrap(),
+            ThreadedRodeo::try_with_capacity_memory_limits_and_hasher(
+                Capacity::for_strings(10),
+                MemoryLimits::for_memory_usage(4096),
+                RandomState::new(),
+            )
+            .unwrap(),
+        ];
+
+        for rodeo in rodeos.iter_mut() {
+            let key = rodeo.get_or_intern("Test");
+            assert_eq!("Test", rodeo.resolve(&key));
+        }
+    }
+
     #[test]
     fn get_or_intern() {
         let rodeo = ThreadedRodeo::default();
@@ -1188,6 +2812,68 @@ mod tests {
         assert!(rodeo.try_get_or_intern("C").is_err());
     }
 
+    #[test]
+    fn key_exhaustion_is_stable() {
+        let rodeo: ThreadedRodeo<MicroSpur> = ThreadedRodeo::new();
+
+        for i in 0..u8::MAX as usize {
+            rodeo.get_or_intern(i.to_string());
+        }
+        let memory_usage = rodeo.current_memory_usage();
+
+        // Repeated failures don't advance the key counter or take up arena space
+        for _ in 0..1000 {
+            let err = rodeo.try_get_or_intern("A").unwrap_err();
+            assert_eq!(err.kind(), LassoErrorKind::KeySpaceExhaustion);
+            assert!(rodeo.try_get_or_intern_static("B").is_err());
+        }
+        assert_eq!(rodeo.key.load(Ordering::SeqCst), u8::MAX as usize);
+        assert_eq!(rodeo.current_memory_usage(), memory_usage);
+
+        // Previously interned strings are still accessible
+        let zero = rodeo.try_get_or_intern("0").unwrap();
+        assert_eq!(rodeo.resolve(&zero), "0");
+
+        // No keys were skipped
+        let reader = rodeo.into_reader();
+        assert_eq!(reader.len(), u8::MAX as usize);
+        for i in 0..u8::MAX as usize {
+            let key = MicroSpur::try_from_usize(i).unwrap();
+            assert_eq!(reader.resolve(&key), i.to_string());
+        }
+    }
+
+    #[test]
+    #[cfg(not(any(miri, feature = "no-std")))]
+    fn key_exhaustion_threaded() {
+        let rodeo: Arc<ThreadedRodeo<MicroSpur>> = Arc::new(ThreadedRodeo::new());
+        let barrier = Arc::new(Barrier::new(4));
+
+        let handles: Vec<_> = (0..4)
+            .map(|thread| {
+                let (rodeo, barrier) = (Arc::clone(&rodeo), Arc::clone(&barrier));
+
+                thread::spawn(move || {
+                    barrier.wait();
+                    for i in 0..u8::MAX as usize * 2 {
+                        let _ = rodeo.try_get_or_intern(format!("{}-{}", thread, i));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(rodeo.len(), u8::MAX as usize);
+        assert_eq!(rodeo.key.load(Ordering::SeqCst), u8::MAX as usize);
+
+        let reader = Arc::try_unwrap(rodeo).unwrap().into_reader();
+        for i in 0..u8::MAX as usize {
+            assert!(reader.contains_key(&MicroSpur::try_from_usize(i).unwrap()));
+        }
+    }
+
     #[test]
     #[cfg(not(any(miri, feature = "no-std")))]
     fn try_get_or_intern_threaded() {
@@ -1238,11 +2924,26 @@ mod tests {
         let a = rodeo.get_or_intern_static("A");
         assert_eq!(a, rodeo.get_or_intern_static("A"));
 
-        let b = rodeo.get_or_intern_static("B");
-        assert_eq!(b, rodeo.get_or_intern_static("B"));
+        let b = rodeo.get_or_intern_static("B");
+        assert_eq!(b, rodeo.get_or_intern_static("B"));
+
+        let c = rodeo.get_or_intern_static("C");
+        assert_eq!(c, rodeo.get_or_intern_static("C"));
+    }
+
+    #[test]
+    fn get_or_intern_cow() {
+        let rodeo = ThreadedRodeo::default();
+
+        // Borrowed strings aren't copied into the arena
+        let string: &'static str = "borrowed";
+        let a = rodeo.get_or_intern_cow(Cow::Borrowed(string));
+        assert_eq!(rodeo.resolve(&a).as_ptr(), string.as_ptr());
+        assert_eq!(rodeo.get_or_intern_cow(Cow::Owned(string.to_string())), a);
 
-        let c = rodeo.get_or_intern_static("C");
-        assert_eq!(c, rodeo.get_or_intern_static("C"));
+        let b = rodeo.get_or_intern_cow(Cow::Owned("owned".to_string()));
+        assert_eq!(rodeo.resolve(&b), "owned");
+        assert_eq!(rodeo.try_get_or_intern_cow(Cow::Borrowed("owned")), Ok(b));
     }
 
     #[test]
@@ -1284,6 +2985,66 @@ mod tests {
         assert_eq!(c, rodeo.try_get_or_intern("C"));
     }
 
+    #[test]
+    fn static_strings() {
+        let rodeo = ThreadedRodeo::default();
+
+        let a = rodeo.get_or_intern("A");
+        let b = rodeo.get_or_intern_static("B");
+        assert_eq!(a, rodeo.get_or_intern_static("A"));
+        assert_eq!(b, rodeo.get_or_intern("B"));
+
+        let statics: Vec<_> = rodeo.static_strings().collect();
+        assert_eq!(statics, [(b, "B")]);
+        assert_eq!(rodeo.len(), 2);
+
+        rodeo.clear();
+        assert_eq!(rodeo.static_strings().count(), 0);
+    }
+
+    #[test]
+    #[cfg(not(any(miri, feature = "no-std")))]
+    fn static_strings_threaded() {
+        let rodeo: Arc<ThreadedRodeo> = Arc::new(ThreadedRodeo::new());
+        let strings: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+        let statics: &'static [String] = Box::leak(strings.clone().into_boxed_slice());
+
+        let moved = Arc::clone(&rodeo);
+        let handle = thread::spawn(move || {
+            statics
+                .iter()
+                .map(|string| moved.get_or_intern_static(string))
+                .collect::<Vec<_>>()
+        });
+        let keys: Vec<_> = strings
+            .iter()
+            .map(|string| rodeo.get_or_intern(string))
+            .collect();
+
+        // Whichever thread won, both got the same key for every string
+        assert_eq!(handle.join().unwrap(), keys);
+        assert_eq!(rodeo.len(), 100);
+
+        for (key, string) in rodeo.static_strings() {
+            assert_eq!(rodeo.resolve(&key), string);
+            assert!(statics.iter().any(|s| core::ptr::eq(s.as_str(), string)));
+        }
+    }
+
+    #[test]
+    fn metrics() {
+        let rodeo = ThreadedRodeo::default();
+        rodeo.get_or_intern("a");
+        rodeo.get_or_intern_static("b");
+        rodeo.get_or_intern("c".repeat(5000));
+
+        let metrics = rodeo.metrics();
+        assert_eq!(metrics.interned_total(), 3);
+        assert_eq!(metrics.map_entries(), 3);
+        assert_eq!(metrics.bytes_allocated(), rodeo.current_memory_usage());
+        assert_eq!(metrics.memory_limit(), rodeo.max_memory_usage());
+    }
+
     #[test]
     fn get() {
         let rodeo = ThreadedRodeo::default();
@@ -1361,6 +3122,27 @@ mod tests {
         assert_eq!(None, rodeo.try_resolve(&Spur::try_from_usize(100).unwrap()));
     }
 
+    #[test]
+    fn resolve_many() {
+        let rodeo = ThreadedRodeo::default();
+        let keys: Vec<Spur> = (0..100).map(|i| rodeo.get_or_intern(i.to_string())).collect();
+
+        // Keys are spread over every shard, the strings come back in the order they were asked for
+        let reversed: Vec<Spur> = keys.iter().rev().copied().collect();
+        let expected: Vec<String> = (0..100).rev().map(|i: usize| i.to_string()).collect();
+        assert_eq!(rodeo.resolve_many(&reversed), expected);
+        assert!(rodeo.resolve_many(&[]).is_empty());
+
+        assert_eq!(
+            rodeo.try_resolve_many(&[keys[1], keys[1]]),
+            Some(vec!["1", "1"])
+        );
+        assert_eq!(
+            rodeo.try_resolve_many(&[keys[0], Spur::try_from_usize(1000).unwrap()]),
+            None
+        );
+    }
+
     #[test]
     #[cfg(not(any(miri, feature = "no-std")))]
     fn try_resolve_threaded() {
@@ -1394,6 +3176,107 @@ mod tests {
         assert!(rodeo.is_empty());
     }
 
+    #[test]
+    fn clear() {
+        let rodeo: ThreadedRodeo<Spur> = ThreadedRodeo::default();
+        let a = rodeo.get_or_intern("A");
+        let long = "B".repeat(10_000);
+        let b = rodeo.get_or_intern_owned(long.clone());
+        let (resolved_a, resolved_b) = (rodeo.resolve(&a), rodeo.resolve(&b));
+
+        rodeo.clear();
+        assert!(rodeo.is_empty());
+        assert_eq!(rodeo.get("A"), None);
+        assert_eq!(rodeo.try_resolve(&b), None);
+
+        // Strings resolved before clearing are still alive
+        assert_eq!(resolved_a, "A");
+        assert_eq!(resolved_b, long);
+
+        // Keys start over from zero
+        assert_eq!(rodeo.get_or_intern("C"), a);
+        assert_eq!(rodeo.resolve(&a), "C");
+        assert_eq!(rodeo.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "profile-interning")]
+    fn profiling_report() {
+        let rodeo: ThreadedRodeo<Spur> = ThreadedRodeo::default();
+        for _ in 0..2 {
+            rodeo.get_or_intern("a");
+        }
+        rodeo.get_or_intern_static("static");
+        crate::Interner::get_or_intern(&mut &rodeo, "a");
+
+        let report = rodeo.profiling_report();
+        let sites: Vec<_> = report
+            .iter()
+            .map(|site| (site.interns(), site.bytes(), site.location().file()))
+            .collect();
+        assert_eq!(sites, [(1, 6, file!()), (2, 2, file!()), (1, 1, file!())]);
+
+        rodeo.reset_profiling();
+        assert!(rodeo.profiling_report().is_empty());
+    }
+
+    #[test]
+    fn free_cleared() {
+        let mut rodeo: ThreadedRodeo<Spur> =
+            ThreadedRodeo::with_capacity(Capacity::for_bytes(NonZeroUsize::new(16).unwrap()));
+        for i in 0..100 {
+            rodeo.get_or_intern(i.to_string());
+        }
+        let full = rodeo.arena_memory_usage();
+
+        rodeo.clear();
+        assert_eq!(rodeo.arena_memory_usage(), full);
+
+        rodeo.get_or_intern("A");
+        let cleared = rodeo.arena_memory_usage();
+        assert!(cleared > full);
+
+        rodeo.free_cleared();
+        assert_eq!(rodeo.arena_memory_usage(), cleared - full);
+        assert_eq!(rodeo.resolve(&rodeo.get("A").unwrap()), "A");
+
+        // Freeing twice does nothing
+        rodeo.free_cleared();
+        assert_eq!(rodeo.arena_memory_usage(), cleared - full);
+    }
+
+    #[test]
+    #[cfg(not(any(miri, feature = "no-std")))]
+    fn clear_threaded() {
+        let rodeo: Arc<ThreadedRodeo<Spur>> = Arc::new(ThreadedRodeo::new());
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let rodeo = Arc::clone(&rodeo);
+                thread::spawn(move || {
+                    for i in 0..1000 {
+                        rodeo.get_or_intern(i.to_string());
+                        if i % 100 == 0 {
+                            rodeo.clear();
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // Every remaining string has a unique key that resolves back to it
+        for (key, string) in rodeo.iter() {
+            assert_eq!(rodeo.get(string), Some(key));
+        }
+        assert!(rodeo
+            .iter()
+            .all(|(key, _)| key.into_usize() < rodeo.len()));
+    }
+
     // #[test]
     // fn clone() {
     //     let rodeo = ThreadedRodeo::default();
@@ -1432,6 +3315,17 @@ mod tests {
         println!("{:?}", rodeo);
     }
 
+    #[test]
+    #[cfg(not(miri))]
+    fn debug_elides() {
+        let rodeo: ThreadedRodeo = (0..20_usize).map(|i| i.to_string()).collect();
+
+        let debug = format!("{:?}", rodeo);
+        assert!(debug.starts_with("ThreadedRodeo { len: 20, memory_usage: "));
+        assert!(debug.contains("strings: {Spur(1): \"0\", Spur(2): \"1\""));
+        assert!(debug.ends_with("Spur(16): \"15\", ...: 4 more} }"));
+    }
+
     #[test]
     fn iter() {
         let rodeo = ThreadedRodeo::default();
@@ -1445,6 +3339,23 @@ mod tests {
         assert!(values.contains(&(2, "C")));
     }
 
+    #[test]
+    fn key_hasher_spreads_keys() {
+        let rodeo: ThreadedRodeo<MiniSpur> = ThreadedRodeo::new();
+        for i in 0..u16::MAX as usize - 1 {
+            rodeo.get_or_intern(i.to_string());
+        }
+
+        let shards = rodeo.strings.shard_lens();
+        let fullest = shards.iter().copied().max().unwrap();
+        assert!(fullest <= 2 * rodeo.len() / shards.len());
+
+        for i in 0..rodeo.len() {
+            let key = MiniSpur::try_from_usize(i).unwrap();
+            assert_eq!(rodeo.resolve(&key), i.to_string());
+        }
+    }
+
     #[test]
     fn strings() {
         let rodeo = ThreadedRodeo::default();
@@ -1458,6 +3369,216 @@ mod tests {
         assert!(strings.contains(&"C"));
     }
 
+    #[test]
+    fn for_each_shard() {
+        let rodeo = ThreadedRodeo::default();
+        let long = "a".repeat(1000);
+        for i in 0..100 {
+            rodeo.get_or_intern(i.to_string());
+        }
+        let long_key = rodeo.get_or_intern(&long);
+
+        let mut seen = Vec::new();
+        rodeo.for_each_shard(|shard| {
+            seen.extend(shard.map(|(key, string)| (key, string.to_owned())));
+        });
+        seen.sort_unstable();
+
+        let mut expected: Vec<_> = rodeo.iter().map(|(key, s)| (key, s.to_owned())).collect();
+        expected.sort_unstable();
+        assert_eq!(seen, expected);
+        assert!(seen.contains(&(long_key, long)));
+    }
+
+    #[test]
+    fn rebalance_shards() {
+        let mut rodeo = ThreadedRodeo::default();
+        let keys: Vec<Spur> = (0..100)
+            .map(|i| rodeo.get_or_intern(i.to_string()))
+            .collect();
+        assert!(!rodeo.is_mixing_shard_hashes());
+
+        rodeo.rebalance_shards();
+        assert!(rodeo.is_mixing_shard_hashes());
+        assert_eq!(rodeo.shard_distribution().iter().sum::<usize>(), 100);
+        for (i, key) in keys.iter().enumerate() {
+            assert_eq!(rodeo.get(i.to_string()), Some(*key));
+            assert_eq!(rodeo.get_or_intern(i.to_string()), *key);
+        }
+
+        let rodeo = RodeoBuilder::new()
+            .mix_shard_hashes(true)
+            .build_threaded::<Spur>();
+        assert!(rodeo.is_mixing_shard_hashes());
+        let key = rodeo.get_or_intern("A");
+        assert_eq!(rodeo.get("A"), Some(key));
+    }
+
+    #[test]
+    #[cfg(feature = "timestamps")]
+    fn timestamps() {
+        use core::sync::atomic::{AtomicU64, Ordering};
+
+        fn clock() -> u64 {
+            static TICKS: AtomicU64 = AtomicU64::new(10);
+            TICKS.fetch_add(10, Ordering::Relaxed)
+        }
+
+        let rodeo = RodeoBuilder::new().clock(clock).build_threaded::<Spur>();
+        let a = rodeo.get_or_intern("a");
+        let b = rodeo.get_or_intern_static("b");
+        let c = rodeo.get_or_intern("c");
+
+        assert_eq!(rodeo.get_or_intern("a"), a);
+        assert_eq!(rodeo.interned_at(&a), Some(10));
+        assert_eq!(rodeo.interned_at(&b), Some(20));
+        assert_eq!(rodeo.interned_at(&c), Some(30));
+        assert_eq!(rodeo.interned_at(&Spur::try_from_usize(3).unwrap()), None);
+
+        assert_eq!(rodeo.iter_older_than(10).count(), 0);
+        assert_eq!(
+            rodeo.iter_older_than(30).collect::<Vec<_>>(),
+            [(a, "a"), (b, "b")],
+        );
+
+        rodeo.clear();
+        assert_eq!(rodeo.interned_at(&a), None);
+        assert_eq!(rodeo.iter_older_than(u64::MAX).count(), 0);
+        assert_eq!(rodeo.get_or_intern("d"), a);
+        assert_eq!(rodeo.interned_at(&a), Some(40));
+
+        // Strings converted from a reader are given the time of the conversion
+        let mut single: Rodeo = RodeoBuilder::new().clock(clock).build();
+        let e = single.get_or_intern("e");
+        let threaded = single.into_reader().into_threaded();
+        assert!(threaded.interned_at(&e).unwrap() <= crate::monotonic_ticks());
+    }
+
+    #[test]
+    fn drain_shards() {
+        let mut rodeo = ThreadedRodeo::default();
+        for i in 0..100 {
+            rodeo.get_or_intern(i.to_string());
+        }
+
+        let mut drained: Vec<_> = rodeo.drain_shards().flatten().collect();
+        drained.sort_unstable_by_key(|&(key, _)| key);
+        assert_eq!(drained.len(), 100);
+        for (i, (key, string)) in drained.into_iter().enumerate() {
+            assert_eq!(key.into_usize(), i);
+            assert_eq!(string, i.to_string());
+        }
+
+        assert!(rodeo.is_empty());
+        assert_eq!(rodeo.arena_memory_usage(), 0);
+        assert_eq!(rodeo.get_or_intern("new").into_usize(), 0);
+
+        // Stopping early still empties the interner
+        rodeo.get_or_intern("other");
+        let first = rodeo.drain_shards().next().unwrap();
+        assert!(!first.is_empty());
+        assert!(rodeo.is_empty());
+        assert_eq!(rodeo.get("new"), None);
+    }
+
+    #[test]
+    fn iter_ordered() {
+        let rodeo = ThreadedRodeo::default();
+        let strings: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+        for string in &strings {
+            rodeo.get_or_intern(string);
+        }
+
+        let values: Vec<_> = rodeo
+            .iter_ordered()
+            .map(|(k, v)| (k.into_usize(), v))
+            .collect();
+        let expected: Vec<_> = strings.iter().map(String::as_str).enumerate().collect();
+        assert_eq!(values, expected);
+
+        let ordered: Vec<_> = rodeo.strings_ordered().collect();
+        assert_eq!(ordered, strings);
+
+        // Strings interned after the iterator was made aren't yielded
+        let mut iter = rodeo.strings_ordered();
+        rodeo.get_or_intern("late");
+        assert_eq!(iter.by_ref().count(), 100);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn shrink_to_fit() {
+        let rodeo: ThreadedRodeo<Spur> = RodeoBuilder::new().shrink_on_clear(true).build_threaded();
+        for i in 0..1000 {
+            rodeo.get_or_intern(i.to_string());
+        }
+        rodeo.get_or_intern_static("static");
+        let full = rodeo.current_memory_usage();
+
+        // Shrinking keeps every string findable
+        rodeo.shrink_to_fit();
+        for i in 0..1000 {
+            let key = rodeo.get(i.to_string()).unwrap();
+            assert_eq!(rodeo.resolve(&key), i.to_string());
+        }
+        assert!(rodeo.get("static").is_some());
+
+        // Only the arena's memory is left after a clear
+        rodeo.clear();
+        assert!(rodeo.current_memory_usage() < full);
+        assert_eq!(rodeo.current_memory_usage(), rodeo.arena_memory_usage());
+
+        let key = rodeo.get_or_intern("a");
+        assert_eq!(rodeo.resolve(&key), "a");
+    }
+
+    #[test]
+    fn snapshot_iter() {
+        let rodeo = ThreadedRodeo::default();
+        let strings: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+        for string in &strings {
+            rodeo.get_or_intern(string);
+        }
+
+        let snapshot = rodeo.snapshot_iter();
+        rodeo.get_or_intern("late");
+        assert_eq!(snapshot.len(), 100);
+
+        let values: Vec<_> = snapshot.map(|(k, v)| (k.into_usize(), v)).collect();
+        let expected: Vec<_> = strings.iter().map(String::as_str).enumerate().collect();
+        assert_eq!(values, expected);
+
+        let last = rodeo.snapshot_iter().next_back();
+        assert_eq!(last.map(|(_, string)| string), Some("late"));
+    }
+
+    #[test]
+    #[cfg(not(any(miri, feature = "no-std")))]
+    fn snapshot_iter_while_interning() {
+        let rodeo: Arc<ThreadedRodeo> = Arc::new(ThreadedRodeo::default());
+        let writer = {
+            let rodeo = Arc::clone(&rodeo);
+            thread::spawn(move || {
+                for i in 0..1000 {
+                    rodeo.get_or_intern(i.to_string());
+                }
+            })
+        };
+
+        while !writer.is_finished() {
+            // Every snapshot is made of strings that are interned under their keys
+            let mut previous = None;
+            for (key, string) in rodeo.snapshot_iter() {
+                assert_eq!(rodeo.get(string), Some(key));
+                assert!(previous < Some(key.into_usize()));
+                previous = Some(key.into_usize());
+            }
+        }
+        writer.join().unwrap();
+
+        assert_eq!(rodeo.snapshot_iter().len(), 1000);
+    }
+
     #[test]
     #[cfg(not(any(miri, feature = "no-std")))]
     fn debug_iter() {
@@ -1483,6 +3604,22 @@ mod tests {
         assert_eq!("A", resolver.resolve(&key));
     }
 
+    #[test]
+    fn into_resolver_compact() {
+        let rodeo = ThreadedRodeo::default();
+        let keys: Vec<Spur> = (0..1000)
+            .map(|i| rodeo.get_or_intern(i.to_string()))
+            .collect();
+        let bytes = rodeo.strings().map(str::len).sum::<usize>();
+
+        let resolver = rodeo.into_resolver_compact();
+        assert_eq!(resolver.len(), 1000);
+        assert_eq!(resolver.arena_memory_usage(), bytes);
+        for (i, key) in keys.iter().enumerate() {
+            assert_eq!(resolver.resolve(key), i.to_string());
+        }
+    }
+
     #[test]
     fn into_reader() {
         let rodeo = ThreadedRodeo::default();
@@ -1492,6 +3629,65 @@ mod tests {
         assert_eq!("A", reader.resolve(&key));
     }
 
+    #[test]
+    fn convert_with_gaps() {
+        let make = || {
+            let rodeo = ThreadedRodeo::default();
+            let a = rodeo.get_or_intern("A");
+            // A thread that panicked after taking its key leaves it without a string
+            let gap = rodeo.reserve_key().unwrap();
+            let b = rodeo.get_or_intern("B");
+
+            (rodeo, [a, gap, b])
+        };
+
+        let (rodeo, [a, gap, b]) = make();
+        let resolver = rodeo.into_resolver();
+        assert_eq!(resolver.len(), 3);
+        assert_eq!(resolver.resolve(&a), "A");
+        assert_eq!(resolver.try_resolve(&gap), None);
+        assert!(!resolver.contains_key(&gap));
+        assert_eq!(resolver.resolve(&b), "B");
+
+        // Gaps are kept when changing the key type
+        let resolver = resolver.try_rekey::<MiniSpur>().unwrap();
+        assert!(!resolver.contains_key(&MiniSpur::try_from_usize(1).unwrap()));
+
+        let (rodeo, [a, gap, b]) = make();
+        let reader = rodeo.into_reader();
+        assert_eq!(reader.try_resolve(&gap), None);
+        assert!(!reader.contains_key(&gap));
+        assert!(reader.contains_key(&b));
+        assert_eq!(reader.get("A"), Some(a));
+        assert_eq!(reader.get("B"), Some(b));
+        assert_eq!(reader.get(""), None);
+
+        // Gaps are still missing their strings after converting back and forth
+        let rodeo = reader.into_threaded();
+        assert_eq!(rodeo.try_resolve(&gap), None);
+        assert_eq!(rodeo.get(""), None);
+        let resolver = rodeo.into_reader().into_resolver();
+        assert!(!resolver.contains_key(&gap));
+        assert_eq!(resolver.resolve(&b), "B");
+
+        // Compacting keeps the gaps too
+        let (rodeo, [a, gap, b]) = make();
+        let resolver = rodeo.into_resolver_compact();
+        assert_eq!(resolver.resolve(&a), "A");
+        assert!(!resolver.contains_key(&gap));
+        assert_eq!(resolver.resolve(&b), "B");
+        assert_eq!(resolver.arena_memory_usage(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn resolve_gap() {
+        let rodeo = ThreadedRodeo::default();
+        let gap = rodeo.reserve_key().unwrap();
+
+        rodeo.into_resolver().resolve(&gap);
+    }
+
     #[test]
     fn memory_exhausted() {
         let rodeo: ThreadedRodeo<Spur> = ThreadedRodeo::with_capacity_and_memory_limits(
@@ -1615,10 +3811,69 @@ mod tests {
 
         rodeo.get_or_intern("0123456789");
 
-        assert_eq!(rodeo.current_memory_usage(), 10);
+        assert_eq!(rodeo.arena_memory_usage(), 10);
         assert_eq!(rodeo.max_memory_usage(), 10);
     }
 
+    #[test]
+    fn total_budget() {
+        let rodeo: ThreadedRodeo<Spur> =
+            ThreadedRodeo::with_memory_limits(MemoryLimits::total_budget(16 * 1024));
+        assert!(rodeo.current_memory_usage() > rodeo.arena_memory_usage());
+
+        let mut interned = 0;
+        while rodeo.try_get_or_intern(interned.to_string()).is_ok() {
+            interned += 1;
+        }
+
+        let err = rodeo.try_get_or_intern("new").unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::MemoryLimitReached);
+        assert!(rodeo.try_get_or_intern_static("static").is_err());
+        assert!(rodeo.current_memory_usage() <= 16 * 1024);
+        assert_eq!(rodeo.len(), interned);
+
+        // Lifting the total budget only counts the arena again
+        rodeo.set_memory_limits(MemoryLimits::for_memory_usage(16 * 1024));
+        assert!(rodeo.try_get_or_intern("new").is_ok());
+    }
+
+    #[test]
+    fn strings_at_memory_limit() {
+        let rodeo: ThreadedRodeo = ThreadedRodeo::with_capacity_and_memory_limits(
+            Capacity::for_bytes(NonZeroUsize::new(4).unwrap()),
+            MemoryLimits::for_memory_usage(4),
+        );
+        rodeo.get_or_intern("abc");
+
+        // A single byte fits exactly into the last byte of the arena
+        let d = rodeo.try_get_or_intern("d").unwrap();
+        let err = rodeo.try_get_or_intern("e").unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::MemoryLimitReached);
+
+        // Empty strings take no memory, so they can always be interned
+        let empty = rodeo.try_get_or_intern("").unwrap();
+        assert_eq!(rodeo.try_get_or_intern(""), Ok(empty));
+        assert_eq!(rodeo.try_get_or_intern_static(""), Ok(empty));
+        assert_eq!(rodeo.resolve(&empty), "");
+        assert_eq!(rodeo.try_get_or_intern("d"), Ok(d));
+        assert_eq!(rodeo.arena_memory_usage(), 4);
+    }
+
+    #[test]
+    fn empty_strings_within_total_budget() {
+        let rodeo: ThreadedRodeo =
+            ThreadedRodeo::with_memory_limits(MemoryLimits::total_budget(16 * 1024));
+        let mut interned = 0;
+        while rodeo.try_get_or_intern(interned.to_string()).is_ok() {
+            interned += 1;
+        }
+
+        let empty = rodeo.try_get_or_intern("").unwrap();
+        assert_eq!(rodeo.try_get_or_intern_static(""), Ok(empty));
+        assert_eq!(rodeo.len(), interned + 1);
+        assert!(rodeo.try_get_or_intern("a").is_err());
+    }
+
     #[test]
     fn contains() {
         let rodeo = ThreadedRodeo::default();
@@ -1714,6 +3969,24 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn deserialized_keys_dont_collide() {
+        let rodeo = ThreadedRodeo::default();
+        let a = rodeo.get_or_intern("a");
+        let b = rodeo.get_or_intern("b");
+
+        let ser = serde_json::to_string(&rodeo).unwrap();
+        let deser: ThreadedRodeo = serde_json::from_str(&ser).unwrap();
+
+        let c = deser.get_or_intern("c");
+        assert_ne!(c, a);
+        assert_ne!(c, b);
+        assert_eq!(deser.resolve(&a), "a");
+        assert_eq!(deser.resolve(&b), "b");
+        assert_eq!(deser.resolve(&c), "c");
+    }
+
     #[test]
     fn threaded_rodeo_eq() {
         let a = ThreadedRodeo::default();
@@ -1782,6 +4055,41 @@ mod tests {
         assert_eq!(a, b.into_reader());
     }
 
+    #[test]
+    fn eq_across_hashers_and_keys() {
+        let a: ThreadedRodeo<Spur> = ThreadedRodeo::new();
+        a.get_or_intern("a");
+        a.get_or_intern("b");
+        let b: ThreadedRodeo<MiniSpur, FixedState> =
+            ThreadedRodeo::with_hasher(FixedState::default());
+        b.get_or_intern("a");
+        b.get_or_intern("b");
+        let mut c: Rodeo<MiniSpur, FixedState> = Rodeo::with_hasher(FixedState::default());
+        c.get_or_intern("a");
+        c.get_or_intern("b");
+
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+        assert_eq!(a, c.into_resolver());
+    }
+
+    #[test]
+    fn equivalent_content() {
+        let a = ThreadedRodeo::default();
+        a.get_or_intern("a");
+        a.get_or_intern("b");
+        let mut b: Rodeo<MiniSpur, FixedState> = Rodeo::with_hasher(FixedState::default());
+        b.get_or_intern("b");
+        b.get_or_intern("a");
+
+        assert_ne!(a, b);
+        assert!(a.equivalent_content(&b));
+        assert!(b.equivalent_content(&a));
+
+        a.get_or_intern("c");
+        assert!(!a.equivalent_content(&b));
+    }
+
     // Test for race conditions on key insertion
     // https://github.com/Kixiron/lasso/issues/18
     #[test]
@@ -1842,3 +4150,106 @@ mod tests {
         }
     }
 }
+
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use crate::{FixedState, Spur, ThreadedRodeo};
+    use loom::{model::Builder, sync::Arc, thread};
+
+    fn model<F>(f: F)
+    where
+        F: Fn() + Sync + Send + 'static,
+    {
+        let mut builder = Builder::new();
+        builder.preemption_bound.get_or_insert(3);
+        builder.check(f);
+    }
+
+    // A fixed hasher keeps the shards that strings land in the same across executions
+    fn rodeo() -> Arc<ThreadedRodeo<Spur, FixedState>> {
+        Arc::new(ThreadedRodeo::with_hasher(FixedState::new()))
+    }
+
+    #[test]
+    fn loom_insert_same_string() {
+        model(|| {
+            let rodeo = rodeo();
+
+            let other = Arc::clone(&rodeo);
+            let thread = thread::spawn(move || other.try_get_or_intern("same").unwrap());
+            let key = rodeo.try_get_or_intern("same").unwrap();
+
+            assert_eq!(thread.join().unwrap(), key);
+            assert_eq!(rodeo.len(), 1);
+            assert_eq!(rodeo.resolve(&key), "same");
+        });
+    }
+
+    #[test]
+    fn loom_insert_different_strings() {
+        model(|| {
+            let rodeo = rodeo();
+
+            let other = Arc::clone(&rodeo);
+            let thread = thread::spawn(move || other.try_get_or_intern("first").unwrap());
+            let second = rodeo.try_get_or_intern("second").unwrap();
+            let first = thread.join().unwrap();
+
+            assert_ne!(first, second);
+            assert_eq!(rodeo.len(), 2);
+            assert_eq!(rodeo.resolve(&first), "first");
+            assert_eq!(rodeo.resolve(&second), "second");
+        });
+    }
+
+    #[test]
+    fn loom_clear_while_interning() {
+        model(|| {
+            let rodeo = rodeo();
+
+            let other = Arc::clone(&rodeo);
+            let thread = thread::spawn(move || {
+                let key = other.try_get_or_intern("first").unwrap();
+                (key, other.try_resolve(&key) == Some("first"))
+            });
+
+            rodeo.clear();
+            let (key, resolved) = thread.join().unwrap();
+
+            // The string was either interned after the clear or cleared along with
+            // everything else, never half of each
+            match rodeo.len() {
+                0 => assert_eq!(rodeo.get("first"), None),
+                _ => assert_eq!(rodeo.get("first"), Some(key)),
+            }
+            assert!(resolved || rodeo.is_empty());
+        });
+    }
+
+    #[test]
+    fn loom_into_reader_while_interning() {
+        model(|| {
+            let rodeo = rodeo();
+
+            // Whichever thread finishes last turns the interner into a reader, which
+            // must see every string the other thread interned
+            let convert = |rodeo: Arc<ThreadedRodeo<Spur, FixedState>>| {
+                if let Ok(rodeo) = Arc::try_unwrap(rodeo) {
+                    let reader = rodeo.into_reader();
+                    assert_eq!(reader.len(), 2);
+                    assert!(reader.contains("first") && reader.contains("second"));
+                }
+            };
+
+            let other = Arc::clone(&rodeo);
+            let thread = thread::spawn(move || {
+                other.try_get_or_intern("first").unwrap();
+                convert(other);
+            });
+
+            rodeo.try_get_or_intern("second").unwrap();
+            convert(rodeo);
+            thread.join().unwrap();
+        });
+    }
+}
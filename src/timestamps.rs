@@ -0,0 +1,214 @@
+//! Per key intern times, enabled with the `timestamps` feature
+
+use crate::{keys::Key, util::Iter};
+use alloc::vec::Vec;
+use core::{
+    iter::{FusedIterator, Zip},
+    slice,
+};
+
+#[cfg(all(feature = "multi-threaded", not(feature = "no-std")))]
+use crate::sync::RwLock;
+
+/// Returns the nanoseconds elapsed since the first time any interner's default clock was
+/// read, which is the clock interners record intern times with unless they were built with
+/// [`RodeoBuilder::clock`]
+///
+/// The ticks are monotonic, so they're useful for comparing intern times with each other
+/// and with the current time but mean nothing outside of the current process. Without the
+/// standard library there is no clock to read, so `no-std` interners record zero for every
+/// string unless they're given a clock of their own
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{monotonic_ticks, Rodeo};
+///
+/// let mut rodeo = Rodeo::default();
+/// let key = rodeo.get_or_intern("Strings of things");
+///
+/// assert!(rodeo.interned_at(&key).unwrap() <= monotonic_ticks());
+/// ```
+///
+/// [`RodeoBuilder::clock`]: crate::RodeoBuilder::clock
+#[cfg_attr(feature = "inline-more", inline)]
+pub fn monotonic_ticks() -> u64 {
+    #[cfg(not(feature = "no-std"))]
+    {
+        use std::{sync::OnceLock, time::Instant};
+
+        static ORIGIN: OnceLock<Instant> = OnceLock::new();
+        let elapsed = ORIGIN.get_or_init(Instant::now).elapsed();
+
+        u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX)
+    }
+
+    #[cfg(feature = "no-std")]
+    {
+        0
+    }
+}
+
+/// The time every string of a [`Rodeo`] was interned, indexed by key
+///
+/// [`Rodeo`]: crate::Rodeo
+#[derive(Debug, Clone)]
+pub(crate) struct Timestamps {
+    ticks: Vec<u64>,
+    clock: fn() -> u64,
+}
+
+impl Timestamps {
+    pub(crate) fn new() -> Self {
+        Self {
+            ticks: Vec::new(),
+            clock: monotonic_ticks,
+        }
+    }
+
+    pub(crate) fn set_clock(&mut self, clock: fn() -> u64) {
+        self.clock = clock;
+    }
+
+    /// Records the string with the key of `index` being interned now, which must be the
+    /// next key
+    pub(crate) fn record(&mut self, index: usize) {
+        debug_assert_eq!(self.ticks.len(), index);
+        self.ticks.push((self.clock)());
+    }
+
+    /// Records every string up to `len` that doesn't have a time yet as being interned now
+    #[cfg(feature = "serialize")]
+    pub(crate) fn fill(&mut self, len: usize) {
+        let now = (self.clock)();
+        self.ticks.resize(len.max(self.ticks.len()), now);
+    }
+
+    pub(crate) fn get(&self, index: usize) -> Option<u64> {
+        self.ticks.get(index).copied()
+    }
+
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.ticks.truncate(len);
+    }
+
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.ticks.shrink_to_fit();
+    }
+
+    /// Removes every time, freeing their memory if `shrink` is set
+    pub(crate) fn clear(&mut self, shrink: bool) {
+        if shrink {
+            self.ticks = Vec::new();
+        } else {
+            self.ticks.clear();
+        }
+    }
+}
+
+/// An iterator over the keys and strings of a [`Rodeo`] that were interned before a
+/// given time, made by [`Rodeo::iter_older_than`]
+///
+/// [`Rodeo`]: crate::Rodeo
+/// [`Rodeo::iter_older_than`]: crate::Rodeo::iter_older_than
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct OlderThan<'a, K> {
+    iter: Zip<Iter<'a, K>, slice::Iter<'a, u64>>,
+    cutoff: u64,
+}
+
+impl<'a, K: Key> OlderThan<'a, K> {
+    pub(crate) fn new(iter: Iter<'a, K>, timestamps: &'a Timestamps, cutoff: u64) -> Self {
+        Self {
+            iter: iter.zip(timestamps.ticks.iter()),
+            cutoff,
+        }
+    }
+}
+
+impl<'a, K: Key> Iterator for OlderThan<'a, K> {
+    type Item = (K, &'a str);
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let cutoff = self.cutoff;
+        self.iter
+            .by_ref()
+            .find(|&(_, &ticks)| ticks < cutoff)
+            .map(|(entry, _)| entry)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<K: Key> FusedIterator for OlderThan<'_, K> {}
+
+/// The time every string of a [`ThreadedRodeo`] was interned, indexed by key
+///
+/// Strings are given their keys out of order by different threads, so the slots of keys
+/// that haven't recorded their time yet are zero until they do
+///
+/// [`ThreadedRodeo`]: crate::ThreadedRodeo
+#[cfg(all(feature = "multi-threaded", not(feature = "no-std")))]
+pub(crate) struct ConcurrentTimestamps {
+    ticks: RwLock<Vec<u64>>,
+    clock: fn() -> u64,
+}
+
+#[cfg(all(feature = "multi-threaded", not(feature = "no-std")))]
+impl ConcurrentTimestamps {
+    pub(crate) fn new() -> Self {
+        Self {
+            ticks: RwLock::new(Vec::new()),
+            clock: monotonic_ticks,
+        }
+    }
+
+    pub(crate) fn set_clock(&mut self, clock: fn() -> u64) {
+        self.clock = clock;
+    }
+
+    /// Records the string with the key of `index` being interned now
+    pub(crate) fn record(&self, index: usize) {
+        let now = (self.clock)();
+
+        let mut ticks = self.ticks.write();
+        if ticks.len() <= index {
+            ticks.resize(index + 1, 0);
+        }
+        ticks[index] = now;
+    }
+
+    /// Records every string up to `len` that doesn't have a time yet as being interned now
+    pub(crate) fn fill(&mut self, len: usize) {
+        let now = (self.clock)();
+        let ticks = self.ticks.get_mut();
+        ticks.resize(len.max(ticks.len()), now);
+    }
+
+    pub(crate) fn get(&self, index: usize) -> Option<u64> {
+        self.ticks.read().get(index).copied()
+    }
+
+    /// Calls `f` with the time of every key
+    pub(crate) fn with_ticks<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&[u64]) -> R,
+    {
+        f(&self.ticks.read())
+    }
+
+    /// Removes every time, freeing their memory if `shrink` is set
+    pub(crate) fn clear(&self, shrink: bool) {
+        let mut ticks = self.ticks.write();
+        if shrink {
+            *ticks = Vec::new();
+        } else {
+            ticks.clear();
+        }
+    }
+}
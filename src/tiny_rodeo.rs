@@ -0,0 +1,502 @@
+use crate::{
+    keys::{Key, MicroSpur, MiniSpur},
+    Capacity, LassoError, LassoErrorKind, LassoResult,
+};
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt::{self, Debug},
+    iter::{FromIterator, FusedIterator},
+    marker::PhantomData,
+    mem::size_of,
+};
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for crate::MicroSpur {}
+    impl Sealed for crate::MiniSpur {}
+}
+
+/// A key small enough to be used with a [`TinyRodeo`], either [`MicroSpur`] or [`MiniSpur`]
+pub trait SmallKey: Key + sealed::Sealed {}
+
+impl SmallKey for MicroSpur {}
+impl SmallKey for MiniSpur {}
+
+/// A compact interner for small numbers of strings
+///
+/// Where a [`Rodeo`] keeps a hash map, a hasher and an arena of buckets, a `TinyRodeo` stores
+/// every string back to back in a single buffer alongside the end offset of each string and
+/// an index of keys sorted by their strings. Strings are found by binary searching the index,
+/// which for the few hundred strings a [`MicroSpur`] can address is about as fast as hashing
+/// while taking a fraction of the memory. This makes it a good fit for creating huge numbers
+/// of short lived interners, like one for every record being parsed
+///
+/// Since strings share a single growable buffer, resolved strings borrow the interner and
+/// can't be held across calls that intern new strings
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{MicroSpur, TinyRodeo};
+///
+/// let mut rodeo: TinyRodeo<MicroSpur> = TinyRodeo::new();
+/// let key = rodeo.get_or_intern("Hello, world!");
+///
+/// assert_eq!(rodeo.get_or_intern("Hello, world!"), key);
+/// assert_eq!(rodeo.resolve(&key), "Hello, world!");
+/// ```
+///
+/// [`Rodeo`]: crate::Rodeo
+#[derive(Clone)]
+pub struct TinyRodeo<K = MicroSpur> {
+    /// Every interned string, back to back in key order
+    bytes: String,
+    /// The offset in `bytes` that each string ends at, indexed by key
+    ends: Vec<u32>,
+    /// The index of every key, sorted by the key's string
+    sorted: Vec<u16>,
+    __key: PhantomData<K>,
+}
+
+impl<K> TinyRodeo<K>
+where
+    K: SmallKey,
+{
+    /// Create an empty `TinyRodeo`, which doesn't allocate until a string is interned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{MicroSpur, TinyRodeo};
+    ///
+    /// let rodeo: TinyRodeo<MicroSpur> = TinyRodeo::new();
+    /// assert!(rodeo.is_empty());
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn new() -> Self {
+        Self {
+            bytes: String::new(),
+            ends: Vec::new(),
+            sorted: Vec::new(),
+            __key: PhantomData,
+        }
+    }
+
+    /// Create an empty `TinyRodeo` with room for the given number of strings and bytes
+    ///
+    /// See [`Capacity`] for more details
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Capacity, MicroSpur, TinyRodeo};
+    /// use core::num::NonZeroUsize;
+    ///
+    /// let capacity = Capacity::new(10, NonZeroUsize::new(100).unwrap());
+    /// let rodeo: TinyRodeo<MicroSpur> = TinyRodeo::with_capacity(capacity);
+    ///
+    /// assert_eq!(rodeo.capacity(), 10);
+    /// ```
+    ///
+    /// [`Capacity`]: crate::Capacity
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacity(capacity: Capacity) -> Self {
+        Self {
+            bytes: String::with_capacity(capacity.bytes.get()),
+            ends: Vec::with_capacity(capacity.strings),
+            sorted: Vec::with_capacity(capacity.strings),
+            __key: PhantomData,
+        }
+    }
+
+    /// Get the key for a string, interning it if it does not yet exist
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key space is exhausted (for [`MicroSpur`] this happens after `u8::MAX`
+    /// unique strings) or if the interner would hold more than `u32::MAX` bytes
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{MicroSpur, TinyRodeo};
+    ///
+    /// let mut rodeo: TinyRodeo<MicroSpur> = TinyRodeo::new();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    /// [`MicroSpur`]: crate::MicroSpur
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_or_intern<T>(&mut self, val: T) -> K
+    where
+        T: AsRef<str>,
+    {
+        self.try_get_or_intern(val)
+            .expect("Failed to get or intern string")
+    }
+
+    /// Get the key for a string, interning it if it does not yet exist
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{MicroSpur, TinyRodeo};
+    ///
+    /// let mut rodeo: TinyRodeo<MicroSpur> = TinyRodeo::new();
+    /// let key = rodeo.try_get_or_intern("Strings of things with wings and dings").unwrap();
+    ///
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_get_or_intern<T>(&mut self, val: T) -> LassoResult<K>
+    where
+        T: AsRef<str>,
+    {
+        let string = val.as_ref();
+        let position = match self.search(string) {
+            Ok(key) => return Ok(key),
+            Err(position) => position,
+        };
+
+        let index = self.ends.len();
+        // Small keys never have indices that don't fit in a `u16`
+        let key = K::try_from_usize(index).ok_or_else(|| {
+            LassoError::new(LassoErrorKind::KeySpaceExhaustion).with_string_len(string.len())
+        })?;
+
+        let end = u32::try_from(self.bytes.len() + string.len()).map_err(|_| {
+            LassoError::memory_limit_reached(string.len(), self.bytes.len(), u32::MAX as usize)
+                .with_string_len(string.len())
+        })?;
+
+        self.bytes
+            .try_reserve(string.len())
+            .map_err(|_| LassoError::failed_allocation(string.len()))?;
+        self.ends
+            .try_reserve(1)
+            .map_err(|_| LassoError::failed_allocation(size_of::<u32>()))?;
+        self.sorted
+            .try_reserve(1)
+            .map_err(|_| LassoError::failed_allocation(size_of::<u16>()))?;
+
+        self.bytes.push_str(string);
+        self.ends.push(end);
+        self.sorted.insert(position, index as u16);
+
+        Ok(key)
+    }
+
+    /// Get the key for a string if it's been interned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{MicroSpur, TinyRodeo};
+    ///
+    /// let mut rodeo: TinyRodeo<MicroSpur> = TinyRodeo::new();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// assert_eq!(Some(key), rodeo.get("Strings of things with wings and dings"));
+    /// assert_eq!(None, rodeo.get("This string isn't interned"));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get<T>(&self, val: T) -> Option<K>
+    where
+        T: AsRef<str>,
+    {
+        self.search(val.as_ref()).ok()
+    }
+
+    /// Returns `true` if the given string has been interned
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains<T>(&self, val: T) -> bool
+    where
+        T: AsRef<str>,
+    {
+        self.get(val).is_some()
+    }
+
+    /// Returns `true` if the given key exists in the interner
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains_key(&self, key: &K) -> bool {
+        key.into_usize() < self.ends.len()
+    }
+
+    /// Resolves a string by its key
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{MicroSpur, TinyRodeo};
+    ///
+    /// let mut rodeo: TinyRodeo<MicroSpur> = TinyRodeo::new();
+    /// let key = rodeo.get_or_intern("Strings of things with wings and dings");
+    ///
+    /// assert_eq!("Strings of things with wings and dings", rodeo.resolve(&key));
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn resolve(&self, key: &K) -> &str {
+        self.try_resolve(key)
+            .expect("Key out of bounds, did you use a key from another interner?")
+    }
+
+    /// Resolves a string by its key, returning `None` if the key is out of bounds
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_resolve(&self, key: &K) -> Option<&str> {
+        self.string_at(key.into_usize())
+    }
+
+    /// Binary searches the sorted index for `string`, returning its key or the position in
+    /// the index that it should be inserted at
+    fn search(&self, string: &str) -> Result<K, usize> {
+        self.sorted
+            .binary_search_by(|&index| {
+                self.string_at(index as usize)
+                    .unwrap_or_else(|| unreachable!())
+                    .cmp(string)
+            })
+            .map(|position| {
+                K::try_from_usize(self.sorted[position] as usize).unwrap_or_else(|| unreachable!())
+            })
+    }
+}
+
+impl<K> TinyRodeo<K> {
+    /// Returns the number of interned strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns `true` if there are no interned strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Returns the number of strings that can be interned without a reallocation
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn capacity(&self) -> usize {
+        self.ends.capacity()
+    }
+
+    /// Clears the interner, invalidating all previously interned keys while keeping its memory
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+        self.ends.clear();
+        self.sorted.clear();
+    }
+
+    /// Returns the number of bytes the interner has allocated
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{MicroSpur, TinyRodeo};
+    ///
+    /// let mut rodeo: TinyRodeo<MicroSpur> = TinyRodeo::new();
+    /// assert_eq!(rodeo.memory_usage(), 0);
+    ///
+    /// rodeo.get_or_intern("abc");
+    /// assert!(rodeo.memory_usage() >= 3 + 4 + 2);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn memory_usage(&self) -> usize {
+        self.bytes.capacity()
+            + self.ends.capacity() * size_of::<u32>()
+            + self.sorted.capacity() * size_of::<u16>()
+    }
+
+    /// Returns an iterator over the interned strings and their keys, in key order
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter(&self) -> TinyIter<'_, K> {
+        TinyIter {
+            rodeo: self,
+            index: 0,
+        }
+    }
+
+    fn string_at(&self, index: usize) -> Option<&str> {
+        let end = *self.ends.get(index)? as usize;
+        let start = index
+            .checked_sub(1)
+            .map_or(0, |previous| self.ends[previous] as usize);
+
+        // Safety: Strings are only ever split at the boundaries of the whole strings that
+        //         were pushed into the buffer
+        Some(unsafe { self.bytes.get_unchecked(start..end) })
+    }
+}
+
+impl<K> Default for TinyRodeo<K>
+where
+    K: SmallKey,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> Debug for TinyRodeo<K>
+where
+    K: SmallKey + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<Str, K> FromIterator<Str> for TinyRodeo<K>
+where
+    Str: AsRef<str>,
+    K: SmallKey,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = Str>,
+    {
+        let mut rodeo = Self::new();
+        rodeo.extend(iter);
+
+        rodeo
+    }
+}
+
+impl<K, T> Extend<T> for TinyRodeo<K>
+where
+    K: SmallKey,
+    T: AsRef<str>,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for string in iter {
+            self.get_or_intern(string);
+        }
+    }
+}
+
+impl<'a, K: SmallKey> IntoIterator for &'a TinyRodeo<K> {
+    type Item = (K, &'a str);
+    type IntoIter = TinyIter<'a, K>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the strings and keys of a [`TinyRodeo`]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TinyIter<'a, K> {
+    rodeo: &'a TinyRodeo<K>,
+    index: usize,
+}
+
+impl<K> Debug for TinyIter<'_, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TinyIter")
+            .field("index", &self.index)
+            .field("len", &self.rodeo.len())
+            .finish()
+    }
+}
+
+impl<K> Clone for TinyIter<'_, K> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self {
+            rodeo: self.rodeo,
+            index: self.index,
+        }
+    }
+}
+
+impl<'a, K> Iterator for TinyIter<'a, K>
+where
+    K: SmallKey,
+{
+    type Item = (K, &'a str);
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let string = self.rodeo.string_at(self.index)?;
+        let key = K::try_from_usize(self.index)?;
+        self.index += 1;
+
+        Some((key, string))
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.rodeo.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, K: SmallKey> ExactSizeIterator for TinyIter<'a, K> {}
+impl<'a, K: SmallKey> FusedIterator for TinyIter<'a, K> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec};
+
+    #[test]
+    fn intern_and_resolve() {
+        let mut rodeo: TinyRodeo<MiniSpur> = TinyRodeo::new();
+        let strings = ["pear", "", "apple", "zebra", "app", "pear"];
+        let keys: Vec<MiniSpur> = strings.iter().map(|s| rodeo.get_or_intern(s)).collect();
+
+        assert_eq!(keys[0], keys[5]);
+        assert_eq!(rodeo.len(), 5);
+        for (key, string) in keys.iter().zip(strings) {
+            assert_eq!(rodeo.resolve(key), string);
+            assert_eq!(rodeo.get(string), Some(*key));
+        }
+        assert_eq!(rodeo.get("ap"), None);
+        assert!(!rodeo.contains("zebras"));
+        assert!(!rodeo.contains_key(&MiniSpur::try_from_usize(5).unwrap()));
+
+        let iterated: Vec<_> = rodeo.iter().map(|(_, string)| string).collect();
+        assert_eq!(iterated, vec!["pear", "", "apple", "zebra", "app"]);
+        assert_eq!(
+            format!("{:?}", rodeo.iter().nth(2)),
+            "Some((MiniSpur(3), \"apple\"))"
+        );
+
+        rodeo.clear();
+        assert!(rodeo.is_empty());
+        assert_eq!(rodeo.try_resolve(&keys[0]), None);
+    }
+
+    #[test]
+    fn key_space_exhaustion() {
+        let mut rodeo: TinyRodeo<MicroSpur> = (0..u8::MAX).map(|i| format!("{}", i)).collect();
+        assert_eq!(rodeo.len(), u8::MAX as usize);
+
+        let err = rodeo.try_get_or_intern("one too many").unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::KeySpaceExhaustion);
+        assert_eq!(
+            rodeo.get_or_intern("7"),
+            MicroSpur::try_from_usize(7).unwrap()
+        );
+    }
+}
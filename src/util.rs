@@ -1,13 +1,55 @@
-use crate::{keys::Key, reader::RodeoReader, resolver::RodeoResolver, rodeo::Rodeo};
-use core::{fmt, iter, marker::PhantomData, num::NonZeroUsize, slice};
+use crate::{
+    interface::Resolver, keys::Key, reader::RodeoReader, resolver::RodeoResolver, rodeo::Rodeo,
+};
+use alloc::{string::String, vec, vec::Vec};
+use core::{
+    fmt::{self, Write},
+    iter,
+    marker::PhantomData,
+    num::NonZeroUsize,
+    slice,
+};
 
 /// A continence type for an error from an interner
 pub type LassoResult<T> = core::result::Result<T, LassoError>;
 
 /// An error encountered while using an interner
+///
+/// Along with the [`LassoErrorKind`] used for matching on errors, errors carry whatever context
+/// was available where they happened, like how much memory was requested or how long the string
+/// being interned was. All of it is included in the error's [`Display`] message
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{Capacity, LassoErrorKind, MemoryLimits, Rodeo};
+///
+/// let mut rodeo: Rodeo =
+///     Rodeo::with_capacity_and_memory_limits(Capacity::minimal(), MemoryLimits::for_memory_usage(16));
+///
+/// let err = rodeo.try_get_or_intern("a".repeat(64)).unwrap_err();
+/// assert_eq!(err.kind(), LassoErrorKind::MemoryLimitReached);
+/// assert_eq!(err.requested_bytes(), Some(64));
+/// assert_eq!(err.memory_limit(), Some(16));
+/// assert_eq!(err.string_len(), Some(64));
+/// ```
+///
+/// [`Display`]: core::fmt::Display
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LassoError {
     kind: LassoErrorKind,
+    /// The number of bytes that were being allocated
+    requested_bytes: Option<usize>,
+    /// The memory in use when the error occurred
+    memory_usage: Option<usize>,
+    /// The memory limit in effect when the error occurred
+    memory_limit: Option<usize>,
+    /// The length of the string that was being interned
+    string_len: Option<usize>,
+    /// The number of leading bytes that were valid UTF-8 or held no invalid escapes
+    valid_up_to: Option<usize>,
+    /// The key that didn't line up with the interner's keys
+    key: Option<usize>,
 }
 
 impl LassoError {
@@ -16,24 +58,246 @@ impl LassoError {
     pub const fn kind(&self) -> LassoErrorKind {
         self.kind
     }
+
+    /// Gets the number of bytes that were being allocated, if the error came from an allocation
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn requested_bytes(&self) -> Option<usize> {
+        self.requested_bytes
+    }
+
+    /// Gets the interner's memory usage when a memory limit was reached
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn memory_usage(&self) -> Option<usize> {
+        self.memory_usage
+    }
+
+    /// Gets the memory limit that was reached
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn memory_limit(&self) -> Option<usize> {
+        self.memory_limit
+    }
+
+    /// Gets the length of the string that failed to be interned
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn string_len(&self) -> Option<usize> {
+        self.string_len
+    }
+
+    /// Gets the number of leading bytes that were valid UTF-8, if the error came from
+    /// interning invalid UTF-8, or the byte offset of the escape that couldn't be
+    /// unescaped, if the error came from interning an unescaped string
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn valid_up_to(&self) -> Option<usize> {
+        self.valid_up_to
+    }
+
+    /// Gets the key that was missing, given out twice or given to a string that already had
+    /// another key, if the error came from adding strings with keys already assigned to them,
+    /// or the key that wasn't in the interner, if the error came from slicing its string
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn key(&self) -> Option<usize> {
+        self.key
+    }
 }
 
 impl LassoError {
     pub(crate) const fn new(kind: LassoErrorKind) -> Self {
-        Self { kind }
+        Self {
+            kind,
+            requested_bytes: None,
+            memory_usage: None,
+            memory_limit: None,
+            string_len: None,
+            valid_up_to: None,
+            key: None,
+        }
+    }
+
+    /// Create an error for bytes that aren't valid UTF-8
+    pub(crate) const fn invalid_utf8(valid_up_to: usize, string_len: usize) -> Self {
+        let mut err = Self::new(LassoErrorKind::InvalidUtf8).with_string_len(string_len);
+        err.valid_up_to = Some(valid_up_to);
+        err
+    }
+
+    /// Create an error for an escape starting at byte `valid_up_to` that couldn't be unescaped
+    pub(crate) const fn invalid_escape(valid_up_to: usize, string_len: usize) -> Self {
+        let mut err = Self::new(LassoErrorKind::InvalidEscape).with_string_len(string_len);
+        err.valid_up_to = Some(valid_up_to);
+        err
+    }
+
+    /// Create an error for a string given a key that doesn't line up with the interner's keys
+    pub(crate) const fn invalid_key(key: usize) -> Self {
+        let mut err = Self::new(LassoErrorKind::InvalidKey);
+        err.key = Some(key);
+        err
+    }
+
+    /// Create an error for an allocation of `requested_bytes` that failed
+    pub(crate) const fn failed_allocation(requested_bytes: usize) -> Self {
+        Self::new(LassoErrorKind::FailedAllocation).with_requested_bytes(requested_bytes)
+    }
+
+    /// Create an error for allocating `requested_bytes` while `memory_usage` of `memory_limit`
+    /// bytes were already in use
+    pub(crate) const fn memory_limit_reached(
+        requested_bytes: usize,
+        memory_usage: usize,
+        memory_limit: usize,
+    ) -> Self {
+        Self::new(LassoErrorKind::MemoryLimitReached)
+            .with_requested_bytes(requested_bytes)
+            .with_memory_usage(memory_usage, memory_limit)
+    }
+
+    pub(crate) const fn with_memory_usage(
+        mut self,
+        memory_usage: usize,
+        memory_limit: usize,
+    ) -> Self {
+        self.memory_usage = Some(memory_usage);
+        self.memory_limit = Some(memory_limit);
+        self
+    }
+
+    pub(crate) const fn with_requested_bytes(mut self, requested_bytes: usize) -> Self {
+        self.requested_bytes = Some(requested_bytes);
+        self
+    }
+
+    /// Records the length of the string being interned, keeping any length that was already set
+    pub(crate) const fn with_string_len(mut self, string_len: usize) -> Self {
+        if self.string_len.is_none() {
+            self.string_len = Some(string_len);
+        }
+
+        self
     }
 }
 
 impl fmt::Display for LassoError {
     #[cfg_attr(feature = "inline-more", inline)]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Lasso encountered an error: {}", self.kind)
+        write!(f, "Lasso encountered an error: {}", self.kind)?;
+
+        match (self.requested_bytes, self.memory_usage, self.memory_limit) {
+            (Some(requested), Some(usage), Some(limit)) => write!(
+                f,
+                " (requested {} bytes with {} of {} bytes in use)",
+                requested, usage, limit,
+            )?,
+            (Some(requested), _, _) => write!(f, " (requested {} bytes)", requested)?,
+            (None, Some(usage), Some(limit)) => {
+                write!(f, " ({} of {} bytes in use)", usage, limit)?
+            }
+            _ => {}
+        }
+
+        if let Some(valid_up_to) = self.valid_up_to {
+            write!(f, " (valid up to byte {})", valid_up_to)?;
+        }
+
+        if let Some(key) = self.key {
+            write!(f, " (key {})", key)?;
+        }
+
+        if let Some(len) = self.string_len {
+            write!(f, " while interning a string of {} bytes", len)?;
+        }
+
+        Ok(())
     }
 }
 
 #[cfg(not(feature = "no-std"))]
 impl std::error::Error for LassoError {}
 
+/// The error returned by [`Resolver::resolve_into`] when a string doesn't fit in the buffer
+///
+/// [`Resolver::resolve_into`]: crate::Resolver::resolve_into
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BufferTooSmall {
+    /// The length of the string that was being copied
+    required: usize,
+}
+
+impl BufferTooSmall {
+    pub(crate) const fn new(required: usize) -> Self {
+        Self { required }
+    }
+
+    /// Gets the number of bytes the buffer needed to hold the string
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn required(&self) -> usize {
+        self.required
+    }
+}
+
+impl fmt::Display for BufferTooSmall {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The buffer was too small to hold a string of {} bytes",
+            self.required,
+        )
+    }
+}
+
+#[cfg(not(feature = "no-std"))]
+impl std::error::Error for BufferTooSmall {}
+
+/// Lazily formats the string of a key, created by [`Resolver::display`] and
+/// [`Resolver::try_display`]
+///
+/// The string is only looked up when the adaptor is formatted, and keys that aren't in the
+/// resolver are written as `<invalid key #N>` instead of panicking
+///
+/// [`Resolver::display`]: crate::Resolver::display
+/// [`Resolver::try_display`]: crate::Resolver::try_display
+#[derive(Debug)]
+pub struct DisplayKey<'a, K, R: ?Sized> {
+    resolver: &'a R,
+    key: K,
+}
+
+impl<'a, K, R: ?Sized> DisplayKey<'a, K, R> {
+    pub(crate) const fn new(resolver: &'a R, key: K) -> Self {
+        Self { resolver, key }
+    }
+
+    /// Gets the key being displayed
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<K: Copy, R: ?Sized> Clone for DisplayKey<'_, K, R> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: Copy, R: ?Sized> Copy for DisplayKey<'_, K, R> {}
+
+impl<K, R> fmt::Display for DisplayKey<'_, K, R>
+where
+    K: Key,
+    R: Resolver<K> + ?Sized,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.resolver.try_resolve(&self.key) {
+            // Forwarded so that width, fill and precision apply to the string
+            Some(string) => fmt::Display::fmt(string, f),
+            None => write!(f, "<invalid key #{}>", self.key.into_usize()),
+        }
+    }
+}
+
 /// The kind of error that occurred
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum LassoErrorKind {
@@ -47,6 +311,17 @@ pub enum LassoErrorKind {
     KeySpaceExhaustion,
     /// A memory allocation failed
     FailedAllocation,
+    /// The bytes being interned weren't valid UTF-8
+    InvalidUtf8,
+    /// A string was given a key that doesn't line up with the keys of the interner, either
+    /// because the key was skipped, given out twice or the string already has another key
+    InvalidKey,
+    /// The string being unescaped held an escape that isn't valid in its [`EscapeStyle`]
+    ///
+    /// [`EscapeStyle`]: crate::EscapeStyle
+    InvalidEscape,
+    /// A range was out of bounds of the string it slices or didn't lie on char boundaries
+    InvalidRange,
 }
 
 impl LassoErrorKind {
@@ -71,6 +346,30 @@ impl LassoErrorKind {
     pub fn is_failed_alloc(self) -> bool {
         self == Self::FailedAllocation
     }
+
+    /// The bytes being interned weren't valid UTF-8
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_invalid_utf8(self) -> bool {
+        self == Self::InvalidUtf8
+    }
+
+    /// A string was given a key that doesn't line up with the keys of the interner
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_invalid_key(self) -> bool {
+        self == Self::InvalidKey
+    }
+
+    /// The string being unescaped held an invalid escape
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_invalid_escape(self) -> bool {
+        self == Self::InvalidEscape
+    }
+
+    /// A range was out of bounds of its string or didn't lie on char boundaries
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_invalid_range(self) -> bool {
+        self == Self::InvalidRange
+    }
 }
 
 impl fmt::Display for LassoErrorKind {
@@ -80,6 +379,12 @@ impl fmt::Display for LassoErrorKind {
             Self::MemoryLimitReached => f.write_str("The configured memory limit was reached"),
             Self::KeySpaceExhaustion => f.write_str("The key space was exhausted"),
             Self::FailedAllocation => f.write_str("Failed to allocate memory"),
+            Self::InvalidUtf8 => f.write_str("The string was not valid UTF-8"),
+            Self::InvalidKey => {
+                f.write_str("The string's key didn't line up with the interner's keys")
+            }
+            Self::InvalidEscape => f.write_str("The string held an invalid escape"),
+            Self::InvalidRange => f.write_str("The range didn't fit within the string"),
         }
     }
 }
@@ -94,6 +399,10 @@ pub struct Capacity {
 }
 
 impl Capacity {
+    /// The largest number of bytes that a single arena bucket can hold, capacities with more
+    /// bytes than this can never be allocated
+    pub const MAX_BYTES: usize = isize::MAX as usize;
+
     /// Create a new `Capacity` with the number of strings that the interner will hold
     /// and the number of bytes that the interner will hold
     #[cfg_attr(feature = "inline-more", inline)]
@@ -101,6 +410,43 @@ impl Capacity {
         Self { strings, bytes }
     }
 
+    /// Create a new `Capacity` like [`Capacity::new`], returning an error if it could
+    /// never be allocated
+    ///
+    /// Capacities fail with [`LassoErrorKind::FailedAllocation`] if `bytes` is larger than
+    /// [`Capacity::MAX_BYTES`] or if room for `strings` strings can't fit in the address space
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{Capacity, LassoErrorKind};
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let bytes = NonZeroUsize::new(4096).unwrap();
+    /// assert_eq!(Capacity::try_new(10, bytes), Ok(Capacity::new(10, bytes)));
+    ///
+    /// let err = Capacity::try_new(usize::MAX, bytes).unwrap_err();
+    /// assert_eq!(err.kind(), LassoErrorKind::FailedAllocation);
+    /// assert!(Capacity::try_new(10, NonZeroUsize::new(usize::MAX).unwrap()).is_err());
+    /// ```
+    ///
+    /// [`LassoErrorKind::FailedAllocation`]: crate::LassoErrorKind::FailedAllocation
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_new(strings: usize, bytes: NonZeroUsize) -> LassoResult<Self> {
+        if bytes.get() > Self::MAX_BYTES {
+            return Err(LassoError::failed_allocation(bytes.get()));
+        }
+
+        // Every string takes up at least a key and a string pointer within the interner
+        let per_string = core::mem::size_of::<&str>() + core::mem::size_of::<u32>();
+        match strings.checked_mul(per_string) {
+            Some(string_bytes) if string_bytes <= Self::MAX_BYTES => Ok(Self { strings, bytes }),
+            _ => Err(LassoError::failed_allocation(
+                strings.saturating_mul(per_string),
+            )),
+        }
+    }
+
     /// Create a new `Capacity` with the number of strings that the interner will hold
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn for_strings(strings: usize) -> Self {
@@ -156,24 +502,57 @@ impl Default for Capacity {
 
 /// Settings for the memory consumption of an interner
 ///
-/// By default `max_memory_usage` is set to `usize::MAX`
+/// By default `max_memory_usage` is set to `usize::MAX` and only limits the memory
+/// used to store interned strings, see [`MemoryLimits::total_budget`] to also
+/// limit the memory used by the interner's maps
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct MemoryLimits {
     /// The maximum memory an interner will allocate
     pub(crate) max_memory_usage: usize,
+    /// Whether `max_memory_usage` also covers the interner's maps and indices
+    pub(crate) total_budget: bool,
 }
 
 impl MemoryLimits {
     /// Create a new `MemoryLimits` with the number of bytes that the interner can allocate
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn new(max_memory_usage: usize) -> Self {
-        Self { max_memory_usage }
+        Self {
+            max_memory_usage,
+            total_budget: false,
+        }
     }
 
     /// Create a new `MemoryLimits` with the number of bytes that the interner can allocate
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn for_memory_usage(max_memory_usage: usize) -> Self {
-        Self { max_memory_usage }
+        Self::new(max_memory_usage)
+    }
+
+    /// Create a new `MemoryLimits` with the total number of bytes that the interner can use,
+    /// including the estimated size of its maps and indices along with its interned strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lasso::{MemoryLimits, Rodeo};
+    ///
+    /// let mut rodeo: Rodeo = Rodeo::with_memory_limits(MemoryLimits::total_budget(8192));
+    /// for i in 0.. {
+    ///     if rodeo.try_get_or_intern(i.to_string()).is_err() {
+    ///         break;
+    ///     }
+    /// }
+    ///
+    /// assert!(rodeo.current_memory_usage() <= 8192);
+    /// ```
+    ///
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn total_budget(max_memory_usage: usize) -> Self {
+        Self {
+            max_memory_usage,
+            total_budget: true,
+        }
     }
 
     /// Returns the maximum memory usage this `MemoryLimits` can allocate
@@ -181,16 +560,388 @@ impl MemoryLimits {
     pub fn max_memory_usage(&self) -> usize {
         self.max_memory_usage
     }
+
+    /// Returns `true` if the memory limit covers the interner's maps and indices
+    /// as well as its interned strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_total_budget(&self) -> bool {
+        self.total_budget
+    }
 }
 
 /// Creates a `MemoryLimits` with `max_memory_usage` set to `usize::max_value()`
 impl Default for MemoryLimits {
     #[cfg_attr(feature = "inline-more", inline)]
     fn default() -> Self {
+        Self::new(usize::MAX)
+    }
+}
+
+/// The preallocated storage of an interner that never allocates after it's created, holding
+/// up to `BYTES` bytes of strings and a fixed number of strings
+///
+/// Every bit of memory the interner uses is allocated up front by [`Rodeo::with_fixed_arena`],
+/// interning a string that doesn't fit fails with [`LassoErrorKind::MemoryLimitReached`]
+/// instead of allocating more
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{FixedArena, Rodeo};
+///
+/// let mut rodeo: Rodeo = Rodeo::with_fixed_arena(FixedArena::<8>::new(2));
+/// rodeo.get_or_intern("abcd");
+/// rodeo.get_or_intern("efgh");
+///
+/// // Both the arena and the strings are full
+/// assert!(rodeo.try_get_or_intern("i").unwrap_err().kind().is_memory_limit());
+///
+/// // Already interned strings can still be looked up
+/// assert!(rodeo.try_get_or_intern("abcd").is_ok());
+/// ```
+///
+/// [`Rodeo::with_fixed_arena`]: crate::Rodeo::with_fixed_arena
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FixedArena<const BYTES: usize> {
+    /// The number of strings the interner has room for
+    pub(crate) strings: usize,
+}
+
+impl<const BYTES: usize> FixedArena<BYTES> {
+    /// The size of the arena, which can't be zero
+    pub(crate) const BYTES: NonZeroUsize = match NonZeroUsize::new(BYTES) {
+        Some(bytes) => bytes,
+        None => panic!("a `FixedArena` must have room for at least one byte"),
+    };
+
+    /// Create a new `FixedArena` with room for `strings` strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn new(strings: usize) -> Self {
+        Self { strings }
+    }
+
+    /// Returns the number of strings the arena has room for
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn strings(&self) -> usize {
+        self.strings
+    }
+
+    /// Returns the number of bytes the arena has room for
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub const fn bytes(&self) -> usize {
+        BYTES
+    }
+}
+
+/// Statistics on how much deduplication an interner has achieved
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::Rodeo;
+///
+/// let mut rodeo = Rodeo::default();
+/// rodeo.get_or_intern("hello");
+/// rodeo.get_or_intern("hello");
+/// rodeo.get_or_intern("world");
+///
+/// let stats = rodeo.dedup_stats();
+/// assert_eq!(stats.hits(), 1);
+/// assert_eq!(stats.misses(), 2);
+/// assert_eq!(stats.bytes_submitted(), 15);
+/// assert_eq!(stats.bytes_stored(), 10);
+/// assert_eq!(stats.bytes_saved(), 5);
+/// ```
+///
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DedupStats {
+    /// The number of strings that were already interned
+    pub(crate) hits: usize,
+    /// The number of strings that were newly interned
+    pub(crate) misses: usize,
+    /// The total length of every string given to the interner
+    pub(crate) bytes_submitted: usize,
+    /// The total length of every unique string
+    pub(crate) bytes_stored: usize,
+}
+
+impl DedupStats {
+    /// Records a string that was already interned
+    #[inline]
+    pub(crate) fn record_hit(&mut self, len: usize) {
+        self.hits = self.hits.saturating_add(1);
+        self.bytes_submitted = self.bytes_submitted.saturating_add(len);
+    }
+
+    /// Records a newly interned string
+    #[inline]
+    pub(crate) fn record_miss(&mut self, len: usize) {
+        self.misses = self.misses.saturating_add(1);
+        self.bytes_submitted = self.bytes_submitted.saturating_add(len);
+        self.bytes_stored = self.bytes_stored.saturating_add(len);
+    }
+
+    /// Returns the number of interned strings that were already present
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Returns the number of interned strings that weren't already present
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Returns the total length of every string given to the interner
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn bytes_submitted(&self) -> usize {
+        self.bytes_submitted
+    }
+
+    /// Returns the total length of every unique string given to the interner
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn bytes_stored(&self) -> usize {
+        self.bytes_stored
+    }
+
+    /// Returns the number of bytes that didn't need to be stored thanks to deduplication
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn bytes_saved(&self) -> usize {
+        self.bytes_submitted.saturating_sub(self.bytes_stored)
+    }
+}
+
+/// A breakdown of the memory used by an interner
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::Rodeo;
+///
+/// let mut rodeo = Rodeo::default();
+/// rodeo.get_or_intern("hello");
+/// rodeo.get_or_intern("world");
+///
+/// let stats = rodeo.memory_stats();
+/// assert_eq!(stats.string_bytes(), 10);
+/// assert_eq!(stats.arena_bytes(), rodeo.arena_memory_usage());
+/// assert_eq!(stats.total_bytes(), rodeo.current_memory_usage());
+///
+/// // The reader and resolver still own the interner's strings
+/// let resolver = rodeo.into_resolver();
+/// assert_eq!(resolver.memory_stats().arena_bytes(), stats.arena_bytes());
+/// ```
+///
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct MemoryStats {
+    /// The memory allocated to hold interned strings
+    pub(crate) arena_bytes: usize,
+    /// The estimated memory used by the interner's maps and vecs
+    pub(crate) overhead_bytes: usize,
+    /// The total length of every interned string
+    pub(crate) string_bytes: usize,
+}
+
+impl MemoryStats {
+    pub(crate) fn new<'a, I>(arena_bytes: usize, overhead_bytes: usize, strings: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
         Self {
-            max_memory_usage: usize::MAX,
+            arena_bytes,
+            overhead_bytes,
+            string_bytes: strings.into_iter().map(str::len).sum(),
         }
     }
+
+    /// Returns the memory allocated to hold interned strings
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn arena_bytes(&self) -> usize {
+        self.arena_bytes
+    }
+
+    /// Returns the estimated memory used by the interner's internal maps and vecs
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn overhead_bytes(&self) -> usize {
+        self.overhead_bytes
+    }
+
+    /// Returns the total length of every interned string
+    ///
+    /// Strings that share their bytes with another string, like static strings or
+    /// substrings of other interned strings, are counted in full
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn string_bytes(&self) -> usize {
+        self.string_bytes
+    }
+
+    /// Returns all of the memory used by the interner
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn total_bytes(&self) -> usize {
+        self.arena_bytes.saturating_add(self.overhead_bytes)
+    }
+}
+
+/// A point in time view of an interner's health, for exporting to monitoring systems
+///
+/// # Example
+///
+/// ```rust
+/// use lasso::{MemoryLimits, Rodeo};
+///
+/// let mut rodeo: Rodeo = Rodeo::with_memory_limits(MemoryLimits::for_memory_usage(4096));
+/// rodeo.get_or_intern("hello");
+///
+/// let metrics = rodeo.metrics();
+/// assert_eq!(metrics.interned_total(), 1);
+/// assert_eq!(metrics.map_entries(), 1);
+/// assert_eq!(metrics.bytes_allocated(), rodeo.current_memory_usage());
+/// assert_eq!(metrics.memory_limit(), 4096);
+///
+/// let text = metrics.to_prometheus("lasso");
+/// assert!(text.contains("# TYPE lasso_interned_total counter\nlasso_interned_total 1\n"));
+/// ```
+///
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct MetricsSnapshot {
+    /// The number of strings that have been interned
+    pub(crate) interned_total: usize,
+    /// All of the memory used by the interner
+    pub(crate) bytes_allocated: usize,
+    /// The number of entries in the interner's `str` -> key map
+    pub(crate) map_entries: usize,
+    /// The interner's maximum memory usage
+    pub(crate) memory_limit: usize,
+}
+
+impl MetricsSnapshot {
+    /// Returns the number of strings that have been interned
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn interned_total(&self) -> usize {
+        self.interned_total
+    }
+
+    /// Returns all of the memory used by the interner, the same as its `current_memory_usage()`
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn bytes_allocated(&self) -> usize {
+        self.bytes_allocated
+    }
+
+    /// Returns the number of entries in the interner's `str` -> key map
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn map_entries(&self) -> usize {
+        self.map_entries
+    }
+
+    /// Returns the interner's maximum memory usage
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn memory_limit(&self) -> usize {
+        self.memory_limit
+    }
+
+    /// Formats the metrics in the Prometheus text exposition format, with every metric's
+    /// name starting with `prefix` and an underscore
+    ///
+    /// `interned_total` is written as a counter and the rest as gauges. `prefix` isn't
+    /// escaped, so it should be a valid metric name on its own, like `"lasso"` or
+    /// `"my_service_symbols"`. An empty prefix leaves metric names unprefixed
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn to_prometheus(&self, prefix: &str) -> String {
+        let metrics = [
+            (
+                "interned_total",
+                "counter",
+                "The number of strings that have been interned",
+                self.interned_total,
+            ),
+            (
+                "bytes_allocated",
+                "gauge",
+                "The memory used by the interner in bytes",
+                self.bytes_allocated,
+            ),
+            (
+                "map_entries",
+                "gauge",
+                "The number of entries in the interner's string map",
+                self.map_entries,
+            ),
+            (
+                "memory_limit",
+                "gauge",
+                "The maximum memory the interner may use in bytes",
+                self.memory_limit,
+            ),
+        ];
+        let separator = if prefix.is_empty() { "" } else { "_" };
+
+        let mut text = String::new();
+        for (name, kind, help, value) in metrics {
+            // Writing into a `String` can't fail
+            let _ = write!(
+                text,
+                "# HELP {prefix}{separator}{name} {help}\n\
+                 # TYPE {prefix}{separator}{name} {kind}\n\
+                 {prefix}{separator}{name} {value}\n",
+            );
+        }
+
+        text
+    }
+}
+
+/// The number of strings an interner's `Debug` output shows before eliding the rest
+pub(crate) const DEBUG_STRINGS: usize = 16;
+
+/// Formats the `Debug` output shared by all interners, which shows their size and their
+/// first few strings instead of their internals
+///
+/// `get` gives the key and string at an index, for every index below `len`
+pub(crate) fn debug_interner<'a, K, F>(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    len: usize,
+    memory_usage: usize,
+    get: F,
+) -> fmt::Result
+where
+    K: fmt::Debug,
+    F: Fn(usize) -> Option<(K, &'a str)>,
+{
+    struct DebugStrings<F> {
+        len: usize,
+        get: F,
+    }
+
+    impl<'a, K, F> fmt::Debug for DebugStrings<F>
+    where
+        K: fmt::Debug,
+        F: Fn(usize) -> Option<(K, &'a str)>,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let shown = self.len.min(DEBUG_STRINGS);
+
+            let mut map = f.debug_map();
+            for (key, string) in (0..shown).filter_map(&self.get) {
+                map.entry(&key, &string);
+            }
+            if self.len > shown {
+                map.entry(
+                    &format_args!("..."),
+                    &format_args!("{} more", self.len - shown),
+                );
+            }
+
+            map.finish()
+        }
+    }
+
+    f.debug_struct(name)
+        .field("len", &len)
+        .field("memory_usage", &memory_usage)
+        .field("strings", &DebugStrings { len, get })
+        .finish()
 }
 
 /// An iterator over an interner's strings and keys
@@ -227,6 +978,66 @@ impl<'a, K> Iter<'a, K> {
     }
 }
 
+/// Estimates the capacity a hash table grows to once it runs out of room for more than
+/// `capacity` items, following `hashbrown`'s growth policy
+pub(crate) fn grown_table_capacity(capacity: usize) -> usize {
+    let items = capacity.saturating_add(1);
+    let buckets = if items < 4 {
+        4
+    } else if items < 8 {
+        8
+    } else {
+        (items.saturating_mul(8) / 7).next_power_of_two()
+    };
+
+    // Small tables can use all but one bucket, larger ones only fill 7/8ths of theirs
+    if buckets < 8 {
+        buckets - 1
+    } else {
+        buckets / 8 * 7
+    }
+}
+
+/// Counts the strings whose lengths fall between each of the ascending `bucket_bounds`,
+/// the last bucket holds every string longer than the final bound
+pub(crate) fn length_histogram(strings: &[&str], bucket_bounds: &[usize]) -> Vec<usize> {
+    debug_assert!(
+        bucket_bounds
+            .windows(2)
+            .all(|bounds| bounds[0] <= bounds[1]),
+        "bucket bounds must be sorted",
+    );
+
+    let mut histogram = vec![0; bucket_bounds.len() + 1];
+    for string in strings {
+        histogram[bucket_bounds.partition_point(|&bound| bound < string.len())] += 1;
+    }
+
+    histogram
+}
+
+/// Finds the `n` longest strings, longest first with ties going to the lowest key
+pub(crate) fn top_longest<'a, K>(strings: &[&'a str], n: usize) -> Vec<(K, &'a str)>
+where
+    K: Key,
+{
+    let longest_first =
+        |&a: &usize, &b: &usize| strings[b].len().cmp(&strings[a].len()).then(a.cmp(&b));
+
+    let mut indices: Vec<usize> = (0..strings.len()).collect();
+    if n < indices.len() {
+        // Only the first `n` need to be sorted
+        indices.select_nth_unstable_by(n, longest_first);
+        indices.truncate(n);
+    }
+    indices.sort_unstable_by(longest_first);
+
+    indices
+        .into_iter()
+        .map(|idx| iter_element((idx, &strings[idx])))
+        .collect()
+}
+
 fn iter_element<'a, K>((key, string): (usize, &&'a str)) -> (K, &'a str)
 where
     K: Key,
@@ -455,6 +1266,23 @@ macro_rules! index_unchecked {
 mod tests {
     use super::*;
 
+    #[test]
+    fn try_new_capacity() {
+        let bytes = NonZeroUsize::new(100).unwrap();
+        assert_eq!(Capacity::try_new(100, bytes), Ok(Capacity::new(100, bytes)));
+
+        let max = NonZeroUsize::new(Capacity::MAX_BYTES).unwrap();
+        assert!(Capacity::try_new(0, max).is_ok());
+
+        let err =
+            Capacity::try_new(0, NonZeroUsize::new(Capacity::MAX_BYTES + 1).unwrap()).unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::FailedAllocation);
+        assert_eq!(err.requested_bytes(), Some(Capacity::MAX_BYTES + 1));
+
+        let err = Capacity::try_new(usize::MAX, bytes).unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::FailedAllocation);
+    }
+
     #[test]
     fn new_capacity() {
         let capacity = Capacity::new(100, NonZeroUsize::new(100).unwrap());
@@ -476,6 +1304,64 @@ mod tests {
         assert_eq!(capacity.bytes().get(), 1);
     }
 
+    #[test]
+    fn error_context() {
+        use alloc::string::ToString;
+
+        let err = LassoError::memory_limit_reached(64, 100, 128).with_string_len(10);
+        assert_eq!(err.kind(), LassoErrorKind::MemoryLimitReached);
+        assert_eq!(
+            (
+                err.requested_bytes(),
+                err.memory_usage(),
+                err.memory_limit()
+            ),
+            (Some(64), Some(100), Some(128)),
+        );
+        assert_eq!(
+            err.to_string(),
+            "Lasso encountered an error: The configured memory limit was reached (requested 64 \
+             bytes with 100 of 128 bytes in use) while interning a string of 10 bytes",
+        );
+
+        // The innermost string length is kept
+        assert_eq!(err.with_string_len(20).string_len(), Some(10));
+
+        let err = LassoError::new(LassoErrorKind::KeySpaceExhaustion);
+        assert_eq!(err.requested_bytes(), None);
+        assert_eq!(
+            err.to_string(),
+            "Lasso encountered an error: The key space was exhausted",
+        );
+        assert_eq!(
+            LassoError::failed_allocation(10).to_string(),
+            "Lasso encountered an error: Failed to allocate memory (requested 10 bytes)",
+        );
+    }
+
+    #[test]
+    fn interning_errors_have_context() {
+        use alloc::string::ToString;
+
+        let mut rodeo: Rodeo<crate::MicroSpur> = Rodeo::with_capacity_and_memory_limits(
+            Capacity::minimal(),
+            MemoryLimits::for_memory_usage(8),
+        );
+
+        let err = rodeo.try_get_or_intern("a".repeat(16)).unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::MemoryLimitReached);
+        assert_eq!(err.string_len(), Some(16));
+        assert_eq!(err.memory_limit(), Some(8));
+
+        let mut rodeo: Rodeo<crate::MicroSpur> = Rodeo::new();
+        for i in 0..u8::MAX as usize {
+            rodeo.get_or_intern(i.to_string());
+        }
+        let err = rodeo.try_get_or_intern("abc").unwrap_err();
+        assert_eq!(err.kind(), LassoErrorKind::KeySpaceExhaustion);
+        assert_eq!(err.string_len(), Some(3));
+    }
+
     #[test]
     fn iter_rodeo() {
         let mut rodeo = Rodeo::default();
@@ -593,4 +1479,34 @@ mod tests {
         assert_eq!(None, iter.next());
         assert_eq!((0, Some(0)), iter.size_hint());
     }
+
+    #[test]
+    fn prometheus() {
+        let metrics = MetricsSnapshot {
+            interned_total: 3,
+            bytes_allocated: 1024,
+            map_entries: 3,
+            memory_limit: 4096,
+        };
+
+        assert_eq!(
+            metrics.to_prometheus("symbols"),
+            "# HELP symbols_interned_total The number of strings that have been interned\n\
+             # TYPE symbols_interned_total counter\n\
+             symbols_interned_total 3\n\
+             # HELP symbols_bytes_allocated The memory used by the interner in bytes\n\
+             # TYPE symbols_bytes_allocated gauge\n\
+             symbols_bytes_allocated 1024\n\
+             # HELP symbols_map_entries The number of entries in the interner's string map\n\
+             # TYPE symbols_map_entries gauge\n\
+             symbols_map_entries 3\n\
+             # HELP symbols_memory_limit The maximum memory the interner may use in bytes\n\
+             # TYPE symbols_memory_limit gauge\n\
+             symbols_memory_limit 4096\n",
+        );
+
+        let unprefixed = metrics.to_prometheus("");
+        assert!(unprefixed.starts_with("# HELP interned_total "));
+        assert!(unprefixed.ends_with("\nmemory_limit 4096\n"));
+    }
 }
@@ -0,0 +1,183 @@
+//! Serializes a [`Rodeo`] as explicit `(key, string)` pairs
+//!
+//! `Rodeo`'s own [`Serialize`] implementation writes a plain list of strings and relies on
+//! their order to rebuild the keys. The functions in this module instead write every key
+//! alongside its string and check on load that the keys are exactly the ones the strings will
+//! be given back, so a snapshot that was edited, merged or written with missing keys is
+//! rejected instead of silently handing out different keys.
+//!
+//! They're meant to be used through `#[serde(with = "lasso::with_keys")]` on a field, but can
+//! also be called directly.
+//!
+//! This module is only available with the `serialize` feature!
+//!
+//! # Example
+//!
+//! ```rust
+//! use lasso::{with_keys, Rodeo};
+//!
+//! let mut rodeo = Rodeo::default();
+//! let key = rodeo.get_or_intern("Hello, world!");
+//!
+//! let mut json = Vec::new();
+//! with_keys::serialize(&rodeo, &mut serde_json::Serializer::new(&mut json)).unwrap();
+//! assert_eq!(json, br#"[[0,"Hello, world!"]]"#);
+//!
+//! let rodeo: Rodeo = with_keys::deserialize(&mut serde_json::Deserializer::from_slice(&json)).unwrap();
+//! assert_eq!(rodeo.resolve(&key), "Hello, world!");
+//!
+//! // Keys that don't line up with their strings are rejected
+//! let gap = br#"[[0,"Hello, world!"],[2,"Goodbye"]]"#;
+//! let rodeo: Result<Rodeo, _> = with_keys::deserialize(&mut serde_json::Deserializer::from_slice(gap));
+//! assert!(rodeo.is_err());
+//! ```
+//!
+//! [`Rodeo`]: crate::Rodeo
+//! [`Serialize`]: serde::Serialize
+
+use crate::{Capacity, Key, Rodeo};
+use core::{hash::BuildHasher, num::NonZeroUsize};
+use serde::{
+    de::{Deserialize, Deserializer, Error},
+    ser::{Serialize, Serializer},
+};
+
+#[cfg(feature = "no-std")]
+use alloc::{string::String, vec::Vec};
+
+/// Serializes the strings of a [`Rodeo`] as a sequence of `(key, string)` pairs, ordered by key
+///
+/// [`Rodeo`]: crate::Rodeo
+#[cfg_attr(feature = "inline-more", inline)]
+pub fn serialize<K, H, S>(rodeo: &Rodeo<K, H>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    K: Key + Serialize,
+    S: Serializer,
+{
+    serializer.collect_seq(rodeo.iter())
+}
+
+/// Deserializes a [`Rodeo`] from a sequence of `(key, string)` pairs
+///
+/// The pairs may come in any order, but their keys must cover every index from zero up to the
+/// number of pairs exactly once and no string may appear twice, otherwise an error is returned
+///
+/// [`Rodeo`]: crate::Rodeo
+#[cfg_attr(feature = "inline-more", inline)]
+pub fn deserialize<'de, K, S, D>(deserializer: D) -> Result<Rodeo<K, S>, D::Error>
+where
+    K: Key + Deserialize<'de>,
+    S: BuildHasher + Default,
+    D: Deserializer<'de>,
+{
+    let mut pairs: Vec<(K, String)> = Vec::deserialize(deserializer)?;
+    pairs.sort_unstable_by_key(|&(key, _)| key.into_usize());
+
+    let capacity = {
+        let total_bytes = pairs.iter().map(|(_, s)| s.len()).sum::<usize>();
+        let total_bytes =
+            NonZeroUsize::new(total_bytes).unwrap_or_else(|| Capacity::default().bytes());
+
+        Capacity::new(pairs.len(), total_bytes)
+    };
+    let mut rodeo: Rodeo<K, S> =
+        Rodeo::try_with_capacity_and_hasher(capacity, S::default()).map_err(D::Error::custom)?;
+
+    for (expected, (key, string)) in pairs.into_iter().enumerate() {
+        // The pairs are sorted, so a key that's lower than its position was repeated and one
+        // that's higher means a key before it was skipped
+        let index = key.into_usize();
+        if index < expected {
+            return Err(D::Error::custom(format_args!(
+                "the key {} was given more than once",
+                index,
+            )));
+        } else if index > expected {
+            return Err(D::Error::custom(format_args!(
+                "the key {} is missing",
+                expected,
+            )));
+        }
+
+        let interned = rodeo
+            .try_get_or_intern(&*string)
+            .map_err(D::Error::custom)?;
+        if interned != key {
+            return Err(D::Error::custom(format_args!(
+                "the string {:?} was given to both key {} and key {}",
+                string,
+                interned.into_usize(),
+                index,
+            )));
+        }
+    }
+
+    Ok(rodeo)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{with_keys, Key, Rodeo, Spur};
+    #[cfg(feature = "no-std")]
+    use alloc::{
+        string::{String, ToString},
+        vec::Vec,
+    };
+
+    fn to_json(rodeo: &Rodeo) -> String {
+        let mut json = Vec::new();
+        with_keys::serialize(rodeo, &mut serde_json::Serializer::new(&mut json)).unwrap();
+
+        String::from_utf8(json).unwrap()
+    }
+
+    fn from_json(json: &str) -> Result<Rodeo, serde_json::Error> {
+        with_keys::deserialize(&mut serde_json::Deserializer::from_str(json))
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rodeo = Rodeo::default();
+        let keys: Vec<Spur> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|s| rodeo.get_or_intern(s))
+            .collect();
+
+        let json = to_json(&rodeo);
+        assert_eq!(json, r#"[[0,"a"],[1,"b"],[2,"c"],[3,"d"]]"#);
+
+        let deser = from_json(&json).unwrap();
+        assert_eq!(deser.len(), 4);
+        for (key, string) in keys.iter().zip(["a", "b", "c", "d"].iter()) {
+            assert_eq!(deser.resolve(key), *string);
+            assert_eq!(deser.get(string), Some(*key));
+        }
+
+        assert!(from_json(&to_json(&Rodeo::default())).unwrap().is_empty());
+    }
+
+    #[test]
+    fn out_of_order() {
+        let rodeo = from_json(r#"[[2,"c"],[0,"a"],[1,"b"]]"#).unwrap();
+
+        assert_eq!(rodeo.resolve(&Spur::try_from_usize(0).unwrap()), "a");
+        assert_eq!(rodeo.resolve(&Spur::try_from_usize(1).unwrap()), "b");
+        assert_eq!(rodeo.resolve(&Spur::try_from_usize(2).unwrap()), "c");
+    }
+
+    #[test]
+    fn invalid_keys() {
+        let err = from_json(r#"[[0,"a"],[2,"c"]]"#).unwrap_err();
+        assert!(err.to_string().contains("the key 1 is missing"));
+
+        let err = from_json(r#"[[0,"a"],[0,"b"]]"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("the key 0 was given more than once"));
+
+        let err = from_json(r#"[[0,"a"],[1,"a"]]"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(r#"the string "a" was given to both key 0 and key 1"#));
+    }
+}